@@ -12,6 +12,8 @@ use globa_flux_rust::db::{
     fetch_tenant_ai_routing_policy, fetch_usage_event, get_pool, insert_usage_event,
     sum_spent_usd_today,
 };
+use globa_flux_rust::error_codes::annotate_error_body;
+use globa_flux_rust::http_client::http_client_for_url;
 use globa_flux_rust::providers::gemini::{
     generate_text as gemini_generate_text, pricing_for_model as gemini_pricing_for_model,
     stream_generate as gemini_stream_generate, GeminiConfig, GeminiStreamEvent,
@@ -292,9 +294,10 @@ async fn openai_generate_text(
       ]
     });
 
-    let client = reqwest::Client::new();
+    let client = http_client_for_url(&url)
+        .map_err(|e| -> Error { Box::new(std::io::Error::other(e.to_string())) })?;
     let resp = client
-        .post(url)
+        .post(&url)
         .headers(headers)
         .json(&payload)
         .send()
@@ -341,9 +344,10 @@ async fn anthropic_generate_text(
       "messages": [{"role":"user","content": user}]
     });
 
-    let client = reqwest::Client::new();
+    let client = http_client_for_url(&url)
+        .map_err(|e| -> Error { Box::new(std::io::Error::other(e.to_string())) })?;
     let resp = client
-        .post(url)
+        .post(&url)
         .header("x-api-key", api_key)
         .header("anthropic-version", "2023-06-01")
         .header(reqwest::header::CONTENT_TYPE, "application/json")
@@ -709,8 +713,9 @@ fn sse_response(status: StatusCode, body: String) -> Result<Response<ResponseBod
 
 fn json_response(
     status: StatusCode,
-    value: serde_json::Value,
+    mut value: serde_json::Value,
 ) -> Result<Response<ResponseBody>, Error> {
+    annotate_error_body(&mut value);
     Ok(Response::builder()
         .status(status)
         .header("content-type", "application/json; charset=utf-8")
@@ -3,7 +3,12 @@ use http_body_util::BodyExt;
 use hyper::{HeaderMap, Method, StatusCode};
 use vercel_runtime::{run, service_fn, Error, Request, Response, ResponseBody};
 
-use globa_flux_rust::db::get_pool;
+use globa_flux_rust::db::{fetch_policy_params_json, get_pool};
+use globa_flux_rust::decision_engine::{
+    explain_decision, min_surfaced_confidence, surfaced_direction, ExplanationLang,
+    INSUFFICIENT_CONFIDENCE,
+};
+use globa_flux_rust::error_codes::annotate_error_body;
 
 fn bearer_token(header_value: Option<&str>) -> Option<&str> {
     let value = header_value?;
@@ -14,8 +19,9 @@ fn bearer_token(header_value: Option<&str>) -> Option<&str> {
 
 fn json_response(
     status: StatusCode,
-    value: serde_json::Value,
+    mut value: serde_json::Value,
 ) -> Result<Response<ResponseBody>, Error> {
+    annotate_error_body(&mut value);
     Ok(Response::builder()
         .status(status)
         .header("content-type", "application/json; charset=utf-8")
@@ -53,6 +59,25 @@ fn default_decision(as_of_dt: NaiveDate) -> serde_json::Value {
     })
 }
 
+/// Keeps evidence and re-evaluation triggers but drops the direction and forbidden actions when
+/// confidence is below `min_confidence`; returns whether the decision was masked.
+fn apply_confidence_gate(decision: &mut serde_json::Value, min_confidence: f64) -> bool {
+    let direction = decision["direction"]
+        .as_str()
+        .unwrap_or("PROTECT")
+        .to_string();
+    let confidence = decision["confidence"].as_f64().unwrap_or(0.0);
+    decision["minConfidence"] = serde_json::json!(min_confidence);
+    if surfaced_direction(&direction, confidence, min_confidence).is_some() {
+        decision["status"] = serde_json::json!("actionable");
+        return false;
+    }
+    decision["status"] = serde_json::json!(INSUFFICIENT_CONFIDENCE);
+    decision["direction"] = serde_json::Value::Null;
+    decision["forbidden"] = serde_json::json!([]);
+    true
+}
+
 async fn handle_today(
     method: &Method,
     headers: &HeaderMap,
@@ -102,6 +127,8 @@ async fn handle_today(
         .and_then(|v| NaiveDate::parse_from_str(&v, "%Y-%m-%d").ok())
         .unwrap_or(today);
 
+    let lang = ExplanationLang::parse(query_param(uri.query(), "lang").as_deref());
+
     let pool = get_pool().await?;
 
     let row = sqlx::query_as::<_, (String, f64, String, String, String)>(
@@ -123,7 +150,7 @@ async fn handle_today(
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    let decision = if let Some((
+    let mut decision = if let Some((
         direction,
         confidence,
         evidence_json,
@@ -147,6 +174,36 @@ async fn handle_today(
         default_decision(as_of_dt)
     };
 
+    let policy_params_json =
+        fetch_policy_params_json(pool, &tenant_id, &channel_id, "active").await?;
+    apply_confidence_gate(
+        &mut decision,
+        min_surfaced_confidence(policy_params_json.as_deref()),
+    );
+
+    let strings = |key: &str| -> Vec<String> {
+        decision[key]
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let explanation = explain_decision(
+        decision["direction"]
+            .as_str()
+            .unwrap_or(INSUFFICIENT_CONFIDENCE),
+        decision["confidence"].as_f64().unwrap_or(0.0),
+        &strings("evidence"),
+        &strings("forbidden"),
+        lang,
+    );
+    decision["explanation"] = serde_json::json!(explanation);
+    decision["explanationLang"] = serde_json::json!(lang.as_str());
+
     json_response(
         StatusCode::OK,
         serde_json::json!({"ok": true, "decision": decision}),
@@ -182,4 +239,22 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
+
+    #[test]
+    fn confidence_gate_masks_only_low_confidence_decisions() {
+        let as_of_dt = NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+
+        let mut shaky = default_decision(as_of_dt);
+        shaky["confidence"] = serde_json::json!(0.45);
+        assert!(apply_confidence_gate(&mut shaky, 0.5));
+        assert_eq!(shaky["status"], INSUFFICIENT_CONFIDENCE);
+        assert!(shaky["direction"].is_null());
+        assert_eq!(shaky["forbidden"], serde_json::json!([]));
+        assert_eq!(shaky["evidence"][0], "MVP stub: no channel data synced yet");
+
+        let mut confident = default_decision(as_of_dt);
+        assert!(!apply_confidence_gate(&mut confident, 0.5));
+        assert_eq!(confident["status"], "actionable");
+        assert_eq!(confident["direction"], "PROTECT");
+    }
 }
@@ -8,12 +8,19 @@ use std::collections::HashMap;
 use vercel_runtime::{run, service_fn, Error, Request, Response, ResponseBody};
 
 use globa_flux_rust::db::{
-    create_geo_monitor_project, enqueue_geo_monitor_prompt_tasks, ensure_geo_monitor_run,
+    create_geo_monitor_project, enqueue_geo_monitor_prompt_batch_tasks,
+    enqueue_geo_monitor_prompt_tasks, ensure_geo_monitor_run,
     fetch_geo_monitor_project, fetch_geo_monitor_run_results, fetch_geo_monitor_run_summary, fetch_latest_geo_monitor_run,
-    fetch_tenant_ai_provider_setting, fetch_tenant_ai_routing_policy, get_pool, list_geo_monitor_projects,
-    list_geo_monitor_prompts, replace_geo_monitor_prompts,
+    fetch_tenant_ai_provider_setting, fetch_tenant_ai_routing_policy, fetch_usage_cost_summary, get_pool,
+    list_geo_monitor_projects, list_geo_monitor_prompts, replace_geo_monitor_prompts, GeoMonitorPromptInput,
+    UsageCostGroupBy,
+};
+use globa_flux_rust::error_codes::annotate_error_body;
+use globa_flux_rust::feature_flags::{check_tenant_feature, TenantFeature};
+use globa_flux_rust::geo_monitor::{
+    clamp_geo_monitor_max_output_tokens, clamp_geo_monitor_temperature, geo_monitor_prompt_batch_size,
+    parse_string_list_json,
 };
-use globa_flux_rust::geo_monitor::parse_string_list_json;
 
 fn bearer_token(header_value: Option<&str>) -> Option<&str> {
     let value = header_value?;
@@ -24,8 +31,9 @@ fn bearer_token(header_value: Option<&str>) -> Option<&str> {
 
 fn json_response(
     status: StatusCode,
-    value: serde_json::Value,
+    mut value: serde_json::Value,
 ) -> Result<Response<ResponseBody>, Error> {
+    annotate_error_body(&mut value);
     Ok(Response::builder()
         .status(status)
         .header("content-type", "application/json; charset=utf-8")
@@ -111,6 +119,10 @@ struct PromptInput {
     #[serde(default)]
     theme: Option<String>,
     text: String,
+    #[serde(default)]
+    temperature: Option<f64>,
+    #[serde(default)]
+    max_output_tokens: Option<i32>,
 }
 
 #[derive(Deserialize)]
@@ -132,6 +144,10 @@ struct GeoMonitorRpcRequest {
     schedule: Option<String>,
     #[serde(default)]
     prompts: Option<Vec<PromptInput>>,
+    #[serde(default)]
+    group_by: Option<String>,
+    #[serde(default)]
+    days: Option<i64>,
 }
 
 #[derive(Deserialize)]
@@ -141,6 +157,30 @@ struct DispatchRequest {
     tenant_id: Option<String>,
 }
 
+/// Per-prompt tasks by default; batched tasks when `GEO_MONITOR_PROMPT_BATCH_SIZE` > 1.
+async fn enqueue_geo_monitor_prompts(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    project_id: i64,
+    run_for_dt: chrono::NaiveDate,
+    prompt_ids: &[i64],
+) -> Result<u64, Error> {
+    let batch_size = geo_monitor_prompt_batch_size();
+    if batch_size > 1 {
+        enqueue_geo_monitor_prompt_batch_tasks(
+            pool,
+            tenant_id,
+            project_id,
+            run_for_dt,
+            prompt_ids.len(),
+            batch_size,
+        )
+        .await
+    } else {
+        enqueue_geo_monitor_prompt_tasks(pool, tenant_id, project_id, run_for_dt, prompt_ids).await
+    }
+}
+
 fn required_string(input: Option<String>, field: &str) -> Result<String, Error> {
     let value = input.unwrap_or_default().trim().to_string();
     if value.is_empty() {
@@ -241,6 +281,11 @@ async fn handle_dispatch(
     let mut skipped_tenants: Vec<String> = Vec::new();
 
     for (tenant_id, project_id) in projects.iter() {
+        if check_tenant_feature(pool, tenant_id, TenantFeature::GeoMonitor).await?.is_err() {
+            skipped_tenants.push(format!("{tenant_id}: feature_disabled"));
+            continue;
+        }
+
         let runtime = if let Some(cached) = runtime_cache.get(tenant_id) {
             cached.clone()
         } else {
@@ -276,9 +321,8 @@ async fn handle_dispatch(
         .await?;
         runs_ensured += 1;
 
-        let enqueued =
-            enqueue_geo_monitor_prompt_tasks(pool, tenant_id, *project_id, run_for_dt, &prompt_ids)
-                .await?;
+        let enqueued = enqueue_geo_monitor_prompts(pool, tenant_id, *project_id, run_for_dt, &prompt_ids)
+            .await?;
         tasks_enqueued = tasks_enqueued.saturating_add(enqueued);
     }
 
@@ -350,6 +394,10 @@ async fn handle_geo_monitor(
 
     let pool = get_pool().await?;
 
+    if let Err(body) = check_tenant_feature(pool, &tenant_id, TenantFeature::GeoMonitor).await? {
+        return json_response(StatusCode::FORBIDDEN, body);
+    }
+
     match parsed.op.as_str() {
         "list_projects" => {
             let projects = list_geo_monitor_projects(pool, &tenant_id).await?;
@@ -440,7 +488,7 @@ async fn handle_geo_monitor(
             let prompts = list_geo_monitor_prompts(pool, &tenant_id, project_id).await?;
             let prompts_json = prompts
         .iter()
-        .map(|p| serde_json::json!({"id": p.id, "theme": p.theme, "text": p.prompt_text, "enabled": p.enabled, "sort_order": p.sort_order}))
+        .map(|p| serde_json::json!({"id": p.id, "theme": p.theme, "text": p.prompt_text, "enabled": p.enabled, "sort_order": p.sort_order, "temperature": p.temperature, "max_output_tokens": p.max_output_tokens}))
         .collect::<Vec<_>>();
 
             let latest_run = fetch_latest_geo_monitor_run(pool, &tenant_id, project_id).await?;
@@ -520,14 +568,14 @@ async fn handle_geo_monitor(
             }
 
             let prompts = parsed.prompts.unwrap_or_default();
-            let mut cleaned: Vec<(Option<String>, String)> = Vec::new();
+            let mut cleaned: Vec<GeoMonitorPromptInput> = Vec::new();
             for p in prompts.into_iter() {
                 let text = p.text.trim().to_string();
                 if text.is_empty() {
                     continue;
                 }
-                cleaned.push((
-                    p.theme.and_then(|t| {
+                cleaned.push(GeoMonitorPromptInput {
+                    theme: p.theme.and_then(|t| {
                         let t = t.trim().to_string();
                         if t.is_empty() {
                             None
@@ -535,8 +583,10 @@ async fn handle_geo_monitor(
                             Some(t)
                         }
                     }),
-                    text,
-                ));
+                    prompt_text: text,
+                    temperature: clamp_geo_monitor_temperature(p.temperature),
+                    max_output_tokens: clamp_geo_monitor_max_output_tokens(p.max_output_tokens),
+                });
             }
 
             replace_geo_monitor_prompts(pool, &tenant_id, project_id, cleaned.as_slice()).await?;
@@ -594,7 +644,7 @@ async fn handle_geo_monitor(
             )
             .await?;
 
-            let enqueued = enqueue_geo_monitor_prompt_tasks(
+            let enqueued = enqueue_geo_monitor_prompts(
                 pool,
                 &tenant_id,
                 project_id,
@@ -622,6 +672,51 @@ async fn handle_geo_monitor(
             )
         }
 
+        "cost_summary" => {
+            let group_by = match parsed.group_by.as_deref() {
+                None => UsageCostGroupBy::Project,
+                Some(raw) => match UsageCostGroupBy::parse(raw) {
+                    Some(v) => v,
+                    None => {
+                        return json_response(
+                            StatusCode::BAD_REQUEST,
+                            serde_json::json!({"ok": false, "error": "bad_request", "message": "group_by must be one of: project, channel, event_type"}),
+                        )
+                    }
+                },
+            };
+            let days = parsed.days.unwrap_or(30).clamp(1, 366);
+
+            let end = Utc::now();
+            let start = end - chrono::Duration::days(days);
+            let groups = fetch_usage_cost_summary(pool, &tenant_id, start, end, group_by).await?;
+            let total_cost_usd: f64 = groups.iter().map(|g| g.cost_usd).sum();
+            let payload = groups
+                .into_iter()
+                .map(|g| {
+                    serde_json::json!({
+                      "key": if g.key.is_empty() { None } else { Some(g.key) },
+                      "events": g.events,
+                      "prompt_tokens": g.prompt_tokens,
+                      "completion_tokens": g.completion_tokens,
+                      "cost_usd": g.cost_usd,
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            json_response(
+                StatusCode::OK,
+                serde_json::json!({
+                  "ok": true,
+                  "group_by": group_by.as_str(),
+                  "start": start.to_rfc3339(),
+                  "end": end.to_rfc3339(),
+                  "total_cost_usd": total_cost_usd,
+                  "groups": payload
+                }),
+            )
+        }
+
         other => json_response(
             StatusCode::BAD_REQUEST,
             serde_json::json!({"ok": false, "error": "bad_request", "message": format!("unknown op: {other}")}),
@@ -8,39 +8,78 @@ use vercel_runtime::{run, service_fn, Error, Request, Response, ResponseBody};
 
 use globa_flux_rust::db::{
     decision_daily_exists, ensure_geo_monitor_run, fetch_geo_monitor_project,
-    fetch_geo_monitor_prompt, fetch_new_video_publish_counts_by_dt,
-    fetch_or_seed_youtube_oauth_app_config, fetch_policy_params_json, fetch_revenue_sum_usd_7d,
+    fetch_geo_monitor_prompt, fetch_geo_monitor_run_result_prompt_ids,
+    fetch_new_video_publish_counts_by_dt, list_geo_monitor_prompts, GeoMonitorPromptRow,
+    fetch_days_with_data, fetch_policy_params_json, fetch_provisional_decision_outcomes,
+    finalize_expired_provisional_decision_outcomes,
+    fetch_revenue_sum_usd_7d,
     fetch_active_tenant_ai_provider_setting, fetch_tenant_ai_routing_policy,
-    fetch_top_video_ids_by_revenue, fetch_youtube_channel_id,
-    fetch_youtube_connection_tokens, finalize_geo_monitor_run_if_complete, get_pool,
-    insert_geo_monitor_run_result, insert_usage_event, update_youtube_connection_tokens,
+    fetch_top_video_ids_by_revenue, fetch_top_videos_with_revenue, fetch_video_daily_metric_rows,
+    fetch_youtube_channel_id, fetch_youtube_last_synced_dt, advance_youtube_last_synced_dt,
+    fetch_experiment_baseline_thumbnail, fetch_experiment_full_snapshot, fetch_stored_video_snapshot, fetch_app_video_change_kinds, fetch_video_change_dts, fetch_youtube_connection_tokens, finalize_geo_monitor_run_if_complete, get_pool,
+    insert_geo_monitor_run_result, insert_usage_event_attributed, max_attempt_for_job_type,
     upsert_decision_outcome, upsert_observed_action, upsert_policy_eval_report,
-    upsert_policy_params, upsert_video_daily_metric,
+    upsert_policy_params, upsert_video_daily_metrics_batch,
+    record_video_change, record_youtube_api_usage, upsert_stored_video_snapshot,
+    complete_dispatch_lock, dispatch_lock_ttl_secs, job_task_lock_ttl_secs, release_dispatch_lock, try_acquire_dispatch_lock,
+    evidence_retention_cutoff, evidence_retention_days, mark_experiment_rollback_failed,
+    prune_decision_evidence, validate_sql_identifier, DispatchLockOutcome,
+    fetch_refilled_gap_dts, mark_gap_fills_refilled, record_gap_fill_attempts,
+};
+use globa_flux_rust::backfill::{
+    explicit_run_for_dates, find_date_gaps, gap_fill_run_for_dates, incremental_fetch_start,
+    run_bounded_chunks, run_bounded_isolated, unrefilled_gaps, upsert_concurrency, MAX_EXPLICIT_RUN_FOR_DTS,
+    METRIC_UPSERT_BATCH_SIZE, SYNC_OVERLAP_DAYS,
 };
 use globa_flux_rust::decision_engine::{compute_decision, DecisionEngineConfig};
-use globa_flux_rust::outcome_engine::compute_outcome_label;
+use globa_flux_rust::error_codes::annotate_error_body;
+use globa_flux_rust::http_client::http_client_for_url;
+use globa_flux_rust::experiments::{
+    effective_experiment_duration_days, experiment_baseline_window,
+    experiment_completed_day_offset, experiment_current_end_dt, experiment_last_complete_dt,
+    experiment_min_duration_days, snapshot_change_observed_actions, EXPERIMENT_BASELINE_DAYS,
+};
+use globa_flux_rust::onboarding::{
+    configured_backfill_weeks, weekly_backfill_run_for_dts, DEFAULT_NEW_CHANNEL_BACKFILL_WEEKS,
+};
+use globa_flux_rust::providers::llm_error::LlmProviderError;
+use globa_flux_rust::outcome_engine::{
+    compute_outcome_label, outcome_windows, stored_outcome_windows, OutcomeLabelConfig, OutcomeWindows,
+    PROVISIONAL_OUTCOME_MAX_AGE_DAYS,
+};
 use globa_flux_rust::providers::gemini::{
     generate_text as gemini_generate_text, pricing_for_model as gemini_pricing_for_model,
     GeminiConfig,
 };
-use globa_flux_rust::providers::youtube::{refresh_tokens, youtube_oauth_client_from_config};
 use globa_flux_rust::providers::youtube_analytics::{
-    fetch_video_daily_metrics_for_channel, youtube_analytics_error_to_vercel_error,
+    fetch_channel_daily_metrics_checked, youtube_analytics_error_to_vercel_error,
+    ChannelDailyMetricsFetch, SilentEmptyDailyReport, YoutubeAnalyticsError,
 };
 use globa_flux_rust::providers::youtube_reporting::{
     download_report_file, ensure_job_for_report_type, list_report_types, list_reports,
+    YoutubeReportingReportType,
 };
 use globa_flux_rust::providers::youtube_videos::{
-    set_video_thumbnail_from_url, update_video_publish_at, update_video_title,
+    experiment_change_quota_units, fetch_video_snapshot, restore_video_snapshot,
+    set_video_thumbnail_from_bytes, snapshot_restore_quota_units, update_video_publish_at,
+    update_video_title, VIDEOS_LIST_QUOTA_UNITS,
 };
 use globa_flux_rust::reach_reporting::ingest_channel_reach_basic_a1;
 use globa_flux_rust::secrets::decrypt_secret;
-use globa_flux_rust::youtube_alerts::evaluate_youtube_alerts;
+use globa_flux_rust::sync_schedule::SyncSchedule;
+use globa_flux_rust::youtube_alerts::{
+    evaluate_experiment_failure_alert, evaluate_source_divergence_alert, evaluate_youtube_alerts,
+};
+use globa_flux_rust::youtube_auth::{
+    call_with_fresh_youtube_token, is_reauth_required, refresh_youtube_tokens_if_expired,
+};
 use globa_flux_rust::{
     cost::{compute_cost_usd, ModelPricingUsdPerMToken},
     geo_monitor::{
-        contains_any_case_insensitive, extract_rank_from_markdown_list, normalize_aliases,
-        parse_string_list_json,
+        call_with_prompt_retry, contains_any_case_insensitive, extract_rank_from_markdown_list,
+        geo_monitor_batch_prompt_ids, geo_monitor_prompt_retries, normalize_aliases, parse_geo_monitor_batch_channel_id,
+        parse_string_list_json, run_geo_monitor_prompt_batch, GeoMonitorGenerationParams,
+        GEO_MONITOR_PROMPT_BATCH_JOB_TYPE,
     },
 };
 use globa_flux_rust::providers::openai::pricing_for_model as openai_pricing_for_model;
@@ -55,8 +94,9 @@ fn bearer_token(header_value: Option<&str>) -> Option<&str> {
 
 fn json_response(
     status: StatusCode,
-    value: serde_json::Value,
+    mut value: serde_json::Value,
 ) -> Result<Response<ResponseBody>, Error> {
+    annotate_error_body(&mut value);
     Ok(Response::builder()
         .status(status)
         .header("content-type", "application/json; charset=utf-8")
@@ -140,6 +180,10 @@ fn query_value<'a>(query: Option<&'a str>, key: &str) -> Option<&'a str> {
 }
 
 const YOUTUBE_REPORTING_BACKFILL_DAYS: i64 = 90;
+// The Reporting API keeps generated reports for 180 days; older windows return nothing.
+const YOUTUBE_REPORTING_BACKFILL_DAYS_MAX: i64 = 180;
+// Bump when the wide-table parser changes; files parsed under an older version get reingested.
+const YOUTUBE_REPORTING_PARSE_VERSION: &str = "v1";
 
 #[derive(Clone)]
 enum ResolvedProviderConfig {
@@ -333,32 +377,32 @@ async fn openai_generate_text(
       ]
     });
 
-    let client = reqwest::Client::new();
+    let client = http_client_for_url(&url)
+        .map_err(|e| -> Error { Box::new(std::io::Error::other(e.to_string())) })?;
     let resp = client
-        .post(url)
+        .post(&url)
         .headers(headers)
         .json(&payload)
         .send()
         .await
         .map_err(|e| -> Error { Box::new(std::io::Error::other(e.to_string())) })?;
     let status = resp.status();
-    let json = resp
-        .json::<Value>()
+    let body = resp
+        .bytes()
         .await
         .map_err(|e| -> Error { Box::new(std::io::Error::other(e.to_string())) })?;
 
+    // Checked before parsing: a gateway's 429/5xx page is often not JSON, and its status is
+    // what decides whether the call is retried.
     if !status.is_success() {
-        let message = json
-            .get("error")
-            .and_then(|e| e.get("message"))
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown_openai_error");
-        return Err(Box::new(std::io::Error::other(format!(
-            "OpenAI error (status {}): {}",
+        return Err(Box::new(LlmProviderError::from_http_response(
+            "OpenAI",
             status.as_u16(),
-            message
-        ))));
+            &body,
+        )));
     }
+    let json = serde_json::from_slice::<Value>(&body)
+        .map_err(|e| -> Error { Box::new(std::io::Error::other(e.to_string())) })?;
 
     Ok((openai_extract_text(&json), openai_extract_usage(&json)))
 }
@@ -382,9 +426,10 @@ async fn anthropic_generate_text(
       "messages": [{"role":"user","content": user}]
     });
 
-    let client = reqwest::Client::new();
+    let client = http_client_for_url(&url)
+        .map_err(|e| -> Error { Box::new(std::io::Error::other(e.to_string())) })?;
     let resp = client
-        .post(url)
+        .post(&url)
         .header("x-api-key", api_key)
         .header("anthropic-version", "2023-06-01")
         .header(reqwest::header::CONTENT_TYPE, "application/json")
@@ -394,23 +439,22 @@ async fn anthropic_generate_text(
         .await
         .map_err(|e| -> Error { Box::new(std::io::Error::other(e.to_string())) })?;
     let status = resp.status();
-    let json = resp
-        .json::<Value>()
+    let body = resp
+        .bytes()
         .await
         .map_err(|e| -> Error { Box::new(std::io::Error::other(e.to_string())) })?;
 
+    // Checked before parsing: a gateway's 429/5xx page is often not JSON, and its status is
+    // what decides whether the call is retried.
     if !status.is_success() {
-        let message = json
-            .get("error")
-            .and_then(|e| e.get("message"))
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown_anthropic_error");
-        return Err(Box::new(std::io::Error::other(format!(
-            "Anthropic error (status {}): {}",
+        return Err(Box::new(LlmProviderError::from_http_response(
+            "Anthropic",
             status.as_u16(),
-            message
-        ))));
+            &body,
+        )));
     }
+    let json = serde_json::from_slice::<Value>(&body)
+        .map_err(|e| -> Error { Box::new(std::io::Error::other(e.to_string())) })?;
 
     Ok((anthropic_extract_text(&json), anthropic_extract_usage(&json)))
 }
@@ -544,6 +588,174 @@ async fn resolve_runtime_from_active_setting(
     }))
 }
 
+struct GeoMonitorRunContext {
+    project_id: i64,
+    run_id: i64,
+    needles: Vec<String>,
+    resolved: ResolvedAiRuntime,
+}
+
+async fn geo_monitor_run_context(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    project_id: i64,
+    run_for_dt: NaiveDate,
+) -> Result<GeoMonitorRunContext, Error> {
+    let project = fetch_geo_monitor_project(pool, tenant_id, project_id)
+        .await?
+        .ok_or_else(|| Box::new(std::io::Error::other("missing geo monitor project")) as Error)?;
+
+    let prompt_total: i32 = sqlx::query_scalar(
+        r#"
+      SELECT COUNT(*) FROM geo_monitor_prompts
+      WHERE tenant_id = ? AND project_id = ? AND enabled = 1;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(project_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    let resolved = resolve_ai_runtime(pool, tenant_id).await?;
+    let run = ensure_geo_monitor_run(
+        pool,
+        tenant_id,
+        project_id,
+        run_for_dt,
+        &resolved.provider,
+        &resolved.model,
+        prompt_total,
+    )
+    .await?;
+
+    let aliases = parse_string_list_json(project.brand_aliases_json.as_deref());
+    let needles = normalize_aliases(&project.name, aliases.as_slice());
+
+    Ok(GeoMonitorRunContext {
+        project_id,
+        run_id: run.id,
+        needles,
+        resolved,
+    })
+}
+
+/// Runs one prompt and records its result; provider failures are recorded as error results.
+async fn process_geo_monitor_prompt(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    run_for_dt: NaiveDate,
+    ctx: &GeoMonitorRunContext,
+    prompt: &GeoMonitorPromptRow,
+) -> Result<(), Error> {
+    let project_id = ctx.project_id;
+    let prompt_id = prompt.id;
+    let resolved = &ctx.resolved;
+
+    let system = "You are a helpful assistant.";
+    let params = GeoMonitorGenerationParams::for_prompt(prompt);
+
+    let idempotency_key =
+        format!("{tenant_id}:geo_monitor_prompt:{project_id}:{run_for_dt}:{prompt_id}");
+
+    let pricing = pricing_for_resolved_runtime(resolved);
+
+    // Transient provider errors are retried in place; the error result is only recorded once
+    // retries are exhausted, so the run is not finalized around a recoverable failure.
+    match call_with_prompt_retry(
+        geo_monitor_prompt_retries(),
+        || {
+            generate_text_for_runtime(
+                resolved,
+                system,
+                &prompt.prompt_text,
+                params.temperature,
+                params.max_output_tokens,
+                Some(&idempotency_key),
+            )
+        },
+        |attempt| tokio::time::sleep(std::time::Duration::from_millis(500 * u64::from(attempt))),
+    )
+    .await
+    {
+        Ok((text, usage)) => {
+            let presence = contains_any_case_insensitive(&text, ctx.needles.as_slice());
+            let rank = extract_rank_from_markdown_list(&text, ctx.needles.as_slice());
+
+            let cost_usd = pricing
+                .map(|p| {
+                    compute_cost_usd(
+                        p,
+                        usage.prompt_tokens as u32,
+                        usage.completion_tokens as u32,
+                    )
+                })
+                .unwrap_or(0.0);
+
+            if let Err(err) = insert_usage_event_attributed(
+                pool,
+                tenant_id,
+                "geo_monitor_prompt",
+                &idempotency_key,
+                &resolved.provider,
+                &resolved.model,
+                usage.prompt_tokens,
+                usage.completion_tokens,
+                cost_usd,
+                Some(ctx.project_id),
+                None,
+            )
+            .await
+            {
+                if err
+                    .as_database_error()
+                    .is_some_and(|e| e.is_unique_violation())
+                {
+                    // idempotent replay: ignore
+                } else {
+                    return Err(Box::new(err) as Error);
+                }
+            }
+
+            let _ = insert_geo_monitor_run_result(
+                pool,
+                tenant_id,
+                project_id,
+                run_for_dt,
+                ctx.run_id,
+                prompt_id,
+                &prompt.prompt_text,
+                Some(&text),
+                presence,
+                rank,
+                cost_usd,
+                None,
+            )
+            .await?;
+            Ok(())
+        }
+        Err(err) => {
+            let msg = truncate_string(&err.to_string(), 2000);
+            let _ = insert_geo_monitor_run_result(
+                pool,
+                tenant_id,
+                project_id,
+                run_for_dt,
+                ctx.run_id,
+                prompt_id,
+                &prompt.prompt_text,
+                None,
+                false,
+                None,
+                0.0,
+                Some(&msg),
+            )
+            .await?;
+            Ok(())
+        }
+    }
+}
+
 async fn resolve_ai_runtime(
     pool: &sqlx::MySqlPool,
     tenant_id: &str,
@@ -722,6 +934,123 @@ async fn upsert_alert(
     Ok(())
 }
 
+const ANALYTICS_SILENT_EMPTY_ALERT_KEY: &str = "analytics_silent_empty";
+
+fn silent_empty_alert_details(
+    start_dt: NaiveDate,
+    end_dt: NaiveDate,
+    report: &SilentEmptyDailyReport,
+) -> String {
+    serde_json::json!({
+      "window": { "start_dt": start_dt.to_string(), "end_dt": end_dt.to_string() },
+      "daily_rows": 0,
+      "top_videos_views": report.top_videos_views,
+      "top_videos": report
+        .top_videos
+        .iter()
+        .map(|r| serde_json::json!({"video_id": r.video_id, "views": r.views}))
+        .collect::<Vec<_>>(),
+      "note": "dimensions=day[,video] reports were empty while dimensions=video shows views; the sync watermark was not advanced.",
+    })
+    .to_string()
+}
+
+async fn resolve_open_alert(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    alert_key: &str,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      UPDATE yt_alerts
+      SET resolved_at = CURRENT_TIMESTAMP(3),
+          updated_at = CURRENT_TIMESTAMP(3)
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND alert_key = ?
+        AND resolved_at IS NULL;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(alert_key)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+/// Videos whose Studio-side edits are tracked through snapshot diffs.
+const VIDEO_SNAPSHOT_TRACK_LIMIT: i64 = 10;
+
+/// Diffs fresh snapshots of the channel's top videos against the stored ones and records
+/// title/thumbnail/publish changes the creator made outside the app as observed_actions.
+async fn detect_manual_video_changes(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    access_token: &str,
+    today: NaiveDate,
+) -> Result<usize, Error> {
+    let video_ids = fetch_top_video_ids_by_revenue(
+        pool,
+        tenant_id,
+        channel_id,
+        today - Duration::days(28),
+        today - Duration::days(1),
+        VIDEO_SNAPSHOT_TRACK_LIMIT,
+    )
+    .await?;
+
+    let mut recorded = 0usize;
+    for video_id in video_ids {
+        let _ = record_youtube_api_usage(
+            pool,
+            tenant_id,
+            channel_id,
+            "videos.list",
+            VIDEOS_LIST_QUOTA_UNITS,
+        )
+        .await;
+        let current = match fetch_video_snapshot(access_token, &video_id).await {
+            Ok(v) => v,
+            Err(err) => {
+                eprintln!("daily_channel: snapshot {video_id} error: {err}");
+                continue;
+            }
+        };
+        let previous = fetch_stored_video_snapshot(pool, tenant_id, channel_id, &video_id).await?;
+        let app_change_kinds = match previous.as_ref() {
+            Some(previous) => {
+                fetch_app_video_change_kinds(
+                    pool,
+                    tenant_id,
+                    channel_id,
+                    &video_id,
+                    previous.checked_at.date_naive(),
+                    today,
+                )
+                .await?
+            }
+            None => Vec::new(),
+        };
+        for (action_type, meta_json) in snapshot_change_observed_actions(
+            &video_id,
+            previous.as_ref(),
+            &current,
+            &app_change_kinds,
+        ) {
+            upsert_observed_action(pool, tenant_id, channel_id, today, &action_type, Some(&meta_json))
+                .await?;
+            recorded += 1;
+        }
+        upsert_stored_video_snapshot(pool, tenant_id, channel_id, &video_id, &current).await?;
+    }
+    Ok(recorded)
+}
+
 async fn evaluate_running_experiments_for_channel(
     pool: &sqlx::MySqlPool,
     tenant_id: &str,
@@ -729,7 +1058,8 @@ async fn evaluate_running_experiments_for_channel(
     access_token: &str,
     run_for_dt: NaiveDate,
 ) -> Result<(), Error> {
-    let last_complete_dt = run_for_dt - Duration::days(1);
+    let last_complete_dt =
+        experiment_last_complete_dt(run_for_dt, experiment_completed_day_offset());
 
     let rows = sqlx::query_as::<
         _,
@@ -782,10 +1112,36 @@ async fn evaluate_running_experiments_for_channel(
         let primary_video_id = video_ids[0].trim().to_string();
 
         let start_dt = started_at.date_naive();
-        let baseline_start_dt = start_dt - Duration::days(7);
-        let baseline_end_dt = start_dt - Duration::days(1);
+        // Look back two windows so a shifted baseline is still known to be clean of the change.
+        let change_dts = fetch_video_change_dts(
+            pool,
+            tenant_id,
+            channel_id,
+            &primary_video_id,
+            start_dt - Duration::days(EXPERIMENT_BASELINE_DAYS * 2),
+            start_dt - Duration::days(1),
+        )
+        .await?;
+        let baseline_window = experiment_baseline_window(start_dt, &change_dts);
+        let baseline_start_dt = baseline_window.start_dt;
+        let baseline_end_dt = baseline_window.end_dt;
+        if let Some(note) = baseline_window.note.as_deref() {
+            sqlx::query(
+                r#"
+          UPDATE yt_experiments
+          SET baseline_note = ?
+          WHERE id = ? AND tenant_id = ?;
+        "#,
+            )
+            .bind(note)
+            .bind(id)
+            .bind(tenant_id)
+            .execute(pool)
+            .await
+            .map_err(|e| -> Error { Box::new(e) })?;
+        }
         let ended_dt = ended_at.map(|dt| dt.date_naive());
-        let current_end_dt = ended_dt.unwrap_or(last_complete_dt).min(last_complete_dt);
+        let current_end_dt = experiment_current_end_dt(ended_dt, last_complete_dt);
 
         let baseline = aggregate_metrics_for_videos(
             pool,
@@ -858,38 +1214,76 @@ async fn evaluate_running_experiments_for_channel(
                 .filter(|v| v.is_object())
                 .unwrap_or_else(|| serde_json::json!({}));
 
-            let rollback_err: Option<String> = match exp_type.as_str() {
-                "title" => match json_string_field(&baseline_payload, "title") {
-                    None => Some("baseline variant A missing title".to_string()),
-                    Some(title) => update_video_title(access_token, &primary_video_id, &title)
-                        .await
-                        .err()
-                        .map(|e| e.to_string()),
-                },
-                "thumbnail" => match json_string_field(&baseline_payload, "thumbnail_url")
-                    .or_else(|| json_string_field(&baseline_payload, "thumbnailUrl"))
-                {
-                    None => Some("baseline variant A missing thumbnail_url".to_string()),
-                    Some(url) => {
-                        set_video_thumbnail_from_url(access_token, &primary_video_id, &url)
-                            .await
-                            .err()
-                            .map(|e| e.to_string())
+            let full_snapshot = fetch_experiment_full_snapshot(pool, tenant_id, id).await?;
+            let original_thumbnail = if exp_type == "thumbnail" {
+                fetch_experiment_baseline_thumbnail(pool, id).await?
+            } else {
+                None
+            };
+            let rollback_err: Option<String> = if let Some(snapshot) = full_snapshot.as_ref() {
+                match restore_video_snapshot(access_token, &primary_video_id, snapshot, original_thumbnail.as_ref()).await {
+                    Err(e) => Some(e.to_string()),
+                    Ok(()) if exp_type == "thumbnail" && original_thumbnail.is_none() => {
+                        Some("baseline thumbnail image was not stored".to_string())
                     }
-                },
-                "publish_time" => match json_string_field(&baseline_payload, "publish_at")
-                    .or_else(|| json_string_field(&baseline_payload, "publishAt"))
-                {
-                    None => Some("baseline variant A missing publish_at".to_string()),
-                    Some(publish_at) => {
-                        update_video_publish_at(access_token, &primary_video_id, &publish_at)
+                    Ok(()) => None,
+                }
+            } else {
+                match exp_type.as_str() {
+                    "title" => match json_string_field(&baseline_payload, "title") {
+                        None => Some("baseline variant A missing title".to_string()),
+                        Some(title) => update_video_title(access_token, &primary_video_id, &title)
                             .await
                             .err()
-                            .map(|e| e.to_string())
-                    }
-                },
-                _ => None,
+                            .map(|e| e.to_string()),
+                    },
+                    "thumbnail" => match original_thumbnail.as_ref() {
+                        None => Some("baseline thumbnail image was not stored".to_string()),
+                        Some(thumbnail) => {
+                            set_video_thumbnail_from_bytes(access_token, &primary_video_id, thumbnail)
+                                .await
+                                .err()
+                                .map(|e| e.to_string())
+                        }
+                    },
+                    "publish_time" => match json_string_field(&baseline_payload, "publish_at")
+                        .or_else(|| json_string_field(&baseline_payload, "publishAt"))
+                    {
+                        None => Some("baseline variant A missing publish_at".to_string()),
+                        Some(publish_at) => {
+                            update_video_publish_at(access_token, &primary_video_id, &publish_at)
+                                .await
+                                .err()
+                                .map(|e| e.to_string())
+                        }
+                    },
+                    _ => None,
+                }
             };
+            let quota_units = match full_snapshot.as_ref() {
+                Some(_) => snapshot_restore_quota_units(original_thumbnail.is_some()),
+                None => experiment_change_quota_units(exp_type.as_str()).into_iter().collect(),
+            };
+            for (api_method, units) in quota_units {
+                let _ =
+                    record_youtube_api_usage(pool, tenant_id, channel_id, api_method, units).await;
+            }
+            if rollback_err.is_some() {
+                let _ = mark_experiment_rollback_failed(pool, tenant_id, id).await;
+            }
+            if rollback_err.is_none()
+                && matches!(exp_type.as_str(), "title" | "thumbnail" | "publish_time")
+            {
+                let _ = record_video_change(
+                    pool,
+                    tenant_id,
+                    channel_id,
+                    &primary_video_id,
+                    exp_type.as_str(),
+                    run_for_dt,
+                )
+                .await;
+            }
 
             let mut tx = pool.begin().await.map_err(|e| -> Error { Box::new(e) })?;
             let updated = sqlx::query(
@@ -972,7 +1366,9 @@ async fn evaluate_running_experiments_for_channel(
             continue;
         }
 
-        if let Some(days) = planned_duration_days.filter(|v| *v > 0) {
+        if let Some(days) =
+            effective_experiment_duration_days(planned_duration_days, experiment_min_duration_days())
+        {
             let elapsed_days = if current_end_dt >= start_dt {
                 (current_end_dt - start_dt).num_days() + 1
             } else {
@@ -1006,7 +1402,27 @@ async fn evaluate_running_experiments_for_channel(
                     .filter(|v| v.is_object())
                     .unwrap_or_else(|| serde_json::json!({}));
 
-                let rollback_err: Option<String> = if state == "lost" {
+                let full_snapshot = if state == "lost" {
+                    fetch_experiment_full_snapshot(pool, tenant_id, id).await?
+                } else {
+                    None
+                };
+                let original_thumbnail = if state == "lost" && exp_type == "thumbnail" {
+                    fetch_experiment_baseline_thumbnail(pool, id).await?
+                } else {
+                    None
+                };
+                let rollback_err: Option<String> = if state != "lost" {
+                    None
+                } else if let Some(snapshot) = full_snapshot.as_ref() {
+                    match restore_video_snapshot(access_token, &primary_video_id, snapshot, original_thumbnail.as_ref()).await {
+                        Err(e) => Some(e.to_string()),
+                        Ok(()) if exp_type == "thumbnail" && original_thumbnail.is_none() => {
+                            Some("baseline thumbnail image was not stored".to_string())
+                        }
+                        Ok(()) => None,
+                    }
+                } else {
                     match exp_type.as_str() {
                         "title" => match json_string_field(&baseline_payload, "title") {
                             None => Some("baseline variant A missing title".to_string()),
@@ -1017,12 +1433,10 @@ async fn evaluate_running_experiments_for_channel(
                                     .map(|e| e.to_string())
                             }
                         },
-                        "thumbnail" => match json_string_field(&baseline_payload, "thumbnail_url")
-                            .or_else(|| json_string_field(&baseline_payload, "thumbnailUrl"))
-                        {
-                            None => Some("baseline variant A missing thumbnail_url".to_string()),
-                            Some(url) => {
-                                set_video_thumbnail_from_url(access_token, &primary_video_id, &url)
+                        "thumbnail" => match original_thumbnail.as_ref() {
+                            None => Some("baseline thumbnail image was not stored".to_string()),
+                            Some(thumbnail) => {
+                                set_video_thumbnail_from_bytes(access_token, &primary_video_id, thumbnail)
                                     .await
                                     .err()
                                     .map(|e| e.to_string())
@@ -1043,9 +1457,38 @@ async fn evaluate_running_experiments_for_channel(
                         },
                         _ => None,
                     }
-                } else {
-                    None
                 };
+                if state == "lost" {
+                    let quota_units = match full_snapshot.as_ref() {
+                        Some(_) => snapshot_restore_quota_units(original_thumbnail.is_some()),
+                        None => experiment_change_quota_units(exp_type.as_str())
+                            .into_iter()
+                            .collect(),
+                    };
+                    for (api_method, units) in quota_units {
+                        let _ = record_youtube_api_usage(
+                            pool, tenant_id, channel_id, api_method, units,
+                        )
+                        .await;
+                    }
+                }
+                if rollback_err.is_some() {
+                    let _ = mark_experiment_rollback_failed(pool, tenant_id, id).await;
+                }
+                if state == "lost"
+                    && rollback_err.is_none()
+                    && matches!(exp_type.as_str(), "title" | "thumbnail" | "publish_time")
+                {
+                    let _ = record_video_change(
+                        pool,
+                        tenant_id,
+                        channel_id,
+                        &primary_video_id,
+                        exp_type.as_str(),
+                        run_for_dt,
+                    )
+                    .await;
+                }
 
                 let mut tx = pool.begin().await.map_err(|e| -> Error { Box::new(e) })?;
                 let updated = sqlx::query(
@@ -1142,7 +1585,19 @@ fn youtube_reporting_created_after_rfc3339(
     dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
 }
 
-fn yt_reporting_wide_table_name(report_type_id: &str) -> String {
+/// Request override, then `YOUTUBE_REPORTING_BACKFILL_DAYS`, then the 90-day default.
+fn youtube_reporting_backfill_days(requested: Option<i64>) -> i64 {
+    requested
+        .or_else(|| {
+            std::env::var("YOUTUBE_REPORTING_BACKFILL_DAYS")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+        })
+        .unwrap_or(YOUTUBE_REPORTING_BACKFILL_DAYS)
+        .clamp(1, YOUTUBE_REPORTING_BACKFILL_DAYS_MAX)
+}
+
+fn yt_reporting_wide_table_name(report_type_id: &str) -> Result<String, Error> {
     let base = globa_flux_rust::db::sanitize_sql_identifier(report_type_id);
     let hash = sha2::Sha256::digest(report_type_id.as_bytes());
     let suffix = format!("{:x}", hash);
@@ -1155,7 +1610,8 @@ fn yt_reporting_wide_table_name(report_type_id: &str) -> String {
             name.pop();
         }
     }
-    name
+    validate_sql_identifier(&name)?;
+    Ok(name)
 }
 
 fn maybe_gunzip_bytes(input: &[u8]) -> Result<Vec<u8>, std::io::Error> {
@@ -1208,11 +1664,58 @@ async fn upsert_yt_reporting_wide_table_metadata(
     Ok(())
 }
 
-async fn ensure_yt_reporting_wide_table(
-    pool: &sqlx::MySqlPool,
+const YOUTUBE_REPORTING_MAX_COLUMNS_DEFAULT: usize = 300;
+/// Well under MySQL's 4096-column limit, and row-size limits hit long before that.
+const YOUTUBE_REPORTING_MAX_COLUMNS_CAP: usize = 1000;
+
+/// `YOUTUBE_REPORTING_MAX_COLUMNS` (default 300, clamped to 1..=1000).
+fn youtube_reporting_max_columns(value: Option<&str>) -> usize {
+    value
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .unwrap_or(YOUTUBE_REPORTING_MAX_COLUMNS_DEFAULT)
+        .clamp(1, YOUTUBE_REPORTING_MAX_COLUMNS_CAP)
+}
+
+/// Refuses a report wider than `max_columns` before any wide-table DDL runs, so one odd report
+/// type can't grow the schema without bound.
+fn check_wide_table_width(
+    report_type_id: &str,
+    columns_len: usize,
+    max_columns: usize,
+) -> Result<(), String> {
+    if columns_len > max_columns {
+        return Err(format!(
+            "report type {report_type_id} has {columns_len} columns, over the {max_columns}-column wide-table limit (YOUTUBE_REPORTING_MAX_COLUMNS); not ingested"
+        ));
+    }
+    Ok(())
+}
+
+/// Report files to re-queue: parsed or failed under an older parser, or rejected as wider than
+/// the column limit (`too_wide_columns`) that the current limit now admits.
+const STALE_REPORT_FILES_SQL: &str = r#"
+      SELECT report_id
+      FROM yt_reporting_report_files
+      WHERE tenant_id = ?
+        AND content_owner_id = ?
+        AND parse_status IN ('parsed','error')
+        AND (parse_version IS NULL OR parse_version <> ?
+             OR (parse_status = 'error' AND too_wide_columns <= ?))
+      ORDER BY id ASC
+      LIMIT 200;
+    "#;
+
+/// The CREATE TABLE statement plus one idempotent ADD COLUMN per column. The table and every
+/// column are validated here, so no caller can splice an unchecked identifier into DDL.
+fn yt_reporting_wide_table_ddl(
     table_name: &str,
     columns: &[String],
-) -> Result<(), Error> {
+) -> Result<(String, Vec<String>), Error> {
+    validate_sql_identifier(table_name)?;
+    for col in columns {
+        validate_sql_identifier(col)?;
+    }
+
     let mut ddl = String::new();
     ddl.push_str(&format!(
         "CREATE TABLE IF NOT EXISTS `{table_name}` (\
@@ -1236,14 +1739,29 @@ async fn ensure_yt_reporting_wide_table(
      );",
     );
 
+    let alters = columns
+        .iter()
+        .map(|col| {
+            format!("ALTER TABLE `{table_name}` ADD COLUMN IF NOT EXISTS `{col}` LONGTEXT NULL;")
+        })
+        .collect();
+
+    Ok((ddl, alters))
+}
+
+async fn ensure_yt_reporting_wide_table(
+    pool: &sqlx::MySqlPool,
+    table_name: &str,
+    columns: &[String],
+) -> Result<(), Error> {
+    let (ddl, alters) = yt_reporting_wide_table_ddl(table_name, columns)?;
+
     sqlx::query(&ddl)
         .execute(pool)
         .await
         .map_err(|e| -> Error { Box::new(e) })?;
 
-    for col in columns {
-        let alter =
-            format!("ALTER TABLE `{table_name}` ADD COLUMN IF NOT EXISTS `{col}` LONGTEXT NULL;");
+    for alter in alters {
         sqlx::query(&alter)
             .execute(pool)
             .await
@@ -1267,6 +1785,10 @@ async fn insert_yt_reporting_wide_rows_batch(
     if rows.is_empty() {
         return Ok(());
     }
+    validate_sql_identifier(table_name)?;
+    for col in columns {
+        validate_sql_identifier(col)?;
+    }
 
     let mut qb = sqlx::QueryBuilder::<sqlx::MySql>::new("INSERT INTO ");
     qb.push(format!("`{table_name}`"));
@@ -1302,22 +1824,136 @@ async fn insert_yt_reporting_wide_rows_batch(
     Ok(())
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum DispatchSchedule {
-    Daily,
-    Weekly,
-    YoutubeReporting,
+/// Drops the rows a report stored in its wide table, so a parse starting from row 0 (first parse
+/// or a reparse under a newer parser) never leaves values from an earlier parse behind.
+async fn delete_yt_reporting_wide_rows(
+    pool: &sqlx::MySqlPool,
+    table_name: &str,
+    tenant_id: &str,
+    content_owner_id: &str,
+    report_id: &str,
+) -> Result<(), Error> {
+    validate_sql_identifier(table_name)?;
+    sqlx::query(&format!(
+        "DELETE FROM `{table_name}` WHERE tenant_id = ? AND content_owner_id = ? AND report_id = ?;"
+    ))
+    .bind(tenant_id)
+    .bind(content_owner_id)
+    .bind(report_id)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
 }
 
-impl DispatchSchedule {
-    fn from_query(query: Option<&str>) -> Self {
-        let value = query_value(query, "schedule").unwrap_or("");
-        match value {
-            "weekly" | "Weekly" | "WEEKLY" => DispatchSchedule::Weekly,
-            "youtube_reporting" | "youtubeReporting" | "YouTubeReporting" => {
-                DispatchSchedule::YoutubeReporting
+/// Yields `(row_no, values)` for data rows after `checkpoint_row_no` (1-based, header excluded).
+/// Rows at or below the checkpoint are read as raw bytes into one reused record and dropped, so
+/// a resume near the end of a large report does not decode everything before it again.
+fn wide_rows_after_checkpoint<R: std::io::Read>(
+    rdr: &mut csv::Reader<R>,
+    columns_len: usize,
+    checkpoint_row_no: i64,
+) -> impl Iterator<Item = Result<(i64, Vec<Option<String>>), csv::Error>> + '_ {
+    let mut skipped = 0i64;
+    let mut skip_error = None;
+    let mut record = csv::ByteRecord::new();
+    while skipped < checkpoint_row_no {
+        match rdr.read_byte_record(&mut record) {
+            Ok(true) => skipped += 1,
+            Ok(false) => break,
+            Err(e) => {
+                skip_error = Some(e);
+                break;
             }
-            _ => DispatchSchedule::Daily,
+        }
+    }
+
+    let rest = skip_error.is_none().then(|| rdr.records());
+    skip_error
+        .map(Err)
+        .into_iter()
+        .chain(rest.into_iter().flatten().zip(skipped + 1..).map(
+            move |(result, row_no)| {
+                let record = result?;
+                let values = (0..columns_len)
+                    .map(|idx| {
+                        let v = record.get(idx).unwrap_or("");
+                        if v.is_empty() {
+                            None
+                        } else {
+                            Some(v.to_string())
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                Ok((row_no, values))
+            },
+        ))
+}
+
+async fn update_yt_reporting_ingest_checkpoint(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    content_owner_id: &str,
+    report_id: &str,
+    row_no: i64,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      UPDATE yt_reporting_report_files
+      SET ingested_row_no = GREATEST(ingested_row_no, ?)
+      WHERE tenant_id = ?
+        AND content_owner_id = ?
+        AND report_id = ?;
+    "#,
+    )
+    .bind(row_no)
+    .bind(tenant_id)
+    .bind(content_owner_id)
+    .bind(report_id)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ReportParseAction {
+    Skip,
+    Resume,
+    Reparse,
+}
+
+/// A file marked parsed without a version predates parse versioning, so it is reparsed like one
+/// from an older parser (the stale-report sweep re-queues both).
+fn report_parse_action(parse_status: &str, parse_version: Option<&str>) -> ReportParseAction {
+    match parse_version {
+        Some(v) if v != YOUTUBE_REPORTING_PARSE_VERSION => ReportParseAction::Reparse,
+        None if parse_status == "parsed" => ReportParseAction::Reparse,
+        _ if parse_status == "parsed" => ReportParseAction::Skip,
+        _ => ReportParseAction::Resume,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DispatchSchedule {
+    Daily,
+    Weekly,
+    YoutubeReporting,
+    Retention,
+}
+
+impl DispatchSchedule {
+    fn from_query(query: Option<&str>) -> Self {
+        let value = query_value(query, "schedule").unwrap_or("");
+        match value {
+            "weekly" | "Weekly" | "WEEKLY" => DispatchSchedule::Weekly,
+            "youtube_reporting" | "youtubeReporting" | "YouTubeReporting" => {
+                DispatchSchedule::YoutubeReporting
+            }
+            "retention" | "Retention" | "RETENTION" => DispatchSchedule::Retention,
+            _ => DispatchSchedule::Daily,
         }
     }
 
@@ -1326,10 +1962,77 @@ impl DispatchSchedule {
             DispatchSchedule::Daily => "daily_channel",
             DispatchSchedule::Weekly => "weekly_channel",
             DispatchSchedule::YoutubeReporting => "youtube_reporting_owner",
+            DispatchSchedule::Retention => "evidence_retention",
         }
     }
 }
 
+/// The run date for a channel in a scheduled sweep, or `None` when `now` is outside its
+/// `sync_schedule` window. Without a schedule the channel keeps the sweep's date; with one the
+/// date is the creator's local day unless the caller pinned `run_for_dt`.
+fn scheduled_run_for_dt(
+    sync_schedule: Option<&SyncSchedule>,
+    now: chrono::DateTime<Utc>,
+    run_for_dt: chrono::NaiveDate,
+    run_for_dt_pinned: bool,
+) -> Option<chrono::NaiveDate> {
+    let Some(sync_schedule) = sync_schedule else {
+        return Some(run_for_dt);
+    };
+    if !sync_schedule.is_due(now) {
+        return None;
+    }
+    Some(if run_for_dt_pinned {
+        run_for_dt
+    } else {
+        sync_schedule.local_run_for_dt(now)
+    })
+}
+
+/// Dispatch lock scope: one per schedule and tenant filter (plus channel for targeted runs) and
+/// per `params_hash`, so a backfill or forced run is not swallowed by a plain dispatch's lock.
+fn dispatch_lock_key(
+    job_type: &str,
+    tenant_id: Option<&str>,
+    channel_id: Option<&str>,
+    params_hash: &str,
+) -> String {
+    let mut key = format!("dispatch:{job_type}:{}", tenant_id.unwrap_or("*"));
+    if let Some(channel_id) = channel_id {
+        key.push(':');
+        key.push_str(channel_id);
+    }
+    key.push(':');
+    key.push_str(params_hash);
+    key
+}
+
+/// Short hash of the normalized request parameters that change what a dispatch enqueues.
+/// `run_for_dt` is the pinned date only (None when it defaults to today), and `run_for_dts` the
+/// parsed, deduplicated list, so equivalent spellings of one request share a lock.
+fn dispatch_params_hash(
+    parsed: &DispatchRequest,
+    run_for_dt: Option<chrono::NaiveDate>,
+    run_for_dts: Option<&[chrono::NaiveDate]>,
+    force: bool,
+) -> String {
+    let opt = |v: Option<i64>| v.map(|v| v.to_string()).unwrap_or_default();
+    let canonical = format!(
+        "run_for_dt={};run_for_dts={};backfill_weeks={};new_channel_backfill_weeks={};reporting_backfill_days={};gap_scan_weeks={};force={force}",
+        run_for_dt.map(|dt| dt.to_string()).unwrap_or_default(),
+        run_for_dts
+            .map(|dts| dts.iter().map(|dt| dt.to_string()).collect::<Vec<_>>().join(","))
+            .unwrap_or_default(),
+        parsed.backfill_weeks.unwrap_or(0).clamp(0, 52),
+        opt(parsed.new_channel_backfill_weeks),
+        opt(parsed.reporting_backfill_days),
+        opt(parsed.gap_scan_weeks),
+    );
+    format!("{:x}", sha2::Sha256::digest(canonical.as_bytes()))[..16].to_string()
+}
+
+/// Paused connections (`active = 0`) keep their history but are skipped by scheduled dispatch,
+/// as are connections whose refresh token was revoked (`needs_reauth = 1`) until they reconnect.
 fn candidate_select_sql(schedule: DispatchSchedule, has_tenant_filter: bool) -> &'static str {
     match (schedule, has_tenant_filter) {
         (DispatchSchedule::YoutubeReporting, true) => {
@@ -1339,7 +2042,9 @@ fn candidate_select_sql(schedule: DispatchSchedule, has_tenant_filter: bool) ->
         WHERE tenant_id = ?
           AND oauth_provider = 'youtube'
           AND content_owner_id IS NOT NULL
-          AND content_owner_id <> '';
+          AND content_owner_id <> ''
+          AND active = 1
+          AND needs_reauth = 0;
       "#
         }
         (DispatchSchedule::YoutubeReporting, false) => {
@@ -1348,7 +2053,9 @@ fn candidate_select_sql(schedule: DispatchSchedule, has_tenant_filter: bool) ->
         FROM channel_connections
         WHERE oauth_provider = 'youtube'
           AND content_owner_id IS NOT NULL
-          AND content_owner_id <> '';
+          AND content_owner_id <> ''
+          AND active = 1
+          AND needs_reauth = 0;
       "#
         }
         (_, true) => {
@@ -1358,7 +2065,9 @@ fn candidate_select_sql(schedule: DispatchSchedule, has_tenant_filter: bool) ->
         WHERE tenant_id = ?
           AND oauth_provider = 'youtube'
           AND channel_id IS NOT NULL
-          AND channel_id <> '';
+          AND channel_id <> ''
+          AND active = 1
+          AND needs_reauth = 0;
       "#
         }
         (_, false) => {
@@ -1367,7 +2076,9 @@ fn candidate_select_sql(schedule: DispatchSchedule, has_tenant_filter: bool) ->
         FROM channel_connections
         WHERE oauth_provider = 'youtube'
           AND channel_id IS NOT NULL
-          AND channel_id <> '';
+          AND channel_id <> ''
+          AND active = 1
+          AND needs_reauth = 0;
       "#
         }
     }
@@ -1382,8 +2093,18 @@ struct DispatchRequest {
     channel_id: Option<String>,
     #[serde(default)]
     run_for_dt: Option<String>,
+    /// Exact dates to enqueue; overrides the backfill/gap-scan derivation when present.
+    #[serde(default)]
+    run_for_dts: Option<Vec<String>>,
     #[serde(default)]
     backfill_weeks: Option<i64>,
+    /// Weeks backfilled for channels with no stored metrics; overrides `NEW_CHANNEL_BACKFILL_WEEKS`.
+    #[serde(default)]
+    new_channel_backfill_weeks: Option<i64>,
+    #[serde(default)]
+    reporting_backfill_days: Option<i64>,
+    #[serde(default)]
+    gap_scan_weeks: Option<i64>,
 }
 
 #[derive(Deserialize)]
@@ -1395,46 +2116,53 @@ struct TickRequest {
     tenant_id: Option<String>,
 }
 
-#[derive(Deserialize)]
-struct DecisionEngineConfigJson {
-    #[serde(default)]
-    min_days_with_data: Option<usize>,
-    #[serde(default)]
-    high_concentration_threshold: Option<f64>,
-    #[serde(default)]
-    trend_down_threshold_usd: Option<f64>,
-    #[serde(default)]
-    top_n_for_new_asset: Option<usize>,
-}
+const DEFAULT_GAP_SCAN_WEEKS: i64 = 4;
 
-fn default_policy_params_json(cfg: &DecisionEngineConfig) -> String {
-    serde_json::json!({
-      "min_days_with_data": cfg.min_days_with_data,
-      "high_concentration_threshold": cfg.high_concentration_threshold,
-      "trend_down_threshold_usd": cfg.trend_down_threshold_usd,
-      "top_n_for_new_asset": cfg.top_n_for_new_asset,
-    })
-    .to_string()
-}
+/// Looks for missing metric dates between the channel's first synced day (bounded by
+/// `gap_scan_weeks`) and the start of the regular daily window, and returns the gaps plus the
+/// extra daily_channel run_for_dts that refill them. Gaps an earlier refill task already fetched
+/// are left out.
+async fn detect_metric_gap_run_for_dts(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    run_for_dt: chrono::NaiveDate,
+    gap_scan_weeks: i64,
+) -> Result<Option<(Vec<chrono::NaiveDate>, Vec<chrono::NaiveDate>)>, Error> {
+    let scan_end_dt = run_for_dt - Duration::days(8);
+    let scan_start_dt = run_for_dt - Duration::days(gap_scan_weeks * 7);
+    if scan_start_dt > scan_end_dt {
+        return Ok(None);
+    }
 
-fn cfg_from_policy_params_json(raw: &str) -> Option<DecisionEngineConfig> {
-    let parsed: DecisionEngineConfigJson = serde_json::from_str(raw).ok()?;
-    let mut cfg = DecisionEngineConfig::default();
+    let present_dts: Vec<chrono::NaiveDate> = sqlx::query_scalar(
+        r#"
+      SELECT DISTINCT dt
+      FROM video_daily_metrics
+      WHERE tenant_id = ? AND channel_id = ?
+        AND dt BETWEEN ? AND ?;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(scan_start_dt)
+    .bind(scan_end_dt)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
 
-    if let Some(v) = parsed.min_days_with_data {
-        cfg.min_days_with_data = v;
-    }
-    if let Some(v) = parsed.high_concentration_threshold {
-        cfg.high_concentration_threshold = v;
-    }
-    if let Some(v) = parsed.trend_down_threshold_usd {
-        cfg.trend_down_threshold_usd = v;
-    }
-    if let Some(v) = parsed.top_n_for_new_asset {
-        cfg.top_n_for_new_asset = v;
-    }
+    // Don't report history from before the channel's first synced day as a gap.
+    let Some(first_dt) = present_dts.iter().min().copied() else {
+        return Ok(None);
+    };
 
-    Some(cfg)
+    let refilled = fetch_refilled_gap_dts(pool, tenant_id, channel_id, first_dt, scan_end_dt).await?;
+    let gaps = unrefilled_gaps(&find_date_gaps(&present_dts, first_dt, scan_end_dt), &refilled);
+    if gaps.is_empty() {
+        return Ok(None);
+    }
+    let gap_run_for_dts = gap_fill_run_for_dates(&gaps, run_for_dt - Duration::days(1));
+    Ok(Some((gaps, gap_run_for_dts)))
 }
 
 async fn handle_dispatch(
@@ -1502,6 +2230,19 @@ async fn handle_dispatch(
         })?
         .unwrap_or_else(|| now.date_naive());
 
+    let explicit_run_for_dts = match parsed.run_for_dts.as_deref() {
+        None => None,
+        Some(raw) => match explicit_run_for_dates(raw, now.date_naive(), MAX_EXPLICIT_RUN_FOR_DTS) {
+            Ok(v) => Some(v),
+            Err(message) => {
+                return json_response(
+                    StatusCode::BAD_REQUEST,
+                    serde_json::json!({"ok": false, "error": "bad_request", "message": message}),
+                )
+            }
+        },
+    };
+
     let pool = get_pool().await?;
 
     let tenant_filter = parsed
@@ -1581,157 +2322,545 @@ async fn handle_dispatch(
     };
 
     let job_type = schedule.job_type();
-    let mut enqueued: usize = 0;
-    let backfill_weeks = parsed.backfill_weeks.unwrap_or(0).clamp(0, 52);
 
-    for (tenant_id, channel_id) in channels.iter() {
-        let mut run_for_dts: Vec<chrono::NaiveDate> = vec![run_for_dt];
-
-        // First sync should backfill enough history for baseline comparisons + reports.
-        // Only do this when the channel has no metrics yet.
-        if schedule == DispatchSchedule::Daily {
-            if backfill_weeks > 1 {
-                // Insert newest first so the worker processes current data first (ORDER BY id ASC).
-                run_for_dts = (0..backfill_weeks)
-                    .map(|i| run_for_dt - Duration::days((i * 7) as i64))
-                    .collect();
-            } else {
-                let max_dt: Option<chrono::NaiveDate> = sqlx::query_scalar(
-                    r#"
-          SELECT MAX(dt) AS max_dt
-          FROM video_daily_metrics
-          WHERE tenant_id = ? AND channel_id = ?;
-        "#,
-                )
-                .bind(tenant_id)
-                .bind(channel_id)
-                .fetch_one(pool)
-                .await
-                .unwrap_or(None);
+    // A platform retry of the same cron lands here while the first dispatch is still running
+    // (or just finished); return the first's result instead of enqueueing again.
+    let params_hash = dispatch_params_hash(
+        &parsed,
+        parsed.run_for_dt.is_some().then_some(run_for_dt),
+        explicit_run_for_dts.as_deref(),
+        force,
+    );
+    let lock_key = dispatch_lock_key(job_type, tenant_filter.as_deref(), channel_filter.as_deref(), &params_hash);
+    let lock_holder = format!(
+        "{}:{}",
+        worker_id(),
+        Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    );
+    match try_acquire_dispatch_lock(pool, &lock_key, &lock_holder, Utc::now(), dispatch_lock_ttl_secs()).await? {
+        DispatchLockOutcome::Acquired => {}
+        DispatchLockOutcome::Held { result_json } => {
+            let previous = result_json
+                .as_deref()
+                .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok());
+            return json_response(
+                StatusCode::OK,
+                serde_json::json!({
+                  "ok": true,
+                  "skipped": true,
+                  "reason": if previous.is_some() { "dispatch_recently_completed" } else { "dispatch_in_progress" },
+                  "lock_key": lock_key,
+                  "previous": previous
+                }),
+            );
+        }
+    }
 
-                if max_dt.is_none() {
+    let mut enqueued: usize = 0;
+    let backfill_weeks = parsed.backfill_weeks.unwrap_or(0).clamp(0, 52);
+    let new_channel_backfill_weeks = configured_backfill_weeks(
+        parsed.new_channel_backfill_weeks,
+        std::env::var("NEW_CHANNEL_BACKFILL_WEEKS").ok().as_deref(),
+        DEFAULT_NEW_CHANNEL_BACKFILL_WEEKS,
+        1,
+    );
+    let reporting_backfill_days = if schedule == DispatchSchedule::YoutubeReporting {
+        Some(youtube_reporting_backfill_days(parsed.reporting_backfill_days))
+    } else {
+        None
+    };
+    let gap_scan_weeks = parsed
+        .gap_scan_weeks
+        .unwrap_or(DEFAULT_GAP_SCAN_WEEKS)
+        .clamp(0, 26);
+    let mut gap_reports: Vec<serde_json::Value> = Vec::new();
+    let mut gap_tasks_enqueued: usize = 0;
+    // Targeted and explicit-date runs are operator requests; only the sweep honors schedules.
+    let honor_sync_schedules = matches!(schedule, DispatchSchedule::Daily | DispatchSchedule::Weekly)
+        && channel_filter.is_none()
+        && explicit_run_for_dts.is_none();
+    let mut schedule_skipped: Vec<serde_json::Value> = Vec::new();
+
+    let enqueue_result: Result<(), Error> = async {
+        for (tenant_id, channel_id) in channels.iter() {
+            let mut run_for_dt = run_for_dt;
+            if honor_sync_schedules {
+                let sync_schedule = fetch_policy_params_json(pool, tenant_id, channel_id, "active")
+                    .await?
+                    .and_then(|raw| SyncSchedule::from_policy_params_json(&raw));
+                match scheduled_run_for_dt(sync_schedule.as_ref(), now, run_for_dt, parsed.run_for_dt.is_some()) {
+                    Some(dt) => run_for_dt = dt,
+                    None => {
+                        schedule_skipped.push(serde_json::json!({"tenant_id": tenant_id, "channel_id": channel_id}));
+                        continue;
+                    }
+                }
+            }
+            let mut run_for_dts: Vec<chrono::NaiveDate> = vec![run_for_dt];
+            let mut pending_gap_fills: Vec<chrono::NaiveDate> = Vec::new();
+
+            if let Some(explicit) = explicit_run_for_dts.as_ref() {
+                run_for_dts = explicit.clone();
+            } else if schedule == DispatchSchedule::Daily {
+                // First sync should backfill enough history for baseline comparisons + reports.
+                // Only do this when the channel has no metrics yet.
+                if backfill_weeks > 1 {
                     // Insert newest first so the worker processes current data first (ORDER BY id ASC).
-                    run_for_dts = (0..4)
+                    run_for_dts = (0..backfill_weeks)
                         .map(|i| run_for_dt - Duration::days((i * 7) as i64))
                         .collect();
+                } else {
+                    let max_dt: Option<chrono::NaiveDate> = sqlx::query_scalar(
+                        r#"
+              SELECT MAX(dt) AS max_dt
+              FROM video_daily_metrics
+              WHERE tenant_id = ? AND channel_id = ?;
+            "#,
+                    )
+                    .bind(tenant_id)
+                    .bind(channel_id)
+                    .fetch_one(pool)
+                    .await
+                    .unwrap_or(None);
+
+                    if max_dt.is_none() {
+                        // Insert newest first so the worker processes current data first (ORDER BY id ASC).
+                        run_for_dts = weekly_backfill_run_for_dts(run_for_dt, new_channel_backfill_weeks);
+                    } else if gap_scan_weeks > 0 {
+                        // Days missed while the worker was down fall outside the regular 7-day window.
+                        let gap_run_for_dts =
+                            detect_metric_gap_run_for_dts(pool, tenant_id, channel_id, run_for_dt, gap_scan_weeks)
+                                .await?;
+                        if let Some((gaps, gap_run_for_dts)) = gap_run_for_dts {
+                            gap_tasks_enqueued += gap_run_for_dts.len();
+                            gap_reports.push(serde_json::json!({
+                              "tenant_id": tenant_id,
+                              "channel_id": channel_id,
+                              "gap_dts": gaps.iter().map(|dt| dt.to_string()).collect::<Vec<_>>(),
+                              "run_for_dts": gap_run_for_dts.iter().map(|dt| dt.to_string()).collect::<Vec<_>>(),
+                            }));
+                            run_for_dts.extend(gap_run_for_dts);
+                            pending_gap_fills = gaps;
+                        }
+                    }
                 }
             }
+
+            let max_attempt = max_attempt_for_job_type(job_type);
+            for run_for_dt in run_for_dts.into_iter() {
+                enqueued += 1;
+                let dedupe_key = format!("{tenant_id}:{job_type}:{channel_id}:{run_for_dt}");
+
+                if force {
+                    sqlx::query(
+            r#"
+              INSERT INTO job_tasks (tenant_id, job_type, channel_id, run_for_dt, dedupe_key, status, attempt, max_attempt, run_after, backfill_days)
+              VALUES (?, ?, ?, ?, ?, 'pending', 0, ?, ?, ?)
+              ON DUPLICATE KEY UPDATE
+                updated_at = CURRENT_TIMESTAMP(3),
+                backfill_days = COALESCE(VALUES(backfill_days), backfill_days),
+                max_attempt = CASE
+                  WHEN max_attempt < VALUES(max_attempt) THEN VALUES(max_attempt)
+                  ELSE max_attempt
+                END,
+                run_after = CASE
+                  WHEN status = 'running' THEN run_after
+                  ELSE ?
+                END,
+                status = CASE
+                  WHEN status = 'running' THEN status
+                  ELSE 'pending'
+                END,
+                attempt = CASE
+                  WHEN status = 'running' THEN attempt
+                  ELSE 0
+                END,
+                last_error = CASE
+                  WHEN status = 'running' THEN last_error
+                  ELSE NULL
+                END,
+                locked_by = CASE
+                  WHEN status = 'running' THEN locked_by
+                  ELSE NULL
+                END,
+                locked_at = CASE
+                  WHEN status = 'running' THEN locked_at
+                  ELSE NULL
+                END;
+            "#,
+            )
+            .bind(tenant_id)
+            .bind(job_type)
+            .bind(channel_id)
+            .bind(run_for_dt)
+            .bind(dedupe_key)
+            .bind(max_attempt)
+            .bind(now)
+            .bind(reporting_backfill_days)
+            .bind(now)
+            .execute(pool)
+            .await
+            .map_err(|e| -> Error { Box::new(e) })?;
+                } else {
+                    sqlx::query(
+            r#"
+              INSERT INTO job_tasks (tenant_id, job_type, channel_id, run_for_dt, dedupe_key, status, attempt, max_attempt, run_after, backfill_days)
+              VALUES (?, ?, ?, ?, ?, 'pending', 0, ?, ?, ?)
+              ON DUPLICATE KEY UPDATE
+                updated_at = CURRENT_TIMESTAMP(3),
+                backfill_days = COALESCE(VALUES(backfill_days), backfill_days),
+                max_attempt = CASE
+                  WHEN max_attempt < VALUES(max_attempt) THEN VALUES(max_attempt)
+                  ELSE max_attempt
+                END,
+                attempt = CASE
+                  WHEN status = 'dead' THEN 0
+                  ELSE attempt
+                END,
+                last_error = CASE
+                  WHEN status = 'dead' THEN NULL
+                  ELSE last_error
+                END,
+                locked_by = CASE
+                  WHEN status = 'dead' THEN NULL
+                  ELSE locked_by
+                END,
+                locked_at = CASE
+                  WHEN status = 'dead' THEN NULL
+                  ELSE locked_at
+                END,
+                run_after = CASE
+                  WHEN status IN ('pending','retrying','dead') THEN ?
+                  ELSE run_after
+                END,
+                status = CASE
+                  WHEN status = 'dead' THEN 'pending'
+                  ELSE status
+                END;
+            "#,
+            )
+            .bind(tenant_id)
+            .bind(job_type)
+            .bind(channel_id)
+            .bind(run_for_dt)
+            .bind(dedupe_key)
+            .bind(max_attempt)
+            .bind(now)
+            .bind(reporting_backfill_days)
+            .bind(now)
+            .execute(pool)
+            .await
+            .map_err(|e| -> Error { Box::new(e) })?;
+                }
+            }
+            record_gap_fill_attempts(pool, tenant_id, channel_id, &pending_gap_fills).await?;
         }
+        Ok(())
+    }
+    .await;
 
-        for run_for_dt in run_for_dts.into_iter() {
-            enqueued += 1;
-            let dedupe_key = format!("{tenant_id}:{job_type}:{channel_id}:{run_for_dt}");
+    if let Err(err) = enqueue_result {
+        let _ = release_dispatch_lock(pool, &lock_key, &lock_holder).await;
+        return Err(err);
+    }
 
-            if force {
-                sqlx::query(
+    let result = serde_json::json!({
+      "ok": true,
+      "tenant_id": tenant_filter,
+      "job_type": job_type,
+      "run_for_dt": run_for_dt.to_string(),
+      "run_for_dts": explicit_run_for_dts.as_ref().map(|dts| dts.iter().map(|dt| dt.to_string()).collect::<Vec<_>>()),
+      "force": force,
+      "candidates": channels.len(),
+      "enqueued": enqueued,
+      "reporting_backfill_days": reporting_backfill_days,
+      "new_channel_backfill_weeks": if schedule == DispatchSchedule::Daily { Some(new_channel_backfill_weeks) } else { None },
+      "gap_scan_weeks": if schedule == DispatchSchedule::Daily { Some(gap_scan_weeks) } else { None },
+      "gap_tasks_enqueued": gap_tasks_enqueued,
+      "gaps": gap_reports,
+      "schedule_skipped": schedule_skipped
+    });
+    complete_dispatch_lock(pool, &lock_key, &lock_holder, &result.to_string()).await?;
+
+    json_response(StatusCode::OK, result)
+}
+
+/// Job types the tick executes; each gets its own stale-lock cutoff when reclaiming.
+const TASK_JOB_TYPES: &[&str] = &[
+    "daily_channel",
+    "weekly_channel",
+    "evidence_retention",
+    "geo_monitor_prompt",
+    "youtube_reporting_owner",
+    "youtube_reporting_report",
+];
+
+/// `locked_at` cutoff per known job type, plus the global cutoff for anything else.
+fn stale_lock_cutoffs(
+    now: DateTime<Utc>,
+    ttl_secs: impl Fn(Option<&str>) -> i64,
+) -> (Vec<(&'static str, DateTime<Utc>)>, DateTime<Utc>) {
+    let per_type = TASK_JOB_TYPES
+        .iter()
+        .map(|job_type| (*job_type, now - Duration::seconds(ttl_secs(Some(job_type)))))
+        .collect();
+    (per_type, now - Duration::seconds(ttl_secs(None)))
+}
+
+/// Binds: `run_after`, the tenant (when filtered), a `(job_type, cutoff)` pair per type, then the
+/// default cutoff.
+fn reclaim_stale_tasks_sql(has_tenant_filter: bool, job_type_count: usize) -> String {
+    let tenant_clause = if has_tenant_filter { "tenant_id = ? AND " } else { "" };
+    let whens = " WHEN ? THEN ?".repeat(job_type_count);
+    format!(
+        "UPDATE job_tasks \
+         SET status='retrying', run_after=?, locked_by=NULL, locked_at=NULL \
+         WHERE {tenant_clause}status='running' \
+           AND locked_at IS NOT NULL \
+           AND locked_at < CASE job_type{whens} ELSE ? END;"
+    )
+}
+
+/// Report types one youtube_reporting_owner task ingests at once. Each type makes its own
+/// jobs/reports listing calls, so the cap keeps a large owner within Reporting API quota.
+const YOUTUBE_REPORTING_TYPE_CONCURRENCY_DEFAULT: usize = 2;
+const YOUTUBE_REPORTING_TYPE_CONCURRENCY_MAX: usize = 4;
+
+/// `YOUTUBE_REPORTING_TYPE_CONCURRENCY` (default 2, clamped to 1..=4).
+fn youtube_reporting_type_concurrency(value: Option<&str>) -> usize {
+    value
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .unwrap_or(YOUTUBE_REPORTING_TYPE_CONCURRENCY_DEFAULT)
+        .clamp(1, YOUTUBE_REPORTING_TYPE_CONCURRENCY_MAX)
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct OwnerReportTypesSummary {
+    processed: usize,
+    reports_queued: usize,
+    /// `(report_type_id, error)` for types that failed; the other types still ran.
+    failed: Vec<(String, String)>,
+}
+
+async fn ingest_owner_report_types<F, Fut>(
+    report_types: Vec<YoutubeReportingReportType>,
+    concurrency: usize,
+    ingest: F,
+) -> OwnerReportTypesSummary
+where
+    F: Fn(YoutubeReportingReportType) -> Fut,
+    Fut: std::future::Future<Output = Result<usize, Error>>,
+{
+    let results = run_bounded_isolated(report_types, concurrency, |rt| {
+        let report_type_id = rt.report_type_id.clone();
+        let ingested = ingest(rt);
+        async move { (report_type_id, ingested.await) }
+    })
+    .await;
+
+    let mut summary = OwnerReportTypesSummary::default();
+    for (report_type_id, result) in results {
+        match result {
+            Ok(queued) => {
+                summary.processed += 1;
+                summary.reports_queued += queued;
+            }
+            Err(err) => summary.failed.push((report_type_id, err.to_string())),
+        }
+    }
+    summary
+}
+
+/// Registers the report type, ensures its reporting job, and records and queues the reports
+/// created after `created_after`. Returns how many reports were queued.
+async fn ingest_owner_report_type(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    content_owner_id: &str,
+    access_token: &str,
+    rt: &YoutubeReportingReportType,
+    created_after: &str,
+    run_for_dt: NaiveDate,
+) -> Result<usize, Error> {
+    let system_managed = if rt.system_managed { 1i8 } else { 0i8 };
+    sqlx::query(
         r#"
-          INSERT INTO job_tasks (tenant_id, job_type, channel_id, run_for_dt, dedupe_key, status, attempt, max_attempt, run_after)
-          VALUES (?, ?, ?, ?, ?, 'pending', 0, 3, ?)
-          ON DUPLICATE KEY UPDATE
-            updated_at = CURRENT_TIMESTAMP(3),
-            max_attempt = CASE
-              WHEN max_attempt < 3 THEN 3
-              ELSE max_attempt
-            END,
-            run_after = CASE
-              WHEN status = 'running' THEN run_after
-              ELSE ?
-            END,
-            status = CASE
-              WHEN status = 'running' THEN status
-              ELSE 'pending'
-            END,
-            attempt = CASE
-              WHEN status = 'running' THEN attempt
-              ELSE 0
-            END,
-            last_error = CASE
-              WHEN status = 'running' THEN last_error
-              ELSE NULL
-            END,
-            locked_by = CASE
-              WHEN status = 'running' THEN locked_by
-              ELSE NULL
-            END,
-            locked_at = CASE
-              WHEN status = 'running' THEN locked_at
-              ELSE NULL
-            END;
+            INSERT INTO yt_reporting_report_types
+                (content_owner_id, report_type_id, report_type_name, system_managed)
+            VALUES
+                (?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                report_type_name = VALUES(report_type_name),
+                system_managed = VALUES(system_managed),
+                updated_at = CURRENT_TIMESTAMP(3);
         "#,
+    )
+    .bind(content_owner_id)
+    .bind(&rt.report_type_id)
+    .bind(rt.report_type_name.as_deref())
+    .bind(system_managed)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    let job_id = ensure_job_for_report_type(access_token, content_owner_id, &rt.report_type_id)
+        .await
+        .map_err(|e| -> Error { Box::new(std::io::Error::other(format!("ensure_job failed: {e}"))) })?;
+
+    sqlx::query(
+        r#"
+            INSERT INTO yt_reporting_jobs
+                (tenant_id, content_owner_id, report_type_id, job_id)
+            VALUES
+                (?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                job_id = VALUES(job_id),
+                updated_at = CURRENT_TIMESTAMP(3);
+        "#,
+    )
+    .bind(tenant_id)
+    .bind(content_owner_id)
+    .bind(&rt.report_type_id)
+    .bind(&job_id)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    let reports = list_reports(access_token, &job_id, content_owner_id, Some(created_after))
+        .await
+        .map_err(|e| -> Error {
+            Box::new(std::io::Error::other(format!("list_reports failed for job_id={job_id}: {e}")))
+        })?;
+    let reports_queued = reports.len();
+
+    for rep in reports {
+        let start_time = parse_rfc3339_utc(rep.start_time.as_deref());
+        let end_time = parse_rfc3339_utc(rep.end_time.as_deref());
+        let create_time = parse_rfc3339_utc(rep.create_time.as_deref());
+
+        sqlx::query(
+            r#"
+                INSERT INTO yt_reporting_report_files
+                    (tenant_id, content_owner_id, report_type_id, job_id, report_id, download_url, start_time, end_time, create_time)
+                VALUES
+                    (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON DUPLICATE KEY UPDATE
+                    download_url = COALESCE(VALUES(download_url), download_url),
+                    start_time = COALESCE(VALUES(start_time), start_time),
+                    end_time = COALESCE(VALUES(end_time), end_time),
+                    create_time = COALESCE(VALUES(create_time), create_time),
+                    updated_at = CURRENT_TIMESTAMP(3);
+            "#,
         )
         .bind(tenant_id)
-        .bind(job_type)
-        .bind(channel_id)
-        .bind(run_for_dt)
-        .bind(dedupe_key)
-        .bind(now)
-        .bind(now)
+        .bind(content_owner_id)
+        .bind(&rt.report_type_id)
+        .bind(&job_id)
+        .bind(&rep.report_id)
+        .bind(rep.download_url.as_deref())
+        .bind(start_time)
+        .bind(end_time)
+        .bind(create_time)
         .execute(pool)
         .await
         .map_err(|e| -> Error { Box::new(e) })?;
-            } else {
-                sqlx::query(
-        r#"
-          INSERT INTO job_tasks (tenant_id, job_type, channel_id, run_for_dt, dedupe_key, status, attempt, max_attempt, run_after)
-          VALUES (?, ?, ?, ?, ?, 'pending', 0, 3, ?)
-          ON DUPLICATE KEY UPDATE
-            updated_at = CURRENT_TIMESTAMP(3),
-            max_attempt = CASE
-              WHEN max_attempt < 3 THEN 3
-              ELSE max_attempt
-            END,
-            attempt = CASE
-              WHEN status = 'dead' THEN 0
-              ELSE attempt
-            END,
-            last_error = CASE
-              WHEN status = 'dead' THEN NULL
-              ELSE last_error
-            END,
-            locked_by = CASE
-              WHEN status = 'dead' THEN NULL
-              ELSE locked_by
-            END,
-            locked_at = CASE
-              WHEN status = 'dead' THEN NULL
-              ELSE locked_at
-            END,
-            run_after = CASE
-              WHEN status IN ('pending','retrying','dead') THEN ?
-              ELSE run_after
-            END,
-            status = CASE
-              WHEN status = 'dead' THEN 'pending'
-              ELSE status
-            END;
-        "#,
+
+        let task_channel_id = format!("{content_owner_id}:{}", rep.report_id);
+        let dedupe_key = format!(
+            "{tenant_id}:youtube_reporting_report:{content_owner_id}:{}",
+            rep.report_id
+        );
+        sqlx::query(
+            r#"
+                INSERT INTO job_tasks (tenant_id, job_type, channel_id, run_for_dt, dedupe_key, status, max_attempt)
+                VALUES (?, 'youtube_reporting_report', ?, ?, ?, 'pending', ?)
+                ON DUPLICATE KEY UPDATE updated_at = CURRENT_TIMESTAMP(3);
+            "#,
         )
         .bind(tenant_id)
-        .bind(job_type)
-        .bind(channel_id)
+        .bind(task_channel_id)
         .bind(run_for_dt)
         .bind(dedupe_key)
-        .bind(now)
-        .bind(now)
+        .bind(max_attempt_for_job_type("youtube_reporting_report"))
         .execute(pool)
         .await
         .map_err(|e| -> Error { Box::new(e) })?;
-            }
-        }
     }
 
-    json_response(
-        StatusCode::OK,
-        serde_json::json!({
-          "ok": true,
-          "tenant_id": tenant_filter,
-          "job_type": job_type,
-          "run_for_dt": run_for_dt.to_string(),
-          "force": force,
-          "candidates": channels.len(),
-          "enqueued": enqueued
-        }),
+    Ok(reports_queued)
+}
+
+/// Provisional outcomes re-checked per daily_channel run.
+const PROVISIONAL_OUTCOME_RECHECK_LIMIT: i64 = 14;
+
+/// Computes and upserts the outcome of the decision at `windows.decision_dt`. The outcome is
+/// stored as provisional while the post window is missing days.
+async fn store_decision_outcome(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    cfg: &DecisionEngineConfig,
+    windows: OutcomeWindows,
+    outcome_dt: NaiveDate,
+) -> Result<(), Error> {
+    let pre_start_dt = windows.pre_start_dt;
+    let pre_end_dt = windows.pre_end_dt;
+    let post_start_dt = windows.post_start_dt;
+    let post_end_dt = windows.post_end_dt;
+
+    let pre_sum = fetch_revenue_sum_usd_7d(pool, tenant_id, channel_id, pre_start_dt, pre_end_dt).await?;
+    let post_sum = fetch_revenue_sum_usd_7d(pool, tenant_id, channel_id, post_start_dt, post_end_dt).await?;
+    let post_days_with_data =
+        fetch_days_with_data(pool, tenant_id, channel_id, post_start_dt, post_end_dt).await?;
+    let provisional = windows.is_provisional(post_days_with_data);
+
+    let top_n = match cfg.outcome_top_n {
+        Some(n) => (n as i64).clamp(1, 50),
+        None => (cfg.top_n_for_new_asset as i64).clamp(1, 10),
+    };
+    let pre_top =
+        fetch_top_video_ids_by_revenue(pool, tenant_id, channel_id, pre_start_dt, pre_end_dt, top_n).await?;
+    let post_top =
+        fetch_top_videos_with_revenue(pool, tenant_id, channel_id, post_start_dt, post_end_dt, top_n).await?;
+
+    let outcome_cfg = OutcomeLabelConfig {
+        catastrophic_drop_pct: cfg.catastrophic_drop_pct,
+        new_asset_top_k: cfg.new_asset_top_k,
+        new_asset_min_revenue_share: cfg.new_asset_min_revenue_share,
+    };
+    let outcome = compute_outcome_label(pre_sum, post_sum, &pre_top, &post_top, &outcome_cfg);
+    let notes = serde_json::json!({
+        "pre_window": { "start_dt": pre_start_dt.to_string(), "end_dt": pre_end_dt.to_string(), "revenue_sum_usd_7d": pre_sum },
+        "post_window": {
+            "start_dt": post_start_dt.to_string(),
+            "end_dt": post_end_dt.to_string(),
+            "revenue_sum_usd_7d": post_sum,
+            "days_with_data": post_days_with_data,
+        },
+        "top_n": top_n,
+        "catastrophic_drop_pct": outcome_cfg.catastrophic_drop_pct,
+        "new_asset_top_k": outcome_cfg.new_asset_top_k,
+        "new_asset_min_revenue_share": outcome_cfg.new_asset_min_revenue_share,
+        "provisional": provisional,
+    })
+    .to_string();
+
+    upsert_decision_outcome(
+        pool,
+        tenant_id,
+        channel_id,
+        windows.decision_dt,
+        outcome_dt,
+        outcome.revenue_change_pct_7d,
+        outcome.catastrophic_flag,
+        outcome.new_top_asset_flag,
+        provisional,
+        windows.post_window_days(),
+        Some(&notes),
     )
+    .await?;
+
+    Ok(())
 }
 
 async fn handle_tick(
@@ -1794,46 +2923,21 @@ async fn handle_tick(
         .unwrap_or_else(Utc::now);
     let pool = get_pool().await?;
 
-    let lock_ttl_secs: i64 = std::env::var("JOB_TASK_LOCK_TTL_SECS")
-        .ok()
-        .and_then(|v| v.parse().ok())
-        .unwrap_or(600)
-        .clamp(60, 3600);
-    let stale_before = now - Duration::seconds(lock_ttl_secs);
-
-    let reclaimed = if let Some(tenant_id) = tenant_filter {
-        sqlx::query(
-            r#"
-        UPDATE job_tasks
-        SET status='retrying', run_after=?, locked_by=NULL, locked_at=NULL
-        WHERE tenant_id = ?
-          AND status='running'
-          AND locked_at IS NOT NULL
-          AND locked_at < ?;
-      "#,
-        )
-        .bind(now)
-        .bind(tenant_id)
-        .bind(stale_before)
-        .execute(pool)
-        .await
-        .map_err(|e| -> Error { Box::new(e) })?
-        .rows_affected()
-    } else {
-        sqlx::query(
-            r#"
-        UPDATE job_tasks
-        SET status='retrying', run_after=?, locked_by=NULL, locked_at=NULL
-        WHERE status='running' AND locked_at IS NOT NULL AND locked_at < ?;
-      "#,
-        )
-        .bind(now)
-        .bind(stale_before)
+    let (type_cutoffs, default_cutoff) = stale_lock_cutoffs(now, job_task_lock_ttl_secs);
+    let reclaim_sql = reclaim_stale_tasks_sql(tenant_filter.is_some(), type_cutoffs.len());
+    let mut reclaim = sqlx::query(&reclaim_sql).bind(now);
+    if let Some(tenant_id) = tenant_filter {
+        reclaim = reclaim.bind(tenant_id);
+    }
+    for (job_type, cutoff) in &type_cutoffs {
+        reclaim = reclaim.bind(*job_type).bind(*cutoff);
+    }
+    let reclaimed = reclaim
+        .bind(default_cutoff)
         .execute(pool)
         .await
         .map_err(|e| -> Error { Box::new(e) })?
-        .rows_affected()
-    };
+        .rows_affected();
 
     let worker_id = worker_id();
 
@@ -1846,10 +2950,11 @@ async fn handle_tick(
         Option<chrono::NaiveDate>,
         i32,
         i32,
+        Option<i32>,
     )> = if let Some(tenant_id) = tenant_filter {
         sqlx::query_as(
             r#"
-          SELECT id, tenant_id, job_type, channel_id, run_for_dt, attempt, max_attempt
+          SELECT id, tenant_id, job_type, channel_id, run_for_dt, attempt, max_attempt, backfill_days
           FROM job_tasks
           WHERE tenant_id = ?
             AND status IN ('pending','retrying')
@@ -1868,7 +2973,7 @@ async fn handle_tick(
     } else {
         sqlx::query_as(
             r#"
-          SELECT id, tenant_id, job_type, channel_id, run_for_dt, attempt, max_attempt
+          SELECT id, tenant_id, job_type, channel_id, run_for_dt, attempt, max_attempt, backfill_days
           FROM job_tasks
           WHERE status IN ('pending','retrying')
             AND run_after <= ?
@@ -1884,7 +2989,7 @@ async fn handle_tick(
         .map_err(|e| -> Error { Box::new(e) })?
     };
 
-    for (id, _tenant_id, _job_type, _channel_id, _run_for_dt, _attempt, _max_attempt) in
+    for (id, _tenant_id, _job_type, _channel_id, _run_for_dt, _attempt, _max_attempt, _backfill_days) in
         claimed.iter()
     {
         sqlx::query(
@@ -1909,7 +3014,9 @@ async fn handle_tick(
     let mut dead = 0usize;
     let mut last_error: Option<String> = None;
 
-    for (id, tenant_id, job_type, channel_id, run_for_dt, attempt, max_attempt) in claimed.iter() {
+    for (id, tenant_id, job_type, channel_id, run_for_dt, attempt, max_attempt, backfill_days) in
+        claimed.iter()
+    {
         let attempt_next = attempt.saturating_add(1);
 
         let result: Result<(), Error> = match job_type.as_str() {
@@ -1933,145 +3040,64 @@ async fn handle_tick(
                         )) as Error
                     })?;
 
-                    let project = fetch_geo_monitor_project(pool, tenant_id, project_id)
-                        .await?
-                        .ok_or_else(|| {
-                            Box::new(std::io::Error::other("missing geo monitor project")) as Error
-                        })?;
+                    let ctx = geo_monitor_run_context(pool, tenant_id, project_id, run_for_dt).await?;
                     let prompt = fetch_geo_monitor_prompt(pool, tenant_id, project_id, prompt_id)
                         .await?
                         .ok_or_else(|| {
                             Box::new(std::io::Error::other("missing geo monitor prompt")) as Error
                         })?;
 
-                    let prompt_total: i32 = sqlx::query_scalar(
-                        r#"
-              SELECT COUNT(*) FROM geo_monitor_prompts
-              WHERE tenant_id = ? AND project_id = ? AND enabled = 1;
-            "#,
-                    )
-                    .bind(tenant_id)
-                    .bind(project_id)
-                    .fetch_one(pool)
-                    .await
-                    .map_err(|e| -> Error { Box::new(e) })?;
-
-                    let resolved = resolve_ai_runtime(pool, tenant_id).await?;
-                    let provider = resolved.provider.clone();
-                    let model = resolved.model.clone();
-
-                    let run = ensure_geo_monitor_run(
-                        pool,
-                        tenant_id,
-                        project_id,
-                        run_for_dt,
-                        &provider,
-                        &model,
-                        prompt_total,
-                    )
-                    .await?;
-
-                    let aliases = parse_string_list_json(project.brand_aliases_json.as_deref());
-                    let needles = normalize_aliases(&project.name, aliases.as_slice());
-
-                    let system = "You are a helpful assistant.";
-                    let temperature = 0.2;
-                    let max_output_tokens: u32 = 1024;
-
-                    let idempotency_key = format!(
-                        "{tenant_id}:geo_monitor_prompt:{project_id}:{run_for_dt}:{prompt_id}"
-                    );
-
-                    let pricing = pricing_for_resolved_runtime(&resolved);
+                    process_geo_monitor_prompt(pool, tenant_id, run_for_dt, &ctx, &prompt).await?;
+                    let _ = finalize_geo_monitor_run_if_complete(pool, ctx.run_id).await?;
+                    Ok(())
+                })()
+                .await
+            }
+            GEO_MONITOR_PROMPT_BATCH_JOB_TYPE => {
+                async {
+                    let run_for_dt = run_for_dt.ok_or_else(|| {
+                        Box::new(std::io::Error::other(
+                            "geo_monitor_prompt_batch task missing run_for_dt",
+                        )) as Error
+                    })?;
+                    let (project_id, batch_size, batch_index) =
+                        parse_geo_monitor_batch_channel_id(channel_id).ok_or_else(|| {
+                            Box::new(std::io::Error::other(
+                                "geo_monitor_prompt_batch invalid channel_id",
+                            )) as Error
+                        })?;
 
-                    match generate_text_for_runtime(
-                        &resolved,
-                        system,
-                        &prompt.prompt_text,
-                        temperature,
-                        max_output_tokens,
-                        Some(&idempotency_key),
-                    )
-                    .await
-                    {
-                        Ok((text, usage)) => {
-                            let presence = contains_any_case_insensitive(&text, needles.as_slice());
-                            let rank = extract_rank_from_markdown_list(&text, needles.as_slice());
-
-                            let cost_usd = pricing
-                                .map(|p| {
-                                    compute_cost_usd(
-                                        p,
-                                        usage.prompt_tokens as u32,
-                                        usage.completion_tokens as u32,
-                                    )
-                                })
-                                .unwrap_or(0.0);
-
-                            if let Err(err) = insert_usage_event(
-                                pool,
-                                tenant_id,
-                                "geo_monitor_prompt",
-                                &idempotency_key,
-                                &provider,
-                                &model,
-                                usage.prompt_tokens,
-                                usage.completion_tokens,
-                                cost_usd,
-                            )
-                            .await
-                            {
-                                if err
-                                    .as_database_error()
-                                    .is_some_and(|e| e.is_unique_violation())
-                                {
-                                    // idempotent replay: ignore
-                                } else {
-                                    return Err(Box::new(err) as Error);
-                                }
+                    let ctx = geo_monitor_run_context(pool, tenant_id, project_id, run_for_dt).await?;
+                    let prompts: std::collections::HashMap<i64, GeoMonitorPromptRow> =
+                        list_geo_monitor_prompts(pool, tenant_id, project_id)
+                            .await?
+                            .into_iter()
+                            .filter(|p| p.enabled)
+                            .map(|p| (p.id, p))
+                            .collect();
+                    let prompt_ids: Vec<i64> = prompts.keys().copied().collect();
+                    let batch_ids = geo_monitor_batch_prompt_ids(&prompt_ids, batch_size, batch_index);
+                    let recorded: std::collections::HashSet<i64> =
+                        fetch_geo_monitor_run_result_prompt_ids(pool, ctx.run_id)
+                            .await?
+                            .into_iter()
+                            .collect();
+
+                    let ctx = &ctx;
+                    let prompts = &prompts;
+                    run_geo_monitor_prompt_batch(&batch_ids, &recorded, move |prompt_id| async move {
+                        match prompts.get(&prompt_id) {
+                            Some(prompt) => {
+                                process_geo_monitor_prompt(pool, tenant_id, run_for_dt, ctx, prompt).await
                             }
-
-                            let _ = insert_geo_monitor_run_result(
-                                pool,
-                                tenant_id,
-                                project_id,
-                                run_for_dt,
-                                run.id,
-                                prompt_id,
-                                &prompt.prompt_text,
-                                Some(&text),
-                                presence,
-                                rank,
-                                cost_usd,
-                                None,
-                            )
-                            .await?;
-                            let _ = finalize_geo_monitor_run_if_complete(pool, run.id).await?;
-
-                            Ok(())
+                            None => Ok(()),
                         }
-                        Err(err) => {
-                            let msg = truncate_string(&err.to_string(), 2000);
-                            let _ = insert_geo_monitor_run_result(
-                                pool,
-                                tenant_id,
-                                project_id,
-                                run_for_dt,
-                                run.id,
-                                prompt_id,
-                                &prompt.prompt_text,
-                                None,
-                                false,
-                                None,
-                                0.0,
-                                Some(&msg),
-                            )
-                            .await?;
-                            let _ = finalize_geo_monitor_run_if_complete(pool, run.id).await?;
-                            Ok(())
-                        }
-                    }
-                })()
+                    })
+                    .await?;
+
+                    let _ = finalize_geo_monitor_run_if_complete(pool, ctx.run_id).await?;
+                    Ok(())
+                }
                 .await
             }
             "daily_channel" => {
@@ -2095,89 +3121,72 @@ async fn handle_tick(
           let active_params_json = fetch_policy_params_json(pool, tenant_id, channel_id, "active").await?;
           let cfg = active_params_json
             .as_deref()
-            .and_then(cfg_from_policy_params_json)
+            .and_then(DecisionEngineConfig::from_policy_params_json)
             .unwrap_or_else(DecisionEngineConfig::default);
 
           if active_params_json.is_none() {
-            let params_json = default_policy_params_json(&active_cfg_default);
+            let params_json = active_cfg_default.to_policy_params_value().to_string();
             upsert_policy_params(pool, tenant_id, channel_id, "active", &params_json, "system").await?;
           }
 
-          // Proactive refresh if expired (best-effort).
-          let now_dt = now;
-          let needs_refresh = tokens
-            .expires_at
-            .map(|t| t <= now_dt)
-            .unwrap_or(false);
-
-          if needs_refresh {
-            if let Some(refresh) = tokens.refresh_token.clone() {
-              let app = fetch_or_seed_youtube_oauth_app_config(pool, tenant_id)
-                .await?
-                .ok_or_else(|| Box::new(std::io::Error::other("missing youtube oauth app config")) as Error)?;
-              let client_secret = app
-                .client_secret
-                .as_deref()
-                .map(str::trim)
-                .filter(|v| !v.is_empty())
-                .ok_or_else(|| {
-                  Box::new(std::io::Error::other("missing youtube oauth client_secret")) as Error
-                })?;
-              let (client, _redirect) =
-                youtube_oauth_client_from_config(&app.client_id, client_secret, &app.redirect_uri)?;
-              let refreshed = refresh_tokens(&client, &refresh).await?;
-              update_youtube_connection_tokens(pool, tenant_id, channel_id, &refreshed).await?;
-              tokens.access_token = refreshed.access_token;
-              tokens.refresh_token = refreshed.refresh_token.or(Some(refresh));
+          // Only re-fetch from the last synced day (minus a small overlap) when the watermark is
+          // inside this window; the rest of the window is already stored.
+          let last_synced_dt = fetch_youtube_last_synced_dt(pool, tenant_id, channel_id).await?;
+          let fetch_start_dt =
+            incremental_fetch_start(start_dt, end_dt, last_synced_dt, SYNC_OVERLAP_DAYS);
+
+          // Proactive refresh if expired (best-effort), then one refresh + retry on 401.
+          refresh_youtube_tokens_if_expired(pool, tenant_id, channel_id, &mut tokens).await?;
+          let ChannelDailyMetricsFetch { rows: fetched, silent_empty } = call_with_fresh_youtube_token(
+            pool,
+            tenant_id,
+            channel_id,
+            &mut tokens,
+            |err: &YoutubeAnalyticsError| err.status == Some(401),
+            |access_token| async move {
+              fetch_channel_daily_metrics_checked(&access_token, channel_id, fetch_start_dt, end_dt)
+                .await
+            },
+          )
+          .await?
+          .map_err(youtube_analytics_error_to_vercel_error)?;
+
+          run_bounded_chunks(
+            fetched.chunks(METRIC_UPSERT_BATCH_SIZE).collect(),
+            upsert_concurrency(),
+            |chunk| upsert_video_daily_metrics_batch(pool, tenant_id, channel_id, chunk),
+          )
+          .await?;
+          // An empty 200 from a channel that has views is missing data, not a quiet week: keep the
+          // watermark so the window is fetched again next run, and leave a note why.
+          match silent_empty {
+            Some(report) => {
+              let details_json = silent_empty_alert_details(fetch_start_dt, end_dt, &report);
+              upsert_alert(
+                pool,
+                tenant_id,
+                channel_id,
+                ANALYTICS_SILENT_EMPTY_ALERT_KEY,
+                "Data sync",
+                "warning",
+                "YouTube Analytics returned no daily rows although the channel has views; the window will be re-fetched.",
+                Some(&details_json),
+              )
+              .await?;
+            }
+            None => {
+              advance_youtube_last_synced_dt(pool, tenant_id, channel_id, end_dt).await?;
+              mark_gap_fills_refilled(pool, tenant_id, channel_id, fetch_start_dt, end_dt).await?;
+              resolve_open_alert(pool, tenant_id, channel_id, ANALYTICS_SILENT_EMPTY_ALERT_KEY).await?;
             }
           }
 
-          let metrics = match fetch_video_daily_metrics_for_channel(&tokens.access_token, channel_id, start_dt, end_dt).await {
-            Ok(rows) => rows,
-            Err(err) if err.status == Some(401) => {
-              if let Some(refresh) = tokens.refresh_token.clone() {
-                let app = fetch_or_seed_youtube_oauth_app_config(pool, tenant_id)
-                  .await?
-                  .ok_or_else(|| Box::new(std::io::Error::other("missing youtube oauth app config")) as Error)?;
-                let client_secret = app
-                  .client_secret
-                  .as_deref()
-                  .map(str::trim)
-                  .filter(|v| !v.is_empty())
-                  .ok_or_else(|| {
-                    Box::new(std::io::Error::other("missing youtube oauth client_secret")) as Error
-                  })?;
-                let (client, _redirect) =
-                  youtube_oauth_client_from_config(&app.client_id, client_secret, &app.redirect_uri)?;
-                let refreshed = refresh_tokens(&client, &refresh).await?;
-                update_youtube_connection_tokens(pool, tenant_id, channel_id, &refreshed).await?;
-                tokens.access_token = refreshed.access_token;
-
-                fetch_video_daily_metrics_for_channel(&tokens.access_token, channel_id, start_dt, end_dt)
-                  .await
-                  .map_err(youtube_analytics_error_to_vercel_error)?
-              } else {
-                return Err(youtube_analytics_error_to_vercel_error(err));
-              }
-            }
-            Err(err) => return Err(youtube_analytics_error_to_vercel_error(err)),
+          let metrics = if fetch_start_dt > start_dt {
+            fetch_video_daily_metric_rows(pool, tenant_id, channel_id, start_dt, end_dt).await?
+          } else {
+            fetched
           };
 
-          for row in metrics.iter() {
-            upsert_video_daily_metric(
-              pool,
-              tenant_id,
-              channel_id,
-              row.dt,
-              &row.video_id,
-              row.estimated_revenue_usd,
-              row.impressions,
-              row.impressions_ctr,
-              row.views,
-            )
-            .await?;
-          }
-
           // Reach metrics (impressions/CTR) are only available via the YouTube Reporting API bulk reports.
           // We intentionally ingest reach only for the "current daily run" (not each backfill task) to:
           // - avoid hammering the Reporting API during initial backfills
@@ -2358,50 +3367,34 @@ async fn handle_tick(
           .await
           .map_err(|e| -> Error { Box::new(e) })?;
 
-          let decision_dt = run_for_dt - chrono::Duration::days(7);
+          let windows = outcome_windows(run_for_dt, cfg.outcome_window_days);
+          let decision_dt = windows.decision_dt;
           if decision_daily_exists(pool, tenant_id, channel_id, decision_dt).await? {
-            let pre_start_dt = decision_dt - chrono::Duration::days(7);
-            let pre_end_dt = decision_dt - chrono::Duration::days(1);
-            let post_start_dt = decision_dt;
-            let post_end_dt = decision_dt + chrono::Duration::days(6);
-
-            let pre_sum =
-              fetch_revenue_sum_usd_7d(pool, tenant_id, channel_id, pre_start_dt, pre_end_dt).await?;
-            let post_sum = fetch_revenue_sum_usd_7d(
-              pool,
-              tenant_id,
-              channel_id,
-              post_start_dt,
-              post_end_dt,
-            )
-            .await?;
-
-            let top_n = (cfg.top_n_for_new_asset as i64).clamp(1, 10);
-            let pre_top =
-              fetch_top_video_ids_by_revenue(pool, tenant_id, channel_id, pre_start_dt, pre_end_dt, top_n).await?;
-            let post_top =
-              fetch_top_video_ids_by_revenue(pool, tenant_id, channel_id, post_start_dt, post_end_dt, top_n).await?;
-
-            let outcome = compute_outcome_label(pre_sum, post_sum, &pre_top, &post_top);
-            let notes = serde_json::json!({
-              "pre_window": { "start_dt": pre_start_dt.to_string(), "end_dt": pre_end_dt.to_string(), "revenue_sum_usd_7d": pre_sum },
-              "post_window": { "start_dt": post_start_dt.to_string(), "end_dt": post_end_dt.to_string(), "revenue_sum_usd_7d": post_sum },
-              "top_n": top_n,
-            })
-            .to_string();
+            store_decision_outcome(pool, tenant_id, channel_id, &cfg, windows, run_for_dt).await?;
+          }
 
-            upsert_decision_outcome(
-              pool,
-              tenant_id,
-              channel_id,
-              decision_dt,
-              run_for_dt,
-              outcome.revenue_change_pct_7d,
-              outcome.catastrophic_flag,
-              outcome.new_top_asset_flag,
-              Some(&notes),
-            )
-            .await?;
+          // Outcomes first computed before their post window filled in are finalized once the
+          // missing days land; each keeps its original outcome_dt and window length so the same
+          // row is updated over the same windows. Ones still incomplete after the max age are
+          // finalized as computed so a permanent data gap cannot hold the recheck slots.
+          finalize_expired_provisional_decision_outcomes(
+            pool,
+            tenant_id,
+            channel_id,
+            run_for_dt - Duration::days(PROVISIONAL_OUTCOME_MAX_AGE_DAYS),
+          )
+          .await?;
+          for (pending_decision_dt, pending_outcome_dt, pending_window_days) in
+            fetch_provisional_decision_outcomes(pool, tenant_id, channel_id, PROVISIONAL_OUTCOME_RECHECK_LIMIT).await?
+          {
+            if pending_decision_dt == decision_dt {
+              continue;
+            }
+            let pending_windows = stored_outcome_windows(
+              pending_decision_dt,
+              pending_window_days.unwrap_or(cfg.outcome_window_days),
+            );
+            store_decision_outcome(pool, tenant_id, channel_id, &cfg, pending_windows, pending_outcome_dt).await?;
           }
 
           if let Err(err) = evaluate_running_experiments_for_channel(
@@ -2422,15 +3415,52 @@ async fn handle_tick(
           // Keep guardrails fresh after the latest sync window completes.
           // For initial backfills we may run multiple `daily_channel` tasks; evaluate only once (today's run).
           if run_for_dt == now.date_naive() {
+            if let Err(err) =
+              detect_manual_video_changes(pool, tenant_id, channel_id, &tokens.access_token, run_for_dt).await
+            {
+              eprintln!("daily_channel: detect_manual_video_changes error: {}", err);
+            }
             if let Err(err) = evaluate_youtube_alerts(pool, tenant_id, channel_id).await {
               eprintln!("daily_channel: evaluate_youtube_alerts error: {}", err);
             }
+            if let Err(err) = evaluate_source_divergence_alert(pool, tenant_id, channel_id).await {
+              eprintln!("daily_channel: evaluate_source_divergence_alert error: {}", err);
+            }
+            if let Err(err) = evaluate_experiment_failure_alert(pool, tenant_id, channel_id).await {
+              eprintln!("daily_channel: evaluate_experiment_failure_alert error: {}", err);
+            }
           }
 
           Ok(())
         })()
         .await
             }
+            "evidence_retention" => {
+                async {
+                    let run_for_dt = run_for_dt.ok_or_else(|| {
+                        Box::new(std::io::Error::other(
+                            "evidence_retention task missing run_for_dt",
+                        )) as Error
+                    })?;
+
+                    let params_json =
+                        fetch_policy_params_json(pool, tenant_id, channel_id, "active").await?;
+                    let retention_days = evidence_retention_days(
+                        params_json.as_deref(),
+                        std::env::var("EVIDENCE_RETENTION_DAYS").ok().as_deref(),
+                    );
+                    let cutoff = evidence_retention_cutoff(run_for_dt, retention_days);
+                    let counts = prune_decision_evidence(pool, tenant_id, channel_id, cutoff).await?;
+                    if counts.decisions_pruned > 0 || counts.outcomes_pruned > 0 {
+                        eprintln!(
+                            "evidence_retention: tenant={tenant_id} channel={channel_id} cutoff={cutoff} decisions={} outcomes={}",
+                            counts.decisions_pruned, counts.outcomes_pruned
+                        );
+                    }
+                    Ok(())
+                }
+                .await
+            }
             "weekly_channel" => {
                 (|| async {
                     let run_for_dt = run_for_dt.ok_or_else(|| {
@@ -2440,7 +3470,7 @@ async fn handle_tick(
                     })?;
 
                     let default_cfg = DecisionEngineConfig::default();
-                    let params_json = default_policy_params_json(&default_cfg);
+                    let params_json = default_cfg.to_policy_params_value().to_string();
 
                     upsert_policy_params(
                         pool,
@@ -2515,35 +3545,11 @@ async fn handle_tick(
             })?;
 
           // Proactive refresh if expired (best-effort).
-          let needs_refresh = tokens
-            .expires_at
-            .map(|t| t <= now)
-            .unwrap_or(false);
-        if needs_refresh {
-          if let Some(refresh) = tokens.refresh_token.clone() {
-            let app = fetch_or_seed_youtube_oauth_app_config(pool, tenant_id)
-              .await?
-              .ok_or_else(|| Box::new(std::io::Error::other("missing youtube oauth app config")) as Error)?;
-            let client_secret = app
-              .client_secret
-              .as_deref()
-              .map(str::trim)
-              .filter(|v| !v.is_empty())
-              .ok_or_else(|| {
-                Box::new(std::io::Error::other("missing youtube oauth client_secret")) as Error
-              })?;
-            let (client, _redirect) =
-              youtube_oauth_client_from_config(&app.client_id, client_secret, &app.redirect_uri)?;
-            let refreshed = refresh_tokens(&client, &refresh).await?;
-            update_youtube_connection_tokens(pool, tenant_id, &channel_id_for_tokens, &refreshed).await?;
-            tokens.access_token = refreshed.access_token;
-            tokens.refresh_token = refreshed.refresh_token.or(Some(refresh));
-          }
-        }
+          refresh_youtube_tokens_if_expired(pool, tenant_id, &channel_id_for_tokens, &mut tokens).await?;
 
           let created_after = youtube_reporting_created_after_rfc3339(
             run_for_dt,
-            YOUTUBE_REPORTING_BACKFILL_DAYS,
+            youtube_reporting_backfill_days(backfill_days.map(i64::from)),
           );
 
           let report_types = list_report_types(&tokens.access_token, content_owner_id)
@@ -2554,134 +3560,71 @@ async fn handle_tick(
               )))
             })?;
 
-          for rt in report_types {
-            let system_managed = if rt.system_managed { 1i8 } else { 0i8 };
-            sqlx::query(
-              r#"
-                INSERT INTO yt_reporting_report_types
-                  (content_owner_id, report_type_id, report_type_name, system_managed)
-                VALUES
-                  (?, ?, ?, ?)
-                ON DUPLICATE KEY UPDATE
-                  report_type_name = VALUES(report_type_name),
-                  system_managed = VALUES(system_managed),
-                  updated_at = CURRENT_TIMESTAMP(3);
-              "#,
-            )
+          let access_token = tokens.access_token.as_str();
+          let created_after = created_after.as_str();
+          let summary = ingest_owner_report_types(
+            report_types,
+            youtube_reporting_type_concurrency(
+              std::env::var("YOUTUBE_REPORTING_TYPE_CONCURRENCY").ok().as_deref(),
+            ),
+            |rt| async move {
+              ingest_owner_report_type(
+                pool,
+                tenant_id,
+                content_owner_id,
+                access_token,
+                &rt,
+                created_after,
+                run_for_dt,
+              )
+              .await
+            },
+          )
+          .await;
+          for (report_type_id, err) in summary.failed.iter() {
+            eprintln!(
+              "youtube_reporting_owner: report_type_id={report_type_id} failed: {err}"
+            );
+          }
+
+          // Files parsed by an older parser (including ones outside the listing window) are
+          // re-queued so parser fixes roll out across history, as are over-wide files once
+          // YOUTUBE_REPORTING_MAX_COLUMNS is raised to fit them.
+          let max_columns = youtube_reporting_max_columns(
+            std::env::var("YOUTUBE_REPORTING_MAX_COLUMNS").ok().as_deref(),
+          );
+          let stale_report_ids = sqlx::query_scalar::<_, String>(STALE_REPORT_FILES_SQL)
+            .bind(tenant_id)
             .bind(content_owner_id)
-            .bind(&rt.report_type_id)
-            .bind(rt.report_type_name.as_deref())
-            .bind(system_managed)
-            .execute(pool)
+            .bind(YOUTUBE_REPORTING_PARSE_VERSION)
+            .bind(max_columns as i64)
+            .fetch_all(pool)
             .await
             .map_err(|e| -> Error { Box::new(e) })?;
 
-            let job_id = match ensure_job_for_report_type(
-              &tokens.access_token,
-              content_owner_id,
-              &rt.report_type_id,
-            )
-            .await
-            {
-              Ok(v) => v,
-              Err(err) => {
-                eprintln!(
-                  "youtube_reporting_owner: ensure_job failed for report_type_id={}: {}",
-                  rt.report_type_id, err
-                );
-                continue;
-              }
-            };
-
+          for report_id in stale_report_ids {
+            let task_channel_id = format!("{content_owner_id}:{report_id}");
+            let dedupe_key = format!("{tenant_id}:youtube_reporting_report:{content_owner_id}:{report_id}");
             sqlx::query(
               r#"
-                INSERT INTO yt_reporting_jobs
-                  (tenant_id, content_owner_id, report_type_id, job_id)
-                VALUES
-                  (?, ?, ?, ?)
+                INSERT INTO job_tasks (tenant_id, job_type, channel_id, run_for_dt, dedupe_key, status, max_attempt)
+                VALUES (?, 'youtube_reporting_report', ?, ?, ?, 'pending', ?)
                 ON DUPLICATE KEY UPDATE
-                  job_id = VALUES(job_id),
-                  updated_at = CURRENT_TIMESTAMP(3);
+                  updated_at = CURRENT_TIMESTAMP(3),
+                  attempt = CASE WHEN status IN ('succeeded','dead') THEN 0 ELSE attempt END,
+                  last_error = CASE WHEN status IN ('succeeded','dead') THEN NULL ELSE last_error END,
+                  run_after = CASE WHEN status IN ('succeeded','dead') THEN CURRENT_TIMESTAMP(3) ELSE run_after END,
+                  status = CASE WHEN status IN ('succeeded','dead') THEN 'pending' ELSE status END;
               "#,
             )
             .bind(tenant_id)
-            .bind(content_owner_id)
-            .bind(&rt.report_type_id)
-            .bind(&job_id)
+            .bind(task_channel_id)
+            .bind(run_for_dt)
+            .bind(dedupe_key)
+            .bind(max_attempt_for_job_type("youtube_reporting_report"))
             .execute(pool)
             .await
             .map_err(|e| -> Error { Box::new(e) })?;
-
-            let reports = match list_reports(
-              &tokens.access_token,
-              &job_id,
-              content_owner_id,
-              Some(created_after.as_str()),
-            )
-            .await
-            {
-              Ok(v) => v,
-              Err(err) => {
-                eprintln!(
-                  "youtube_reporting_owner: list_reports failed for report_type_id={} job_id={}: {}",
-                  rt.report_type_id, job_id, err
-                );
-                continue;
-              }
-            };
-
-            for rep in reports {
-              let start_time = parse_rfc3339_utc(rep.start_time.as_deref());
-              let end_time = parse_rfc3339_utc(rep.end_time.as_deref());
-              let create_time = parse_rfc3339_utc(rep.create_time.as_deref());
-
-              sqlx::query(
-                r#"
-                  INSERT INTO yt_reporting_report_files
-                    (tenant_id, content_owner_id, report_type_id, job_id, report_id, download_url, start_time, end_time, create_time)
-                  VALUES
-                    (?, ?, ?, ?, ?, ?, ?, ?, ?)
-                  ON DUPLICATE KEY UPDATE
-                    download_url = COALESCE(VALUES(download_url), download_url),
-                    start_time = COALESCE(VALUES(start_time), start_time),
-                    end_time = COALESCE(VALUES(end_time), end_time),
-                    create_time = COALESCE(VALUES(create_time), create_time),
-                    updated_at = CURRENT_TIMESTAMP(3);
-                "#,
-              )
-              .bind(tenant_id)
-              .bind(content_owner_id)
-              .bind(&rt.report_type_id)
-              .bind(&job_id)
-              .bind(&rep.report_id)
-              .bind(rep.download_url.as_deref())
-              .bind(start_time)
-              .bind(end_time)
-              .bind(create_time)
-              .execute(pool)
-              .await
-              .map_err(|e| -> Error { Box::new(e) })?;
-
-              let task_channel_id = format!("{content_owner_id}:{}", rep.report_id);
-              let dedupe_key = format!(
-                "{tenant_id}:youtube_reporting_report:{content_owner_id}:{}",
-                rep.report_id
-              );
-              sqlx::query(
-                r#"
-                  INSERT INTO job_tasks (tenant_id, job_type, channel_id, run_for_dt, dedupe_key, status)
-                  VALUES (?, 'youtube_reporting_report', ?, ?, ?, 'pending')
-                  ON DUPLICATE KEY UPDATE updated_at = CURRENT_TIMESTAMP(3);
-                "#,
-              )
-              .bind(tenant_id)
-              .bind(task_channel_id)
-              .bind(run_for_dt)
-              .bind(dedupe_key)
-              .execute(pool)
-              .await
-              .map_err(|e| -> Error { Box::new(e) })?;
-            }
           }
 
           Ok(())
@@ -2712,35 +3655,11 @@ async fn handle_tick(
             })?;
 
           // Proactive refresh if expired (best-effort).
-          let needs_refresh = tokens
-            .expires_at
-            .map(|t| t <= now)
-            .unwrap_or(false);
-          if needs_refresh {
-            if let Some(refresh) = tokens.refresh_token.clone() {
-              let app = fetch_or_seed_youtube_oauth_app_config(pool, tenant_id)
-                .await?
-                .ok_or_else(|| Box::new(std::io::Error::other("missing youtube oauth app config")) as Error)?;
-              let client_secret = app
-                .client_secret
-                .as_deref()
-                .map(str::trim)
-                .filter(|v| !v.is_empty())
-                .ok_or_else(|| {
-                  Box::new(std::io::Error::other("missing youtube oauth client_secret")) as Error
-                })?;
-              let (client, _redirect) =
-                youtube_oauth_client_from_config(&app.client_id, client_secret, &app.redirect_uri)?;
-              let refreshed = refresh_tokens(&client, &refresh).await?;
-              update_youtube_connection_tokens(pool, tenant_id, &channel_id_for_tokens, &refreshed).await?;
-              tokens.access_token = refreshed.access_token;
-              tokens.refresh_token = refreshed.refresh_token.or(Some(refresh));
-            }
-          }
+          refresh_youtube_tokens_if_expired(pool, tenant_id, &channel_id_for_tokens, &mut tokens).await?;
 
-          let row = sqlx::query_as::<_, (String, String, Option<String>, Option<Vec<u8>>, String)>(
+          let row = sqlx::query_as::<_, (String, String, Option<String>, Option<Vec<u8>>, String, Option<String>, i64)>(
             r#"
-              SELECT report_type_id, job_id, download_url, raw_bytes, parse_status
+              SELECT report_type_id, job_id, download_url, raw_bytes, parse_status, parse_version, ingested_row_no
               FROM yt_reporting_report_files
               WHERE tenant_id = ?
                 AND content_owner_id = ?
@@ -2755,15 +3674,38 @@ async fn handle_tick(
           .await
           .map_err(|e| -> Error { Box::new(e) })?;
 
-          let Some((report_type_id, job_id, download_url, raw_bytes, parse_status)) = row else {
+          let Some((report_type_id, job_id, download_url, raw_bytes, parse_status, parse_version, ingested_row_no)) = row else {
             return Err(Box::new(std::io::Error::other(
               "missing yt_reporting_report_files row",
             )) as Error);
           };
 
-          if parse_status == "parsed" {
-            return Ok(());
-          }
+          let ingested_row_no = match report_parse_action(&parse_status, parse_version.as_deref()) {
+            ReportParseAction::Skip => return Ok(()),
+            ReportParseAction::Resume => ingested_row_no,
+            ReportParseAction::Reparse => {
+              // Parsed under an older parser: restart from row 0 and clear the version so a
+              // partial reparse resumes from its own checkpoint on the next attempt.
+              sqlx::query(
+                r#"
+                  UPDATE yt_reporting_report_files
+                  SET parse_status = 'pending',
+                      parse_version = NULL,
+                      ingested_row_no = 0
+                  WHERE tenant_id = ?
+                    AND content_owner_id = ?
+                    AND report_id = ?;
+                "#,
+              )
+              .bind(tenant_id)
+              .bind(&content_owner_id)
+              .bind(&report_id)
+              .execute(pool)
+              .await
+              .map_err(|e| -> Error { Box::new(e) })?;
+              0
+            }
+          };
 
           let bytes = match raw_bytes {
             Some(b) => b,
@@ -2808,6 +3750,7 @@ async fn handle_tick(
             }
           };
 
+          let too_wide_columns = std::sync::OnceLock::<i64>::new();
           let parse_result: Result<(), Error> = (|| async {
             let decoded = maybe_gunzip_bytes(&bytes).map_err(|e| -> Error { Box::new(e) })?;
 
@@ -2823,9 +3766,37 @@ async fn handle_tick(
               .collect::<Vec<_>>();
 
             let columns = globa_flux_rust::db::dedupe_columns(&headers);
-            let table_name = yt_reporting_wide_table_name(&report_type_id);
+            let max_columns = youtube_reporting_max_columns(
+              std::env::var("YOUTUBE_REPORTING_MAX_COLUMNS").ok().as_deref(),
+            );
+            if let Err(message) = check_wide_table_width(&report_type_id, columns.len(), max_columns) {
+              let _ = too_wide_columns.set(columns.len() as i64);
+              // Surface it on the tenant's channel; the file is marked as a parse error below and
+              // kept for replay once the limit is raised.
+              let details_json = serde_json::json!({
+                "content_owner_id": content_owner_id,
+                "report_type_id": report_type_id,
+                "report_id": report_id,
+                "columns": columns.len(),
+                "max_columns": max_columns,
+              })
+              .to_string();
+              let _ = upsert_alert(
+                pool,
+                tenant_id,
+                &channel_id_for_tokens,
+                &format!("reporting_report_too_wide:{}", globa_flux_rust::db::sanitize_sql_identifier(&report_type_id)),
+                "Data reach",
+                "warning",
+                &message,
+                Some(&details_json),
+              )
+              .await;
+              return Err(Box::new(std::io::Error::other(message)) as Error);
+            }
+            let table_name = yt_reporting_wide_table_name(&report_type_id)?;
             let columns_json = serde_json::to_string(&columns).unwrap_or_else(|_| "[]".to_string());
-            let parse_version = "v1";
+            let parse_version = YOUTUBE_REPORTING_PARSE_VERSION;
 
             upsert_yt_reporting_wide_table_metadata(
               pool,
@@ -2838,29 +3809,22 @@ async fn handle_tick(
 
             ensure_yt_reporting_wide_table(pool, &table_name, &columns).await?;
 
+            if ingested_row_no == 0 {
+              delete_yt_reporting_wide_rows(pool, &table_name, tenant_id, &content_owner_id, &report_id).await?;
+            }
+
             let binds_per_row = 6usize.saturating_add(columns.len());
             let max_rows = (65000usize / binds_per_row).max(1);
             let batch_size = max_rows.min(200);
 
-            let mut row_no: i64 = 0;
+            // Resume after the last batch a previous (timed out) attempt committed.
             let mut batch: Vec<(i64, Vec<Option<String>>)> = Vec::with_capacity(batch_size);
 
-            for result in rdr.records() {
-              let record = result
+            for result in wide_rows_after_checkpoint(&mut rdr, columns.len(), ingested_row_no) {
+              let row = result
                 .map_err(|e| -> Error { Box::new(std::io::Error::other(e.to_string())) })?;
-              row_no += 1;
-
-              let mut values: Vec<Option<String>> = Vec::with_capacity(columns.len());
-              for idx in 0..columns.len() {
-                let v = record.get(idx).unwrap_or("");
-                if v.is_empty() {
-                  values.push(None);
-                } else {
-                  values.push(Some(v.to_string()));
-                }
-              }
 
-              batch.push((row_no, values));
+              batch.push(row);
               if batch.len() >= batch_size {
                 insert_yt_reporting_wide_rows_batch(
                   pool,
@@ -2874,6 +3838,14 @@ async fn handle_tick(
                   batch.as_slice(),
                 )
                 .await?;
+                update_yt_reporting_ingest_checkpoint(
+                  pool,
+                  tenant_id,
+                  &content_owner_id,
+                  &report_id,
+                  batch.last().map(|(row_no, _)| *row_no).unwrap_or(ingested_row_no),
+                )
+                .await?;
                 batch.clear();
               }
             }
@@ -2891,6 +3863,14 @@ async fn handle_tick(
                 batch.as_slice(),
               )
               .await?;
+              update_yt_reporting_ingest_checkpoint(
+                pool,
+                tenant_id,
+                &content_owner_id,
+                &report_id,
+                batch.last().map(|(row_no, _)| *row_no).unwrap_or(ingested_row_no),
+              )
+              .await?;
             }
 
             Ok(())
@@ -2903,14 +3883,16 @@ async fn handle_tick(
                 r#"
                   UPDATE yt_reporting_report_files
                   SET parse_status = 'parsed',
-                      parse_version = 'v1',
+                      parse_version = ?,
                       parsed_at = CURRENT_TIMESTAMP(3),
-                      parse_error = NULL
+                      parse_error = NULL,
+                      too_wide_columns = NULL
                   WHERE tenant_id = ?
                     AND content_owner_id = ?
                     AND report_id = ?;
                 "#,
               )
+              .bind(YOUTUBE_REPORTING_PARSE_VERSION)
               .bind(tenant_id)
               .bind(&content_owner_id)
               .bind(&report_id)
@@ -2926,15 +3908,18 @@ async fn handle_tick(
                 r#"
                   UPDATE yt_reporting_report_files
                   SET parse_status = 'error',
-                      parse_version = 'v1',
+                      parse_version = ?,
                       parsed_at = CURRENT_TIMESTAMP(3),
-                      parse_error = ?
+                      parse_error = ?,
+                      too_wide_columns = ?
                   WHERE tenant_id = ?
                     AND content_owner_id = ?
                     AND report_id = ?;
                 "#,
               )
+              .bind(YOUTUBE_REPORTING_PARSE_VERSION)
               .bind(message)
+              .bind(too_wide_columns.get().copied())
               .bind(tenant_id)
               .bind(&content_owner_id)
               .bind(&report_id)
@@ -2942,7 +3927,8 @@ async fn handle_tick(
               .await
               .map_err(|e| -> Error { Box::new(e) })?;
 
-              // Parsing errors are not retried; the raw blob remains for replay.
+              // Parsing errors are not retried; the raw blob remains for replay, and an over-wide
+              // file is re-queued by the stale-report sweep once the column limit fits it.
               Ok(())
             }
           }
@@ -2976,7 +3962,8 @@ async fn handle_tick(
                     last_error = Some(message.clone());
                 }
 
-                if attempt_next >= *max_attempt {
+                // A revoked refresh token won't recover on retry; the connection is flagged for reauth.
+                if attempt_next >= *max_attempt || is_reauth_required(&err) {
                     sqlx::query(
                         r#"
               UPDATE job_tasks
@@ -3078,6 +4065,122 @@ async fn main() -> Result<(), Error> {
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn owner_report_types_run_in_parallel_and_isolate_a_failure() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let report_types: Vec<YoutubeReportingReportType> = ["channel_basic_a2", "broken", "content_owner_ad_rates_a1", "playlist_basic_a1"]
+            .iter()
+            .map(|id| YoutubeReportingReportType {
+                report_type_id: id.to_string(),
+                report_type_name: None,
+                system_managed: false,
+            })
+            .collect();
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let summary = ingest_owner_report_types(report_types, 2, |rt| {
+            let active = active.clone();
+            let peak = peak.clone();
+            async move {
+                let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                active.fetch_sub(1, Ordering::SeqCst);
+                if rt.report_type_id == "broken" {
+                    return Err(Box::new(std::io::Error::other("list_reports failed: 403")) as Error);
+                }
+                Ok(3)
+            }
+        })
+        .await;
+
+        assert_eq!(summary.processed, 3);
+        assert_eq!(summary.reports_queued, 9);
+        assert_eq!(
+            summary.failed,
+            vec![("broken".to_string(), "list_reports failed: 403".to_string())]
+        );
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+
+        assert_eq!(youtube_reporting_type_concurrency(None), 2);
+        assert_eq!(youtube_reporting_type_concurrency(Some("16")), 4);
+        assert_eq!(youtube_reporting_type_concurrency(Some("0")), 1);
+    }
+
+    #[test]
+    fn a_channel_outside_its_sync_window_is_not_dispatched() {
+        let weekly = SyncSchedule::from_policy_params_json(
+            r#"{"sync_schedule": {"frequency": "weekly", "weekday": "mon", "hour": 6, "utc_offset_minutes": 60}}"#,
+        )
+        .unwrap();
+        let sweep_dt = chrono::NaiveDate::from_ymd_opt(2026, 3, 3).unwrap();
+        // Tuesday 05:00 UTC: wrong day for a Monday-only channel.
+        let tuesday = Utc.with_ymd_and_hms(2026, 3, 3, 5, 0, 0).unwrap();
+        assert_eq!(scheduled_run_for_dt(Some(&weekly), tuesday, sweep_dt, false), None);
+        // Channels without a schedule are dispatched exactly as before.
+        assert_eq!(scheduled_run_for_dt(None, tuesday, sweep_dt, false), Some(sweep_dt));
+
+        // Monday 06:xx local (05:xx UTC) is due, on the creator's local day.
+        let monday = Utc.with_ymd_and_hms(2026, 3, 2, 5, 15, 0).unwrap();
+        assert_eq!(
+            scheduled_run_for_dt(Some(&weekly), monday, sweep_dt, false),
+            chrono::NaiveDate::from_ymd_opt(2026, 3, 2)
+        );
+        assert_eq!(scheduled_run_for_dt(Some(&weekly), monday, sweep_dt, true), Some(sweep_dt));
+    }
+    use globa_flux_rust::db::{dispatch_lock_is_active, DEFAULT_DISPATCH_LOCK_TTL_SECS};
+
+    #[test]
+    fn reclaim_respects_per_job_type_lock_ttl() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 6, 0, 0).unwrap();
+        let ttl = |job_type: Option<&str>| match job_type {
+            Some("youtube_reporting_report") => 7200,
+            _ => 600,
+        };
+        let (per_type, default_cutoff) = stale_lock_cutoffs(now, ttl);
+        assert_eq!(per_type.len(), TASK_JOB_TYPES.len());
+        assert_eq!(default_cutoff, now - Duration::seconds(600));
+
+        let locked_at = now - Duration::minutes(20);
+        let is_stale = |job_type: &str| {
+            let cutoff = per_type
+                .iter()
+                .find(|(t, _)| *t == job_type)
+                .map(|(_, c)| *c)
+                .unwrap_or(default_cutoff);
+            locked_at < cutoff
+        };
+        assert!(!is_stale("youtube_reporting_report"));
+        assert!(is_stale("geo_monitor_prompt"));
+        assert!(is_stale("unknown_type"));
+
+        let sql = reclaim_stale_tasks_sql(true, per_type.len());
+        assert!(sql.contains("tenant_id = ? AND status='running'"));
+        assert_eq!(sql.matches("WHEN ? THEN ?").count(), TASK_JOB_TYPES.len());
+        assert!(sql.contains("ELSE ? END"));
+        assert!(!reclaim_stale_tasks_sql(false, 0).contains("tenant_id"));
+    }
+
+    #[test]
+    fn dispatch_candidates_exclude_inactive_connections() {
+        for schedule in [DispatchSchedule::Daily, DispatchSchedule::YoutubeReporting] {
+            for has_tenant_filter in [true, false] {
+                let sql = candidate_select_sql(schedule, has_tenant_filter);
+                assert!(sql.contains("AND active = 1"), "{sql}");
+                assert!(sql.contains("AND needs_reauth = 0"), "{sql}");
+                assert_eq!(
+                    sql.contains("SELECT DISTINCT tenant_id, content_owner_id"),
+                    schedule == DispatchSchedule::YoutubeReporting,
+                    "{sql}"
+                );
+            }
+        }
+
+    }
+
     #[test]
     fn parses_youtube_reporting_report_task_key() {
         assert_eq!(
@@ -3089,6 +4192,44 @@ mod tests {
         assert_eq!(parse_youtube_reporting_report_task_key("nope"), None);
     }
 
+    #[test]
+    fn second_dispatch_within_lock_window_is_skipped() {
+        let body_hash = |body: serde_json::Value| {
+            let parsed: DispatchRequest = serde_json::from_value(body).unwrap();
+            dispatch_params_hash(&parsed, None, None, false)
+        };
+        let plain: DispatchRequest = serde_json::from_value(serde_json::json!({"now_ms": 1})).unwrap();
+        let params = dispatch_params_hash(&plain, None, None, false);
+        assert_eq!(params, body_hash(serde_json::json!({"now_ms": 2, "backfill_weeks": 0})));
+
+        let first = dispatch_lock_key("daily_channel", Some("t1"), None, &params);
+        let retry = dispatch_lock_key("daily_channel", Some("t1"), None, &params);
+        assert_eq!(first, retry);
+        assert_ne!(first, dispatch_lock_key("weekly_channel", Some("t1"), None, &params));
+        assert_ne!(first, dispatch_lock_key("daily_channel", None, None, &params));
+        assert_ne!(first, dispatch_lock_key("daily_channel", Some("t1"), Some("UC1"), &params));
+
+        // A backfill, forced, pinned-date or explicit-date run is not swallowed by the plain lock.
+        let dt = chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        for other in [
+            body_hash(serde_json::json!({"now_ms": 1, "backfill_weeks": 4})),
+            body_hash(serde_json::json!({"now_ms": 1, "gap_scan_weeks": 8})),
+            dispatch_params_hash(&plain, None, None, true),
+            dispatch_params_hash(&plain, Some(dt), None, false),
+            dispatch_params_hash(&plain, None, Some(&[dt]), false),
+        ] {
+            assert_ne!(first, dispatch_lock_key("daily_channel", Some("t1"), None, &other));
+        }
+
+        let acquired_at = Utc.with_ymd_and_hms(2026, 3, 1, 6, 0, 0).unwrap();
+        let expires_at = acquired_at + Duration::seconds(DEFAULT_DISPATCH_LOCK_TTL_SECS);
+        // The retry 5s later sees the first dispatch's lock and is skipped.
+        assert!(dispatch_lock_is_active(Some(expires_at), acquired_at + Duration::seconds(5)));
+        // Once the window passes, the next dispatch acquires the lock again.
+        assert!(!dispatch_lock_is_active(Some(expires_at), expires_at + Duration::seconds(1)));
+        assert!(!dispatch_lock_is_active(None, acquired_at));
+    }
+
     #[test]
     fn formats_created_after_for_backfill() {
         let run_for_dt = chrono::NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
@@ -3100,9 +4241,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn created_after_reflects_configured_backfill_horizon() {
+        let run_for_dt = chrono::NaiveDate::from_ymd_opt(2026, 6, 1).unwrap();
+
+        let expected =
+            chrono::Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap() - chrono::Duration::days(150);
+        assert_eq!(
+            youtube_reporting_created_after_rfc3339(
+                run_for_dt,
+                youtube_reporting_backfill_days(Some(150))
+            ),
+            expected.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        );
+
+        assert_eq!(youtube_reporting_backfill_days(Some(400)), 180);
+        assert_eq!(youtube_reporting_backfill_days(Some(0)), 1);
+    }
+
     #[test]
     fn reporting_wide_table_name_is_mysql_safe() {
-        let name = yt_reporting_wide_table_name("channel_basic_a2");
+        let name = yt_reporting_wide_table_name("channel_basic_a2").unwrap();
         assert!(name.starts_with("yt_rpt_"));
         assert!(name.len() <= 64);
         assert!(name
@@ -3110,6 +4269,60 @@ mod tests {
             .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'));
     }
 
+    #[test]
+    fn adversarial_report_type_ids_never_reach_ddl_unchecked() {
+        for report_type_id in [
+            "x`; DROP TABLE job_tasks; --",
+            "a` LONGTEXT NULL, `b",
+            "\u{0}\n`",
+            "报告",
+            &"very_long_report_type_".repeat(10),
+        ] {
+            let name = yt_reporting_wide_table_name(report_type_id).unwrap();
+            assert!(!name.contains('`'), "{name}");
+            let (ddl, alters) =
+                yt_reporting_wide_table_ddl(&name, &["views".to_string()]).unwrap();
+            assert!(ddl.starts_with(&format!("CREATE TABLE IF NOT EXISTS `{name}` (")));
+            assert_eq!(alters.len(), 1);
+        }
+
+        // Identifiers that skipped the sanitizer are refused outright.
+        for (table, column) in [
+            ("yt_rpt_x`; DROP TABLE t; --", "views"),
+            ("yt_rpt_ok_1a2b3c4d", "views` LONGTEXT, `x"),
+            ("yt_rpt_ok_1a2b3c4d", ""),
+            ("Yt_Rpt", "views"),
+        ] {
+            assert!(
+                yt_reporting_wide_table_ddl(table, &[column.to_string()]).is_err(),
+                "{table} / {column}"
+            );
+        }
+    }
+
+    #[test]
+    fn over_wide_report_is_rejected_before_ddl() {
+        let headers: Vec<String> = (0..301).map(|i| format!("metric {i}")).collect();
+        let columns = globa_flux_rust::db::dedupe_columns(&headers);
+        let max = youtube_reporting_max_columns(None);
+        assert_eq!(max, 300);
+
+        let err = check_wide_table_width("content_owner_huge_a1", columns.len(), max).unwrap_err();
+        assert!(err.contains("301 columns"), "{err}");
+        assert!(err.contains("300-column"), "{err}");
+
+        assert!(check_wide_table_width("content_owner_huge_a1", 300, max).is_ok());
+        let raised = youtube_reporting_max_columns(Some("400"));
+        assert!(check_wide_table_width("content_owner_huge_a1", columns.len(), raised).is_ok());
+        assert_eq!(youtube_reporting_max_columns(Some("99999")), 1000);
+        assert_eq!(youtube_reporting_max_columns(Some("0")), 1);
+
+        // A file rejected at the current parse_version is still re-queued once the limit fits it.
+        assert!(STALE_REPORT_FILES_SQL
+            .contains("OR (parse_status = 'error' AND too_wide_columns <= ?)"));
+        assert_eq!(STALE_REPORT_FILES_SQL.matches('?').count(), 4);
+    }
+
     #[test]
     fn gunzips_when_magic_header_present() {
         use std::io::Write;
@@ -3124,6 +4337,74 @@ mod tests {
         assert_eq!(maybe_gunzip_bytes(plain).unwrap(), plain);
     }
 
+    #[test]
+    fn resumed_wide_row_ingestion_skips_rows_up_to_checkpoint() {
+        let csv_text = "a,b\n1,x\n2,\n3,z\n4,w\n5,v\n";
+        let mut rdr = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(csv_text.as_bytes());
+
+        let rows = wide_rows_after_checkpoint(&mut rdr, 2, 3)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(
+            rows.iter().map(|(row_no, _)| *row_no).collect::<Vec<_>>(),
+            vec![4, 5]
+        );
+        assert_eq!(
+            rows[0].1,
+            vec![Some("4".to_string()), Some("w".to_string())]
+        );
+
+        let mut rdr = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(csv_text.as_bytes());
+        let rows = wide_rows_after_checkpoint(&mut rdr, 2, 0)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[1].1, vec![Some("2".to_string()), None]);
+
+        // Skipped rows are never decoded, so a non-UTF-8 row before the checkpoint is harmless.
+        let bytes: &[u8] = b"a,b\n1,\xff\n2,y\n";
+        let mut rdr = csv::ReaderBuilder::new().has_headers(true).from_reader(bytes);
+        let rows = wide_rows_after_checkpoint(&mut rdr, 2, 1)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(rows, vec![(2, vec![Some("2".to_string()), Some("y".to_string())])]);
+
+        let mut rdr = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(csv_text.as_bytes());
+        assert_eq!(wide_rows_after_checkpoint(&mut rdr, 2, 10).count(), 0);
+    }
+
+    #[test]
+    fn report_parse_version_mismatch_triggers_reparse() {
+        assert_eq!(
+            report_parse_action("parsed", Some(YOUTUBE_REPORTING_PARSE_VERSION)),
+            ReportParseAction::Skip
+        );
+        assert_eq!(
+            report_parse_action("parsed", Some("v0")),
+            ReportParseAction::Reparse
+        );
+        assert_eq!(
+            report_parse_action("error", Some("v0")),
+            ReportParseAction::Reparse
+        );
+        assert_eq!(
+            report_parse_action("pending", None),
+            ReportParseAction::Resume
+        );
+        // Parsed before versioning: re-queued by the stale sweep, so it must not be skipped.
+        assert_eq!(
+            report_parse_action("parsed", None),
+            ReportParseAction::Reparse
+        );
+    }
+
     #[test]
     fn parses_rfc3339_timestamps_as_utc() {
         let dt = parse_rfc3339_utc(Some("2026-01-01T00:00:00Z")).unwrap();
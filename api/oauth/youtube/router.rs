@@ -1,33 +1,93 @@
 use bytes::Bytes;
-use http_body_util::BodyExt;
+use http_body_util::{BodyExt, StreamBody};
+use hyper::body::Frame;
 use hyper::{HeaderMap, Method, StatusCode, Uri};
 use serde::Deserialize;
 use vercel_runtime::{run, service_fn, Error, Request, Response, ResponseBody};
 
-use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
 
+use globa_flux_rust::backfill::{find_date_gaps, METRIC_UPSERT_BATCH_SIZE};
+use globa_flux_rust::channel_totals::{
+    channel_total_filter, channel_total_sums, channel_total_sums_when, derive_channel_totals,
+    video_rows_filter, API_CHANNEL_TOTAL_VIDEO_ID, CSV_CHANNEL_TOTAL_VIDEO_ID,
+};
+use globa_flux_rust::cost::merge_daily_usage;
 use globa_flux_rust::db::{
-    fetch_or_seed_youtube_oauth_app_config, fetch_youtube_channel_id,
-    fetch_youtube_connection_tokens, fetch_youtube_content_owner_id,
-    fetch_youtube_oauth_app_config, get_pool, set_youtube_channel_id, set_youtube_content_owner_id,
-    update_youtube_connection_tokens, upsert_observed_action, upsert_video_daily_metric,
-    upsert_youtube_connection, upsert_youtube_oauth_app_config,
+    delete_alert_template, delete_channel_rpm_baseline, enqueue_daily_channel_tasks,
+    evidence_retention_days, fetch_alert_notification_settings, fetch_alert_templates,
+    fetch_authoritative_channel_total_dts, fetch_channel_rpm_baseline, fetch_decision_daily,
+    fetch_experiment_baseline_thumbnail, fetch_experiment_full_snapshot, fetch_latest_metric_dt,
+    fetch_llm_cost_daily, fetch_open_alerts_detected_since, fetch_or_seed_youtube_oauth_app_config,
+    fetch_pinned_channels, fetch_policy_params_json, fetch_tenant_id_for_api_key_hash,
+    fetch_video_change_dts, fetch_video_daily_metric_rows, fetch_youtube_api_units_daily,
+    fetch_youtube_channel_id, fetch_youtube_content_owner_id, fetch_youtube_last_synced_dt,
+    fetch_youtube_oauth_app_config, get_pool, insert_experiment_baseline_thumbnail,
+    insert_tenant_api_key, mark_experiment_rollback_failed, pin_channel, record_video_change,
+    record_youtube_api_usage, revoke_tenant_api_key, rewind_youtube_sync_for_purge,
+    set_youtube_channel_id, set_youtube_connection_active, set_youtube_content_owner_id,
+    unpin_channel, upsert_alert_notification_settings, upsert_alert_template,
+    upsert_channel_rpm_baseline, upsert_derived_channel_totals, upsert_observed_action,
+    upsert_video_daily_metric, upsert_youtube_connection, upsert_youtube_oauth_app_config,
+    YoutubeConnectionTokens, YoutubeOAuthAppConfig, DEFAULT_EVIDENCE_RETENTION_DAYS,
+};
+use globa_flux_rust::decision_engine::{
+    diff_decisions, min_surfaced_confidence, surfaced_direction, DecisionEngineConfig,
+    DEFAULT_MIN_SURFACED_CONFIDENCE, INSUFFICIENT_CONFIDENCE,
+};
+use globa_flux_rust::error_codes::{annotate_error_body, ErrorCode};
+use globa_flux_rust::experiments::{
+    effective_experiment_duration_days, experiment_baseline_window,
+    experiment_completed_day_offset, experiment_current_end_dt, experiment_last_complete_dt,
+    experiment_min_duration_days, video_owned_by_channel, ExperimentBaselineWindow,
+    EXPERIMENT_BASELINE_DAYS,
+};
+use globa_flux_rust::feature_flags::{
+    check_tenant_feature, feature_enabled, fetch_tenant_feature_flags_cached, TenantFeature,
+};
+use globa_flux_rust::guardrails::{
+    experiment_failure_rate_threshold, DEFAULT_EXPERIMENT_FAILURE_RATE_THRESHOLD,
+};
+use globa_flux_rust::onboarding::{
+    configured_backfill_weeks, connect_onboarding, create_first_decision,
+    onboarding_backfill_run_for_dts, ConnectOnboarding, FirstDecisionError,
+    DEFAULT_ONBOARDING_BACKFILL_WEEKS,
 };
-use globa_flux_rust::decision_engine::{compute_decision, DecisionEngineConfig};
 use globa_flux_rust::providers::youtube::{
-    build_authorize_url, exchange_code_for_tokens, refresh_tokens, youtube_oauth_client_from_config,
+    build_authorize_url, exchange_code_for_tokens, validate_redirect_uri,
+    youtube_oauth_client_from_config, RedirectUriAllowList,
 };
 use globa_flux_rust::providers::youtube_analytics::{
     fetch_top_videos_by_revenue_for_channel, fetch_top_videos_by_views_for_channel,
-    fetch_video_daily_metrics_for_channel, youtube_analytics_error_to_vercel_error,
+    fetch_video_daily_metrics_for_channel, VideoDailyMetricRow, REPORTS_QUERY_QUOTA_UNITS,
 };
 use globa_flux_rust::providers::youtube_api::{fetch_my_channel_id, list_my_channels};
 use globa_flux_rust::providers::youtube_partner::fetch_my_content_owner_id;
 use globa_flux_rust::providers::youtube_videos::{
-    fetch_video_snapshot, set_video_thumbnail_from_url, update_video_publish_at, update_video_title,
+    decode_thumbnail_base64, download_video_thumbnail, experiment_change_quota_units,
+    fetch_video_snapshot, restore_video_snapshot, set_video_thumbnail_from_bytes,
+    set_video_thumbnail_from_url, snapshot_restore_quota_units, update_video_publish_at,
+    update_video_title, VideoSnapshot, YoutubeVideoError, MAX_UPLOADED_THUMBNAIL_BYTES,
+    VIDEOS_LIST_QUOTA_UNITS,
+};
+use globa_flux_rust::reach_reporting::ctr_weight;
+use globa_flux_rust::secrets::{
+    oauth_state_secret, oauth_state_ttl_secs, sign_oauth_state, verify_oauth_state, OAuthStateError,
+};
+use globa_flux_rust::tenant_api_keys::{
+    generate_tenant_api_key, hash_tenant_api_key, is_tenant_api_key, requested_tenant_id,
+    tenant_api_key_display_prefix, tenant_key_access, TenantKeyAccess,
 };
-use globa_flux_rust::youtube_alerts::evaluate_youtube_alerts;
+use globa_flux_rust::youtube_alerts::{
+    build_alert_notifications, evaluate_experiment_failure_alert, evaluate_source_divergence_alert,
+    evaluate_youtube_alerts, resolve_reauth_required_alert, AlertNotificationMode,
+    AlertNotificationSettings, DEFAULT_ALERT_DIGEST_WINDOW_MINUTES,
+    MAX_ALERT_DIGEST_WINDOW_MINUTES,
+};
+use globa_flux_rust::youtube_auth::{ensure_fresh_youtube_tokens, YoutubeTokenError};
 use ring::rand::{SecureRandom, SystemRandom};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 
 fn bearer_token(header_value: Option<&str>) -> Option<&str> {
     let value = header_value?;
@@ -38,14 +98,50 @@ fn bearer_token(header_value: Option<&str>) -> Option<&str> {
 
 fn json_response(
     status: StatusCode,
-    value: serde_json::Value,
+    mut value: serde_json::Value,
 ) -> Result<Response<ResponseBody>, Error> {
+    annotate_error_body(&mut value);
     Ok(Response::builder()
         .status(status)
         .header("content-type", "application/json; charset=utf-8")
         .body(ResponseBody::from(value))?)
 }
 
+const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+
+/// `Accept: application/msgpack` (or the older `application/x-msgpack`) opts a client into
+/// MessagePack bundles; anything else, including `*/*`, keeps JSON.
+fn wants_msgpack(headers: &HeaderMap) -> bool {
+    headers
+        .get("accept")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| {
+            accept.split(',').any(|part| {
+                let media = part.split(';').next().unwrap_or("").trim();
+                media.eq_ignore_ascii_case(MSGPACK_CONTENT_TYPE)
+                    || media.eq_ignore_ascii_case("application/x-msgpack")
+            })
+        })
+}
+
+/// Bundle bodies negotiate the encoding; the MessagePack map carries the same keys as the JSON.
+fn bundle_response(
+    headers: &HeaderMap,
+    status: StatusCode,
+    mut value: serde_json::Value,
+) -> Result<Response<ResponseBody>, Error> {
+    if !wants_msgpack(headers) {
+        return json_response(status, value);
+    }
+    annotate_error_body(&mut value);
+    let bytes = rmp_serde::to_vec_named(&value).map_err(|e| -> Error { Box::new(e) })?;
+    Ok(Response::builder()
+        .status(status)
+        .header("content-type", MSGPACK_CONTENT_TYPE)
+        .header("vary", "accept")
+        .body(ResponseBody::from(bytes))?)
+}
+
 fn has_tidb_url() -> bool {
     std::env::var("TIDB_DATABASE_URL")
         .or_else(|_| std::env::var("DATABASE_URL"))
@@ -53,51 +149,49 @@ fn has_tidb_url() -> bool {
         .unwrap_or(false)
 }
 
-async fn ensure_fresh_youtube_access_token(
-    pool: &sqlx::MySqlPool,
-    tenant_id: &str,
-    channel_id: &str,
-) -> Result<String, Error> {
-    let mut tokens = fetch_youtube_connection_tokens(pool, tenant_id, channel_id)
-        .await?
-        .ok_or_else(|| {
-            Box::new(std::io::Error::other("missing youtube channel connection")) as Error
-        })?;
-
-    let needs_refresh = tokens
-        .expires_at
-        .map(|dt| dt <= chrono::Utc::now())
-        .unwrap_or(false);
-
-    if needs_refresh {
-        if let Some(refresh) = tokens.refresh_token.clone() {
-            let app = fetch_or_seed_youtube_oauth_app_config(pool, tenant_id).await?;
-            let Some(app) = app else {
-                return Err(
-                    Box::new(std::io::Error::other("missing youtube oauth app config")) as Error,
-                );
-            };
-
-            let Some(client_secret) = app
-                .client_secret
-                .as_deref()
-                .map(str::trim)
-                .filter(|v| !v.is_empty())
-            else {
-                return Err(
-                    Box::new(std::io::Error::other("missing youtube oauth client_secret")) as Error,
-                );
-            };
-
-            let (client, _redirect) =
-                youtube_oauth_client_from_config(&app.client_id, client_secret, &app.redirect_uri)?;
-            let refreshed = refresh_tokens(&client, &refresh).await?;
-            update_youtube_connection_tokens(pool, tenant_id, channel_id, &refreshed).await?;
-            tokens.access_token = refreshed.access_token;
-        }
+fn youtube_token_error_response(err: YoutubeTokenError) -> Result<Response<ResponseBody>, Error> {
+    match err {
+        YoutubeTokenError::NotConnected => json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "No YouTube tokens found for this tenant"}),
+        ),
+        YoutubeTokenError::MissingAppConfig => json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({
+              "ok": false,
+              "error": "not_configured",
+              "message": "Missing YouTube OAuth app config for tenant. Configure via /api/oauth/youtube/app_config or set YOUTUBE_CLIENT_ID/YOUTUBE_CLIENT_SECRET/YOUTUBE_REDIRECT_URI on the Rust backend."
+            }),
+        ),
+        YoutubeTokenError::MissingClientSecret => json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing YouTube OAuth client_secret for tenant"}),
+        ),
+        YoutubeTokenError::Revoked => json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "reauth_required", "message": "YouTube access was revoked; reconnect the channel via OAuth"}),
+        ),
+        YoutubeTokenError::NoRefreshToken => json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized", "message": "YouTube access token expired and no refresh token available"}),
+        ),
+        YoutubeTokenError::Other(err) => Err(err),
     }
+}
 
-    Ok(tokens.access_token)
+fn first_decision_error_response(err: FirstDecisionError) -> Result<Response<ResponseBody>, Error> {
+    match err {
+        FirstDecisionError::Token(err) => youtube_token_error_response(err),
+        FirstDecisionError::Analytics(err) if err.status == Some(403) => json_response(
+            StatusCode::FORBIDDEN,
+            serde_json::json!({"ok": false, "error": "forbidden", "message": "No permission to access this channel's analytics", "details": err.to_string()}),
+        ),
+        FirstDecisionError::Analytics(err) => json_response(
+            StatusCode::BAD_GATEWAY,
+            serde_json::json!({"ok": false, "error": "youtube_analytics_error", "message": err.to_string(), "status": err.status}),
+        ),
+        FirstDecisionError::Other(err) => Err(err),
+    }
 }
 
 fn truncate_string(value: &str, max_chars: usize) -> String {
@@ -114,14 +208,6 @@ fn truncate_string(value: &str, max_chars: usize) -> String {
     out
 }
 
-fn now_ms() -> i64 {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_millis() as i64)
-        .unwrap_or(0)
-}
-
 fn decode_hex_digit(b: u8) -> Option<u8> {
     match b {
         b'0'..=b'9' => Some(b - b'0'),
@@ -202,6 +288,24 @@ fn get_query_param(uri: &Uri, key: &str) -> Option<String> {
     None
 }
 
+fn get_query_flag(uri: &Uri, key: &str) -> bool {
+    get_query_param(uri, key)
+        .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// `Some(403 feature_disabled)` when the tenant has the feature switched off.
+async fn feature_disabled_response(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    feature: TenantFeature,
+) -> Result<Option<Response<ResponseBody>>, Error> {
+    match check_tenant_feature(pool, tenant_id, feature).await? {
+        Ok(()) => Ok(None),
+        Err(body) => json_response(StatusCode::FORBIDDEN, body).map(Some),
+    }
+}
+
 fn parse_dt(v: &str) -> Option<NaiveDate> {
     let s = v.trim();
     NaiveDate::parse_from_str(s, "%Y-%m-%d")
@@ -214,6 +318,61 @@ fn round2(v: f64) -> f64 {
     (v * 100.0).round() / 100.0
 }
 
+const DEFAULT_RPM_MIN_VIEWS: i64 = 10;
+
+/// `RPM_MIN_VIEWS` (default 10, `0` disables the floor): rows with fewer views report a null RPM.
+fn rpm_min_views() -> i64 {
+    std::env::var("RPM_MIN_VIEWS")
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .unwrap_or(DEFAULT_RPM_MIN_VIEWS)
+        .clamp(0, 1_000_000)
+}
+
+/// Revenue per 1000 views, or `None` below `min_views` where a couple of views turn a few cents
+/// into a meaningless RPM. Zero views with the floor disabled keep reporting 0.
+fn rpm_with_floor(revenue_usd: f64, views: i64, min_views: i64) -> Option<f64> {
+    if views < min_views {
+        None
+    } else if views > 0 {
+        Some((revenue_usd / (views as f64)) * 1000.0)
+    } else {
+        Some(0.0)
+    }
+}
+
+/// How CTR is rendered in metric responses: a 4-decimal fraction (default) or, with
+/// `ctr_as_percent=true`, a 2-decimal percent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CtrFormat {
+    Fraction,
+    Percent,
+}
+
+impl CtrFormat {
+    fn from_uri(uri: &Uri) -> Self {
+        if get_query_flag(uri, "ctr_as_percent") {
+            CtrFormat::Percent
+        } else {
+            CtrFormat::Fraction
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            CtrFormat::Fraction => "fraction",
+            CtrFormat::Percent => "percent",
+        }
+    }
+
+    fn format(self, ctr: f64) -> f64 {
+        match self {
+            CtrFormat::Fraction => (ctr * 10000.0).round() / 10000.0,
+            CtrFormat::Percent => round2(ctr * 100.0),
+        }
+    }
+}
+
 fn median_i64(values: &mut [i64]) -> Option<i64> {
     if values.is_empty() {
         return None;
@@ -227,6 +386,19 @@ fn median_i64(values: &mut [i64]) -> Option<i64> {
     }
 }
 
+fn median_f64(values: &mut [f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len() % 2 == 1 {
+        Some(values[mid])
+    } else {
+        Some((values[mid - 1] + values[mid]) / 2.0)
+    }
+}
+
 #[derive(Deserialize)]
 struct StartRequest {
     tenant_id: String,
@@ -669,9 +841,22 @@ async fn handle_start(
         );
     };
 
+    let Some(state_secret) = oauth_state_secret() else {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing OAUTH_STATE_SECRET (or RUST_INTERNAL_TOKEN)"}),
+        );
+    };
+    let signed_state = sign_oauth_state(
+        &state_secret,
+        &parsed.tenant_id,
+        &parsed.state,
+        Utc::now().timestamp(),
+    );
+
     let (client, _redirect) =
         youtube_oauth_client_from_config(&app.client_id, client_secret, &app.redirect_uri)?;
-    let (authorize_url, state) = build_authorize_url(&client, Some(parsed.state));
+    let (authorize_url, state) = build_authorize_url(&client, Some(signed_state));
 
     json_response(
         StatusCode::OK,
@@ -683,6 +868,12 @@ async fn handle_start(
 struct ExchangeRequest {
     tenant_id: String,
     code: String,
+    /// The signed `state` Google echoed back to the redirect URI.
+    #[serde(default)]
+    state: String,
+    /// Weeks of history to queue behind the first decision; overrides `ONBOARDING_BACKFILL_WEEKS`.
+    #[serde(default)]
+    backfill_weeks: Option<i64>,
 }
 
 async fn handle_exchange(
@@ -723,13 +914,33 @@ async fn handle_exchange(
         Box::new(std::io::Error::other(format!("invalid json body: {e}")))
     })?;
 
-    if parsed.tenant_id.is_empty() || parsed.code.is_empty() {
+    if parsed.tenant_id.is_empty() || parsed.code.is_empty() || parsed.state.is_empty() {
         return json_response(
             StatusCode::BAD_REQUEST,
-            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id and code are required"}),
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id, code and state are required"}),
         );
     }
 
+    let state = match oauth_state_secret()
+        .ok_or(OAuthStateError::BadSignature)
+        .and_then(|secret| {
+            verify_oauth_state(
+                &secret,
+                &parsed.tenant_id,
+                &parsed.state,
+                Utc::now().timestamp(),
+                oauth_state_ttl_secs(),
+            )
+        }) {
+        Ok(v) => v,
+        Err(err) => {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "invalid_state", "reason": err.as_str()}),
+            );
+        }
+    };
+
     let pool = get_pool().await?;
     let app = fetch_or_seed_youtube_oauth_app_config(pool, &parsed.tenant_id).await?;
     let Some(app) = app else {
@@ -761,95 +972,127 @@ async fn handle_exchange(
     upsert_youtube_connection(pool, &parsed.tenant_id, &channel_id, &tokens)
         .await
         .map_err(|e| -> Error { Box::new(e) })?;
+    resolve_reauth_required_alert(pool, &parsed.tenant_id, &channel_id).await?;
 
-    // Hybrid onboarding: generate the first decision quickly after OAuth connect.
-    // Uses the last 7 completed days (ending yesterday) as the decision window.
     let as_of_dt = Utc::now().date_naive();
-    let start_dt = as_of_dt - Duration::days(7);
-    let end_dt = as_of_dt - Duration::days(1);
-
-    let metrics =
-        fetch_video_daily_metrics_for_channel(&tokens.access_token, &channel_id, start_dt, end_dt)
-            .await
-            .map_err(youtube_analytics_error_to_vercel_error)?;
+    let latest_stored_dt = fetch_latest_metric_dt(pool, &parsed.tenant_id, &channel_id).await?;
+    let last_synced_dt = fetch_youtube_last_synced_dt(pool, &parsed.tenant_id, &channel_id).await?;
+    let onboarding = connect_onboarding(latest_stored_dt, last_synced_dt, as_of_dt);
+    let connection_status = onboarding.connection_status();
+
+    if let ConnectOnboarding::CatchUp {
+        resume_after_dt,
+        run_for_dts,
+    } = onboarding
+    {
+        // A reconnect keeps the stored history and decisions; the worker resumes from the watermark.
+        enqueue_daily_channel_tasks(pool, &parsed.tenant_id, &channel_id, &run_for_dts).await?;
+        return json_response(
+            StatusCode::OK,
+            serde_json::json!({
+              "ok": true,
+              "channel_id": channel_id,
+              "state": state,
+              "connection_status": connection_status,
+              "resume_after_dt": resume_after_dt.to_string(),
+              "catch_up_run_for_dts": run_for_dts.iter().map(|dt| dt.to_string()).collect::<Vec<_>>(),
+            }),
+        );
+    }
 
-    for row in metrics.iter() {
-        upsert_video_daily_metric(
-            pool,
-            &parsed.tenant_id,
-            &channel_id,
-            row.dt,
-            &row.video_id,
-            row.estimated_revenue_usd,
-            row.impressions,
-            row.impressions_ctr,
-            row.views,
-        )
-        .await?;
+    // Hybrid onboarding: generate the first decision quickly after OAuth connect.
+    if let Err(err) =
+        create_first_decision(pool, &parsed.tenant_id, &channel_id, &channel_id, as_of_dt).await
+    {
+        return first_decision_error_response(err);
     }
 
-    let decision = compute_decision(
-        metrics.as_slice(),
-        as_of_dt,
-        start_dt,
-        end_dt,
-        DecisionEngineConfig::default(),
+    // Deeper history on request: the worker fills the weeks before the first decision's window.
+    let backfill_weeks = configured_backfill_weeks(
+        parsed.backfill_weeks,
+        std::env::var("ONBOARDING_BACKFILL_WEEKS").ok().as_deref(),
+        DEFAULT_ONBOARDING_BACKFILL_WEEKS,
+        0,
     );
-
-    let evidence_json =
-        serde_json::to_string(&decision.evidence).unwrap_or_else(|_| "[]".to_string());
-    let forbidden_json =
-        serde_json::to_string(&decision.forbidden).unwrap_or_else(|_| "[]".to_string());
-    let reevaluate_json =
-        serde_json::to_string(&decision.reevaluate).unwrap_or_else(|_| "[]".to_string());
-
-    sqlx::query(
-        r#"
-      INSERT INTO decision_daily (
-        tenant_id, channel_id, as_of_dt,
-        direction, confidence,
-        evidence_json, forbidden_json, reevaluate_json
-      )
-      VALUES (?, ?, ?, ?, ?, ?, ?, ?)
-      ON DUPLICATE KEY UPDATE
-        direction = VALUES(direction),
-        confidence = VALUES(confidence),
-        evidence_json = VALUES(evidence_json),
-        forbidden_json = VALUES(forbidden_json),
-        reevaluate_json = VALUES(reevaluate_json),
-        updated_at = CURRENT_TIMESTAMP(3);
-    "#,
-    )
-    .bind(&parsed.tenant_id)
-    .bind(&channel_id)
-    .bind(as_of_dt)
-    .bind(&decision.direction)
-    .bind(decision.confidence)
-    .bind(evidence_json)
-    .bind(forbidden_json)
-    .bind(reevaluate_json)
-    .execute(pool)
-    .await
-    .map_err(|e| -> Error { Box::new(e) })?;
+    let backfill_run_for_dts = onboarding_backfill_run_for_dts(as_of_dt, backfill_weeks);
+    if !backfill_run_for_dts.is_empty() {
+        enqueue_daily_channel_tasks(pool, &parsed.tenant_id, &channel_id, &backfill_run_for_dts)
+            .await?;
+    }
 
     json_response(
         StatusCode::OK,
-        serde_json::json!({"ok": true, "channel_id": channel_id, "first_decision_as_of_dt": as_of_dt.to_string()}),
+        serde_json::json!({
+          "ok": true,
+          "channel_id": channel_id,
+          "state": state,
+          "connection_status": connection_status,
+          "first_decision_as_of_dt": as_of_dt.to_string(),
+          "backfill_run_for_dts": backfill_run_for_dts.iter().map(|dt| dt.to_string()).collect::<Vec<_>>(),
+        }),
     )
 }
 
 #[derive(Deserialize)]
-struct SetActiveChannelRequest {
+struct ConnectionActiveRequest {
     tenant_id: String,
+    active: bool,
+}
+
+const MAX_PINNED_CHANNELS: usize = 50;
+
+#[derive(Debug, serde::Serialize, PartialEq)]
+struct PinnedChannelItem {
     channel_id: String,
+    label: Option<String>,
+    is_default: bool,
 }
 
-async fn handle_set_active_channel(
+/// Pinned channels in pin order with the active (default) channel flagged; an active channel
+/// that was never pinned is listed first so the picker always offers it.
+fn pinned_channels_view(
+    default_channel_id: Option<&str>,
+    pinned: &[(String, Option<String>)],
+) -> Vec<PinnedChannelItem> {
+    let default_channel_id = default_channel_id.map(str::trim).filter(|v| !v.is_empty());
+    let mut items: Vec<PinnedChannelItem> = Vec::with_capacity(pinned.len() + 1);
+    if let Some(default_id) = default_channel_id {
+        if !pinned
+            .iter()
+            .any(|(channel_id, _)| channel_id == default_id)
+        {
+            items.push(PinnedChannelItem {
+                channel_id: default_id.to_string(),
+                label: None,
+                is_default: true,
+            });
+        }
+    }
+    items.extend(pinned.iter().map(|(channel_id, label)| PinnedChannelItem {
+        channel_id: channel_id.clone(),
+        label: label.clone(),
+        is_default: Some(channel_id.as_str()) == default_channel_id,
+    }));
+    items
+}
+
+#[derive(Deserialize)]
+struct PinnedChannelRequest {
+    tenant_id: String,
+    channel_id: String,
+    #[serde(default)]
+    op: Option<String>,
+    #[serde(default)]
+    label: Option<String>,
+}
+
+async fn handle_youtube_pinned_channels(
     method: &Method,
     headers: &HeaderMap,
-    body: Bytes,
+    uri: &Uri,
+    body: Option<Bytes>,
 ) -> Result<Response<ResponseBody>, Error> {
-    if method != Method::POST {
+    if method != Method::GET && method != Method::POST {
         return json_response(
             StatusCode::METHOD_NOT_ALLOWED,
             serde_json::json!({"ok": false, "error": "method_not_allowed"}),
@@ -859,7 +1102,6 @@ async fn handle_set_active_channel(
     let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
     let provided =
         bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
-
     if expected.is_empty() || provided != expected {
         return json_response(
             StatusCode::UNAUTHORIZED,
@@ -874,209 +1116,396 @@ async fn handle_set_active_channel(
         );
     }
 
-    let parsed: SetActiveChannelRequest = serde_json::from_slice(&body).map_err(|e| -> Error {
-        Box::new(std::io::Error::other(format!("invalid json body: {e}")))
-    })?;
-
-    let tenant_id = parsed.tenant_id.trim();
-    let channel_id = parsed.channel_id.trim();
-    if tenant_id.is_empty() || channel_id.is_empty() {
+    let (tenant_id, change) = if method == Method::POST {
+        let body = body.unwrap_or_default();
+        let parsed: PinnedChannelRequest = serde_json::from_slice(&body).map_err(|e| -> Error {
+            Box::new(std::io::Error::other(format!("invalid json body: {e}")))
+        })?;
+        (parsed.tenant_id.trim().to_string(), Some(parsed))
+    } else {
+        (
+            get_query_param(uri, "tenant_id")
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+            None,
+        )
+    };
+    if tenant_id.is_empty() {
         return json_response(
             StatusCode::BAD_REQUEST,
-            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id and channel_id are required"}),
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
         );
     }
 
     let pool = get_pool().await?;
+    let mut pinned = fetch_pinned_channels(pool, &tenant_id).await?;
 
-    let existing_channel_id = fetch_youtube_channel_id(pool, tenant_id).await?;
-    let Some(existing_channel_id) = existing_channel_id else {
-        return json_response(
-            StatusCode::NOT_FOUND,
-            serde_json::json!({"ok": false, "error": "not_connected", "message": "No YouTube channel connection found for this tenant"}),
-        );
-    };
-
-    let mut tokens = fetch_youtube_connection_tokens(pool, tenant_id, &existing_channel_id)
-        .await?
-        .ok_or_else(|| {
-            Box::new(std::io::Error::other("missing youtube channel connection")) as Error
-        })?;
-
-    // Proactive refresh if expired (best-effort).
-    let needs_refresh = tokens
-        .expires_at
-        .map(|dt| dt <= chrono::Utc::now())
-        .unwrap_or(false);
-    if needs_refresh {
-        if let Some(refresh) = tokens.refresh_token.clone() {
-            let app = fetch_or_seed_youtube_oauth_app_config(pool, tenant_id).await?;
-            let Some(app) = app else {
-                return json_response(
-                    StatusCode::NOT_FOUND,
-                    serde_json::json!({
-                      "ok": false,
-                      "error": "not_configured",
-                      "message": "Missing YouTube OAuth app config for tenant. Configure via /api/oauth/youtube/app_config or set YOUTUBE_CLIENT_ID/YOUTUBE_CLIENT_SECRET/YOUTUBE_REDIRECT_URI on the Rust backend."
-                    }),
-                );
-            };
-            let Some(client_secret) = app
-                .client_secret
-                .as_deref()
-                .map(str::trim)
-                .filter(|v| !v.is_empty())
-            else {
-                return json_response(
-                    StatusCode::NOT_FOUND,
-                    serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing YouTube OAuth client_secret for tenant"}),
-                );
-            };
-
-            let (client, _redirect) =
-                youtube_oauth_client_from_config(&app.client_id, client_secret, &app.redirect_uri)?;
-            let refreshed = refresh_tokens(&client, &refresh).await?;
-            update_youtube_connection_tokens(pool, tenant_id, &existing_channel_id, &refreshed)
-                .await?;
-            tokens.access_token = refreshed.access_token;
-            tokens.refresh_token = refreshed.refresh_token.or(Some(refresh));
+    if let Some(change) = change {
+        let channel_id = change.channel_id.trim();
+        if channel_id.is_empty() {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": "channel_id is required"}),
+            );
         }
-    }
-
-    let as_of_dt = Utc::now().date_naive();
-    let start_dt = as_of_dt - Duration::days(7);
-    let end_dt = as_of_dt - Duration::days(1);
-
-    let metrics = match fetch_video_daily_metrics_for_channel(
-        &tokens.access_token,
-        channel_id,
-        start_dt,
-        end_dt,
-    )
-    .await
-    {
-        Ok(rows) => rows,
-        Err(err) if err.status == Some(401) => {
-            if let Some(refresh) = tokens.refresh_token.clone() {
-                let app = fetch_or_seed_youtube_oauth_app_config(pool, tenant_id).await?;
-                let Some(app) = app else {
+        match change.op.as_deref().map(str::trim).unwrap_or("pin") {
+            "pin" => {
+                let already_pinned = pinned.iter().any(|(id, _)| id == channel_id);
+                if !already_pinned && pinned.len() >= MAX_PINNED_CHANNELS {
                     return json_response(
-                        StatusCode::NOT_FOUND,
+                        StatusCode::BAD_REQUEST,
                         serde_json::json!({
                           "ok": false,
-                          "error": "not_configured",
-                          "message": "Missing YouTube OAuth app config for tenant. Configure via /api/oauth/youtube/app_config or set YOUTUBE_CLIENT_ID/YOUTUBE_CLIENT_SECRET/YOUTUBE_REDIRECT_URI on the Rust backend."
+                          "error": "bad_request",
+                          "message": format!("at most {MAX_PINNED_CHANNELS} channels can be pinned"),
                         }),
                     );
-                };
-                let Some(client_secret) = app
-                    .client_secret
+                }
+                let label = change
+                    .label
                     .as_deref()
                     .map(str::trim)
                     .filter(|v| !v.is_empty())
-                else {
-                    return json_response(
-                        StatusCode::NOT_FOUND,
-                        serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing YouTube OAuth client_secret for tenant"}),
-                    );
-                };
-                let (client, _redirect) = youtube_oauth_client_from_config(
-                    &app.client_id,
-                    client_secret,
-                    &app.redirect_uri,
-                )?;
-                let refreshed = refresh_tokens(&client, &refresh).await?;
-                update_youtube_connection_tokens(pool, tenant_id, &existing_channel_id, &refreshed)
-                    .await?;
-                tokens.access_token = refreshed.access_token;
-
-                fetch_video_daily_metrics_for_channel(
-                    &tokens.access_token,
-                    channel_id,
-                    start_dt,
-                    end_dt,
-                )
-                .await
-                .map_err(youtube_analytics_error_to_vercel_error)?
-            } else {
+                    .map(|v| truncate_string(v, 255));
+                pin_channel(pool, &tenant_id, channel_id, label.as_deref()).await?;
+            }
+            "unpin" => {
+                unpin_channel(pool, &tenant_id, channel_id).await?;
+            }
+            _ => {
                 return json_response(
-                    StatusCode::UNAUTHORIZED,
-                    serde_json::json!({"ok": false, "error": "unauthorized", "message": "YouTube access token expired and no refresh token available"}),
+                    StatusCode::BAD_REQUEST,
+                    serde_json::json!({"ok": false, "error": "bad_request", "message": "op must be pin or unpin"}),
                 );
             }
         }
-        Err(err) if err.status == Some(403) => {
+        pinned = fetch_pinned_channels(pool, &tenant_id).await?;
+    }
+
+    let default_channel_id = fetch_youtube_channel_id(pool, &tenant_id).await?;
+    json_response(
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "default_channel_id": default_channel_id,
+          "items": pinned_channels_view(default_channel_id.as_deref(), &pinned),
+        }),
+    )
+}
+
+const DEFAULT_VIDEO_SNAPSHOT_CACHE_TTL_MS: u64 = 60_000;
+
+struct CachedVideoSnapshot {
+    snapshot: VideoSnapshot,
+    fetched_at: DateTime<Utc>,
+    expires_at: std::time::Instant,
+}
+
+static VIDEO_SNAPSHOT_CACHE: std::sync::OnceLock<
+    std::sync::Mutex<std::collections::HashMap<String, CachedVideoSnapshot>>,
+> = std::sync::OnceLock::new();
+
+/// `VIDEO_SNAPSHOT_CACHE_TTL_MS` (default 60s; `0` disables caching).
+fn video_snapshot_cache_ttl() -> std::time::Duration {
+    let ms = std::env::var("VIDEO_SNAPSHOT_CACHE_TTL_MS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_VIDEO_SNAPSHOT_CACHE_TTL_MS);
+    std::time::Duration::from_millis(ms)
+}
+
+/// Returns the cached snapshot for `key` while it is fresh, otherwise runs `fetch` and caches the
+/// result (errors are never cached). The flag is `true` when the value came from the cache.
+/// `force` skips the lookup but still refreshes the entry.
+async fn cached_video_snapshot<F, Fut, E>(
+    key: &str,
+    ttl: std::time::Duration,
+    force: bool,
+    fetch: F,
+) -> Result<(VideoSnapshot, DateTime<Utc>, bool), E>
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = Result<VideoSnapshot, E>>,
+{
+    let cache = VIDEO_SNAPSHOT_CACHE
+        .get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()));
+    if let Ok(mut guard) = cache.lock() {
+        match guard.get(key) {
+            Some(entry) if !force && entry.expires_at > std::time::Instant::now() => {
+                return Ok((entry.snapshot.clone(), entry.fetched_at, true));
+            }
+            Some(_) => {
+                guard.remove(key);
+            }
+            None => {}
+        }
+    }
+
+    let snapshot = fetch().await?;
+    let fetched_at = Utc::now();
+    if !ttl.is_zero() {
+        if let Ok(mut guard) = cache.lock() {
+            guard.insert(
+                key.to_string(),
+                CachedVideoSnapshot {
+                    snapshot: snapshot.clone(),
+                    fetched_at,
+                    expires_at: std::time::Instant::now() + ttl,
+                },
+            );
+        }
+    }
+    Ok((snapshot, fetched_at, false))
+}
+
+enum VideoSnapshotFetchError {
+    Token(YoutubeTokenError),
+    Api(YoutubeVideoError),
+}
+
+/// Current title/thumbnail/privacy/publishAt of one video, e.g. for an experiment setup screen.
+async fn handle_youtube_video_snapshot(
+    method: &Method,
+    headers: &HeaderMap,
+    uri: &Uri,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::GET {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let tenant_id = get_query_param(uri, "tenant_id").unwrap_or_default();
+    let video_id = get_query_param(uri, "video_id").unwrap_or_default();
+    if tenant_id.trim().is_empty() || video_id.trim().is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id and video_id are required"}),
+        );
+    }
+
+    let pool = get_pool().await?;
+    let channel_id = match get_query_param(uri, "channel_id")
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+    {
+        Some(v) => v,
+        None => fetch_youtube_channel_id(pool, tenant_id.trim())
+            .await?
+            .unwrap_or_default(),
+    };
+
+    if channel_id.trim().is_empty() {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "No active YouTube channel for this tenant"}),
+        );
+    }
+
+    let cache_key = format!(
+        "{}:{}:{}",
+        tenant_id.trim(),
+        channel_id.trim(),
+        video_id.trim()
+    );
+    let force = get_query_flag(uri, "force");
+    let fetched = cached_video_snapshot(&cache_key, video_snapshot_cache_ttl(), force, || async {
+        let tokens = ensure_fresh_youtube_tokens(pool, tenant_id.trim(), channel_id.trim())
+            .await
+            .map_err(VideoSnapshotFetchError::Token)?;
+        let _ = record_youtube_api_usage(
+            pool,
+            tenant_id.trim(),
+            channel_id.trim(),
+            "videos.list",
+            VIDEOS_LIST_QUOTA_UNITS,
+        )
+        .await;
+        fetch_video_snapshot(&tokens.access_token, video_id.trim())
+            .await
+            .map_err(VideoSnapshotFetchError::Api)
+    })
+    .await;
+
+    let (snapshot, fetched_at, cached) = match fetched {
+        Ok(v) => v,
+        Err(VideoSnapshotFetchError::Token(err)) => return youtube_token_error_response(err),
+        Err(VideoSnapshotFetchError::Api(err)) if err.status == Some(404) => {
             return json_response(
-                StatusCode::FORBIDDEN,
-                serde_json::json!({"ok": false, "error": "forbidden", "message": "No permission to access this channel's analytics", "details": err.to_string()}),
+                StatusCode::NOT_FOUND,
+                serde_json::json!({"ok": false, "error": "not_found", "message": err.to_string()}),
             );
         }
-        Err(err) => {
+        Err(VideoSnapshotFetchError::Api(err)) => {
             return json_response(
                 StatusCode::BAD_GATEWAY,
-                serde_json::json!({"ok": false, "error": "youtube_analytics_error", "message": err.to_string(), "status": err.status}),
+                serde_json::json!({"ok": false, "error": "youtube_api_error", "message": err.to_string(), "status": err.status}),
             );
         }
     };
 
-    for row in metrics.iter() {
-        upsert_video_daily_metric(
-            pool,
-            tenant_id,
-            channel_id,
-            row.dt,
-            &row.video_id,
-            row.estimated_revenue_usd,
-            row.impressions,
-            row.impressions_ctr,
-            row.views,
-        )
-        .await?;
+    json_response(
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "channel_id": channel_id,
+          "video_id": video_id.trim(),
+          "snapshot": {
+            "channel_id": snapshot.channel_id,
+            "title": snapshot.title,
+            "description": snapshot.description,
+            "category_id": snapshot.category_id,
+            "tags": snapshot.tags,
+            "privacy_status": snapshot.privacy_status,
+            "publish_at": snapshot.publish_at,
+            "thumbnail_url": snapshot.thumbnail_url,
+          },
+          "fetched_at": datetime_to_rfc3339_utc(fetched_at),
+          "cached": cached,
+        }),
+    )
+}
+
+async fn handle_connection_active(
+    method: &Method,
+    headers: &HeaderMap,
+    body: Bytes,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::POST {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
     }
 
-    let decision = compute_decision(
-        metrics.as_slice(),
-        as_of_dt,
-        start_dt,
-        end_dt,
-        DecisionEngineConfig::default(),
-    );
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
 
-    let evidence_json =
-        serde_json::to_string(&decision.evidence).unwrap_or_else(|_| "[]".to_string());
-    let forbidden_json =
-        serde_json::to_string(&decision.forbidden).unwrap_or_else(|_| "[]".to_string());
-    let reevaluate_json =
-        serde_json::to_string(&decision.reevaluate).unwrap_or_else(|_| "[]".to_string());
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
 
-    sqlx::query(
-        r#"
-      INSERT INTO decision_daily (
-        tenant_id, channel_id, as_of_dt,
-        direction, confidence,
-        evidence_json, forbidden_json, reevaluate_json
-      )
-      VALUES (?, ?, ?, ?, ?, ?, ?, ?)
-      ON DUPLICATE KEY UPDATE
-        direction = VALUES(direction),
-        confidence = VALUES(confidence),
-        evidence_json = VALUES(evidence_json),
-        forbidden_json = VALUES(forbidden_json),
-        reevaluate_json = VALUES(reevaluate_json),
-        updated_at = CURRENT_TIMESTAMP(3);
-    "#,
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let parsed: ConnectionActiveRequest = serde_json::from_slice(&body).map_err(|e| -> Error {
+        Box::new(std::io::Error::other(format!("invalid json body: {e}")))
+    })?;
+
+    let tenant_id = parsed.tenant_id.trim();
+    if tenant_id.is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+        );
+    }
+
+    let pool = get_pool().await?;
+    if !set_youtube_connection_active(pool, tenant_id, parsed.active).await? {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "No YouTube channel connection found for this tenant"}),
+        );
+    }
+
+    json_response(
+        StatusCode::OK,
+        serde_json::json!({"ok": true, "active": parsed.active}),
     )
-    .bind(tenant_id)
-    .bind(channel_id)
-    .bind(as_of_dt)
-    .bind(&decision.direction)
-    .bind(decision.confidence)
-    .bind(evidence_json)
-    .bind(forbidden_json)
-    .bind(reevaluate_json)
-    .execute(pool)
-    .await
-    .map_err(|e| -> Error { Box::new(e) })?;
+}
+
+#[derive(Deserialize)]
+struct SetActiveChannelRequest {
+    tenant_id: String,
+    channel_id: String,
+}
+
+async fn handle_set_active_channel(
+    method: &Method,
+    headers: &HeaderMap,
+    body: Bytes,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::POST {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let parsed: SetActiveChannelRequest = serde_json::from_slice(&body).map_err(|e| -> Error {
+        Box::new(std::io::Error::other(format!("invalid json body: {e}")))
+    })?;
+
+    let tenant_id = parsed.tenant_id.trim();
+    let channel_id = parsed.channel_id.trim();
+    if tenant_id.is_empty() || channel_id.is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id and channel_id are required"}),
+        );
+    }
+
+    let pool = get_pool().await?;
+
+    let existing_channel_id = fetch_youtube_channel_id(pool, tenant_id).await?;
+    let Some(existing_channel_id) = existing_channel_id else {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "No YouTube channel connection found for this tenant"}),
+        );
+    };
+
+    let as_of_dt = Utc::now().date_naive();
+    if let Err(err) =
+        create_first_decision(pool, tenant_id, &existing_channel_id, channel_id, as_of_dt).await
+    {
+        return first_decision_error_response(err);
+    }
 
     set_youtube_channel_id(pool, tenant_id, channel_id).await?;
 
@@ -1182,50 +1611,10 @@ async fn handle_youtube_channels_mine(
         );
     };
 
-    let mut tokens = fetch_youtube_connection_tokens(pool, &tenant_id, &channel_id)
-        .await?
-        .ok_or_else(|| {
-            Box::new(std::io::Error::other("missing youtube channel connection")) as Error
-        })?;
-
-    // Proactive refresh if expired (best-effort).
-    let needs_refresh = tokens
-        .expires_at
-        .map(|dt| dt <= chrono::Utc::now())
-        .unwrap_or(false);
-    if needs_refresh {
-        if let Some(refresh) = tokens.refresh_token.clone() {
-            let app = fetch_or_seed_youtube_oauth_app_config(pool, &tenant_id).await?;
-            let Some(app) = app else {
-                return json_response(
-                    StatusCode::NOT_FOUND,
-                    serde_json::json!({
-                      "ok": false,
-                      "error": "not_configured",
-                      "message": "Missing YouTube OAuth app config for tenant. Configure via /api/oauth/youtube/app_config or set YOUTUBE_CLIENT_ID/YOUTUBE_CLIENT_SECRET/YOUTUBE_REDIRECT_URI on the Rust backend."
-                    }),
-                );
-            };
-            let Some(client_secret) = app
-                .client_secret
-                .as_deref()
-                .map(str::trim)
-                .filter(|v| !v.is_empty())
-            else {
-                return json_response(
-                    StatusCode::NOT_FOUND,
-                    serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing YouTube OAuth client_secret for tenant"}),
-                );
-            };
-
-            let (client, _redirect) =
-                youtube_oauth_client_from_config(&app.client_id, client_secret, &app.redirect_uri)?;
-            let refreshed = refresh_tokens(&client, &refresh).await?;
-            update_youtube_connection_tokens(pool, &tenant_id, &channel_id, &refreshed).await?;
-            tokens.access_token = refreshed.access_token;
-            tokens.refresh_token = refreshed.refresh_token.or(Some(refresh));
-        }
-    }
+    let tokens = match ensure_fresh_youtube_tokens(pool, &tenant_id, &channel_id).await {
+        Ok(v) => v,
+        Err(err) => return youtube_token_error_response(err),
+    };
 
     let items = match list_my_channels(&tokens.access_token).await {
         Ok(items) => items,
@@ -1246,10 +1635,49 @@ async fn handle_youtube_channels_mine(
 #[derive(Deserialize)]
 struct AppConfigUpsertRequest {
     tenant_id: String,
-    client_id: String,
+    #[serde(default)]
+    client_id: Option<String>,
     #[serde(default)]
     client_secret: Option<String>,
-    redirect_uri: String,
+    #[serde(default)]
+    redirect_uri: Option<String>,
+}
+
+/// Merges a partial app config update over the stored config: omitted (or blank) fields keep
+/// their existing values, and the result must still be complete.
+fn merge_app_config_update(
+    existing: Option<&YoutubeOAuthAppConfig>,
+    update: &AppConfigUpsertRequest,
+) -> Result<YoutubeOAuthAppConfig, &'static str> {
+    fn provided(value: &Option<String>) -> Option<String> {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    }
+    fn stored(value: Option<&str>) -> Option<String> {
+        value
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    }
+
+    let client_id = provided(&update.client_id)
+        .or_else(|| stored(existing.map(|cfg| cfg.client_id.as_str())))
+        .ok_or("client_id is required")?;
+    let redirect_uri = provided(&update.redirect_uri)
+        .or_else(|| stored(existing.map(|cfg| cfg.redirect_uri.as_str())))
+        .ok_or("redirect_uri is required")?;
+    let client_secret = provided(&update.client_secret)
+        .or_else(|| stored(existing.and_then(|cfg| cfg.client_secret.as_deref())))
+        .ok_or("client_secret is required for initial setup")?;
+
+    Ok(YoutubeOAuthAppConfig {
+        client_id,
+        client_secret: Some(client_secret),
+        redirect_uri,
+    })
 }
 
 async fn handle_app_config(
@@ -1316,7 +1744,7 @@ async fn handle_app_config(
                 }),
             )
         }
-        Method::POST => {
+        Method::POST | Method::PATCH => {
             let body =
                 body.ok_or_else(|| Box::new(std::io::Error::other("missing body")) as Error)?;
             let parsed: AppConfigUpsertRequest =
@@ -1330,46 +1758,34 @@ async fn handle_app_config(
                     serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
                 );
             }
-            if parsed.client_id.trim().is_empty() {
-                return json_response(
-                    StatusCode::BAD_REQUEST,
-                    serde_json::json!({"ok": false, "error": "bad_request", "message": "client_id is required"}),
-                );
-            }
-            if parsed.redirect_uri.trim().is_empty() {
-                return json_response(
-                    StatusCode::BAD_REQUEST,
-                    serde_json::json!({"ok": false, "error": "bad_request", "message": "redirect_uri is required"}),
-                );
-            }
-
-            let secret = parsed
-                .client_secret
-                .as_deref()
-                .map(str::trim)
-                .filter(|v| !v.is_empty());
 
             let pool = get_pool().await?;
             let existing = fetch_youtube_oauth_app_config(pool, &parsed.tenant_id).await?;
-            let has_existing_secret = existing
-                .as_ref()
-                .and_then(|cfg| cfg.client_secret.as_deref())
-                .map(str::trim)
-                .is_some_and(|v| !v.is_empty());
+            let merged = match merge_app_config_update(existing.as_ref(), &parsed) {
+                Ok(v) => v,
+                Err(message) => {
+                    return json_response(
+                        StatusCode::BAD_REQUEST,
+                        serde_json::json!({"ok": false, "error": "bad_request", "message": message}),
+                    );
+                }
+            };
 
-            if secret.is_none() && !has_existing_secret {
+            if let Err(message) =
+                validate_redirect_uri(&merged.redirect_uri, &RedirectUriAllowList::from_env())
+            {
                 return json_response(
                     StatusCode::BAD_REQUEST,
-                    serde_json::json!({"ok": false, "error": "bad_request", "message": "client_secret is required for initial setup"}),
+                    serde_json::json!({"ok": false, "error": "redirect_uri_not_allowed", "message": message}),
                 );
             }
 
             upsert_youtube_oauth_app_config(
                 pool,
                 &parsed.tenant_id,
-                parsed.client_id.trim(),
-                secret,
-                parsed.redirect_uri.trim(),
+                &merged.client_id,
+                merged.client_secret.as_deref(),
+                &merged.redirect_uri,
             )
             .await?;
 
@@ -1438,57 +1854,11 @@ async fn handle_content_owner_discover(
         );
     };
 
-    let tokens = fetch_youtube_connection_tokens(pool, &parsed.tenant_id, &channel_id).await?;
-    let Some(mut tokens) = tokens else {
-        return json_response(
-            StatusCode::NOT_FOUND,
-            serde_json::json!({"ok": false, "error": "not_connected", "message": "No YouTube tokens found for this tenant"}),
-        );
+    let tokens = match ensure_fresh_youtube_tokens(pool, &parsed.tenant_id, &channel_id).await {
+        Ok(v) => v,
+        Err(err) => return youtube_token_error_response(err),
     };
 
-    // Best-effort proactive refresh if expired.
-    let needs_refresh = tokens
-        .expires_at
-        .map(|dt| dt <= chrono::Utc::now())
-        .unwrap_or(false);
-    if needs_refresh {
-        if let Some(refresh) = tokens.refresh_token.clone() {
-            let app = fetch_or_seed_youtube_oauth_app_config(pool, &parsed.tenant_id).await?;
-            let Some(app) = app else {
-                return json_response(
-                    StatusCode::NOT_FOUND,
-                    serde_json::json!({
-                      "ok": false,
-                      "error": "not_configured",
-                      "message": "Missing YouTube OAuth app config for tenant. Configure via /api/oauth/youtube/app_config or set YOUTUBE_CLIENT_ID/YOUTUBE_CLIENT_SECRET/YOUTUBE_REDIRECT_URI on the Rust backend."
-                    }),
-                );
-            };
-            let Some(client_secret) = app
-                .client_secret
-                .as_deref()
-                .map(str::trim)
-                .filter(|v| !v.is_empty())
-            else {
-                return json_response(
-                    StatusCode::NOT_FOUND,
-                    serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing YouTube OAuth client_secret for tenant"}),
-                );
-            };
-
-            let (client, _redirect) =
-                youtube_oauth_client_from_config(&app.client_id, client_secret, &app.redirect_uri)?;
-            let refreshed = refresh_tokens(&client, &refresh).await?;
-            update_youtube_connection_tokens(pool, &parsed.tenant_id, &channel_id, &refreshed)
-                .await?;
-            tokens.access_token = refreshed.access_token;
-            tokens.refresh_token = refreshed.refresh_token.or(Some(refresh));
-            tokens.expires_at = refreshed
-                .expires_in_seconds
-                .map(|secs| chrono::Utc::now() + chrono::Duration::seconds(secs as i64));
-        }
-    }
-
     let content_owner_id = fetch_my_content_owner_id(&tokens.access_token).await?;
     set_youtube_content_owner_id(pool, &parsed.tenant_id, content_owner_id.as_deref()).await?;
 
@@ -1506,10 +1876,295 @@ struct MetricDailyItem {
     views: i64,
     revenue_usd: f64,
     ctr: Option<f64>,
-    rpm: f64,
+    rpm: Option<f64>,
     source: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MetricsGranularity {
+    Day,
+    Week,
+    Month,
+}
+
+impl MetricsGranularity {
+    fn from_query(v: Option<&str>) -> Option<Self> {
+        match v.map(str::trim).filter(|v| !v.is_empty()) {
+            None | Some("day") => Some(MetricsGranularity::Day),
+            Some("week") => Some(MetricsGranularity::Week),
+            Some("month") => Some(MetricsGranularity::Month),
+            Some(_) => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            MetricsGranularity::Day => "day",
+            MetricsGranularity::Week => "week",
+            MetricsGranularity::Month => "month",
+        }
+    }
+
+    /// Buckets are keyed by their first day: the ISO week's Monday or the 1st of the month.
+    fn bucket_start(self, dt: NaiveDate) -> NaiveDate {
+        match self {
+            MetricsGranularity::Day => dt,
+            MetricsGranularity::Week => {
+                dt - Duration::days(i64::from(dt.weekday().num_days_from_monday()))
+            }
+            MetricsGranularity::Month => dt.with_day(1).unwrap_or(dt),
+        }
+    }
+}
+
+/// Sums `(dt, revenue_usd, impressions, views, ctr_num, ctr_denom)` day rows into buckets so CTR
+/// and RPM can be recomputed from bucket totals. Day rows are bucketed after the per-day source
+/// preference (CSV over API channel totals) has been applied.
+fn bucket_metric_rows(
+    rows: Vec<(NaiveDate, f64, i64, i64, f64, i64)>,
+    granularity: MetricsGranularity,
+) -> Vec<(NaiveDate, f64, i64, i64, f64, i64)> {
+    if granularity == MetricsGranularity::Day {
+        return rows;
+    }
+
+    let mut buckets: std::collections::BTreeMap<NaiveDate, (f64, i64, i64, f64, i64)> =
+        std::collections::BTreeMap::new();
+    for (dt, revenue_usd, impressions, views, ctr_num, ctr_denom) in rows {
+        let b = buckets
+            .entry(granularity.bucket_start(dt))
+            .or_insert((0.0, 0, 0, 0.0, 0));
+        b.0 += revenue_usd;
+        b.1 += impressions;
+        b.2 += views;
+        b.3 += ctr_num;
+        b.4 += ctr_denom;
+    }
+    buckets
+        .into_iter()
+        .map(
+            |(dt, (revenue_usd, impressions, views, ctr_num, ctr_denom))| {
+                (dt, revenue_usd, impressions, views, ctr_num, ctr_denom)
+            },
+        )
+        .collect()
+}
+
+#[derive(Debug, serde::Serialize)]
+struct ChannelMedianItem {
+    date: String,
+    median_rpm: Option<f64>,
+    median_ctr: Option<f64>,
+    videos: usize,
+}
+
+/// Per-bucket median RPM/CTR across the channel's videos, from
+/// `(dt, video_id, revenue_usd, views, ctr_num, ctr_denom)` rows. A video only counts towards a
+/// metric when it has at least `rpm_min_views` views (RPM) or CTR-bearing impressions (CTR) in
+/// that bucket.
+fn channel_median_series(
+    rows: Vec<(NaiveDate, String, f64, i64, f64, i64)>,
+    granularity: MetricsGranularity,
+    ctr_format: CtrFormat,
+    rpm_min_views: i64,
+) -> Vec<ChannelMedianItem> {
+    let mut per_video: std::collections::BTreeMap<(NaiveDate, String), (f64, i64, f64, i64)> =
+        std::collections::BTreeMap::new();
+    for (dt, video_id, revenue_usd, views, ctr_num, ctr_denom) in rows {
+        let v = per_video
+            .entry((granularity.bucket_start(dt), video_id))
+            .or_insert((0.0, 0, 0.0, 0));
+        v.0 += revenue_usd;
+        v.1 += views;
+        v.2 += ctr_num;
+        v.3 += ctr_denom;
+    }
+
+    let mut buckets: std::collections::BTreeMap<NaiveDate, (Vec<f64>, Vec<f64>, usize)> =
+        std::collections::BTreeMap::new();
+    for ((dt, _video_id), (revenue_usd, views, ctr_num, ctr_denom)) in per_video {
+        let b = buckets.entry(dt).or_default();
+        if views > 0 {
+            if let Some(rpm) = rpm_with_floor(revenue_usd, views, rpm_min_views) {
+                b.0.push(rpm);
+            }
+        }
+        if ctr_denom > 0 {
+            b.1.push(ctr_num / (ctr_denom as f64));
+        }
+        b.2 += 1;
+    }
+
+    buckets
+        .into_iter()
+        .map(|(dt, (mut rpms, mut ctrs, videos))| ChannelMedianItem {
+            date: dt.to_string(),
+            median_rpm: median_f64(&mut rpms).map(round2),
+            median_ctr: median_f64(&mut ctrs).map(|v| ctr_format.format(v)),
+            videos,
+        })
+        .collect()
+}
+
+/// Inserts zero-value rows for dates in `start_dt..=end_dt` that have no data, keeping order.
+fn fill_zero_days(
+    rows: Vec<(NaiveDate, f64, i64, i64, f64, i64)>,
+    start_dt: NaiveDate,
+    end_dt: NaiveDate,
+) -> Vec<(NaiveDate, f64, i64, i64, f64, i64)> {
+    let mut by_dt: std::collections::BTreeMap<NaiveDate, (NaiveDate, f64, i64, i64, f64, i64)> =
+        rows.into_iter().map(|row| (row.0, row)).collect();
+    let mut dt = start_dt;
+    while dt <= end_dt {
+        by_dt.entry(dt).or_insert((dt, 0.0, 0, 0, 0.0, 0));
+        dt += Duration::days(1);
+    }
+    by_dt.into_values().collect()
+}
+
+/// Weighted-CTR numerator/denominator over `video_daily_metrics`, mirroring `ctr_weight`: rows
+/// without a stored CTR still count when the reach report gave their clicks.
+const CTR_NUM_SQL: &str = "CAST(COALESCE(SUM(CASE WHEN impressions_ctr IS NOT NULL THEN impressions_ctr * impressions WHEN clicks IS NOT NULL AND impressions > 0 THEN LEAST(clicks, impressions) END), 0) AS DOUBLE)";
+const CTR_DENOM_SQL: &str = "CAST(COALESCE(SUM(CASE WHEN impressions_ctr IS NOT NULL OR (clicks IS NOT NULL AND impressions > 0) THEN impressions END), 0) AS SIGNED)";
+
+/// Per-day `(dt, revenue, impressions, views, ctr_num, ctr_denom)` over the stored rows matching
+/// `row_filter` (one video, or every video row when the channel has no stored totals).
+fn daily_metrics_sql(row_filter: &str) -> String {
+    format!(
+        r#"
+        SELECT dt,
+               CAST(SUM(estimated_revenue_usd) AS DOUBLE) AS revenue_usd,
+               CAST(SUM(impressions) AS SIGNED) AS impressions,
+               CAST(SUM(views) AS SIGNED) AS views,
+               {ctr_num} AS ctr_num,
+               {ctr_denom} AS ctr_denom
+        FROM video_daily_metrics
+        WHERE tenant_id = ?
+          AND channel_id = ?
+          AND dt BETWEEN ? AND ?
+          AND {row_filter}
+        GROUP BY dt
+        ORDER BY dt ASC;
+      "#,
+        ctr_num = CTR_NUM_SQL,
+        ctr_denom = CTR_DENOM_SQL,
+    )
+}
+
+/// Like `daily_metrics_sql`, from the channel-total rows (preferring the highest-priority source
+/// per day).
+fn channel_total_daily_metrics_sql() -> String {
+    format!(
+        r#"
+        SELECT dt,
+               CAST(COALESCE(
+                 {sums_0},
+                 0
+               ) AS DOUBLE) AS revenue_usd,
+               CAST(COALESCE(
+                 {sums_1},
+                 0
+               ) AS SIGNED) AS impressions,
+               CAST(COALESCE(
+                 {sums_2},
+                 0
+               ) AS SIGNED) AS views,
+               {ctr_num} AS ctr_num,
+               {ctr_denom} AS ctr_denom
+        FROM video_daily_metrics
+        WHERE tenant_id = ?
+          AND channel_id = ?
+          AND dt BETWEEN ? AND ?
+          AND {channel_totals}
+        GROUP BY dt
+        ORDER BY dt ASC;
+      "#,
+        sums_0 = channel_total_sums("estimated_revenue_usd"),
+        sums_1 = channel_total_sums("impressions"),
+        sums_2 = channel_total_sums("views"),
+        channel_totals = channel_total_filter(),
+        ctr_num = CTR_NUM_SQL,
+        ctr_denom = CTR_DENOM_SQL,
+    )
+}
+
+/// Per-day, per-video `(dt, video_id, revenue, views, ctr_num, ctr_denom)` for the channel median.
+fn per_video_daily_metrics_sql() -> String {
+    format!(
+        r#"
+        SELECT dt,
+               video_id,
+               CAST(SUM(estimated_revenue_usd) AS DOUBLE) AS revenue_usd,
+               CAST(SUM(views) AS SIGNED) AS views,
+               {ctr_num} AS ctr_num,
+               {ctr_denom} AS ctr_denom
+        FROM video_daily_metrics
+        WHERE tenant_id = ?
+          AND channel_id = ?
+          AND dt BETWEEN ? AND ?
+          AND {video_rows}
+        GROUP BY dt, video_id;
+      "#,
+        video_rows = video_rows_filter(),
+        ctr_num = CTR_NUM_SQL,
+        ctr_denom = CTR_DENOM_SQL,
+    )
+}
+
+/// Sums Analytics per-video rows into the `(dt, revenue, impressions, views, ctr_num, ctr_denom)`
+/// shape the daily-metrics query returns, optionally for one video.
+fn analytics_metric_rows(
+    rows: &[VideoDailyMetricRow],
+    video_id: Option<&str>,
+) -> Vec<(NaiveDate, f64, i64, i64, f64, i64)> {
+    let mut by_dt: std::collections::BTreeMap<NaiveDate, (f64, i64, i64, f64, i64)> =
+        std::collections::BTreeMap::new();
+    for row in rows
+        .iter()
+        .filter(|row| video_id.is_none_or(|id| row.video_id == id))
+    {
+        let entry = by_dt.entry(row.dt).or_default();
+        entry.0 += row.estimated_revenue_usd;
+        entry.1 += row.impressions;
+        entry.2 += row.views;
+        if let Some((num, denom)) = ctr_weight(row.impressions, row.impressions_ctr, None) {
+            entry.3 += num;
+            entry.4 += denom;
+        }
+    }
+    by_dt
+        .into_iter()
+        .map(|(dt, (revenue, impressions, views, ctr_num, ctr_denom))| {
+            (dt, revenue, impressions, views, ctr_num, ctr_denom)
+        })
+        .collect()
+}
+
+/// The `ok: false` body for a token refresh that failed before an Analytics call.
+fn youtube_token_error_body(
+    err: YoutubeTokenError,
+    channel_id: &str,
+    start_dt: NaiveDate,
+    end_dt: NaiveDate,
+) -> serde_json::Value {
+    let msg = err.to_string();
+    let code = match err {
+        YoutubeTokenError::MissingAppConfig | YoutubeTokenError::MissingClientSecret => {
+            ErrorCode::NotConfigured
+        }
+        YoutubeTokenError::NotConnected => ErrorCode::NotConnected,
+        _ => ErrorCode::UpstreamError,
+    };
+    serde_json::json!({
+        "ok": false,
+        "error": code.as_str(),
+        "message": msg,
+        "channel_id": channel_id,
+        "start_dt": start_dt.to_string(),
+        "end_dt": end_dt.to_string()
+    })
+}
+
 async fn handle_youtube_metrics_daily(
     method: &Method,
     headers: &HeaderMap,
@@ -1580,30 +2235,66 @@ async fn handle_youtube_metrics_daily(
         );
     }
 
+    let Some(granularity) =
+        MetricsGranularity::from_query(get_query_param(uri, "granularity").as_deref())
+    else {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "granularity must be day|week|month"}),
+        );
+    };
+
     let video_id_filter = get_query_param(uri, "video_id")
         .map(|v| v.trim().to_string())
         .filter(|v| !v.is_empty());
 
-    let rows: Vec<(NaiveDate, f64, i64, i64, f64, i64)> = if let Some(video_id) =
-        video_id_filter.as_deref()
-    {
-        sqlx::query_as::<_, (NaiveDate, f64, i64, i64, f64, i64)>(
-            r#"
-        SELECT dt,
-               CAST(SUM(estimated_revenue_usd) AS DOUBLE) AS revenue_usd,
-               CAST(SUM(impressions) AS SIGNED) AS impressions,
-               CAST(SUM(views) AS SIGNED) AS views,
-               CAST(COALESCE(SUM(impressions_ctr * impressions), 0) AS DOUBLE) AS ctr_num,
-               CAST(COALESCE(SUM(CASE WHEN impressions_ctr IS NOT NULL THEN impressions ELSE 0 END), 0) AS SIGNED) AS ctr_denom
-        FROM video_daily_metrics
-        WHERE tenant_id = ?
-          AND channel_id = ?
-          AND dt BETWEEN ? AND ?
-          AND video_id = ?
-        GROUP BY dt
-        ORDER BY dt ASC;
-      "#,
+    let force = get_query_flag(uri, "force");
+    let rows: Vec<(NaiveDate, f64, i64, i64, f64, i64)> = if force {
+        let access_token =
+            match ensure_fresh_youtube_tokens(pool, tenant_id.trim(), channel_id.trim()).await {
+                Ok(v) => v.access_token,
+                Err(err) => {
+                    return json_response(
+                        StatusCode::OK,
+                        youtube_token_error_body(err, &channel_id, start_dt, end_dt),
+                    );
+                }
+            };
+        let _ = record_youtube_api_usage(
+            pool,
+            tenant_id.trim(),
+            channel_id.trim(),
+            "reports.query",
+            REPORTS_QUERY_QUOTA_UNITS,
+        )
+        .await;
+        match fetch_video_daily_metrics_for_channel(
+            &access_token,
+            channel_id.trim(),
+            start_dt,
+            end_dt,
         )
+        .await
+        {
+            Ok(fetched) => analytics_metric_rows(&fetched, video_id_filter.as_deref()),
+            Err(err) => {
+                return json_response(
+                    StatusCode::OK,
+                    serde_json::json!({
+                        "ok": false,
+                        "error": "upstream_error",
+                        "message": err.to_string(),
+                        "channel_id": channel_id,
+                        "start_dt": start_dt.to_string(),
+                        "end_dt": end_dt.to_string()
+                    }),
+                );
+            }
+        }
+    } else if let Some(video_id) = video_id_filter.as_deref() {
+        sqlx::query_as::<_, (NaiveDate, f64, i64, i64, f64, i64)>(&daily_metrics_sql(
+            "video_id = ?",
+        ))
         .bind(tenant_id.trim())
         .bind(channel_id.trim())
         .bind(start_dt)
@@ -1614,33 +2305,7 @@ async fn handle_youtube_metrics_daily(
         .map_err(|e| -> Error { Box::new(e) })?
     } else {
         let totals = sqlx::query_as::<_, (NaiveDate, f64, i64, i64, f64, i64)>(
-            r#"
-        SELECT dt,
-               CAST(COALESCE(
-                 SUM(CASE WHEN video_id='csv_channel_total' THEN estimated_revenue_usd END),
-                 SUM(CASE WHEN video_id='__CHANNEL_TOTAL__' THEN estimated_revenue_usd END),
-                 0
-               ) AS DOUBLE) AS revenue_usd,
-               CAST(COALESCE(
-                 SUM(CASE WHEN video_id='csv_channel_total' THEN impressions END),
-                 SUM(CASE WHEN video_id='__CHANNEL_TOTAL__' THEN impressions END),
-                 0
-               ) AS SIGNED) AS impressions,
-               CAST(COALESCE(
-                 SUM(CASE WHEN video_id='csv_channel_total' THEN views END),
-                 SUM(CASE WHEN video_id='__CHANNEL_TOTAL__' THEN views END),
-                 0
-               ) AS SIGNED) AS views,
-               CAST(COALESCE(SUM(impressions_ctr * impressions), 0) AS DOUBLE) AS ctr_num,
-               CAST(COALESCE(SUM(CASE WHEN impressions_ctr IS NOT NULL THEN impressions ELSE 0 END), 0) AS SIGNED) AS ctr_denom
-        FROM video_daily_metrics
-        WHERE tenant_id = ?
-          AND channel_id = ?
-          AND dt BETWEEN ? AND ?
-          AND video_id IN ('__CHANNEL_TOTAL__','csv_channel_total')
-        GROUP BY dt
-        ORDER BY dt ASC;
-      "#,
+            &channel_total_daily_metrics_sql(),
         )
         .bind(tenant_id.trim())
         .bind(channel_id.trim())
@@ -1653,23 +2318,9 @@ async fn handle_youtube_metrics_daily(
         if !totals.is_empty() {
             totals
         } else {
-            sqlx::query_as::<_, (NaiveDate, f64, i64, i64, f64, i64)>(
-                r#"
-          SELECT dt,
-                 CAST(SUM(estimated_revenue_usd) AS DOUBLE) AS revenue_usd,
-                 CAST(SUM(impressions) AS SIGNED) AS impressions,
-                 CAST(SUM(views) AS SIGNED) AS views,
-                 CAST(COALESCE(SUM(impressions_ctr * impressions), 0) AS DOUBLE) AS ctr_num,
-                 CAST(COALESCE(SUM(CASE WHEN impressions_ctr IS NOT NULL THEN impressions ELSE 0 END), 0) AS SIGNED) AS ctr_denom
-          FROM video_daily_metrics
-          WHERE tenant_id = ?
-            AND channel_id = ?
-            AND dt BETWEEN ? AND ?
-            AND video_id NOT IN ('__CHANNEL_TOTAL__','csv_channel_total')
-          GROUP BY dt
-          ORDER BY dt ASC;
-        "#,
-            )
+            sqlx::query_as::<_, (NaiveDate, f64, i64, i64, f64, i64)>(&daily_metrics_sql(
+                video_rows_filter(),
+            ))
             .bind(tenant_id.trim())
             .bind(channel_id.trim())
             .bind(start_dt)
@@ -1680,30 +2331,60 @@ async fn handle_youtube_metrics_daily(
         }
     };
 
-    let video_id_out = video_id_filter.unwrap_or_else(|| "channel_total".to_string());
-    let items: Vec<MetricDailyItem> = rows
-        .into_iter()
-        .map(
-            |(dt, revenue_usd, impressions, views, ctr_num, ctr_denom)| {
-                let ctr = if ctr_denom > 0 {
-                    Some(ctr_num / (ctr_denom as f64))
-                } else {
-                    None
-                };
-                let rpm = if views > 0 {
-                    (revenue_usd / (views as f64)) * 1000.0
-                } else {
-                    0.0
-                };
-                MetricDailyItem {
-                    date: dt.to_string(),
-                    video_id: video_id_out.clone(),
+    let present_dts: Vec<NaiveDate> = rows.iter().map(|row| row.0).collect();
+    let completeness = window_completeness(start_dt, end_dt, &present_dts);
+    let include_zero_days = get_query_flag(uri, "include_zero_days");
+    let ctr_format = CtrFormat::from_uri(uri);
+    let rpm_min_views = rpm_min_views();
+    let rows = if include_zero_days {
+        fill_zero_days(rows, start_dt, end_dt)
+    } else {
+        rows
+    };
+
+    let channel_median =
+        if video_id_filter.is_some() && get_query_flag(uri, "compare_channel_median") {
+            let video_rows = sqlx::query_as::<_, (NaiveDate, String, f64, i64, f64, i64)>(
+                &per_video_daily_metrics_sql(),
+            )
+            .bind(tenant_id.trim())
+            .bind(channel_id.trim())
+            .bind(start_dt)
+            .bind(end_dt)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| -> Error { Box::new(e) })?;
+            Some(channel_median_series(
+                video_rows,
+                granularity,
+                ctr_format,
+                rpm_min_views,
+            ))
+        } else {
+            None
+        };
+
+    let source = if force { "youtube_analytics" } else { "tidb" };
+    let video_id_out = video_id_filter.unwrap_or_else(|| "channel_total".to_string());
+    let items: Vec<MetricDailyItem> = bucket_metric_rows(rows, granularity)
+        .into_iter()
+        .map(
+            |(dt, revenue_usd, impressions, views, ctr_num, ctr_denom)| {
+                let ctr = if ctr_denom > 0 {
+                    Some(ctr_num / (ctr_denom as f64))
+                } else {
+                    None
+                };
+                let rpm = rpm_with_floor(revenue_usd, views, rpm_min_views);
+                MetricDailyItem {
+                    date: dt.to_string(),
+                    video_id: video_id_out.clone(),
                     impressions,
                     views,
                     revenue_usd: round2(revenue_usd),
-                    ctr: ctr.map(|v| (v * 10000.0).round() / 10000.0),
-                    rpm: round2(rpm),
-                    source: "tidb".to_string(),
+                    ctr: ctr.map(|v| ctr_format.format(v)),
+                    rpm: rpm.map(round2),
+                    source: source.to_string(),
                 }
             },
         )
@@ -1711,7 +2392,7 @@ async fn handle_youtube_metrics_daily(
 
     json_response(
         StatusCode::OK,
-        serde_json::json!({"ok": true, "items": items, "channel_id": channel_id, "start_dt": start_dt.to_string(), "end_dt": end_dt.to_string()}),
+        serde_json::json!({"ok": true, "items": items, "source": source, "forced": force, "channel_id": channel_id, "start_dt": start_dt.to_string(), "end_dt": end_dt.to_string(), "granularity": granularity.as_str(), "include_zero_days": include_zero_days, "ctr_unit": ctr_format.as_str(), "completeness": completeness, "channel_median": channel_median}),
     )
 }
 
@@ -1719,6 +2400,8 @@ async fn handle_youtube_metrics_daily(
 struct SponsorQuoteDefaultsBasis {
     long_source: String,
     long_n: i64,
+    /// Shorts ids left out of the long-form ranking (`exclude_shorts=true`).
+    excluded_shorts: i64,
     shorts_source: String,
     shorts_n: i64,
 }
@@ -1768,6 +2451,12 @@ async fn handle_youtube_sponsor_quote_defaults(
     }
 
     let pool = get_pool().await?;
+    if let Some(response) =
+        feature_disabled_response(pool, tenant_id.trim(), TenantFeature::SponsorQuotes).await?
+    {
+        return Ok(response);
+    }
+
     let channel_id = match get_query_param(uri, "channel_id")
         .map(|v| v.trim().to_string())
         .filter(|v| !v.is_empty())
@@ -1788,55 +2477,45 @@ async fn handle_youtube_sponsor_quote_defaults(
     let today = Utc::now().date_naive();
     let start_dt = today - Duration::days(28);
     let end_dt = today;
+    let exclusion =
+        shorts_exclusion_for_request(pool, tenant_id.trim(), channel_id.trim(), uri).await?;
 
-    let rows = sqlx::query_as::<_, (String, i64)>(
-        r#"
-      SELECT video_id,
-             CAST(SUM(views) AS SIGNED) AS views_28d
-      FROM video_daily_metrics
-      WHERE tenant_id = ?
-        AND channel_id = ?
-        AND dt BETWEEN ? AND ?
-        AND video_id NOT IN ('__CHANNEL_TOTAL__','csv_channel_total')
-      GROUP BY video_id
-      ORDER BY views_28d DESC
-      LIMIT 10;
-    "#,
+    let rows = fetch_top_video_views(
+        pool,
+        tenant_id.trim(),
+        channel_id.trim(),
+        start_dt,
+        end_dt,
+        &exclusion,
     )
-    .bind(tenant_id.trim())
-    .bind(channel_id.trim())
-    .bind(start_dt)
-    .bind(end_dt)
-    .fetch_all(pool)
-    .await
-    .map_err(|e| -> Error { Box::new(e) })?;
+    .await?;
 
     let mut long_source = "top_10_video_views_28d_median".to_string();
     let mut long_n = rows.len() as i64;
 
-    let mut views: Vec<i64> = rows.iter().map(|(_, v)| *v).filter(|v| *v > 0).collect();
+    let mut views = long_form_views_basis(&rows, &exclusion, 10);
     if views.is_empty() {
         // Fallback: some channels/projects don't support `dimensions=day,video`, so TiDB has only
         // channel-total rows. Use YouTube Analytics `dimensions=video` as a best-effort source.
-        match ensure_fresh_youtube_access_token(pool, tenant_id.trim(), channel_id.trim()).await {
-            Ok(access_token) => {
+        match ensure_fresh_youtube_tokens(pool, tenant_id.trim(), channel_id.trim()).await {
+            Ok(YoutubeConnectionTokens { access_token, .. }) => {
                 match fetch_top_videos_by_views_for_channel(
                     &access_token,
                     channel_id.trim(),
                     start_dt,
                     end_dt,
-                    10,
+                    exclusion.api_fetch_limit(10),
                 )
                 .await
                 {
                     Ok(api_rows) => {
-                        views = api_rows
-                            .iter()
-                            .map(|r| r.views)
-                            .filter(|v| *v > 0)
+                        let api_rows: Vec<(String, i64)> = api_rows
+                            .into_iter()
+                            .map(|r| (r.video_id, r.views))
                             .collect();
+                        views = long_form_views_basis(&api_rows, &exclusion, 10);
                         long_source = "youtube_analytics_top10_video_views_28d_median".to_string();
-                        long_n = api_rows.len() as i64;
+                        long_n = api_rows.len().min(10) as i64;
                     }
                     Err(_err) => {
                         long_source = "fallback_default".to_string();
@@ -1860,6 +2539,7 @@ async fn handle_youtube_sponsor_quote_defaults(
         basis: SponsorQuoteDefaultsBasis {
             long_source,
             long_n,
+            excluded_shorts: exclusion.ids.len() as i64,
             shorts_source: "long_x0.6".to_string(),
             shorts_n: long_n,
         },
@@ -1879,9 +2559,228 @@ struct SponsorQuoteRequest {
     avg_views_long: Option<i64>,
     avg_views_shorts: Option<i64>,
     rpm_hint: Option<f64>,
+    #[serde(default)]
+    rpm_method: Option<String>,
+    #[serde(default)]
+    deliverables: Option<Vec<SponsorDeliverable>>,
+    /// Rank long-form only for the default views (see `ShortsExclusion`).
+    #[serde(default)]
+    exclude_shorts: bool,
+    #[serde(default)]
+    shorts_ids: Vec<String>,
 }
 
-#[derive(serde::Serialize)]
+/// A quotable deliverable: the fee is `views / 1000 * cpm * multiplier`, using the long-form or
+/// shorts average views depending on `format`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, Deserialize)]
+struct SponsorDeliverable {
+    name: String,
+    multiplier: f64,
+    #[serde(default = "default_sponsor_deliverable_format")]
+    format: String,
+}
+
+fn default_sponsor_deliverable_format() -> String {
+    "long".to_string()
+}
+
+const SPONSOR_DELIVERABLES_MAX: usize = 12;
+const SPONSOR_DELIVERABLE_MULTIPLIER_MAX: f64 = 10.0;
+
+fn default_sponsor_deliverables() -> Vec<SponsorDeliverable> {
+    [
+        ("integration", 1.0, "long"),
+        ("dedicated", 2.0, "long"),
+        ("shorts", 0.5, "shorts"),
+    ]
+    .into_iter()
+    .map(|(name, multiplier, format)| SponsorDeliverable {
+        name: name.to_string(),
+        multiplier,
+        format: format.to_string(),
+    })
+    .collect()
+}
+
+/// Validates a custom deliverable set, falling back to the defaults when none is given.
+fn resolve_sponsor_deliverables(
+    requested: Option<Vec<SponsorDeliverable>>,
+) -> Result<Vec<SponsorDeliverable>, String> {
+    let Some(requested) = requested.filter(|v| !v.is_empty()) else {
+        return Ok(default_sponsor_deliverables());
+    };
+    if requested.len() > SPONSOR_DELIVERABLES_MAX {
+        return Err(format!(
+            "at most {SPONSOR_DELIVERABLES_MAX} deliverables are allowed"
+        ));
+    }
+
+    let mut out: Vec<SponsorDeliverable> = Vec::with_capacity(requested.len());
+    for d in requested {
+        let name = d.name.trim();
+        if name.is_empty() || name.len() > 64 {
+            return Err("deliverable name must be 1-64 characters".to_string());
+        }
+        if out.iter().any(|v| v.name.eq_ignore_ascii_case(name)) {
+            return Err(format!("duplicate deliverable: {name}"));
+        }
+        if !d.multiplier.is_finite()
+            || d.multiplier <= 0.0
+            || d.multiplier > SPONSOR_DELIVERABLE_MULTIPLIER_MAX
+        {
+            return Err(format!(
+                "deliverable {name}: multiplier must be in (0, {SPONSOR_DELIVERABLE_MULTIPLIER_MAX}]"
+            ));
+        }
+        let format = d.format.trim().to_ascii_lowercase();
+        if format != "long" && format != "shorts" {
+            return Err(format!("deliverable {name}: format must be long|shorts"));
+        }
+        out.push(SponsorDeliverable {
+            name: name.to_string(),
+            multiplier: d.multiplier,
+            format,
+        });
+    }
+    Ok(out)
+}
+
+fn sponsor_quote_lines(
+    deliverables: &[SponsorDeliverable],
+    avg_views_long: i64,
+    avg_views_shorts: i64,
+    cpm_low: f64,
+    cpm_high: f64,
+) -> Vec<SponsorQuoteLine> {
+    deliverables
+        .iter()
+        .map(|d| {
+            let views = if d.format == "shorts" {
+                avg_views_shorts
+            } else {
+                avg_views_long
+            };
+            let low = ((views as f64) / 1000.0) * cpm_low * d.multiplier;
+            let high = ((views as f64) / 1000.0) * cpm_high * d.multiplier;
+            SponsorQuoteLine {
+                deliverable: d.name.clone(),
+                cpm_range: (cpm_low, cpm_high),
+                flat_fee_range: (low.round() as i64, high.round() as i64),
+                avg_views_used: views,
+            }
+        })
+        .collect()
+}
+
+/// How the sponsor quote's base RPM is derived when no `rpm_hint` is given. `Window` is the
+/// original single trailing 28-day window; the others trade recency for stability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SponsorRpmMethod {
+    Window,
+    Blended,
+    Trimmed,
+}
+
+impl SponsorRpmMethod {
+    fn from_param(v: Option<&str>) -> Option<Self> {
+        match v.map(str::trim).filter(|v| !v.is_empty()) {
+            None | Some("window") => Some(SponsorRpmMethod::Window),
+            Some("blended") => Some(SponsorRpmMethod::Blended),
+            Some("trimmed") => Some(SponsorRpmMethod::Trimmed),
+            Some(_) => None,
+        }
+    }
+}
+
+const SPONSOR_RPM_WINDOW_DAYS: i64 = 28;
+const SPONSOR_RPM_BLENDED_WINDOWS: i64 = 3;
+const SPONSOR_RPM_TRIMMED_DAYS: i64 = 90;
+const SPONSOR_RPM_TRIM_FRACTION: f64 = 0.1;
+const SPONSOR_RPM_FALLBACK: f64 = 12.0;
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, Deserialize)]
+struct SponsorRpmBasis {
+    method: String,
+    window_days: i64,
+    sample_size: i64,
+}
+
+/// Largest manual RPM baseline accepted, in USD per 1000 views.
+const MAX_MANUAL_RPM_USD: f64 = 1_000.0;
+
+/// An RPM the caller or creator supplied, which wins over any derived one: the request's
+/// `rpm_hint` first, then the channel's stored manual baseline.
+fn given_sponsor_rpm(
+    rpm_hint: Option<f64>,
+    manual_rpm: Option<f64>,
+) -> Option<(f64, SponsorRpmBasis)> {
+    let positive = |v: &f64| v.is_finite() && *v > 0.0;
+    let (rpm, method) = match (rpm_hint.filter(positive), manual_rpm.filter(positive)) {
+        (Some(hint), _) => (hint, "hint"),
+        (None, Some(manual)) => (manual, "manual"),
+        (None, None) => return None,
+    };
+    Some((
+        rpm,
+        SponsorRpmBasis {
+            method: method.to_string(),
+            window_days: 0,
+            sample_size: 0,
+        },
+    ))
+}
+
+/// Mean of per-window RPMs over `windows` consecutive `window_days` windows ending at `end_dt`,
+/// from `(dt, revenue_usd, views)` day rows. Windows without revenue are skipped; the sample
+/// size is the number of windows averaged.
+fn blended_window_rpm(
+    rows: &[(NaiveDate, f64, i64)],
+    end_dt: NaiveDate,
+    windows: i64,
+    window_days: i64,
+) -> Option<(f64, i64)> {
+    let mut rpms: Vec<f64> = Vec::new();
+    for w in 0..windows.max(1) {
+        let w_end = end_dt - Duration::days(w * window_days);
+        let w_start = w_end - Duration::days(window_days - 1);
+        let (revenue, views) = rows
+            .iter()
+            .filter(|(dt, _, _)| *dt >= w_start && *dt <= w_end)
+            .fold((0.0_f64, 0_i64), |(r, v), (_, rev, vw)| (r + rev, v + vw));
+        if views > 0 && revenue > 0.0 {
+            rpms.push((revenue / (views as f64)) * 1000.0);
+        }
+    }
+    if rpms.is_empty() {
+        return None;
+    }
+    Some((
+        rpms.iter().sum::<f64>() / (rpms.len() as f64),
+        rpms.len() as i64,
+    ))
+}
+
+/// Mean of daily RPMs after dropping `trim_fraction` of days from each tail, so a few viral or
+/// broken days don't move the base. The sample size is the number of days kept.
+fn trimmed_daily_rpm(rows: &[(NaiveDate, f64, i64)], trim_fraction: f64) -> Option<(f64, i64)> {
+    let mut rpms: Vec<f64> = rows
+        .iter()
+        .filter(|(_, revenue, views)| *views > 0 && *revenue > 0.0)
+        .map(|(_, revenue, views)| (revenue / (*views as f64)) * 1000.0)
+        .collect();
+    if rpms.is_empty() {
+        return None;
+    }
+    rpms.sort_by(|a, b| a.total_cmp(b));
+    let trim = ((rpms.len() as f64) * trim_fraction.clamp(0.0, 0.45)).floor() as usize;
+    let kept = &rpms[trim..rpms.len() - trim];
+    Some((
+        kept.iter().sum::<f64>() / (kept.len() as f64),
+        kept.len() as i64,
+    ))
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, Deserialize)]
 struct SponsorQuoteLine {
     deliverable: String,
     cpm_range: (f64, f64),
@@ -1889,6 +2788,51 @@ struct SponsorQuoteLine {
     avg_views_used: i64,
 }
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, Deserialize)]
+struct SponsorQuoteInputs {
+    avg_views_long: i64,
+    avg_views_shorts: i64,
+    rpm_hint: Option<f64>,
+    rpm_base: f64,
+    #[serde(default)]
+    rpm_basis: Option<SponsorRpmBasis>,
+    #[serde(default)]
+    deliverables: Vec<SponsorDeliverable>,
+}
+
+/// The persisted form of a quote (`yt_sponsor_quotes.quote_json`).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, Deserialize)]
+struct StoredSponsorQuote {
+    quote_id: String,
+    channel_id: String,
+    niches: Vec<String>,
+    inputs: SponsorQuoteInputs,
+    quotes: Vec<SponsorQuoteLine>,
+    created_at: String,
+}
+
+/// Render-ready view of a saved quote for a shareable page.
+fn sponsor_quote_share_payload(quote: &StoredSponsorQuote) -> serde_json::Value {
+    let lines: Vec<serde_json::Value> = quote
+        .quotes
+        .iter()
+        .map(|line| {
+            serde_json::json!({
+              "deliverable": line.deliverable,
+              "fee_label": format!("${}–${}", line.flat_fee_range.0, line.flat_fee_range.1),
+              "cpm_label": format!("${:.2}–${:.2} CPM", line.cpm_range.0, line.cpm_range.1),
+              "avg_views_used": line.avg_views_used,
+            })
+        })
+        .collect();
+    serde_json::json!({
+      "title": "Sponsorship rate card",
+      "niches": quote.niches,
+      "created_at": quote.created_at,
+      "lines": lines,
+    })
+}
+
 async fn handle_youtube_sponsor_quote(
     method: &Method,
     headers: &HeaderMap,
@@ -1918,7 +2862,7 @@ async fn handle_youtube_sponsor_quote(
         );
     }
 
-    let parsed: SponsorQuoteRequest = serde_json::from_slice(&body).map_err(|e| -> Error {
+    let mut parsed: SponsorQuoteRequest = serde_json::from_slice(&body).map_err(|e| -> Error {
         Box::new(std::io::Error::other(format!("invalid json body: {e}")))
     })?;
 
@@ -1930,6 +2874,13 @@ async fn handle_youtube_sponsor_quote(
     }
 
     let pool = get_pool().await?;
+    if let Some(response) =
+        feature_disabled_response(pool, parsed.tenant_id.trim(), TenantFeature::SponsorQuotes)
+            .await?
+    {
+        return Ok(response);
+    }
+
     let channel_id = match parsed
         .channel_id
         .as_deref()
@@ -1949,47 +2900,135 @@ async fn handle_youtube_sponsor_quote(
         );
     }
 
+    let deliverables = match resolve_sponsor_deliverables(parsed.deliverables.take()) {
+        Ok(v) => v,
+        Err(message) => {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": message}),
+            );
+        }
+    };
+
+    let Some(rpm_method) = SponsorRpmMethod::from_param(parsed.rpm_method.as_deref()) else {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "rpm_method must be window|blended|trimmed"}),
+        );
+    };
+
     let today = Utc::now().date_naive();
-    let start_dt = today - Duration::days(28);
+    let start_dt = today - Duration::days(SPONSOR_RPM_WINDOW_DAYS);
     let end_dt = today;
 
-    let defaults_rows = sqlx::query_as::<_, (String, i64)>(
-        r#"
-      SELECT video_id,
-             CAST(SUM(views) AS SIGNED) AS views_28d
-      FROM video_daily_metrics
-      WHERE tenant_id = ?
-        AND channel_id = ?
-        AND dt BETWEEN ? AND ?
-        AND video_id NOT IN ('__CHANNEL_TOTAL__','csv_channel_total')
-      GROUP BY video_id
-      ORDER BY views_28d DESC
-      LIMIT 10;
-    "#,
+    let shorts = if parsed.exclude_shorts {
+        let policy_params_json =
+            fetch_policy_params_json(pool, parsed.tenant_id.trim(), channel_id.trim(), "active")
+                .await?;
+        ShortsExclusion::from_sources(
+            true,
+            Some(&parsed.shorts_ids.join(",")),
+            policy_params_json.as_deref(),
+        )
+    } else {
+        ShortsExclusion::default()
+    };
+    let defaults_rows = fetch_top_video_views(
+        pool,
+        parsed.tenant_id.trim(),
+        channel_id.trim(),
+        start_dt,
+        end_dt,
+        &shorts,
     )
-    .bind(parsed.tenant_id.trim())
-    .bind(channel_id.trim())
-    .bind(start_dt)
-    .bind(end_dt)
-    .fetch_all(pool)
-    .await
-    .map_err(|e| -> Error { Box::new(e) })?;
+    .await?;
 
-    let mut default_views: Vec<i64> = defaults_rows
-        .iter()
-        .map(|(_, v)| *v)
-        .filter(|v| *v > 0)
-        .collect();
+    let mut default_views = long_form_views_basis(&defaults_rows, &shorts, 10);
     let default_long = median_i64(&mut default_views).unwrap_or(50_000);
     let default_shorts = ((default_long as f64) * 0.6).round() as i64;
 
     let avg_views_long = parsed.avg_views_long.unwrap_or(default_long).max(1);
     let avg_views_shorts = parsed.avg_views_shorts.unwrap_or(default_shorts).max(1);
 
-    let rpm_base = if let Some(hint) = parsed.rpm_hint.filter(|v| *v > 0.0) {
-        hint
+    let manual_rpm = fetch_channel_rpm_baseline(pool, parsed.tenant_id.trim(), channel_id.trim())
+        .await?
+        .map(|(rpm, _)| rpm);
+    let (rpm_base, rpm_basis) = if let Some(given) = given_sponsor_rpm(parsed.rpm_hint, manual_rpm)
+    {
+        given
+    } else if rpm_method != SponsorRpmMethod::Window {
+        let lookback_days = match rpm_method {
+            SponsorRpmMethod::Trimmed => SPONSOR_RPM_TRIMMED_DAYS,
+            _ => SPONSOR_RPM_WINDOW_DAYS * SPONSOR_RPM_BLENDED_WINDOWS,
+        };
+        // Per day: CSV channel total, else API channel total, else the per-video sum.
+        let daily = sqlx::query_as::<_, (NaiveDate, f64, i64)>(&format!(
+            r#"
+        SELECT dt,
+               CAST(COALESCE(
+                 {sums_0},
+                 SUM(CASE WHEN {video_rows} THEN estimated_revenue_usd END),
+                 0
+               ) AS DOUBLE) AS revenue_usd,
+               CAST(COALESCE(
+                 {sums_1},
+                 SUM(CASE WHEN {video_rows} THEN views END),
+                 0
+               ) AS SIGNED) AS views
+        FROM video_daily_metrics
+        WHERE tenant_id = ?
+          AND channel_id = ?
+          AND dt BETWEEN ? AND ?
+        GROUP BY dt
+        ORDER BY dt ASC;
+      "#,
+            sums_0 = channel_total_sums("estimated_revenue_usd"),
+            sums_1 = channel_total_sums("views"),
+            video_rows = video_rows_filter()
+        ))
+        .bind(parsed.tenant_id.trim())
+        .bind(channel_id.trim())
+        .bind(end_dt - Duration::days(lookback_days - 1))
+        .bind(end_dt)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| -> Error { Box::new(e) })?;
+
+        let (method, derived) = match rpm_method {
+            SponsorRpmMethod::Trimmed => (
+                "trimmed",
+                trimmed_daily_rpm(&daily, SPONSOR_RPM_TRIM_FRACTION),
+            ),
+            _ => (
+                "blended",
+                blended_window_rpm(
+                    &daily,
+                    end_dt,
+                    SPONSOR_RPM_BLENDED_WINDOWS,
+                    SPONSOR_RPM_WINDOW_DAYS,
+                ),
+            ),
+        };
+        match derived {
+            Some((rpm, sample_size)) => (
+                rpm,
+                SponsorRpmBasis {
+                    method: method.to_string(),
+                    window_days: lookback_days,
+                    sample_size,
+                },
+            ),
+            None => (
+                SPONSOR_RPM_FALLBACK,
+                SponsorRpmBasis {
+                    method: "fallback".to_string(),
+                    window_days: lookback_days,
+                    sample_size: 0,
+                },
+            ),
+        }
     } else {
-        let (total_rows, total_rev, total_views) = sqlx::query_as::<_, (i64, f64, i64)>(
+        let (total_rows, total_rev, total_views) = sqlx::query_as::<_, (i64, f64, i64)>(&format!(
             r#"
         SELECT CAST(COUNT(*) AS SIGNED) AS rows_n,
                CAST(COALESCE(SUM(estimated_revenue_usd), 0) AS DOUBLE) AS revenue_usd,
@@ -1998,9 +3037,10 @@ async fn handle_youtube_sponsor_quote(
         WHERE tenant_id = ?
           AND channel_id = ?
           AND dt BETWEEN ? AND ?
-          AND video_id IN ('__CHANNEL_TOTAL__','csv_channel_total');
+          AND {channel_totals};
       "#,
-        )
+            channel_totals = channel_total_filter()
+        ))
         .bind(parsed.tenant_id.trim())
         .bind(channel_id.trim())
         .bind(start_dt)
@@ -2012,7 +3052,7 @@ async fn handle_youtube_sponsor_quote(
         let (revenue, views) = if total_rows > 0 {
             (total_rev, total_views)
         } else {
-            sqlx::query_as::<_, (f64, i64)>(
+            sqlx::query_as::<_, (f64, i64)>(&format!(
                 r#"
           SELECT CAST(COALESCE(SUM(estimated_revenue_usd), 0) AS DOUBLE) AS revenue_usd,
                  CAST(COALESCE(SUM(views), 0) AS SIGNED) AS views
@@ -2020,9 +3060,10 @@ async fn handle_youtube_sponsor_quote(
           WHERE tenant_id = ?
             AND channel_id = ?
             AND dt BETWEEN ? AND ?
-            AND video_id NOT IN ('__CHANNEL_TOTAL__','csv_channel_total');
+            AND {video_rows};
         "#,
-            )
+                video_rows = video_rows_filter()
+            ))
             .bind(parsed.tenant_id.trim())
             .bind(channel_id.trim())
             .bind(start_dt)
@@ -2033,50 +3074,288 @@ async fn handle_youtube_sponsor_quote(
         };
 
         if views > 0 && revenue > 0.0 {
-            (revenue / (views as f64)) * 1000.0
+            (
+                (revenue / (views as f64)) * 1000.0,
+                SponsorRpmBasis {
+                    method: "window".to_string(),
+                    window_days: SPONSOR_RPM_WINDOW_DAYS,
+                    sample_size: total_rows,
+                },
+            )
         } else {
-            12.0
+            (
+                SPONSOR_RPM_FALLBACK,
+                SponsorRpmBasis {
+                    method: "fallback".to_string(),
+                    window_days: SPONSOR_RPM_WINDOW_DAYS,
+                    sample_size: 0,
+                },
+            )
         }
     };
 
     let cpm_low = round2(rpm_base * 0.8);
     let cpm_high = round2(rpm_base * 1.4);
 
-    let deliverables = vec![
-        ("integration", avg_views_long, 1.0_f64),
-        ("dedicated", avg_views_long, 2.0_f64),
-        ("shorts", avg_views_shorts, 0.5_f64),
-    ];
+    let quotes = sponsor_quote_lines(
+        &deliverables,
+        avg_views_long,
+        avg_views_shorts,
+        cpm_low,
+        cpm_high,
+    );
 
-    let quotes: Vec<SponsorQuoteLine> = deliverables
-        .into_iter()
-        .map(|(deliverable, views, multiplier)| {
-            let low = ((views as f64) / 1000.0) * cpm_low * multiplier;
-            let high = ((views as f64) / 1000.0) * cpm_high * multiplier;
-            SponsorQuoteLine {
-                deliverable: deliverable.to_string(),
-                cpm_range: (cpm_low, cpm_high),
-                flat_fee_range: (low.round() as i64, high.round() as i64),
-                avg_views_used: views,
-            }
-        })
-        .collect();
+    // quote_id doubles as the share token, so it must not be guessable.
+    let quote_id = format!("quote_{}", gen_share_token()?);
+    let stored = StoredSponsorQuote {
+        quote_id: quote_id.clone(),
+        channel_id: channel_id.clone(),
+        niches: parsed.niches.unwrap_or_default(),
+        inputs: SponsorQuoteInputs {
+            avg_views_long,
+            avg_views_shorts,
+            rpm_hint: parsed.rpm_hint,
+            rpm_base: round2(rpm_base),
+            rpm_basis: Some(rpm_basis),
+            deliverables,
+        },
+        quotes,
+        created_at: datetime_to_rfc3339_utc(Utc::now()),
+    };
+    let quote_json = serde_json::to_string(&stored).map_err(|e| -> Error { Box::new(e) })?;
 
-    let quote_id = format!("quote_{}", now_ms());
+    sqlx::query(
+        r#"
+          INSERT INTO yt_sponsor_quotes
+            (quote_id, tenant_id, channel_id, quote_json)
+          VALUES
+            (?, ?, ?, ?);
+        "#,
+    )
+    .bind(quote_id.as_str())
+    .bind(parsed.tenant_id.trim())
+    .bind(channel_id.trim())
+    .bind(quote_json)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
 
     json_response(
         StatusCode::OK,
         serde_json::json!({
           "ok": true,
           "quote_id": quote_id,
-          "quotes": quotes,
+          "quotes": stored.quotes,
           "channel_id": channel_id,
-          "niches": parsed.niches.unwrap_or_default(),
+          "niches": stored.niches,
+          "rpm_base": stored.inputs.rpm_base,
+          "rpm_basis": stored.inputs.rpm_basis,
+          "deliverables": stored.inputs.deliverables,
+          "created_at": stored.created_at,
         }),
     )
 }
 
-#[derive(serde::Serialize)]
+#[derive(Deserialize)]
+struct RpmBaselinePutRequest {
+    tenant_id: String,
+    #[serde(default)]
+    channel_id: Option<String>,
+    /// `null` removes the baseline so quotes derive the RPM again.
+    #[serde(default)]
+    rpm_usd: Option<f64>,
+    #[serde(default)]
+    note: Option<String>,
+}
+
+/// GET/POST a channel's manual RPM baseline, which sponsor quotes use (as `rpm_basis.method:
+/// "manual"`) unless the request passes its own `rpm_hint`.
+async fn handle_youtube_rpm_baseline(
+    method: &Method,
+    headers: &HeaderMap,
+    uri: &Uri,
+    body: Option<Bytes>,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::GET && method != Method::POST {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let (tenant_id, channel_id, put) = if method == Method::POST {
+        let Some(body) = body else {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": "missing body"}),
+            );
+        };
+        let parsed: RpmBaselinePutRequest =
+            serde_json::from_slice(&body).map_err(|e| -> Error {
+                Box::new(std::io::Error::other(format!("invalid json body: {e}")))
+            })?;
+        if parsed
+            .rpm_usd
+            .is_some_and(|v| !v.is_finite() || v <= 0.0 || v > MAX_MANUAL_RPM_USD)
+        {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": format!("rpm_usd must be > 0 and <= {MAX_MANUAL_RPM_USD}")}),
+            );
+        }
+        let note = parsed
+            .note
+            .map(|v| truncate_string(v.trim(), 500))
+            .filter(|v| !v.is_empty());
+        (
+            parsed.tenant_id.trim().to_string(),
+            parsed.channel_id,
+            Some((parsed.rpm_usd, note)),
+        )
+    } else {
+        (
+            get_query_param(uri, "tenant_id")
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+            get_query_param(uri, "channel_id"),
+            None,
+        )
+    };
+
+    if tenant_id.is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+        );
+    }
+
+    let pool = get_pool().await?;
+    let channel_id = match channel_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+    {
+        Some(v) => v.to_string(),
+        None => fetch_youtube_channel_id(pool, &tenant_id)
+            .await?
+            .unwrap_or_default(),
+    };
+
+    if channel_id.trim().is_empty() {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "No active YouTube channel for this tenant"}),
+        );
+    }
+
+    if let Some((rpm_usd, note)) = put {
+        match rpm_usd {
+            Some(rpm_usd) => {
+                upsert_channel_rpm_baseline(pool, &tenant_id, &channel_id, rpm_usd, note.as_deref())
+                    .await?
+            }
+            None => delete_channel_rpm_baseline(pool, &tenant_id, &channel_id).await?,
+        }
+    }
+
+    let baseline = fetch_channel_rpm_baseline(pool, &tenant_id, &channel_id).await?;
+    json_response(
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "channel_id": channel_id,
+          "rpm_usd": baseline.as_ref().map(|(rpm, _)| *rpm),
+          "note": baseline.and_then(|(_, note)| note),
+          "source": "manual",
+        }),
+    )
+}
+
+/// Public (share-link) read of a saved quote; the unguessable quote_id is the credential, as with
+/// report share tokens.
+async fn handle_youtube_sponsor_quote_get(
+    method: &Method,
+    uri: &Uri,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::GET {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let quote_id = get_query_param(uri, "quote_id").unwrap_or_default();
+    let quote_id = quote_id.trim();
+    if quote_id.is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "quote_id is required"}),
+        );
+    }
+
+    let pool = get_pool().await?;
+    let row = sqlx::query_as::<_, (String,)>(
+        r#"
+          SELECT quote_json
+          FROM yt_sponsor_quotes
+          WHERE quote_id = ?
+          LIMIT 1;
+        "#,
+    )
+    .bind(quote_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    let Some((quote_json,)) = row else {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_found"}),
+        );
+    };
+
+    let quote: StoredSponsorQuote =
+        serde_json::from_str(&quote_json).map_err(|e| -> Error { Box::new(e) })?;
+
+    json_response(StatusCode::OK, sponsor_quote_public_body(&quote))
+}
+
+/// Share-link response body. It carries only the render-ready share payload, never the stored
+/// `inputs`: the channel's RPM baseline and hint are private to the creator.
+fn sponsor_quote_public_body(quote: &StoredSponsorQuote) -> serde_json::Value {
+    serde_json::json!({
+      "ok": true,
+      "quote_id": quote.quote_id,
+      "share": sponsor_quote_share_payload(quote),
+    })
+}
+
+#[derive(serde::Serialize)]
 struct SyncStatusTaskItem {
     id: i64,
     job_type: String,
@@ -2234,17 +3513,258 @@ async fn handle_youtube_sync_status(
     )
 }
 
-#[derive(serde::Serialize)]
-struct TopVideoItem {
-    video_id: String,
-    views: i64,
-    impressions: i64,
+/// Most Shorts ids one request carries into SQL, and the Analytics API's `maxResults` cap.
+const MAX_EXCLUDED_SHORTS: usize = 500;
+const ANALYTICS_TOP_VIDEOS_MAX_RESULTS: i64 = 200;
+
+#[derive(Deserialize)]
+struct ShortsPolicyParamsJson {
+    #[serde(default)]
+    shorts_video_ids: Vec<String>,
+    #[serde(default)]
+    long_form_video_ids: Vec<String>,
+}
+
+/// Comma-separated query ids plus policy ids, trimmed, deduplicated and capped.
+fn merge_video_ids(query_ids: Option<&str>, from_policy: Vec<String>) -> Vec<String> {
+    let mut ids: Vec<String> = query_ids
+        .unwrap_or("")
+        .split(',')
+        .map(str::to_string)
+        .chain(from_policy)
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect();
+    ids.sort();
+    ids.dedup();
+    ids.truncate(MAX_EXCLUDED_SHORTS);
+    ids
+}
+
+/// Video ids left out of "top videos" rankings when a caller asks for long-form only
+/// (`exclude_shorts=true`): the comma-separated `shorts_ids` query param plus the channel's
+/// `policy_params.shorts_video_ids`. Without the flag nothing is excluded.
+#[derive(Debug, Default, Clone, PartialEq)]
+struct ShortsExclusion {
+    ids: Vec<String>,
+}
+
+impl ShortsExclusion {
+    fn from_sources(
+        exclude_shorts: bool,
+        query_ids: Option<&str>,
+        policy_params_json: Option<&str>,
+    ) -> Self {
+        if !exclude_shorts {
+            return Self::default();
+        }
+        let from_policy = policy_params_json
+            .and_then(|raw| serde_json::from_str::<ShortsPolicyParamsJson>(raw).ok())
+            .map(|p| p.shorts_video_ids)
+            .unwrap_or_default();
+        Self {
+            ids: merge_video_ids(query_ids, from_policy),
+        }
+    }
+
+    /// `AND video_id NOT IN (?, …)`, bound with `ids` in order; empty when nothing is excluded.
+    fn sql_filter(&self) -> String {
+        if self.ids.is_empty() {
+            return String::new();
+        }
+        format!(
+            "AND video_id NOT IN ({})",
+            vec!["?"; self.ids.len()].join(", ")
+        )
+    }
+
+    fn keeps(&self, video_id: &str) -> bool {
+        !self.ids.iter().any(|id| id == video_id)
+    }
+
+    /// The API ranks Shorts too, so ask for enough extra rows that `limit` long-form ones remain.
+    fn api_fetch_limit(&self, limit: i64) -> i64 {
+        (limit + self.ids.len() as i64).min(ANALYTICS_TOP_VIDEOS_MAX_RESULTS)
+    }
+
+    fn retain_long_form<T>(&self, items: &mut Vec<T>, video_id: impl Fn(&T) -> &str, limit: usize) {
+        items.retain(|item| self.keeps(video_id(item)));
+        items.truncate(limit);
+    }
+}
+
+async fn shorts_exclusion_for_request(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    uri: &Uri,
+) -> Result<ShortsExclusion, Error> {
+    if !get_query_flag(uri, "exclude_shorts") {
+        return Ok(ShortsExclusion::default());
+    }
+    let policy_params_json =
+        fetch_policy_params_json(pool, tenant_id, channel_id, "active").await?;
+    Ok(ShortsExclusion::from_sources(
+        true,
+        get_query_param(uri, "shorts_ids").as_deref(),
+        policy_params_json.as_deref(),
+    ))
+}
+
+/// Top 10 videos by views over the window, from stored per-video rows.
+async fn fetch_top_video_views(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    start_dt: NaiveDate,
+    end_dt: NaiveDate,
+    shorts: &ShortsExclusion,
+) -> Result<Vec<(String, i64)>, Error> {
+    let sql = format!(
+        r#"
+      SELECT video_id,
+             CAST(SUM(views) AS SIGNED) AS views_28d
+      FROM video_daily_metrics
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND dt BETWEEN ? AND ?
+        AND {video_rows}
+        {shorts_filter}
+      GROUP BY video_id
+      ORDER BY views_28d DESC
+      LIMIT 10;
+    "#,
+        video_rows = video_rows_filter(),
+        shorts_filter = shorts.sql_filter(),
+    );
+    let mut query = sqlx::query_as::<_, (String, i64)>(&sql)
+        .bind(tenant_id)
+        .bind(channel_id)
+        .bind(start_dt)
+        .bind(end_dt);
+    for id in &shorts.ids {
+        query = query.bind(id);
+    }
+    query
+        .fetch_all(pool)
+        .await
+        .map_err(|e| -> Error { Box::new(e) })
+}
+
+/// Views of the top `top_n` long-form videos, the basis of the sponsor quote's median.
+fn long_form_views_basis(
+    rows: &[(String, i64)],
+    exclusion: &ShortsExclusion,
+    top_n: usize,
+) -> Vec<i64> {
+    let mut rows: Vec<&(String, i64)> = rows.iter().collect();
+    exclusion.retain_long_form(&mut rows, |(id, _)| id.as_str(), top_n);
+    rows.into_iter()
+        .map(|(_, v)| *v)
+        .filter(|v| *v > 0)
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VideoClass {
+    Short,
+    LongForm,
+    Unknown,
+}
+
+/// Which videos are Shorts and which are long-form, from the `shorts_ids` / `long_form_ids`
+/// query params and the channel's `policy_params.shorts_video_ids` / `long_form_video_ids`.
+/// With only one list, every other video is in the other class; with both, videos in neither are
+/// `Unknown`; with none, every video is.
+#[derive(Debug, Default, Clone, PartialEq)]
+struct VideoClassification {
+    shorts: Vec<String>,
+    long_form: Vec<String>,
+}
+
+impl VideoClassification {
+    fn from_sources(
+        query_shorts: Option<&str>,
+        query_long_form: Option<&str>,
+        policy_params_json: Option<&str>,
+    ) -> Self {
+        let policy = policy_params_json
+            .and_then(|raw| serde_json::from_str::<ShortsPolicyParamsJson>(raw).ok());
+        let (policy_shorts, policy_long_form) = match policy {
+            Some(p) => (p.shorts_video_ids, p.long_form_video_ids),
+            None => (Vec::new(), Vec::new()),
+        };
+        Self {
+            shorts: merge_video_ids(query_shorts, policy_shorts),
+            long_form: merge_video_ids(query_long_form, policy_long_form),
+        }
+    }
+
+    fn classify(&self, video_id: &str) -> VideoClass {
+        let listed = |ids: &[String]| ids.iter().any(|id| id == video_id);
+        if listed(&self.shorts) {
+            VideoClass::Short
+        } else if listed(&self.long_form) {
+            VideoClass::LongForm
+        } else {
+            match (self.shorts.is_empty(), self.long_form.is_empty()) {
+                (false, true) => VideoClass::LongForm,
+                (true, false) => VideoClass::Short,
+                _ => VideoClass::Unknown,
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize)]
+struct VideoClassTotals {
+    videos: i64,
     revenue_usd: f64,
-    ctr: Option<f64>,
-    rpm: f64,
+    views: i64,
+    /// Of the window's total; `None` when the total is zero.
+    revenue_share: Option<f64>,
+    views_share: Option<f64>,
 }
 
-async fn handle_youtube_top_videos(
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize)]
+struct ShortsRevenueSplit {
+    shorts: VideoClassTotals,
+    long_form: VideoClassTotals,
+    unknown: VideoClassTotals,
+    total_revenue_usd: f64,
+    total_views: i64,
+}
+
+/// Splits per-video window totals `(video_id, revenue_usd, views)` between the three classes.
+fn split_revenue_by_video_class(
+    rows: &[(String, f64, i64)],
+    classification: &VideoClassification,
+) -> ShortsRevenueSplit {
+    let mut split = ShortsRevenueSplit::default();
+    for (video_id, revenue_usd, views) in rows {
+        let bucket = match classification.classify(video_id) {
+            VideoClass::Short => &mut split.shorts,
+            VideoClass::LongForm => &mut split.long_form,
+            VideoClass::Unknown => &mut split.unknown,
+        };
+        bucket.videos += 1;
+        bucket.revenue_usd += revenue_usd;
+        bucket.views += views;
+        split.total_revenue_usd += revenue_usd;
+        split.total_views += views;
+    }
+    let (total_revenue_usd, total_views) = (split.total_revenue_usd, split.total_views);
+    for bucket in [&mut split.shorts, &mut split.long_form, &mut split.unknown] {
+        bucket.revenue_share =
+            (total_revenue_usd > 0.0).then(|| bucket.revenue_usd / total_revenue_usd);
+        bucket.views_share = (total_views > 0).then(|| bucket.views as f64 / total_views as f64);
+    }
+    split
+}
+
+/// GET windowed revenue and views split between Shorts, long-form and unclassified videos
+/// (see `VideoClassification`); defaults to the last 28 days.
+async fn handle_youtube_shorts_revenue_split(
     method: &Method,
     headers: &HeaderMap,
     uri: &Uri,
@@ -2299,11 +3819,6 @@ async fn handle_youtube_top_videos(
         );
     }
 
-    let limit = get_query_param(uri, "limit")
-        .and_then(|v| v.parse::<i64>().ok())
-        .map(|v| v.clamp(1, 50))
-        .unwrap_or(10);
-
     let today = Utc::now().date_naive();
     let start_dt = get_query_param(uri, "start_dt")
         .and_then(|v| NaiveDate::parse_from_str(v.trim(), "%Y-%m-%d").ok())
@@ -2311,122 +3826,249 @@ async fn handle_youtube_top_videos(
     let end_dt = get_query_param(uri, "end_dt")
         .and_then(|v| NaiveDate::parse_from_str(v.trim(), "%Y-%m-%d").ok())
         .unwrap_or(today);
+    if start_dt > end_dt {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "start_dt must be <= end_dt"}),
+        );
+    }
+
+    let policy_params_json =
+        fetch_policy_params_json(pool, tenant_id.trim(), channel_id.trim(), "active").await?;
+    let classification = VideoClassification::from_sources(
+        get_query_param(uri, "shorts_ids").as_deref(),
+        get_query_param(uri, "long_form_ids").as_deref(),
+        policy_params_json.as_deref(),
+    );
 
-    let rows = sqlx::query_as::<_, (String, f64, i64, i64, f64, i64)>(
+    let sql = format!(
         r#"
-	      SELECT video_id,
-	             CAST(COALESCE(SUM(estimated_revenue_usd), 0) AS DOUBLE) AS revenue_usd,
-	             CAST(COALESCE(SUM(views), 0) AS SIGNED) AS views,
-	             CAST(COALESCE(SUM(impressions), 0) AS SIGNED) AS impressions,
-	             CAST(COALESCE(SUM(impressions_ctr * impressions), 0) AS DOUBLE) AS ctr_num,
-	             CAST(COALESCE(SUM(CASE WHEN impressions_ctr IS NOT NULL THEN impressions ELSE 0 END), 0) AS SIGNED) AS ctr_denom
-	      FROM video_daily_metrics
-	      WHERE tenant_id = ?
-	        AND channel_id = ?
-	        AND dt BETWEEN ? AND ?
-	        AND video_id NOT IN ('__CHANNEL_TOTAL__','csv_channel_total')
-	      GROUP BY video_id
-	      ORDER BY revenue_usd DESC, views DESC
+      SELECT video_id,
+             CAST(COALESCE(SUM(estimated_revenue_usd), 0) AS DOUBLE) AS revenue_usd,
+             CAST(COALESCE(SUM(views), 0) AS SIGNED) AS views
+      FROM video_daily_metrics
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND dt BETWEEN ? AND ?
+        AND {video_rows}
+      GROUP BY video_id;
+    "#,
+        video_rows = video_rows_filter(),
+    );
+    let rows = sqlx::query_as::<_, (String, f64, i64)>(&sql)
+        .bind(tenant_id.trim())
+        .bind(channel_id.trim())
+        .bind(start_dt)
+        .bind(end_dt)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| -> Error { Box::new(e) })?;
+
+    let split = split_revenue_by_video_class(&rows, &classification);
+    json_response(
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "channel_id": channel_id,
+          "start_dt": start_dt.to_string(),
+          "end_dt": end_dt.to_string(),
+          "classified_shorts": classification.shorts.len(),
+          "classified_long_form": classification.long_form.len(),
+          "split": split,
+        }),
+    )
+}
+
+#[derive(serde::Serialize)]
+struct TopVideoItem {
+    video_id: String,
+    views: i64,
+    impressions: i64,
+    revenue_usd: f64,
+    ctr: Option<f64>,
+    rpm: Option<f64>,
+}
+
+async fn handle_youtube_top_videos(
+    method: &Method,
+    headers: &HeaderMap,
+    uri: &Uri,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::GET {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let tenant_id = get_query_param(uri, "tenant_id").unwrap_or_default();
+    if tenant_id.trim().is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+        );
+    }
+
+    let pool = get_pool().await?;
+    let channel_id = match get_query_param(uri, "channel_id")
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+    {
+        Some(v) => v,
+        None => fetch_youtube_channel_id(pool, tenant_id.trim())
+            .await?
+            .unwrap_or_default(),
+    };
+
+    if channel_id.trim().is_empty() {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "No active YouTube channel for this tenant"}),
+        );
+    }
+
+    let ctr_format = CtrFormat::from_uri(uri);
+    let rpm_min_views = rpm_min_views();
+    let limit = get_query_param(uri, "limit")
+        .and_then(|v| v.parse::<i64>().ok())
+        .map(|v| v.clamp(1, 50))
+        .unwrap_or(10);
+
+    let today = Utc::now().date_naive();
+    let start_dt = get_query_param(uri, "start_dt")
+        .and_then(|v| NaiveDate::parse_from_str(v.trim(), "%Y-%m-%d").ok())
+        .unwrap_or(today - Duration::days(28));
+    let end_dt = get_query_param(uri, "end_dt")
+        .and_then(|v| NaiveDate::parse_from_str(v.trim(), "%Y-%m-%d").ok())
+        .unwrap_or(today);
+
+    let shorts =
+        shorts_exclusion_for_request(pool, tenant_id.trim(), channel_id.trim(), uri).await?;
+
+    // `force=true` skips the stored rows and re-reads YouTube Analytics.
+    let force = get_query_flag(uri, "force");
+    let rows = if force {
+        Vec::new()
+    } else {
+        let sql = format!(
+            r#"
+	      SELECT video_id,
+	             CAST(COALESCE(SUM(estimated_revenue_usd), 0) AS DOUBLE) AS revenue_usd,
+	             CAST(COALESCE(SUM(views), 0) AS SIGNED) AS views,
+	             CAST(COALESCE(SUM(impressions), 0) AS SIGNED) AS impressions,
+	             CAST(COALESCE(SUM(impressions_ctr * impressions), 0) AS DOUBLE) AS ctr_num,
+	             CAST(COALESCE(SUM(CASE WHEN impressions_ctr IS NOT NULL THEN impressions ELSE 0 END), 0) AS SIGNED) AS ctr_denom
+	      FROM video_daily_metrics
+	      WHERE tenant_id = ?
+	        AND channel_id = ?
+	        AND dt BETWEEN ? AND ?
+	        AND {video_rows}
+	        {shorts_filter}
+	      GROUP BY video_id
+	      ORDER BY revenue_usd DESC, views DESC
 	      LIMIT ?;
 	    "#,
-    )
-    .bind(tenant_id.trim())
-    .bind(channel_id.trim())
-    .bind(start_dt)
-    .bind(end_dt)
-    .bind(limit)
-    .fetch_all(pool)
-    .await
-    .map_err(|e| -> Error { Box::new(e) })?;
+            video_rows = video_rows_filter(),
+            shorts_filter = shorts.sql_filter(),
+        );
+        let mut query = sqlx::query_as::<_, (String, f64, i64, i64, f64, i64)>(&sql)
+            .bind(tenant_id.trim())
+            .bind(channel_id.trim())
+            .bind(start_dt)
+            .bind(end_dt);
+        for id in &shorts.ids {
+            query = query.bind(id);
+        }
+        query
+            .bind(limit)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| -> Error { Box::new(e) })?
+    };
 
     let mut items: Vec<TopVideoItem> = rows
         .into_iter()
         .map(
             |(video_id, revenue_usd, views, impressions, ctr_num, ctr_denom)| {
                 let ctr = if ctr_denom > 0 {
-                    Some(((ctr_num / (ctr_denom as f64)) * 10000.0).round() / 10000.0)
+                    Some(ctr_format.format(ctr_num / (ctr_denom as f64)))
                 } else {
                     None
                 };
-                let rpm = if views > 0 {
-                    (revenue_usd / (views as f64)) * 1000.0
-                } else {
-                    0.0
-                };
+                let rpm = rpm_with_floor(revenue_usd, views, rpm_min_views);
                 TopVideoItem {
                     video_id,
                     views,
                     impressions,
                     revenue_usd: round2(revenue_usd),
                     ctr,
-                    rpm: round2(rpm),
+                    rpm: rpm.map(round2),
                 }
             },
         )
         .collect();
 
-    if items.is_empty() {
-        let access_token = match ensure_fresh_youtube_access_token(
+    if force || items.is_empty() {
+        let access_token =
+            match ensure_fresh_youtube_tokens(pool, tenant_id.trim(), channel_id.trim()).await {
+                Ok(v) => v.access_token,
+                Err(err) => {
+                    return json_response(
+                        StatusCode::OK,
+                        youtube_token_error_body(err, &channel_id, start_dt, end_dt),
+                    );
+                }
+            };
+        let _ = record_youtube_api_usage(
             pool,
             tenant_id.trim(),
             channel_id.trim(),
+            "reports.query",
+            REPORTS_QUERY_QUOTA_UNITS,
         )
-        .await
-        {
-            Ok(v) => v,
-            Err(err) => {
-                let msg = err.to_string();
-                let code = if msg.contains("not_configured")
-                    || msg.contains("oauth app config")
-                    || msg.contains("client_secret")
-                {
-                    "not_configured"
-                } else if msg.contains("missing youtube channel connection") {
-                    "not_connected"
-                } else {
-                    "upstream_error"
-                };
-                return json_response(
-                    StatusCode::OK,
-                    serde_json::json!({
-                        "ok": false,
-                        "error": code,
-                        "message": msg,
-                        "channel_id": channel_id,
-                        "start_dt": start_dt.to_string(),
-                        "end_dt": end_dt.to_string()
-                    }),
-                );
-            }
-        };
+        .await;
 
         match fetch_top_videos_by_revenue_for_channel(
             &access_token,
             channel_id.trim(),
             start_dt,
             end_dt,
-            limit,
+            shorts.api_fetch_limit(limit),
         )
         .await
         {
-            Ok(rows) => {
+            Ok(mut rows) => {
+                shorts.retain_long_form(&mut rows, |r| r.video_id.as_str(), limit as usize);
                 items = rows
                     .into_iter()
                     .map(|row| {
                         let revenue_usd = row.estimated_revenue_usd;
                         let views = row.views;
-                        let rpm = if views > 0 {
-                            (revenue_usd / (views as f64)) * 1000.0
-                        } else {
-                            0.0
-                        };
+                        let rpm = rpm_with_floor(revenue_usd, views, rpm_min_views);
                         TopVideoItem {
                             video_id: row.video_id,
                             views,
                             impressions: 0,
                             revenue_usd: round2(revenue_usd),
                             ctr: None,
-                            rpm: round2(rpm),
+                            rpm: rpm.map(round2),
                         }
                     })
                     .collect();
@@ -2436,9 +4078,12 @@ async fn handle_youtube_top_videos(
                     serde_json::json!({
                         "ok": true,
                         "source": "youtube_analytics",
+                        "forced": force,
+                        "excluded_shorts": shorts.ids.len(),
                         "channel_id": channel_id,
                         "start_dt": start_dt.to_string(),
                         "end_dt": end_dt.to_string(),
+                        "ctr_unit": ctr_format.as_str(),
                         "items": items
                     }),
                 );
@@ -2461,132 +4106,100 @@ async fn handle_youtube_top_videos(
 
     json_response(
         StatusCode::OK,
-        serde_json::json!({"ok": true, "source": "tidb", "channel_id": channel_id, "start_dt": start_dt.to_string(), "end_dt": end_dt.to_string(), "items": items}),
+        serde_json::json!({"ok": true, "source": "tidb", "forced": false, "excluded_shorts": shorts.ids.len(), "channel_id": channel_id, "start_dt": start_dt.to_string(), "end_dt": end_dt.to_string(), "ctr_unit": ctr_format.as_str(), "items": items}),
     )
 }
 
-#[derive(serde::Serialize)]
-struct DataHealthTotals {
-    views: i64,
-    impressions: i64,
+/// Default and max movers returned per direction by `youtube_top_movers`.
+const TOP_MOVERS_DEFAULT_N: usize = 5;
+const TOP_MOVERS_MAX_N: usize = 25;
+
+/// One video's revenue and views in the current week and the week before it.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+struct MoverItem {
+    video_id: String,
     revenue_usd: f64,
-    rpm: f64,
+    prev_revenue_usd: f64,
+    revenue_delta_usd: f64,
+    views: i64,
+    prev_views: i64,
+    views_delta: i64,
 }
 
-#[derive(serde::Serialize)]
-struct DataHealthWindow {
-    start_dt: String,
-    end_dt: String,
-    days: i64,
+impl MoverItem {
+    fn new(
+        video_id: String,
+        revenue_usd: f64,
+        prev_revenue_usd: f64,
+        views: i64,
+        prev_views: i64,
+    ) -> Self {
+        Self {
+            video_id,
+            revenue_usd: round2(revenue_usd),
+            prev_revenue_usd: round2(prev_revenue_usd),
+            revenue_delta_usd: round2(revenue_usd - prev_revenue_usd),
+            views,
+            prev_views,
+            views_delta: views - prev_views,
+        }
+    }
 }
 
-#[derive(serde::Serialize)]
-struct DataHealthPeriod {
-    source: String,
-    partial: bool,
-    days_with_data: i64,
-    last_dt: Option<String>,
-    last_updated_at: Option<String>,
-    totals: DataHealthTotals,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MoverMetric {
+    Revenue,
+    Views,
 }
 
-async fn aggregate_data_health_period(
-    pool: &sqlx::MySqlPool,
-    tenant_id: &str,
-    channel_id: &str,
-    start_dt: NaiveDate,
-    end_dt: NaiveDate,
-) -> Result<DataHealthPeriod, Error> {
-    let row = sqlx::query_as::<_, (i64, Option<NaiveDate>, Option<DateTime<Utc>>, f64, i64, i64)>(
-        r#"
-      SELECT COUNT(DISTINCT dt) AS days_with_data,
-             MAX(dt) AS last_dt,
-             MAX(updated_at) AS last_updated_at,
-             CAST(COALESCE(SUM(estimated_revenue_usd), 0) AS DOUBLE) AS revenue_usd,
-             CAST(COALESCE(SUM(views), 0) AS SIGNED) AS views,
-             CAST(COALESCE(SUM(impressions), 0) AS SIGNED) AS impressions
-      FROM video_daily_metrics
-      WHERE tenant_id = ?
-        AND channel_id = ?
-        AND dt BETWEEN ? AND ?
-        AND video_id IN ('__CHANNEL_TOTAL__','csv_channel_total');
-    "#,
-    )
-    .bind(tenant_id)
-    .bind(channel_id)
-    .bind(start_dt)
-    .bind(end_dt)
-    .fetch_one(pool)
-    .await
-    .map_err(|e| -> Error { Box::new(e) })?;
+#[derive(Debug, Default, PartialEq, serde::Serialize)]
+struct RankedMovers {
+    gainers: Vec<MoverItem>,
+    decliners: Vec<MoverItem>,
+}
 
-    let (days_with_data, last_dt, last_updated_at, revenue_usd, views, impressions) = row;
-    if days_with_data > 0 {
-        let rpm = if views > 0 {
-            (revenue_usd / (views as f64)) * 1000.0
-        } else {
-            0.0
-        };
-        return Ok(DataHealthPeriod {
-            source: "channel_total".to_string(),
-            partial: false,
-            days_with_data,
-            last_dt: last_dt.map(|d| d.to_string()),
-            last_updated_at: last_updated_at.map(datetime_to_rfc3339_utc),
-            totals: DataHealthTotals {
-                views,
-                impressions,
-                revenue_usd: round2(revenue_usd),
-                rpm: round2(rpm),
-            },
+/// Top `top_n` gainers (largest positive delta first) and decliners (largest drop first) by
+/// `metric`; unchanged videos are in neither list, and ties keep video_id order.
+fn rank_movers(items: &[MoverItem], metric: MoverMetric, top_n: usize) -> RankedMovers {
+    let delta = |item: &MoverItem| match metric {
+        MoverMetric::Revenue => item.revenue_delta_usd,
+        MoverMetric::Views => item.views_delta as f64,
+    };
+    // Gainers rank by descending delta, decliners by ascending delta.
+    let ranked = |gaining: bool| -> Vec<MoverItem> {
+        let mut out: Vec<MoverItem> = items
+            .iter()
+            .filter(|item| {
+                if gaining {
+                    delta(item) > 0.0
+                } else {
+                    delta(item) < 0.0
+                }
+            })
+            .cloned()
+            .collect();
+        out.sort_by(|a, b| {
+            let by_delta = delta(a).total_cmp(&delta(b));
+            if gaining {
+                by_delta.reverse()
+            } else {
+                by_delta
+            }
+            .then_with(|| a.video_id.cmp(&b.video_id))
         });
+        out.truncate(top_n);
+        out
+    };
+
+    RankedMovers {
+        gainers: ranked(true),
+        decliners: ranked(false),
     }
+}
 
-    let row = sqlx::query_as::<_, (i64, Option<NaiveDate>, Option<DateTime<Utc>>, f64, i64, i64)>(
-        r#"
-      SELECT COUNT(DISTINCT dt) AS days_with_data,
-             MAX(dt) AS last_dt,
-             MAX(updated_at) AS last_updated_at,
-             CAST(COALESCE(SUM(estimated_revenue_usd), 0) AS DOUBLE) AS revenue_usd,
-             CAST(COALESCE(SUM(views), 0) AS SIGNED) AS views,
-             CAST(COALESCE(SUM(impressions), 0) AS SIGNED) AS impressions
-      FROM video_daily_metrics
-      WHERE tenant_id = ?
-        AND channel_id = ?
-        AND dt BETWEEN ? AND ?
-        AND video_id NOT IN ('__CHANNEL_TOTAL__','csv_channel_total');
-    "#,
-    )
-    .bind(tenant_id)
-    .bind(channel_id)
-    .bind(start_dt)
-    .bind(end_dt)
-    .fetch_one(pool)
-    .await
-    .map_err(|e| -> Error { Box::new(e) })?;
-
-    let (days_with_data, last_dt, last_updated_at, revenue_usd, views, impressions) = row;
-    let rpm = if views > 0 {
-        (revenue_usd / (views as f64)) * 1000.0
-    } else {
-        0.0
-    };
-    Ok(DataHealthPeriod {
-        source: "video_sum".to_string(),
-        partial: true,
-        days_with_data,
-        last_dt: last_dt.map(|d| d.to_string()),
-        last_updated_at: last_updated_at.map(datetime_to_rfc3339_utc),
-        totals: DataHealthTotals {
-            views,
-            impressions,
-            revenue_usd: round2(revenue_usd),
-            rpm: round2(rpm),
-        },
-    })
-}
-
-async fn handle_youtube_data_health(
+/// Week-over-week movers: the 7 days ending `end_dt` (default yesterday) against the 7 days
+/// before, compared in one grouped query over per-video rows.
+async fn handle_youtube_top_movers(
     method: &Method,
     headers: &HeaderMap,
     uri: &Uri,
@@ -2641,226 +4254,478 @@ async fn handle_youtube_data_health(
         );
     }
 
-    let today = Utc::now().date_naive();
-    let default_end = today - Duration::days(1);
-    let start_dt = get_query_param(uri, "start_dt")
-        .and_then(|v| NaiveDate::parse_from_str(v.trim(), "%Y-%m-%d").ok())
-        .unwrap_or(default_end - Duration::days(27));
+    let top_n = get_query_param(uri, "top_n")
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .map(|v| v.clamp(1, TOP_MOVERS_MAX_N))
+        .unwrap_or(TOP_MOVERS_DEFAULT_N);
     let end_dt = get_query_param(uri, "end_dt")
         .and_then(|v| NaiveDate::parse_from_str(v.trim(), "%Y-%m-%d").ok())
-        .unwrap_or(default_end);
+        .unwrap_or_else(|| Utc::now().date_naive() - Duration::days(1));
+    let start_dt = end_dt - Duration::days(6);
+    let prev_end_dt = start_dt - Duration::days(1);
+    let prev_start_dt = prev_end_dt - Duration::days(6);
 
-    let days = ((end_dt - start_dt).num_days() + 1).max(1);
-    let baseline_start = start_dt - Duration::days(days);
-    let baseline_end = start_dt - Duration::days(1);
+    let rows = sqlx::query_as::<_, (String, f64, f64, i64, i64)>(&format!(
+        r#"
+      SELECT video_id,
+             CAST(COALESCE(SUM(CASE WHEN dt >= ? THEN estimated_revenue_usd ELSE 0 END), 0) AS DOUBLE) AS revenue_usd,
+             CAST(COALESCE(SUM(CASE WHEN dt < ? THEN estimated_revenue_usd ELSE 0 END), 0) AS DOUBLE) AS prev_revenue_usd,
+             CAST(COALESCE(SUM(CASE WHEN dt >= ? THEN views ELSE 0 END), 0) AS SIGNED) AS views,
+             CAST(COALESCE(SUM(CASE WHEN dt < ? THEN views ELSE 0 END), 0) AS SIGNED) AS prev_views
+      FROM video_daily_metrics
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND dt BETWEEN ? AND ?
+        AND {video_rows}
+      GROUP BY video_id;
+    "#,
+        video_rows = video_rows_filter(),
+    ))
+    .bind(start_dt)
+    .bind(start_dt)
+    .bind(start_dt)
+    .bind(start_dt)
+    .bind(tenant_id.trim())
+    .bind(channel_id.trim())
+    .bind(prev_start_dt)
+    .bind(end_dt)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
 
-    let window = DataHealthWindow {
-        start_dt: start_dt.to_string(),
-        end_dt: end_dt.to_string(),
-        days,
-    };
-    let baseline_window = DataHealthWindow {
-        start_dt: baseline_start.to_string(),
-        end_dt: baseline_end.to_string(),
-        days,
-    };
+    let items: Vec<MoverItem> = rows
+        .into_iter()
+        .map(
+            |(video_id, revenue_usd, prev_revenue_usd, views, prev_views)| {
+                MoverItem::new(video_id, revenue_usd, prev_revenue_usd, views, prev_views)
+            },
+        )
+        .collect();
 
-    let current =
-        aggregate_data_health_period(pool, tenant_id.trim(), channel_id.trim(), start_dt, end_dt)
-            .await?;
-    let baseline = aggregate_data_health_period(
-        pool,
-        tenant_id.trim(),
-        channel_id.trim(),
-        baseline_start,
-        baseline_end,
+    json_response(
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "channel_id": channel_id,
+          "current_window": { "start_dt": start_dt.to_string(), "end_dt": end_dt.to_string() },
+          "previous_window": { "start_dt": prev_start_dt.to_string(), "end_dt": prev_end_dt.to_string() },
+          "top_n": top_n,
+          "revenue": rank_movers(&items, MoverMetric::Revenue, top_n),
+          "views": rank_movers(&items, MoverMetric::Views, top_n),
+        }),
     )
-    .await?;
-
-    let expected_days = days;
-    let coverage = if expected_days > 0 {
-        (current.days_with_data as f64) / (expected_days as f64)
-    } else {
-        0.0
-    };
+}
 
-    let (lag_days, stale) = current
-        .last_dt
-        .as_deref()
-        .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
-        .map(|dt| {
-            let raw = (end_dt - dt).num_days();
-            let lag = raw.max(0);
-            // YouTube Analytics commonly lags by ~48h; treat 0–2d lag as expected (not stale).
-            let is_stale = lag > 2;
-            (lag, is_stale, dt)
-        })
-        .map(|(lag, is_stale, dt)| (Some((lag, dt)), is_stale))
-        .unwrap_or((None, true));
+#[derive(serde::Serialize)]
+struct DataHealthTotals {
+    views: i64,
+    impressions: i64,
+    revenue_usd: f64,
+    rpm: f64,
+    /// Revenue divided by the days that actually have data, so a partially synced window can be
+    /// compared with a complete one.
+    revenue_per_active_day: Option<f64>,
+}
 
-    let mut notes: Vec<String> = Vec::new();
-    if current.partial {
-        notes.push(
-            "Using video-level sums (may be partial if YouTube Analytics limits rows).".to_string(),
-        );
-    }
-    if let Some((lag, dt)) = lag_days {
-        if lag > 0 && !stale {
-            notes.push(format!(
-                "YouTube Analytics often lags 1–2 days. Latest dt {dt} (lag {lag}d vs end_dt {end_dt})."
-            ));
-        } else if stale {
-            notes.push(format!(
-                "Latest metric date is behind the requested end_dt (lag {lag}d; latest dt {dt}). Sync may be stale."
-            ));
+impl DataHealthTotals {
+    fn new(views: i64, impressions: i64, revenue_usd: f64, days_with_data: i64) -> Self {
+        let rpm = if views > 0 {
+            (revenue_usd / (views as f64)) * 1000.0
+        } else {
+            0.0
+        };
+        DataHealthTotals {
+            views,
+            impressions,
+            revenue_usd: round2(revenue_usd),
+            rpm: round2(rpm),
+            revenue_per_active_day: (days_with_data > 0)
+                .then(|| round2(revenue_usd / days_with_data as f64)),
         }
-    } else if stale {
-        notes.push("No metrics found yet in this window (sync may be stale).".to_string());
-    }
-    if coverage < 0.8 {
-        notes.push("Low coverage: fewer days with data than expected in the window.".to_string());
     }
+}
 
-    json_response(
-        StatusCode::OK,
-        serde_json::json!({"ok": true, "channel_id": channel_id, "window": window, "baseline_window": baseline_window, "current": current, "baseline": baseline, "notes": notes}),
-    )
+/// Current vs baseline, both on raw sums and normalized per active day.
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct DataHealthComparison {
+    revenue_change_pct: Option<f64>,
+    revenue_per_active_day_change_pct: Option<f64>,
 }
 
-#[derive(serde::Serialize)]
-struct OutcomeLatestItem {
-    decision_dt: String,
-    outcome_dt: String,
-    revenue_change_pct_7d: Option<f64>,
-    catastrophic_flag: bool,
-    new_top_asset_flag: bool,
-    notes: Option<serde_json::Value>,
+fn change_pct(current: f64, baseline: f64) -> Option<f64> {
+    (baseline.abs() > f64::EPSILON).then(|| round2((current - baseline) / baseline * 100.0))
 }
 
-async fn fetch_outcome_latest(
-    pool: &sqlx::MySqlPool,
-    tenant_id: &str,
-    channel_id: &str,
-) -> Result<Option<OutcomeLatestItem>, Error> {
-    let row = sqlx::query_as::<_, (NaiveDate, NaiveDate, Option<f64>, i8, i8, Option<String>)>(
-        r#"
-          SELECT decision_dt, outcome_dt, revenue_change_pct_7d, catastrophic_flag, new_top_asset_flag, notes
-          FROM decision_outcome
-          WHERE tenant_id = ? AND channel_id = ?
-          ORDER BY outcome_dt DESC, decision_dt DESC
-          LIMIT 1;
-        "#,
-    )
-    .bind(tenant_id)
-    .bind(channel_id)
-    .fetch_optional(pool)
-    .await
-    .map_err(|e| -> Error { Box::new(e) })?;
+fn compare_data_health_periods(
+    current: &DataHealthPeriod,
+    baseline: &DataHealthPeriod,
+) -> DataHealthComparison {
+    DataHealthComparison {
+        revenue_change_pct: change_pct(current.totals.revenue_usd, baseline.totals.revenue_usd),
+        revenue_per_active_day_change_pct: current
+            .totals
+            .revenue_per_active_day
+            .zip(baseline.totals.revenue_per_active_day)
+            .and_then(|(current, baseline)| change_pct(current, baseline)),
+    }
+}
 
-    Ok(row.map(
-        |(
-            decision_dt,
-            outcome_dt,
-            revenue_change_pct_7d,
-            catastrophic_flag,
-            new_top_asset_flag,
-            notes,
-        )| {
-            let notes_json = notes.as_deref().and_then(|raw| {
-                let trimmed = raw.trim();
-                if trimmed.is_empty() {
-                    return None;
-                }
-                match serde_json::from_str::<serde_json::Value>(trimmed) {
-                    Ok(v) => Some(v),
-                    Err(_) => Some(serde_json::Value::String(trimmed.to_string())),
-                }
-            });
+#[derive(serde::Serialize)]
+struct DataHealthWindow {
+    start_dt: String,
+    end_dt: String,
+    days: i64,
+}
 
-            OutcomeLatestItem {
-                decision_dt: decision_dt.to_string(),
-                outcome_dt: outcome_dt.to_string(),
-                revenue_change_pct_7d,
-                catastrophic_flag: catastrophic_flag != 0,
-                new_top_asset_flag: new_top_asset_flag != 0,
-                notes: notes_json,
-            }
-        },
-    ))
+#[derive(serde::Serialize)]
+struct DataHealthPeriod {
+    source: String,
+    partial: bool,
+    days_with_data: i64,
+    last_dt: Option<String>,
+    last_updated_at: Option<String>,
+    totals: DataHealthTotals,
+    reach_rows: i64,
+    reach_coverage: String,
 }
 
-async fn handle_youtube_outcome_latest(
-    method: &Method,
-    headers: &HeaderMap,
-    uri: &Uri,
-) -> Result<Response<ResponseBody>, Error> {
-    if method != Method::GET {
-        return json_response(
-            StatusCode::METHOD_NOT_ALLOWED,
-            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
-        );
+/// How data health picks the comparison window. `Adjacent` is the equal-length window right
+/// before the requested one; `WeekAligned` shifts back whole weeks so both windows cover the same
+/// weekdays (a weekend-heavy window is never compared to a weekday-heavy one).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DataHealthBaseline {
+    Adjacent,
+    WeekAligned { weeks: i64 },
+}
+
+const MAX_BASELINE_WEEKS: i64 = 52;
+
+impl DataHealthBaseline {
+    /// `baseline=week_aligned` opts in; `baseline_weeks=N` picks the offset (defaults to the
+    /// fewest whole weeks that clear the window, so the two never overlap).
+    fn from_uri(uri: &Uri) -> Self {
+        let mode = get_query_param(uri, "baseline").unwrap_or_default();
+        if !mode.trim().eq_ignore_ascii_case("week_aligned") {
+            return DataHealthBaseline::Adjacent;
+        }
+        let weeks = get_query_param(uri, "baseline_weeks")
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .map(|v| v.clamp(1, MAX_BASELINE_WEEKS))
+            .unwrap_or(0);
+        DataHealthBaseline::WeekAligned { weeks }
     }
 
-    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
-    let provided =
-        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
-    if expected.is_empty() || provided != expected {
-        return json_response(
-            StatusCode::UNAUTHORIZED,
-            serde_json::json!({"ok": false, "error": "unauthorized"}),
-        );
+    fn as_str(self) -> &'static str {
+        match self {
+            DataHealthBaseline::Adjacent => "adjacent",
+            DataHealthBaseline::WeekAligned { .. } => "week_aligned",
+        }
     }
 
-    if !has_tidb_url() {
-        return json_response(
-            StatusCode::NOT_IMPLEMENTED,
-            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
-        );
+    /// Inclusive `(start, end)` of the baseline for `start_dt..=end_dt`.
+    fn window(self, start_dt: NaiveDate, end_dt: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let days = ((end_dt - start_dt).num_days() + 1).max(1);
+        match self {
+            DataHealthBaseline::Adjacent => (
+                start_dt - Duration::days(days),
+                start_dt - Duration::days(1),
+            ),
+            DataHealthBaseline::WeekAligned { weeks } => {
+                let min_weeks = (days + 6) / 7;
+                let shift = weeks.max(min_weeks) * 7;
+                (
+                    start_dt - Duration::days(shift),
+                    end_dt - Duration::days(shift),
+                )
+            }
+        }
     }
 
-    let tenant_id = get_query_param(uri, "tenant_id").unwrap_or_default();
-    if tenant_id.trim().is_empty() {
-        return json_response(
-            StatusCode::BAD_REQUEST,
-            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
-        );
+    fn note(self, baseline_start: NaiveDate, baseline_end: NaiveDate) -> Option<String> {
+        match self {
+            DataHealthBaseline::Adjacent => None,
+            DataHealthBaseline::WeekAligned { .. } => Some(format!(
+                "Baseline is week-aligned ({baseline_start} to {baseline_end}) so both windows cover the same weekdays."
+            )),
+        }
     }
+}
 
-    let pool = get_pool().await?;
-    let channel_id = match get_query_param(uri, "channel_id")
-        .map(|v| v.trim().to_string())
-        .filter(|v| !v.is_empty())
-    {
-        Some(v) => v,
-        None => fetch_youtube_channel_id(pool, tenant_id.trim())
-            .await?
-            .unwrap_or_default(),
-    };
+/// A row holding reach data: stamped by a reach ingest (even one reporting zero impressions), or,
+/// for rows stored before that stamp existed, carrying a CTR or impressions.
+const REACH_ROW_SQL: &str =
+    "(reach_ingested_at IS NOT NULL OR impressions_ctr IS NOT NULL OR impressions > 0)";
+
+/// `missing` means no reach data was ingested for the window (impressions are unknown, not zero);
+/// `zero` means reach rows exist but report no impressions.
+fn reach_coverage(reach_rows: i64, impressions: i64) -> &'static str {
+    if reach_rows <= 0 {
+        "missing"
+    } else if impressions <= 0 {
+        "zero"
+    } else {
+        "populated"
+    }
+}
 
-    if channel_id.trim().is_empty() {
-        return json_response(
-            StatusCode::NOT_FOUND,
-            serde_json::json!({"ok": false, "error": "not_connected", "message": "No active YouTube channel for this tenant"}),
-        );
+async fn aggregate_data_health_period(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    start_dt: NaiveDate,
+    end_dt: NaiveDate,
+) -> Result<DataHealthPeriod, Error> {
+    let row = sqlx::query_as::<
+        _,
+        (
+            i64,
+            Option<NaiveDate>,
+            Option<DateTime<Utc>>,
+            f64,
+            i64,
+            i64,
+            i64,
+        ),
+    >(&format!(
+        r#"
+      SELECT COUNT(DISTINCT dt) AS days_with_data,
+             MAX(dt) AS last_dt,
+             MAX(updated_at) AS last_updated_at,
+             CAST(COALESCE(SUM(estimated_revenue_usd), 0) AS DOUBLE) AS revenue_usd,
+             CAST(COALESCE(SUM(views), 0) AS SIGNED) AS views,
+             CAST(COALESCE(SUM(impressions), 0) AS SIGNED) AS impressions,
+             CAST(COALESCE(SUM(CASE WHEN {reach_row} THEN 1 ELSE 0 END), 0) AS SIGNED) AS reach_rows
+      FROM video_daily_metrics
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND dt BETWEEN ? AND ?
+        AND {channel_totals};
+    "#,
+        channel_totals = channel_total_filter(),
+        reach_row = REACH_ROW_SQL,
+    ))
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(start_dt)
+    .bind(end_dt)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    let (days_with_data, last_dt, last_updated_at, revenue_usd, views, impressions, reach_rows) =
+        row;
+    if days_with_data > 0 {
+        return Ok(DataHealthPeriod {
+            source: "channel_total".to_string(),
+            partial: false,
+            days_with_data,
+            last_dt: last_dt.map(|d| d.to_string()),
+            last_updated_at: last_updated_at.map(datetime_to_rfc3339_utc),
+            totals: DataHealthTotals::new(views, impressions, revenue_usd, days_with_data),
+            reach_rows,
+            reach_coverage: reach_coverage(reach_rows, impressions).to_string(),
+        });
     }
 
-    match fetch_outcome_latest(pool, tenant_id.trim(), channel_id.trim()).await {
-        Ok(Some(item)) => json_response(
-            StatusCode::OK,
-            serde_json::json!({"ok": true, "channel_id": channel_id, "found": true, "item": item}),
+    let row = sqlx::query_as::<
+        _,
+        (
+            i64,
+            Option<NaiveDate>,
+            Option<DateTime<Utc>>,
+            f64,
+            i64,
+            i64,
+            i64,
         ),
-        Ok(None) => json_response(
-            StatusCode::OK,
-            serde_json::json!({"ok": true, "channel_id": channel_id, "found": false, "item": null}),
+    >(&format!(
+        r#"
+      SELECT COUNT(DISTINCT dt) AS days_with_data,
+             MAX(dt) AS last_dt,
+             MAX(updated_at) AS last_updated_at,
+             CAST(COALESCE(SUM(estimated_revenue_usd), 0) AS DOUBLE) AS revenue_usd,
+             CAST(COALESCE(SUM(views), 0) AS SIGNED) AS views,
+             CAST(COALESCE(SUM(impressions), 0) AS SIGNED) AS impressions,
+             CAST(COALESCE(SUM(CASE WHEN {reach_row} THEN 1 ELSE 0 END), 0) AS SIGNED) AS reach_rows
+      FROM video_daily_metrics
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND dt BETWEEN ? AND ?
+        AND {video_rows};
+    "#,
+        video_rows = video_rows_filter(),
+        reach_row = REACH_ROW_SQL,
+    ))
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(start_dt)
+    .bind(end_dt)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    let (days_with_data, last_dt, last_updated_at, revenue_usd, views, impressions, reach_rows) =
+        row;
+    Ok(DataHealthPeriod {
+        source: "video_sum".to_string(),
+        partial: true,
+        days_with_data,
+        last_dt: last_dt.map(|d| d.to_string()),
+        last_updated_at: last_updated_at.map(datetime_to_rfc3339_utc),
+        totals: DataHealthTotals::new(views, impressions, revenue_usd, days_with_data),
+        reach_rows,
+        reach_coverage: reach_coverage(reach_rows, impressions).to_string(),
+    })
+}
+
+const COMPLETENESS_MAX_MISSING_DATES: usize = 31;
+
+#[derive(serde::Serialize)]
+struct WindowCompleteness {
+    expected_days: i64,
+    present_days: i64,
+    missing_dates: Vec<String>,
+    missing_dates_truncated: bool,
+    complete: bool,
+}
+
+/// Day coverage for a requested window (same expected-days math as data health), naming the
+/// missing dates so clients can render partial data honestly.
+fn window_completeness(
+    start_dt: NaiveDate,
+    end_dt: NaiveDate,
+    present_dts: &[NaiveDate],
+) -> WindowCompleteness {
+    let expected_days = ((end_dt - start_dt).num_days() + 1).max(0);
+    let missing = find_date_gaps(present_dts, start_dt, end_dt);
+    let present_days = expected_days - missing.len() as i64;
+    WindowCompleteness {
+        expected_days,
+        present_days,
+        missing_dates_truncated: missing.len() > COMPLETENESS_MAX_MISSING_DATES,
+        missing_dates: missing
+            .iter()
+            .take(COMPLETENESS_MAX_MISSING_DATES)
+            .map(|dt| dt.to_string())
+            .collect(),
+        complete: missing.is_empty(),
+    }
+}
+
+/// YouTube Analytics commonly lags by ~48h; a 0–2d lag is expected unless the tenant configures
+/// `freshness_sla_days` in its active policy_params.
+const DEFAULT_FRESHNESS_SLA_DAYS: i64 = 2;
+
+fn freshness_sla_days(policy_params_json: Option<&str>) -> i64 {
+    policy_params_json
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .and_then(|v| v.get("freshness_sla_days").and_then(|v| v.as_i64()))
+        .map(|v| v.clamp(0, 30))
+        .unwrap_or(DEFAULT_FRESHNESS_SLA_DAYS)
+}
+
+/// No data at all in the window counts as a breach.
+fn freshness_sla_breached(lag_days: Option<i64>, sla_days: i64) -> bool {
+    lag_days.is_none_or(|lag| lag > sla_days)
+}
+
+/// Relative gap (in percent) between summed per-video revenue and channel-total revenue that is
+/// tolerated before data health calls it out; override with `revenue_reconciliation_tolerance_pct`.
+const DEFAULT_REVENUE_RECONCILIATION_TOLERANCE_PCT: f64 = 1.0;
+
+fn revenue_reconciliation_tolerance_pct(policy_params_json: Option<&str>) -> f64 {
+    policy_params_json
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .and_then(|v| {
+            v.get("revenue_reconciliation_tolerance_pct")
+                .and_then(|v| v.as_f64())
+        })
+        .filter(|v| v.is_finite())
+        .map(|v| v.clamp(0.0, 100.0))
+        .unwrap_or(DEFAULT_REVENUE_RECONCILIATION_TOLERANCE_PCT)
+}
+
+/// `{value, default, source}` where `source` is `override` whenever the effective value differs
+/// from the built-in default.
+fn effective_config_entry<T: serde::Serialize + PartialEq>(
+    value: T,
+    default: T,
+) -> serde_json::Value {
+    let source = if value == default {
+        "default"
+    } else {
+        "override"
+    };
+    serde_json::json!({"value": value, "default": default, "source": source})
+}
+
+/// Every tunable that applies to a channel, after defaults, the active `policy_params`, env and
+/// tenant feature flags are merged the same way the jobs and handlers merge them.
+fn effective_config(
+    policy_params_json: Option<&str>,
+    retention_env: Option<&str>,
+    flags: &[(String, bool)],
+) -> serde_json::Value {
+    let defaults = DecisionEngineConfig::default();
+    let cfg = policy_params_json
+        .and_then(DecisionEngineConfig::from_policy_params_json)
+        .unwrap_or_default();
+    let defaults_json = defaults.to_policy_params_value();
+    let mut decision_engine = serde_json::Map::new();
+    if let Some(values) = cfg.to_policy_params_value().as_object() {
+        for (key, value) in values {
+            decision_engine.insert(
+                key.clone(),
+                effective_config_entry(value.clone(), defaults_json[key].clone()),
+            );
+        }
+    }
+    decision_engine.insert(
+        "min_surfaced_confidence".to_string(),
+        effective_config_entry(
+            min_surfaced_confidence(policy_params_json),
+            DEFAULT_MIN_SURFACED_CONFIDENCE,
         ),
-        Err(err) => json_response(
-            StatusCode::BAD_GATEWAY,
-            serde_json::json!({"ok": false, "error": "outcome_query_failed", "message": truncate_string(&err.to_string(), 2000), "channel_id": channel_id}),
+    );
+
+    let feature_flags: serde_json::Map<String, serde_json::Value> = TenantFeature::ALL
+        .iter()
+        .map(|feature| {
+            (
+                feature.as_str().to_string(),
+                effective_config_entry(feature_enabled(flags, *feature), true),
+            )
+        })
+        .collect();
+
+    serde_json::json!({
+      "decision_engine": decision_engine,
+      "alerts": {
+        "freshness_sla_days": effective_config_entry(
+          freshness_sla_days(policy_params_json),
+          DEFAULT_FRESHNESS_SLA_DAYS,
         ),
-    }
+        "revenue_reconciliation_tolerance_pct": effective_config_entry(
+          revenue_reconciliation_tolerance_pct(policy_params_json),
+          DEFAULT_REVENUE_RECONCILIATION_TOLERANCE_PCT,
+        ),
+        "experiment_failure_rate_threshold": effective_config_entry(
+          experiment_failure_rate_threshold(policy_params_json),
+          DEFAULT_EXPERIMENT_FAILURE_RATE_THRESHOLD,
+        ),
+      },
+      "retention": {
+        "evidence_retention_days": effective_config_entry(
+          evidence_retention_days(policy_params_json, retention_env),
+          DEFAULT_EVIDENCE_RETENTION_DAYS,
+        ),
+      },
+      "feature_flags": feature_flags,
+    })
 }
 
-async fn handle_youtube_dashboard_bundle(
+/// Support view of the configuration actually in effect for a channel.
+async fn handle_youtube_effective_config(
     method: &Method,
     headers: &HeaderMap,
     uri: &Uri,
@@ -2915,312 +4780,113 @@ async fn handle_youtube_dashboard_bundle(
         );
     }
 
-    let today = Utc::now().date_naive();
-    let default_end = today - Duration::days(1);
-    let start_dt = get_query_param(uri, "start_dt")
-        .and_then(|v| parse_dt(&v))
-        .unwrap_or(default_end - Duration::days(27));
-    let end_dt = get_query_param(uri, "end_dt")
-        .and_then(|v| parse_dt(&v))
-        .unwrap_or(default_end);
-
-    if start_dt > end_dt {
-        return json_response(
-            StatusCode::BAD_REQUEST,
-            serde_json::json!({"ok": false, "error": "bad_request", "message": "start_dt must be <= end_dt"}),
-        );
-    }
-
-    let mut errors = serde_json::Map::new();
-
-    let health = {
-        let days = ((end_dt - start_dt).num_days() + 1).max(1);
-        let baseline_start = start_dt - Duration::days(days);
-        let baseline_end = start_dt - Duration::days(1);
-
-        let window = DataHealthWindow {
-            start_dt: start_dt.to_string(),
-            end_dt: end_dt.to_string(),
-            days,
-        };
-        let baseline_window = DataHealthWindow {
-            start_dt: baseline_start.to_string(),
-            end_dt: baseline_end.to_string(),
-            days,
-        };
-
-        let current = aggregate_data_health_period(
-            pool,
-            tenant_id.trim(),
-            channel_id.trim(),
-            start_dt,
-            end_dt,
-        )
-        .await;
-        let baseline = aggregate_data_health_period(
-            pool,
-            tenant_id.trim(),
-            channel_id.trim(),
-            baseline_start,
-            baseline_end,
-        )
-        .await;
+    let policy_params_json =
+        fetch_policy_params_json(pool, tenant_id.trim(), channel_id.trim(), "active").await?;
+    let flags = fetch_tenant_feature_flags_cached(pool, tenant_id.trim()).await?;
+    let config = effective_config(
+        policy_params_json.as_deref(),
+        std::env::var("EVIDENCE_RETENTION_DAYS").ok().as_deref(),
+        &flags,
+    );
 
-        match (current, baseline) {
-            (Ok(current), Ok(baseline)) => {
-                let expected_days = days;
-                let coverage = if expected_days > 0 {
-                    (current.days_with_data as f64) / (expected_days as f64)
-                } else {
-                    0.0
-                };
+    json_response(
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "tenant_id": tenant_id.trim(),
+          "channel_id": channel_id,
+          "has_policy_params": policy_params_json.is_some(),
+          "config": config,
+        }),
+    )
+}
 
-                let stale = current
-                    .last_dt
-                    .as_deref()
-                    .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
-                    .map(|dt| dt < end_dt)
-                    .unwrap_or(true);
-
-                let mut notes: Vec<String> = Vec::new();
-                if current.partial {
-                    notes.push(
-                        "Using video-level sums (may be partial if YouTube Analytics limits rows)."
-                            .to_string(),
-                    );
-                }
-                if stale {
-                    notes.push(
-                        "Latest metric date is behind the requested end_dt (sync may be stale)."
-                            .to_string(),
-                    );
-                }
-                if coverage < 0.8 {
-                    notes.push(
-                        "Low coverage: fewer days with data than expected in the window."
-                            .to_string(),
-                    );
-                }
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct RevenueReconciliation {
+    days_compared: i64,
+    channel_total_usd: f64,
+    video_sum_usd: f64,
+    abs_diff_usd: f64,
+    rel_diff_pct: Option<f64>,
+    tolerance_pct: f64,
+    within_tolerance: bool,
+}
 
-                Some(serde_json::json!({
-                  "ok": true,
-                  "channel_id": channel_id,
-                  "window": window,
-                  "baseline_window": baseline_window,
-                  "current": current,
-                  "baseline": baseline,
-                  "notes": notes,
-                }))
-            }
-            (Err(err), _) | (_, Err(err)) => {
-                errors.insert(
-                    "health".to_string(),
-                    serde_json::Value::String(truncate_string(&err.to_string(), 2000)),
-                );
-                None
-            }
+/// Compares unrounded revenue on days that have both a channel-total row and video rows, from
+/// `(dt, channel_total_usd, video_sum_usd)`; rounding is applied to the reported figures only.
+fn reconcile_revenue(
+    rows: &[(NaiveDate, Option<f64>, Option<f64>)],
+    tolerance_pct: f64,
+) -> RevenueReconciliation {
+    let mut days_compared = 0;
+    let mut channel_total_usd = 0.0;
+    let mut video_sum_usd = 0.0;
+    for (_dt, channel_total, video_sum) in rows {
+        if let (Some(channel_total), Some(video_sum)) = (channel_total, video_sum) {
+            days_compared += 1;
+            channel_total_usd += channel_total;
+            video_sum_usd += video_sum;
         }
+    }
+
+    let abs_diff_usd = (channel_total_usd - video_sum_usd).abs();
+    let rel_diff_pct = if channel_total_usd.abs() > 0.0 {
+        Some(abs_diff_usd / channel_total_usd.abs() * 100.0)
+    } else {
+        None
+    };
+    let within_tolerance = match rel_diff_pct {
+        Some(pct) => pct <= tolerance_pct,
+        None => abs_diff_usd < 0.005,
     };
 
-    let metrics: Vec<MetricDailyItem> = match sqlx::query_as::<_, (NaiveDate, f64, i64, i64, f64, i64)>(
-        r#"
+    RevenueReconciliation {
+        days_compared,
+        channel_total_usd: round2(channel_total_usd),
+        video_sum_usd: round2(video_sum_usd),
+        abs_diff_usd: round2(abs_diff_usd),
+        rel_diff_pct: rel_diff_pct.map(|v| (v * 100.0).round() / 100.0),
+        tolerance_pct,
+        within_tolerance,
+    }
+}
+
+async fn fetch_revenue_reconciliation_rows(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    start_dt: NaiveDate,
+    end_dt: NaiveDate,
+) -> Result<Vec<(NaiveDate, Option<f64>, Option<f64>)>, Error> {
+    sqlx::query_as::<_, (NaiveDate, Option<f64>, Option<f64>)>(
+        &format!(
+            r#"
       SELECT dt,
              CAST(COALESCE(
-               SUM(CASE WHEN video_id='csv_channel_total' THEN estimated_revenue_usd END),
-               SUM(CASE WHEN video_id='__CHANNEL_TOTAL__' THEN estimated_revenue_usd END),
-               0
-             ) AS DOUBLE) AS revenue_usd,
-             CAST(COALESCE(
-               SUM(CASE WHEN video_id='csv_channel_total' THEN impressions END),
-               SUM(CASE WHEN video_id='__CHANNEL_TOTAL__' THEN impressions END),
-               0
-             ) AS SIGNED) AS impressions,
-             CAST(COALESCE(
-               SUM(CASE WHEN video_id='csv_channel_total' THEN views END),
-               SUM(CASE WHEN video_id='__CHANNEL_TOTAL__' THEN views END),
-               0
-             ) AS SIGNED) AS views,
-             CAST(COALESCE(
-               SUM(CASE WHEN video_id='csv_channel_total' THEN impressions_ctr * impressions END),
-               SUM(CASE WHEN video_id='__CHANNEL_TOTAL__' THEN impressions_ctr * impressions END),
-               0
-             ) AS DOUBLE) AS ctr_num,
-             CAST(COALESCE(
-               SUM(CASE WHEN video_id='csv_channel_total' AND impressions_ctr IS NOT NULL THEN impressions END),
-               SUM(CASE WHEN video_id='__CHANNEL_TOTAL__' AND impressions_ctr IS NOT NULL THEN impressions END),
-               0
-             ) AS SIGNED) AS ctr_denom
+               {sums_0}
+             ) AS DOUBLE) AS channel_total_usd,
+             CAST(SUM(CASE WHEN {video_rows} THEN estimated_revenue_usd END) AS DOUBLE) AS video_sum_usd
       FROM video_daily_metrics
       WHERE tenant_id = ?
         AND channel_id = ?
         AND dt BETWEEN ? AND ?
-        AND video_id IN ('__CHANNEL_TOTAL__','csv_channel_total')
       GROUP BY dt
       ORDER BY dt ASC;
     "#,
+            sums_0 = channel_total_sums("estimated_revenue_usd"),
+            video_rows = video_rows_filter(),
+        ),
     )
-    .bind(tenant_id.trim())
-    .bind(channel_id.trim())
+    .bind(tenant_id)
+    .bind(channel_id)
     .bind(start_dt)
     .bind(end_dt)
     .fetch_all(pool)
     .await
-    {
-        Ok(totals) => {
-            let rows: Vec<(NaiveDate, f64, i64, i64, f64, i64)> = if !totals.is_empty() {
-                totals
-            } else {
-                match sqlx::query_as::<_, (NaiveDate, f64, i64, i64, f64, i64)>(
-                    r#"
-              SELECT dt,
-                     CAST(SUM(estimated_revenue_usd) AS DOUBLE) AS revenue_usd,
-                     CAST(SUM(impressions) AS SIGNED) AS impressions,
-                     CAST(SUM(views) AS SIGNED) AS views,
-                     CAST(COALESCE(SUM(impressions_ctr * impressions), 0) AS DOUBLE) AS ctr_num,
-                     CAST(COALESCE(SUM(CASE WHEN impressions_ctr IS NOT NULL THEN impressions ELSE 0 END), 0) AS SIGNED) AS ctr_denom
-              FROM video_daily_metrics
-              WHERE tenant_id = ?
-                AND channel_id = ?
-                AND dt BETWEEN ? AND ?
-                AND video_id NOT IN ('__CHANNEL_TOTAL__','csv_channel_total')
-              GROUP BY dt
-              ORDER BY dt ASC;
-            "#,
-                )
-                .bind(tenant_id.trim())
-                .bind(channel_id.trim())
-                .bind(start_dt)
-                .bind(end_dt)
-                .fetch_all(pool)
-                .await
-                {
-                    Ok(v) => v,
-                    Err(err) => {
-                        errors.insert(
-                            "metrics".to_string(),
-                            serde_json::Value::String(truncate_string(&err.to_string(), 2000)),
-                        );
-                        Vec::new()
-                    }
-                }
-            };
+    .map_err(|e| -> Error { Box::new(e) })
+}
 
-            rows.into_iter()
-                .map(|(dt, revenue_usd, impressions, views, ctr_num, ctr_denom)| {
-                    let ctr = if ctr_denom > 0 {
-                        Some(ctr_num / (ctr_denom as f64))
-                    } else {
-                        None
-                    };
-                    let rpm = if views > 0 {
-                        (revenue_usd / (views as f64)) * 1000.0
-                    } else {
-                        0.0
-                    };
-                    MetricDailyItem {
-                        date: dt.to_string(),
-                        video_id: "channel_total".to_string(),
-                        impressions,
-                        views,
-                        revenue_usd: round2(revenue_usd),
-                        ctr: ctr.map(|v| (v * 10000.0).round() / 10000.0),
-                        rpm: round2(rpm),
-                        source: "tidb".to_string(),
-                    }
-                })
-                .collect()
-        }
-        Err(err) => {
-            errors.insert(
-                "metrics".to_string(),
-                serde_json::Value::String(truncate_string(&err.to_string(), 2000)),
-            );
-            Vec::new()
-        }
-    };
-
-    let alerts: Vec<AlertItem> = match sqlx::query_as::<
-        _,
-        (
-            i64,
-            String,
-            String,
-            String,
-            DateTime<Utc>,
-            Option<DateTime<Utc>>,
-            Option<String>,
-        ),
-    >(
-        r#"
-	          SELECT id, kind, severity, message,
-	                 CAST(detected_at AS DATETIME) AS detected_at,
-	                 CAST(resolved_at AS DATETIME) AS resolved_at,
-	                 details_json
-	          FROM yt_alerts
-	          WHERE tenant_id = ? AND channel_id = ?
-	          ORDER BY (resolved_at IS NULL) DESC, detected_at DESC
-          LIMIT 50;
-        "#,
-    )
-    .bind(tenant_id.trim())
-    .bind(channel_id.trim())
-    .fetch_all(pool)
-    .await
-    {
-        Ok(rows) => rows
-            .into_iter()
-            .map(
-                |(id, kind, severity, message, detected_at, resolved_at, details_json)| AlertItem {
-                    id: format!("alert_{id}"),
-                    kind,
-                    severity,
-                    message,
-                    details: details_json
-                        .as_deref()
-                        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok()),
-                    detected_at: datetime_to_rfc3339_utc(detected_at),
-                    resolved_at: resolved_at.map(datetime_to_rfc3339_utc),
-                },
-            )
-            .collect(),
-        Err(err) => {
-            errors.insert(
-                "alerts".to_string(),
-                serde_json::Value::String(truncate_string(&err.to_string(), 2000)),
-            );
-            Vec::new()
-        }
-    };
-
-    let outcome_latest: Option<OutcomeLatestItem> =
-        match fetch_outcome_latest(pool, tenant_id.trim(), channel_id.trim()).await {
-            Ok(v) => v,
-            Err(err) => {
-                errors.insert(
-                    "outcome".to_string(),
-                    serde_json::Value::String(truncate_string(&err.to_string(), 2000)),
-                );
-                None
-            }
-        };
-
-    json_response(
-        StatusCode::OK,
-        serde_json::json!({
-          "ok": true,
-          "channel_id": channel_id,
-          "start_dt": start_dt.to_string(),
-          "end_dt": end_dt.to_string(),
-          "health": health,
-          "metrics": metrics,
-          "alerts": alerts,
-          "outcome_latest": outcome_latest,
-          "errors": errors,
-        }),
-    )
-}
-
-async fn handle_youtube_sync_bundle(
+async fn handle_youtube_data_health(
     method: &Method,
     headers: &HeaderMap,
     uri: &Uri,
@@ -3275,90 +4941,6 @@ async fn handle_youtube_sync_bundle(
         );
     }
 
-    let mut errors = serde_json::Map::new();
-
-    let sync_status = match sqlx::query_as::<
-        _,
-        (
-            i64,
-            String,
-            Option<NaiveDate>,
-            String,
-            i64,
-            i64,
-            DateTime<Utc>,
-            DateTime<Utc>,
-            Option<String>,
-        ),
-    >(
-        r#"
-      SELECT id, job_type, run_for_dt, status, attempt, max_attempt,
-             run_after,
-             updated_at,
-             last_error
-      FROM job_tasks
-      WHERE tenant_id = ?
-        AND channel_id = ?
-        AND job_type IN ('daily_channel','weekly_channel','youtube_reporting_owner')
-      ORDER BY updated_at DESC
-      LIMIT 30;
-    "#,
-    )
-    .bind(tenant_id.trim())
-    .bind(channel_id.trim())
-    .fetch_all(pool)
-    .await
-    {
-        Ok(rows) => {
-            let mut counts = serde_json::Map::new();
-            for status in rows.iter().map(|(_, _, _, status, _, _, _, _, _)| status) {
-                let v = counts
-                    .entry(status.clone())
-                    .or_insert(serde_json::Value::Number(0.into()));
-                if let serde_json::Value::Number(n) = v {
-                    let next = n.as_i64().unwrap_or(0) + 1;
-                    *v = serde_json::Value::Number(next.into());
-                }
-            }
-
-            let items: Vec<SyncStatusTaskItem> = rows
-                .into_iter()
-                .map(
-                    |(
-                        id,
-                        job_type,
-                        run_for_dt,
-                        status,
-                        attempt,
-                        max_attempt,
-                        run_after,
-                        updated_at,
-                        last_error,
-                    )| SyncStatusTaskItem {
-                        id,
-                        job_type,
-                        run_for_dt: run_for_dt.map(|d| d.to_string()),
-                        status,
-                        attempt,
-                        max_attempt,
-                        run_after: datetime_to_rfc3339_utc(run_after),
-                        updated_at: datetime_to_rfc3339_utc(updated_at),
-                        last_error: last_error.map(|e| truncate_string(&e, 800)),
-                    },
-                )
-                .collect();
-
-            Some(serde_json::json!({"counts": counts, "items": items}))
-        }
-        Err(err) => {
-            errors.insert(
-                "sync_status".to_string(),
-                serde_json::Value::String(truncate_string(&err.to_string(), 2000)),
-            );
-            None
-        }
-    };
-
     let today = Utc::now().date_naive();
     let default_end = today - Duration::days(1);
     let start_dt = get_query_param(uri, "start_dt")
@@ -3368,372 +4950,182 @@ async fn handle_youtube_sync_bundle(
         .and_then(|v| NaiveDate::parse_from_str(v.trim(), "%Y-%m-%d").ok())
         .unwrap_or(default_end);
 
-    let health = {
-        let days = ((end_dt - start_dt).num_days() + 1).max(1);
-        let baseline_start = start_dt - Duration::days(days);
-        let baseline_end = start_dt - Duration::days(1);
+    let baseline_mode = DataHealthBaseline::from_uri(uri);
+    let days = ((end_dt - start_dt).num_days() + 1).max(1);
+    let (baseline_start, baseline_end) = baseline_mode.window(start_dt, end_dt);
 
-        let window = DataHealthWindow {
-            start_dt: start_dt.to_string(),
-            end_dt: end_dt.to_string(),
-            days,
-        };
-        let baseline_window = DataHealthWindow {
-            start_dt: baseline_start.to_string(),
-            end_dt: baseline_end.to_string(),
-            days,
-        };
+    let window = DataHealthWindow {
+        start_dt: start_dt.to_string(),
+        end_dt: end_dt.to_string(),
+        days,
+    };
+    let baseline_window = DataHealthWindow {
+        start_dt: baseline_start.to_string(),
+        end_dt: baseline_end.to_string(),
+        days,
+    };
+
+    let current =
+        aggregate_data_health_period(pool, tenant_id.trim(), channel_id.trim(), start_dt, end_dt)
+            .await?;
+    let baseline = aggregate_data_health_period(
+        pool,
+        tenant_id.trim(),
+        channel_id.trim(),
+        baseline_start,
+        baseline_end,
+    )
+    .await?;
+
+    let expected_days = days;
+    let coverage = if expected_days > 0 {
+        (current.days_with_data as f64) / (expected_days as f64)
+    } else {
+        0.0
+    };
 
-        let current = aggregate_data_health_period(
+    let policy_params_json =
+        fetch_policy_params_json(pool, tenant_id.trim(), channel_id.trim(), "active").await?;
+    let sla_days = freshness_sla_days(policy_params_json.as_deref());
+    let reconciliation = reconcile_revenue(
+        &fetch_revenue_reconciliation_rows(
             pool,
             tenant_id.trim(),
             channel_id.trim(),
             start_dt,
             end_dt,
         )
-        .await;
-        let baseline = aggregate_data_health_period(
-            pool,
-            tenant_id.trim(),
-            channel_id.trim(),
-            baseline_start,
-            baseline_end,
-        )
-        .await;
-
-        match (current, baseline) {
-            (Ok(current), Ok(baseline)) => {
-                let expected_days = days;
-                let coverage = if expected_days > 0 {
-                    (current.days_with_data as f64) / (expected_days as f64)
-                } else {
-                    0.0
-                };
+        .await?,
+        revenue_reconciliation_tolerance_pct(policy_params_json.as_deref()),
+    );
 
-                let stale = current
-                    .last_dt
-                    .as_deref()
-                    .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
-                    .map(|dt| dt < end_dt)
-                    .unwrap_or(true);
-
-                let mut notes: Vec<String> = Vec::new();
-                if current.partial {
-                    notes.push(
-                        "Using video-level sums (may be partial if YouTube Analytics limits rows)."
-                            .to_string(),
-                    );
-                }
-                if stale {
-                    notes.push(
-                        "Latest metric date is behind the requested end_dt (sync may be stale)."
-                            .to_string(),
-                    );
-                }
-                if coverage < 0.8 {
-                    notes.push(
-                        "Low coverage: fewer days with data than expected in the window."
-                            .to_string(),
-                    );
-                }
+    let lag_days = current
+        .last_dt
+        .as_deref()
+        .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+        .map(|dt| ((end_dt - dt).num_days().max(0), dt));
+    let stale = freshness_sla_breached(lag_days.map(|(lag, _)| lag), sla_days);
 
-                Some(serde_json::json!({
-                  "ok": true,
-                  "channel_id": channel_id,
-                  "window": window,
-                  "baseline_window": baseline_window,
-                  "current": current,
-                  "baseline": baseline,
-                  "notes": notes,
-                }))
-            }
-            (Err(err), _) | (_, Err(err)) => {
-                errors.insert(
-                    "health".to_string(),
-                    serde_json::Value::String(truncate_string(&err.to_string(), 2000)),
-                );
-                None
-            }
+    let mut notes: Vec<String> = Vec::new();
+    if current.partial {
+        notes.push(
+            "Using video-level sums (may be partial if YouTube Analytics limits rows).".to_string(),
+        );
+    }
+    if let Some((lag, dt)) = lag_days {
+        if lag > 0 && !stale {
+            notes.push(format!(
+                "YouTube Analytics often lags 1–2 days. Latest dt {dt} (lag {lag}d vs end_dt {end_dt}; SLA {sla_days}d)."
+            ));
+        } else if stale {
+            notes.push(format!(
+                "Latest metric date is behind the requested end_dt (lag {lag}d exceeds SLA {sla_days}d; latest dt {dt}). Sync may be stale."
+            ));
         }
-    };
+    } else if stale {
+        notes.push("No metrics found yet in this window (sync may be stale).".to_string());
+    }
+    if coverage < 0.8 {
+        notes.push("Low coverage: fewer days with data than expected in the window.".to_string());
+    }
+    notes.extend(baseline_mode.note(baseline_start, baseline_end));
+    if reconciliation.days_compared > 0 && !reconciliation.within_tolerance {
+        notes.push(format!(
+            "Per-video revenue sums to ${:.2} vs channel total ${:.2} (diff ${:.2}); YouTube reports channel totals separately, so small gaps are expected.",
+            reconciliation.video_sum_usd, reconciliation.channel_total_usd, reconciliation.abs_diff_usd
+        ));
+    }
 
-    let uploads = match sqlx::query_as::<_, CsvUploadRow>(
-        r#"
-      SELECT id, filename, status, created_at
-      FROM yt_csv_uploads
-      WHERE tenant_id = ?
-        AND channel_id = ?
-      ORDER BY created_at DESC
-      LIMIT 20;
-    "#,
+    json_response(
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "channel_id": channel_id,
+          "window": window,
+          "baseline_window": baseline_window,
+          "baseline_mode": baseline_mode.as_str(),
+          "comparison": compare_data_health_periods(&current, &baseline),
+          "current": current,
+          "baseline": baseline,
+          "freshness": {
+            "sla_days": sla_days,
+            "lag_days": lag_days.map(|(lag, _)| lag),
+            "sla_breached": stale
+          },
+          "reconciliation": reconciliation,
+          "notes": notes
+        }),
     )
-    .bind(tenant_id.trim())
-    .bind(channel_id.trim())
-    .fetch_all(pool)
-    .await
-    {
-        Ok(rows) => rows
-            .into_iter()
-            .map(|(id, filename, status, created_at)| UploadItem {
-                id: format!("upload_{id}"),
-                filename,
-                channel_id: channel_id.clone(),
-                created_at: datetime_to_rfc3339_utc(created_at),
-                status,
-            })
-            .collect(),
-        Err(err) => {
-            errors.insert(
-                "uploads".to_string(),
-                serde_json::Value::String(truncate_string(&err.to_string(), 2000)),
-            );
-            Vec::new()
-        }
-    };
+}
 
-    let reporting = match fetch_youtube_content_owner_id(pool, tenant_id.trim()).await {
-        Ok(Some(content_owner_id)) if !content_owner_id.trim().is_empty() => {
-            let owner_id = content_owner_id.trim();
+#[derive(serde::Serialize)]
+struct OutcomeLatestItem {
+    decision_dt: String,
+    outcome_dt: String,
+    revenue_change_pct_7d: Option<f64>,
+    catastrophic_flag: bool,
+    new_top_asset_flag: bool,
+    /// The post window was still missing days; the outcome is recomputed once they land.
+    provisional: bool,
+    notes: Option<serde_json::Value>,
+}
 
-            let jobs_rows = sqlx::query_as::<_, (String, String, DateTime<Utc>, DateTime<Utc>)>(
-                r#"
-          SELECT report_type_id, job_id, created_at, updated_at
-          FROM yt_reporting_jobs
-          WHERE tenant_id = ? AND content_owner_id = ?
-          ORDER BY updated_at DESC
-          LIMIT 50;
-        "#,
-            )
-            .bind(tenant_id.trim())
-            .bind(owner_id)
-            .fetch_all(pool)
-            .await
-            .unwrap_or_default();
-
-            let mut jobs_by_type: std::collections::HashMap<String, String> =
-                std::collections::HashMap::new();
-            for (report_type_id, job_id, _created_at, _updated_at) in jobs_rows.into_iter() {
-                jobs_by_type.entry(report_type_id).or_insert(job_id);
-            }
-
-            let stats_rows = sqlx::query_as::<
-                _,
-                (
-                    String,
-                    i64,
-                    i64,
-                    i64,
-                    Option<DateTime<Utc>>,
-                    Option<DateTime<Utc>>,
-                ),
-            >(
-                r#"
-          SELECT report_type_id,
-                 CAST(COUNT(*) AS SIGNED) AS total_reports,
-                 CAST(SUM(CASE WHEN downloaded_at IS NOT NULL THEN 1 ELSE 0 END) AS SIGNED) AS reports_downloaded,
-                 CAST(SUM(CASE WHEN parse_status='parsed' THEN 1 ELSE 0 END) AS SIGNED) AS reports_parsed,
-                 MAX(create_time) AS last_create_time,
-                 MAX(parsed_at) AS last_parsed_at
-          FROM yt_reporting_report_files
-          WHERE tenant_id = ? AND content_owner_id = ?
-          GROUP BY report_type_id
-          ORDER BY last_create_time DESC;
-        "#,
-            )
-            .bind(tenant_id.trim())
-            .bind(owner_id)
-            .fetch_all(pool)
-            .await
-            .unwrap_or_default();
-
-            let error_rows = sqlx::query_as::<_, (String, String, DateTime<Utc>)>(
-                r#"
-            SELECT report_type_id, parse_error, updated_at
-            FROM yt_reporting_report_files
-            WHERE tenant_id = ?
-              AND content_owner_id = ?
-              AND parse_status = 'error'
-              AND parse_error IS NOT NULL
-            ORDER BY updated_at DESC
-            LIMIT 50;
-          "#,
-            )
-            .bind(tenant_id.trim())
-            .bind(owner_id)
-            .fetch_all(pool)
-            .await
-            .unwrap_or_default();
-
-            let mut last_error_by_type: std::collections::HashMap<String, (String, String)> =
-                std::collections::HashMap::new();
-            for (report_type_id, parse_error, updated_at) in error_rows.into_iter() {
-                if last_error_by_type.contains_key(&report_type_id) {
-                    continue;
-                }
-                last_error_by_type.insert(
-                    report_type_id,
-                    (
-                        truncate_string(&parse_error, 800),
-                        datetime_to_rfc3339_utc(updated_at),
-                    ),
-                );
-            }
-
-            let report_types: Vec<serde_json::Value> = stats_rows
-                .into_iter()
-                .map(
-                    |(report_type_id, total, downloaded, parsed, last_create, last_parsed)| {
-                        let job_id = jobs_by_type.get(&report_type_id).cloned();
-                        let last_error =
-                            last_error_by_type.get(&report_type_id).map(|v| v.0.clone());
-                        let last_error_at =
-                            last_error_by_type.get(&report_type_id).map(|v| v.1.clone());
-                        serde_json::json!({
-                          "report_type_id": report_type_id,
-                          "job_id": job_id,
-                          "reports_total": total,
-                          "reports_downloaded": downloaded,
-                          "reports_parsed": parsed,
-                          "last_create_time": last_create.map(datetime_to_rfc3339_utc),
-                          "last_parsed_at": last_parsed.map(datetime_to_rfc3339_utc),
-                          "last_error": last_error,
-                          "last_error_at": last_error_at,
-                        })
-                    },
-                )
-                .collect();
-
-            Some(serde_json::json!({
-              "ok": true,
-              "docs": "https://developers.google.com/youtube/reporting",
-              "note": "Reporting API jobs can take up to ~24h to generate the first daily reports after enabling/creating the job.",
-              "content_owner_id": owner_id,
-              "report_types": report_types,
-            }))
-        }
-        Ok(_) => None,
-        Err(err) => {
-            errors.insert(
-                "reporting".to_string(),
-                serde_json::Value::String(truncate_string(&err.to_string(), 2000)),
-            );
-            None
-        }
-    };
-
-    let alerts: Vec<AlertItem> = match sqlx::query_as::<
-        _,
-        (
-            i64,
-            String,
-            String,
-            String,
-            DateTime<Utc>,
-            Option<DateTime<Utc>>,
-            Option<String>,
-        ),
-    >(
+async fn fetch_outcome_latest(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+) -> Result<Option<OutcomeLatestItem>, Error> {
+    let row = sqlx::query_as::<_, (NaiveDate, NaiveDate, Option<f64>, i8, i8, i8, Option<String>)>(
         r#"
-	          SELECT id, kind, severity, message,
-	                 CAST(detected_at AS DATETIME) AS detected_at,
-	                 CAST(resolved_at AS DATETIME) AS resolved_at,
-	                 details_json
-	          FROM yt_alerts
-	          WHERE tenant_id = ? AND channel_id = ?
-	          ORDER BY (resolved_at IS NULL) DESC, detected_at DESC
-          LIMIT 50;
+          SELECT decision_dt, outcome_dt, revenue_change_pct_7d, catastrophic_flag, new_top_asset_flag,
+                 provisional, notes
+          FROM decision_outcome
+          WHERE tenant_id = ? AND channel_id = ?
+          ORDER BY outcome_dt DESC, decision_dt DESC
+          LIMIT 1;
         "#,
     )
-    .bind(tenant_id.trim())
-    .bind(channel_id.trim())
-    .fetch_all(pool)
+    .bind(tenant_id)
+    .bind(channel_id)
+    .fetch_optional(pool)
     .await
-    {
-        Ok(rows) => rows
-            .into_iter()
-            .map(
-                |(id, kind, severity, message, detected_at, resolved_at, details_json)| AlertItem {
-                    id: format!("alert_{id}"),
-                    kind,
-                    severity,
-                    message,
-                    details: details_json
-                        .as_deref()
-                        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok()),
-                    detected_at: datetime_to_rfc3339_utc(detected_at),
-                    resolved_at: resolved_at.map(datetime_to_rfc3339_utc),
-                },
-            )
-            .collect(),
-        Err(err) => {
-            errors.insert(
-                "alerts".to_string(),
-                serde_json::Value::String(truncate_string(&err.to_string(), 2000)),
-            );
-            Vec::new()
-        }
-    };
+    .map_err(|e| -> Error { Box::new(e) })?;
 
-    let share_latest =
-        match sqlx::query_as::<_, (String, Option<DateTime<Utc>>, i64, Option<DateTime<Utc>>)>(
-            r#"
-          SELECT token,
-                 CAST(expires_at AS DATETIME) AS expires_at,
-                 CAST(hits AS SIGNED) AS hits,
-                 CAST(last_opened_at AS DATETIME) AS last_opened_at
-          FROM yt_report_shares
-          WHERE tenant_id = ?
-            AND channel_id = ?
-            AND start_dt = ?
-            AND end_dt = ?
-            AND (expires_at IS NULL OR expires_at > ?)
-          ORDER BY created_at DESC
-          LIMIT 1;
-        "#,
-        )
-        .bind(tenant_id.trim())
-        .bind(channel_id.trim())
-        .bind(start_dt)
-        .bind(end_dt)
-        .bind(Utc::now())
-        .fetch_optional(pool)
-        .await
-        {
-            Ok(Some((token, expires_at, hits, last_opened_at))) => Some(serde_json::json!({
-              "token": token,
-              "expires_at": expires_at.map(datetime_to_rfc3339_utc),
-              "hits": hits,
-              "last_opened_at": last_opened_at.map(datetime_to_rfc3339_utc),
-            })),
-            Ok(None) => None,
-            Err(err) => {
-                errors.insert(
-                    "share_latest".to_string(),
-                    serde_json::Value::String(truncate_string(&err.to_string(), 2000)),
-                );
-                None
-            }
-        };
+    Ok(row.map(
+        |(
+            decision_dt,
+            outcome_dt,
+            revenue_change_pct_7d,
+            catastrophic_flag,
+            new_top_asset_flag,
+            provisional,
+            notes,
+        )| {
+            let notes_json = notes.as_deref().and_then(|raw| {
+                let trimmed = raw.trim();
+                if trimmed.is_empty() {
+                    return None;
+                }
+                match serde_json::from_str::<serde_json::Value>(trimmed) {
+                    Ok(v) => Some(v),
+                    Err(_) => Some(serde_json::Value::String(trimmed.to_string())),
+                }
+            });
 
-    json_response(
-        StatusCode::OK,
-        serde_json::json!({
-          "ok": true,
-          "channel_id": channel_id,
-          "start_dt": start_dt.to_string(),
-          "end_dt": end_dt.to_string(),
-          "sync_status": sync_status,
-          "health": health,
-          "alerts": alerts,
-          "uploads": uploads,
-          "reporting": reporting,
-          "share_latest": share_latest,
-          "errors": errors,
-        }),
-    )
+            OutcomeLatestItem {
+                decision_dt: decision_dt.to_string(),
+                outcome_dt: outcome_dt.to_string(),
+                revenue_change_pct_7d,
+                catastrophic_flag: catastrophic_flag != 0,
+                new_top_asset_flag: new_top_asset_flag != 0,
+                provisional: provisional != 0,
+                notes: notes_json,
+            }
+        },
+    ))
 }
 
-async fn handle_youtube_reporting_status(
+async fn handle_youtube_outcome_latest(
     method: &Method,
     headers: &HeaderMap,
     uri: &Uri,
@@ -3771,157 +5163,164 @@ async fn handle_youtube_reporting_status(
     }
 
     let pool = get_pool().await?;
-    let owner = match get_query_param(uri, "content_owner_id")
+    let channel_id = match get_query_param(uri, "channel_id")
         .map(|v| v.trim().to_string())
         .filter(|v| !v.is_empty())
     {
-        Some(v) => Some(v),
-        None => fetch_youtube_content_owner_id(pool, tenant_id.trim()).await?,
+        Some(v) => v,
+        None => fetch_youtube_channel_id(pool, tenant_id.trim())
+            .await?
+            .unwrap_or_default(),
     };
 
-    let Some(owner_id) = owner.filter(|v| !v.trim().is_empty()) else {
+    if channel_id.trim().is_empty() {
         return json_response(
-            StatusCode::OK,
-            serde_json::json!({
-              "ok": true,
-              "docs": "https://developers.google.com/youtube/reporting",
-              "note": "Content owner id not discovered yet. Ensure YouTube Partner scope is granted and run sync again.",
-              "content_owner_id": null,
-              "report_types": [],
-            }),
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "No active YouTube channel for this tenant"}),
         );
-    };
-
-    let jobs_rows = sqlx::query_as::<_, (String, String, DateTime<Utc>, DateTime<Utc>)>(
-        r#"
-      SELECT report_type_id, job_id, created_at, updated_at
-      FROM yt_reporting_jobs
-      WHERE tenant_id = ? AND content_owner_id = ?
-      ORDER BY updated_at DESC
-      LIMIT 50;
-    "#,
-    )
-    .bind(tenant_id.trim())
-    .bind(owner_id.trim())
-    .fetch_all(pool)
-    .await
-    .unwrap_or_default();
-
-    let mut jobs_by_type: std::collections::HashMap<String, String> =
-        std::collections::HashMap::new();
-    for (report_type_id, job_id, _created_at, _updated_at) in jobs_rows.into_iter() {
-        jobs_by_type.entry(report_type_id).or_insert(job_id);
     }
 
-    let stats_rows = sqlx::query_as::<
-        _,
-        (
-            String,
-            i64,
-            i64,
-            i64,
-            Option<DateTime<Utc>>,
-            Option<DateTime<Utc>>,
+    match fetch_outcome_latest(pool, tenant_id.trim(), channel_id.trim()).await {
+        Ok(Some(item)) => json_response(
+            StatusCode::OK,
+            serde_json::json!({"ok": true, "channel_id": channel_id, "found": true, "item": item}),
         ),
-    >(
-        r#"
-      SELECT report_type_id,
-             CAST(COUNT(*) AS SIGNED) AS total_reports,
-             CAST(SUM(CASE WHEN downloaded_at IS NOT NULL THEN 1 ELSE 0 END) AS SIGNED) AS reports_downloaded,
-             CAST(SUM(CASE WHEN parse_status='parsed' THEN 1 ELSE 0 END) AS SIGNED) AS reports_parsed,
-             MAX(create_time) AS last_create_time,
-             MAX(parsed_at) AS last_parsed_at
-      FROM yt_reporting_report_files
-      WHERE tenant_id = ? AND content_owner_id = ?
-      GROUP BY report_type_id
-      ORDER BY last_create_time DESC;
-    "#,
-    )
-    .bind(tenant_id.trim())
-    .bind(owner_id.trim())
-    .fetch_all(pool)
-    .await
-    .unwrap_or_default();
+        Ok(None) => json_response(
+            StatusCode::OK,
+            serde_json::json!({"ok": true, "channel_id": channel_id, "found": false, "item": null}),
+        ),
+        Err(err) => json_response(
+            StatusCode::BAD_GATEWAY,
+            serde_json::json!({"ok": false, "error": "outcome_query_failed", "message": truncate_string(&err.to_string(), 2000), "channel_id": channel_id}),
+        ),
+    }
+}
 
-    let error_rows = sqlx::query_as::<_, (String, String, DateTime<Utc>)>(
-        r#"
-        SELECT report_type_id, parse_error, updated_at
-        FROM yt_reporting_report_files
-        WHERE tenant_id = ?
-          AND content_owner_id = ?
-          AND parse_status = 'error'
-          AND parse_error IS NOT NULL
-        ORDER BY updated_at DESC
-        LIMIT 50;
-      "#,
-    )
-    .bind(tenant_id.trim())
-    .bind(owner_id.trim())
-    .fetch_all(pool)
-    .await
-    .unwrap_or_default();
+#[derive(Debug, serde::Serialize)]
+struct DecisionHistoryItem {
+    as_of_dt: String,
+    direction: String,
+    confidence: f64,
+    previous_direction: Option<String>,
+    direction_changed: bool,
+}
 
-    let mut last_error_by_type: std::collections::HashMap<String, (String, String)> =
-        std::collections::HashMap::new();
-    for (report_type_id, parse_error, updated_at) in error_rows.into_iter() {
-        if last_error_by_type.contains_key(&report_type_id) {
-            continue;
-        }
-        last_error_by_type.insert(
-            report_type_id,
-            (
-                truncate_string(&parse_error, 800),
-                datetime_to_rfc3339_utc(updated_at),
-            ),
+/// Flags each row whose direction differs from the previous stored decision (rows sorted by
+/// as_of_dt; missing days compare against the last decision before the gap).
+fn annotate_direction_changes(rows: Vec<(NaiveDate, String, f64)>) -> Vec<DecisionHistoryItem> {
+    let mut previous: Option<String> = None;
+    rows.into_iter()
+        .map(|(as_of_dt, direction, confidence)| {
+            let direction_changed = previous.as_deref().is_some_and(|prev| prev != direction);
+            DecisionHistoryItem {
+                as_of_dt: as_of_dt.to_string(),
+                previous_direction: previous.replace(direction.clone()),
+                direction,
+                confidence,
+                direction_changed,
+            }
+        })
+        .collect()
+}
+
+/// What changed between two stored decisions: `dt` against `before_dt`, or against the latest
+/// decision before `dt` when `before_dt` is omitted.
+async fn handle_youtube_decision_diff(
+    method: &Method,
+    headers: &HeaderMap,
+    uri: &Uri,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::GET {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
         );
     }
 
-    let report_types: Vec<serde_json::Value> = stats_rows
-        .into_iter()
-        .map(
-            |(report_type_id, total, downloaded, parsed, last_create, last_parsed)| {
-                let job_id = jobs_by_type.get(&report_type_id).cloned();
-                let last_error = last_error_by_type.get(&report_type_id).map(|v| v.0.clone());
-                let last_error_at = last_error_by_type.get(&report_type_id).map(|v| v.1.clone());
-                serde_json::json!({
-                  "report_type_id": report_type_id,
-                  "job_id": job_id,
-                  "reports_total": total,
-                  "reports_downloaded": downloaded,
-                  "reports_parsed": parsed,
-                  "last_create_time": last_create.map(datetime_to_rfc3339_utc),
-                  "last_parsed_at": last_parsed.map(datetime_to_rfc3339_utc),
-                  "last_error": last_error,
-                  "last_error_at": last_error_at,
-                })
-            },
-        )
-        .collect();
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let tenant_id = get_query_param(uri, "tenant_id").unwrap_or_default();
+    if tenant_id.trim().is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+        );
+    }
+
+    let Some(dt) = get_query_param(uri, "dt").and_then(|v| parse_dt(&v)) else {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "dt (YYYY-MM-DD) is required"}),
+        );
+    };
+    let before_dt = get_query_param(uri, "before_dt").and_then(|v| parse_dt(&v));
+    if before_dt.is_some_and(|b| b >= dt) {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "before_dt must be < dt"}),
+        );
+    }
+
+    let pool = get_pool().await?;
+    let channel_id = match get_query_param(uri, "channel_id")
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+    {
+        Some(v) => v,
+        None => fetch_youtube_channel_id(pool, tenant_id.trim())
+            .await?
+            .unwrap_or_default(),
+    };
+
+    if channel_id.trim().is_empty() {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "No active YouTube channel for this tenant"}),
+        );
+    }
+
+    let after = fetch_decision_daily(pool, tenant_id.trim(), channel_id.trim(), dt, false).await?;
+    let before = match before_dt {
+        Some(before_dt) => {
+            fetch_decision_daily(pool, tenant_id.trim(), channel_id.trim(), before_dt, false)
+                .await?
+        }
+        None => fetch_decision_daily(pool, tenant_id.trim(), channel_id.trim(), dt, true).await?,
+    };
+    let (Some(before), Some(after)) = (before, after) else {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_found", "message": "No stored decision for one of the dates"}),
+        );
+    };
 
+    let diff = diff_decisions(&before, &after);
     json_response(
         StatusCode::OK,
         serde_json::json!({
           "ok": true,
-          "docs": "https://developers.google.com/youtube/reporting",
-          "note": "Reporting API jobs can take up to ~24h to generate the first daily reports after enabling/creating the job.",
-          "content_owner_id": owner_id.trim(),
-          "report_types": report_types,
+          "channel_id": channel_id,
+          "diff": diff,
         }),
     )
 }
 
-#[derive(serde::Serialize)]
-struct UploadItem {
-    id: String,
-    filename: String,
-    channel_id: String,
-    created_at: String,
-    status: String,
-}
-
-type CsvUploadRow = (i64, String, String, DateTime<Utc>);
-
-async fn handle_youtube_uploads_list(
+async fn handle_youtube_decision_history(
     method: &Method,
     headers: &HeaderMap,
     uri: &Uri,
@@ -3976,215 +5375,157 @@ async fn handle_youtube_uploads_list(
         );
     }
 
-    let rows = sqlx::query_as::<_, CsvUploadRow>(
+    let today = Utc::now().date_naive();
+    let start_dt = get_query_param(uri, "start_dt")
+        .and_then(|v| parse_dt(&v))
+        .unwrap_or(today - Duration::days(27));
+    let end_dt = get_query_param(uri, "end_dt")
+        .and_then(|v| parse_dt(&v))
+        .unwrap_or(today);
+
+    if start_dt > end_dt {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "start_dt must be <= end_dt"}),
+        );
+    }
+
+    let rows = sqlx::query_as::<_, (NaiveDate, String, f64)>(
         r#"
-      SELECT id, filename, status, created_at
-      FROM yt_csv_uploads
-      WHERE tenant_id = ?
-        AND channel_id = ?
-      ORDER BY created_at DESC
-      LIMIT 20;
+      SELECT as_of_dt, direction, CAST(confidence AS DOUBLE) AS confidence
+      FROM decision_daily
+      WHERE tenant_id = ? AND channel_id = ?
+        AND as_of_dt BETWEEN ? AND ?
+      ORDER BY as_of_dt ASC;
     "#,
     )
     .bind(tenant_id.trim())
     .bind(channel_id.trim())
+    .bind(start_dt)
+    .bind(end_dt)
     .fetch_all(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    let items: Vec<UploadItem> = rows
-        .into_iter()
-        .map(|(id, filename, status, created_at)| UploadItem {
-            id: format!("upload_{id}"),
-            filename,
-            channel_id: channel_id.clone(),
-            created_at: datetime_to_rfc3339_utc(created_at),
-            status,
-        })
-        .collect();
+    let items = annotate_direction_changes(rows);
+    let direction_changes = items.iter().filter(|item| item.direction_changed).count();
 
     json_response(
         StatusCode::OK,
-        serde_json::json!({"ok": true, "items": items, "channel_id": channel_id}),
+        serde_json::json!({
+          "ok": true,
+          "channel_id": channel_id,
+          "start_dt": start_dt.to_string(),
+          "end_dt": end_dt.to_string(),
+          "direction_changes": direction_changes,
+          "items": items,
+        }),
     )
 }
 
-fn normalize_csv_header_name(input: &str) -> String {
-    let mut out = String::with_capacity(input.len());
-    let mut last_was_sep = false;
-    for ch in input.trim().chars() {
-        if ch.is_ascii_alphanumeric() {
-            out.push(ch.to_ascii_lowercase());
-            last_was_sep = false;
-        } else if !last_was_sep {
-            out.push('_');
-            last_was_sep = true;
-        }
-    }
-    out.trim_matches('_').to_string()
-}
-
-fn parse_i64_field(raw: &str) -> Option<i64> {
-    let cleaned = raw.trim().replace(',', "");
-    cleaned.parse::<i64>().ok()
+/// Decision/outcome exports cover at most this many days (one decision per day).
+const DECISIONS_EXPORT_MAX_DAYS: i64 = 366;
+
+const DECISIONS_EXPORT_COLUMNS: &[&str] = &[
+    "decision_dt",
+    "direction",
+    "confidence",
+    "outcome_dt",
+    "revenue_change_pct_7d",
+    "catastrophic_flag",
+    "new_top_asset_flag",
+    "provisional",
+    "outcome_label",
+];
+
+/// A decision_daily row with its outcome, if one has been computed.
+#[derive(Debug, Clone, PartialEq)]
+struct DecisionOutcomeExportRow {
+    decision_dt: NaiveDate,
+    direction: String,
+    confidence: f64,
+    outcome_dt: Option<NaiveDate>,
+    revenue_change_pct_7d: Option<f64>,
+    catastrophic_flag: bool,
+    new_top_asset_flag: bool,
+    provisional: bool,
 }
 
-fn parse_f64_field(raw: &str) -> Option<f64> {
-    let cleaned = raw.trim().replace(',', "").replace('$', "");
-    cleaned.parse::<f64>().ok()
+impl DecisionOutcomeExportRow {
+    /// Empty until the outcome exists; a catastrophic drop outranks a new top asset.
+    fn outcome_label(&self) -> &'static str {
+        if self.outcome_dt.is_none() {
+            ""
+        } else if self.catastrophic_flag {
+            "catastrophic_drop"
+        } else if self.new_top_asset_flag {
+            "new_top_asset"
+        } else {
+            "no_flags"
+        }
+    }
 }
 
-fn parse_ctr_field(raw: &str) -> Option<f64> {
-    let s = raw.trim();
-    let is_percent = s.ends_with('%');
-    let cleaned = s.trim_end_matches('%').replace(',', "");
-    let v = cleaned.parse::<f64>().ok()?;
-    if is_percent {
-        Some(v / 100.0)
+/// Text cells starting with `=`, `+`, `-`, `@`, tab or CR are prefixed with `'` so spreadsheets
+/// don't evaluate them as formulas; plain numbers (including negatives) are left alone.
+fn csv_injection_safe_cell(value: &str) -> std::borrow::Cow<'_, str> {
+    let risky = matches!(
+        value.chars().next(),
+        Some('=' | '+' | '-' | '@' | '\t' | '\r')
+    );
+    if risky && value.trim().parse::<f64>().is_err() {
+        std::borrow::Cow::Owned(format!("'{value}"))
     } else {
-        Some(v)
+        std::borrow::Cow::Borrowed(value)
     }
 }
 
-#[derive(Debug, Clone)]
-struct CsvMetricRow {
-    dt: NaiveDate,
-    video_id: String,
-    estimated_revenue_usd: f64,
-    impressions: i64,
-    impressions_ctr: Option<f64>,
-    views: i64,
-}
-
-fn parse_csv_metrics(csv_text: &str) -> Result<Vec<CsvMetricRow>, String> {
-    use std::collections::HashMap;
-
-    if csv_text.trim().is_empty() {
-        return Err("csv_text is empty".to_string());
-    }
-
-    let mut rdr = csv::ReaderBuilder::new()
-        .has_headers(true)
-        .flexible(true)
-        .from_reader(csv_text.as_bytes());
-
-    let headers = rdr
-        .headers()
-        .map_err(|e| format!("invalid csv headers: {e}"))?
-        .clone();
-
-    let mut idx: HashMap<String, usize> = HashMap::new();
-    for (i, h) in headers.iter().enumerate() {
-        idx.insert(normalize_csv_header_name(h), i);
-    }
-
-    let find_idx = |candidates: &[&str]| -> Option<usize> {
-        for c in candidates {
-            if let Some(i) = idx.get(*c) {
-                return Some(*i);
-            }
-        }
-        None
-    };
-
-    let dt_idx =
-        find_idx(&["date", "day", "dt"]).ok_or_else(|| "missing date/day/dt column".to_string())?;
-    let video_idx = find_idx(&["video_id", "videoid", "video"]);
-    let views_idx = find_idx(&["views", "view"]);
-    let impressions_idx = find_idx(&["impressions", "impr", "impression"]);
-    let revenue_idx = find_idx(&[
-        "revenue_usd",
-        "estimated_revenue_usd",
-        "estimatedrevenue",
-        "estimated_revenue",
-        "revenue",
-    ]);
-    let rpm_idx = find_idx(&["rpm"]);
-    let ctr_idx = find_idx(&["ctr", "impressions_click_through_rate"]);
-
-    let mut out: Vec<CsvMetricRow> = Vec::new();
-
-    for (row_i, rec) in rdr.records().enumerate() {
-        let rec = rec.map_err(|e| format!("invalid csv row {}: {}", row_i + 1, e))?;
-
-        let dt_raw = rec.get(dt_idx).unwrap_or("").trim();
-        let dt = parse_dt(dt_raw)
-            .ok_or_else(|| format!("invalid date at row {}: {}", row_i + 1, dt_raw))?;
-
-        let video_id = video_idx
-            .and_then(|i| rec.get(i))
-            .map(|v| v.trim().to_string())
-            .filter(|v| !v.is_empty())
-            .unwrap_or_else(|| "csv_channel_total".to_string());
-
-        let impressions = impressions_idx
-            .and_then(|i| rec.get(i))
-            .and_then(parse_i64_field)
-            .unwrap_or(0)
-            .max(0);
-
-        let views_from_field = views_idx.and_then(|i| rec.get(i)).and_then(parse_i64_field);
-
-        let impressions_ctr = ctr_idx.and_then(|i| rec.get(i)).and_then(parse_ctr_field);
-
-        let views_from_ctr = match (ctr_idx, impressions) {
-            (Some(_i), impr) if impr > 0 => {
-                impressions_ctr.map(|ctr| ((impr as f64) * ctr).round() as i64)
-            }
-            _ => None,
-        };
-
-        let views = views_from_field.or(views_from_ctr).unwrap_or(0).max(0);
-
-        let revenue_from_field = revenue_idx
-            .and_then(|i| rec.get(i))
-            .and_then(parse_f64_field);
-
-        let revenue_from_rpm = match (rpm_idx, views) {
-            (Some(i), v) if v > 0 => rec
-                .get(i)
-                .and_then(parse_f64_field)
-                .map(|rpm| (rpm * (v as f64)) / 1000.0),
-            _ => None,
-        };
-
-        let revenue = revenue_from_field
-            .or(revenue_from_rpm)
-            .unwrap_or(0.0)
-            .max(0.0);
-
-        // Drop fully-empty rows (common in exports).
-        if impressions == 0 && views == 0 && revenue == 0.0 {
-            continue;
-        }
-
-        out.push(CsvMetricRow {
-            dt,
-            video_id,
-            estimated_revenue_usd: revenue,
-            impressions,
-            impressions_ctr,
-            views,
-        });
+fn render_decision_outcomes_csv(rows: &[DecisionOutcomeExportRow]) -> Result<Vec<u8>, csv::Error> {
+    let flag = |v: bool| if v { "1" } else { "0" }.to_string();
+    let mut wtr = csv::WriterBuilder::new().from_writer(Vec::new());
+    wtr.write_record(DECISIONS_EXPORT_COLUMNS)?;
+    for row in rows {
+        let has_outcome = row.outcome_dt.is_some();
+        let cells = [
+            row.decision_dt.to_string(),
+            row.direction.clone(),
+            row.confidence.to_string(),
+            row.outcome_dt.map(|d| d.to_string()).unwrap_or_default(),
+            row.revenue_change_pct_7d
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+            if has_outcome {
+                flag(row.catastrophic_flag)
+            } else {
+                String::new()
+            },
+            if has_outcome {
+                flag(row.new_top_asset_flag)
+            } else {
+                String::new()
+            },
+            if has_outcome {
+                flag(row.provisional)
+            } else {
+                String::new()
+            },
+            row.outcome_label().to_string(),
+        ];
+        wtr.write_record(
+            cells
+                .iter()
+                .map(|cell| csv_injection_safe_cell(cell).into_owned()),
+        )?;
     }
-
-    Ok(out)
-}
-
-#[derive(Deserialize)]
-struct UploadCsvRequest {
-    tenant_id: String,
-    channel_id: Option<String>,
-    filename: String,
-    csv_text: String,
+    wtr.into_inner().map_err(|e| e.into_error().into())
 }
 
-async fn handle_youtube_upload_csv(
+async fn handle_youtube_decisions_export(
     method: &Method,
     headers: &HeaderMap,
-    body: Bytes,
+    uri: &Uri,
 ) -> Result<Response<ResponseBody>, Error> {
-    if method != Method::POST {
+    if method != Method::GET {
         return json_response(
             StatusCode::METHOD_NOT_ALLOWED,
             serde_json::json!({"ok": false, "error": "method_not_allowed"}),
@@ -4208,41 +5549,21 @@ async fn handle_youtube_upload_csv(
         );
     }
 
-    let parsed: UploadCsvRequest = serde_json::from_slice(&body).map_err(|e| -> Error {
-        Box::new(std::io::Error::other(format!("invalid json body: {e}")))
-    })?;
-
-    if parsed.tenant_id.trim().is_empty() {
+    let tenant_id = get_query_param(uri, "tenant_id").unwrap_or_default();
+    if tenant_id.trim().is_empty() {
         return json_response(
             StatusCode::BAD_REQUEST,
             serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
         );
     }
-    if parsed.filename.trim().is_empty() {
-        return json_response(
-            StatusCode::BAD_REQUEST,
-            serde_json::json!({"ok": false, "error": "bad_request", "message": "filename is required"}),
-        );
-    }
-
-    // Guardrail: keep this endpoint safe for MVP use.
-    if parsed.csv_text.len() > 5_000_000 {
-        return json_response(
-            StatusCode::PAYLOAD_TOO_LARGE,
-            serde_json::json!({"ok": false, "error": "payload_too_large", "message": "csv_text too large"}),
-        );
-    }
 
     let pool = get_pool().await?;
-    let tenant_id = parsed.tenant_id.trim();
-    let channel_id = match parsed
-        .channel_id
-        .as_deref()
-        .map(str::trim)
+    let channel_id = match get_query_param(uri, "channel_id")
+        .map(|v| v.trim().to_string())
         .filter(|v| !v.is_empty())
     {
-        Some(v) => v.to_string(),
-        None => fetch_youtube_channel_id(pool, tenant_id)
+        Some(v) => v,
+        None => fetch_youtube_channel_id(pool, tenant_id.trim())
             .await?
             .unwrap_or_default(),
     };
@@ -4254,193 +5575,456 @@ async fn handle_youtube_upload_csv(
         );
     }
 
-    let insert = sqlx::query(
+    let today = Utc::now().date_naive();
+    let start_dt = get_query_param(uri, "start_dt")
+        .and_then(|v| parse_dt(&v))
+        .unwrap_or(today - Duration::days(89));
+    let end_dt = get_query_param(uri, "end_dt")
+        .and_then(|v| parse_dt(&v))
+        .unwrap_or(today);
+
+    if start_dt > end_dt {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "start_dt must be <= end_dt"}),
+        );
+    }
+    if (end_dt - start_dt).num_days() + 1 > DECISIONS_EXPORT_MAX_DAYS {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": format!("range must span at most {DECISIONS_EXPORT_MAX_DAYS} days")}),
+        );
+    }
+
+    let rows = sqlx::query_as::<
+        _,
+        (
+            NaiveDate,
+            String,
+            f64,
+            Option<NaiveDate>,
+            Option<f64>,
+            Option<i8>,
+            Option<i8>,
+            Option<i8>,
+        ),
+    >(
         r#"
-      INSERT INTO yt_csv_uploads (tenant_id, channel_id, filename, status)
-      VALUES (?, ?, ?, 'received');
+      SELECT d.as_of_dt, d.direction, CAST(d.confidence AS DOUBLE) AS confidence,
+             o.outcome_dt, o.revenue_change_pct_7d, o.catastrophic_flag, o.new_top_asset_flag,
+             o.provisional
+      FROM decision_daily d
+      LEFT JOIN decision_outcome o
+        ON o.tenant_id = d.tenant_id AND o.channel_id = d.channel_id AND o.decision_dt = d.as_of_dt
+      WHERE d.tenant_id = ? AND d.channel_id = ?
+        AND d.as_of_dt BETWEEN ? AND ?
+      ORDER BY d.as_of_dt ASC, o.outcome_dt ASC;
     "#,
     )
-    .bind(tenant_id)
+    .bind(tenant_id.trim())
     .bind(channel_id.trim())
-    .bind(parsed.filename.trim())
-    .execute(pool)
+    .bind(start_dt)
+    .bind(end_dt)
+    .fetch_all(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    let upload_id = insert.last_insert_id() as i64;
-
-    let parsed_rows = match parse_csv_metrics(&parsed.csv_text) {
-        Ok(rows) => rows,
-        Err(err) => {
-            sqlx::query(
-                r#"
-          UPDATE yt_csv_uploads
-          SET status = 'error',
-              error = ?,
-              updated_at = CURRENT_TIMESTAMP(3)
-          WHERE id = ? AND tenant_id = ? AND channel_id = ?;
-        "#,
-            )
-            .bind(&err)
-            .bind(upload_id)
-            .bind(tenant_id)
-            .bind(channel_id.trim())
-            .execute(pool)
-            .await
-            .map_err(|e| -> Error { Box::new(e) })?;
+    let rows: Vec<DecisionOutcomeExportRow> = rows
+        .into_iter()
+        .map(
+            |(
+                decision_dt,
+                direction,
+                confidence,
+                outcome_dt,
+                revenue_change_pct_7d,
+                catastrophic_flag,
+                new_top_asset_flag,
+                provisional,
+            )| DecisionOutcomeExportRow {
+                decision_dt,
+                direction,
+                confidence,
+                outcome_dt,
+                revenue_change_pct_7d,
+                catastrophic_flag: catastrophic_flag.unwrap_or(0) != 0,
+                new_top_asset_flag: new_top_asset_flag.unwrap_or(0) != 0,
+                provisional: provisional.unwrap_or(0) != 0,
+            },
+        )
+        .collect();
 
-            return json_response(
-                StatusCode::BAD_REQUEST,
-                serde_json::json!({"ok": false, "error": "bad_csv", "message": err}),
-            );
-        }
-    };
+    let csv_bytes = render_decision_outcomes_csv(&rows).map_err(|e| -> Error {
+        Box::new(std::io::Error::other(format!("csv render error: {e}")))
+    })?;
 
-    let mut min_dt: Option<NaiveDate> = None;
-    let mut max_dt: Option<NaiveDate> = None;
-    let mut channel_total_rows: i64 = 0;
-    let mut per_video_rows: i64 = 0;
-    let mut rows_with_views: i64 = 0;
-    let mut rows_with_impressions: i64 = 0;
-    let mut rows_with_revenue: i64 = 0;
-    let mut ctr_present_rows: i64 = 0;
-    let mut ctr_nonzero_rows: i64 = 0;
+    let filename = format!(
+        "decisions_{}_{start_dt}_{end_dt}.csv",
+        globa_flux_rust::db::sanitize_sql_identifier(&channel_id)
+    );
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "text/csv; charset=utf-8")
+        .header(
+            "content-disposition",
+            format!("attachment; filename=\"{filename}\""),
+        )
+        .header("x-row-count", rows.len().to_string())
+        .body(ResponseBody::from(csv_bytes))?)
+}
 
-    for row in parsed_rows.iter() {
-        min_dt = Some(match min_dt {
-            Some(cur) => cur.min(row.dt),
-            None => row.dt,
-        });
-        max_dt = Some(match max_dt {
-            Some(cur) => cur.max(row.dt),
-            None => row.dt,
-        });
+const DEFAULT_BUNDLE_SECTION_TIMEOUT_MS: u64 = 8_000;
 
-        if row.video_id == "csv_channel_total" {
-            channel_total_rows += 1;
-        } else {
-            per_video_rows += 1;
-        }
+fn bundle_section_timeout() -> std::time::Duration {
+    let ms = std::env::var("BUNDLE_SECTION_TIMEOUT_MS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_BUNDLE_SECTION_TIMEOUT_MS)
+        .clamp(100, 60_000);
+    std::time::Duration::from_millis(ms)
+}
 
-        if row.views > 0 {
-            rows_with_views += 1;
-        }
-        if row.impressions > 0 {
-            rows_with_impressions += 1;
-        }
-        if row.estimated_revenue_usd > 0.0 {
-            rows_with_revenue += 1;
-        }
+// Bundle sections are independent reads; a slow one is cut off so it only nulls its own section.
+async fn run_bundle_section<T>(
+    timeout: std::time::Duration,
+    section: impl std::future::Future<Output = Result<T, String>>,
+) -> Result<T, String> {
+    match tokio::time::timeout(timeout, section).await {
+        Ok(result) => result,
+        Err(_) => Err(format!("timed out after {}ms", timeout.as_millis())),
+    }
+}
 
-        if let Some(ctr) = row.impressions_ctr {
-            ctr_present_rows += 1;
-            if ctr > 0.0 {
-                ctr_nonzero_rows += 1;
-            }
+fn take_bundle_section<T>(
+    errors: &mut serde_json::Map<String, serde_json::Value>,
+    name: &str,
+    result: Result<T, String>,
+) -> Option<T> {
+    match result {
+        Ok(v) => Some(v),
+        Err(err) => {
+            errors.insert(
+                name.to_string(),
+                serde_json::Value::String(truncate_string(&err, 2000)),
+            );
+            None
         }
     }
+}
 
-    for row in parsed_rows.iter() {
-        upsert_video_daily_metric(
-            pool,
-            tenant_id,
-            channel_id.trim(),
-            row.dt,
-            &row.video_id,
-            row.estimated_revenue_usd,
-            row.impressions,
-            row.impressions_ctr,
-            row.views,
-        )
-        .await?;
+async fn bundle_data_health(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    start_dt: NaiveDate,
+    end_dt: NaiveDate,
+    baseline_mode: DataHealthBaseline,
+) -> Result<serde_json::Value, String> {
+    let days = ((end_dt - start_dt).num_days() + 1).max(1);
+    let (baseline_start, baseline_end) = baseline_mode.window(start_dt, end_dt);
+
+    let window = DataHealthWindow {
+        start_dt: start_dt.to_string(),
+        end_dt: end_dt.to_string(),
+        days,
+    };
+    let baseline_window = DataHealthWindow {
+        start_dt: baseline_start.to_string(),
+        end_dt: baseline_end.to_string(),
+        days,
+    };
+
+    let current = aggregate_data_health_period(pool, tenant_id, channel_id, start_dt, end_dt).await;
+    let baseline =
+        aggregate_data_health_period(pool, tenant_id, channel_id, baseline_start, baseline_end)
+            .await;
+
+    match (current, baseline) {
+        (Ok(current), Ok(baseline)) => {
+            let expected_days = days;
+            let coverage = if expected_days > 0 {
+                (current.days_with_data as f64) / (expected_days as f64)
+            } else {
+                0.0
+            };
+
+            let stale = current
+                .last_dt
+                .as_deref()
+                .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+                .map(|dt| dt < end_dt)
+                .unwrap_or(true);
+
+            let mut notes: Vec<String> = Vec::new();
+            if current.partial {
+                notes.push(
+                    "Using video-level sums (may be partial if YouTube Analytics limits rows)."
+                        .to_string(),
+                );
+            }
+            if stale {
+                notes.push(
+                    "Latest metric date is behind the requested end_dt (sync may be stale)."
+                        .to_string(),
+                );
+            }
+            if coverage < 0.8 {
+                notes.push(
+                    "Low coverage: fewer days with data than expected in the window.".to_string(),
+                );
+            }
+            notes.extend(baseline_mode.note(baseline_start, baseline_end));
+
+            Ok(serde_json::json!({
+              "ok": true,
+              "channel_id": channel_id,
+              "window": window,
+              "baseline_window": baseline_window,
+              "baseline_mode": baseline_mode.as_str(),
+              "comparison": compare_data_health_periods(&current, &baseline),
+              "current": current,
+              "baseline": baseline,
+              "notes": notes,
+            }))
+        }
+        (Err(err), _) | (_, Err(err)) => Err(err.to_string()),
     }
+}
 
-    sqlx::query(
+type ChannelTotalRow = (NaiveDate, f64, i64, i64, f64, i64);
+
+/// Daily channel totals (revenue, impressions, views, ctr_num, ctr_denom), preferring stored
+/// channel-total rows and falling back to summing video rows when none exist for the window.
+async fn fetch_channel_total_rows(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    start_dt: NaiveDate,
+    end_dt: NaiveDate,
+) -> Result<Vec<ChannelTotalRow>, sqlx::Error> {
+    let totals = sqlx::query_as::<_, ChannelTotalRow>(&format!(
         r#"
-      UPDATE yt_csv_uploads
-      SET status = 'parsed',
-          rows_parsed = ?,
-          error = NULL,
-          updated_at = CURRENT_TIMESTAMP(3)
-      WHERE id = ? AND tenant_id = ? AND channel_id = ?;
+      SELECT dt,
+             CAST(COALESCE(
+               {sums_0},
+               0
+             ) AS DOUBLE) AS revenue_usd,
+             CAST(COALESCE(
+               {sums_1},
+               0
+             ) AS SIGNED) AS impressions,
+             CAST(COALESCE(
+               {sums_2},
+               0
+             ) AS SIGNED) AS views,
+             CAST(COALESCE(
+               {sums_3},
+               0
+             ) AS DOUBLE) AS ctr_num,
+             CAST(COALESCE(
+               {sums_4},
+               0
+             ) AS SIGNED) AS ctr_denom
+      FROM video_daily_metrics
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND dt BETWEEN ? AND ?
+        AND {channel_totals}
+      GROUP BY dt
+      ORDER BY dt ASC;
+    "#,
+        sums_0 = channel_total_sums("estimated_revenue_usd"),
+        sums_1 = channel_total_sums("impressions"),
+        sums_2 = channel_total_sums("views"),
+        sums_3 = channel_total_sums("impressions_ctr * impressions"),
+        sums_4 = channel_total_sums_when("impressions_ctr IS NOT NULL", "impressions"),
+        channel_totals = channel_total_filter()
+    ))
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(start_dt)
+    .bind(end_dt)
+    .fetch_all(pool)
+    .await?;
+    if !totals.is_empty() {
+        return Ok(totals);
+    }
+
+    sqlx::query_as::<_, ChannelTotalRow>(
+        &format!(
+            r#"
+      SELECT dt,
+             CAST(SUM(estimated_revenue_usd) AS DOUBLE) AS revenue_usd,
+             CAST(SUM(impressions) AS SIGNED) AS impressions,
+             CAST(SUM(views) AS SIGNED) AS views,
+             CAST(COALESCE(SUM(impressions_ctr * impressions), 0) AS DOUBLE) AS ctr_num,
+             CAST(COALESCE(SUM(CASE WHEN impressions_ctr IS NOT NULL THEN impressions ELSE 0 END), 0) AS SIGNED) AS ctr_denom
+      FROM video_daily_metrics
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND dt BETWEEN ? AND ?
+        AND {video_rows}
+      GROUP BY dt
+      ORDER BY dt ASC;
     "#,
+            video_rows = video_rows_filter(),
+        ),
     )
-    .bind(parsed_rows.len() as i64)
-    .bind(upload_id)
     .bind(tenant_id)
-    .bind(channel_id.trim())
-    .execute(pool)
+    .bind(channel_id)
+    .bind(start_dt)
+    .bind(end_dt)
+    .fetch_all(pool)
     .await
-    .map_err(|e| -> Error { Box::new(e) })?;
+}
 
-    // CSV is often used when revenue/RPM metrics are blocked; evaluate guardrails immediately.
-    let eval_error = match evaluate_youtube_alerts(pool, tenant_id, channel_id.trim()).await {
-        Ok(()) => None,
-        Err(err) => Some(truncate_string(&err.to_string(), 2000)),
+#[derive(Debug, Default, PartialEq, serde::Serialize)]
+struct YoutubeKpis {
+    revenue_7d_usd: f64,
+    revenue_28d_usd: f64,
+    views_7d: i64,
+    views_28d: i64,
+}
+
+/// Headline sums for the 7 and 28 days ending at `end_dt` (inclusive).
+fn youtube_kpis_from_rows(rows: &[ChannelTotalRow], end_dt: NaiveDate) -> YoutubeKpis {
+    let start_7d = end_dt - Duration::days(6);
+    let start_28d = end_dt - Duration::days(27);
+    let mut kpis = YoutubeKpis::default();
+    for (dt, revenue_usd, _impressions, views, _ctr_num, _ctr_denom) in rows {
+        if *dt > end_dt || *dt < start_28d {
+            continue;
+        }
+        kpis.revenue_28d_usd += revenue_usd;
+        kpis.views_28d += views;
+        if *dt >= start_7d {
+            kpis.revenue_7d_usd += revenue_usd;
+            kpis.views_7d += views;
+        }
+    }
+    kpis.revenue_7d_usd = round2(kpis.revenue_7d_usd);
+    kpis.revenue_28d_usd = round2(kpis.revenue_28d_usd);
+    kpis
+}
+
+async fn handle_youtube_kpis(
+    method: &Method,
+    headers: &HeaderMap,
+    uri: &Uri,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::GET {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let tenant_id = get_query_param(uri, "tenant_id").unwrap_or_default();
+    if tenant_id.trim().is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+        );
+    }
+
+    let pool = get_pool().await?;
+    let channel_id = match get_query_param(uri, "channel_id")
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+    {
+        Some(v) => v,
+        None => fetch_youtube_channel_id(pool, tenant_id.trim())
+            .await?
+            .unwrap_or_default(),
     };
 
+    if channel_id.trim().is_empty() {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "No active YouTube channel for this tenant"}),
+        );
+    }
+
+    let end_dt = get_query_param(uri, "end_dt")
+        .and_then(|v| parse_dt(&v))
+        .unwrap_or(Utc::now().date_naive() - Duration::days(1));
+    let start_dt = end_dt - Duration::days(27);
+
+    let rows =
+        fetch_channel_total_rows(pool, tenant_id.trim(), channel_id.trim(), start_dt, end_dt)
+            .await
+            .map_err(|e| -> Error { Box::new(e) })?;
+    let kpis = youtube_kpis_from_rows(&rows, end_dt);
+    let policy_params_json =
+        fetch_policy_params_json(pool, tenant_id.trim(), channel_id.trim(), "active").await?;
+    let min_confidence = min_surfaced_confidence(policy_params_json.as_deref());
+
+    let latest_decision = sqlx::query_as::<_, (NaiveDate, String, f64)>(
+        r#"
+      SELECT as_of_dt, direction, CAST(confidence AS DOUBLE) AS confidence
+      FROM decision_daily
+      WHERE tenant_id = ? AND channel_id = ?
+      ORDER BY as_of_dt DESC
+      LIMIT 1;
+    "#,
+    )
+    .bind(tenant_id.trim())
+    .bind(channel_id.trim())
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?
+    .map(|(as_of_dt, direction, confidence)| {
+        let surfaced = surfaced_direction(&direction, confidence, min_confidence);
+        serde_json::json!({
+          "as_of_dt": as_of_dt.to_string(),
+          "direction": surfaced,
+          "status": if surfaced.is_some() { "actionable" } else { INSUFFICIENT_CONFIDENCE },
+          "confidence": confidence,
+        })
+    });
+
     json_response(
         StatusCode::OK,
         serde_json::json!({
           "ok": true,
-          "upload_id": format!("upload_{upload_id}"),
-          "rows_parsed": parsed_rows.len(),
           "channel_id": channel_id,
-          "eval_error": eval_error,
-          "csv_stats": {
-            "total_rows": parsed_rows.len(),
-            "channel_total_rows": channel_total_rows,
-            "per_video_rows": per_video_rows,
-            "date_min": min_dt.map(|d| d.to_string()),
-            "date_max": max_dt.map(|d| d.to_string()),
-            "has_views": rows_with_views > 0,
-            "has_impressions": rows_with_impressions > 0,
-            "has_revenue": rows_with_revenue > 0,
-            "has_ctr": ctr_present_rows > 0,
-            "ctr_present_rows": ctr_present_rows,
-            "ctr_nonzero_rows": ctr_nonzero_rows
-          }
+          "end_dt": end_dt.to_string(),
+          "kpis": kpis,
+          "latest_decision": latest_decision,
         }),
     )
 }
 
-#[derive(serde::Serialize)]
-struct AlertItem {
-    id: String,
-    kind: String,
-    severity: String,
-    message: String,
-    details: Option<serde_json::Value>,
-    detected_at: String,
-    resolved_at: Option<String>,
-}
-
-#[derive(Deserialize)]
-struct ResolveAlertRequest {
-    tenant_id: String,
-    id: String,
-    #[serde(default)]
-    note: Option<String>,
-    #[serde(default)]
-    action: Option<String>,
-}
-
-fn parse_prefixed_id(raw: &str, prefix: &str) -> Option<i64> {
-    let s = raw.trim();
-    let s = s.strip_prefix(prefix).unwrap_or(s);
-    s.parse::<i64>().ok()
-}
-
-fn datetime_to_rfc3339_utc(dt: DateTime<Utc>) -> String {
-    dt.to_rfc3339()
-}
-
-async fn handle_youtube_alerts(
+/// Tenant-wide cost overview: YouTube Data API quota units next to LLM spend, per UTC day.
+async fn handle_usage_summary(
     method: &Method,
     headers: &HeaderMap,
     uri: &Uri,
-    body: Option<Bytes>,
 ) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::GET {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
     let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
     let provided =
         bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
@@ -4458,19 +6042,3148 @@ async fn handle_youtube_alerts(
         );
     }
 
-    if method == Method::GET {
-        let tenant_id = get_query_param(uri, "tenant_id").unwrap_or_default();
-        if tenant_id.trim().is_empty() {
-            return json_response(
-                StatusCode::BAD_REQUEST,
-                serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
-            );
-        }
+    let tenant_id = get_query_param(uri, "tenant_id").unwrap_or_default();
+    if tenant_id.trim().is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+        );
+    }
 
-        let pool = get_pool().await?;
-        let channel_id = match get_query_param(uri, "channel_id")
-            .map(|v| v.trim().to_string())
-            .filter(|v| !v.is_empty())
+    let today = Utc::now().date_naive();
+    let end_dt = get_query_param(uri, "end_dt")
+        .and_then(|v| parse_dt(&v))
+        .unwrap_or(today);
+    let start_dt = get_query_param(uri, "start_dt")
+        .and_then(|v| parse_dt(&v))
+        .unwrap_or(end_dt - Duration::days(29));
+
+    if start_dt > end_dt || (end_dt - start_dt).num_days() > 366 {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "start_dt must be <= end_dt and the range at most 366 days"}),
+        );
+    }
+
+    let pool = get_pool().await?;
+    let api_rows = fetch_youtube_api_units_daily(pool, tenant_id.trim(), start_dt, end_dt).await?;
+    let llm_rows = fetch_llm_cost_daily(pool, tenant_id.trim(), start_dt, end_dt).await?;
+    let (days, totals) = merge_daily_usage(&api_rows, &llm_rows);
+
+    json_response(
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "tenant_id": tenant_id.trim(),
+          "start_dt": start_dt.to_string(),
+          "end_dt": end_dt.to_string(),
+          "days": days,
+          "totals": totals,
+        }),
+    )
+}
+
+async fn handle_youtube_dashboard_bundle(
+    method: &Method,
+    headers: &HeaderMap,
+    uri: &Uri,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::GET {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let tenant_id = get_query_param(uri, "tenant_id").unwrap_or_default();
+    if tenant_id.trim().is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+        );
+    }
+
+    let pool = get_pool().await?;
+    let channel_id = match get_query_param(uri, "channel_id")
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+    {
+        Some(v) => v,
+        None => fetch_youtube_channel_id(pool, tenant_id.trim())
+            .await?
+            .unwrap_or_default(),
+    };
+
+    if channel_id.trim().is_empty() {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "No active YouTube channel for this tenant"}),
+        );
+    }
+
+    let today = Utc::now().date_naive();
+    let default_end = today - Duration::days(1);
+    let start_dt = get_query_param(uri, "start_dt")
+        .and_then(|v| parse_dt(&v))
+        .unwrap_or(default_end - Duration::days(27));
+    let end_dt = get_query_param(uri, "end_dt")
+        .and_then(|v| parse_dt(&v))
+        .unwrap_or(default_end);
+
+    if start_dt > end_dt {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "start_dt must be <= end_dt"}),
+        );
+    }
+
+    let health = bundle_data_health(
+        pool,
+        tenant_id.trim(),
+        channel_id.trim(),
+        start_dt,
+        end_dt,
+        DataHealthBaseline::from_uri(uri),
+    );
+
+    let ctr_format = CtrFormat::from_uri(uri);
+    let rpm_min_views = rpm_min_views();
+    let metrics = async {
+        let rows =
+            fetch_channel_total_rows(pool, tenant_id.trim(), channel_id.trim(), start_dt, end_dt)
+                .await
+                .map_err(|e| e.to_string())?;
+        Ok::<Vec<MetricDailyItem>, String>(
+            rows.into_iter()
+                .map(
+                    |(dt, revenue_usd, impressions, views, ctr_num, ctr_denom)| {
+                        let ctr = if ctr_denom > 0 {
+                            Some(ctr_num / (ctr_denom as f64))
+                        } else {
+                            None
+                        };
+                        let rpm = rpm_with_floor(revenue_usd, views, rpm_min_views);
+                        MetricDailyItem {
+                            date: dt.to_string(),
+                            video_id: "channel_total".to_string(),
+                            impressions,
+                            views,
+                            revenue_usd: round2(revenue_usd),
+                            ctr: ctr.map(|v| ctr_format.format(v)),
+                            rpm: rpm.map(round2),
+                            source: "tidb".to_string(),
+                        }
+                    },
+                )
+                .collect(),
+        )
+    };
+
+    let alerts = async {
+        Ok::<Vec<AlertItem>, String>(
+            match sqlx::query_as::<
+                _,
+                (
+                    i64,
+                    String,
+                    String,
+                    String,
+                    DateTime<Utc>,
+                    Option<DateTime<Utc>>,
+                    Option<String>,
+                ),
+            >(
+                r#"
+	          SELECT id, kind, severity, message,
+	                 CAST(detected_at AS DATETIME) AS detected_at,
+	                 CAST(resolved_at AS DATETIME) AS resolved_at,
+	                 details_json
+	          FROM yt_alerts
+	          WHERE tenant_id = ? AND channel_id = ?
+	          ORDER BY (resolved_at IS NULL) DESC, detected_at DESC
+          LIMIT 50;
+        "#,
+            )
+            .bind(tenant_id.trim())
+            .bind(channel_id.trim())
+            .fetch_all(pool)
+            .await
+            {
+                Ok(rows) => rows
+                    .into_iter()
+                    .map(
+                        |(id, kind, severity, message, detected_at, resolved_at, details_json)| {
+                            AlertItem {
+                                id: format!("alert_{id}"),
+                                kind,
+                                severity,
+                                message,
+                                details: details_json.as_deref().and_then(|raw| {
+                                    serde_json::from_str::<serde_json::Value>(raw).ok()
+                                }),
+                                detected_at: datetime_to_rfc3339_utc(detected_at),
+                                resolved_at: resolved_at.map(datetime_to_rfc3339_utc),
+                            }
+                        },
+                    )
+                    .collect(),
+                Err(err) => return Err(err.to_string()),
+            },
+        )
+    };
+
+    let outcome_latest = async {
+        Ok::<Option<OutcomeLatestItem>, String>(
+            match fetch_outcome_latest(pool, tenant_id.trim(), channel_id.trim()).await {
+                Ok(v) => v,
+                Err(err) => return Err(err.to_string()),
+            },
+        )
+    };
+
+    let timeout = bundle_section_timeout();
+    let (health, metrics, alerts, outcome_latest) = tokio::join!(
+        run_bundle_section(timeout, health),
+        run_bundle_section(timeout, metrics),
+        run_bundle_section(timeout, alerts),
+        run_bundle_section(timeout, outcome_latest),
+    );
+
+    let mut errors = serde_json::Map::new();
+    let health = take_bundle_section(&mut errors, "health", health);
+    let metrics = take_bundle_section(&mut errors, "metrics", metrics).unwrap_or_default();
+    let alerts = take_bundle_section(&mut errors, "alerts", alerts).unwrap_or_default();
+    let outcome_latest = take_bundle_section(&mut errors, "outcome", outcome_latest).flatten();
+
+    let metric_dts: Vec<NaiveDate> = metrics.iter().filter_map(|m| parse_dt(&m.date)).collect();
+    let completeness = window_completeness(start_dt, end_dt, &metric_dts);
+
+    bundle_response(
+        headers,
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "channel_id": channel_id,
+          "start_dt": start_dt.to_string(),
+          "end_dt": end_dt.to_string(),
+          "health": health,
+          "metrics": metrics,
+          "ctr_unit": ctr_format.as_str(),
+          "completeness": completeness,
+          "alerts": alerts,
+          "outcome_latest": outcome_latest,
+          "errors": errors,
+        }),
+    )
+}
+
+async fn handle_youtube_sync_bundle(
+    method: &Method,
+    headers: &HeaderMap,
+    uri: &Uri,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::GET {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let tenant_id = get_query_param(uri, "tenant_id").unwrap_or_default();
+    if tenant_id.trim().is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+        );
+    }
+
+    let pool = get_pool().await?;
+    let channel_id = match get_query_param(uri, "channel_id")
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+    {
+        Some(v) => v,
+        None => fetch_youtube_channel_id(pool, tenant_id.trim())
+            .await?
+            .unwrap_or_default(),
+    };
+
+    if channel_id.trim().is_empty() {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "No active YouTube channel for this tenant"}),
+        );
+    }
+
+    let sync_status = async {
+        Ok::<Option<serde_json::Value>, String>(
+            match sqlx::query_as::<
+                _,
+                (
+                    i64,
+                    String,
+                    Option<NaiveDate>,
+                    String,
+                    i64,
+                    i64,
+                    DateTime<Utc>,
+                    DateTime<Utc>,
+                    Option<String>,
+                ),
+            >(
+                r#"
+      SELECT id, job_type, run_for_dt, status, attempt, max_attempt,
+             run_after,
+             updated_at,
+             last_error
+      FROM job_tasks
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND job_type IN ('daily_channel','weekly_channel','youtube_reporting_owner')
+      ORDER BY updated_at DESC
+      LIMIT 30;
+    "#,
+            )
+            .bind(tenant_id.trim())
+            .bind(channel_id.trim())
+            .fetch_all(pool)
+            .await
+            {
+                Ok(rows) => {
+                    let mut counts = serde_json::Map::new();
+                    for status in rows.iter().map(|(_, _, _, status, _, _, _, _, _)| status) {
+                        let v = counts
+                            .entry(status.clone())
+                            .or_insert(serde_json::Value::Number(0.into()));
+                        if let serde_json::Value::Number(n) = v {
+                            let next = n.as_i64().unwrap_or(0) + 1;
+                            *v = serde_json::Value::Number(next.into());
+                        }
+                    }
+
+                    let items: Vec<SyncStatusTaskItem> = rows
+                        .into_iter()
+                        .map(
+                            |(
+                                id,
+                                job_type,
+                                run_for_dt,
+                                status,
+                                attempt,
+                                max_attempt,
+                                run_after,
+                                updated_at,
+                                last_error,
+                            )| SyncStatusTaskItem {
+                                id,
+                                job_type,
+                                run_for_dt: run_for_dt.map(|d| d.to_string()),
+                                status,
+                                attempt,
+                                max_attempt,
+                                run_after: datetime_to_rfc3339_utc(run_after),
+                                updated_at: datetime_to_rfc3339_utc(updated_at),
+                                last_error: last_error.map(|e| truncate_string(&e, 800)),
+                            },
+                        )
+                        .collect();
+
+                    Some(serde_json::json!({"counts": counts, "items": items}))
+                }
+                Err(err) => return Err(err.to_string()),
+            },
+        )
+    };
+
+    let today = Utc::now().date_naive();
+    let default_end = today - Duration::days(1);
+    let start_dt = get_query_param(uri, "start_dt")
+        .and_then(|v| NaiveDate::parse_from_str(v.trim(), "%Y-%m-%d").ok())
+        .unwrap_or(default_end - Duration::days(27));
+    let end_dt = get_query_param(uri, "end_dt")
+        .and_then(|v| NaiveDate::parse_from_str(v.trim(), "%Y-%m-%d").ok())
+        .unwrap_or(default_end);
+
+    let health = bundle_data_health(
+        pool,
+        tenant_id.trim(),
+        channel_id.trim(),
+        start_dt,
+        end_dt,
+        DataHealthBaseline::from_uri(uri),
+    );
+
+    let uploads = async {
+        Ok::<Vec<UploadItem>, String>(
+            match sqlx::query_as::<_, CsvUploadRow>(
+                r#"
+      SELECT id, filename, status, created_at
+      FROM yt_csv_uploads
+      WHERE tenant_id = ?
+        AND channel_id = ?
+      ORDER BY created_at DESC
+      LIMIT 20;
+    "#,
+            )
+            .bind(tenant_id.trim())
+            .bind(channel_id.trim())
+            .fetch_all(pool)
+            .await
+            {
+                Ok(rows) => rows
+                    .into_iter()
+                    .map(|(id, filename, status, created_at)| UploadItem {
+                        id: format!("upload_{id}"),
+                        filename,
+                        channel_id: channel_id.clone(),
+                        created_at: datetime_to_rfc3339_utc(created_at),
+                        status,
+                    })
+                    .collect(),
+                Err(err) => return Err(err.to_string()),
+            },
+        )
+    };
+
+    let reporting = async {
+        Ok::<Option<serde_json::Value>, String>(
+            match fetch_youtube_content_owner_id(pool, tenant_id.trim()).await {
+                Ok(Some(content_owner_id)) if !content_owner_id.trim().is_empty() => {
+                    let owner_id = content_owner_id.trim();
+
+                    let jobs_rows =
+                        sqlx::query_as::<_, (String, String, DateTime<Utc>, DateTime<Utc>)>(
+                            r#"
+          SELECT report_type_id, job_id, created_at, updated_at
+          FROM yt_reporting_jobs
+          WHERE tenant_id = ? AND content_owner_id = ?
+          ORDER BY updated_at DESC
+          LIMIT 50;
+        "#,
+                        )
+                        .bind(tenant_id.trim())
+                        .bind(owner_id)
+                        .fetch_all(pool)
+                        .await
+                        .unwrap_or_default();
+
+                    let mut jobs_by_type: std::collections::HashMap<String, String> =
+                        std::collections::HashMap::new();
+                    for (report_type_id, job_id, _created_at, _updated_at) in jobs_rows.into_iter()
+                    {
+                        jobs_by_type.entry(report_type_id).or_insert(job_id);
+                    }
+
+                    let stats_rows = sqlx::query_as::<
+                _,
+                (
+                    String,
+                    i64,
+                    i64,
+                    i64,
+                    Option<DateTime<Utc>>,
+                    Option<DateTime<Utc>>,
+                ),
+            >(
+                r#"
+          SELECT report_type_id,
+                 CAST(COUNT(*) AS SIGNED) AS total_reports,
+                 CAST(SUM(CASE WHEN downloaded_at IS NOT NULL THEN 1 ELSE 0 END) AS SIGNED) AS reports_downloaded,
+                 CAST(SUM(CASE WHEN parse_status='parsed' THEN 1 ELSE 0 END) AS SIGNED) AS reports_parsed,
+                 MAX(create_time) AS last_create_time,
+                 MAX(parsed_at) AS last_parsed_at
+          FROM yt_reporting_report_files
+          WHERE tenant_id = ? AND content_owner_id = ?
+          GROUP BY report_type_id
+          ORDER BY last_create_time DESC;
+        "#,
+            )
+            .bind(tenant_id.trim())
+            .bind(owner_id)
+            .fetch_all(pool)
+            .await
+            .unwrap_or_default();
+
+                    let error_rows = sqlx::query_as::<_, (String, String, DateTime<Utc>)>(
+                        r#"
+            SELECT report_type_id, parse_error, updated_at
+            FROM yt_reporting_report_files
+            WHERE tenant_id = ?
+              AND content_owner_id = ?
+              AND parse_status = 'error'
+              AND parse_error IS NOT NULL
+            ORDER BY updated_at DESC
+            LIMIT 50;
+          "#,
+                    )
+                    .bind(tenant_id.trim())
+                    .bind(owner_id)
+                    .fetch_all(pool)
+                    .await
+                    .unwrap_or_default();
+
+                    let mut last_error_by_type: std::collections::HashMap<
+                        String,
+                        (String, String),
+                    > = std::collections::HashMap::new();
+                    for (report_type_id, parse_error, updated_at) in error_rows.into_iter() {
+                        if last_error_by_type.contains_key(&report_type_id) {
+                            continue;
+                        }
+                        last_error_by_type.insert(
+                            report_type_id,
+                            (
+                                truncate_string(&parse_error, 800),
+                                datetime_to_rfc3339_utc(updated_at),
+                            ),
+                        );
+                    }
+
+                    let report_types: Vec<serde_json::Value> = stats_rows
+                        .into_iter()
+                        .map(
+                            |(
+                                report_type_id,
+                                total,
+                                downloaded,
+                                parsed,
+                                last_create,
+                                last_parsed,
+                            )| {
+                                let job_id = jobs_by_type.get(&report_type_id).cloned();
+                                let last_error =
+                                    last_error_by_type.get(&report_type_id).map(|v| v.0.clone());
+                                let last_error_at =
+                                    last_error_by_type.get(&report_type_id).map(|v| v.1.clone());
+                                serde_json::json!({
+                                  "report_type_id": report_type_id,
+                                  "job_id": job_id,
+                                  "reports_total": total,
+                                  "reports_downloaded": downloaded,
+                                  "reports_parsed": parsed,
+                                  "last_create_time": last_create.map(datetime_to_rfc3339_utc),
+                                  "last_parsed_at": last_parsed.map(datetime_to_rfc3339_utc),
+                                  "last_error": last_error,
+                                  "last_error_at": last_error_at,
+                                })
+                            },
+                        )
+                        .collect();
+
+                    Some(serde_json::json!({
+                      "ok": true,
+                      "docs": "https://developers.google.com/youtube/reporting",
+                      "note": "Reporting API jobs can take up to ~24h to generate the first daily reports after enabling/creating the job.",
+                      "content_owner_id": owner_id,
+                      "report_types": report_types,
+                    }))
+                }
+                Ok(_) => None,
+                Err(err) => return Err(err.to_string()),
+            },
+        )
+    };
+
+    let alerts = async {
+        Ok::<Vec<AlertItem>, String>(
+            match sqlx::query_as::<
+                _,
+                (
+                    i64,
+                    String,
+                    String,
+                    String,
+                    DateTime<Utc>,
+                    Option<DateTime<Utc>>,
+                    Option<String>,
+                ),
+            >(
+                r#"
+	          SELECT id, kind, severity, message,
+	                 CAST(detected_at AS DATETIME) AS detected_at,
+	                 CAST(resolved_at AS DATETIME) AS resolved_at,
+	                 details_json
+	          FROM yt_alerts
+	          WHERE tenant_id = ? AND channel_id = ?
+	          ORDER BY (resolved_at IS NULL) DESC, detected_at DESC
+          LIMIT 50;
+        "#,
+            )
+            .bind(tenant_id.trim())
+            .bind(channel_id.trim())
+            .fetch_all(pool)
+            .await
+            {
+                Ok(rows) => rows
+                    .into_iter()
+                    .map(
+                        |(id, kind, severity, message, detected_at, resolved_at, details_json)| {
+                            AlertItem {
+                                id: format!("alert_{id}"),
+                                kind,
+                                severity,
+                                message,
+                                details: details_json.as_deref().and_then(|raw| {
+                                    serde_json::from_str::<serde_json::Value>(raw).ok()
+                                }),
+                                detected_at: datetime_to_rfc3339_utc(detected_at),
+                                resolved_at: resolved_at.map(datetime_to_rfc3339_utc),
+                            }
+                        },
+                    )
+                    .collect(),
+                Err(err) => return Err(err.to_string()),
+            },
+        )
+    };
+
+    let share_latest = async {
+        Ok::<Option<serde_json::Value>, String>(
+            match sqlx::query_as::<_, (String, Option<DateTime<Utc>>, i64, Option<DateTime<Utc>>)>(
+                r#"
+          SELECT token,
+                 CAST(expires_at AS DATETIME) AS expires_at,
+                 CAST(hits AS SIGNED) AS hits,
+                 CAST(last_opened_at AS DATETIME) AS last_opened_at
+          FROM yt_report_shares
+          WHERE tenant_id = ?
+            AND channel_id = ?
+            AND start_dt = ?
+            AND end_dt = ?
+            AND (expires_at IS NULL OR expires_at > ?)
+          ORDER BY created_at DESC
+          LIMIT 1;
+        "#,
+            )
+            .bind(tenant_id.trim())
+            .bind(channel_id.trim())
+            .bind(start_dt)
+            .bind(end_dt)
+            .bind(Utc::now())
+            .fetch_optional(pool)
+            .await
+            {
+                Ok(Some((token, expires_at, hits, last_opened_at))) => Some(serde_json::json!({
+                  "token": token,
+                  "expires_at": expires_at.map(datetime_to_rfc3339_utc),
+                  "hits": hits,
+                  "last_opened_at": last_opened_at.map(datetime_to_rfc3339_utc),
+                })),
+                Ok(None) => None,
+                Err(err) => return Err(err.to_string()),
+            },
+        )
+    };
+
+    let timeout = bundle_section_timeout();
+    let (sync_status, health, uploads, reporting, alerts, share_latest) = tokio::join!(
+        run_bundle_section(timeout, sync_status),
+        run_bundle_section(timeout, health),
+        run_bundle_section(timeout, uploads),
+        run_bundle_section(timeout, reporting),
+        run_bundle_section(timeout, alerts),
+        run_bundle_section(timeout, share_latest),
+    );
+
+    let mut errors = serde_json::Map::new();
+    let sync_status = take_bundle_section(&mut errors, "sync_status", sync_status).flatten();
+    let health = take_bundle_section(&mut errors, "health", health);
+    let uploads = take_bundle_section(&mut errors, "uploads", uploads).unwrap_or_default();
+    let reporting = take_bundle_section(&mut errors, "reporting", reporting).flatten();
+    let alerts = take_bundle_section(&mut errors, "alerts", alerts).unwrap_or_default();
+    let share_latest = take_bundle_section(&mut errors, "share_latest", share_latest).flatten();
+
+    bundle_response(
+        headers,
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "channel_id": channel_id,
+          "start_dt": start_dt.to_string(),
+          "end_dt": end_dt.to_string(),
+          "sync_status": sync_status,
+          "health": health,
+          "alerts": alerts,
+          "uploads": uploads,
+          "reporting": reporting,
+          "share_latest": share_latest,
+          "errors": errors,
+        }),
+    )
+}
+
+async fn handle_youtube_reporting_status(
+    method: &Method,
+    headers: &HeaderMap,
+    uri: &Uri,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::GET {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let tenant_id = get_query_param(uri, "tenant_id").unwrap_or_default();
+    if tenant_id.trim().is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+        );
+    }
+
+    let pool = get_pool().await?;
+    let owner = match get_query_param(uri, "content_owner_id")
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+    {
+        Some(v) => Some(v),
+        None => fetch_youtube_content_owner_id(pool, tenant_id.trim()).await?,
+    };
+
+    let Some(owner_id) = owner.filter(|v| !v.trim().is_empty()) else {
+        return json_response(
+            StatusCode::OK,
+            serde_json::json!({
+              "ok": true,
+              "docs": "https://developers.google.com/youtube/reporting",
+              "note": "Content owner id not discovered yet. Ensure YouTube Partner scope is granted and run sync again.",
+              "content_owner_id": null,
+              "report_types": [],
+            }),
+        );
+    };
+
+    let jobs_rows = sqlx::query_as::<_, (String, String, DateTime<Utc>, DateTime<Utc>)>(
+        r#"
+      SELECT report_type_id, job_id, created_at, updated_at
+      FROM yt_reporting_jobs
+      WHERE tenant_id = ? AND content_owner_id = ?
+      ORDER BY updated_at DESC
+      LIMIT 50;
+    "#,
+    )
+    .bind(tenant_id.trim())
+    .bind(owner_id.trim())
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default();
+
+    let mut jobs_by_type: std::collections::HashMap<String, String> =
+        std::collections::HashMap::new();
+    for (report_type_id, job_id, _created_at, _updated_at) in jobs_rows.into_iter() {
+        jobs_by_type.entry(report_type_id).or_insert(job_id);
+    }
+
+    let stats_rows = sqlx::query_as::<
+        _,
+        (
+            String,
+            i64,
+            i64,
+            i64,
+            Option<DateTime<Utc>>,
+            Option<DateTime<Utc>>,
+        ),
+    >(
+        r#"
+      SELECT report_type_id,
+             CAST(COUNT(*) AS SIGNED) AS total_reports,
+             CAST(SUM(CASE WHEN downloaded_at IS NOT NULL THEN 1 ELSE 0 END) AS SIGNED) AS reports_downloaded,
+             CAST(SUM(CASE WHEN parse_status='parsed' THEN 1 ELSE 0 END) AS SIGNED) AS reports_parsed,
+             MAX(create_time) AS last_create_time,
+             MAX(parsed_at) AS last_parsed_at
+      FROM yt_reporting_report_files
+      WHERE tenant_id = ? AND content_owner_id = ?
+      GROUP BY report_type_id
+      ORDER BY last_create_time DESC;
+    "#,
+    )
+    .bind(tenant_id.trim())
+    .bind(owner_id.trim())
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default();
+
+    let error_rows = sqlx::query_as::<_, (String, String, DateTime<Utc>)>(
+        r#"
+        SELECT report_type_id, parse_error, updated_at
+        FROM yt_reporting_report_files
+        WHERE tenant_id = ?
+          AND content_owner_id = ?
+          AND parse_status = 'error'
+          AND parse_error IS NOT NULL
+        ORDER BY updated_at DESC
+        LIMIT 50;
+      "#,
+    )
+    .bind(tenant_id.trim())
+    .bind(owner_id.trim())
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default();
+
+    let mut last_error_by_type: std::collections::HashMap<String, (String, String)> =
+        std::collections::HashMap::new();
+    for (report_type_id, parse_error, updated_at) in error_rows.into_iter() {
+        if last_error_by_type.contains_key(&report_type_id) {
+            continue;
+        }
+        last_error_by_type.insert(
+            report_type_id,
+            (
+                truncate_string(&parse_error, 800),
+                datetime_to_rfc3339_utc(updated_at),
+            ),
+        );
+    }
+
+    let report_types: Vec<serde_json::Value> = stats_rows
+        .into_iter()
+        .map(
+            |(report_type_id, total, downloaded, parsed, last_create, last_parsed)| {
+                let job_id = jobs_by_type.get(&report_type_id).cloned();
+                let last_error = last_error_by_type.get(&report_type_id).map(|v| v.0.clone());
+                let last_error_at = last_error_by_type.get(&report_type_id).map(|v| v.1.clone());
+                serde_json::json!({
+                  "report_type_id": report_type_id,
+                  "job_id": job_id,
+                  "reports_total": total,
+                  "reports_downloaded": downloaded,
+                  "reports_parsed": parsed,
+                  "last_create_time": last_create.map(datetime_to_rfc3339_utc),
+                  "last_parsed_at": last_parsed.map(datetime_to_rfc3339_utc),
+                  "last_error": last_error,
+                  "last_error_at": last_error_at,
+                })
+            },
+        )
+        .collect();
+
+    json_response(
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "docs": "https://developers.google.com/youtube/reporting",
+          "note": "Reporting API jobs can take up to ~24h to generate the first daily reports after enabling/creating the job.",
+          "content_owner_id": owner_id.trim(),
+          "report_types": report_types,
+        }),
+    )
+}
+
+const REPORTING_EXPORT_MAX_ROWS: i64 = 50_000;
+/// Rows read and written per step of a streamed export, so memory stays bounded by one chunk
+/// rather than the whole export.
+const REPORTING_EXPORT_CHUNK_ROWS: i64 = 1_000;
+
+/// Wide tables are created by the reporting worker as `yt_rpt_<sanitized>_<hash8>`; anything else
+/// coming back from metadata is refused before it is spliced into SQL.
+fn is_safe_wide_table_identifier(name: &str) -> bool {
+    globa_flux_rust::db::is_safe_sql_identifier(name)
+}
+
+/// CSV bytes for one chunk of rows; only the first chunk carries the header line.
+fn render_wide_table_csv(
+    columns: &[String],
+    rows: &[Vec<Option<String>>],
+    include_header: bool,
+) -> Result<Vec<u8>, csv::Error> {
+    let mut wtr = csv::WriterBuilder::new().from_writer(Vec::new());
+    if include_header {
+        wtr.write_record(columns)?;
+    }
+    for row in rows {
+        wtr.write_record(
+            (0..columns.len()).map(|idx| row.get(idx).cloned().flatten().unwrap_or_default()),
+        )?;
+    }
+    wtr.into_inner().map_err(|e| e.into_error().into())
+}
+
+/// Rows the next chunk may read after `sent` rows, or `None` once the export limit is reached.
+fn reporting_export_chunk_limit(sent: i64, limit: i64, chunk_rows: i64) -> Option<i64> {
+    let remaining = limit - sent;
+    (remaining > 0).then(|| remaining.min(chunk_rows))
+}
+
+/// One validated wide-table export, read in `(report_id, row_no)` keyset order.
+#[derive(Clone)]
+struct ReportingExportQuery {
+    table_name: String,
+    columns: Vec<String>,
+    tenant_id: String,
+    owner_id: String,
+    report_type_id: String,
+    /// `YYYYMMDD` bounds, only set when the table has a `date` column.
+    start_date: Option<String>,
+    end_date: Option<String>,
+}
+
+type ReportingExportCursor = (String, i64);
+
+type ReportingExportChunk = (Vec<Vec<Option<String>>>, Option<ReportingExportCursor>);
+
+impl ReportingExportQuery {
+    /// The next `limit` rows strictly after `after` in keyset order.
+    fn chunk_query(
+        &self,
+        after: Option<&ReportingExportCursor>,
+        limit: i64,
+    ) -> sqlx::QueryBuilder<'static, sqlx::MySql> {
+        let mut qb = sqlx::QueryBuilder::<sqlx::MySql>::new("SELECT ");
+        {
+            let mut separated = qb.separated(", ");
+            for col in self.columns.iter() {
+                separated.push(format!("`{col}`"));
+            }
+            separated.push("report_id");
+            separated.push("row_no");
+        }
+        qb.push(format!(" FROM `{}` WHERE tenant_id = ", self.table_name));
+        qb.push_bind(self.tenant_id.clone());
+        qb.push(" AND content_owner_id = ");
+        qb.push_bind(self.owner_id.clone());
+        qb.push(" AND report_type_id = ");
+        qb.push_bind(self.report_type_id.clone());
+        if let Some(start_date) = self.start_date.as_ref() {
+            qb.push(" AND `date` >= ");
+            qb.push_bind(start_date.clone());
+        }
+        if let Some(end_date) = self.end_date.as_ref() {
+            qb.push(" AND `date` <= ");
+            qb.push_bind(end_date.clone());
+        }
+        if let Some((report_id, row_no)) = after {
+            qb.push(" AND (report_id > ");
+            qb.push_bind(report_id.clone());
+            qb.push(" OR (report_id = ");
+            qb.push_bind(report_id.clone());
+            qb.push(" AND row_no > ");
+            qb.push_bind(*row_no);
+            qb.push("))");
+        }
+        qb.push(" ORDER BY report_id ASC, row_no ASC LIMIT ");
+        qb.push_bind(limit);
+        qb.push(";");
+        qb
+    }
+
+    async fn fetch_chunk(
+        &self,
+        pool: &sqlx::MySqlPool,
+        after: Option<&ReportingExportCursor>,
+        limit: i64,
+    ) -> Result<ReportingExportChunk, Error> {
+        use sqlx::Row;
+
+        let mut qb = self.chunk_query(after, limit);
+        let rows = qb
+            .build()
+            .fetch_all(pool)
+            .await
+            .map_err(|e| -> Error { Box::new(e) })?;
+
+        let n = self.columns.len();
+        let mut values: Vec<Vec<Option<String>>> = Vec::with_capacity(rows.len());
+        let mut last: Option<ReportingExportCursor> = None;
+        for row in rows.iter() {
+            let mut out: Vec<Option<String>> = Vec::with_capacity(n);
+            for idx in 0..n {
+                out.push(
+                    row.try_get::<Option<String>, _>(idx)
+                        .map_err(|e| -> Error { Box::new(e) })?,
+                );
+            }
+            values.push(out);
+            last = Some((
+                row.try_get::<String, _>(n)
+                    .map_err(|e| -> Error { Box::new(e) })?,
+                row.try_get::<i64, _>(n + 1)
+                    .map_err(|e| -> Error { Box::new(e) })?,
+            ));
+        }
+        Ok((values, last))
+    }
+}
+
+fn csv_render_error(e: csv::Error) -> Error {
+    Box::new(std::io::Error::other(format!("csv render error: {e}")))
+}
+
+/// Streams the rest of an export whose first chunk sent `sent` rows ending at `cursor`: reads
+/// `fetch(cursor, n)` pages until `limit` rows are sent or a short page ends the table, and sends
+/// each as a CSV frame. Stops quietly once the client hangs up.
+async fn send_remaining_export_chunks<F, Fut>(
+    fetch: F,
+    columns: Vec<String>,
+    mut cursor: ReportingExportCursor,
+    mut sent: i64,
+    limit: i64,
+    chunk_rows: i64,
+    tx: mpsc::Sender<Result<Frame<Bytes>, Error>>,
+) where
+    F: Fn(ReportingExportCursor, i64) -> Fut,
+    Fut: std::future::Future<Output = Result<ReportingExportChunk, Error>>,
+{
+    while let Some(chunk_limit) = reporting_export_chunk_limit(sent, limit, chunk_rows) {
+        let (rows, next_cursor) = match fetch(cursor.clone(), chunk_limit).await {
+            Ok(v) => v,
+            Err(err) => {
+                let _ = tx.send(Err(err)).await;
+                return;
+            }
+        };
+        let bytes = match render_wide_table_csv(&columns, &rows, false) {
+            Ok(v) => v,
+            Err(err) => {
+                let _ = tx.send(Err(csv_render_error(err))).await;
+                return;
+            }
+        };
+        if tx.send(Ok(Frame::data(Bytes::from(bytes)))).await.is_err() {
+            return;
+        }
+        sent += rows.len() as i64;
+        match next_cursor {
+            Some(next) if rows.len() as i64 == chunk_limit => cursor = next,
+            _ => return,
+        }
+    }
+}
+
+async fn handle_youtube_reporting_export(
+    method: &Method,
+    headers: &HeaderMap,
+    uri: &Uri,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::GET {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let tenant_id = get_query_param(uri, "tenant_id").unwrap_or_default();
+    let report_type_id = get_query_param(uri, "report_type_id").unwrap_or_default();
+    if tenant_id.trim().is_empty() || report_type_id.trim().is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id and report_type_id are required"}),
+        );
+    }
+
+    let start_dt = get_query_param(uri, "start_dt").and_then(|v| parse_dt(&v));
+    let end_dt = get_query_param(uri, "end_dt").and_then(|v| parse_dt(&v));
+    let limit = get_query_param(uri, "limit")
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .unwrap_or(REPORTING_EXPORT_MAX_ROWS)
+        .clamp(1, REPORTING_EXPORT_MAX_ROWS);
+
+    let pool = get_pool().await?;
+    let owner = match get_query_param(uri, "content_owner_id")
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+    {
+        Some(v) => Some(v),
+        None => fetch_youtube_content_owner_id(pool, tenant_id.trim()).await?,
+    };
+    let Some(owner_id) = owner.filter(|v| !v.trim().is_empty()) else {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "Content owner id not discovered yet"}),
+        );
+    };
+
+    let meta = sqlx::query_as::<_, (String, String)>(
+        r#"
+      SELECT table_name, columns_json
+      FROM yt_reporting_wide_tables
+      WHERE report_type_id = ?
+      LIMIT 1;
+    "#,
+    )
+    .bind(report_type_id.trim())
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    let Some((table_name, columns_json)) = meta else {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_found", "message": "No ingested rows for this report_type_id"}),
+        );
+    };
+
+    let columns = serde_json::from_str::<Vec<String>>(&columns_json).unwrap_or_default();
+    if !table_name.starts_with("yt_rpt_")
+        || !is_safe_wide_table_identifier(&table_name)
+        || columns.is_empty()
+        || !columns.iter().all(|c| is_safe_wide_table_identifier(c))
+    {
+        return json_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            serde_json::json!({"ok": false, "error": "invalid_metadata", "message": "Reporting table metadata failed identifier validation"}),
+        );
+    }
+
+    // Reporting API daily reports carry `date` as YYYYMMDD; only filter when the column exists.
+    let has_date_column = columns.iter().any(|c| c == "date");
+    let date_bound = |dt: Option<NaiveDate>| {
+        dt.filter(|_| has_date_column)
+            .map(|dt| dt.format("%Y%m%d").to_string())
+    };
+    let query = ReportingExportQuery {
+        table_name,
+        columns,
+        tenant_id: tenant_id.trim().to_string(),
+        owner_id: owner_id.trim().to_string(),
+        report_type_id: report_type_id.trim().to_string(),
+        start_date: date_bound(start_dt),
+        end_date: date_bound(end_dt),
+    };
+
+    // The first chunk is read before responding, so a failing query is still a plain error; the
+    // rest is streamed one chunk at a time, so the row count is not known up front and no
+    // `x-row-count` header is sent.
+    let first_limit = reporting_export_chunk_limit(0, limit, REPORTING_EXPORT_CHUNK_ROWS)
+        .unwrap_or(REPORTING_EXPORT_CHUNK_ROWS);
+    let (first_rows, first_cursor) = query.fetch_chunk(pool, None, first_limit).await?;
+    let first_bytes =
+        render_wide_table_csv(&query.columns, &first_rows, true).map_err(csv_render_error)?;
+
+    let (tx, rx) = mpsc::channel::<Result<Frame<Bytes>, Error>>(4);
+    let _ = tx.send(Ok(Frame::data(Bytes::from(first_bytes)))).await;
+    let sent = first_rows.len() as i64;
+    if let (Some(cursor), true) = (first_cursor, sent == first_limit) {
+        let columns = query.columns.clone();
+        let fetch = move |after: ReportingExportCursor, n: i64| {
+            let query = query.clone();
+            async move { query.fetch_chunk(pool, Some(&after), n).await }
+        };
+        tokio::spawn(send_remaining_export_chunks(
+            fetch,
+            columns,
+            cursor,
+            sent,
+            limit,
+            REPORTING_EXPORT_CHUNK_ROWS,
+            tx,
+        ));
+    }
+
+    let filename = format!(
+        "{}.csv",
+        globa_flux_rust::db::sanitize_sql_identifier(&report_type_id)
+    );
+    let body = StreamBody::new(ReceiverStream::new(rx));
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "text/csv; charset=utf-8")
+        .header(
+            "content-disposition",
+            format!("attachment; filename=\"{filename}\""),
+        )
+        .body(ResponseBody::from(body))?)
+}
+
+#[derive(serde::Serialize)]
+struct UploadItem {
+    id: String,
+    filename: String,
+    channel_id: String,
+    created_at: String,
+    status: String,
+}
+
+type CsvUploadRow = (i64, String, String, DateTime<Utc>);
+
+async fn handle_youtube_uploads_list(
+    method: &Method,
+    headers: &HeaderMap,
+    uri: &Uri,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::GET {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let tenant_id = get_query_param(uri, "tenant_id").unwrap_or_default();
+    if tenant_id.trim().is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+        );
+    }
+
+    let pool = get_pool().await?;
+    let channel_id = match get_query_param(uri, "channel_id")
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+    {
+        Some(v) => v,
+        None => fetch_youtube_channel_id(pool, tenant_id.trim())
+            .await?
+            .unwrap_or_default(),
+    };
+
+    if channel_id.trim().is_empty() {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "No active YouTube channel for this tenant"}),
+        );
+    }
+
+    let Ok(since) = parse_since_param(get_query_param(uri, "since").as_deref()) else {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "since must be an RFC3339 timestamp"}),
+        );
+    };
+    let next_since = Utc::now();
+
+    let rows = sqlx::query_as::<_, CsvUploadRow>(
+        r#"
+      SELECT id, filename, status, created_at
+      FROM yt_csv_uploads
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND (? IS NULL OR updated_at > ?)
+      ORDER BY created_at DESC
+      LIMIT 20;
+    "#,
+    )
+    .bind(tenant_id.trim())
+    .bind(channel_id.trim())
+    .bind(since)
+    .bind(since)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    let items: Vec<UploadItem> = rows
+        .into_iter()
+        .map(|(id, filename, status, created_at)| UploadItem {
+            id: format!("upload_{id}"),
+            filename,
+            channel_id: channel_id.clone(),
+            created_at: datetime_to_rfc3339_utc(created_at),
+            status,
+        })
+        .collect();
+
+    json_response(
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "items": items,
+          "channel_id": channel_id,
+          "since": since.map(datetime_to_rfc3339_utc),
+          "next_since": datetime_to_rfc3339_utc(next_since),
+        }),
+    )
+}
+
+fn normalize_csv_header_name(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut last_was_sep = false;
+    for ch in input.trim().chars() {
+        if ch.is_ascii_alphanumeric() {
+            out.push(ch.to_ascii_lowercase());
+            last_was_sep = false;
+        } else if !last_was_sep {
+            out.push('_');
+            last_was_sep = true;
+        }
+    }
+    out.trim_matches('_').to_string()
+}
+
+fn parse_i64_field(raw: &str) -> Option<i64> {
+    let cleaned = raw.trim().replace(',', "");
+    cleaned.parse::<i64>().ok()
+}
+
+fn parse_f64_field(raw: &str) -> Option<f64> {
+    let cleaned = raw.trim().replace(',', "").replace('$', "");
+    cleaned.parse::<f64>().ok()
+}
+
+fn parse_ctr_field(raw: &str) -> Option<f64> {
+    let s = raw.trim();
+    let is_percent = s.ends_with('%');
+    let cleaned = s.trim_end_matches('%').replace(',', "");
+    let v = cleaned.parse::<f64>().ok()?;
+    if is_percent {
+        Some(v / 100.0)
+    } else {
+        Some(v)
+    }
+}
+
+/// Resolves the ambiguous `01/02/2026` style dates; explicit formats always win over this.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum CsvDateLocale {
+    #[default]
+    MonthFirst,
+    DayFirst,
+}
+
+impl CsvDateLocale {
+    fn from_hint(hint: &str) -> Option<Self> {
+        match hint.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "mdy" | "us" | "en-us" => Some(CsvDateLocale::MonthFirst),
+            "dmy" | "eu" | "en-gb" => Some(CsvDateLocale::DayFirst),
+            _ => None,
+        }
+    }
+}
+
+/// How views and impressions are filled in when a row carries CTR. `PreferViews` (default) keeps
+/// a views column and only derives views from `impressions * ctr` when it is missing;
+/// `PreferCtr` treats CTR as authoritative, deriving views from impressions and backfilling
+/// missing impressions from `views / ctr`; `Strict` never derives anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum CsvReconstruction {
+    #[default]
+    PreferViews,
+    PreferCtr,
+    Strict,
+}
+
+impl CsvReconstruction {
+    fn from_param(v: Option<&str>) -> Option<Self> {
+        match v.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("") | Some("prefer_views") => Some(CsvReconstruction::PreferViews),
+            Some("prefer_ctr") => Some(CsvReconstruction::PreferCtr),
+            Some("strict") => Some(CsvReconstruction::Strict),
+            Some(_) => None,
+        }
+    }
+
+    /// `(impressions, views)` for a row from its raw fields.
+    fn resolve(self, impressions: Option<i64>, views: Option<i64>, ctr: Option<f64>) -> (i64, i64) {
+        let impressions = impressions.map(|v| v.max(0));
+        let views = views.map(|v| v.max(0));
+        let views_from_ctr = |impr: i64| {
+            ctr.filter(|_| impr > 0)
+                .map(|ctr| ((impr as f64) * ctr).round() as i64)
+        };
+        match self {
+            CsvReconstruction::PreferViews => {
+                let impr = impressions.unwrap_or(0);
+                (
+                    impr,
+                    views.or_else(|| views_from_ctr(impr)).unwrap_or(0).max(0),
+                )
+            }
+            CsvReconstruction::PreferCtr => {
+                let ctr = ctr.filter(|v| v.is_finite() && *v > 0.0);
+                match (impressions.filter(|v| *v > 0), views, ctr) {
+                    (Some(impr), views, Some(_)) => {
+                        (impr, views_from_ctr(impr).or(views).unwrap_or(0).max(0))
+                    }
+                    (None, Some(views), Some(ctr)) => {
+                        (((views as f64) / ctr).round() as i64, views)
+                    }
+                    (impr, views, _) => (impr.unwrap_or(0), views.unwrap_or(0)),
+                }
+            }
+            CsvReconstruction::Strict => (impressions.unwrap_or(0), views.unwrap_or(0)),
+        }
+    }
+}
+
+const CSV_RECONSTRUCTION_MESSAGE: &str = "reconstruction must be prefer_views|prefer_ctr|strict";
+
+const CSV_VIEWS_COLUMNS: &[&str] = &["views", "view"];
+const CSV_IMPRESSIONS_COLUMNS: &[&str] = &["impressions", "impr", "impression"];
+const CSV_REVENUE_COLUMNS: &[&str] = &[
+    "revenue_usd",
+    "estimated_revenue_usd",
+    "estimatedrevenue",
+    "estimated_revenue",
+    "revenue",
+];
+const CSV_CTR_COLUMNS: &[&str] = &["ctr", "impressions_click_through_rate"];
+
+/// Normalized header names tried (in order) for each metric column.
+#[derive(Debug, Clone)]
+struct CsvColumnSynonyms {
+    views: Vec<String>,
+    impressions: Vec<String>,
+    revenue: Vec<String>,
+    ctr: Vec<String>,
+}
+
+impl CsvColumnSynonyms {
+    /// Built-ins extended by `CSV_VIEWS_COLUMNS`, `CSV_IMPRESSIONS_COLUMNS`,
+    /// `CSV_REVENUE_COLUMNS` and `CSV_CTR_COLUMNS` (semicolon-separated header names).
+    fn from_env() -> Self {
+        let env = |key: &str| std::env::var(key).unwrap_or_default();
+        Self {
+            views: csv_column_synonyms(CSV_VIEWS_COLUMNS, &env("CSV_VIEWS_COLUMNS")),
+            impressions: csv_column_synonyms(
+                CSV_IMPRESSIONS_COLUMNS,
+                &env("CSV_IMPRESSIONS_COLUMNS"),
+            ),
+            revenue: csv_column_synonyms(CSV_REVENUE_COLUMNS, &env("CSV_REVENUE_COLUMNS")),
+            ctr: csv_column_synonyms(CSV_CTR_COLUMNS, &env("CSV_CTR_COLUMNS")),
+        }
+    }
+}
+
+/// Built-ins stay first; additions are normalized like CSV headers so `Your estimated revenue
+/// (USD)` matches the export's header as-is.
+fn csv_column_synonyms(builtin: &[&str], additions: &str) -> Vec<String> {
+    let mut out: Vec<String> = builtin.iter().map(|v| v.to_string()).collect();
+    for name in additions.split(';') {
+        let name = normalize_csv_header_name(name);
+        if !name.is_empty() && !out.contains(&name) {
+            out.push(name);
+        }
+    }
+    out
+}
+
+#[derive(Debug, Clone)]
+struct CsvParseOptions {
+    date_formats: Vec<String>,
+    column_synonyms: CsvColumnSynonyms,
+    reconstruction: CsvReconstruction,
+}
+
+impl Default for CsvParseOptions {
+    fn default() -> Self {
+        Self {
+            date_formats: csv_date_formats(CsvDateLocale::default(), &[]),
+            column_synonyms: CsvColumnSynonyms::from_env(),
+            reconstruction: CsvReconstruction::default(),
+        }
+    }
+}
+
+/// Built-ins for the locale first, then `CSV_DATE_FORMATS` (semicolon-separated chrono formats),
+/// then request-level formats.
+fn csv_date_formats(locale: CsvDateLocale, extra: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = vec!["%Y-%m-%d".to_string(), "%Y/%m/%d".to_string()];
+    match locale {
+        CsvDateLocale::MonthFirst => out.push("%m/%d/%Y".to_string()),
+        CsvDateLocale::DayFirst => {
+            out.push("%d/%m/%Y".to_string());
+            out.push("%d.%m.%Y".to_string());
+            out.push("%d-%m-%Y".to_string());
+        }
+    }
+
+    let from_env = std::env::var("CSV_DATE_FORMATS").unwrap_or_default();
+    for fmt in from_env.split(';').chain(extra.iter().map(String::as_str)) {
+        let fmt = fmt.trim();
+        if !fmt.is_empty() && !out.iter().any(|v| v == fmt) {
+            out.push(fmt.to_string());
+        }
+    }
+    out
+}
+
+fn parse_dt_with_formats(v: &str, formats: &[String]) -> Option<NaiveDate> {
+    let s = v.trim();
+    formats
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(s, fmt).ok())
+}
+
+#[derive(Debug, Clone)]
+struct CsvMetricRow {
+    dt: NaiveDate,
+    video_id: String,
+    estimated_revenue_usd: f64,
+    impressions: i64,
+    impressions_ctr: Option<f64>,
+    views: i64,
+}
+
+/// Rows dated past today (UTC) plus this many days are treated as a broken export.
+const CSV_FUTURE_DATE_SKEW_DAYS: i64 = 1;
+
+/// Splits out rows dated after `today + skew_days`, returning the kept rows and the dropped count.
+fn drop_future_dated_rows(
+    rows: Vec<CsvMetricRow>,
+    today: NaiveDate,
+    skew_days: i64,
+) -> (Vec<CsvMetricRow>, i64) {
+    let cutoff = today + Duration::days(skew_days.max(0));
+    let before = rows.len();
+    let kept: Vec<CsvMetricRow> = rows.into_iter().filter(|row| row.dt <= cutoff).collect();
+    let dropped = (before - kept.len()) as i64;
+    (kept, dropped)
+}
+
+/// Header positions of the metric columns `parse_csv_metrics` reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CsvColumns {
+    dt: usize,
+    video: Option<usize>,
+    views: Option<usize>,
+    impressions: Option<usize>,
+    revenue: Option<usize>,
+    rpm: Option<usize>,
+    ctr: Option<usize>,
+}
+
+impl CsvColumns {
+    /// Which original header each field was read from (null when the column is absent).
+    fn mapping(&self, headers: &csv::StringRecord) -> serde_json::Value {
+        let name = |i: Option<usize>| i.and_then(|i| headers.get(i)).map(str::to_string);
+        serde_json::json!({
+          "date": name(Some(self.dt)),
+          "video_id": name(self.video),
+          "views": name(self.views),
+          "impressions": name(self.impressions),
+          "revenue_usd": name(self.revenue),
+          "rpm": name(self.rpm),
+          "ctr": name(self.ctr),
+        })
+    }
+}
+
+fn resolve_csv_columns(
+    headers: &csv::StringRecord,
+    options: &CsvParseOptions,
+) -> Result<CsvColumns, String> {
+    use std::collections::HashMap;
+
+    let mut idx: HashMap<String, usize> = HashMap::new();
+    for (i, h) in headers.iter().enumerate() {
+        idx.insert(normalize_csv_header_name(h), i);
+    }
+
+    let find_idx = |candidates: &[&str]| -> Option<usize> {
+        for c in candidates {
+            if let Some(i) = idx.get(*c) {
+                return Some(*i);
+            }
+        }
+        None
+    };
+    let find_synonym_idx = |candidates: &[String]| -> Option<usize> {
+        candidates.iter().find_map(|c| idx.get(c.as_str()).copied())
+    };
+
+    let synonyms = &options.column_synonyms;
+    Ok(CsvColumns {
+        dt: find_idx(&["date", "day", "dt"])
+            .ok_or_else(|| "missing date/day/dt column".to_string())?,
+        video: find_idx(&["video_id", "videoid", "video"]),
+        views: find_synonym_idx(&synonyms.views),
+        impressions: find_synonym_idx(&synonyms.impressions),
+        revenue: find_synonym_idx(&synonyms.revenue),
+        rpm: find_idx(&["rpm"]),
+        ctr: find_synonym_idx(&synonyms.ctr),
+    })
+}
+
+fn csv_headers(csv_text: &str) -> Result<csv::StringRecord, String> {
+    csv::ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .from_reader(csv_text.as_bytes())
+        .headers()
+        .cloned()
+        .map_err(|e| format!("invalid csv headers: {e}"))
+}
+
+/// At most this many skipped rows are described in `csv_stats`; the count is always exact.
+const CSV_MAX_REPORTED_ROW_ERRORS: usize = 50;
+
+/// One data row (1-based, header excluded) that was skipped and why.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+struct CsvRowError {
+    row: usize,
+    column: Option<String>,
+    value: Option<String>,
+    reason: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CsvSkippedRows {
+    count: i64,
+    errors: Vec<CsvRowError>,
+}
+
+impl CsvSkippedRows {
+    fn push(&mut self, error: CsvRowError) {
+        self.count += 1;
+        if self.errors.len() < CSV_MAX_REPORTED_ROW_ERRORS {
+            self.errors.push(error);
+        }
+    }
+}
+
+/// A numeric cell: `None` when the column is absent or the cell blank, an error naming the column
+/// when it holds something that is not a number (e.g. `abc`), so the row is skipped rather than
+/// read as zero.
+fn csv_numeric_field<T>(
+    rec: &csv::StringRecord,
+    headers: &csv::StringRecord,
+    idx: Option<usize>,
+    row: usize,
+    parse: fn(&str) -> Option<T>,
+) -> Result<Option<T>, CsvRowError> {
+    let Some(raw) = idx.and_then(|i| rec.get(i)).map(str::trim) else {
+        return Ok(None);
+    };
+    if raw.is_empty() {
+        return Ok(None);
+    }
+    match parse(raw) {
+        Some(v) => Ok(Some(v)),
+        None => Err(CsvRowError {
+            row,
+            column: idx.and_then(|i| headers.get(i)).map(str::to_string),
+            value: Some(raw.to_string()),
+            reason: "invalid number".to_string(),
+        }),
+    }
+}
+
+/// Malformed rows are skipped and reported; the upload only fails when the file itself is
+/// unusable or no row at all could be read.
+fn parse_csv_metrics(
+    csv_text: &str,
+    options: &CsvParseOptions,
+) -> Result<(Vec<CsvMetricRow>, CsvSkippedRows), String> {
+    if csv_text.trim().is_empty() {
+        return Err("csv_text is empty".to_string());
+    }
+
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .from_reader(csv_text.as_bytes());
+
+    let headers = rdr
+        .headers()
+        .map_err(|e| format!("invalid csv headers: {e}"))?
+        .clone();
+
+    let CsvColumns {
+        dt: dt_idx,
+        video: video_idx,
+        views: views_idx,
+        impressions: impressions_idx,
+        revenue: revenue_idx,
+        rpm: rpm_idx,
+        ctr: ctr_idx,
+    } = resolve_csv_columns(&headers, options)?;
+
+    let mut out: Vec<CsvMetricRow> = Vec::new();
+    let mut skipped = CsvSkippedRows::default();
+
+    for (row_i, rec) in rdr.records().enumerate() {
+        let rec = match rec {
+            Ok(rec) => rec,
+            Err(e) => {
+                skipped.push(CsvRowError {
+                    row: row_i + 1,
+                    column: None,
+                    value: None,
+                    reason: format!("invalid csv row: {e}"),
+                });
+                continue;
+            }
+        };
+
+        let dt_raw = rec.get(dt_idx).unwrap_or("").trim();
+        let Some(dt) = parse_dt_with_formats(dt_raw, &options.date_formats) else {
+            skipped.push(CsvRowError {
+                row: row_i + 1,
+                column: headers.get(dt_idx).map(str::to_string),
+                value: Some(dt_raw.to_string()),
+                reason: "invalid date".to_string(),
+            });
+            continue;
+        };
+
+        let video_id = video_idx
+            .and_then(|i| rec.get(i))
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| CSV_CHANNEL_TOTAL_VIDEO_ID.to_string());
+
+        let numeric_fields = (|| -> Result<_, CsvRowError> {
+            let row = row_i + 1;
+            Ok((
+                csv_numeric_field(&rec, &headers, impressions_idx, row, parse_i64_field)?,
+                csv_numeric_field(&rec, &headers, views_idx, row, parse_i64_field)?,
+                csv_numeric_field(&rec, &headers, revenue_idx, row, parse_f64_field)?,
+                csv_numeric_field(&rec, &headers, rpm_idx, row, parse_f64_field)?,
+            ))
+        })();
+        let (impressions_from_field, views_from_field, revenue_from_field, rpm_from_field) =
+            match numeric_fields {
+                Ok(fields) => fields,
+                Err(error) => {
+                    skipped.push(error);
+                    continue;
+                }
+            };
+        let impressions_ctr = ctr_idx.and_then(|i| rec.get(i)).and_then(parse_ctr_field);
+
+        let (impressions, views) = options.reconstruction.resolve(
+            impressions_from_field,
+            views_from_field,
+            impressions_ctr,
+        );
+
+        let revenue_from_rpm = rpm_from_field
+            .filter(|_| views > 0)
+            .map(|rpm| (rpm * (views as f64)) / 1000.0);
+
+        let revenue = revenue_from_field
+            .or(revenue_from_rpm)
+            .unwrap_or(0.0)
+            .max(0.0);
+
+        // Drop fully-empty rows (common in exports).
+        if impressions == 0 && views == 0 && revenue == 0.0 {
+            continue;
+        }
+
+        out.push(CsvMetricRow {
+            dt,
+            video_id,
+            estimated_revenue_usd: revenue,
+            impressions,
+            impressions_ctr,
+            views,
+        });
+    }
+
+    if out.is_empty() {
+        if let Some(first) = skipped.errors.first() {
+            return Err(format!(
+                "no valid rows ({} skipped); first error at row {}: {}{}",
+                skipped.count,
+                first.row,
+                first.reason,
+                first
+                    .value
+                    .as_deref()
+                    .map(|v| format!(": {v}"))
+                    .unwrap_or_default()
+            ));
+        }
+    }
+
+    Ok((out, skipped))
+}
+
+const CSV_MAX_BYTES: usize = 5_000_000;
+const CSV_LOCALE_HINT_MESSAGE: &str = "locale_hint must be mdy|dmy (or us|eu|en-US|en-GB)";
+const CSV_PREVIEW_SAMPLE_ROWS: usize = 10;
+const DEFAULT_CSV_MAX_ROWS: usize = 200_000;
+const DEFAULT_CSV_MAX_DATE_SPAN_DAYS: i64 = 730;
+
+/// Caps on what one upload may ingest, beyond the byte limit; each upload is upserted row by row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CsvUploadLimits {
+    max_rows: usize,
+    max_date_span_days: i64,
+}
+
+impl CsvUploadLimits {
+    /// `CSV_MAX_ROWS` / `CSV_MAX_DATE_SPAN_DAYS` override the defaults.
+    fn from_env() -> Self {
+        let env = |key: &str| std::env::var(key).ok().map(|v| v.trim().to_string());
+        Self {
+            max_rows: env("CSV_MAX_ROWS")
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(DEFAULT_CSV_MAX_ROWS),
+            max_date_span_days: env("CSV_MAX_DATE_SPAN_DAYS")
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(DEFAULT_CSV_MAX_DATE_SPAN_DAYS),
+        }
+    }
+}
+
+/// Returns the error code, message and offending count when `rows` exceed `limits`.
+fn check_csv_upload_limits(
+    rows: &[CsvMetricRow],
+    limits: CsvUploadLimits,
+) -> Result<(), (&'static str, String, i64)> {
+    if rows.len() > limits.max_rows {
+        return Err((
+            "csv_too_many_rows",
+            format!(
+                "csv has {} rows (max {}); split the export into smaller date ranges",
+                rows.len(),
+                limits.max_rows
+            ),
+            rows.len() as i64,
+        ));
+    }
+
+    let min_dt = rows.iter().map(|row| row.dt).min();
+    let max_dt = rows.iter().map(|row| row.dt).max();
+    if let (Some(min_dt), Some(max_dt)) = (min_dt, max_dt) {
+        let span_days = (max_dt - min_dt).num_days() + 1;
+        if span_days > limits.max_date_span_days {
+            return Err((
+                "csv_date_span_too_large",
+                format!(
+                    "csv spans {span_days} days ({min_dt}..{max_dt}; max {})",
+                    limits.max_date_span_days
+                ),
+                span_days,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// `Err` carries the message for an unrecognized `locale_hint` or `reconstruction`.
+fn csv_parse_options_for_request(
+    locale_hint: Option<&str>,
+    date_formats: &[String],
+    reconstruction: Option<&str>,
+) -> Result<CsvParseOptions, &'static str> {
+    let date_locale = match locale_hint.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(hint) => CsvDateLocale::from_hint(hint).ok_or(CSV_LOCALE_HINT_MESSAGE)?,
+        None => CsvDateLocale::default(),
+    };
+    Ok(CsvParseOptions {
+        date_formats: csv_date_formats(date_locale, date_formats),
+        column_synonyms: CsvColumnSynonyms::from_env(),
+        reconstruction: CsvReconstruction::from_param(reconstruction)
+            .ok_or(CSV_RECONSTRUCTION_MESSAGE)?,
+    })
+}
+
+const DEFAULT_CSV_STORE_MAX_BYTES: usize = 2_000_000;
+
+/// `CSV_STORE_MAX_BYTES` (default 2MB, at most `CSV_MAX_BYTES`; `0` stores nothing): uploads up
+/// to this size keep their `csv_text` so they can be reprocessed later.
+fn csv_store_max_bytes() -> usize {
+    std::env::var("CSV_STORE_MAX_BYTES")
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .unwrap_or(DEFAULT_CSV_STORE_MAX_BYTES)
+        .min(CSV_MAX_BYTES)
+}
+
+fn csv_text_for_storage(csv_text: &str, max_bytes: usize) -> Option<&str> {
+    if csv_text.is_empty() || csv_text.len() > max_bytes {
+        None
+    } else {
+        Some(csv_text)
+    }
+}
+
+/// Request options persisted with an upload so a reprocess parses it the way it was uploaded.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, serde::Serialize)]
+struct StoredCsvParseOptions {
+    #[serde(default)]
+    locale_hint: Option<String>,
+    #[serde(default)]
+    date_formats: Vec<String>,
+    #[serde(default)]
+    max_future_rows: Option<i64>,
+    #[serde(default)]
+    reconstruction: Option<String>,
+}
+
+impl StoredCsvParseOptions {
+    fn from_json(raw: Option<&str>) -> Self {
+        raw.and_then(|v| serde_json::from_str(v).ok())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct CsvUploadStats {
+    total_rows: usize,
+    channel_total_rows: i64,
+    per_video_rows: i64,
+    date_min: Option<String>,
+    date_max: Option<String>,
+    has_views: bool,
+    has_impressions: bool,
+    has_revenue: bool,
+    has_ctr: bool,
+    ctr_present_rows: i64,
+    ctr_nonzero_rows: i64,
+    future_dated_rows: i64,
+    skipped_rows: i64,
+    row_errors: Vec<CsvRowError>,
+}
+
+fn csv_upload_stats(
+    rows: &[CsvMetricRow],
+    future_dated_rows: i64,
+    skipped: &CsvSkippedRows,
+) -> CsvUploadStats {
+    let mut min_dt: Option<NaiveDate> = None;
+    let mut max_dt: Option<NaiveDate> = None;
+    let mut channel_total_rows: i64 = 0;
+    let mut per_video_rows: i64 = 0;
+    let mut rows_with_views: i64 = 0;
+    let mut rows_with_impressions: i64 = 0;
+    let mut rows_with_revenue: i64 = 0;
+    let mut ctr_present_rows: i64 = 0;
+    let mut ctr_nonzero_rows: i64 = 0;
+
+    for row in rows.iter() {
+        min_dt = Some(match min_dt {
+            Some(cur) => cur.min(row.dt),
+            None => row.dt,
+        });
+        max_dt = Some(match max_dt {
+            Some(cur) => cur.max(row.dt),
+            None => row.dt,
+        });
+
+        if row.video_id == CSV_CHANNEL_TOTAL_VIDEO_ID {
+            channel_total_rows += 1;
+        } else {
+            per_video_rows += 1;
+        }
+
+        if row.views > 0 {
+            rows_with_views += 1;
+        }
+        if row.impressions > 0 {
+            rows_with_impressions += 1;
+        }
+        if row.estimated_revenue_usd > 0.0 {
+            rows_with_revenue += 1;
+        }
+
+        if let Some(ctr) = row.impressions_ctr {
+            ctr_present_rows += 1;
+            if ctr > 0.0 {
+                ctr_nonzero_rows += 1;
+            }
+        }
+    }
+
+    CsvUploadStats {
+        total_rows: rows.len(),
+        channel_total_rows,
+        per_video_rows,
+        date_min: min_dt.map(|d| d.to_string()),
+        date_max: max_dt.map(|d| d.to_string()),
+        has_views: rows_with_views > 0,
+        has_impressions: rows_with_impressions > 0,
+        has_revenue: rows_with_revenue > 0,
+        has_ctr: ctr_present_rows > 0,
+        ctr_present_rows,
+        ctr_nonzero_rows,
+        future_dated_rows,
+        skipped_rows: skipped.count,
+        row_errors: skipped.errors.clone(),
+    }
+}
+
+#[derive(Deserialize)]
+struct UploadCsvPreviewRequest {
+    csv_text: String,
+    #[serde(default)]
+    date_formats: Vec<String>,
+    #[serde(default)]
+    locale_hint: Option<String>,
+    /// `prefer_views` (default) | `prefer_ctr` | `strict`; see `CsvReconstruction`.
+    #[serde(default)]
+    reconstruction: Option<String>,
+}
+
+/// Runs the same parser as `youtube_upload_csv` and reports what would be ingested; nothing is
+/// written (no yt_csv_uploads row, no metrics upserts), so it does not need the database.
+async fn handle_youtube_upload_csv_preview(
+    method: &Method,
+    headers: &HeaderMap,
+    body: Bytes,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::POST {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    let parsed: UploadCsvPreviewRequest = serde_json::from_slice(&body).map_err(|e| -> Error {
+        Box::new(std::io::Error::other(format!("invalid json body: {e}")))
+    })?;
+
+    let csv_options = match csv_parse_options_for_request(
+        parsed.locale_hint.as_deref(),
+        &parsed.date_formats,
+        parsed.reconstruction.as_deref(),
+    ) {
+        Ok(v) => v,
+        Err(message) => {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": message}),
+            );
+        }
+    };
+
+    if parsed.csv_text.len() > CSV_MAX_BYTES {
+        return json_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            serde_json::json!({"ok": false, "error": "payload_too_large", "message": "csv_text too large"}),
+        );
+    }
+
+    let columns = csv_headers(&parsed.csv_text).and_then(|headers| {
+        resolve_csv_columns(&headers, &csv_options).map(|cols| cols.mapping(&headers))
+    });
+    let rows = columns
+        .clone()
+        .and_then(|_| parse_csv_metrics(&parsed.csv_text, &csv_options));
+    let (columns, (rows, skipped)) = match (columns, rows) {
+        (Ok(columns), Ok(rows)) => (columns, rows),
+        (Err(err), _) | (_, Err(err)) => {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_csv", "message": err}),
+            );
+        }
+    };
+
+    let (rows, future_dated_rows) =
+        drop_future_dated_rows(rows, Utc::now().date_naive(), CSV_FUTURE_DATE_SKEW_DAYS);
+    let sample: Vec<serde_json::Value> = rows
+        .iter()
+        .take(CSV_PREVIEW_SAMPLE_ROWS)
+        .map(|row| {
+            serde_json::json!({
+              "date": row.dt.to_string(),
+              "video_id": row.video_id,
+              "views": row.views,
+              "impressions": row.impressions,
+              "impressions_ctr": row.impressions_ctr,
+              "revenue_usd": row.estimated_revenue_usd,
+            })
+        })
+        .collect();
+
+    json_response(
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "preview": true,
+          "columns": columns,
+          "sample_rows": sample,
+          "csv_stats": csv_upload_stats(&rows, future_dated_rows, &skipped),
+          "limit_error": check_csv_upload_limits(&rows, CsvUploadLimits::from_env())
+            .err()
+            .map(|(code, message, count)| serde_json::json!({"error": code, "message": message, "count": count})),
+        }),
+    )
+}
+
+#[derive(Deserialize)]
+struct UploadCsvRequest {
+    tenant_id: String,
+    channel_id: Option<String>,
+    filename: String,
+    csv_text: String,
+    #[serde(default)]
+    date_formats: Vec<String>,
+    #[serde(default)]
+    locale_hint: Option<String>,
+    /// Fail the upload when more rows than this are future-dated (default: drop them and continue).
+    #[serde(default)]
+    max_future_rows: Option<i64>,
+    #[serde(default)]
+    reconstruction: Option<String>,
+}
+
+async fn handle_youtube_upload_csv(
+    method: &Method,
+    headers: &HeaderMap,
+    body: Bytes,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::POST {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let parsed: UploadCsvRequest = serde_json::from_slice(&body).map_err(|e| -> Error {
+        Box::new(std::io::Error::other(format!("invalid json body: {e}")))
+    })?;
+
+    if parsed.tenant_id.trim().is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+        );
+    }
+    if parsed.filename.trim().is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "filename is required"}),
+        );
+    }
+
+    let csv_options = match csv_parse_options_for_request(
+        parsed.locale_hint.as_deref(),
+        &parsed.date_formats,
+        parsed.reconstruction.as_deref(),
+    ) {
+        Ok(v) => v,
+        Err(message) => {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": message}),
+            );
+        }
+    };
+
+    // Guardrail: keep this endpoint safe for MVP use.
+    if parsed.csv_text.len() > CSV_MAX_BYTES {
+        return json_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            serde_json::json!({"ok": false, "error": "payload_too_large", "message": "csv_text too large"}),
+        );
+    }
+
+    let pool = get_pool().await?;
+    let tenant_id = parsed.tenant_id.trim();
+    let channel_id = match parsed
+        .channel_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+    {
+        Some(v) => v.to_string(),
+        None => fetch_youtube_channel_id(pool, tenant_id)
+            .await?
+            .unwrap_or_default(),
+    };
+
+    if channel_id.trim().is_empty() {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "No active YouTube channel for this tenant"}),
+        );
+    }
+
+    let stored_options = StoredCsvParseOptions {
+        locale_hint: parsed.locale_hint.clone(),
+        date_formats: parsed.date_formats.clone(),
+        max_future_rows: parsed.max_future_rows,
+        reconstruction: parsed.reconstruction.clone(),
+    };
+    let insert = sqlx::query(
+        r#"
+      INSERT INTO yt_csv_uploads (tenant_id, channel_id, filename, status, csv_text, parse_options_json)
+      VALUES (?, ?, ?, 'received', ?, ?);
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id.trim())
+    .bind(parsed.filename.trim())
+    .bind(csv_text_for_storage(&parsed.csv_text, csv_store_max_bytes()))
+    .bind(serde_json::to_string(&stored_options).ok())
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    let upload_id = insert.last_insert_id() as i64;
+
+    ingest_csv_upload(
+        pool,
+        tenant_id,
+        channel_id.trim(),
+        upload_id,
+        &parsed.csv_text,
+        &csv_options,
+        parsed.max_future_rows,
+    )
+    .await
+}
+
+/// Parses `csv_text` into the upload's channel and records the outcome on `yt_csv_uploads`;
+/// shared by fresh uploads and reprocessing of a stored upload.
+async fn ingest_csv_upload(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    upload_id: i64,
+    csv_text: &str,
+    csv_options: &CsvParseOptions,
+    max_future_rows: Option<i64>,
+) -> Result<Response<ResponseBody>, Error> {
+    let (parsed_rows, skipped) = match parse_csv_metrics(csv_text, csv_options) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            sqlx::query(
+                r#"
+          UPDATE yt_csv_uploads
+          SET status = 'error',
+              error = ?,
+              updated_at = CURRENT_TIMESTAMP(3)
+          WHERE id = ? AND tenant_id = ? AND channel_id = ?;
+        "#,
+            )
+            .bind(&err)
+            .bind(upload_id)
+            .bind(tenant_id)
+            .bind(channel_id)
+            .execute(pool)
+            .await
+            .map_err(|e| -> Error { Box::new(e) })?;
+
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_csv", "message": err}),
+            );
+        }
+    };
+
+    let (parsed_rows, future_dated_rows) = drop_future_dated_rows(
+        parsed_rows,
+        Utc::now().date_naive(),
+        CSV_FUTURE_DATE_SKEW_DAYS,
+    );
+    if let Some(max_future) = max_future_rows.filter(|v| *v >= 0) {
+        if future_dated_rows > max_future {
+            let err = format!(
+                "{future_dated_rows} rows are dated in the future (max_future_rows={max_future})"
+            );
+            sqlx::query(
+                r#"
+          UPDATE yt_csv_uploads
+          SET status = 'error',
+              error = ?,
+              updated_at = CURRENT_TIMESTAMP(3)
+          WHERE id = ? AND tenant_id = ? AND channel_id = ?;
+        "#,
+            )
+            .bind(&err)
+            .bind(upload_id)
+            .bind(tenant_id)
+            .bind(channel_id)
+            .execute(pool)
+            .await
+            .map_err(|e| -> Error { Box::new(e) })?;
+
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_csv", "message": err, "future_dated_rows": future_dated_rows}),
+            );
+        }
+    }
+
+    if let Err((code, err, count)) =
+        check_csv_upload_limits(&parsed_rows, CsvUploadLimits::from_env())
+    {
+        sqlx::query(
+            r#"
+          UPDATE yt_csv_uploads
+          SET status = 'error',
+              error = ?,
+              updated_at = CURRENT_TIMESTAMP(3)
+          WHERE id = ? AND tenant_id = ? AND channel_id = ?;
+        "#,
+        )
+        .bind(&err)
+        .bind(upload_id)
+        .bind(tenant_id)
+        .bind(channel_id)
+        .execute(pool)
+        .await
+        .map_err(|e| -> Error { Box::new(e) })?;
+
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": code, "message": err, "count": count}),
+        );
+    }
+
+    let csv_stats = csv_upload_stats(&parsed_rows, future_dated_rows, &skipped);
+
+    for row in parsed_rows.iter() {
+        upsert_video_daily_metric(
+            pool,
+            tenant_id,
+            channel_id,
+            row.dt,
+            &row.video_id,
+            row.estimated_revenue_usd,
+            row.impressions,
+            row.impressions_ctr,
+            row.views,
+        )
+        .await?;
+    }
+
+    sqlx::query(
+        r#"
+      UPDATE yt_csv_uploads
+      SET status = 'parsed',
+          rows_parsed = ?,
+          error = NULL,
+          updated_at = CURRENT_TIMESTAMP(3)
+      WHERE id = ? AND tenant_id = ? AND channel_id = ?;
+    "#,
+    )
+    .bind(parsed_rows.len() as i64)
+    .bind(upload_id)
+    .bind(tenant_id)
+    .bind(channel_id)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    // CSV is often used when revenue/RPM metrics are blocked; evaluate guardrails immediately.
+    let eval_error = match evaluate_youtube_alerts(pool, tenant_id, channel_id).await {
+        Ok(()) => None,
+        Err(err) => Some(truncate_string(&err.to_string(), 2000)),
+    };
+    // A CSV exported for the wrong channel shows up as CSV vs API disagreement on the same dates.
+    let eval_error = match evaluate_source_divergence_alert(pool, tenant_id, channel_id).await {
+        Ok(()) => eval_error,
+        Err(err) => eval_error.or_else(|| Some(truncate_string(&err.to_string(), 2000))),
+    };
+
+    json_response(
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "upload_id": format!("upload_{upload_id}"),
+          "rows_parsed": parsed_rows.len(),
+          "channel_id": channel_id,
+          "eval_error": eval_error,
+          "csv_stats": csv_stats
+        }),
+    )
+}
+
+#[derive(Deserialize)]
+struct ReprocessCsvRequest {
+    tenant_id: String,
+    upload_id: String,
+    /// Override the stored options (e.g. fix a wrong locale); omitted fields keep the stored value.
+    #[serde(default)]
+    locale_hint: Option<String>,
+    #[serde(default)]
+    date_formats: Option<Vec<String>>,
+    #[serde(default)]
+    reconstruction: Option<String>,
+}
+
+async fn handle_youtube_upload_csv_reprocess(
+    method: &Method,
+    headers: &HeaderMap,
+    body: Bytes,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::POST {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let parsed: ReprocessCsvRequest = serde_json::from_slice(&body).map_err(|e| -> Error {
+        Box::new(std::io::Error::other(format!("invalid json body: {e}")))
+    })?;
+
+    let tenant_id = parsed.tenant_id.trim();
+    if tenant_id.is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+        );
+    }
+    let Some(upload_id) = parse_prefixed_id(&parsed.upload_id, "upload_") else {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "invalid upload_id"}),
+        );
+    };
+
+    let pool = get_pool().await?;
+    let row = sqlx::query_as::<_, (String, Option<String>, Option<String>)>(
+        r#"
+      SELECT channel_id, csv_text, parse_options_json
+      FROM yt_csv_uploads
+      WHERE id = ? AND tenant_id = ?
+      LIMIT 1;
+    "#,
+    )
+    .bind(upload_id)
+    .bind(tenant_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    let Some((channel_id, csv_text, options_json)) = row else {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_found"}),
+        );
+    };
+    let Some(csv_text) = csv_text else {
+        return json_response(
+            StatusCode::CONFLICT,
+            serde_json::json!({
+              "ok": false,
+              "error": "conflict",
+              "message": "csv_text was not stored for this upload (older upload or above CSV_STORE_MAX_BYTES); upload the file again",
+            }),
+        );
+    };
+
+    let mut options = StoredCsvParseOptions::from_json(options_json.as_deref());
+    if let Some(locale_hint) = parsed.locale_hint {
+        options.locale_hint = Some(locale_hint);
+    }
+    if let Some(date_formats) = parsed.date_formats {
+        options.date_formats = date_formats;
+    }
+    if let Some(reconstruction) = parsed.reconstruction {
+        options.reconstruction = Some(reconstruction);
+    }
+    let csv_options = match csv_parse_options_for_request(
+        options.locale_hint.as_deref(),
+        &options.date_formats,
+        options.reconstruction.as_deref(),
+    ) {
+        Ok(v) => v,
+        Err(message) => {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": message}),
+            );
+        }
+    };
+
+    sqlx::query(
+        r#"
+      UPDATE yt_csv_uploads
+      SET status = 'received',
+          parse_options_json = ?,
+          updated_at = CURRENT_TIMESTAMP(3)
+      WHERE id = ? AND tenant_id = ?;
+    "#,
+    )
+    .bind(serde_json::to_string(&options).ok())
+    .bind(upload_id)
+    .bind(tenant_id)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    ingest_csv_upload(
+        pool,
+        tenant_id,
+        &channel_id,
+        upload_id,
+        &csv_text,
+        &csv_options,
+        options.max_future_rows,
+    )
+    .await
+}
+
+#[derive(Deserialize)]
+struct PurgeMetricsRequest {
+    tenant_id: String,
+    #[serde(default)]
+    channel_id: Option<String>,
+    start_dt: String,
+    end_dt: String,
+    #[serde(default)]
+    video_id: Option<String>,
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    confirm: bool,
+}
+
+/// Which rows of a day a purge touches: the channel-total sentinels written by CSV uploads or the
+/// Analytics fallback, or the real per-video rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MetricsPurgeSource {
+    All,
+    Csv,
+    ApiChannelTotal,
+    Videos,
+}
+
+impl MetricsPurgeSource {
+    fn from_param(v: Option<&str>) -> Option<Self> {
+        match v.map(str::trim).filter(|v| !v.is_empty()) {
+            None | Some("all") => Some(MetricsPurgeSource::All),
+            Some("csv") => Some(MetricsPurgeSource::Csv),
+            Some("api_channel_total") => Some(MetricsPurgeSource::ApiChannelTotal),
+            Some("videos") => Some(MetricsPurgeSource::Videos),
+            Some(_) => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            MetricsPurgeSource::All => "all",
+            MetricsPurgeSource::Csv => "csv",
+            MetricsPurgeSource::ApiChannelTotal => "api_channel_total",
+            MetricsPurgeSource::Videos => "videos",
+        }
+    }
+
+    fn sql_filter(self) -> String {
+        match self {
+            MetricsPurgeSource::All => String::new(),
+            MetricsPurgeSource::Csv => format!(" AND video_id = '{CSV_CHANNEL_TOTAL_VIDEO_ID}'"),
+            MetricsPurgeSource::ApiChannelTotal => {
+                format!(" AND video_id = '{API_CHANNEL_TOTAL_VIDEO_ID}'")
+            }
+            MetricsPurgeSource::Videos => format!(" AND {}", video_rows_filter()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MetricsPurgeScope {
+    start_dt: NaiveDate,
+    end_dt: NaiveDate,
+    video_id: Option<String>,
+    source: MetricsPurgeSource,
+}
+
+impl MetricsPurgeScope {
+    /// Binds, in order: tenant_id, channel_id, start_dt, end_dt, then video_id when scoped.
+    fn delete_sql(&self) -> String {
+        format!(
+            "DELETE FROM video_daily_metrics WHERE tenant_id = ? AND channel_id = ? AND dt BETWEEN ? AND ?{}{};",
+            if self.video_id.is_some() {
+                " AND video_id = ?"
+            } else {
+                ""
+            },
+            self.source.sql_filter()
+        )
+    }
+}
+
+async fn handle_youtube_metrics_purge(
+    method: &Method,
+    headers: &HeaderMap,
+    body: Bytes,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::POST {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let parsed: PurgeMetricsRequest = serde_json::from_slice(&body).map_err(|e| -> Error {
+        Box::new(std::io::Error::other(format!("invalid json body: {e}")))
+    })?;
+
+    let tenant_id = parsed.tenant_id.trim();
+    if tenant_id.is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+        );
+    }
+
+    let (Some(start_dt), Some(end_dt)) = (parse_dt(&parsed.start_dt), parse_dt(&parsed.end_dt))
+    else {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "start_dt and end_dt must be YYYY-MM-DD"}),
+        );
+    };
+    if start_dt > end_dt {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "start_dt must be on or before end_dt"}),
+        );
+    }
+
+    let Some(source) = MetricsPurgeSource::from_param(parsed.source.as_deref()) else {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "source must be all|csv|api_channel_total|videos"}),
+        );
+    };
+
+    // Deletes are irreversible; callers must opt in explicitly.
+    if !parsed.confirm {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "confirm_required", "message": "Set confirm=true to delete metrics"}),
+        );
+    }
+
+    let scope = MetricsPurgeScope {
+        start_dt,
+        end_dt,
+        video_id: parsed
+            .video_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string),
+        source,
+    };
+
+    let pool = get_pool().await?;
+    let channel_id = match parsed
+        .channel_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+    {
+        Some(v) => v.to_string(),
+        None => fetch_youtube_channel_id(pool, tenant_id)
+            .await?
+            .unwrap_or_default(),
+    };
+
+    if channel_id.trim().is_empty() {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "No active YouTube channel for this tenant"}),
+        );
+    }
+
+    // The watermark moves back with the delete, or the incremental sync would treat the purged
+    // days as already stored.
+    let mut tx = pool.begin().await.map_err(|e| -> Error { Box::new(e) })?;
+    let sql = scope.delete_sql();
+    let mut query = sqlx::query(&sql)
+        .bind(tenant_id)
+        .bind(channel_id.trim())
+        .bind(scope.start_dt)
+        .bind(scope.end_dt);
+    if let Some(video_id) = scope.video_id.as_deref() {
+        query = query.bind(video_id);
+    }
+    let res = query
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| -> Error { Box::new(e) })?;
+    rewind_youtube_sync_for_purge(
+        &mut tx,
+        tenant_id,
+        channel_id.trim(),
+        scope.start_dt,
+        scope.end_dt,
+    )
+    .await?;
+    tx.commit().await.map_err(|e| -> Error { Box::new(e) })?;
+
+    json_response(
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "channel_id": channel_id,
+          "start_dt": scope.start_dt.to_string(),
+          "end_dt": scope.end_dt.to_string(),
+          "video_id": scope.video_id,
+          "source": scope.source.as_str(),
+          "rows_deleted": res.rows_affected()
+        }),
+    )
+}
+
+#[derive(Deserialize)]
+struct RebuildChannelTotalsRequest {
+    tenant_id: String,
+    #[serde(default)]
+    channel_id: Option<String>,
+    start_dt: String,
+    end_dt: String,
+}
+
+/// Longest window one rebuild call sums, so a single request stays a bounded scan.
+const CHANNEL_TOTALS_REBUILD_MAX_DAYS: i64 = 366;
+
+async fn handle_youtube_channel_totals_rebuild(
+    method: &Method,
+    headers: &HeaderMap,
+    body: Bytes,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::POST {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let parsed: RebuildChannelTotalsRequest =
+        serde_json::from_slice(&body).map_err(|e| -> Error {
+            Box::new(std::io::Error::other(format!("invalid json body: {e}")))
+        })?;
+
+    let tenant_id = parsed.tenant_id.trim();
+    if tenant_id.is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+        );
+    }
+
+    let (Some(start_dt), Some(end_dt)) = (parse_dt(&parsed.start_dt), parse_dt(&parsed.end_dt))
+    else {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "start_dt and end_dt must be YYYY-MM-DD"}),
+        );
+    };
+    if start_dt > end_dt {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "start_dt must be on or before end_dt"}),
+        );
+    }
+    if (end_dt - start_dt).num_days() + 1 > CHANNEL_TOTALS_REBUILD_MAX_DAYS {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": format!("range must span at most {CHANNEL_TOTALS_REBUILD_MAX_DAYS} days")}),
+        );
+    }
+
+    let pool = get_pool().await?;
+    let channel_id = match parsed
+        .channel_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+    {
+        Some(v) => v.to_string(),
+        None => fetch_youtube_channel_id(pool, tenant_id)
+            .await?
+            .unwrap_or_default(),
+    };
+
+    if channel_id.trim().is_empty() {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "No active YouTube channel for this tenant"}),
+        );
+    }
+
+    let authoritative: std::collections::BTreeSet<NaiveDate> =
+        fetch_authoritative_channel_total_dts(pool, tenant_id, channel_id.trim(), start_dt, end_dt)
+            .await?
+            .into_iter()
+            .collect();
+    let rows =
+        fetch_video_daily_metric_rows(pool, tenant_id, channel_id.trim(), start_dt, end_dt).await?;
+    let derived = derive_channel_totals(&rows, &authoritative);
+    for chunk in derived.chunks(METRIC_UPSERT_BATCH_SIZE) {
+        upsert_derived_channel_totals(pool, tenant_id, channel_id.trim(), chunk).await?;
+    }
+
+    json_response(
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "channel_id": channel_id,
+          "start_dt": start_dt.to_string(),
+          "end_dt": end_dt.to_string(),
+          "days_written": derived.len(),
+          "days_skipped_authoritative": authoritative.len(),
+          "dts": derived.iter().map(|row| row.dt.to_string()).collect::<Vec<_>>()
+        }),
+    )
+}
+
+#[derive(serde::Serialize)]
+struct AlertItem {
+    id: String,
+    kind: String,
+    severity: String,
+    message: String,
+    details: Option<serde_json::Value>,
+    detected_at: String,
+    resolved_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ResolveAlertRequest {
+    tenant_id: String,
+    id: String,
+    #[serde(default)]
+    note: Option<String>,
+    #[serde(default)]
+    action: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BulkResolveAlertsRequest {
+    tenant_id: String,
+    #[serde(default)]
+    channel_id: Option<String>,
+    #[serde(default)]
+    kind: Option<String>,
+    #[serde(default)]
+    alert_key: Option<String>,
+    #[serde(default)]
+    note: Option<String>,
+    #[serde(default)]
+    action: Option<String>,
+}
+
+/// Bulk resolution targets exactly one of `alert_key` (a single guardrail) or `kind` (a category).
+fn bulk_resolve_alerts_selector<'a>(
+    kind: Option<&'a str>,
+    alert_key: Option<&'a str>,
+) -> Option<(&'static str, &'a str)> {
+    let kind = kind.map(str::trim).filter(|v| !v.is_empty());
+    let alert_key = alert_key.map(str::trim).filter(|v| !v.is_empty());
+    match (kind, alert_key) {
+        (None, Some(key)) => Some(("alert_key", key)),
+        (Some(kind), None) => Some(("kind", kind)),
+        _ => None,
+    }
+}
+
+fn bulk_resolve_alerts_sql(column: &str) -> &'static str {
+    if column == "alert_key" {
+        r#"
+      UPDATE yt_alerts
+      SET resolved_at = CURRENT_TIMESTAMP(3),
+          updated_at = CURRENT_TIMESTAMP(3)
+      WHERE tenant_id = ? AND channel_id = ?
+        AND alert_key = ?
+        AND resolved_at IS NULL;
+    "#
+    } else {
+        r#"
+      UPDATE yt_alerts
+      SET resolved_at = CURRENT_TIMESTAMP(3),
+          updated_at = CURRENT_TIMESTAMP(3)
+      WHERE tenant_id = ? AND channel_id = ?
+        AND kind = ?
+        AND resolved_at IS NULL;
+    "#
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AlertStatusFilter {
+    Active,
+    Resolved,
+    All,
+}
+
+impl AlertStatusFilter {
+    fn from_query(value: Option<&str>) -> Option<Self> {
+        match value.map(str::trim).unwrap_or("") {
+            "" | "all" => Some(AlertStatusFilter::All),
+            "active" => Some(AlertStatusFilter::Active),
+            "resolved" => Some(AlertStatusFilter::Resolved),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            AlertStatusFilter::Active => "active",
+            AlertStatusFilter::Resolved => "resolved",
+            AlertStatusFilter::All => "all",
+        }
+    }
+}
+
+type AlertListRow = (
+    i64,
+    String,
+    String,
+    String,
+    DateTime<Utc>,
+    Option<DateTime<Utc>>,
+    Option<String>,
+);
+
+/// The `LIMIT ? OFFSET ?` page of the channel's alerts matching `status` and updated after
+/// `since`. Active alerts list newest detected first, resolved ones most recently resolved
+/// first, and `all` puts active before resolved; `id` breaks ties so pages never overlap.
+fn alerts_list_sql(status: AlertStatusFilter) -> &'static str {
+    match status {
+        AlertStatusFilter::Active => {
+            r#"
+          SELECT id, kind, severity, message,
+                 CAST(detected_at AS DATETIME) AS detected_at,
+                 CAST(resolved_at AS DATETIME) AS resolved_at,
+                 details_json
+          FROM yt_alerts
+          WHERE tenant_id = ? AND channel_id = ?
+            AND resolved_at IS NULL
+            AND (? IS NULL OR updated_at > ?)
+          ORDER BY detected_at DESC, id DESC
+          LIMIT ? OFFSET ?;
+        "#
+        }
+        AlertStatusFilter::Resolved => {
+            r#"
+          SELECT id, kind, severity, message,
+                 CAST(detected_at AS DATETIME) AS detected_at,
+                 CAST(resolved_at AS DATETIME) AS resolved_at,
+                 details_json
+          FROM yt_alerts
+          WHERE tenant_id = ? AND channel_id = ?
+            AND resolved_at IS NOT NULL
+            AND (? IS NULL OR updated_at > ?)
+          ORDER BY resolved_at DESC, id DESC
+          LIMIT ? OFFSET ?;
+        "#
+        }
+        AlertStatusFilter::All => {
+            r#"
+          SELECT id, kind, severity, message,
+                 CAST(detected_at AS DATETIME) AS detected_at,
+                 CAST(resolved_at AS DATETIME) AS resolved_at,
+                 details_json
+          FROM yt_alerts
+          WHERE tenant_id = ? AND channel_id = ?
+            AND (? IS NULL OR updated_at > ?)
+          ORDER BY resolved_at IS NOT NULL, detected_at DESC, id DESC
+          LIMIT ? OFFSET ?;
+        "#
+        }
+    }
+}
+
+/// `since` (RFC3339) for incremental list polling: `Ok(None)` when absent, `Err(())` when it
+/// isn't a valid timestamp.
+fn parse_since_param(value: Option<&str>) -> Result<Option<DateTime<Utc>>, ()> {
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        None => Ok(None),
+        Some(raw) => DateTime::parse_from_rfc3339(raw)
+            .map(|dt| Some(dt.with_timezone(&Utc)))
+            .map_err(|_| ()),
+    }
+}
+
+fn parse_prefixed_id(raw: &str, prefix: &str) -> Option<i64> {
+    let s = raw.trim();
+    let s = s.strip_prefix(prefix).unwrap_or(s);
+    s.parse::<i64>().ok()
+}
+
+fn datetime_to_rfc3339_utc(dt: DateTime<Utc>) -> String {
+    dt.to_rfc3339()
+}
+
+async fn handle_youtube_alerts(
+    method: &Method,
+    headers: &HeaderMap,
+    uri: &Uri,
+    body: Option<Bytes>,
+) -> Result<Response<ResponseBody>, Error> {
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    if method == Method::GET {
+        let tenant_id = get_query_param(uri, "tenant_id").unwrap_or_default();
+        if tenant_id.trim().is_empty() {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+            );
+        }
+
+        let pool = get_pool().await?;
+        let channel_id = match get_query_param(uri, "channel_id")
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
         {
             Some(v) => v,
             None => fetch_youtube_channel_id(pool, tenant_id.trim())
@@ -4485,36 +9198,42 @@ async fn handle_youtube_alerts(
             );
         }
 
-        // Alerts are evaluated by the daily sync job; reads should stay fast.
-        let eval_error: Option<String> = None;
-
-        let rows = match sqlx::query_as::<
-            _,
-            (
-                i64,
-                String,
-                String,
-                String,
-                DateTime<Utc>,
-                Option<DateTime<Utc>>,
-                Option<String>,
-            ),
-        >(
-            r#"
-	          SELECT id, kind, severity, message,
-	                 CAST(detected_at AS DATETIME) AS detected_at,
-	                 CAST(resolved_at AS DATETIME) AS resolved_at,
-	                 details_json
-	          FROM yt_alerts
-	          WHERE tenant_id = ? AND channel_id = ?
-	          ORDER BY (resolved_at IS NULL) DESC, detected_at DESC
-          LIMIT 50;
-        "#,
-        )
-        .bind(tenant_id.trim())
-        .bind(channel_id.trim())
-        .fetch_all(pool)
-        .await
+        let Some(status) = AlertStatusFilter::from_query(get_query_param(uri, "status").as_deref())
+        else {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": "status must be active|resolved|all"}),
+            );
+        };
+        let limit = get_query_param(uri, "limit")
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .unwrap_or(50)
+            .clamp(1, 200);
+        let offset = get_query_param(uri, "offset")
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .unwrap_or(0)
+            .max(0);
+        let Ok(since) = parse_since_param(get_query_param(uri, "since").as_deref()) else {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": "since must be an RFC3339 timestamp"}),
+            );
+        };
+        // Taken before the read so rows updated while it runs are picked up by the next poll.
+        let next_since = Utc::now();
+
+        // Alerts are evaluated by the daily sync job; reads should stay fast.
+        let eval_error: Option<String> = None;
+
+        let rows = match sqlx::query_as::<_, AlertListRow>(alerts_list_sql(status))
+            .bind(tenant_id.trim())
+            .bind(channel_id.trim())
+            .bind(since)
+            .bind(since)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(pool)
+            .await
         {
             Ok(v) => v,
             Err(e) => {
@@ -4550,7 +9269,17 @@ async fn handle_youtube_alerts(
 
         return json_response(
             StatusCode::OK,
-            serde_json::json!({"ok": true, "items": items, "channel_id": channel_id, "eval_error": eval_error}),
+            serde_json::json!({
+              "ok": true,
+              "items": items,
+              "channel_id": channel_id,
+              "eval_error": eval_error,
+              "status": status.as_str(),
+              "limit": limit,
+              "offset": offset,
+              "since": since.map(datetime_to_rfc3339_utc),
+              "next_since": datetime_to_rfc3339_utc(next_since),
+            }),
         );
     }
 
@@ -4562,7 +9291,92 @@ async fn handle_youtube_alerts(
             );
         };
 
-        let parsed: ResolveAlertRequest = serde_json::from_slice(&body).map_err(|e| -> Error {
+        let v: serde_json::Value = serde_json::from_slice(&body).map_err(|e| -> Error {
+            Box::new(std::io::Error::other(format!("invalid json body: {e}")))
+        })?;
+
+        if v.get("id").is_none() && (v.get("kind").is_some() || v.get("alert_key").is_some()) {
+            let parsed: BulkResolveAlertsRequest =
+                serde_json::from_value(v).map_err(|e| -> Error {
+                    Box::new(std::io::Error::other(format!(
+                        "invalid bulk resolve body: {e}"
+                    )))
+                })?;
+
+            let tenant_id = parsed.tenant_id.trim();
+            if tenant_id.is_empty() {
+                return json_response(
+                    StatusCode::BAD_REQUEST,
+                    serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+                );
+            }
+
+            let Some((column, value)) =
+                bulk_resolve_alerts_selector(parsed.kind.as_deref(), parsed.alert_key.as_deref())
+            else {
+                return json_response(
+                    StatusCode::BAD_REQUEST,
+                    serde_json::json!({"ok": false, "error": "bad_request", "message": "exactly one of kind or alert_key is required"}),
+                );
+            };
+
+            let pool = get_pool().await?;
+            let channel_id = match parsed
+                .channel_id
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+            {
+                Some(v) => v.to_string(),
+                None => fetch_youtube_channel_id(pool, tenant_id)
+                    .await?
+                    .unwrap_or_default(),
+            };
+
+            if channel_id.trim().is_empty() {
+                return json_response(
+                    StatusCode::NOT_FOUND,
+                    serde_json::json!({"ok": false, "error": "not_connected", "message": "No active YouTube channel for this tenant"}),
+                );
+            }
+
+            let resolved = sqlx::query(bulk_resolve_alerts_sql(column))
+                .bind(tenant_id)
+                .bind(channel_id.trim())
+                .bind(value)
+                .execute(pool)
+                .await
+                .map_err(|e| -> Error { Box::new(e) })?
+                .rows_affected();
+
+            if resolved > 0 {
+                let meta_json = serde_json::json!({
+                  column: value,
+                  "resolved": resolved,
+                  "handled_at": Utc::now().to_rfc3339(),
+                  "action": parsed.action.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(|v| truncate_string(v, 80)),
+                  "note": parsed.note.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(|v| truncate_string(v, 600)),
+                })
+                .to_string();
+                let action_type = truncate_string(&format!("resolve_alerts:{column}:{value}"), 64);
+                let _ = upsert_observed_action(
+                    pool,
+                    tenant_id,
+                    channel_id.trim(),
+                    Utc::now().date_naive(),
+                    &action_type,
+                    Some(&meta_json),
+                )
+                .await;
+            }
+
+            return json_response(
+                StatusCode::OK,
+                serde_json::json!({"ok": true, "resolved": resolved, "channel_id": channel_id}),
+            );
+        }
+
+        let parsed: ResolveAlertRequest = serde_json::from_value(v).map_err(|e| -> Error {
             Box::new(std::io::Error::other(format!("invalid json body: {e}")))
         })?;
 
@@ -4629,88 +9443,373 @@ async fn handle_youtube_alerts(
                 None => serde_json::json!({}),
             };
 
-            if !details_val.is_object() {
-                details_val = serde_json::json!({ "evidence": details_val });
-            }
+            if !details_val.is_object() {
+                details_val = serde_json::json!({ "evidence": details_val });
+            }
+
+            if let Some(obj) = details_val.as_object_mut() {
+                let mut handled = serde_json::Map::new();
+                handled.insert(
+                    "at".to_string(),
+                    serde_json::Value::String(handled_at.clone()),
+                );
+                if let Some(a) = action.as_deref() {
+                    handled.insert(
+                        "action".to_string(),
+                        serde_json::Value::String(a.to_string()),
+                    );
+                }
+                if let Some(n) = note.as_deref() {
+                    handled.insert("note".to_string(), serde_json::Value::String(n.to_string()));
+                }
+                obj.insert("handled".to_string(), serde_json::Value::Object(handled));
+            }
+
+            serde_json::to_string(&details_val).ok()
+        };
+
+        let details_json_to_write = updated_details_json
+            .as_deref()
+            .or(existing_details_json.as_deref());
+
+        let updated = sqlx::query(
+            r#"
+        UPDATE yt_alerts
+        SET resolved_at = CURRENT_TIMESTAMP(3),
+            details_json = ?,
+            updated_at = CURRENT_TIMESTAMP(3)
+        WHERE id = ? AND tenant_id = ?;
+      "#,
+        )
+        .bind(details_json_to_write)
+        .bind(alert_id)
+        .bind(parsed.tenant_id.trim())
+        .execute(pool)
+        .await
+        .map_err(|e| -> Error { Box::new(e) })?;
+
+        if updated.rows_affected() > 0 {
+            let dt = Utc::now().date_naive();
+            let meta_json = serde_json::json!({
+              "alert_id": parsed.id,
+              "alert_key": alert_key,
+              "handled_at": handled_at,
+              "action": action,
+              "note": note,
+            })
+            .to_string();
+            let action_type = format!("resolve_alert:{alert_id}");
+            let _ = sqlx::query(
+                r#"
+            INSERT INTO observed_actions (tenant_id, channel_id, dt, action_type, action_meta_json)
+            VALUES (?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+              action_meta_json = VALUES(action_meta_json);
+          "#,
+            )
+            .bind(parsed.tenant_id.trim())
+            .bind(channel_id)
+            .bind(dt)
+            .bind(action_type)
+            .bind(meta_json)
+            .execute(pool)
+            .await;
+        }
+
+        return json_response(
+            StatusCode::OK,
+            serde_json::json!({"ok": true, "updated": updated.rows_affected() > 0}),
+        );
+    }
+
+    json_response(
+        StatusCode::METHOD_NOT_ALLOWED,
+        serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+    )
+}
+
+const ALERT_TEMPLATE_MAX_CHARS: usize = 2000;
+
+#[derive(Deserialize)]
+struct AlertTemplatePutRequest {
+    tenant_id: String,
+    #[serde(default)]
+    channel_id: Option<String>,
+    alert_key: String,
+    /// `null` or empty removes the template so the built-in message is used again.
+    #[serde(default)]
+    template: Option<String>,
+}
+
+async fn handle_youtube_alert_templates(
+    method: &Method,
+    headers: &HeaderMap,
+    uri: &Uri,
+    body: Option<Bytes>,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::GET && method != Method::POST {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let (tenant_id, channel_id, put) = if method == Method::POST {
+        let Some(body) = body else {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": "missing body"}),
+            );
+        };
+        let parsed: AlertTemplatePutRequest =
+            serde_json::from_slice(&body).map_err(|e| -> Error {
+                Box::new(std::io::Error::other(format!("invalid json body: {e}")))
+            })?;
+
+        let alert_key = parsed.alert_key.trim().to_string();
+        if alert_key.is_empty() || alert_key.len() > 64 {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": "alert_key is required (max 64 chars)"}),
+            );
+        }
+        let template = parsed
+            .template
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+        if template
+            .as_deref()
+            .is_some_and(|v| v.chars().count() > ALERT_TEMPLATE_MAX_CHARS)
+        {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": format!("template must be at most {ALERT_TEMPLATE_MAX_CHARS} chars")}),
+            );
+        }
+        (
+            parsed.tenant_id.trim().to_string(),
+            parsed.channel_id,
+            Some((alert_key, template)),
+        )
+    } else {
+        (
+            get_query_param(uri, "tenant_id")
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+            get_query_param(uri, "channel_id"),
+            None,
+        )
+    };
+
+    if tenant_id.is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+        );
+    }
+
+    let pool = get_pool().await?;
+    let channel_id = match channel_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+    {
+        Some(v) => v.to_string(),
+        None => fetch_youtube_channel_id(pool, &tenant_id)
+            .await?
+            .unwrap_or_default(),
+    };
+
+    if channel_id.trim().is_empty() {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({"ok": false, "error": "not_connected", "message": "No active YouTube channel for this tenant"}),
+        );
+    }
+
+    if let Some((alert_key, template)) = put {
+        let removed = match template.as_deref() {
+            Some(template) => {
+                upsert_alert_template(pool, &tenant_id, &channel_id, &alert_key, template).await?;
+                false
+            }
+            None => delete_alert_template(pool, &tenant_id, &channel_id, &alert_key).await?,
+        };
+        return json_response(
+            StatusCode::OK,
+            serde_json::json!({
+              "ok": true,
+              "channel_id": channel_id,
+              "alert_key": alert_key,
+              "template": template,
+              "removed": removed,
+            }),
+        );
+    }
+
+    let items: Vec<serde_json::Value> = fetch_alert_templates(pool, &tenant_id, &channel_id)
+        .await?
+        .into_iter()
+        .map(|(alert_key, template)| {
+            serde_json::json!({"alert_key": alert_key, "template": template})
+        })
+        .collect();
+
+    json_response(
+        StatusCode::OK,
+        serde_json::json!({"ok": true, "channel_id": channel_id, "items": items}),
+    )
+}
+
+const ALERT_NOTIFICATIONS_DEFAULT_SINCE_HOURS: i64 = 24;
+const ALERT_NOTIFICATIONS_MAX_SINCE_HOURS: i64 = 24 * 7;
+
+#[derive(Deserialize)]
+struct AlertNotificationSettingsPutRequest {
+    tenant_id: String,
+    mode: String,
+    #[serde(default)]
+    digest_window_minutes: Option<i64>,
+}
 
-            if let Some(obj) = details_val.as_object_mut() {
-                let mut handled = serde_json::Map::new();
-                handled.insert(
-                    "at".to_string(),
-                    serde_json::Value::String(handled_at.clone()),
-                );
-                if let Some(a) = action.as_deref() {
-                    handled.insert(
-                        "action".to_string(),
-                        serde_json::Value::String(a.to_string()),
-                    );
-                }
-                if let Some(n) = note.as_deref() {
-                    handled.insert("note".to_string(), serde_json::Value::String(n.to_string()));
-                }
-                obj.insert("handled".to_string(), serde_json::Value::Object(handled));
-            }
+fn alert_notification_settings_json(settings: &AlertNotificationSettings) -> serde_json::Value {
+    serde_json::json!({
+      "mode": settings.mode.as_str(),
+      "digest_window_minutes": settings.digest_window_minutes,
+    })
+}
 
-            serde_json::to_string(&details_val).ok()
-        };
+/// GET: the tenant's notification settings and the notification payloads its open alerts from
+/// the last `since_hours` group into. POST: sets `mode` (`immediate`|`digest`) and the window.
+async fn handle_youtube_alert_notifications(
+    method: &Method,
+    headers: &HeaderMap,
+    uri: &Uri,
+    body: Option<Bytes>,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::GET && method != Method::POST {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
 
-        let details_json_to_write = updated_details_json
-            .as_deref()
-            .or(existing_details_json.as_deref());
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
 
-        let updated = sqlx::query(
-            r#"
-        UPDATE yt_alerts
-        SET resolved_at = CURRENT_TIMESTAMP(3),
-            details_json = ?,
-            updated_at = CURRENT_TIMESTAMP(3)
-        WHERE id = ? AND tenant_id = ?;
-      "#,
-        )
-        .bind(details_json_to_write)
-        .bind(alert_id)
-        .bind(parsed.tenant_id.trim())
-        .execute(pool)
-        .await
-        .map_err(|e| -> Error { Box::new(e) })?;
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
 
-        if updated.rows_affected() > 0 {
-            let dt = Utc::now().date_naive();
-            let meta_json = serde_json::json!({
-              "alert_id": parsed.id,
-              "alert_key": alert_key,
-              "handled_at": handled_at,
-              "action": action,
-              "note": note,
-            })
-            .to_string();
-            let action_type = format!("resolve_alert:{alert_id}");
-            let _ = sqlx::query(
-                r#"
-            INSERT INTO observed_actions (tenant_id, channel_id, dt, action_type, action_meta_json)
-            VALUES (?, ?, ?, ?, ?)
-            ON DUPLICATE KEY UPDATE
-              action_meta_json = VALUES(action_meta_json);
-          "#,
-            )
-            .bind(parsed.tenant_id.trim())
-            .bind(channel_id)
-            .bind(dt)
-            .bind(action_type)
-            .bind(meta_json)
-            .execute(pool)
-            .await;
+    if method == Method::POST {
+        let Some(body) = body else {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": "missing body"}),
+            );
+        };
+        let parsed: AlertNotificationSettingsPutRequest =
+            serde_json::from_slice(&body).map_err(|e| -> Error {
+                Box::new(std::io::Error::other(format!("invalid json body: {e}")))
+            })?;
+        let tenant_id = parsed.tenant_id.trim();
+        if tenant_id.is_empty() {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+            );
         }
-
+        let Some(mode) = AlertNotificationMode::parse(&parsed.mode) else {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": "mode must be immediate|digest"}),
+            );
+        };
+        if parsed
+            .digest_window_minutes
+            .is_some_and(|v| !(1..=MAX_ALERT_DIGEST_WINDOW_MINUTES).contains(&v))
+        {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"ok": false, "error": "bad_request", "message": format!("digest_window_minutes must be 1..={MAX_ALERT_DIGEST_WINDOW_MINUTES}")}),
+            );
+        }
+        let settings = AlertNotificationSettings {
+            mode,
+            digest_window_minutes: parsed
+                .digest_window_minutes
+                .unwrap_or(DEFAULT_ALERT_DIGEST_WINDOW_MINUTES),
+        };
+        let pool = get_pool().await?;
+        upsert_alert_notification_settings(pool, tenant_id, &settings).await?;
         return json_response(
             StatusCode::OK,
-            serde_json::json!({"ok": true, "updated": updated.rows_affected() > 0}),
+            serde_json::json!({"ok": true, "settings": alert_notification_settings_json(&settings)}),
+        );
+    }
+
+    let tenant_id = get_query_param(uri, "tenant_id").unwrap_or_default();
+    if tenant_id.trim().is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
         );
     }
+    let since_hours = get_query_param(uri, "since_hours")
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .unwrap_or(ALERT_NOTIFICATIONS_DEFAULT_SINCE_HOURS)
+        .clamp(1, ALERT_NOTIFICATIONS_MAX_SINCE_HOURS);
+
+    let pool = get_pool().await?;
+    let settings = fetch_alert_notification_settings(pool, tenant_id.trim()).await?;
+    let now = Utc::now();
+    let alerts = fetch_open_alerts_detected_since(
+        pool,
+        tenant_id.trim(),
+        now - Duration::hours(since_hours),
+    )
+    .await?;
+    let notifications: Vec<serde_json::Value> = build_alert_notifications(settings, &alerts, now)
+        .iter()
+        .map(|n| n.to_json())
+        .collect();
 
     json_response(
-        StatusCode::METHOD_NOT_ALLOWED,
-        serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        StatusCode::OK,
+        serde_json::json!({
+          "ok": true,
+          "settings": alert_notification_settings_json(&settings),
+          "since_hours": since_hours,
+          "notifications": notifications,
+        }),
     )
 }
 
@@ -4735,8 +9834,11 @@ struct ExperimentResponse {
     state: String,
     stop_loss_pct: Option<f64>,
     planned_duration_days: Option<i64>,
+    /// Planned duration after the minimum-duration floor; what completion actually waits for.
+    effective_duration_days: Option<i64>,
     started_at: Option<String>,
     ended_at: Option<String>,
+    baseline_note: Option<String>,
     variants: Option<Vec<ExperimentVariantResponse>>,
 }
 
@@ -4749,6 +9851,31 @@ fn parse_video_ids_json(raw: &str) -> Vec<String> {
         .collect()
 }
 
+/// Baseline window for a single-video experiment, moved off days the app changed the video.
+async fn experiment_baseline_for_videos(
+    pool: &sqlx::MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    video_ids: &[String],
+    start_dt: chrono::NaiveDate,
+) -> Result<ExperimentBaselineWindow, Error> {
+    let change_dts = match video_ids {
+        [video_id] => {
+            fetch_video_change_dts(
+                pool,
+                tenant_id,
+                channel_id,
+                video_id,
+                start_dt - Duration::days(EXPERIMENT_BASELINE_DAYS * 2),
+                start_dt - Duration::days(1),
+            )
+            .await?
+        }
+        _ => Vec::new(),
+    };
+    Ok(experiment_baseline_window(start_dt, &change_dts))
+}
+
 fn json_string_field(payload: &serde_json::Value, key: &str) -> Option<String> {
     payload
         .get(key)
@@ -4967,6 +10094,31 @@ mod experiments_tests {
         assert_eq!(a.ctr, Some(0.05));
         assert_eq!(b.ctr, Some(0.06));
     }
+
+    #[test]
+    fn experiments_list_filters_by_state_and_skips_stats_unless_requested() {
+        assert_eq!(parse_experiment_state_filter(None), Some(vec![]));
+        assert_eq!(parse_experiment_state_filter(Some("all")), Some(vec![]));
+        assert_eq!(
+            parse_experiment_state_filter(Some("running, Won,running")),
+            Some(vec!["running", "won"])
+        );
+        assert_eq!(parse_experiment_state_filter(Some("running,bogus")), None);
+
+        let unfiltered = experiments_list_sql(0);
+        assert!(!unfiltered.contains("state IN"));
+        let filtered = experiments_list_sql(2);
+        assert!(filtered.contains("AND state IN (?, ?)"));
+        assert_eq!(filtered.matches('?').count(), 6);
+
+        let started_at: DateTime<Utc> = "2026-01-05T12:00:00Z".parse().unwrap();
+        assert_eq!(experiment_stats_start_dt(false, Some(started_at)), None);
+        assert_eq!(
+            experiment_stats_start_dt(true, Some(started_at)),
+            NaiveDate::from_ymd_opt(2026, 1, 5)
+        );
+        assert_eq!(experiment_stats_start_dt(true, None), None);
+    }
 }
 
 async fn handle_youtube_experiment_get(
@@ -5015,6 +10167,12 @@ async fn handle_youtube_experiment_get(
     };
 
     let pool = get_pool().await?;
+    if let Some(response) =
+        feature_disabled_response(pool, tenant_id.trim(), TenantFeature::Experiments).await?
+    {
+        return Ok(response);
+    }
+
     let row = sqlx::query_as::<
         _,
         (
@@ -5066,14 +10224,25 @@ async fn handle_youtube_experiment_get(
     let video_ids = parse_video_ids_json(&video_ids_json);
     let mut variants = fetch_experiment_variants(pool, id).await?;
 
+    let mut baseline_note: Option<String> = None;
     if let Some(started_at) = started_at {
         let start_dt = started_at.date_naive();
-        let baseline_start_dt = start_dt - Duration::days(7);
-        let baseline_end_dt = start_dt - Duration::days(1);
+        let baseline_window = experiment_baseline_for_videos(
+            pool,
+            tenant_id.trim(),
+            channel_id.trim(),
+            &video_ids,
+            start_dt,
+        )
+        .await?;
+        let baseline_start_dt = baseline_window.start_dt;
+        let baseline_end_dt = baseline_window.end_dt;
+        baseline_note = baseline_window.note;
 
-        let last_complete_dt = Utc::now().date_naive() - Duration::days(1);
+        let last_complete_dt =
+            experiment_last_complete_dt(Utc::now().date_naive(), experiment_completed_day_offset());
         let ended_dt = ended_at.map(|dt| dt.date_naive());
-        let current_end_dt = ended_dt.unwrap_or(last_complete_dt).min(last_complete_dt);
+        let current_end_dt = experiment_current_end_dt(ended_dt, last_complete_dt);
 
         let baseline = aggregate_metrics_for_videos(
             pool,
@@ -5105,8 +10274,13 @@ async fn handle_youtube_experiment_get(
         state,
         stop_loss_pct,
         planned_duration_days,
+        effective_duration_days: effective_experiment_duration_days(
+            planned_duration_days,
+            experiment_min_duration_days(),
+        ),
         started_at: started_at.map(datetime_to_rfc3339_utc),
         ended_at: ended_at.map(datetime_to_rfc3339_utc),
+        baseline_note,
         variants: if variants.is_empty() {
             None
         } else {
@@ -5135,6 +10309,10 @@ struct CreateExperimentRequest {
     stop_loss_pct: Option<f64>,
     planned_duration_days: Option<i64>,
     variants: Vec<CreateExperimentVariantRequest>,
+    /// Capture title, description, tags, category and thumbnail so a rollback restores all of
+    /// them instead of only the changed field.
+    #[serde(default)]
+    full_snapshot: bool,
 }
 
 #[derive(Deserialize)]
@@ -5153,6 +10331,72 @@ fn normalize_experiment_type(raw: &str) -> Option<&'static str> {
     }
 }
 
+const EXPERIMENT_STATES: &[&str] = &[
+    "draft",
+    "running",
+    "stopped",
+    "won",
+    "lost",
+    "rolled_back",
+    "failed",
+];
+const DEFAULT_EXPERIMENTS_PAGE_SIZE: i64 = 20;
+const MAX_EXPERIMENTS_PAGE_SIZE: i64 = 100;
+
+/// `state=running,won` → the listed states (empty = all); `None` when any entry is unknown.
+fn parse_experiment_state_filter(value: Option<&str>) -> Option<Vec<&'static str>> {
+    let mut states: Vec<&'static str> = Vec::new();
+    for raw in value.unwrap_or("").split(',') {
+        let raw = raw.trim();
+        if raw.is_empty() || raw.eq_ignore_ascii_case("all") {
+            continue;
+        }
+        let state = EXPERIMENT_STATES
+            .iter()
+            .copied()
+            .find(|s| s.eq_ignore_ascii_case(raw))?;
+        if !states.contains(&state) {
+            states.push(state);
+        }
+    }
+    Some(states)
+}
+
+fn experiments_list_sql(state_count: usize) -> String {
+    let state_clause = if state_count == 0 {
+        String::new()
+    } else {
+        format!(
+            "\n          AND state IN ({})",
+            vec!["?"; state_count].join(", ")
+        )
+    };
+    format!(
+        r#"
+        SELECT id, channel_id, type, state, video_ids_json,
+               stop_loss_pct, planned_duration_days,
+               started_at,
+               ended_at
+        FROM yt_experiments
+        WHERE tenant_id = ?
+          AND channel_id = ?{state_clause}
+        ORDER BY created_at DESC, id DESC
+        LIMIT ? OFFSET ?;
+      "#
+    )
+}
+
+/// Start of the stats window, or `None` when stats were not requested or the experiment never ran.
+fn experiment_stats_start_dt(
+    include_stats: bool,
+    started_at: Option<DateTime<Utc>>,
+) -> Option<NaiveDate> {
+    if !include_stats {
+        return None;
+    }
+    started_at.map(|dt| dt.date_naive())
+}
+
 async fn handle_youtube_experiments(
     method: &Method,
     headers: &HeaderMap,
@@ -5186,6 +10430,12 @@ async fn handle_youtube_experiments(
         }
 
         let pool = get_pool().await?;
+        if let Some(response) =
+            feature_disabled_response(pool, tenant_id.trim(), TenantFeature::Experiments).await?
+        {
+            return Ok(response);
+        }
+
         let channel_id = match get_query_param(uri, "channel_id")
             .map(|v| v.trim().to_string())
             .filter(|v| !v.is_empty())
@@ -5203,7 +10453,30 @@ async fn handle_youtube_experiments(
             );
         }
 
-        let rows = sqlx::query_as::<
+        let Some(states) = parse_experiment_state_filter(get_query_param(uri, "state").as_deref())
+        else {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({
+                  "ok": false,
+                  "error": "bad_request",
+                  "message": format!("state must be a comma-separated list of {}", EXPERIMENT_STATES.join("|")),
+                }),
+            );
+        };
+        let limit = get_query_param(uri, "limit")
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .unwrap_or(DEFAULT_EXPERIMENTS_PAGE_SIZE)
+            .clamp(1, MAX_EXPERIMENTS_PAGE_SIZE);
+        let offset = get_query_param(uri, "offset")
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .unwrap_or(0)
+            .max(0);
+        // Per-variant stats cost two metric aggregations per experiment; list views skip them.
+        let include_stats = get_query_flag(uri, "include_stats");
+
+        let sql = experiments_list_sql(states.len());
+        let mut query = sqlx::query_as::<
             _,
             (
                 i64,
@@ -5216,26 +10489,23 @@ async fn handle_youtube_experiments(
                 Option<DateTime<Utc>>,
                 Option<DateTime<Utc>>,
             ),
-        >(
-            r#"
-        SELECT id, channel_id, type, state, video_ids_json,
-               stop_loss_pct, planned_duration_days,
-               started_at,
-               ended_at
-        FROM yt_experiments
-        WHERE tenant_id = ?
-          AND channel_id = ?
-        ORDER BY created_at DESC
-        LIMIT 50;
-      "#,
-        )
+        >(&sql)
         .bind(tenant_id.trim())
-        .bind(channel_id.trim())
-        .fetch_all(pool)
-        .await
-        .map_err(|e| -> Error { Box::new(e) })?;
+        .bind(channel_id.trim());
+        for state in &states {
+            query = query.bind(*state);
+        }
+        let mut rows = query
+            .bind(limit + 1)
+            .bind(offset)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| -> Error { Box::new(e) })?;
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit as usize);
 
-        let last_complete_dt = Utc::now().date_naive() - Duration::days(1);
+        let last_complete_dt =
+            experiment_last_complete_dt(Utc::now().date_naive(), experiment_completed_day_offset());
 
         let mut out: Vec<ExperimentResponse> = Vec::with_capacity(rows.len());
         for (
@@ -5253,13 +10523,22 @@ async fn handle_youtube_experiments(
             let video_ids = parse_video_ids_json(&video_ids_json);
             let mut variants = fetch_experiment_variants(pool, id).await?;
 
-            if let Some(started_at) = started_at {
-                let start_dt = started_at.date_naive();
-                let baseline_start_dt = start_dt - Duration::days(7);
-                let baseline_end_dt = start_dt - Duration::days(1);
+            let mut baseline_note: Option<String> = None;
+            if let Some(start_dt) = experiment_stats_start_dt(include_stats, started_at) {
+                let baseline_window = experiment_baseline_for_videos(
+                    pool,
+                    tenant_id.trim(),
+                    channel_id.trim(),
+                    &video_ids,
+                    start_dt,
+                )
+                .await?;
+                let baseline_start_dt = baseline_window.start_dt;
+                let baseline_end_dt = baseline_window.end_dt;
+                baseline_note = baseline_window.note;
 
                 let ended_dt = ended_at.map(|dt| dt.date_naive());
-                let current_end_dt = ended_dt.unwrap_or(last_complete_dt).min(last_complete_dt);
+                let current_end_dt = experiment_current_end_dt(ended_dt, last_complete_dt);
 
                 let baseline = aggregate_metrics_for_videos(
                     pool,
@@ -5290,8 +10569,13 @@ async fn handle_youtube_experiments(
                 state,
                 stop_loss_pct,
                 planned_duration_days,
+                effective_duration_days: effective_experiment_duration_days(
+                    planned_duration_days,
+                    experiment_min_duration_days(),
+                ),
                 started_at: started_at.map(datetime_to_rfc3339_utc),
                 ended_at: ended_at.map(datetime_to_rfc3339_utc),
+                baseline_note,
                 variants: if variants.is_empty() {
                     None
                 } else {
@@ -5302,7 +10586,16 @@ async fn handle_youtube_experiments(
 
         return json_response(
             StatusCode::OK,
-            serde_json::json!({"ok": true, "items": out, "channel_id": channel_id}),
+            serde_json::json!({
+              "ok": true,
+              "items": out,
+              "channel_id": channel_id,
+              "state": states,
+              "include_stats": include_stats,
+              "limit": limit,
+              "offset": offset,
+              "has_more": has_more,
+            }),
         );
     }
 
@@ -5318,6 +10611,20 @@ async fn handle_youtube_experiments(
             Box::new(std::io::Error::other(format!("invalid json body: {e}")))
         })?;
 
+        if let Some(tenant_id) = v
+            .get("tenant_id")
+            .and_then(|t| t.as_str())
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+        {
+            if let Some(response) =
+                feature_disabled_response(get_pool().await?, tenant_id, TenantFeature::Experiments)
+                    .await?
+            {
+                return Ok(response);
+            }
+        }
+
         if v.get("op").is_some() {
             let parsed: MutateExperimentRequest =
                 serde_json::from_value(v).map_err(|e| -> Error {
@@ -5412,13 +10719,7 @@ async fn handle_youtube_experiments(
                     .unwrap_or_else(|| serde_json::json!({}));
 
             let baseline_title = if exp_type == "title" {
-                json_string_field(&baseline_payload, "title")
-            } else {
-                None
-            };
-            let baseline_thumbnail_url = if exp_type == "thumbnail" {
-                json_string_field(&baseline_payload, "thumbnail_url")
-                    .or_else(|| json_string_field(&baseline_payload, "thumbnailUrl"))
+                json_string_field(&baseline_payload, "title")
             } else {
                 None
             };
@@ -5429,107 +10730,117 @@ async fn handle_youtube_experiments(
                 None
             };
 
-            let mut tokens =
-                fetch_youtube_connection_tokens(pool, parsed.tenant_id.trim(), channel_id.trim())
-                    .await?
-                    .ok_or_else(|| {
-                        Box::new(std::io::Error::other("missing youtube channel connection"))
-                            as Error
-                    })?;
-
-            // Proactive refresh if expired (best-effort).
-            let needs_refresh = tokens
-                .expires_at
-                .map(|dt| dt <= chrono::Utc::now())
-                .unwrap_or(false);
-            if needs_refresh {
-                if let Some(refresh) = tokens.refresh_token.clone() {
-                    let app = fetch_or_seed_youtube_oauth_app_config(pool, parsed.tenant_id.trim())
-                        .await?;
-                    let Some(app) = app else {
-                        return json_response(
-                            StatusCode::NOT_FOUND,
-                            serde_json::json!({
-                              "ok": false,
-                              "error": "not_configured",
-                              "message": "Missing YouTube OAuth app config for tenant. Configure via /api/oauth/youtube/app_config or set YOUTUBE_CLIENT_ID/YOUTUBE_CLIENT_SECRET/YOUTUBE_REDIRECT_URI on the Rust backend."
-                            }),
-                        );
-                    };
-                    let Some(client_secret) = app
-                        .client_secret
-                        .as_deref()
-                        .map(str::trim)
-                        .filter(|v| !v.is_empty())
-                    else {
-                        return json_response(
-                            StatusCode::NOT_FOUND,
-                            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing YouTube OAuth client_secret for tenant"}),
-                        );
-                    };
-
-                    let (client, _redirect) = youtube_oauth_client_from_config(
-                        &app.client_id,
-                        client_secret,
-                        &app.redirect_uri,
-                    )?;
-                    let refreshed = refresh_tokens(&client, &refresh).await?;
-                    update_youtube_connection_tokens(
-                        pool,
-                        parsed.tenant_id.trim(),
-                        channel_id.trim(),
-                        &refreshed,
-                    )
-                    .await?;
-                    tokens.access_token = refreshed.access_token;
-                    tokens.refresh_token = refreshed.refresh_token.or(Some(refresh));
-                }
-            }
+            let tokens =
+                match ensure_fresh_youtube_tokens(pool, parsed.tenant_id.trim(), channel_id.trim())
+                    .await
+                {
+                    Ok(v) => v,
+                    Err(err) => return youtube_token_error_response(err),
+                };
 
-            let rollback_result: Result<(), String> = match exp_type.as_str() {
-                "title" => {
-                    let title = baseline_title.unwrap_or_default();
-                    if title.trim().is_empty() {
-                        Err("baseline variant A missing title".to_string())
-                    } else {
-                        update_video_title(&tokens.access_token, &primary_video_id, &title)
-                            .await
-                            .map_err(|e| e.to_string())
+            let full_snapshot =
+                fetch_experiment_full_snapshot(pool, parsed.tenant_id.trim(), id).await?;
+            let original_thumbnail = if exp_type == "thumbnail" {
+                fetch_experiment_baseline_thumbnail(pool, id).await?
+            } else {
+                None
+            };
+            let rollback_result: Result<(), String> = if let Some(snapshot) = full_snapshot.as_ref()
+            {
+                match restore_video_snapshot(
+                    &tokens.access_token,
+                    &primary_video_id,
+                    snapshot,
+                    original_thumbnail.as_ref(),
+                )
+                .await
+                {
+                    Err(e) => Err(e.to_string()),
+                    Ok(()) if exp_type == "thumbnail" && original_thumbnail.is_none() => {
+                        Err("baseline thumbnail image was not stored".to_string())
                     }
+                    Ok(()) => Ok(()),
                 }
-                "thumbnail" => {
-                    let url = baseline_thumbnail_url.unwrap_or_default();
-                    if url.trim().is_empty() {
-                        Err("baseline variant A missing thumbnail_url".to_string())
-                    } else {
-                        set_video_thumbnail_from_url(&tokens.access_token, &primary_video_id, &url)
-                            .await
-                            .map_err(|e| e.to_string())
+            } else {
+                match exp_type.as_str() {
+                    "title" => {
+                        let title = baseline_title.unwrap_or_default();
+                        if title.trim().is_empty() {
+                            Err("baseline variant A missing title".to_string())
+                        } else {
+                            update_video_title(&tokens.access_token, &primary_video_id, &title)
+                                .await
+                                .map_err(|e| e.to_string())
+                        }
                     }
-                }
-                "publish_time" => {
-                    let publish_at = baseline_publish_at.unwrap_or_default();
-                    if publish_at.trim().is_empty() {
-                        Err("baseline variant A missing publish_at".to_string())
-                    } else {
-                        update_video_publish_at(
+                    "thumbnail" => match original_thumbnail.as_ref() {
+                        None => Err("baseline thumbnail image was not stored".to_string()),
+                        Some(thumbnail) => set_video_thumbnail_from_bytes(
                             &tokens.access_token,
                             &primary_video_id,
-                            &publish_at,
+                            thumbnail,
                         )
                         .await
-                        .map_err(|e| e.to_string())
+                        .map_err(|e| e.to_string()),
+                    },
+                    "publish_time" => {
+                        let publish_at = baseline_publish_at.unwrap_or_default();
+                        if publish_at.trim().is_empty() {
+                            Err("baseline variant A missing publish_at".to_string())
+                        } else {
+                            update_video_publish_at(
+                                &tokens.access_token,
+                                &primary_video_id,
+                                &publish_at,
+                            )
+                            .await
+                            .map_err(|e| e.to_string())
+                        }
                     }
+                    _ => Ok(()),
                 }
-                _ => Ok(()),
             };
+            let quota_units = match full_snapshot.as_ref() {
+                Some(_) => snapshot_restore_quota_units(original_thumbnail.is_some()),
+                None => experiment_change_quota_units(exp_type.as_str())
+                    .into_iter()
+                    .collect(),
+            };
+            for (api_method, units) in quota_units {
+                let _ = record_youtube_api_usage(
+                    pool,
+                    parsed.tenant_id.trim(),
+                    channel_id.trim(),
+                    api_method,
+                    units,
+                )
+                .await;
+            }
 
             if let Err(err) = rollback_result {
+                let _ = mark_experiment_rollback_failed(pool, parsed.tenant_id.trim(), id).await;
+                let _ = evaluate_experiment_failure_alert(
+                    pool,
+                    parsed.tenant_id.trim(),
+                    channel_id.trim(),
+                )
+                .await;
                 return json_response(
                     StatusCode::BAD_GATEWAY,
                     serde_json::json!({"ok": false, "error": "rollback_failed", "message": err}),
                 );
             }
+            if matches!(exp_type.as_str(), "title" | "thumbnail" | "publish_time") {
+                let _ = record_video_change(
+                    pool,
+                    parsed.tenant_id.trim(),
+                    channel_id.trim(),
+                    &primary_video_id,
+                    exp_type.as_str(),
+                    Utc::now().date_naive(),
+                )
+                .await;
+            }
 
             let updated = sqlx::query(
                 r#"
@@ -5675,10 +10986,37 @@ async fn handle_youtube_experiments(
                 serde_json::json!({"ok": false, "error": "bad_request", "message": "Variant B payload must include title"}),
             );
         }
-        if exp_type == "thumbnail" && desired_thumbnail_url.is_none() {
+        // A locally held image can be sent inline instead of hosting it; only a hash reference
+        // is persisted, never the bytes.
+        let desired_thumbnail_upload = if exp_type == "thumbnail" && desired_thumbnail_url.is_none()
+        {
+            let encoded = json_string_field(&payload_b, "thumbnail_base64")
+                .or_else(|| json_string_field(&payload_b, "thumbnailBase64"));
+            match encoded.as_deref().map(decode_thumbnail_base64) {
+                None => None,
+                Some(Ok(v)) => Some(v),
+                Some(Err(err)) => {
+                    let (status, code) = if err.status == Some(413) {
+                        (StatusCode::PAYLOAD_TOO_LARGE, "payload_too_large")
+                    } else {
+                        (StatusCode::BAD_REQUEST, "bad_request")
+                    };
+                    return json_response(
+                        status,
+                        serde_json::json!({"ok": false, "error": code, "message": err.message}),
+                    );
+                }
+            }
+        } else {
+            None
+        };
+        if exp_type == "thumbnail"
+            && desired_thumbnail_url.is_none()
+            && desired_thumbnail_upload.is_none()
+        {
             return json_response(
                 StatusCode::BAD_REQUEST,
-                serde_json::json!({"ok": false, "error": "bad_request", "message": "Variant B payload must include thumbnail_url"}),
+                serde_json::json!({"ok": false, "error": "bad_request", "message": "Variant B payload must include thumbnail_url or thumbnail_base64"}),
             );
         }
         if exp_type == "publish_time" && desired_publish_at.is_none() {
@@ -5688,55 +11026,19 @@ async fn handle_youtube_experiments(
             );
         }
 
-        let mut tokens = fetch_youtube_connection_tokens(pool, tenant_id, channel_id.trim())
-            .await?
-            .ok_or_else(|| {
-                Box::new(std::io::Error::other("missing youtube channel connection")) as Error
-            })?;
-
-        // Proactive refresh if expired (best-effort).
-        let needs_refresh = tokens
-            .expires_at
-            .map(|dt| dt <= chrono::Utc::now())
-            .unwrap_or(false);
-        if needs_refresh {
-            if let Some(refresh) = tokens.refresh_token.clone() {
-                let app = fetch_or_seed_youtube_oauth_app_config(pool, tenant_id).await?;
-                let Some(app) = app else {
-                    return json_response(
-                        StatusCode::NOT_FOUND,
-                        serde_json::json!({
-                          "ok": false,
-                          "error": "not_configured",
-                          "message": "Missing YouTube OAuth app config for tenant. Configure via /api/oauth/youtube/app_config or set YOUTUBE_CLIENT_ID/YOUTUBE_CLIENT_SECRET/YOUTUBE_REDIRECT_URI on the Rust backend."
-                        }),
-                    );
-                };
-                let Some(client_secret) = app
-                    .client_secret
-                    .as_deref()
-                    .map(str::trim)
-                    .filter(|v| !v.is_empty())
-                else {
-                    return json_response(
-                        StatusCode::NOT_FOUND,
-                        serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing YouTube OAuth client_secret for tenant"}),
-                    );
-                };
-
-                let (client, _redirect) = youtube_oauth_client_from_config(
-                    &app.client_id,
-                    client_secret,
-                    &app.redirect_uri,
-                )?;
-                let refreshed = refresh_tokens(&client, &refresh).await?;
-                update_youtube_connection_tokens(pool, tenant_id, channel_id.trim(), &refreshed)
-                    .await?;
-                tokens.access_token = refreshed.access_token;
-                tokens.refresh_token = refreshed.refresh_token.or(Some(refresh));
-            }
-        }
+        let tokens = match ensure_fresh_youtube_tokens(pool, tenant_id, channel_id.trim()).await {
+            Ok(v) => v,
+            Err(err) => return youtube_token_error_response(err),
+        };
 
+        let _ = record_youtube_api_usage(
+            pool,
+            tenant_id,
+            channel_id.trim(),
+            "videos.list",
+            VIDEOS_LIST_QUOTA_UNITS,
+        )
+        .await;
         let baseline_snapshot = match fetch_video_snapshot(&tokens.access_token, &primary_video_id)
             .await
         {
@@ -5749,7 +11051,19 @@ async fn handle_youtube_experiments(
             }
         };
 
-        let baseline_payload = match exp_type {
+        if !video_owned_by_channel(&baseline_snapshot, channel_id.trim()) {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({
+                  "ok": false,
+                  "error": "bad_request",
+                  "message": format!("video_id {primary_video_id} does not belong to channel {}", channel_id.trim()),
+                  "video_channel_id": baseline_snapshot.channel_id,
+                }),
+            );
+        }
+
+        let mut baseline_payload = match exp_type {
             "title" => serde_json::json!({"title": baseline_snapshot.title}),
             "thumbnail" => {
                 let Some(url) = baseline_snapshot.thumbnail_url.clone() else {
@@ -5778,7 +11092,38 @@ async fn handle_youtube_experiments(
             _ => serde_json::json!({}),
         };
 
+        // Rollback re-uploads these bytes; the thumbnail URL will serve variant B once applied.
+        let baseline_thumbnail = match baseline_payload
+            .get("thumbnail_url")
+            .and_then(|v| v.as_str())
+        {
+            Some(url) => match download_video_thumbnail(url).await {
+                Ok(v) => Some(v),
+                Err(err) => {
+                    return json_response(
+                        StatusCode::BAD_GATEWAY,
+                        serde_json::json!({"ok": false, "error": "youtube_api_error", "message": format!("could not store the current thumbnail for rollback: {err}"), "status": err.status}),
+                    );
+                }
+            },
+            None => None,
+        };
+        if let (Some(thumbnail), Some(obj)) = (
+            baseline_thumbnail.as_ref(),
+            baseline_payload.as_object_mut(),
+        ) {
+            obj.insert(
+                "thumbnail_upload".to_string(),
+                thumbnail.payload_reference(),
+            );
+        }
+
         let video_ids_json = serde_json::to_string(&video_ids).unwrap_or_else(|_| "[]".to_string());
+        let full_snapshot_json = if parsed.full_snapshot {
+            serde_json::to_string(&baseline_snapshot).ok()
+        } else {
+            None
+        };
 
         let mut tx = pool.begin().await.map_err(|e| -> Error { Box::new(e) })?;
 
@@ -5790,10 +11135,11 @@ async fn handle_youtube_experiments(
           video_ids_json,
           stop_loss_pct,
           planned_duration_days,
+          full_snapshot_json,
           started_at,
           ended_at
         )
-        VALUES (?, ?, ?, 'draft', ?, ?, ?, NULL, NULL);
+        VALUES (?, ?, ?, 'draft', ?, ?, ?, ?, NULL, NULL);
       "#,
         )
         .bind(tenant_id)
@@ -5802,21 +11148,35 @@ async fn handle_youtube_experiments(
         .bind(video_ids_json)
         .bind(parsed.stop_loss_pct)
         .bind(parsed.planned_duration_days)
+        .bind(full_snapshot_json)
         .execute(&mut *tx)
         .await
         .map_err(|e| -> Error { Box::new(e) })?;
 
         let exp_id = insert.last_insert_id() as i64;
 
+        if let Some(thumbnail) = baseline_thumbnail.as_ref() {
+            insert_experiment_baseline_thumbnail(&mut tx, exp_id, thumbnail).await?;
+        }
+
         for variant in variants.iter() {
             let (payload, status) = if variant.id.trim() == "A" {
                 (baseline_payload.clone(), "control")
             } else {
-                let payload = if variant.payload.is_object() {
+                let mut payload = if variant.payload.is_object() {
                     variant.payload.clone()
                 } else {
                     serde_json::json!({})
                 };
+                if let (Some(upload), Some(obj)) =
+                    (desired_thumbnail_upload.as_ref(), payload.as_object_mut())
+                {
+                    if variant.id.trim() == "B" {
+                        obj.remove("thumbnail_base64");
+                        obj.remove("thumbnailBase64");
+                        obj.insert("thumbnail_upload".to_string(), upload.payload_reference());
+                    }
+                }
                 let status = if variant.id.trim() == "B" {
                     "pending"
                 } else {
@@ -5847,30 +11207,45 @@ async fn handle_youtube_experiments(
 
         tx.commit().await.map_err(|e| -> Error { Box::new(e) })?;
 
-        let apply_result: Result<(), String> = match exp_type {
+        let apply_result: Result<(), YoutubeVideoError> = match exp_type {
             "title" => {
                 let title = desired_title.clone().unwrap_or_default();
-                update_video_title(&tokens.access_token, &primary_video_id, &title)
-                    .await
-                    .map_err(|e| e.to_string())
-            }
-            "thumbnail" => {
-                let url = desired_thumbnail_url.clone().unwrap_or_default();
-                set_video_thumbnail_from_url(&tokens.access_token, &primary_video_id, &url)
-                    .await
-                    .map_err(|e| e.to_string())
+                update_video_title(&tokens.access_token, &primary_video_id, &title).await
             }
+            "thumbnail" => match desired_thumbnail_upload.as_ref() {
+                Some(upload) => {
+                    set_video_thumbnail_from_bytes(&tokens.access_token, &primary_video_id, upload)
+                        .await
+                }
+                None => {
+                    let url = desired_thumbnail_url.clone().unwrap_or_default();
+                    set_video_thumbnail_from_url(&tokens.access_token, &primary_video_id, &url)
+                        .await
+                }
+            },
             "publish_time" => {
                 let publish_at = desired_publish_at.clone().unwrap_or_default();
-                update_video_publish_at(&tokens.access_token, &primary_video_id, &publish_at)
-                    .await
-                    .map_err(|e| e.to_string())
+                update_video_publish_at(&tokens.access_token, &primary_video_id, &publish_at).await
             }
             _ => Ok(()),
         };
+        if let Some((api_method, units)) = experiment_change_quota_units(exp_type) {
+            let _ = record_youtube_api_usage(pool, tenant_id, channel_id.trim(), api_method, units)
+                .await;
+        }
 
         match apply_result {
             Ok(()) => {
+                let _ = record_video_change(
+                    pool,
+                    tenant_id,
+                    channel_id.as_str(),
+                    &primary_video_id,
+                    exp_type,
+                    Utc::now().date_naive(),
+                )
+                .await;
+
                 sqlx::query(
                     r#"
             UPDATE yt_experiments
@@ -5937,43 +11312,360 @@ async fn handle_youtube_experiments(
                 .execute(pool)
                 .await;
 
+                let _ = evaluate_experiment_failure_alert(pool, tenant_id, channel_id.trim()).await;
+
+                // A video edited concurrently is a clean retry for the caller, not an outage.
+                if err.is_stale_snapshot() {
+                    return json_response(
+                        StatusCode::CONFLICT,
+                        serde_json::json!({"ok": false, "error": "stale_snapshot", "message": err.to_string(), "experiment_id": format!("exp_{exp_id}"), "channel_id": channel_id}),
+                    );
+                }
                 return json_response(
                     StatusCode::BAD_GATEWAY,
-                    serde_json::json!({"ok": false, "error": "apply_failed", "message": err, "experiment_id": format!("exp_{exp_id}"), "channel_id": channel_id}),
+                    serde_json::json!({"ok": false, "error": "apply_failed", "message": err.to_string(), "experiment_id": format!("exp_{exp_id}"), "channel_id": channel_id}),
+                );
+            }
+        }
+    }
+
+    json_response(
+        StatusCode::METHOD_NOT_ALLOWED,
+        serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+    )
+}
+
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 20;
+
+/// Stays under the platform function limit so callers get a JSON 504 instead of an opaque kill.
+fn request_timeout() -> std::time::Duration {
+    let secs = std::env::var("ROUTER_REQUEST_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECS)
+        .clamp(1, 300);
+    std::time::Duration::from_secs(secs)
+}
+
+async fn run_with_deadline<F>(
+    action: &str,
+    timeout: std::time::Duration,
+    fut: F,
+) -> Result<Response<ResponseBody>, Error>
+where
+    F: std::future::Future<Output = Result<Response<ResponseBody>, Error>>,
+{
+    match tokio::time::timeout(timeout, fut).await {
+        Ok(result) => result,
+        Err(_) => json_response(
+            StatusCode::GATEWAY_TIMEOUT,
+            serde_json::json!({
+              "ok": false,
+              "error": "timeout",
+              "action": action,
+              "message": format!("request exceeded {}s deadline", timeout.as_secs()),
+            }),
+        ),
+    }
+}
+
+#[derive(Deserialize)]
+struct TenantApiKeysRequest {
+    tenant_id: String,
+    op: String,
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    key_id: Option<i64>,
+}
+
+/// Issues (`op: create`) or revokes (`op: revoke`, `key_id`) a tenant's API key. Internal token
+/// only; the plaintext key is returned once and only its hash is stored.
+async fn handle_tenant_api_keys(
+    method: &Method,
+    headers: &HeaderMap,
+    body: Bytes,
+) -> Result<Response<ResponseBody>, Error> {
+    if method != Method::POST {
+        return json_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            serde_json::json!({"ok": false, "error": "method_not_allowed"}),
+        );
+    }
+
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    let provided =
+        bearer_token(headers.get("authorization").and_then(|v| v.to_str().ok())).unwrap_or("");
+    if expected.is_empty() || provided != expected {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        );
+    }
+
+    if !has_tidb_url() {
+        return json_response(
+            StatusCode::NOT_IMPLEMENTED,
+            serde_json::json!({"ok": false, "error": "not_configured", "message": "Missing TIDB_DATABASE_URL (or DATABASE_URL)"}),
+        );
+    }
+
+    let parsed: TenantApiKeysRequest = serde_json::from_slice(&body).map_err(|e| -> Error {
+        Box::new(std::io::Error::other(format!("invalid json body: {e}")))
+    })?;
+
+    let tenant_id = parsed.tenant_id.trim();
+    if tenant_id.is_empty() {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "tenant_id is required"}),
+        );
+    }
+
+    let pool = get_pool().await?;
+    match parsed.op.trim() {
+        "create" => {
+            let key = generate_tenant_api_key()?;
+            let key_prefix = tenant_api_key_display_prefix(&key);
+            let label = parsed
+                .label
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty());
+            let key_id = insert_tenant_api_key(
+                pool,
+                tenant_id,
+                &hash_tenant_api_key(&key),
+                &key_prefix,
+                label,
+            )
+            .await?;
+            json_response(
+                StatusCode::OK,
+                serde_json::json!({"ok": true, "tenant_id": tenant_id, "key_id": key_id, "key_prefix": key_prefix, "api_key": key}),
+            )
+        }
+        "revoke" => {
+            let Some(key_id) = parsed.key_id else {
+                return json_response(
+                    StatusCode::BAD_REQUEST,
+                    serde_json::json!({"ok": false, "error": "bad_request", "message": "key_id is required"}),
+                );
+            };
+            let revoked = revoke_tenant_api_key(pool, tenant_id, key_id).await?;
+            if !revoked {
+                return json_response(
+                    StatusCode::NOT_FOUND,
+                    serde_json::json!({"ok": false, "error": "not_found", "message": "No active key with that key_id for this tenant"}),
                 );
             }
+            json_response(
+                StatusCode::OK,
+                serde_json::json!({"ok": true, "tenant_id": tenant_id, "key_id": key_id, "revoked": true}),
+            )
+        }
+        _ => json_response(
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({"ok": false, "error": "bad_request", "message": "op must be create or revoke"}),
+        ),
+    }
+}
+
+/// A request with its body already read, so the handler can look at the body (for a tenant
+/// key's `tenant_id`) before routing.
+struct BufferedRequest {
+    method: Method,
+    headers: HeaderMap,
+    uri: Uri,
+    body: Bytes,
+}
+
+impl BufferedRequest {
+    fn method(&self) -> &Method {
+        &self.method
+    }
+
+    fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    fn uri(&self) -> &Uri {
+        &self.uri
+    }
+}
+
+/// Actions a tenant key may never call, even for its own tenant.
+const TENANT_KEY_DENIED_ACTIONS: &[&str] = &["tenant_api_keys"];
+
+/// Resolves a `gfk_` bearer key to its tenant. An allowed key is swapped for the internal token,
+/// so the handlers' own auth checks pass unchanged; the internal token itself stays a superuser.
+async fn authorize_tenant_api_key(
+    action: &str,
+    req: &mut BufferedRequest,
+) -> Result<Option<Response<ResponseBody>>, Error> {
+    let provided = bearer_token(
+        req.headers
+            .get("authorization")
+            .and_then(|v| v.to_str().ok()),
+    )
+    .unwrap_or("")
+    .trim()
+    .to_string();
+    if !is_tenant_api_key(&provided) {
+        return Ok(None);
+    }
+    let expected = std::env::var("RUST_INTERNAL_TOKEN").unwrap_or_default();
+    if expected.is_empty() || !has_tidb_url() {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        )
+        .map(Some);
+    }
+
+    let pool = get_pool().await?;
+    let key_tenant_id =
+        fetch_tenant_id_for_api_key_hash(pool, &hash_tenant_api_key(&provided)).await?;
+    let requested =
+        requested_tenant_id(get_query_param(&req.uri, "tenant_id").as_deref(), &req.body);
+    match tenant_key_access(key_tenant_id.as_deref(), requested.as_deref()) {
+        TenantKeyAccess::UnknownKey => json_response(
+            StatusCode::UNAUTHORIZED,
+            serde_json::json!({"ok": false, "error": "unauthorized"}),
+        )
+        .map(Some),
+        TenantKeyAccess::TenantMismatch => json_response(
+            StatusCode::FORBIDDEN,
+            serde_json::json!({"ok": false, "error": "forbidden", "message": "API key is not valid for this tenant_id"}),
+        )
+        .map(Some),
+        TenantKeyAccess::Allowed if TENANT_KEY_DENIED_ACTIONS.contains(&action) => json_response(
+            StatusCode::FORBIDDEN,
+            serde_json::json!({"ok": false, "error": "forbidden", "message": "This action requires the internal token"}),
+        )
+        .map(Some),
+        TenantKeyAccess::Allowed => {
+            let value = hyper::header::HeaderValue::from_str(&format!("Bearer {expected}"))
+                .map_err(|e| -> Error { Box::new(e) })?;
+            req.headers.insert(hyper::header::AUTHORIZATION, value);
+            Ok(None)
         }
     }
+}
 
-    json_response(
-        StatusCode::METHOD_NOT_ALLOWED,
-        serde_json::json!({"ok": false, "error": "method_not_allowed"}),
-    )
+const DEFAULT_MAX_BODY_BYTES: usize = 1_000_000;
+/// CSV uploads carry `csv_text` (up to `CSV_MAX_BYTES`) inside JSON, where escaping can double it.
+const CSV_MAX_BODY_BYTES: usize = 2 * CSV_MAX_BYTES;
+const CSV_UPLOAD_ACTIONS: &[&str] = &[
+    "youtube_upload_csv",
+    "youtube_upload_csv_reprocess",
+    "youtube_upload_csv_preview",
+];
+/// Experiments can carry variant B's `thumbnail_base64`: a max-size upload grows by 4/3 when
+/// base64-encoded, plus headroom for a data URL prefix and the rest of the JSON.
+const EXPERIMENT_MAX_BODY_BYTES: usize = MAX_UPLOADED_THUMBNAIL_BYTES.div_ceil(3) * 4 + 64 * 1024;
+
+/// `ROUTER_MAX_BODY_BYTES` (1KB..=50MB, default 1MB); CSV uploads and experiments never get less
+/// than their own limit.
+fn max_body_bytes(action: &str, configured: Option<&str>) -> usize {
+    let global = configured
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .unwrap_or(DEFAULT_MAX_BODY_BYTES)
+        .clamp(1_024, 50_000_000);
+    if CSV_UPLOAD_ACTIONS.contains(&action) {
+        global.max(CSV_MAX_BODY_BYTES)
+    } else if action == "youtube_experiments" {
+        global.max(EXPERIMENT_MAX_BODY_BYTES)
+    } else {
+        global
+    }
+}
+
+/// Reads the body, stopping as soon as it passes `limit`; `None` means it was too large.
+async fn collect_body_with_limit<B>(body: B, limit: usize) -> Result<Option<Bytes>, Error>
+where
+    B: hyper::body::Body,
+    B::Error: Into<Error>,
+{
+    match http_body_util::Limited::new(body, limit).collect().await {
+        Ok(collected) => Ok(Some(collected.to_bytes())),
+        Err(err) if err.is::<http_body_util::LengthLimitError>() => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+async fn handle_and_route(action: &str, req: Request) -> Result<Response<ResponseBody>, Error> {
+    let (parts, body) = req.into_parts();
+    let limit = max_body_bytes(
+        action,
+        std::env::var("ROUTER_MAX_BODY_BYTES").ok().as_deref(),
+    );
+    let declared_len = parts
+        .headers
+        .get(hyper::header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<usize>().ok());
+    let body = match declared_len {
+        Some(len) if len > limit => None,
+        _ => collect_body_with_limit(body, limit).await?,
+    };
+    let Some(body) = body else {
+        return json_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            serde_json::json!({"ok": false, "error": "payload_too_large", "message": format!("request body exceeds {limit} bytes")}),
+        );
+    };
+    let mut req = BufferedRequest {
+        method: parts.method,
+        headers: parts.headers,
+        uri: parts.uri,
+        body,
+    };
+    if let Some(denied) = authorize_tenant_api_key(action, &mut req).await? {
+        return Ok(denied);
+    }
+    route(action, req).await
 }
 
 async fn handler(req: Request) -> Result<Response<ResponseBody>, Error> {
     let action = get_query_param(req.uri(), "action").unwrap_or_default();
 
-    let result = match action.as_str() {
+    let result =
+        run_with_deadline(&action, request_timeout(), handle_and_route(&action, req)).await;
+
+    match result {
+        Ok(resp) => Ok(resp),
+        Err(err) => {
+            let message = truncate_string(&err.to_string(), 2000);
+            json_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                serde_json::json!({"ok": false, "error": "internal_error", "action": action, "message": message}),
+            )
+        }
+    }
+}
+
+async fn route(action: &str, req: BufferedRequest) -> Result<Response<ResponseBody>, Error> {
+    match action {
         "status" => handle_status(req.method(), req.headers(), req.uri()).await,
         "start" => {
             let method = req.method().clone();
             let headers = req.headers().clone();
-            let bytes = req.into_body().collect().await?.to_bytes();
+            let bytes = req.body;
             handle_start(&method, &headers, bytes).await
         }
         "exchange" => {
             let method = req.method().clone();
             let headers = req.headers().clone();
-            let bytes = req.into_body().collect().await?.to_bytes();
+            let bytes = req.body;
             handle_exchange(&method, &headers, bytes).await
         }
         "app_config" => {
             let method = req.method().clone();
             let headers = req.headers().clone();
             let uri = req.uri().clone();
-            let body = if method == Method::POST {
-                Some(req.into_body().collect().await?.to_bytes())
+            let body = if method == Method::POST || method == Method::PATCH {
+                Some(req.body)
             } else {
                 None
             };
@@ -5982,15 +11674,38 @@ async fn handler(req: Request) -> Result<Response<ResponseBody>, Error> {
         "content_owner_discover" => {
             let method = req.method().clone();
             let headers = req.headers().clone();
-            let bytes = req.into_body().collect().await?.to_bytes();
+            let bytes = req.body;
             handle_content_owner_discover(&method, &headers, bytes).await
         }
         "set_active_channel" => {
             let method = req.method().clone();
             let headers = req.headers().clone();
-            let bytes = req.into_body().collect().await?.to_bytes();
+            let bytes = req.body;
             handle_set_active_channel(&method, &headers, bytes).await
         }
+        "connection_active" => {
+            let method = req.method().clone();
+            let headers = req.headers().clone();
+            let bytes = req.body;
+            handle_connection_active(&method, &headers, bytes).await
+        }
+        "youtube_pinned_channels" => {
+            let method = req.method().clone();
+            let headers = req.headers().clone();
+            let uri = req.uri().clone();
+            let body = if method == Method::POST {
+                Some(req.body)
+            } else {
+                None
+            };
+            handle_youtube_pinned_channels(&method, &headers, &uri, body).await
+        }
+        "youtube_effective_config" => {
+            handle_youtube_effective_config(req.method(), req.headers(), req.uri()).await
+        }
+        "youtube_video_snapshot" => {
+            handle_youtube_video_snapshot(req.method(), req.headers(), req.uri()).await
+        }
         "youtube_channels_mine" => {
             handle_youtube_channels_mine(req.method(), req.headers(), req.uri()).await
         }
@@ -6006,19 +11721,41 @@ async fn handler(req: Request) -> Result<Response<ResponseBody>, Error> {
         "youtube_outcome_latest" => {
             handle_youtube_outcome_latest(req.method(), req.headers(), req.uri()).await
         }
+        "youtube_decision_history" => {
+            handle_youtube_decision_history(req.method(), req.headers(), req.uri()).await
+        }
+        "youtube_decision_diff" => {
+            handle_youtube_decision_diff(req.method(), req.headers(), req.uri()).await
+        }
+        "youtube_decisions_export" => {
+            handle_youtube_decisions_export(req.method(), req.headers(), req.uri()).await
+        }
+        "tenant_api_keys" => {
+            let method = req.method().clone();
+            let headers = req.headers().clone();
+            handle_tenant_api_keys(&method, &headers, req.body).await
+        }
+        "usage_summary" => handle_usage_summary(req.method(), req.headers(), req.uri()).await,
+        "youtube_kpis" => handle_youtube_kpis(req.method(), req.headers(), req.uri()).await,
         "youtube_dashboard_bundle" => {
             handle_youtube_dashboard_bundle(req.method(), req.headers(), req.uri()).await
         }
         "youtube_sync_bundle" => {
             handle_youtube_sync_bundle(req.method(), req.headers(), req.uri()).await
         }
+        "youtube_shorts_revenue_split" => {
+            handle_youtube_shorts_revenue_split(req.method(), req.headers(), req.uri()).await
+        }
         "youtube_top_videos" => {
             handle_youtube_top_videos(req.method(), req.headers(), req.uri()).await
         }
+        "youtube_top_movers" => {
+            handle_youtube_top_movers(req.method(), req.headers(), req.uri()).await
+        }
         "youtube_report_share_put" => {
             let method = req.method().clone();
             let headers = req.headers().clone();
-            let bytes = req.into_body().collect().await?.to_bytes();
+            let bytes = req.body;
             handle_youtube_report_share_put(&method, &headers, bytes).await
         }
         "youtube_report_share_get" => {
@@ -6039,38 +11776,101 @@ async fn handler(req: Request) -> Result<Response<ResponseBody>, Error> {
         "youtube_sponsor_quote" => {
             let method = req.method().clone();
             let headers = req.headers().clone();
-            let bytes = req.into_body().collect().await?.to_bytes();
+            let bytes = req.body;
             handle_youtube_sponsor_quote(&method, &headers, bytes).await
         }
+        "youtube_sponsor_quote_get" => {
+            handle_youtube_sponsor_quote_get(req.method(), req.uri()).await
+        }
         "youtube_uploads_list" => {
             handle_youtube_uploads_list(req.method(), req.headers(), req.uri()).await
         }
         "youtube_upload_csv" => {
             let method = req.method().clone();
             let headers = req.headers().clone();
-            let bytes = req.into_body().collect().await?.to_bytes();
+            let bytes = req.body;
             handle_youtube_upload_csv(&method, &headers, bytes).await
         }
+        "youtube_upload_csv_reprocess" => {
+            let method = req.method().clone();
+            let headers = req.headers().clone();
+            let bytes = req.body;
+            handle_youtube_upload_csv_reprocess(&method, &headers, bytes).await
+        }
+        "youtube_upload_csv_preview" => {
+            let method = req.method().clone();
+            let headers = req.headers().clone();
+            let bytes = req.body;
+            handle_youtube_upload_csv_preview(&method, &headers, bytes).await
+        }
+        "youtube_metrics_purge" => {
+            let method = req.method().clone();
+            let headers = req.headers().clone();
+            let bytes = req.body;
+            handle_youtube_metrics_purge(&method, &headers, bytes).await
+        }
+        "youtube_channel_totals_rebuild" => {
+            let method = req.method().clone();
+            let headers = req.headers().clone();
+            let bytes = req.body;
+            handle_youtube_channel_totals_rebuild(&method, &headers, bytes).await
+        }
         "youtube_reporting_status" => {
             handle_youtube_reporting_status(req.method(), req.headers(), req.uri()).await
         }
+        "youtube_reporting_export" => {
+            handle_youtube_reporting_export(req.method(), req.headers(), req.uri()).await
+        }
         "youtube_alerts" => {
             let method = req.method().clone();
             let headers = req.headers().clone();
             let uri = req.uri().clone();
             let body = if method == Method::POST {
-                Some(req.into_body().collect().await?.to_bytes())
+                Some(req.body)
             } else {
                 None
             };
             handle_youtube_alerts(&method, &headers, &uri, body).await
         }
+        "youtube_alert_templates" => {
+            let method = req.method().clone();
+            let headers = req.headers().clone();
+            let uri = req.uri().clone();
+            let body = if method == Method::POST {
+                Some(req.body)
+            } else {
+                None
+            };
+            handle_youtube_alert_templates(&method, &headers, &uri, body).await
+        }
+        "youtube_alert_notifications" => {
+            let method = req.method().clone();
+            let headers = req.headers().clone();
+            let uri = req.uri().clone();
+            let body = if method == Method::POST {
+                Some(req.body)
+            } else {
+                None
+            };
+            handle_youtube_alert_notifications(&method, &headers, &uri, body).await
+        }
+        "youtube_rpm_baseline" => {
+            let method = req.method().clone();
+            let headers = req.headers().clone();
+            let uri = req.uri().clone();
+            let body = if method == Method::POST {
+                Some(req.body)
+            } else {
+                None
+            };
+            handle_youtube_rpm_baseline(&method, &headers, &uri, body).await
+        }
         "youtube_experiments" => {
             let method = req.method().clone();
             let headers = req.headers().clone();
             let uri = req.uri().clone();
             let body = if method == Method::POST {
-                Some(req.into_body().collect().await?.to_bytes())
+                Some(req.body)
             } else {
                 None
             };
@@ -6087,43 +11887,778 @@ async fn handler(req: Request) -> Result<Response<ResponseBody>, Error> {
             StatusCode::NOT_FOUND,
             serde_json::json!({"ok": false, "error": "not_found"}),
         ),
-    };
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    run(service_fn(handler)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_config_update_can_rotate_only_the_secret() {
+        let existing = YoutubeOAuthAppConfig {
+            client_id: "client-1".to_string(),
+            client_secret: Some("old-secret".to_string()),
+            redirect_uri: "https://app.example.com/callback".to_string(),
+        };
+        let update: AppConfigUpsertRequest =
+            serde_json::from_str(r#"{"tenant_id":"t1","client_secret":"new-secret"}"#).unwrap();
+
+        let merged = merge_app_config_update(Some(&existing), &update).unwrap();
+        assert_eq!(merged.client_id, "client-1");
+        assert_eq!(merged.redirect_uri, "https://app.example.com/callback");
+        assert_eq!(merged.client_secret.as_deref(), Some("new-secret"));
+
+        assert_eq!(
+            merge_app_config_update(None, &update).err(),
+            Some("client_id is required")
+        );
+    }
+
+    #[tokio::test]
+    async fn start_returns_not_configured_when_tidb_env_missing() {
+        std::env::set_var("RUST_INTERNAL_TOKEN", "secret");
+        std::env::remove_var("TIDB_DATABASE_URL");
+        std::env::remove_var("DATABASE_URL");
+
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer secret".parse().unwrap());
+        headers.insert("content-type", "application/json".parse().unwrap());
+
+        let body = Bytes::from(r#"{"tenant_id":"t1","state":"state123"}"#);
+        let response = handle_start(&Method::POST, &headers, body).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_IMPLEMENTED);
+    }
+
+    #[test]
+    fn fill_zero_days_makes_series_dense() {
+        let d1 = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let d3 = NaiveDate::from_ymd_opt(2026, 3, 3).unwrap();
+        let rows = vec![(d1, 1.0, 10, 5, 0.5, 10), (d3, 2.0, 20, 8, 1.0, 20)];
+
+        let filled = fill_zero_days(rows, d1, d3);
+        let dts: Vec<String> = filled.iter().map(|r| r.0.to_string()).collect();
+        assert_eq!(dts, vec!["2026-03-01", "2026-03-02", "2026-03-03"]);
+        assert_eq!(filled[1].1, 0.0);
+        assert_eq!(filled[1].3, 0);
+        assert_eq!(filled[2].1, 2.0);
+
+        let uri: Uri = "/x?include_zero_days=true".parse().unwrap();
+        assert!(get_query_flag(&uri, "include_zero_days"));
+        let uri: Uri = "/x?include_zero_days=0".parse().unwrap();
+        assert!(!get_query_flag(&uri, "include_zero_days"));
+    }
+
+    #[test]
+    fn channel_median_series_accompanies_video_series() {
+        let d1 = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let d2 = NaiveDate::from_ymd_opt(2026, 3, 3).unwrap();
+        let video_rows = vec![
+            (d1, "v1".to_string(), 1.0, 1000, 50.0, 1000),
+            (d1, "v2".to_string(), 3.0, 1000, 30.0, 1000),
+            (d1, "v3".to_string(), 8.0, 1000, 10.0, 1000),
+            (d2, "v1".to_string(), 2.0, 1000, 0.0, 0),
+            (d2, "v2".to_string(), 0.0, 0, 0.0, 0),
+        ];
+        let video_series = fill_zero_days(
+            vec![
+                (d1, 1.0, 1000, 1000, 50.0, 1000),
+                (d2, 2.0, 0, 1000, 0.0, 0),
+            ],
+            d1,
+            d2,
+        );
+
+        let medians = channel_median_series(
+            video_rows.clone(),
+            MetricsGranularity::Day,
+            CtrFormat::Fraction,
+            DEFAULT_RPM_MIN_VIEWS,
+        );
+        assert_eq!(medians.len(), video_series.len());
+        assert_eq!(medians[0].date, "2026-03-02");
+        assert_eq!(medians[0].median_rpm, Some(3.0));
+        assert_eq!(medians[0].median_ctr, Some(0.03));
+        assert_eq!(medians[0].videos, 3);
+        assert_eq!(medians[1].median_rpm, Some(2.0));
+        assert_eq!(medians[1].median_ctr, None);
+
+        let weekly = channel_median_series(
+            video_rows,
+            MetricsGranularity::Week,
+            CtrFormat::Fraction,
+            DEFAULT_RPM_MIN_VIEWS,
+        );
+        assert_eq!(weekly.len(), 1);
+        assert_eq!(weekly[0].date, "2026-03-02");
+        assert_eq!(weekly[0].median_rpm, Some(3.0));
+    }
+
+    #[test]
+    fn reconcile_revenue_reports_channel_vs_video_gap() {
+        let d = |day| NaiveDate::from_ymd_opt(2026, 3, day).unwrap();
+        let rows = vec![
+            (d(1), Some(10.004), Some(10.001)),
+            (d(2), Some(20.0), Some(19.0)),
+            (d(3), None, Some(5.0)),
+            (d(4), Some(7.0), None),
+        ];
+
+        let r = reconcile_revenue(&rows, 1.0);
+        assert_eq!(r.days_compared, 2);
+        assert_eq!(r.channel_total_usd, 30.0);
+        assert_eq!(r.video_sum_usd, 29.0);
+        assert_eq!(r.abs_diff_usd, 1.0);
+        assert_eq!(r.rel_diff_pct, Some(3.34));
+        assert!(!r.within_tolerance);
+        assert!(reconcile_revenue(&rows, 5.0).within_tolerance);
+
+        assert_eq!(
+            revenue_reconciliation_tolerance_pct(Some(
+                r#"{"revenue_reconciliation_tolerance_pct":2.5}"#
+            )),
+            2.5
+        );
+        assert_eq!(
+            revenue_reconciliation_tolerance_pct(None),
+            DEFAULT_REVENUE_RECONCILIATION_TOLERANCE_PCT
+        );
+    }
+
+    #[tokio::test]
+    async fn handler_errors_carry_a_known_code_and_category() {
+        let uri: Uri = "/api/youtube/kpis?tenant_id=t1".parse().unwrap();
+        let response = handle_youtube_kpis(&Method::POST, &HeaderMap::new(), &uri)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+        let code = ErrorCode::from_code(body["error"].as_str().unwrap()).unwrap();
+        assert_eq!(code, ErrorCode::MethodNotAllowed);
+        assert_eq!(body["error_category"], code.category().as_str());
+        assert!(body["error_docs"].as_str().is_some());
+    }
+
+    #[test]
+    fn pinned_channels_are_returned_with_the_default_flagged() {
+        let pinned = vec![
+            ("UC_a".to_string(), Some("Main".to_string())),
+            ("UC_b".to_string(), None),
+        ];
+
+        let items = pinned_channels_view(Some("UC_b"), &pinned);
+        assert_eq!(
+            items
+                .iter()
+                .map(|i| (i.channel_id.as_str(), i.is_default))
+                .collect::<Vec<_>>(),
+            vec![("UC_a", false), ("UC_b", true)]
+        );
+        assert_eq!(items[0].label.as_deref(), Some("Main"));
+
+        // An active channel that was never pinned still shows up, first.
+        let items = pinned_channels_view(Some("UC_c"), &pinned);
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].channel_id, "UC_c");
+        assert!(items[0].is_default);
+
+        let items = pinned_channels_view(None, &pinned);
+        assert!(items.iter().all(|i| !i.is_default));
+        assert!(pinned_channels_view(None, &[]).is_empty());
+    }
+
+    #[test]
+    fn ctr_as_percent_flag_switches_ctr_shape() {
+        let fraction: Uri = "/api/youtube/metrics?tenant_id=t1".parse().unwrap();
+        let percent: Uri = "/api/youtube/metrics?tenant_id=t1&ctr_as_percent=true"
+            .parse()
+            .unwrap();
+        assert_eq!(CtrFormat::from_uri(&fraction), CtrFormat::Fraction);
+        assert_eq!(CtrFormat::from_uri(&percent), CtrFormat::Percent);
+
+        let ctr = 0.051234;
+        assert_eq!(CtrFormat::Fraction.format(ctr), 0.0512);
+        assert_eq!(CtrFormat::Percent.format(ctr), 5.12);
+        assert_eq!(CtrFormat::Percent.as_str(), "percent");
+
+        let rows = vec![
+            (
+                NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+                "v1".to_string(),
+                1.0,
+                100,
+                50.0,
+                1000,
+            ),
+            (
+                NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+                "v2".to_string(),
+                2.0,
+                100,
+                70.0,
+                1000,
+            ),
+        ];
+        let medians = channel_median_series(
+            rows,
+            MetricsGranularity::Day,
+            CtrFormat::Percent,
+            DEFAULT_RPM_MIN_VIEWS,
+        );
+        assert_eq!(medians[0].median_ctr, Some(6.0));
+    }
+
+    #[test]
+    fn week_aligned_baseline_matches_weekdays() {
+        let dt = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        // Fri 2026-01-02 ..= Sun 2026-01-04 (3 days, weekend-heavy).
+        let (start, end) = (dt("2026-01-02"), dt("2026-01-04"));
+
+        let adjacent = DataHealthBaseline::Adjacent.window(start, end);
+        assert_eq!(adjacent, (dt("2025-12-30"), dt("2026-01-01")));
+        assert_ne!(adjacent.0.weekday(), start.weekday());
+
+        let aligned = DataHealthBaseline::WeekAligned { weeks: 0 }.window(start, end);
+        assert_eq!(aligned, (dt("2025-12-26"), dt("2025-12-28")));
+        assert_eq!(aligned.0.weekday(), start.weekday());
+        assert_eq!(aligned.1.weekday(), end.weekday());
+
+        let two_back = DataHealthBaseline::WeekAligned { weeks: 2 }.window(start, end);
+        assert_eq!(two_back, (dt("2025-12-19"), dt("2025-12-21")));
+
+        // A 10-day window shifts by two weeks so the windows never overlap.
+        let (long_start, long_end) = (dt("2026-01-01"), dt("2026-01-10"));
+        let long = DataHealthBaseline::WeekAligned { weeks: 1 }.window(long_start, long_end);
+        assert_eq!(long, (dt("2025-12-18"), dt("2025-12-27")));
+        assert!(long.1 < long_start);
+
+        let uri: Uri = "/api?baseline=week_aligned&baseline_weeks=3"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            DataHealthBaseline::from_uri(&uri),
+            DataHealthBaseline::WeekAligned { weeks: 3 }
+        );
+        let uri: Uri = "/api?tenant_id=t".parse().unwrap();
+        assert_eq!(
+            DataHealthBaseline::from_uri(&uri),
+            DataHealthBaseline::Adjacent
+        );
+        assert!(DataHealthBaseline::Adjacent.note(start, end).is_none());
+    }
+
+    fn sample_snapshot(title: &str) -> VideoSnapshot {
+        VideoSnapshot {
+            channel_id: Some("c1".to_string()),
+            title: title.to_string(),
+            description: String::new(),
+            category_id: None,
+            tags: None,
+            privacy_status: Some("public".to_string()),
+            publish_at: None,
+            thumbnail_url: Some("https://i.ytimg.com/vi/v1/maxresdefault.jpg".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn video_snapshot_is_cached_within_ttl() {
+        let fetches = std::sync::atomic::AtomicUsize::new(0);
+        let ttl = std::time::Duration::from_secs(60);
+        let fetch = |title: &'static str| {
+            let fetches = &fetches;
+            move || async move {
+                fetches.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                Ok::<_, ()>(sample_snapshot(title))
+            }
+        };
+
+        let (first, _, cached) = cached_video_snapshot("t1:c1:v1", ttl, false, fetch("Original"))
+            .await
+            .unwrap();
+        assert_eq!(first.title, "Original");
+        assert!(!cached);
+
+        let (second, _, cached) = cached_video_snapshot("t1:c1:v1", ttl, false, fetch("Changed"))
+            .await
+            .unwrap();
+        assert_eq!(second.title, "Original");
+        assert!(cached);
+        assert_eq!(fetches.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // Other videos and a disabled cache always hit the API.
+        cached_video_snapshot("t1:c1:v2", ttl, false, fetch("Other"))
+            .await
+            .unwrap();
+        let zero = std::time::Duration::ZERO;
+        cached_video_snapshot("t1:c1:v3", zero, false, fetch("Uncached"))
+            .await
+            .unwrap();
+        let (_, _, cached) = cached_video_snapshot("t1:c1:v3", zero, false, fetch("Uncached"))
+            .await
+            .unwrap();
+        assert!(!cached);
+        assert_eq!(fetches.load(std::sync::atomic::Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn force_refresh_bypasses_the_snapshot_cache() {
+        let fetches = std::sync::atomic::AtomicUsize::new(0);
+        let ttl = std::time::Duration::from_secs(60);
+        let fetch = |title: &'static str| {
+            let fetches = &fetches;
+            move || async move {
+                fetches.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                Ok::<_, ()>(sample_snapshot(title))
+            }
+        };
+
+        cached_video_snapshot("t1:c1:force", ttl, false, fetch("Original"))
+            .await
+            .unwrap();
+        let (forced, _, cached) = cached_video_snapshot("t1:c1:force", ttl, true, fetch("Fresh"))
+            .await
+            .unwrap();
+        assert_eq!(forced.title, "Fresh");
+        assert!(!cached);
+        // The forced read refreshes the entry for later non-forced reads.
+        let (after, _, cached) = cached_video_snapshot("t1:c1:force", ttl, false, fetch("Stale"))
+            .await
+            .unwrap();
+        assert_eq!(after.title, "Fresh");
+        assert!(cached);
+        assert_eq!(fetches.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        let dt = NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+        let row = |video_id: &str, revenue: f64, ctr: Option<f64>| VideoDailyMetricRow {
+            dt,
+            video_id: video_id.to_string(),
+            estimated_revenue_usd: revenue,
+            impressions: 1000,
+            impressions_ctr: ctr,
+            views: 100,
+        };
+        let fetched = vec![row("a", 1.5, Some(0.05)), row("b", 2.5, None)];
+        assert_eq!(
+            analytics_metric_rows(&fetched, None),
+            vec![(dt, 4.0, 2000, 200, 50.0, 1000)]
+        );
+        assert_eq!(
+            analytics_metric_rows(&fetched, Some("b")),
+            vec![(dt, 2.5, 1000, 100, 0.0, 0)]
+        );
+    }
+
+    #[tokio::test]
+    async fn msgpack_bundle_round_trips_to_the_json_structure() {
+        let window = DataHealthWindow {
+            start_dt: "2026-01-01".to_string(),
+            end_dt: "2026-01-28".to_string(),
+            days: 28,
+        };
+        let bundle = serde_json::json!({
+          "ok": true,
+          "channel_id": "UC1",
+          "health": {"window": window, "notes": ["Low coverage"]},
+          "metrics": [{"date": "2026-01-01", "revenue_usd": 12.5, "views": 1200, "ctr": null}],
+          "errors": {},
+        });
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "accept",
+            "application/msgpack, application/json;q=0.5"
+                .parse()
+                .unwrap(),
+        );
+        assert!(wants_msgpack(&headers));
+        let response = bundle_response(&headers, StatusCode::OK, bundle.clone()).unwrap();
+        assert_eq!(response.headers()["content-type"], MSGPACK_CONTENT_TYPE);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let decoded: serde_json::Value = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(decoded, bundle);
+        assert!(bytes.len() < serde_json::to_vec(&bundle).unwrap().len());
+
+        let mut json_headers = HeaderMap::new();
+        json_headers.insert("accept", "*/*".parse().unwrap());
+        assert!(!wants_msgpack(&json_headers));
+        assert!(!wants_msgpack(&HeaderMap::new()));
+        let response = bundle_response(&json_headers, StatusCode::OK, bundle.clone()).unwrap();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let decoded: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(decoded, bundle);
+    }
+
+    #[test]
+    fn effective_config_marks_overrides() {
+        let policy =
+            r#"{"min_days_with_data": 5, "outcome_window_days": 14, "freshness_sla_days": 4}"#;
+        let flags = vec![("geo_monitor".to_string(), false)];
+        let config = effective_config(Some(policy), None, &flags);
+
+        let window = &config["decision_engine"]["outcome_window_days"];
+        assert_eq!(window["value"], 14);
+        assert_eq!(window["source"], "override");
+        // Set to the default value: still reported as the default.
+        assert_eq!(
+            config["decision_engine"]["min_days_with_data"]["source"],
+            "default"
+        );
+        assert_eq!(config["alerts"]["freshness_sla_days"]["value"], 4);
+        assert_eq!(config["alerts"]["freshness_sla_days"]["source"], "override");
+        assert_eq!(config["alerts"]["freshness_sla_days"]["default"], 2);
+        assert_eq!(config["feature_flags"]["geo_monitor"]["value"], false);
+        assert_eq!(config["feature_flags"]["geo_monitor"]["source"], "override");
+        assert_eq!(config["feature_flags"]["experiments"]["source"], "default");
+
+        let retention = effective_config(None, Some("120"), &[]);
+        assert_eq!(
+            retention["retention"]["evidence_retention_days"]["value"],
+            120
+        );
+        assert_eq!(
+            retention["retention"]["evidence_retention_days"]["source"],
+            "override"
+        );
+        assert_eq!(
+            retention["decision_engine"]["catastrophic_drop_pct"]["source"],
+            "default"
+        );
+    }
+
+    #[test]
+    fn revenue_per_active_day_compares_partial_windows_fairly() {
+        let period = |days_with_data: i64, revenue_usd: f64| DataHealthPeriod {
+            source: "channel_total".to_string(),
+            partial: false,
+            days_with_data,
+            last_dt: None,
+            last_updated_at: None,
+            totals: DataHealthTotals::new(days_with_data * 1000, 0, revenue_usd, days_with_data),
+            reach_rows: 0,
+            reach_coverage: "none".to_string(),
+        };
+        // Half the days synced at the same daily rate: raw sums halve, the normalized rate holds.
+        let current = period(14, 700.0);
+        let baseline = period(28, 1400.0);
+        assert_eq!(current.totals.revenue_per_active_day, Some(50.0));
+        assert_eq!(
+            compare_data_health_periods(&current, &baseline),
+            DataHealthComparison {
+                revenue_change_pct: Some(-50.0),
+                revenue_per_active_day_change_pct: Some(0.0),
+            }
+        );
+
+        let empty = period(0, 0.0);
+        assert_eq!(empty.totals.revenue_per_active_day, None);
+        assert_eq!(
+            compare_data_health_periods(&current, &empty).revenue_per_active_day_change_pct,
+            None
+        );
+    }
+
+    #[test]
+    fn metrics_ctr_uses_clicks_for_days_without_stored_ctr() {
+        // A row without a stored CTR adds its clicks over its own impressions; rows with neither
+        // stay out of both sums, so they cannot dilute the day's CTR.
+        assert!(CTR_NUM_SQL.contains(
+            "WHEN impressions_ctr IS NOT NULL THEN impressions_ctr * impressions WHEN clicks IS NOT NULL AND impressions > 0 THEN LEAST(clicks, impressions) END"
+        ));
+        assert!(CTR_DENOM_SQL.contains(
+            "WHEN impressions_ctr IS NOT NULL OR (clicks IS NOT NULL AND impressions > 0) THEN impressions END"
+        ));
+
+        // Every stored-metrics path aggregates in SQL with the same CTR weighting.
+        for (sql, group_by) in [
+            (daily_metrics_sql("video_id = ?"), "GROUP BY dt\n"),
+            (daily_metrics_sql(video_rows_filter()), "GROUP BY dt\n"),
+            (channel_total_daily_metrics_sql(), "GROUP BY dt\n"),
+            (per_video_daily_metrics_sql(), "GROUP BY dt, video_id;"),
+        ] {
+            assert!(sql.contains(&format!("{CTR_NUM_SQL} AS ctr_num")), "{sql}");
+            assert!(
+                sql.contains(&format!("{CTR_DENOM_SQL} AS ctr_denom")),
+                "{sql}"
+            );
+            assert!(sql.contains(group_by), "{sql}");
+        }
+        assert!(daily_metrics_sql("video_id = ?").contains("AND video_id = ?"));
+    }
+
+    #[test]
+    fn top_movers_rank_gainers_and_decliners_from_two_weeks() {
+        // (video_id, previous week's daily revenue/views, this week's daily revenue/views)
+        let seeded = [
+            ("climber", (1.0, 100), (4.0, 300)),
+            ("steady", (2.0, 200), (2.0, 200)),
+            ("faded", (5.0, 500), (1.0, 450)),
+            ("new_upload", (0.0, 0), (2.0, 700)),
+            ("slipping", (3.0, 300), (2.5, 100)),
+        ];
+        let items: Vec<MoverItem> = seeded
+            .iter()
+            .map(|(id, (prev_rev, prev_views), (rev, views))| {
+                MoverItem::new(
+                    id.to_string(),
+                    rev * 7.0,
+                    prev_rev * 7.0,
+                    views * 7,
+                    prev_views * 7,
+                )
+            })
+            .collect();
+
+        let ids = |items: &[MoverItem]| -> Vec<String> {
+            items.iter().map(|item| item.video_id.clone()).collect()
+        };
+
+        let revenue = rank_movers(&items, MoverMetric::Revenue, 5);
+        assert_eq!(ids(&revenue.gainers), vec!["climber", "new_upload"]);
+        assert_eq!(ids(&revenue.decliners), vec!["faded", "slipping"]);
+        assert_eq!(revenue.gainers[0].revenue_delta_usd, 21.0);
+        assert_eq!(revenue.decliners[0].revenue_delta_usd, -28.0);
+
+        let views = rank_movers(&items, MoverMetric::Views, 1);
+        assert_eq!(ids(&views.gainers), vec!["new_upload"]);
+        assert_eq!(views.gainers[0].views_delta, 4900);
+        assert_eq!(ids(&views.decliners), vec!["slipping"]);
+        assert_eq!(views.decliners[0].views_delta, -1400);
+
+        assert_eq!(
+            rank_movers(&[], MoverMetric::Revenue, 5),
+            RankedMovers::default()
+        );
+    }
+
+    #[test]
+    fn decisions_export_csv_has_header_and_escaped_rows() {
+        let d = |day: u32| NaiveDate::from_ymd_opt(2026, 3, day).unwrap();
+        let rows = vec![
+            DecisionOutcomeExportRow {
+                decision_dt: d(1),
+                direction: "reduce_concentration".to_string(),
+                confidence: 0.7,
+                outcome_dt: Some(d(8)),
+                revenue_change_pct_7d: Some(-0.35),
+                catastrophic_flag: true,
+                new_top_asset_flag: true,
+                provisional: false,
+            },
+            DecisionOutcomeExportRow {
+                decision_dt: d(2),
+                direction: "=HYPERLINK(\"http://x\")".to_string(),
+                confidence: 0.5,
+                outcome_dt: None,
+                revenue_change_pct_7d: None,
+                catastrophic_flag: false,
+                new_top_asset_flag: false,
+                provisional: false,
+            },
+        ];
+
+        let csv = String::from_utf8(render_decision_outcomes_csv(&rows).unwrap()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "decision_dt,direction,confidence,outcome_dt,revenue_change_pct_7d,catastrophic_flag,new_top_asset_flag,provisional,outcome_label"
+        );
+        assert_eq!(
+            lines[1],
+            "2026-03-01,reduce_concentration,0.7,2026-03-08,-0.35,1,1,0,catastrophic_drop"
+        );
+        // No outcome yet: the outcome columns stay empty, and the formula is neutralized.
+        assert_eq!(
+            lines[2],
+            "2026-03-02,\"'=HYPERLINK(\"\"http://x\"\")\",0.5,,,,,,"
+        );
+        assert_eq!(lines.len(), 3);
+    }
 
-    match result {
-        Ok(resp) => Ok(resp),
-        Err(err) => {
-            let message = truncate_string(&err.to_string(), 2000);
-            json_response(
-                StatusCode::INTERNAL_SERVER_ERROR,
-                serde_json::json!({"ok": false, "error": "internal_error", "action": action, "message": message}),
-            )
-        }
+    #[test]
+    fn low_view_days_report_a_null_rpm() {
+        // $1 from 2 views would read as a $500 RPM.
+        assert_eq!(rpm_with_floor(1.0, 2, DEFAULT_RPM_MIN_VIEWS), None);
+        assert_eq!(rpm_with_floor(1.0, 0, DEFAULT_RPM_MIN_VIEWS), None);
+        assert_eq!(rpm_with_floor(5.0, 1000, DEFAULT_RPM_MIN_VIEWS), Some(5.0));
+        assert_eq!(rpm_with_floor(1.0, 2, 0), Some(500.0));
+        assert_eq!(rpm_with_floor(0.0, 0, 0), Some(0.0));
+
+        let dt = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let rows = vec![
+            (dt, "v1".to_string(), 2.0, 1000, 0.0, 0),
+            (dt, "v2".to_string(), 4.0, 1000, 0.0, 0),
+            (dt, "tiny".to_string(), 1.0, 2, 0.0, 0),
+        ];
+        let floored = channel_median_series(
+            rows.clone(),
+            MetricsGranularity::Day,
+            CtrFormat::Fraction,
+            DEFAULT_RPM_MIN_VIEWS,
+        );
+        assert_eq!(floored[0].median_rpm, Some(3.0));
+        let unfloored =
+            channel_median_series(rows, MetricsGranularity::Day, CtrFormat::Fraction, 0);
+        assert_eq!(unfloored[0].median_rpm, Some(4.0));
     }
-}
 
-#[tokio::main]
-async fn main() -> Result<(), Error> {
-    run(service_fn(handler)).await
-}
+    #[test]
+    fn excluding_shorts_changes_the_ranking_and_median_views_basis() {
+        let rows: Vec<(String, i64)> = vec![
+            ("short_a".to_string(), 900_000),
+            ("long_a".to_string(), 40_000),
+            ("short_b".to_string(), 500_000),
+            ("long_b".to_string(), 20_000),
+            ("long_c".to_string(), 10_000),
+        ];
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let all = ShortsExclusion::from_sources(false, Some("short_a,short_b"), None);
+        assert!(all.ids.is_empty());
+        assert_eq!(all.sql_filter(), "");
+        let mut views = long_form_views_basis(&rows, &all, 3);
+        assert_eq!(views, vec![900_000, 40_000, 500_000]);
+        assert_eq!(median_i64(&mut views), Some(500_000));
 
-    #[tokio::test]
-    async fn start_returns_not_configured_when_tidb_env_missing() {
-        std::env::set_var("RUST_INTERNAL_TOKEN", "secret");
-        std::env::remove_var("TIDB_DATABASE_URL");
-        std::env::remove_var("DATABASE_URL");
+        let long_form = ShortsExclusion::from_sources(
+            true,
+            Some(" short_a ,,"),
+            Some(r#"{"shorts_video_ids": ["short_b", "short_a"]}"#),
+        );
+        assert_eq!(long_form.ids, vec!["short_a", "short_b"]);
+        assert_eq!(long_form.sql_filter(), "AND video_id NOT IN (?, ?)");
+        let mut views = long_form_views_basis(&rows, &long_form, 3);
+        assert_eq!(views, vec![40_000, 20_000, 10_000]);
+        assert_eq!(median_i64(&mut views), Some(20_000));
 
-        let mut headers = HeaderMap::new();
-        headers.insert("authorization", "Bearer secret".parse().unwrap());
-        headers.insert("content-type", "application/json".parse().unwrap());
+        // The API fallback asks for extra rows, then drops the Shorts from its ranking.
+        assert_eq!(long_form.api_fetch_limit(2), 4);
+        let mut ranked = rows.clone();
+        long_form.retain_long_form(&mut ranked, |(id, _)| id.as_str(), 2);
+        let ids: Vec<&str> = ranked.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["long_a", "long_b"]);
+    }
 
-        let body = Bytes::from(r#"{"tenant_id":"t1","state":"state123"}"#);
-        let response = handle_start(&Method::POST, &headers, body).await.unwrap();
+    #[test]
+    fn manual_rpm_baseline_is_used_and_reported_unless_hinted() {
+        let (rpm, basis) = given_sponsor_rpm(None, Some(7.5)).unwrap();
+        assert_eq!(rpm, 7.5);
+        assert_eq!(basis.method, "manual");
 
-        assert_eq!(response.status(), StatusCode::NOT_IMPLEMENTED);
+        let (rpm, basis) = given_sponsor_rpm(Some(9.0), Some(7.5)).unwrap();
+        assert_eq!(rpm, 9.0);
+        assert_eq!(basis.method, "hint");
+
+        // A zero hint is ignored, so the manual baseline still applies.
+        assert_eq!(
+            given_sponsor_rpm(Some(0.0), Some(7.5)).unwrap().1.method,
+            "manual"
+        );
+        // Without either, the quote derives its RPM from stored metrics.
+        assert_eq!(given_sponsor_rpm(None, None), None);
+    }
+
+    #[test]
+    fn revenue_splits_between_shorts_long_form_and_unknown() {
+        let rows = vec![
+            ("short1".to_string(), 2.0, 3000),
+            ("short2".to_string(), 1.0, 2000),
+            ("long1".to_string(), 12.0, 4000),
+            ("mystery".to_string(), 5.0, 1000),
+        ];
+
+        let both = VideoClassification::from_sources(
+            Some("short1"),
+            Some("long1"),
+            Some(r#"{"shorts_video_ids": ["short2"]}"#),
+        );
+        let split = split_revenue_by_video_class(&rows, &both);
+        assert_eq!(split.total_revenue_usd, 20.0);
+        assert_eq!(split.total_views, 10_000);
+        assert_eq!((split.shorts.videos, split.shorts.views), (2, 5000));
+        assert_eq!(split.shorts.revenue_share, Some(0.15));
+        assert_eq!(split.long_form.revenue_share, Some(0.6));
+        assert_eq!(split.long_form.views_share, Some(0.4));
+        assert_eq!((split.unknown.videos, split.unknown.revenue_usd), (1, 5.0));
+
+        // A Shorts list alone makes every other video long-form.
+        let shorts_only = VideoClassification::from_sources(Some("short1,short2"), None, None);
+        let split = split_revenue_by_video_class(&rows, &shorts_only);
+        assert_eq!(split.long_form.revenue_usd, 17.0);
+        assert_eq!(
+            split.unknown,
+            VideoClassTotals {
+                revenue_share: Some(0.0),
+                views_share: Some(0.0),
+                ..Default::default()
+            }
+        );
+
+        // Without any classification nothing is guessed.
+        let none = split_revenue_by_video_class(&rows, &VideoClassification::default());
+        assert_eq!(none.unknown.revenue_share, Some(1.0));
+        assert_eq!(
+            split_revenue_by_video_class(&[], &VideoClassification::default())
+                .shorts
+                .revenue_share,
+            None
+        );
+    }
+
+    #[test]
+    fn youtube_kpis_sum_trailing_windows() {
+        let end_dt = NaiveDate::from_ymd_opt(2026, 3, 28).unwrap();
+        let rows: Vec<ChannelTotalRow> = (0..30)
+            .map(|i| {
+                let dt = end_dt - Duration::days(i);
+                (dt, 1.25, 0, 100, 0.0, 0)
+            })
+            .collect();
+
+        let kpis = youtube_kpis_from_rows(&rows, end_dt);
+        assert_eq!(
+            kpis,
+            YoutubeKpis {
+                revenue_7d_usd: 8.75,
+                revenue_28d_usd: 35.0,
+                views_7d: 700,
+                views_28d: 2800,
+            }
+        );
+        assert_eq!(youtube_kpis_from_rows(&[], end_dt), YoutubeKpis::default());
+    }
+
+    #[tokio::test]
+    async fn bundle_sections_degrade_independently() {
+        let timeout = std::time::Duration::from_millis(50);
+        let (fast, slow, failing) = tokio::join!(
+            run_bundle_section(timeout, async { Ok::<Vec<i64>, String>(vec![1, 2]) }),
+            run_bundle_section(timeout, async {
+                tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+                Ok::<Vec<i64>, String>(vec![3])
+            }),
+            run_bundle_section(timeout, async {
+                Err::<Option<i64>, String>("boom".to_string())
+            }),
+        );
+
+        let mut errors = serde_json::Map::new();
+        let fast = take_bundle_section(&mut errors, "fast", fast).unwrap_or_default();
+        let slow = take_bundle_section(&mut errors, "slow", slow).unwrap_or_default();
+        let failing = take_bundle_section(&mut errors, "failing", failing).flatten();
+
+        assert_eq!(fast, vec![1, 2]);
+        assert!(slow.is_empty());
+        assert_eq!(failing, None);
+        assert!(!errors.contains_key("fast"));
+        assert_eq!(errors.get("slow").unwrap(), "timed out after 50ms");
+        assert_eq!(errors.get("failing").unwrap(), "boom");
     }
 
     #[tokio::test]
@@ -6135,10 +12670,199 @@ mod tests {
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
 
+    #[test]
+    fn bulk_resolve_targets_all_active_alerts_of_a_kind() {
+        let body = serde_json::json!({"tenant_id": "t1", "kind": "Data reach"});
+        let parsed: BulkResolveAlertsRequest = serde_json::from_value(body).unwrap();
+        let (column, value) =
+            bulk_resolve_alerts_selector(parsed.kind.as_deref(), parsed.alert_key.as_deref())
+                .unwrap();
+        assert_eq!((column, value), ("kind", "Data reach"));
+
+        // One statement covers both reach_reporting_pending and reach_reporting_unavailable.
+        let sql = bulk_resolve_alerts_sql(column);
+        assert!(sql.contains("AND kind = ?"));
+        assert!(sql.contains("AND resolved_at IS NULL"));
+
+        assert_eq!(
+            bulk_resolve_alerts_selector(None, Some("reach_reporting_unavailable")),
+            Some(("alert_key", "reach_reporting_unavailable"))
+        );
+        assert!(bulk_resolve_alerts_sql("alert_key").contains("AND alert_key = ?"));
+        assert_eq!(bulk_resolve_alerts_selector(Some("a"), Some("b")), None);
+        assert_eq!(bulk_resolve_alerts_selector(Some(" "), None), None);
+    }
+
+    #[test]
+    fn alerts_status_resolved_selects_only_resolved_rows() {
+        assert_eq!(
+            AlertStatusFilter::from_query(Some("resolved")),
+            Some(AlertStatusFilter::Resolved)
+        );
+        assert_eq!(
+            AlertStatusFilter::from_query(None),
+            Some(AlertStatusFilter::All)
+        );
+        assert_eq!(AlertStatusFilter::from_query(Some("nope")), None);
+
+        let sql = alerts_list_sql(AlertStatusFilter::Resolved);
+        assert!(sql.contains("AND resolved_at IS NOT NULL"));
+        assert!(sql.contains("ORDER BY resolved_at DESC, id DESC"));
+        assert!(sql.contains("LIMIT ? OFFSET ?"));
+
+        let sql = alerts_list_sql(AlertStatusFilter::Active);
+        assert!(sql.contains("AND resolved_at IS NULL"));
+        assert!(sql.contains("ORDER BY detected_at DESC, id DESC"));
+        assert!(sql.contains("LIMIT ? OFFSET ?"));
+
+        let sql = alerts_list_sql(AlertStatusFilter::All);
+        assert!(!sql.contains("AND resolved_at"));
+        assert!(sql.contains("ORDER BY resolved_at IS NOT NULL, detected_at DESC, id DESC"));
+        assert!(sql.contains("LIMIT ? OFFSET ?"));
+    }
+
+    #[test]
+    fn since_param_limits_alert_lists_to_newer_rows() {
+        assert_eq!(parse_since_param(None), Ok(None));
+        assert_eq!(parse_since_param(Some(" ")), Ok(None));
+        assert!(parse_since_param(Some("yesterday")).is_err());
+        let since = parse_since_param(Some("2026-02-01T10:00:00+02:00"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(datetime_to_rfc3339_utc(since), "2026-02-01T08:00:00+00:00");
+
+        // Every status variant excludes rows not updated after `since` (two binds: NULL check + value).
+        for status in [
+            AlertStatusFilter::Active,
+            AlertStatusFilter::Resolved,
+            AlertStatusFilter::All,
+        ] {
+            let sql = alerts_list_sql(status);
+            assert!(sql.contains("AND (? IS NULL OR updated_at > ?)"));
+            assert_eq!(sql.matches('?').count(), 6);
+        }
+    }
+
+    #[tokio::test]
+    async fn oversized_experiment_body_is_rejected() {
+        let limit = max_body_bytes("youtube_experiments", None);
+        assert_eq!(limit, EXPERIMENT_MAX_BODY_BYTES);
+        let oversized = http_body_util::Full::new(Bytes::from(vec![b' '; limit + 1]));
+        assert_eq!(
+            collect_body_with_limit(oversized, limit).await.unwrap(),
+            None
+        );
+
+        // A max-size thumbnail, base64-encoded as a data URL inside the variant payload, fits.
+        use base64::Engine as _;
+        let thumbnail = vec![0xFF; MAX_UPLOADED_THUMBNAIL_BYTES];
+        let body = serde_json::to_vec(&serde_json::json!({
+            "tenant_id": "t1",
+            "channel_id": "UC1",
+            "type": "thumbnail",
+            "video_ids": ["vid1"],
+            "variants": [
+                {"id": "A", "payload": {}},
+                {"id": "B", "payload": {
+                    "thumbnail_base64": format!(
+                        "data:image/jpeg;base64,{}",
+                        base64::engine::general_purpose::STANDARD.encode(&thumbnail)
+                    ),
+                }},
+            ],
+        }))
+        .unwrap();
+        let len = body.len();
+        let fits = http_body_util::Full::new(Bytes::from(body));
+        assert_eq!(
+            collect_body_with_limit(fits, limit)
+                .await
+                .unwrap()
+                .map(|b| b.len()),
+            Some(len)
+        );
+
+        // Other actions keep the global limit.
+        assert_eq!(
+            max_body_bytes("youtube_alerts", None),
+            DEFAULT_MAX_BODY_BYTES
+        );
+        assert_eq!(max_body_bytes("youtube_alerts", Some("2048")), 2048);
+        assert_eq!(max_body_bytes("youtube_alerts", Some("1")), 1_024);
+
+        // The CSV upload and experiments keep their higher limits, whatever the global one is.
+        assert_eq!(
+            max_body_bytes("youtube_upload_csv", Some("2048")),
+            CSV_MAX_BODY_BYTES
+        );
+        assert_eq!(
+            max_body_bytes("youtube_experiments", Some("2048")),
+            EXPERIMENT_MAX_BODY_BYTES
+        );
+        assert_eq!(
+            max_body_bytes("youtube_experiments", Some("50000000")),
+            50_000_000
+        );
+    }
+
+    #[tokio::test]
+    async fn slow_handler_yields_timeout_response() {
+        let slow = async {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            json_response(StatusCode::OK, serde_json::json!({"ok": true}))
+        };
+
+        let response = run_with_deadline(
+            "youtube_top_videos",
+            std::time::Duration::from_millis(10),
+            slow,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    }
+
+    #[test]
+    fn stored_upload_reprocesses_into_the_same_rows() {
+        let csv =
+            "date,video_id,views,revenue_usd\n01.02.2026,vid1,100,1.5\n02.02.2026,vid1,80,1.0\n";
+        let stored = csv_text_for_storage(csv, DEFAULT_CSV_STORE_MAX_BYTES).unwrap();
+        assert_eq!(csv_text_for_storage(csv, 10), None);
+        assert_eq!(csv_text_for_storage("", DEFAULT_CSV_STORE_MAX_BYTES), None);
+
+        let options = StoredCsvParseOptions {
+            locale_hint: Some("eu".to_string()),
+            date_formats: vec![],
+            max_future_rows: None,
+            reconstruction: Some("prefer_ctr".to_string()),
+        };
+        let options_json = serde_json::to_string(&options).unwrap();
+        let restored = StoredCsvParseOptions::from_json(Some(&options_json));
+        assert_eq!(restored, options);
+        assert_eq!(
+            StoredCsvParseOptions::from_json(None),
+            StoredCsvParseOptions::default()
+        );
+
+        let parse_options = csv_parse_options_for_request(
+            restored.locale_hint.as_deref(),
+            &restored.date_formats,
+            restored.reconstruction.as_deref(),
+        )
+        .unwrap();
+        assert_eq!(parse_options.reconstruction, CsvReconstruction::PreferCtr);
+        let rows = parse_csv_metrics(stored, &parse_options).unwrap().0;
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].dt.to_string(), "2026-02-01");
+        assert_eq!(rows[1].views, 80);
+    }
+
     #[test]
     fn parse_csv_metrics_supports_minimal_schema() {
         let csv = "date,video_id,views,impressions,revenue_usd\n2026-02-01,vid1,100,1000,12.34\n";
-        let rows = parse_csv_metrics(csv).unwrap();
+        let rows = parse_csv_metrics(csv, &CsvParseOptions::default())
+            .unwrap()
+            .0;
         assert_eq!(rows.len(), 1);
         assert_eq!(rows[0].dt.to_string(), "2026-02-01");
         assert_eq!(rows[0].video_id, "vid1");
@@ -6147,6 +12871,619 @@ mod tests {
         assert!((rows[0].estimated_revenue_usd - 12.34).abs() < 1e-6);
     }
 
+    #[test]
+    fn csv_reconstruction_strategies_fill_views_and_impressions() {
+        // Row 1 has impressions + CTR but a disagreeing views column; row 2 lacks impressions.
+        let csv = "date,video_id,impressions,views,ctr\n\
+                   2026-02-01,vid1,1000,30,5%\n\
+                   2026-02-02,vid1,,40,4%\n\
+                   2026-02-03,vid1,2000,,2%\n";
+        let parse = |reconstruction: &str| {
+            let options = csv_parse_options_for_request(None, &[], Some(reconstruction)).unwrap();
+            parse_csv_metrics(csv, &options)
+                .unwrap()
+                .0
+                .into_iter()
+                .map(|row| (row.impressions, row.views))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(parse("prefer_views"), vec![(1000, 30), (0, 40), (2000, 40)]);
+        assert_eq!(
+            parse("prefer_ctr"),
+            vec![(1000, 50), (1000, 40), (2000, 40)]
+        );
+        assert_eq!(parse("strict"), vec![(1000, 30), (0, 40), (2000, 0)]);
+        assert_eq!(
+            csv_parse_options_for_request(None, &[], Some("guess")).unwrap_err(),
+            CSV_RECONSTRUCTION_MESSAGE
+        );
+    }
+
+    #[test]
+    fn parse_csv_metrics_skips_a_bad_row_and_reports_it() {
+        let csv = "Date,video_id,views,revenue_usd\n\
+                   2026-02-01,vid1,100,1.5\n\
+                   not-a-date,vid2,50,0.5\n\
+                   2026-02-02,vid1,80,1.0\n\
+                   2026-02-03,vid1,abc,1.0\n\
+                   2026-02-04,vid2,40,n/a\n\
+                   2026-02-05,vid2,,0.25\n";
+        let (rows, skipped) = parse_csv_metrics(csv, &CsvParseOptions::default()).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(skipped.count, 3);
+        assert_eq!(
+            skipped.errors,
+            vec![
+                CsvRowError {
+                    row: 2,
+                    column: Some("Date".to_string()),
+                    value: Some("not-a-date".to_string()),
+                    reason: "invalid date".to_string(),
+                },
+                CsvRowError {
+                    row: 4,
+                    column: Some("views".to_string()),
+                    value: Some("abc".to_string()),
+                    reason: "invalid number".to_string(),
+                },
+                CsvRowError {
+                    row: 5,
+                    column: Some("revenue_usd".to_string()),
+                    value: Some("n/a".to_string()),
+                    reason: "invalid number".to_string(),
+                },
+            ]
+        );
+        // A blank cell is not an error.
+        assert_eq!(rows[2].views, 0);
+        assert_eq!(rows[2].estimated_revenue_usd, 0.25);
+        let stats = csv_upload_stats(&rows, 0, &skipped);
+        assert_eq!(stats.total_rows, 3);
+        assert_eq!(stats.skipped_rows, 3);
+
+        let all_bad = "date,views\nnope,1\nstill-nope,2\n";
+        let err = parse_csv_metrics(all_bad, &CsvParseOptions::default()).unwrap_err();
+        assert!(err.contains("2 skipped"), "{err}");
+    }
+
+    #[test]
+    fn reporting_export_renders_columns_in_metadata_order() {
+        let columns = vec![
+            "date".to_string(),
+            "channel_id".to_string(),
+            "views".to_string(),
+        ];
+        let rows = vec![
+            vec![
+                Some("20260201".to_string()),
+                Some("UC1".to_string()),
+                Some("10".to_string()),
+            ],
+            vec![Some("20260202".to_string()), None, Some("a,b".to_string())],
+        ];
+
+        let csv = String::from_utf8(render_wide_table_csv(&columns, &rows, true).unwrap()).unwrap();
+        assert_eq!(
+            csv,
+            "date,channel_id,views\n20260201,UC1,10\n20260202,,\"a,b\"\n"
+        );
+
+        // Streamed in chunks, the body is the same bytes as one render.
+        let mut chunked = render_wide_table_csv(&columns, &rows[..1], true).unwrap();
+        chunked.extend(render_wide_table_csv(&columns, &rows[1..], false).unwrap());
+        assert_eq!(String::from_utf8(chunked).unwrap(), csv);
+
+        // A 2,500-row limit reads 1,000 + 1,000 + 500 rows, then stops.
+        let mut sent = 0;
+        let mut chunks = Vec::new();
+        while let Some(n) = reporting_export_chunk_limit(sent, 2_500, REPORTING_EXPORT_CHUNK_ROWS) {
+            chunks.push(n);
+            sent += n;
+        }
+        assert_eq!(chunks, vec![1_000, 1_000, 500]);
+        assert_eq!(
+            reporting_export_chunk_limit(0, 1, REPORTING_EXPORT_CHUNK_ROWS),
+            Some(1)
+        );
+    }
+
+    /// Exports a seeded wide table the way the handler does: the first chunk, then the streamed
+    /// rest. `fetch` answers like the keyset query (rows after the cursor, in key order).
+    async fn export_seeded_table(
+        table: std::sync::Arc<Vec<ReportingExportCursor>>,
+        limit: i64,
+    ) -> Vec<String> {
+        let columns = vec!["views".to_string()];
+        let fetch = move |after: Option<ReportingExportCursor>, n: i64| {
+            let mut page: Vec<ReportingExportCursor> = table
+                .iter()
+                .filter(|key| after.as_ref().is_none_or(|after| *key > after))
+                .cloned()
+                .collect();
+            page.sort();
+            page.truncate(n as usize);
+            let cursor = page.last().cloned();
+            let rows: Vec<Vec<Option<String>>> = page
+                .into_iter()
+                .map(|(report_id, row_no)| vec![Some(format!("{report_id}:{row_no}"))])
+                .collect();
+            async move { Ok::<_, Error>((rows, cursor)) }
+        };
+
+        let first_limit =
+            reporting_export_chunk_limit(0, limit, REPORTING_EXPORT_CHUNK_ROWS).unwrap();
+        let (first_rows, first_cursor) = fetch(None, first_limit).await.unwrap();
+        let mut body = render_wide_table_csv(&columns, &first_rows, true).unwrap();
+        let sent = first_rows.len() as i64;
+        assert_eq!(sent, first_limit);
+
+        let (tx, mut rx) = mpsc::channel(4);
+        let rest = tokio::spawn(send_remaining_export_chunks(
+            move |after, n| fetch(Some(after), n),
+            columns,
+            first_cursor.unwrap(),
+            sent,
+            limit,
+            REPORTING_EXPORT_CHUNK_ROWS,
+            tx,
+        ));
+        while let Some(frame) = rx.recv().await {
+            body.extend_from_slice(&frame.unwrap().into_data().unwrap());
+        }
+        rest.await.unwrap();
+        String::from_utf8(body)
+            .unwrap()
+            .lines()
+            .skip(1)
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn reporting_export_pages_every_row_once_in_keyset_order() {
+        let query = ReportingExportQuery {
+            table_name: "yt_rpt_channel_basic_a2_1a2b3c4d".to_string(),
+            columns: vec!["views".to_string()],
+            tenant_id: "t1".to_string(),
+            owner_id: "CO1".to_string(),
+            report_type_id: "channel_basic_a2".to_string(),
+            start_date: None,
+            end_date: None,
+        };
+        let qb = query.chunk_query(Some(&("r2".to_string(), 7)), REPORTING_EXPORT_CHUNK_ROWS);
+        let sql = qb.sql().to_string();
+        assert!(
+            sql.contains(" AND (report_id > ? OR (report_id = ? AND row_no > ?))"),
+            "{sql}"
+        );
+        assert!(
+            sql.ends_with(" ORDER BY report_id ASC, row_no ASC LIMIT ?;"),
+            "{sql}"
+        );
+
+        // 2,345 rows over three reports, stored out of order; row_no restarts in every report,
+        // so the cursor needs both keys.
+        let mut table: Vec<ReportingExportCursor> = Vec::new();
+        for (report_id, rows) in [("r3", 345), ("r1", 1_200), ("r2", 800)] {
+            for row_no in (1..=rows).rev() {
+                table.push((report_id.to_string(), row_no));
+            }
+        }
+        let mut expected: Vec<String> = {
+            let mut keys = table.clone();
+            keys.sort();
+            keys.into_iter()
+                .map(|(report_id, row_no)| format!("{report_id}:{row_no}"))
+                .collect()
+        };
+        let table = std::sync::Arc::new(table);
+
+        let exported = export_seeded_table(table.clone(), REPORTING_EXPORT_MAX_ROWS).await;
+        assert_eq!(exported.len(), 2_345);
+        assert_eq!(exported, expected);
+
+        // A limit inside the second page stops there, still without repeats.
+        let exported = export_seeded_table(table, 1_500).await;
+        expected.truncate(1_500);
+        assert_eq!(exported, expected);
+    }
+
+    #[test]
+    fn reporting_export_rejects_unsafe_identifiers() {
+        assert!(is_safe_wide_table_identifier(
+            "yt_rpt_channel_basic_a2_1a2b3c4d"
+        ));
+        assert!(!is_safe_wide_table_identifier(
+            "yt_rpt_x`; DROP TABLE t; --"
+        ));
+        assert!(!is_safe_wide_table_identifier("Views"));
+        assert!(!is_safe_wide_table_identifier(""));
+    }
+
+    #[tokio::test]
+    async fn upload_csv_preview_reports_stats_without_database() {
+        std::env::set_var("RUST_INTERNAL_TOKEN", "secret");
+        std::env::remove_var("TIDB_DATABASE_URL");
+        std::env::remove_var("DATABASE_URL");
+
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer secret".parse().unwrap());
+        let body = serde_json::json!({
+          "csv_text": "Date,Video ID,Views,Estimated revenue (USD)\n2026-01-01,v1,100,1.5\n2026-01-02,v2,50,0.5\n"
+        });
+        let response = handle_youtube_upload_csv_preview(
+            &Method::POST,
+            &headers,
+            Bytes::from(body.to_string()),
+        )
+        .await
+        .unwrap();
+        // No DB is configured, so a 200 means nothing was (or could be) written.
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["csv_stats"]["total_rows"], 2);
+        assert_eq!(json["csv_stats"]["per_video_rows"], 2);
+        assert_eq!(json["columns"]["revenue_usd"], "Estimated revenue (USD)");
+        assert_eq!(json["columns"]["impressions"], serde_json::Value::Null);
+        assert_eq!(json["sample_rows"][1]["video_id"], "v2");
+        assert!(json.get("upload_id").is_none());
+    }
+
+    #[test]
+    fn csv_upload_limits_reject_row_count_and_date_span() {
+        let row = |dt: &str, video_id: &str| CsvMetricRow {
+            dt: NaiveDate::parse_from_str(dt, "%Y-%m-%d").unwrap(),
+            video_id: video_id.to_string(),
+            estimated_revenue_usd: 1.0,
+            impressions: 0,
+            impressions_ctr: None,
+            views: 10,
+        };
+        let rows = vec![
+            row("2026-01-01", "a"),
+            row("2026-01-01", "b"),
+            row("2026-01-10", "a"),
+        ];
+
+        let roomy = CsvUploadLimits {
+            max_rows: 3,
+            max_date_span_days: 10,
+        };
+        assert!(check_csv_upload_limits(&rows, roomy).is_ok());
+
+        let (code, _, count) = check_csv_upload_limits(
+            &rows,
+            CsvUploadLimits {
+                max_rows: 2,
+                ..roomy
+            },
+        )
+        .unwrap_err();
+        assert_eq!((code, count), ("csv_too_many_rows", 3));
+
+        let (code, message, count) = check_csv_upload_limits(
+            &rows,
+            CsvUploadLimits {
+                max_date_span_days: 9,
+                ..roomy
+            },
+        )
+        .unwrap_err();
+        assert_eq!((code, count), ("csv_date_span_too_large", 10));
+        assert!(message.contains("2026-01-01..2026-01-10"));
+    }
+
+    #[test]
+    fn parse_csv_metrics_recognizes_configured_column_synonyms() {
+        let csv = "Date,Views,Your estimated revenue (USD)\n2026-02-01,100,1.5\n";
+        let rows = parse_csv_metrics(csv, &CsvParseOptions::default())
+            .unwrap()
+            .0;
+        assert_eq!(rows[0].estimated_revenue_usd, 0.0);
+
+        let mut options = CsvParseOptions::default();
+        options.column_synonyms.revenue = csv_column_synonyms(
+            CSV_REVENUE_COLUMNS,
+            "Your estimated revenue (USD); ;revenue",
+        );
+        assert_eq!(
+            options.column_synonyms.revenue.last().map(String::as_str),
+            Some("your_estimated_revenue_usd")
+        );
+        let rows = parse_csv_metrics(csv, &options).unwrap().0;
+        assert_eq!(rows[0].estimated_revenue_usd, 1.5);
+        assert_eq!(rows[0].views, 100);
+    }
+
+    #[test]
+    fn parse_csv_metrics_accepts_european_dates_with_locale_hint() {
+        let csv = "date,views,revenue_usd\n01-02-2026,100,1.5\n13.02.2026,50,0.5\n";
+        assert!(parse_csv_metrics(csv, &CsvParseOptions::default()).is_err());
+
+        let options = CsvParseOptions {
+            date_formats: csv_date_formats(CsvDateLocale::from_hint("eu").unwrap(), &[]),
+            ..CsvParseOptions::default()
+        };
+        let rows = parse_csv_metrics(csv, &options).unwrap().0;
+        assert_eq!(rows[0].dt.to_string(), "2026-02-01");
+        assert_eq!(rows[1].dt.to_string(), "2026-02-13");
+    }
+
+    #[test]
+    fn custom_sponsor_deliverables_produce_expected_fee_ranges() {
+        let custom: Vec<SponsorDeliverable> = serde_json::from_value(serde_json::json!([
+          {"name": "pre-roll mention", "multiplier": 0.5},
+          {"name": "community post", "multiplier": 0.25, "format": "shorts"}
+        ]))
+        .unwrap();
+        let deliverables = resolve_sponsor_deliverables(Some(custom)).unwrap();
+        let lines = sponsor_quote_lines(&deliverables, 100_000, 40_000, 10.0, 20.0);
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].deliverable, "pre-roll mention");
+        assert_eq!(lines[0].flat_fee_range, (500, 1000));
+        assert_eq!(lines[1].deliverable, "community post");
+        assert_eq!(lines[1].flat_fee_range, (100, 200));
+        assert_eq!(lines[1].avg_views_used, 40_000);
+
+        assert_eq!(
+            resolve_sponsor_deliverables(None).unwrap(),
+            default_sponsor_deliverables()
+        );
+        let too_big: Vec<SponsorDeliverable> =
+            serde_json::from_value(serde_json::json!([{"name": "x", "multiplier": 50.0}])).unwrap();
+        assert!(resolve_sponsor_deliverables(Some(too_big)).is_err());
+    }
+
+    #[test]
+    fn blended_sponsor_rpm_smooths_the_latest_window() {
+        let end = NaiveDate::from_ymd_opt(2026, 3, 31).unwrap();
+        // Three 28-day windows at $10, $10 and (most recent) $40 RPM.
+        let rows: Vec<(NaiveDate, f64, i64)> = (0..84)
+            .map(|i| {
+                let dt = end - Duration::days(i);
+                let revenue = if i < 28 { 40.0 } else { 10.0 };
+                (dt, revenue, 1000)
+            })
+            .collect();
+
+        let (single, n) = blended_window_rpm(&rows, end, 1, SPONSOR_RPM_WINDOW_DAYS).unwrap();
+        assert_eq!((round2(single), n), (40.0, 1));
+
+        let (blended, n) = blended_window_rpm(
+            &rows,
+            end,
+            SPONSOR_RPM_BLENDED_WINDOWS,
+            SPONSOR_RPM_WINDOW_DAYS,
+        )
+        .unwrap();
+        assert_eq!((round2(blended), n), (20.0, 3));
+    }
+
+    #[test]
+    fn trimmed_sponsor_rpm_drops_outlier_days() {
+        let d = |day: u32| NaiveDate::from_ymd_opt(2026, 3, day).unwrap();
+        let mut rows: Vec<(NaiveDate, f64, i64)> = (1..=8).map(|i| (d(i), 10.0, 1000)).collect();
+        rows.push((d(9), 1000.0, 1000));
+        rows.push((d(10), 0.1, 1000));
+
+        let (rpm, kept) = trimmed_daily_rpm(&rows, SPONSOR_RPM_TRIM_FRACTION).unwrap();
+        assert_eq!((round2(rpm), kept), (10.0, 8));
+        assert!(SponsorRpmMethod::from_param(Some("median")).is_none());
+    }
+
+    #[test]
+    fn saved_sponsor_quote_round_trips_by_id() {
+        let stored = StoredSponsorQuote {
+            quote_id: "quote_abc123".to_string(),
+            channel_id: "UC123".to_string(),
+            niches: vec!["tech".to_string()],
+            inputs: SponsorQuoteInputs {
+                avg_views_long: 50_000,
+                avg_views_shorts: 30_000,
+                rpm_hint: None,
+                rpm_base: 12.0,
+                rpm_basis: None,
+                deliverables: default_sponsor_deliverables(),
+            },
+            quotes: vec![SponsorQuoteLine {
+                deliverable: "integration".to_string(),
+                cpm_range: (9.6, 16.8),
+                flat_fee_range: (480, 840),
+                avg_views_used: 50_000,
+            }],
+            created_at: "2026-02-01T00:00:00Z".to_string(),
+        };
+
+        let quote_json = serde_json::to_string(&stored).unwrap();
+        let fetched: StoredSponsorQuote = serde_json::from_str(&quote_json).unwrap();
+        assert_eq!(fetched, stored);
+
+        let share = sponsor_quote_share_payload(&fetched);
+        assert_eq!(share["lines"][0]["deliverable"], "integration");
+        assert_eq!(share["lines"][0]["fee_label"], "$480–$840");
+
+        // The public share response leaves out the creator's RPM inputs.
+        let body = sponsor_quote_public_body(&fetched);
+        assert_eq!(body["share"], share);
+        assert!(body.get("quote").is_none());
+        let raw = body.to_string();
+        for private in ["inputs", "rpm_base", "rpm_hint", "rpm_basis"] {
+            assert!(!raw.contains(private), "{private} leaked into {raw}");
+        }
+    }
+
+    #[test]
+    fn weekly_metric_buckets_sum_their_days() {
+        let d = |m: u32, day: u32| NaiveDate::from_ymd_opt(2026, m, day).unwrap();
+        // 2026-02-01 is a Sunday, so it closes the ISO week starting Monday 2026-01-26.
+        let rows = vec![
+            (d(2, 1), 1.0, 100, 10, 5.0, 100),
+            (d(2, 2), 2.0, 200, 20, 0.0, 0),
+            (d(2, 8), 3.0, 300, 30, 30.0, 300),
+            (d(2, 9), 4.0, 400, 40, 40.0, 400),
+        ];
+
+        let weekly = bucket_metric_rows(rows.clone(), MetricsGranularity::Week);
+        assert_eq!(
+            weekly,
+            vec![
+                (d(1, 26), 1.0, 100, 10, 5.0, 100),
+                (d(2, 2), 5.0, 500, 50, 30.0, 300),
+                (d(2, 9), 4.0, 400, 40, 40.0, 400),
+            ]
+        );
+
+        let monthly = bucket_metric_rows(rows.clone(), MetricsGranularity::Month);
+        assert_eq!(monthly, vec![(d(2, 1), 10.0, 1000, 100, 75.0, 800)]);
+        assert_eq!(
+            bucket_metric_rows(rows.clone(), MetricsGranularity::Day),
+            rows
+        );
+        assert!(MetricsGranularity::from_query(Some("year")).is_none());
+    }
+
+    #[test]
+    fn freshness_sla_is_configurable_per_tenant() {
+        assert_eq!(freshness_sla_days(None), DEFAULT_FRESHNESS_SLA_DAYS);
+        assert_eq!(
+            freshness_sla_days(Some(r#"{"min_days_with_data":7}"#)),
+            DEFAULT_FRESHNESS_SLA_DAYS
+        );
+        let four = freshness_sla_days(Some(r#"{"freshness_sla_days":4}"#));
+        assert_eq!(four, 4);
+
+        assert!(freshness_sla_breached(Some(3), DEFAULT_FRESHNESS_SLA_DAYS));
+        assert!(!freshness_sla_breached(Some(3), four));
+        assert!(!freshness_sla_breached(Some(2), DEFAULT_FRESHNESS_SLA_DAYS));
+        assert!(freshness_sla_breached(None, four));
+    }
+
+    #[test]
+    fn decision_history_annotates_direction_flips() {
+        let d = |day: u32| NaiveDate::from_ymd_opt(2026, 1, day).unwrap();
+        let items = annotate_direction_changes(vec![
+            (d(1), "PROTECT".to_string(), 0.6),
+            (d(2), "PROTECT".to_string(), 0.65),
+            (d(3), "EXPAND".to_string(), 0.7),
+            (d(5), "EXPAND".to_string(), 0.72),
+        ]);
+
+        let changed: Vec<bool> = items.iter().map(|i| i.direction_changed).collect();
+        assert_eq!(changed, vec![false, false, true, false]);
+        assert_eq!(items[0].previous_direction, None);
+        assert_eq!(items[2].previous_direction.as_deref(), Some("PROTECT"));
+        assert_eq!(items[3].previous_direction.as_deref(), Some("EXPAND"));
+    }
+
+    #[test]
+    fn window_completeness_reports_missing_day() {
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+        let present: Vec<NaiveDate> = [1, 2, 4, 5]
+            .iter()
+            .map(|d| NaiveDate::from_ymd_opt(2026, 1, *d).unwrap())
+            .collect();
+
+        let c = window_completeness(start, end, &present);
+        assert_eq!(c.expected_days, 5);
+        assert_eq!(c.present_days, 4);
+        assert_eq!(c.missing_dates, vec!["2026-01-03".to_string()]);
+        assert!(!c.missing_dates_truncated);
+        assert!(!c.complete);
+
+        let long_end = NaiveDate::from_ymd_opt(2026, 3, 31).unwrap();
+        let c = window_completeness(start, long_end, &[]);
+        assert_eq!(c.present_days, 0);
+        assert_eq!(c.missing_dates.len(), COMPLETENESS_MAX_MISSING_DATES);
+        assert!(c.missing_dates_truncated);
+    }
+
+    #[test]
+    fn reach_coverage_distinguishes_missing_from_zero_impressions() {
+        assert_eq!(reach_coverage(0, 0), "missing");
+        assert_eq!(reach_coverage(7, 0), "zero");
+        assert_eq!(reach_coverage(7, 1200), "populated");
+    }
+
+    #[test]
+    fn metrics_purge_scope_targets_only_requested_range() {
+        let d = |day: u32| NaiveDate::from_ymd_opt(2026, 2, day).unwrap();
+        let scope = MetricsPurgeScope {
+            start_dt: d(3),
+            end_dt: d(5),
+            video_id: None,
+            source: MetricsPurgeSource::All,
+        };
+        assert_eq!(
+            scope.delete_sql(),
+            "DELETE FROM video_daily_metrics WHERE tenant_id = ? AND channel_id = ? AND dt BETWEEN ? AND ?;"
+        );
+
+        let csv_only = MetricsPurgeScope {
+            source: MetricsPurgeSource::from_param(Some("csv")).unwrap(),
+            ..scope.clone()
+        };
+        assert!(csv_only.delete_sql().ends_with(&format!(
+            "dt BETWEEN ? AND ? AND video_id = '{CSV_CHANNEL_TOTAL_VIDEO_ID}';"
+        )));
+
+        let one_video = MetricsPurgeScope {
+            video_id: Some("vid_a".to_string()),
+            source: MetricsPurgeSource::Videos,
+            ..scope
+        };
+        assert!(one_video.delete_sql().ends_with(&format!(
+            "dt BETWEEN ? AND ? AND video_id = ? AND {};",
+            video_rows_filter()
+        )));
+        assert!(MetricsPurgeSource::from_param(Some("everything")).is_none());
+    }
+
+    #[test]
+    fn future_dated_csv_rows_are_dropped_and_counted() {
+        let csv =
+            "date,views,revenue_usd\n2026-02-01,100,1.5\n2026-02-03,10,0.1\n2099-01-01,5,9.0\n";
+        let rows = parse_csv_metrics(csv, &CsvParseOptions::default())
+            .unwrap()
+            .0;
+        let today = NaiveDate::from_ymd_opt(2026, 2, 2).unwrap();
+
+        let (kept, dropped) = drop_future_dated_rows(rows, today, CSV_FUTURE_DATE_SKEW_DAYS);
+        assert_eq!(dropped, 1);
+        assert_eq!(kept.len(), 2);
+        assert!(kept.iter().all(|r| r.dt.to_string() != "2099-01-01"));
+    }
+
+    #[test]
+    fn csv_date_locale_resolves_ambiguous_slash_dates() {
+        let us = csv_date_formats(CsvDateLocale::MonthFirst, &[]);
+        let eu = csv_date_formats(CsvDateLocale::DayFirst, &[]);
+        assert_eq!(
+            parse_dt_with_formats("01/02/2026", &us)
+                .unwrap()
+                .to_string(),
+            "2026-01-02"
+        );
+        assert_eq!(
+            parse_dt_with_formats("01/02/2026", &eu)
+                .unwrap()
+                .to_string(),
+            "2026-02-01"
+        );
+
+        let extra = csv_date_formats(CsvDateLocale::MonthFirst, &["%Y.%m.%d".to_string()]);
+        assert_eq!(
+            parse_dt_with_formats("2026.02.01", &extra)
+                .unwrap()
+                .to_string(),
+            "2026-02-01"
+        );
+    }
+
     #[test]
     fn csv_upload_row_created_at_is_datetime_utc() {
         let row: CsvUploadRow = (
@@ -11,6 +11,8 @@ use globa_flux_rust::db::{
     set_tenant_ai_provider_status, update_tenant_ai_provider_test_status, upsert_tenant_ai_provider_setting,
     upsert_tenant_ai_routing_policy,
 };
+use globa_flux_rust::error_codes::annotate_error_body;
+use globa_flux_rust::http_client::http_client_for_url;
 use globa_flux_rust::providers::gemini::{generate_text as gemini_generate_text, GeminiConfig};
 use globa_flux_rust::secrets::{decrypt_secret, encrypt_secret};
 
@@ -23,8 +25,9 @@ fn bearer_token(header_value: Option<&str>) -> Option<&str> {
 
 fn json_response(
     status: StatusCode,
-    value: serde_json::Value,
+    mut value: serde_json::Value,
 ) -> Result<Response<ResponseBody>, Error> {
+    annotate_error_body(&mut value);
     Ok(Response::builder()
         .status(status)
         .header("content-type", "application/json; charset=utf-8")
@@ -146,8 +149,9 @@ async fn openai_test_connection(api_key: &str, model: &str) -> Result<(), Error>
       ]
     });
 
-    let resp = reqwest::Client::new()
-        .post(url)
+    let resp = http_client_for_url(&url)
+        .map_err(|e| -> Error { Box::new(std::io::Error::other(e.to_string())) })?
+        .post(&url)
         .bearer_auth(api_key)
         .header(reqwest::header::CONTENT_TYPE, "application/json")
         .header(reqwest::header::ACCEPT, "application/json")
@@ -187,8 +191,9 @@ async fn anthropic_test_connection(api_key: &str, model: &str) -> Result<(), Err
       "messages": [{"role":"user","content":"Ping"}]
     });
 
-    let resp = reqwest::Client::new()
-        .post(url)
+    let resp = http_client_for_url(&url)
+        .map_err(|e| -> Error { Box::new(std::io::Error::other(e.to_string())) })?
+        .post(&url)
         .header("x-api-key", api_key)
         .header("anthropic-version", "2023-06-01")
         .header(reqwest::header::CONTENT_TYPE, "application/json")
@@ -5,6 +5,7 @@ use serde::Deserialize;
 use vercel_runtime::{run, service_fn, Error, Request, Response, ResponseBody};
 
 use globa_flux_rust::db::{ensure_trial_started, get_pool};
+use globa_flux_rust::error_codes::annotate_error_body;
 
 fn bearer_token(header_value: Option<&str>) -> Option<&str> {
     let value = header_value?;
@@ -15,8 +16,9 @@ fn bearer_token(header_value: Option<&str>) -> Option<&str> {
 
 fn json_response(
     status: StatusCode,
-    value: serde_json::Value,
+    mut value: serde_json::Value,
 ) -> Result<Response<ResponseBody>, Error> {
+    annotate_error_body(&mut value);
     Ok(Response::builder()
         .status(status)
         .header("content-type", "application/json; charset=utf-8")
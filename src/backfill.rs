@@ -1,3 +1,7 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Poll;
+
 use chrono::{Duration, NaiveDate};
 
 pub fn compute_backfill_run_for_dates(
@@ -21,6 +25,214 @@ pub fn compute_backfill_run_for_dates(
     out
 }
 
+/// Trailing days re-fetched on every incremental sync; YouTube revises the newest days.
+pub const SYNC_OVERLAP_DAYS: i64 = 2;
+
+/// First date daily_channel must fetch for `start_dt..=end_dt` given the channel's
+/// `last_synced_dt` watermark. A watermark outside the window (first sync, a long outage, or a
+/// backfill task for an older window) fetches the whole window.
+pub fn incremental_fetch_start(
+    start_dt: NaiveDate,
+    end_dt: NaiveDate,
+    last_synced_dt: Option<NaiveDate>,
+    overlap_days: i64,
+) -> NaiveDate {
+    match last_synced_dt {
+        Some(watermark) if watermark >= start_dt && watermark <= end_dt => {
+            (watermark + Duration::days(1) - Duration::days(overlap_days.max(1)))
+                .clamp(start_dt, end_dt)
+        }
+        _ => start_dt,
+    }
+}
+
+/// Dates in `start_dt..=end_dt` that have no metrics rows (`present_dts` need not be sorted).
+pub fn find_date_gaps(
+    present_dts: &[NaiveDate],
+    start_dt: NaiveDate,
+    end_dt: NaiveDate,
+) -> Vec<NaiveDate> {
+    let present: std::collections::HashSet<NaiveDate> = present_dts.iter().copied().collect();
+    start_dt
+        .iter_days()
+        .take_while(|dt| *dt <= end_dt)
+        .filter(|dt| !present.contains(dt))
+        .collect()
+}
+
+/// Gaps no successful refill has fetched yet; `refilled` are the dates a finished refill task
+/// already covered. A date still missing after that had no activity, so it is not retried.
+pub fn unrefilled_gaps(gaps: &[NaiveDate], refilled: &[NaiveDate]) -> Vec<NaiveDate> {
+    let refilled: std::collections::HashSet<NaiveDate> = refilled.iter().copied().collect();
+    gaps.iter()
+        .copied()
+        .filter(|dt| !refilled.contains(dt))
+        .collect()
+}
+
+/// Picks daily_channel `run_for_dt`s (each covering `run_for_dt - 7 ..= run_for_dt - 1`) so
+/// every gap is inside at least one window, never scheduling past `max_run_for_dt`.
+pub fn gap_fill_run_for_dates(gaps: &[NaiveDate], max_run_for_dt: NaiveDate) -> Vec<NaiveDate> {
+    let mut sorted = gaps.to_vec();
+    sorted.sort();
+    sorted.dedup();
+
+    let mut out: Vec<NaiveDate> = Vec::new();
+    for gap in sorted {
+        if let Some(last) = out.last() {
+            if gap >= *last - Duration::days(7) && gap < *last {
+                continue;
+            }
+        }
+        let run_for_dt = (gap + Duration::days(7)).min(max_run_for_dt);
+        if run_for_dt <= gap {
+            continue;
+        }
+        out.push(run_for_dt);
+    }
+
+    // Insert newest first so the worker processes recent gaps first (ORDER BY id ASC).
+    out.reverse();
+    out
+}
+
+/// Upper bound on dates accepted in one explicit dispatch.
+pub const MAX_EXPLICIT_RUN_FOR_DTS: usize = 60;
+
+/// Validates an explicit `run_for_dts` list: `YYYY-MM-DD` dates, none after `max_run_for_dt`,
+/// at most `cap` distinct dates. Returns them deduped, newest first.
+pub fn explicit_run_for_dates(
+    raw: &[String],
+    max_run_for_dt: NaiveDate,
+    cap: usize,
+) -> Result<Vec<NaiveDate>, String> {
+    let mut out: Vec<NaiveDate> = Vec::with_capacity(raw.len());
+    for value in raw {
+        let value = value.trim();
+        let dt = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|_| format!("invalid run_for_dts entry: {value}"))?;
+        if dt > max_run_for_dt {
+            return Err(format!("run_for_dts entry {dt} is after {max_run_for_dt}"));
+        }
+        out.push(dt);
+    }
+    out.sort();
+    out.dedup();
+    if out.is_empty() {
+        return Err("run_for_dts must not be empty".to_string());
+    }
+    if out.len() > cap {
+        return Err(format!("run_for_dts accepts at most {cap} dates"));
+    }
+
+    // Insert newest first so the worker processes current data first (ORDER BY id ASC).
+    out.reverse();
+    Ok(out)
+}
+
+/// Rows per multi-row metric upsert statement.
+pub const METRIC_UPSERT_BATCH_SIZE: usize = 200;
+pub const DEFAULT_UPSERT_CONCURRENCY: usize = 2;
+/// Stays below the pool's `max_connections(5)` so a task's upserts never starve other queries.
+pub const MAX_UPSERT_CONCURRENCY: usize = 4;
+
+/// `UPSERT_CONCURRENCY` (default 2, clamped to 1..=4): batch statements a single task keeps in
+/// flight at once.
+pub fn upsert_concurrency() -> usize {
+    upsert_concurrency_from_value(std::env::var("UPSERT_CONCURRENCY").ok().as_deref())
+}
+
+pub fn upsert_concurrency_from_value(value: Option<&str>) -> usize {
+    value
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .unwrap_or(DEFAULT_UPSERT_CONCURRENCY)
+        .clamp(1, MAX_UPSERT_CONCURRENCY)
+}
+
+/// Runs `run` for every chunk with at most `concurrency` futures in flight. The first error is
+/// returned as soon as it completes; the remaining in-flight futures are dropped (cancelled) and
+/// no further chunks are started. Returns how many chunks completed.
+pub async fn run_bounded_chunks<T, F, Fut, E>(
+    chunks: Vec<T>,
+    concurrency: usize,
+    mut run: F,
+) -> Result<usize, E>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+{
+    let concurrency = concurrency.max(1);
+    let mut pending = chunks.into_iter();
+    let mut in_flight: Vec<Pin<Box<Fut>>> = Vec::with_capacity(concurrency);
+    let mut completed = 0usize;
+
+    loop {
+        while in_flight.len() < concurrency {
+            match pending.next() {
+                Some(chunk) => in_flight.push(Box::pin(run(chunk))),
+                None => break,
+            }
+        }
+        if in_flight.is_empty() {
+            return Ok(completed);
+        }
+
+        let result = std::future::poll_fn(|cx| {
+            for idx in 0..in_flight.len() {
+                if let Poll::Ready(result) = in_flight[idx].as_mut().poll(cx) {
+                    drop(in_flight.swap_remove(idx));
+                    return Poll::Ready(result);
+                }
+            }
+            Poll::Pending
+        })
+        .await;
+        result?;
+        completed += 1;
+    }
+}
+
+/// Like `run_bounded_chunks`, but a failing item does not stop the others: every item runs (at
+/// most `concurrency` at once) and the results come back in input order.
+pub async fn run_bounded_isolated<T, F, Fut, R>(
+    items: Vec<T>,
+    concurrency: usize,
+    mut run: F,
+) -> Vec<R>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = R>,
+{
+    let concurrency = concurrency.max(1);
+    let mut results: Vec<Option<R>> = items.iter().map(|_| None).collect();
+    let mut pending = items.into_iter().enumerate();
+    let mut in_flight: Vec<(usize, Pin<Box<Fut>>)> = Vec::with_capacity(concurrency);
+
+    loop {
+        while in_flight.len() < concurrency {
+            match pending.next() {
+                Some((idx, item)) => in_flight.push((idx, Box::pin(run(item)))),
+                None => break,
+            }
+        }
+        if in_flight.is_empty() {
+            return results.into_iter().flatten().collect();
+        }
+
+        let (idx, result) = std::future::poll_fn(|cx| {
+            for pos in 0..in_flight.len() {
+                if let Poll::Ready(result) = in_flight[pos].1.as_mut().poll(cx) {
+                    let (idx, _) = in_flight.swap_remove(pos);
+                    return Poll::Ready((idx, result));
+                }
+            }
+            Poll::Pending
+        })
+        .await;
+        results[idx] = Some(result);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -29,6 +241,29 @@ mod tests {
         NaiveDate::from_ymd_opt(y, m, d).unwrap()
     }
 
+    #[test]
+    fn advanced_watermark_fetches_a_shorter_window() {
+        // run_for_dt 2026-01-09 covers 01-02..=01-08.
+        let (start_dt, end_dt) = (d(2026, 1, 2), d(2026, 1, 8));
+        let first = incremental_fetch_start(start_dt, end_dt, None, SYNC_OVERLAP_DAYS);
+        assert_eq!(first, start_dt);
+
+        // The previous day's run synced through 01-07; only the overlap plus the new day remain.
+        let second =
+            incremental_fetch_start(start_dt, end_dt, Some(d(2026, 1, 7)), SYNC_OVERLAP_DAYS);
+        assert_eq!(second, d(2026, 1, 6));
+        assert!((end_dt - second).num_days() < (end_dt - first).num_days());
+
+        // A backfill task for an older window ignores the newer watermark.
+        let backfill = incremental_fetch_start(
+            d(2025, 12, 1),
+            d(2025, 12, 7),
+            Some(d(2026, 1, 8)),
+            SYNC_OVERLAP_DAYS,
+        );
+        assert_eq!(backfill, d(2025, 12, 1));
+    }
+
     #[test]
     fn weekly_chunks_cover_14_days_as_two_tasks() {
         let end_dt = d(2026, 1, 8);
@@ -55,4 +290,131 @@ mod tests {
         sorted.dedup();
         assert_eq!(sorted, run_for);
     }
+
+    #[test]
+    fn detects_seeded_gap_and_schedules_one_window_for_it() {
+        let present: Vec<NaiveDate> = d(2026, 1, 1)
+            .iter_days()
+            .take(28)
+            .filter(|dt| !(d(2026, 1, 10)..=d(2026, 1, 12)).contains(dt))
+            .collect();
+
+        let gaps = find_date_gaps(&present, d(2026, 1, 1), d(2026, 1, 28));
+        assert_eq!(gaps, vec![d(2026, 1, 10), d(2026, 1, 11), d(2026, 1, 12)]);
+
+        let run_for = gap_fill_run_for_dates(&gaps, d(2026, 2, 5));
+        assert_eq!(run_for, vec![d(2026, 1, 17)]);
+
+        assert!(find_date_gaps(&present, d(2026, 1, 13), d(2026, 1, 28)).is_empty());
+
+        // The next dispatch finds 01-10..=01-12 still empty (no activity) after their refill;
+        // only a newly missed day is scheduled.
+        let mut present_later = present.clone();
+        present_later.retain(|dt| *dt != d(2026, 1, 20));
+        let gaps_later = find_date_gaps(&present_later, d(2026, 1, 1), d(2026, 1, 28));
+        let new_gaps = unrefilled_gaps(&gaps_later, &gaps);
+        assert_eq!(new_gaps, vec![d(2026, 1, 20)]);
+        assert_eq!(
+            gap_fill_run_for_dates(&new_gaps, d(2026, 2, 5)),
+            vec![d(2026, 1, 27)]
+        );
+        assert!(unrefilled_gaps(&gaps, &gaps).is_empty());
+        // A refill that never succeeded leaves its gaps to be scheduled again.
+        assert_eq!(unrefilled_gaps(&gaps, &[]), gaps);
+    }
+
+    #[test]
+    fn gap_fill_splits_distant_gaps_and_caps_at_max_run_for_dt() {
+        let gaps = vec![d(2026, 1, 2), d(2026, 1, 20), d(2026, 1, 3)];
+        let run_for = gap_fill_run_for_dates(&gaps, d(2026, 1, 22));
+        assert_eq!(run_for, vec![d(2026, 1, 22), d(2026, 1, 9)]);
+    }
+
+    #[test]
+    fn explicit_run_for_dts_enqueue_exactly_the_given_dates() {
+        let raw: Vec<String> = ["2026-01-09", " 2026-01-02", "2026-01-09", "2025-12-26"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let run_for = explicit_run_for_dates(&raw, d(2026, 1, 10), MAX_EXPLICIT_RUN_FOR_DTS);
+        assert_eq!(
+            run_for,
+            Ok(vec![d(2026, 1, 9), d(2026, 1, 2), d(2025, 12, 26)])
+        );
+
+        assert!(explicit_run_for_dates(&["2026-13-01".to_string()], d(2026, 1, 10), 60).is_err());
+        assert!(explicit_run_for_dates(&["2026-01-11".to_string()], d(2026, 1, 10), 60).is_err());
+        assert!(explicit_run_for_dates(&[], d(2026, 1, 10), 60).is_err());
+        assert!(explicit_run_for_dates(&raw, d(2026, 1, 10), 2).is_err());
+    }
+
+    #[test]
+    fn upsert_concurrency_is_clamped() {
+        assert_eq!(
+            upsert_concurrency_from_value(None),
+            DEFAULT_UPSERT_CONCURRENCY
+        );
+        assert_eq!(upsert_concurrency_from_value(Some("3")), 3);
+        assert_eq!(upsert_concurrency_from_value(Some("0")), 1);
+        assert_eq!(
+            upsert_concurrency_from_value(Some("64")),
+            MAX_UPSERT_CONCURRENCY
+        );
+        assert_eq!(
+            upsert_concurrency_from_value(Some("x")),
+            DEFAULT_UPSERT_CONCURRENCY
+        );
+    }
+
+    #[tokio::test]
+    async fn concurrent_batches_all_land_and_first_error_stops_the_rest() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::{Arc, Mutex};
+
+        let rows: Vec<u32> = (0..1_000).collect();
+        let chunks: Vec<Vec<u32>> = rows.chunks(64).map(|c| c.to_vec()).collect();
+        let landed = Arc::new(Mutex::new(Vec::new()));
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let completed = run_bounded_chunks(chunks.clone(), 3, |chunk| {
+            let landed = landed.clone();
+            let active = active.clone();
+            let peak = peak.clone();
+            async move {
+                let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis((chunk[0] % 5) as u64)).await;
+                landed.lock().unwrap().extend(chunk);
+                active.fetch_sub(1, Ordering::SeqCst);
+                Ok::<(), String>(())
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(completed, chunks.len());
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+        let mut landed = landed.lock().unwrap().clone();
+        landed.sort_unstable();
+        assert_eq!(landed, rows);
+
+        let started = Arc::new(AtomicUsize::new(0));
+        let err = run_bounded_chunks(chunks.clone(), 2, |chunk| {
+            let started = started.clone();
+            async move {
+                started.fetch_add(1, Ordering::SeqCst);
+                if chunk[0] == 64 {
+                    return Err(format!("chunk starting at {} failed", chunk[0]));
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                Ok(())
+            }
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(err, "chunk starting at 64 failed");
+        // Only the first two chunks were ever started; the rest were never scheduled.
+        assert_eq!(started.load(Ordering::SeqCst), 2);
+    }
 }
@@ -4,16 +4,16 @@ use vercel_runtime::Error;
 use globa_flux_rust::http_client::http_client_for_url;
 
 use globa_flux_rust::db::{
-    fetch_or_seed_youtube_oauth_app_config, fetch_youtube_channel_id,
-    fetch_youtube_connection_tokens, get_pool, update_youtube_connection_tokens,
-    upsert_video_daily_metric,
+    fetch_youtube_channel_id, fetch_youtube_connection_tokens, get_pool, upsert_video_daily_metric,
 };
-use globa_flux_rust::providers::youtube::{refresh_tokens, youtube_oauth_client_from_config};
 use globa_flux_rust::providers::youtube_analytics::{
-    fetch_video_daily_metrics, fetch_video_daily_metrics_for_channel,
+    fetch_video_daily_metrics, fetch_video_daily_metrics_for_channel, YoutubeAnalyticsError,
 };
 use globa_flux_rust::providers::youtube_api::fetch_my_channel_id;
 use globa_flux_rust::reach_reporting::ingest_channel_reach_basic_a1;
+use globa_flux_rust::youtube_auth::{
+    call_with_fresh_youtube_token, refresh_youtube_tokens_if_expired,
+};
 
 async fn fetch_report_json_by_url(
     access_token: &str,
@@ -158,32 +158,8 @@ async fn main() -> Result<(), Error> {
         };
 
     // Best-effort refresh before ANY API calls if expired.
-    let now_dt = Utc::now();
-    let needs_refresh = tokens.expires_at.map(|t| t <= now_dt).unwrap_or(false);
-    if needs_refresh {
-        if let Some(refresh) = tokens.refresh_token.clone() {
-            let app = fetch_or_seed_youtube_oauth_app_config(pool, tenant_id.trim())
-                .await?
-                .ok_or_else(|| {
-                    Box::new(std::io::Error::other("missing youtube oauth app config")) as Error
-                })?;
-            let client_secret = app
-                .client_secret
-                .as_deref()
-                .map(str::trim)
-                .filter(|v| !v.is_empty())
-                .ok_or_else(|| {
-                    Box::new(std::io::Error::other("missing youtube oauth client_secret")) as Error
-                })?;
-            let (client, _redirect) =
-                youtube_oauth_client_from_config(&app.client_id, client_secret, &app.redirect_uri)?;
-            let refreshed = refresh_tokens(&client, &refresh).await?;
-            update_youtube_connection_tokens(pool, tenant_id.trim(), channel_id.trim(), &refreshed)
-                .await?;
-            tokens.access_token = refreshed.access_token;
-            tokens.refresh_token = refreshed.refresh_token.or(Some(refresh));
-        }
-    }
+    refresh_youtube_tokens_if_expired(pool, tenant_id.trim(), channel_id.trim(), &mut tokens)
+        .await?;
 
     match fetch_my_channel_id(&tokens.access_token).await {
         Ok(token_channel_id) => {
@@ -409,59 +385,22 @@ async fn main() -> Result<(), Error> {
         );
     }
 
-    let metrics = match fetch_video_daily_metrics_for_channel(
-        &tokens.access_token,
-        &channel_id,
-        start_dt,
-        end_dt,
-    )
-    .await
-    {
-        Ok(v) => v,
-        Err(err) if err.status == Some(401) => {
-            if let Some(refresh) = tokens.refresh_token.clone() {
-                let app = fetch_or_seed_youtube_oauth_app_config(pool, tenant_id.trim())
-                    .await?
-                    .ok_or_else(|| {
-                        Box::new(std::io::Error::other("missing youtube oauth app config")) as Error
-                    })?;
-                let client_secret = app
-                    .client_secret
-                    .as_deref()
-                    .map(str::trim)
-                    .filter(|v| !v.is_empty())
-                    .ok_or_else(|| {
-                        Box::new(std::io::Error::other("missing youtube oauth client_secret"))
-                            as Error
-                    })?;
-                let (client, _redirect) = youtube_oauth_client_from_config(
-                    &app.client_id,
-                    client_secret,
-                    &app.redirect_uri,
-                )?;
-                let refreshed = refresh_tokens(&client, &refresh).await?;
-                update_youtube_connection_tokens(
-                    pool,
-                    tenant_id.trim(),
-                    channel_id.trim(),
-                    &refreshed,
-                )
-                .await?;
-                tokens.access_token = refreshed.access_token;
-                fetch_video_daily_metrics_for_channel(
-                    &tokens.access_token,
-                    &channel_id,
-                    start_dt,
-                    end_dt,
-                )
-                .await
-                .map_err(|e| -> Error { Box::new(e) })?
-            } else {
-                return Err(Box::new(err) as Error);
+    let metrics = call_with_fresh_youtube_token(
+        pool,
+        tenant_id.trim(),
+        channel_id.trim(),
+        &mut tokens,
+        |err: &YoutubeAnalyticsError| err.status == Some(401),
+        |access_token| {
+            let channel_id = channel_id.clone();
+            async move {
+                fetch_video_daily_metrics_for_channel(&access_token, &channel_id, start_dt, end_dt)
+                    .await
             }
-        }
-        Err(err) => return Err(Box::new(err) as Error),
-    };
+        },
+    )
+    .await?
+    .map_err(|e| -> Error { Box::new(e) })?;
 
     let mut upserts = 0usize;
     let mut min_dt: Option<NaiveDate> = None;
@@ -0,0 +1,201 @@
+//! `video_daily_metrics` stores channel-level totals next to per-video rows under sentinel
+//! `video_id`s. Queries use the fragments here rather than spelling the sentinels out, so a new
+//! totals source only has to be added to `CHANNEL_TOTAL_VIDEO_IDS`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::LazyLock;
+
+use chrono::NaiveDate;
+
+use crate::providers::youtube_analytics::VideoDailyMetricRow;
+
+/// Channel totals from a CSV upload.
+pub const CSV_CHANNEL_TOTAL_VIDEO_ID: &str = "csv_channel_total";
+/// Channel totals from the Analytics fallback and from reporting rows without a video.
+pub const API_CHANNEL_TOTAL_VIDEO_ID: &str = "__CHANNEL_TOTAL__";
+
+/// Every sentinel, in the order a day's total is picked when more than one source wrote it.
+pub const CHANNEL_TOTAL_VIDEO_IDS: &[&str] =
+    &[CSV_CHANNEL_TOTAL_VIDEO_ID, API_CHANNEL_TOTAL_VIDEO_ID];
+
+pub fn is_channel_total_video_id(video_id: &str) -> bool {
+    CHANNEL_TOTAL_VIDEO_IDS.contains(&video_id)
+}
+
+fn quoted_list(ids: &[&str]) -> String {
+    ids.iter()
+        .map(|id| format!("'{id}'"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn sums_by_priority(ids: &[&str], condition: Option<&str>, expr: &str) -> String {
+    let condition = condition.map(|c| format!(" AND {c}")).unwrap_or_default();
+    ids.iter()
+        .map(|id| format!("SUM(CASE WHEN video_id='{id}'{condition} THEN {expr} END)"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+static CHANNEL_TOTAL_FILTER: LazyLock<String> =
+    LazyLock::new(|| format!("video_id IN ({})", quoted_list(CHANNEL_TOTAL_VIDEO_IDS)));
+static VIDEO_ROWS_FILTER: LazyLock<String> =
+    LazyLock::new(|| format!("video_id NOT IN ({})", quoted_list(CHANNEL_TOTAL_VIDEO_IDS)));
+
+/// `video_id IN (<sentinels>)`: only channel-total rows.
+pub fn channel_total_filter() -> &'static str {
+    &CHANNEL_TOTAL_FILTER
+}
+
+/// `video_id NOT IN (<sentinels>)`: only real per-video rows.
+pub fn video_rows_filter() -> &'static str {
+    &VIDEO_ROWS_FILTER
+}
+
+/// One `SUM(CASE WHEN video_id='<sentinel>' THEN <expr> END)` per sentinel in priority order, to
+/// be wrapped in `COALESCE(...)` so the highest-priority source present for a day wins.
+pub fn channel_total_sums(expr: &str) -> String {
+    sums_by_priority(CHANNEL_TOTAL_VIDEO_IDS, None, expr)
+}
+
+/// Like `channel_total_sums`, counting only rows that also match `condition`.
+pub fn channel_total_sums_when(condition: &str, expr: &str) -> String {
+    sums_by_priority(CHANNEL_TOTAL_VIDEO_IDS, Some(condition), expr)
+}
+
+/// Per-day `API_CHANNEL_TOTAL_VIDEO_ID` rows summed from the real per-video rows, for channels
+/// that only have video-level data. Days in `skip_dts` (already holding an authoritative total)
+/// are left out; CTR is the impressions-weighted mean of the rows that reported one.
+pub fn derive_channel_totals(
+    rows: &[VideoDailyMetricRow],
+    skip_dts: &BTreeSet<NaiveDate>,
+) -> Vec<VideoDailyMetricRow> {
+    let mut by_dt: BTreeMap<NaiveDate, (f64, i64, i64, f64, i64)> = BTreeMap::new();
+    for row in rows
+        .iter()
+        .filter(|row| !is_channel_total_video_id(&row.video_id) && !skip_dts.contains(&row.dt))
+    {
+        let entry = by_dt.entry(row.dt).or_default();
+        entry.0 += row.estimated_revenue_usd;
+        entry.1 += row.impressions;
+        entry.2 += row.views;
+        if let Some(ctr) = row.impressions_ctr {
+            entry.3 += ctr * row.impressions as f64;
+            entry.4 += row.impressions;
+        }
+    }
+    by_dt
+        .into_iter()
+        .map(
+            |(dt, (revenue, impressions, views, ctr_num, ctr_denom))| VideoDailyMetricRow {
+                dt,
+                video_id: API_CHANNEL_TOTAL_VIDEO_ID.to_string(),
+                estimated_revenue_usd: revenue,
+                impressions,
+                impressions_ctr: (ctr_denom > 0).then(|| ctr_num / ctr_denom as f64),
+                views,
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUERY_SOURCES: &[(&str, &str)] = &[
+        (
+            "api/oauth/youtube/router.rs",
+            include_str!("../api/oauth/youtube/router.rs"),
+        ),
+        (
+            "api/jobs/worker/tick.rs",
+            include_str!("../api/jobs/worker/tick.rs"),
+        ),
+        ("src/db.rs", include_str!("db.rs")),
+        ("src/youtube_alerts.rs", include_str!("youtube_alerts.rs")),
+        ("src/reach_reporting.rs", include_str!("reach_reporting.rs")),
+    ];
+
+    #[test]
+    fn a_new_sentinel_is_counted_as_a_channel_total_everywhere() {
+        let ids = [
+            "csv_channel_total",
+            "__CHANNEL_TOTAL__",
+            "reporting_channel_total",
+        ];
+        assert_eq!(
+            quoted_list(&ids),
+            "'csv_channel_total','__CHANNEL_TOTAL__','reporting_channel_total'"
+        );
+        let sums = sums_by_priority(&ids, None, "views");
+        assert_eq!(sums.matches("SUM(CASE WHEN video_id=").count(), 3);
+        assert!(sums.ends_with("SUM(CASE WHEN video_id='reporting_channel_total' THEN views END)"));
+
+        for id in CHANNEL_TOTAL_VIDEO_IDS {
+            assert!(is_channel_total_video_id(id));
+            assert!(channel_total_filter().contains(&format!("'{id}'")));
+            assert!(video_rows_filter().contains(&format!("'{id}'")));
+            assert!(id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_'));
+        }
+        assert!(!is_channel_total_video_id("dQw4w9WgXcQ"));
+        assert_eq!(
+            channel_total_sums_when("impressions_ctr IS NOT NULL", "impressions"),
+            "SUM(CASE WHEN video_id='csv_channel_total' AND impressions_ctr IS NOT NULL THEN impressions END), \
+             SUM(CASE WHEN video_id='__CHANNEL_TOTAL__' AND impressions_ctr IS NOT NULL THEN impressions END)"
+        );
+
+        // Queries go through the fragments above, so no SQL spells a sentinel out.
+        let mut hardcoded = Vec::new();
+        for (path, source) in QUERY_SOURCES {
+            for id in CHANNEL_TOTAL_VIDEO_IDS {
+                if source.contains(&format!("'{id}'")) {
+                    hardcoded.push(format!("{path}: {id}"));
+                }
+            }
+        }
+        assert!(hardcoded.is_empty(), "hardcoded sentinels: {hardcoded:?}");
+    }
+
+    #[test]
+    fn derived_totals_match_per_video_sums() {
+        let d1 = NaiveDate::from_ymd_opt(2026, 4, 1).unwrap();
+        let d2 = NaiveDate::from_ymd_opt(2026, 4, 2).unwrap();
+        let row = |dt, video_id: &str, revenue, impressions, ctr, views| VideoDailyMetricRow {
+            dt,
+            video_id: video_id.to_string(),
+            estimated_revenue_usd: revenue,
+            impressions,
+            impressions_ctr: ctr,
+            views,
+        };
+        let rows = vec![
+            row(d1, "a", 1.5, 1000, Some(0.05), 400),
+            row(d1, "b", 0.5, 3000, Some(0.01), 100),
+            row(d1, "c", 0.25, 500, None, 50),
+            row(d2, "a", 2.0, 100, None, 10),
+            row(d2, API_CHANNEL_TOTAL_VIDEO_ID, 9.0, 9, None, 9),
+            row(d2, CSV_CHANNEL_TOTAL_VIDEO_ID, 8.0, 8, None, 8),
+        ];
+
+        let totals = derive_channel_totals(&rows, &BTreeSet::new());
+        assert_eq!(totals.len(), 2);
+        let t1 = &totals[0];
+        assert_eq!(t1.video_id, API_CHANNEL_TOTAL_VIDEO_ID);
+        assert!((t1.estimated_revenue_usd - 2.25).abs() < 1e-9);
+        assert_eq!((t1.impressions, t1.views), (4500, 550));
+        // (0.05 * 1000 + 0.01 * 3000) / 4000
+        assert!((t1.impressions_ctr.unwrap() - 0.02).abs() < 1e-9);
+        // Existing sentinel rows are never summed into a derived total.
+        let t2 = &totals[1];
+        assert!((t2.estimated_revenue_usd - 2.0).abs() < 1e-9);
+        assert_eq!(
+            (t2.impressions, t2.views, t2.impressions_ctr),
+            (100, 10, None)
+        );
+
+        let skipped = derive_channel_totals(&rows, &BTreeSet::from([d2]));
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].dt, d1);
+    }
+}
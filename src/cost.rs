@@ -1,3 +1,5 @@
+use chrono::NaiveDate;
+
 #[derive(Clone, Copy, Debug)]
 pub struct ModelPricingUsdPerMToken {
     pub prompt: f64,
@@ -14,10 +16,96 @@ pub fn compute_cost_usd(
     prompt_cost + completion_cost
 }
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct UsageDay {
+    pub dt: String,
+    pub youtube_api_units: i64,
+    pub youtube_api_calls: i64,
+    pub llm_events: i64,
+    pub llm_cost_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct UsageTotals {
+    pub youtube_api_units: i64,
+    pub youtube_api_calls: i64,
+    pub llm_events: i64,
+    pub llm_cost_usd: f64,
+}
+
+/// Merges YouTube API `(dt, units, calls)` and LLM `(dt, events, cost_usd)` rows into one
+/// day-ordered series; a day present in either source appears once.
+pub fn merge_daily_usage(
+    api_rows: &[(NaiveDate, i64, i64)],
+    llm_rows: &[(NaiveDate, i64, f64)],
+) -> (Vec<UsageDay>, UsageTotals) {
+    let mut by_dt: std::collections::BTreeMap<NaiveDate, (i64, i64, i64, f64)> =
+        std::collections::BTreeMap::new();
+    for (dt, units, calls) in api_rows {
+        let d = by_dt.entry(*dt).or_default();
+        d.0 += units;
+        d.1 += calls;
+    }
+    for (dt, events, cost_usd) in llm_rows {
+        let d = by_dt.entry(*dt).or_default();
+        d.2 += events;
+        d.3 += cost_usd;
+    }
+
+    let mut totals = UsageTotals::default();
+    let days = by_dt
+        .into_iter()
+        .map(|(dt, (units, calls, events, cost_usd))| {
+            totals.youtube_api_units += units;
+            totals.youtube_api_calls += calls;
+            totals.llm_events += events;
+            totals.llm_cost_usd += cost_usd;
+            UsageDay {
+                dt: dt.to_string(),
+                youtube_api_units: units,
+                youtube_api_calls: calls,
+                llm_events: events,
+                llm_cost_usd: round_usd(cost_usd),
+            }
+        })
+        .collect();
+    totals.llm_cost_usd = round_usd(totals.llm_cost_usd);
+    (days, totals)
+}
+
+fn round_usd(v: f64) -> f64 {
+    (v * 1_000_000.0).round() / 1_000_000.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn merge_daily_usage_sums_both_sources_per_day() {
+        let d = |day| NaiveDate::from_ymd_opt(2026, 3, day).unwrap();
+        let api_rows = vec![(d(1), 51, 2), (d(2), 1, 1)];
+        let llm_rows = vec![(d(2), 3, 0.015), (d(3), 1, 0.0025)];
+
+        let (days, totals) = merge_daily_usage(&api_rows, &llm_rows);
+        let dts: Vec<&str> = days.iter().map(|d| d.dt.as_str()).collect();
+        assert_eq!(dts, vec!["2026-03-01", "2026-03-02", "2026-03-03"]);
+        assert_eq!(days[0].youtube_api_units, 51);
+        assert_eq!(days[0].llm_events, 0);
+        assert_eq!(days[1].youtube_api_units, 1);
+        assert_eq!(days[1].llm_cost_usd, 0.015);
+        assert_eq!(days[2].youtube_api_calls, 0);
+        assert_eq!(
+            totals,
+            UsageTotals {
+                youtube_api_units: 52,
+                youtube_api_calls: 3,
+                llm_events: 4,
+                llm_cost_usd: 0.0175,
+            }
+        );
+    }
+
     #[test]
     fn compute_cost_usd_applies_per_million_rates() {
         let pricing = ModelPricingUsdPerMToken {
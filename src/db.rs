@@ -4,6 +4,12 @@ use std::collections::HashMap;
 use tokio::sync::OnceCell;
 use vercel_runtime::Error;
 
+use crate::channel_totals::{
+    channel_total_filter, is_channel_total_video_id, video_rows_filter, CSV_CHANNEL_TOTAL_VIDEO_ID,
+};
+use crate::providers::youtube_analytics::VideoDailyMetricRow;
+use crate::providers::youtube_videos::{decoded_thumbnail, DecodedThumbnail, VideoSnapshot};
+
 static POOL: OnceCell<MySqlPool> = OnceCell::const_new();
 
 #[derive(Debug, Clone)]
@@ -37,6 +43,8 @@ async fn ensure_schema(pool: &MySqlPool) -> Result<(), Error> {
         prompt_tokens INT NOT NULL,
         completion_tokens INT NOT NULL,
         cost_usd DECIMAL(12,6) NOT NULL,
+        project_id BIGINT NULL,
+        channel_id VARCHAR(128) NULL,
         occurred_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
         UNIQUE KEY uq_usage_events_idem (tenant_id, event_type, idempotency_key),
         KEY idx_usage_events_day (tenant_id, occurred_at)
@@ -76,6 +84,10 @@ async fn ensure_schema(pool: &MySqlPool) -> Result<(), Error> {
         token_type VARCHAR(32) NOT NULL,
         scope TEXT NULL,
         expires_at TIMESTAMP(3) NULL,
+        active TINYINT(1) NOT NULL DEFAULT 1,
+        needs_reauth TINYINT(1) NOT NULL DEFAULT 0,
+        last_synced_dt DATE NULL,
+        monetized TINYINT(1) NULL,
         created_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
         updated_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
         UNIQUE KEY uq_channel_connections_provider (tenant_id, oauth_provider),
@@ -141,6 +153,7 @@ async fn ensure_schema(pool: &MySqlPool) -> Result<(), Error> {
         locked_by VARCHAR(128) NULL,
         locked_at TIMESTAMP(3) NULL,
         last_error TEXT NULL,
+        backfill_days INT NULL,
         created_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
         updated_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
         UNIQUE KEY uq_job_tasks_dedupe (dedupe_key),
@@ -153,6 +166,95 @@ async fn ensure_schema(pool: &MySqlPool) -> Result<(), Error> {
   .await
   .map_err(|e| -> Error { Box::new(e) })?;
 
+    // Last seen title/thumbnail/publish time per tracked video, diffed to detect manual edits.
+    sqlx::query(
+        r#"
+      CREATE TABLE IF NOT EXISTS yt_video_snapshots (
+        tenant_id VARCHAR(128) NOT NULL,
+        channel_id VARCHAR(128) NOT NULL,
+        video_id VARCHAR(64) NOT NULL,
+        title TEXT NOT NULL,
+        thumbnail_url TEXT NULL,
+        publish_at VARCHAR(64) NULL,
+        checked_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+        PRIMARY KEY (tenant_id, channel_id, video_id)
+      );
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    // YouTube Data API quota units spent per tenant/channel/day, by API method.
+    sqlx::query(
+        r#"
+      CREATE TABLE IF NOT EXISTS youtube_api_usage_daily (
+        tenant_id VARCHAR(128) NOT NULL,
+        channel_id VARCHAR(128) NOT NULL,
+        dt DATE NOT NULL,
+        api_method VARCHAR(64) NOT NULL,
+        calls INT NOT NULL DEFAULT 0,
+        units INT NOT NULL DEFAULT 0,
+        updated_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+        PRIMARY KEY (tenant_id, channel_id, dt, api_method)
+      );
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    // Channels a tenant keeps pinned for quick switching; the active channel stays the default.
+    sqlx::query(
+        r#"
+      CREATE TABLE IF NOT EXISTS tenant_pinned_channels (
+        tenant_id VARCHAR(128) NOT NULL,
+        channel_id VARCHAR(128) NOT NULL,
+        label VARCHAR(255) NULL,
+        created_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+        updated_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+        PRIMARY KEY (tenant_id, channel_id)
+      );
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    // Per-tenant capability switches; a missing row means the feature is on.
+    sqlx::query(
+        r#"
+      CREATE TABLE IF NOT EXISTS tenant_feature_flags (
+        tenant_id VARCHAR(128) NOT NULL,
+        feature VARCHAR(64) NOT NULL,
+        enabled TINYINT(1) NOT NULL DEFAULT 1,
+        created_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+        updated_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+        PRIMARY KEY (tenant_id, feature)
+      );
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    // Short-lived per schedule/tenant lock so a retried cron dispatch does not enqueue twice.
+    sqlx::query(
+        r#"
+      CREATE TABLE IF NOT EXISTS dispatch_locks (
+        lock_key VARCHAR(255) PRIMARY KEY,
+        holder VARCHAR(128) NOT NULL,
+        expires_at TIMESTAMP(3) NOT NULL,
+        result_json TEXT NULL,
+        created_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+        updated_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3)
+      );
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
     sqlx::query(
     r#"
       CREATE TABLE IF NOT EXISTS decision_daily (
@@ -206,6 +308,9 @@ async fn ensure_schema(pool: &MySqlPool) -> Result<(), Error> {
         impressions BIGINT NOT NULL DEFAULT 0,
         impressions_ctr DOUBLE NULL,
         views BIGINT NOT NULL DEFAULT 0,
+        clicks BIGINT NULL,
+        derived_total TINYINT(1) NOT NULL DEFAULT 0,
+        reach_ingested_at TIMESTAMP(3) NULL,
         updated_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
         PRIMARY KEY (tenant_id, channel_id, dt, video_id),
         KEY idx_video_daily_metrics_day (tenant_id, channel_id, dt),
@@ -250,6 +355,8 @@ async fn ensure_schema(pool: &MySqlPool) -> Result<(), Error> {
         rows_parsed INT NOT NULL DEFAULT 0,
         status VARCHAR(16) NOT NULL DEFAULT 'received',
         error TEXT NULL,
+        csv_text MEDIUMTEXT NULL,
+        parse_options_json TEXT NULL,
         created_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
         updated_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
         KEY idx_yt_csv_uploads_tenant (tenant_id, channel_id, created_at)
@@ -284,6 +391,56 @@ async fn ensure_schema(pool: &MySqlPool) -> Result<(), Error> {
   .await
   .map_err(|e| -> Error { Box::new(e) })?;
 
+    // Per-tenant alert notification mode: one notification per alert, or grouped digests.
+    sqlx::query(
+        r#"
+      CREATE TABLE IF NOT EXISTS tenant_alert_settings (
+        tenant_id VARCHAR(128) NOT NULL PRIMARY KEY,
+        notification_mode VARCHAR(16) NOT NULL DEFAULT 'immediate',
+        digest_window_minutes INT NOT NULL DEFAULT 15,
+        updated_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3)
+      );
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    // Creator-set RPM baselines, preferred over derived RPM when quoting sponsors.
+    sqlx::query(
+        r#"
+      CREATE TABLE IF NOT EXISTS yt_channel_rpm_baselines (
+        tenant_id VARCHAR(128) NOT NULL,
+        channel_id VARCHAR(128) NOT NULL,
+        rpm_usd DOUBLE NOT NULL,
+        note VARCHAR(512) NULL,
+        updated_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+        PRIMARY KEY (tenant_id, channel_id)
+      );
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    // Per-channel alert message templates (localized/branded copy keyed by alert_key).
+    sqlx::query(
+        r#"
+      CREATE TABLE IF NOT EXISTS yt_alert_templates (
+        tenant_id VARCHAR(128) NOT NULL,
+        channel_id VARCHAR(128) NOT NULL,
+        alert_key VARCHAR(64) NOT NULL,
+        template TEXT NOT NULL,
+        created_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+        updated_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+        PRIMARY KEY (tenant_id, channel_id, alert_key)
+      );
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
     // Experiments (MVP: persisted experiment definitions + variants).
     sqlx::query(
     r#"
@@ -298,6 +455,7 @@ async fn ensure_schema(pool: &MySqlPool) -> Result<(), Error> {
         planned_duration_days INT NULL,
         started_at TIMESTAMP(3) NULL,
         ended_at TIMESTAMP(3) NULL,
+        baseline_note VARCHAR(255) NULL,
         created_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
         updated_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
         KEY idx_yt_experiments_tenant (tenant_id, channel_id, created_at)
@@ -326,6 +484,23 @@ async fn ensure_schema(pool: &MySqlPool) -> Result<(), Error> {
   .await
   .map_err(|e| -> Error { Box::new(e) })?;
 
+    // The thumbnail a thumbnail experiment replaced, downloaded before the change so a rollback
+    // re-uploads the original image (the CDN URL only ever serves the current one).
+    sqlx::query(
+        r#"
+      CREATE TABLE IF NOT EXISTS yt_experiment_baseline_thumbnails (
+        experiment_id BIGINT PRIMARY KEY,
+        content_type VARCHAR(32) NOT NULL,
+        sha256 CHAR(64) NOT NULL,
+        image_bytes MEDIUMBLOB NOT NULL,
+        created_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3)
+      );
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
     // YouTube Reporting / Content ID ingestion tables (raw blobs + metadata).
     sqlx::query(
     r#"
@@ -386,6 +561,7 @@ async fn ensure_schema(pool: &MySqlPool) -> Result<(), Error> {
         parse_version VARCHAR(32) NULL,
         parsed_at TIMESTAMP(3) NULL,
         parse_error TEXT NULL,
+        ingested_row_no BIGINT NOT NULL DEFAULT 0,
         created_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
         updated_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
         UNIQUE KEY uq_yt_reporting_report_files (tenant_id, content_owner_id, report_id),
@@ -445,6 +621,8 @@ async fn ensure_schema(pool: &MySqlPool) -> Result<(), Error> {
         revenue_change_pct_7d DOUBLE NULL,
         catastrophic_flag TINYINT NOT NULL DEFAULT 0,
         new_top_asset_flag TINYINT NOT NULL DEFAULT 0,
+        provisional TINYINT(1) NOT NULL DEFAULT 0,
+        outcome_window_days INT NULL,
         notes TEXT NULL,
         created_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
         UNIQUE KEY uq_decision_outcome (tenant_id, channel_id, decision_dt, outcome_dt),
@@ -456,6 +634,49 @@ async fn ensure_schema(pool: &MySqlPool) -> Result<(), Error> {
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
+    // Monthly roll-up of decision_daily/decision_outcome rows removed by evidence retention, so
+    // long-term trends survive pruning. Sums (not averages) keep repeated prunes additive.
+    sqlx::query(
+        r#"
+      CREATE TABLE IF NOT EXISTS decision_evidence_monthly (
+        tenant_id VARCHAR(128) NOT NULL,
+        channel_id VARCHAR(128) NOT NULL,
+        month_start DATE NOT NULL,
+        decisions INT NOT NULL DEFAULT 0,
+        exploit_decisions INT NOT NULL DEFAULT 0,
+        explore_decisions INT NOT NULL DEFAULT 0,
+        protect_decisions INT NOT NULL DEFAULT 0,
+        confidence_sum DOUBLE NOT NULL DEFAULT 0,
+        outcomes INT NOT NULL DEFAULT 0,
+        revenue_change_pct_7d_sum DOUBLE NOT NULL DEFAULT 0,
+        revenue_change_pct_7d_n INT NOT NULL DEFAULT 0,
+        catastrophic_outcomes INT NOT NULL DEFAULT 0,
+        updated_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+        PRIMARY KEY (tenant_id, channel_id, month_start)
+      );
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    // Saved sponsor quotes, fetchable by quote_id so creators can send a stable link.
+    sqlx::query(
+        r#"
+      CREATE TABLE IF NOT EXISTS yt_sponsor_quotes (
+        quote_id VARCHAR(64) PRIMARY KEY,
+        tenant_id VARCHAR(128) NOT NULL,
+        channel_id VARCHAR(128) NOT NULL,
+        quote_json LONGTEXT NOT NULL,
+        created_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+        KEY idx_yt_sponsor_quotes_lookup (tenant_id, channel_id, created_at)
+      );
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
     // Public share links for proof reports (HTML snapshots).
     // Purpose: send to brands/partners without requiring login.
     sqlx::query(
@@ -662,6 +883,8 @@ async fn ensure_schema(pool: &MySqlPool) -> Result<(), Error> {
         prompt_text TEXT NOT NULL,
         enabled TINYINT NOT NULL DEFAULT 1,
         sort_order INT NOT NULL DEFAULT 0,
+        temperature DOUBLE NULL,
+        max_output_tokens INT NULL,
         created_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
         updated_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
         KEY idx_geo_monitor_prompts_project (tenant_id, project_id, sort_order),
@@ -723,6 +946,26 @@ async fn ensure_schema(pool: &MySqlPool) -> Result<(), Error> {
   )
   .execute(pool)
   .await
+  .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+    r#"
+      CREATE TABLE IF NOT EXISTS tenant_api_keys (
+        id BIGINT PRIMARY KEY AUTO_INCREMENT,
+        tenant_id VARCHAR(128) NOT NULL,
+        key_hash CHAR(64) NOT NULL,
+        key_prefix VARCHAR(16) NOT NULL,
+        label VARCHAR(128) NULL,
+        created_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+        last_used_at TIMESTAMP(3) NULL,
+        revoked_at TIMESTAMP(3) NULL,
+        UNIQUE KEY uq_tenant_api_keys_hash (key_hash),
+        KEY idx_tenant_api_keys_tenant (tenant_id, revoked_at)
+      );
+    "#,
+  )
+  .execute(pool)
+  .await
   .map_err(|e| -> Error { Box::new(e) })?;
 
     // Best-effort schema upgrades for existing tables (TiDB supports IF NOT EXISTS).
@@ -776,109 +1019,348 @@ async fn ensure_schema(pool: &MySqlPool) -> Result<(), Error> {
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(())
-}
-
-pub async fn get_pool() -> Result<&'static MySqlPool, Error> {
-    POOL.get_or_try_init(|| async {
-        let url = std::env::var("TIDB_DATABASE_URL")
-            .or_else(|_| std::env::var("DATABASE_URL"))
-            .map_err(|_| -> Error {
-                Box::new(std::io::Error::other(
-                    "Missing TIDB_DATABASE_URL (or DATABASE_URL)",
-                ))
-            })?;
-
-        let pool = MySqlPoolOptions::new()
-            .max_connections(5)
-            .connect(&url)
-            .await
-            .map_err(|e| -> Error { Box::new(e) })?;
-
-        ensure_schema(&pool).await?;
-        Ok::<_, Error>(pool)
-    })
+    sqlx::query(
+        r#"
+      ALTER TABLE yt_reporting_report_files
+      ADD COLUMN IF NOT EXISTS ingested_row_no BIGINT NOT NULL DEFAULT 0;
+    "#,
+    )
+    .execute(pool)
     .await
-}
-
-pub async fn sum_spent_usd_today(
-    pool: &MySqlPool,
-    tenant_id: &str,
-    now: DateTime<Utc>,
-) -> Result<f64, Error> {
-    let (start, end) = utc_day_bounds(now);
+    .map_err(|e| -> Error { Box::new(e) })?;
 
-    let spent: f64 = sqlx::query_scalar(
+    sqlx::query(
         r#"
-      SELECT COALESCE(CAST(SUM(cost_usd) AS DOUBLE), 0) AS spent_usd
-      FROM usage_events
-      WHERE tenant_id = ?
-        AND occurred_at >= ? AND occurred_at < ?;
+      ALTER TABLE job_tasks
+      ADD COLUMN IF NOT EXISTS backfill_days INT NULL;
     "#,
     )
-    .bind(tenant_id)
-    .bind(start)
-    .bind(end)
-    .fetch_one(pool)
+    .execute(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(spent)
-}
+    sqlx::query(
+        r#"
+      ALTER TABLE channel_connections
+      ADD COLUMN IF NOT EXISTS active TINYINT(1) NOT NULL DEFAULT 1;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
 
-pub async fn fetch_usage_event(
-    pool: &MySqlPool,
-    tenant_id: &str,
-    event_type: &str,
-    idempotency_key: &str,
-) -> Result<Option<UsageEventRow>, Error> {
-    let row = sqlx::query_as::<_, (String, String, i32, i32, f64)>(
+    sqlx::query(
         r#"
-      SELECT provider, model, prompt_tokens, completion_tokens, CAST(cost_usd AS DOUBLE) AS cost_usd
-      FROM usage_events
-      WHERE tenant_id = ? AND event_type = ? AND idempotency_key = ?
-      LIMIT 1;
+      ALTER TABLE channel_connections
+      ADD COLUMN IF NOT EXISTS needs_reauth TINYINT(1) NOT NULL DEFAULT 0;
     "#,
     )
-    .bind(tenant_id)
-    .bind(event_type)
-    .bind(idempotency_key)
-    .fetch_optional(pool)
+    .execute(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(row.map(
-        |(provider, model, prompt_tokens, completion_tokens, cost_usd)| UsageEventRow {
-            provider,
-            model,
-            prompt_tokens,
-            completion_tokens,
-            cost_usd,
-        },
-    ))
-}
+    sqlx::query(
+        r#"
+      ALTER TABLE channel_connections
+      ADD COLUMN IF NOT EXISTS last_synced_dt DATE NULL;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
 
-pub async fn fetch_daily_usage_used(
-    pool: &MySqlPool,
-    tenant_id: &str,
-    event_type: &str,
-    day: chrono::NaiveDate,
-) -> Result<i64, Error> {
-    let used = sqlx::query_scalar::<_, i64>(
+    sqlx::query(
         r#"
-      SELECT CAST(used AS SIGNED) AS used
-      FROM usage_daily_counters
-      WHERE tenant_id = ? AND day_key = ? AND event_type = ?
-      LIMIT 1;
+      ALTER TABLE usage_events
+      ADD COLUMN IF NOT EXISTS project_id BIGINT NULL;
     "#,
     )
-    .bind(tenant_id)
-    .bind(day)
-    .bind(event_type)
-    .fetch_optional(pool)
+    .execute(pool)
     .await
-    .map_err(|e| -> Error { Box::new(e) })?
-    .unwrap_or(0);
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      ALTER TABLE usage_events
+      ADD COLUMN IF NOT EXISTS channel_id VARCHAR(128) NULL;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      ALTER TABLE geo_monitor_prompts
+      ADD COLUMN IF NOT EXISTS temperature DOUBLE NULL;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      ALTER TABLE geo_monitor_prompts
+      ADD COLUMN IF NOT EXISTS max_output_tokens INT NULL;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      ALTER TABLE yt_experiments
+      ADD COLUMN IF NOT EXISTS baseline_note VARCHAR(255) NULL;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      ALTER TABLE channel_connections
+      ADD COLUMN IF NOT EXISTS monetized TINYINT(1) NULL;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      ALTER TABLE yt_csv_uploads
+      ADD COLUMN IF NOT EXISTS csv_text MEDIUMTEXT NULL;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      ALTER TABLE yt_csv_uploads
+      ADD COLUMN IF NOT EXISTS parse_options_json TEXT NULL;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      ALTER TABLE yt_experiments
+      ADD COLUMN IF NOT EXISTS rollback_failed_at TIMESTAMP(3) NULL;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      ALTER TABLE yt_experiments
+      ADD COLUMN IF NOT EXISTS full_snapshot_json MEDIUMTEXT NULL;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      ALTER TABLE video_daily_metrics
+      ADD COLUMN IF NOT EXISTS clicks BIGINT NULL;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      ALTER TABLE video_daily_metrics
+      ADD COLUMN IF NOT EXISTS derived_total TINYINT(1) NOT NULL DEFAULT 0;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      ALTER TABLE decision_outcome
+      ADD COLUMN IF NOT EXISTS provisional TINYINT(1) NOT NULL DEFAULT 0;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      ALTER TABLE video_daily_metrics
+      ADD COLUMN IF NOT EXISTS reach_ingested_at TIMESTAMP(3) NULL;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    // Gap dates a dispatch enqueued a refill for; `refilled_at` is set once a refill task fetched
+    // the date. A day with no activity has no rows to fetch, so it is refilled once rather than
+    // re-detected on every dispatch, while a failed refill leaves it to be retried.
+    sqlx::query(
+        r#"
+      CREATE TABLE IF NOT EXISTS yt_metric_gap_fills (
+        tenant_id VARCHAR(128) NOT NULL,
+        channel_id VARCHAR(128) NOT NULL,
+        dt DATE NOT NULL,
+        attempted_at TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+        refilled_at TIMESTAMP(3) NULL,
+        PRIMARY KEY (tenant_id, channel_id, dt)
+      );
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      ALTER TABLE decision_outcome
+      ADD COLUMN IF NOT EXISTS outcome_window_days INT NULL;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      ALTER TABLE yt_metric_gap_fills
+      ADD COLUMN IF NOT EXISTS refilled_at TIMESTAMP(3) NULL;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      ALTER TABLE yt_reporting_report_files
+      ADD COLUMN IF NOT EXISTS too_wide_columns INT NULL;
+    "#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+pub async fn get_pool() -> Result<&'static MySqlPool, Error> {
+    POOL.get_or_try_init(|| async {
+        let url = std::env::var("TIDB_DATABASE_URL")
+            .or_else(|_| std::env::var("DATABASE_URL"))
+            .map_err(|_| -> Error {
+                Box::new(std::io::Error::other(
+                    "Missing TIDB_DATABASE_URL (or DATABASE_URL)",
+                ))
+            })?;
+
+        let pool = MySqlPoolOptions::new()
+            .max_connections(5)
+            .connect(&url)
+            .await
+            .map_err(|e| -> Error { Box::new(e) })?;
+
+        ensure_schema(&pool).await?;
+        Ok::<_, Error>(pool)
+    })
+    .await
+}
+
+pub async fn sum_spent_usd_today(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    now: DateTime<Utc>,
+) -> Result<f64, Error> {
+    let (start, end) = utc_day_bounds(now);
+
+    let spent: f64 = sqlx::query_scalar(
+        r#"
+      SELECT COALESCE(CAST(SUM(cost_usd) AS DOUBLE), 0) AS spent_usd
+      FROM usage_events
+      WHERE tenant_id = ?
+        AND occurred_at >= ? AND occurred_at < ?;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(start)
+    .bind(end)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(spent)
+}
+
+pub async fn fetch_usage_event(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    event_type: &str,
+    idempotency_key: &str,
+) -> Result<Option<UsageEventRow>, Error> {
+    let row = sqlx::query_as::<_, (String, String, i32, i32, f64)>(
+        r#"
+      SELECT provider, model, prompt_tokens, completion_tokens, CAST(cost_usd AS DOUBLE) AS cost_usd
+      FROM usage_events
+      WHERE tenant_id = ? AND event_type = ? AND idempotency_key = ?
+      LIMIT 1;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(event_type)
+    .bind(idempotency_key)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(row.map(
+        |(provider, model, prompt_tokens, completion_tokens, cost_usd)| UsageEventRow {
+            provider,
+            model,
+            prompt_tokens,
+            completion_tokens,
+            cost_usd,
+        },
+    ))
+}
+
+pub async fn fetch_daily_usage_used(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    event_type: &str,
+    day: chrono::NaiveDate,
+) -> Result<i64, Error> {
+    let used = sqlx::query_scalar::<_, i64>(
+        r#"
+      SELECT CAST(used AS SIGNED) AS used
+      FROM usage_daily_counters
+      WHERE tenant_id = ? AND day_key = ? AND event_type = ?
+      LIMIT 1;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(day)
+    .bind(event_type)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?
+    .unwrap_or(0);
 
     Ok(used)
 }
@@ -1008,14 +1490,45 @@ pub async fn insert_usage_event(
     completion_tokens: i32,
     cost_usd: f64,
 ) -> Result<(), sqlx::Error> {
-    sqlx::query(
-    r#"
-      INSERT INTO usage_events
-        (tenant_id, event_type, idempotency_key, provider, model, prompt_tokens, completion_tokens, cost_usd)
-      VALUES
-        (?, ?, ?, ?, ?, ?, ?, ?);
-    "#,
-  )
+    insert_usage_event_attributed(
+        pool,
+        tenant_id,
+        event_type,
+        idempotency_key,
+        provider,
+        model,
+        prompt_tokens,
+        completion_tokens,
+        cost_usd,
+        None,
+        None,
+    )
+    .await
+}
+
+/// Same as `insert_usage_event`, tagging the row with the project/channel it was spent on.
+#[allow(clippy::too_many_arguments)]
+pub async fn insert_usage_event_attributed(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    event_type: &str,
+    idempotency_key: &str,
+    provider: &str,
+    model: &str,
+    prompt_tokens: i32,
+    completion_tokens: i32,
+    cost_usd: f64,
+    project_id: Option<i64>,
+    channel_id: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+    r#"
+      INSERT INTO usage_events
+        (tenant_id, event_type, idempotency_key, provider, model, prompt_tokens, completion_tokens, cost_usd, project_id, channel_id)
+      VALUES
+        (?, ?, ?, ?, ?, ?, ?, ?, ?, ?);
+    "#,
+  )
   .bind(tenant_id)
   .bind(event_type)
   .bind(idempotency_key)
@@ -1024,12 +1537,240 @@ pub async fn insert_usage_event(
   .bind(prompt_tokens)
   .bind(completion_tokens)
   .bind(cost_usd)
+  .bind(project_id)
+  .bind(channel_id)
   .execute(pool)
   .await?;
 
     Ok(())
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageCostGroupBy {
+    EventType,
+    Project,
+    Channel,
+}
+
+impl UsageCostGroupBy {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "event_type" => Some(Self::EventType),
+            "project" | "project_id" => Some(Self::Project),
+            "channel" | "channel_id" => Some(Self::Channel),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::EventType => "event_type",
+            Self::Project => "project_id",
+            Self::Channel => "channel_id",
+        }
+    }
+
+    /// Group key as a string; unattributed rows fall into the empty-string bucket.
+    fn key_of(self, totals: &UsageAttributionTotals) -> String {
+        match self {
+            Self::EventType => totals.event_type.clone(),
+            Self::Project => totals.project_id.map(|id| id.to_string()).unwrap_or_default(),
+            Self::Channel => totals.channel_id.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Usage totals for one (event_type, project_id, channel_id) combination.
+#[derive(Debug, Clone, PartialEq)]
+struct UsageAttributionTotals {
+    event_type: String,
+    project_id: Option<i64>,
+    channel_id: Option<String>,
+    events: i64,
+    prompt_tokens: i64,
+    completion_tokens: i64,
+    cost_usd: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageCostGroupRow {
+    pub key: String,
+    pub events: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub cost_usd: f64,
+}
+
+/// Sums the attribution totals per `group_by` key, most expensive first (ties by key).
+fn group_usage_costs(
+    totals: &[UsageAttributionTotals],
+    group_by: UsageCostGroupBy,
+) -> Vec<UsageCostGroupRow> {
+    let mut groups: Vec<UsageCostGroupRow> = Vec::new();
+    for row in totals {
+        let key = group_by.key_of(row);
+        let group = match groups.iter().position(|g| g.key == key) {
+            Some(idx) => &mut groups[idx],
+            None => {
+                groups.push(UsageCostGroupRow {
+                    key,
+                    events: 0,
+                    prompt_tokens: 0,
+                    completion_tokens: 0,
+                    cost_usd: 0.0,
+                });
+                groups.last_mut().expect("just pushed")
+            }
+        };
+        group.events += row.events;
+        group.prompt_tokens += row.prompt_tokens;
+        group.completion_tokens += row.completion_tokens;
+        group.cost_usd += row.cost_usd;
+    }
+    groups.sort_by(|a, b| {
+        b.cost_usd
+            .total_cmp(&a.cost_usd)
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    groups
+}
+
+pub async fn fetch_usage_cost_summary(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    group_by: UsageCostGroupBy,
+) -> Result<Vec<UsageCostGroupRow>, Error> {
+    type TotalsRow = (String, Option<i64>, Option<String>, i64, i64, i64, f64);
+    let rows = sqlx::query_as::<_, TotalsRow>(
+        r#"
+      SELECT event_type, project_id, channel_id,
+             COUNT(*) AS events,
+             CAST(COALESCE(SUM(prompt_tokens), 0) AS SIGNED) AS prompt_tokens,
+             CAST(COALESCE(SUM(completion_tokens), 0) AS SIGNED) AS completion_tokens,
+             COALESCE(CAST(SUM(cost_usd) AS DOUBLE), 0) AS cost_usd
+      FROM usage_events
+      WHERE tenant_id = ?
+        AND occurred_at >= ? AND occurred_at < ?
+      GROUP BY event_type, project_id, channel_id;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(start)
+    .bind(end)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    let totals: Vec<UsageAttributionTotals> = rows
+        .into_iter()
+        .map(
+            |(event_type, project_id, channel_id, events, prompt_tokens, completion_tokens, cost_usd)| {
+                UsageAttributionTotals {
+                    event_type,
+                    project_id,
+                    channel_id,
+                    events,
+                    prompt_tokens,
+                    completion_tokens,
+                    cost_usd,
+                }
+            },
+        )
+        .collect();
+    Ok(group_usage_costs(&totals, group_by))
+}
+
+/// Adds one call of `units` quota units to today's (UTC) row for the API method.
+pub async fn record_youtube_api_usage(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    api_method: &str,
+    units: i64,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      INSERT INTO youtube_api_usage_daily (tenant_id, channel_id, dt, api_method, calls, units)
+      VALUES (?, ?, UTC_DATE(), ?, 1, ?)
+      ON DUPLICATE KEY UPDATE
+        calls = calls + 1,
+        units = units + VALUES(units);
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(api_method)
+    .bind(units)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+/// `(dt, units, calls)` per day across the tenant's channels.
+pub async fn fetch_youtube_api_units_daily(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    start_dt: chrono::NaiveDate,
+    end_dt: chrono::NaiveDate,
+) -> Result<Vec<(chrono::NaiveDate, i64, i64)>, Error> {
+    sqlx::query_as::<_, (chrono::NaiveDate, i64, i64)>(
+        r#"
+      SELECT dt,
+             CAST(SUM(units) AS SIGNED) AS units,
+             CAST(SUM(calls) AS SIGNED) AS calls
+      FROM youtube_api_usage_daily
+      WHERE tenant_id = ?
+        AND dt BETWEEN ? AND ?
+      GROUP BY dt
+      ORDER BY dt ASC;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(start_dt)
+    .bind(end_dt)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })
+}
+
+/// `(dt, events, cost_usd)` per UTC day for usage events that carried tokens or cost (daily
+/// counters such as `chat_risk_check_count` are left out).
+pub async fn fetch_llm_cost_daily(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    start_dt: chrono::NaiveDate,
+    end_dt: chrono::NaiveDate,
+) -> Result<Vec<(chrono::NaiveDate, i64, f64)>, Error> {
+    sqlx::query_as::<_, (chrono::NaiveDate, i64, f64)>(
+        r#"
+      SELECT DATE(occurred_at) AS dt,
+             CAST(COUNT(*) AS SIGNED) AS events,
+             CAST(COALESCE(SUM(cost_usd), 0) AS DOUBLE) AS cost_usd
+      FROM usage_events
+      WHERE tenant_id = ?
+        AND occurred_at >= ?
+        AND occurred_at < ?
+        AND (prompt_tokens + completion_tokens > 0 OR cost_usd > 0)
+      GROUP BY DATE(occurred_at)
+      ORDER BY dt ASC;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(start_dt.and_hms_opt(0, 0, 0).unwrap_or_default())
+    .bind(
+        (end_dt + chrono::Duration::days(1))
+            .and_hms_opt(0, 0, 0)
+            .unwrap_or_default(),
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })
+}
+
 pub async fn ensure_trial_started(
     pool: &MySqlPool,
     tenant_id: &str,
@@ -1134,20 +1875,44 @@ pub async fn set_youtube_channel_id(
     Ok(())
 }
 
-pub async fn set_youtube_content_owner_id(
+/// `(channel_id, label)` in pin order.
+pub async fn fetch_pinned_channels(
     pool: &MySqlPool,
     tenant_id: &str,
-    content_owner_id: Option<&str>,
+) -> Result<Vec<(String, Option<String>)>, Error> {
+    sqlx::query_as::<_, (String, Option<String>)>(
+        r#"
+      SELECT channel_id, label
+      FROM tenant_pinned_channels
+      WHERE tenant_id = ?
+      ORDER BY created_at ASC, channel_id ASC;
+    "#,
+    )
+    .bind(tenant_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })
+}
+
+/// Re-pinning an existing channel only updates its label.
+pub async fn pin_channel(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    label: Option<&str>,
 ) -> Result<(), Error> {
     sqlx::query(
         r#"
-      UPDATE channel_connections
-      SET content_owner_id = ?
-      WHERE tenant_id = ? AND oauth_provider = 'youtube';
+      INSERT INTO tenant_pinned_channels (tenant_id, channel_id, label)
+      VALUES (?, ?, ?)
+      ON DUPLICATE KEY UPDATE
+        label = VALUES(label),
+        updated_at = CURRENT_TIMESTAMP(3);
     "#,
     )
-    .bind(content_owner_id)
     .bind(tenant_id)
+    .bind(channel_id)
+    .bind(label)
     .execute(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
@@ -1155,61 +1920,69 @@ pub async fn set_youtube_content_owner_id(
     Ok(())
 }
 
-#[derive(Debug, Clone)]
-pub struct YoutubeOAuthAppConfig {
-    pub client_id: String,
-    pub client_secret: Option<String>,
-    pub redirect_uri: String,
+/// Returns false when the channel was not pinned.
+pub async fn unpin_channel(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+) -> Result<bool, Error> {
+    let res = sqlx::query(
+        r#"
+      DELETE FROM tenant_pinned_channels
+      WHERE tenant_id = ? AND channel_id = ?;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(res.rows_affected() > 0)
 }
 
-pub async fn fetch_youtube_oauth_app_config(
+/// Pauses or resumes scheduled syncs for the tenant's YouTube connection; returns false when
+/// there is no connection to update.
+pub async fn set_youtube_connection_active(
     pool: &MySqlPool,
     tenant_id: &str,
-) -> Result<Option<YoutubeOAuthAppConfig>, Error> {
-    let row = sqlx::query_as::<_, (String, Option<String>, String)>(
+    active: bool,
+) -> Result<bool, Error> {
+    let res = sqlx::query(
         r#"
-      SELECT client_id, client_secret, redirect_uri
-      FROM oauth_apps
-      WHERE tenant_id = ? AND provider = 'youtube'
-      LIMIT 1;
+      UPDATE channel_connections
+      SET active = ?,
+          updated_at = CURRENT_TIMESTAMP(3)
+      WHERE tenant_id = ? AND oauth_provider = 'youtube';
     "#,
     )
+    .bind(active)
     .bind(tenant_id)
-    .fetch_optional(pool)
+    .execute(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(row.map(
-        |(client_id, client_secret, redirect_uri)| YoutubeOAuthAppConfig {
-            client_id,
-            client_secret,
-            redirect_uri,
-        },
-    ))
+    Ok(res.rows_affected() > 0)
 }
 
-pub async fn upsert_youtube_oauth_app_config(
+/// Set when Google rejects the stored refresh token (`invalid_grant`); cleared on reconnect.
+pub async fn set_youtube_connection_needs_reauth(
     pool: &MySqlPool,
     tenant_id: &str,
-    client_id: &str,
-    client_secret: Option<&str>,
-    redirect_uri: &str,
+    channel_id: &str,
+    needs_reauth: bool,
 ) -> Result<(), Error> {
     sqlx::query(
         r#"
-      INSERT INTO oauth_apps (tenant_id, provider, client_id, client_secret, redirect_uri)
-      VALUES (?, 'youtube', ?, ?, ?)
-      ON DUPLICATE KEY UPDATE
-        client_id = VALUES(client_id),
-        client_secret = COALESCE(VALUES(client_secret), client_secret),
-        redirect_uri = VALUES(redirect_uri),
-        updated_at = CURRENT_TIMESTAMP(3);
+      UPDATE channel_connections
+      SET needs_reauth = ?,
+          updated_at = CURRENT_TIMESTAMP(3)
+      WHERE tenant_id = ? AND oauth_provider = 'youtube' AND channel_id = ?;
     "#,
     )
+    .bind(needs_reauth)
     .bind(tenant_id)
-    .bind(client_id)
-    .bind(client_secret)
-    .bind(redirect_uri)
+    .bind(channel_id)
     .execute(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
@@ -1217,37 +1990,257 @@ pub async fn upsert_youtube_oauth_app_config(
     Ok(())
 }
 
-pub fn youtube_oauth_app_config_from_env() -> Result<YoutubeOAuthAppConfig, Error> {
-    let client_id = std::env::var("YOUTUBE_CLIENT_ID")
-        .map_err(|_| Box::new(std::io::Error::other("Missing YOUTUBE_CLIENT_ID")) as Error)?;
-    let client_secret = std::env::var("YOUTUBE_CLIENT_SECRET")
-        .map_err(|_| Box::new(std::io::Error::other("Missing YOUTUBE_CLIENT_SECRET")) as Error)?;
-    let redirect_uri = std::env::var("YOUTUBE_REDIRECT_URI")
-        .map_err(|_| Box::new(std::io::Error::other("Missing YOUTUBE_REDIRECT_URI")) as Error)?;
+/// Last `dt` the daily sync fetched from YouTube Analytics for this channel.
+pub async fn fetch_youtube_last_synced_dt(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+) -> Result<Option<chrono::NaiveDate>, Error> {
+    let row = sqlx::query_as::<_, (Option<chrono::NaiveDate>,)>(
+        r#"
+      SELECT last_synced_dt
+      FROM channel_connections
+      WHERE tenant_id = ? AND oauth_provider = 'youtube' AND channel_id = ?
+      LIMIT 1;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
 
-    let client_id = client_id.trim().to_string();
-    let client_secret = client_secret.trim().to_string();
-    let redirect_uri = redirect_uri.trim().to_string();
+    Ok(row.and_then(|(dt,)| dt))
+}
 
-    if client_id.is_empty() {
-        return Err(Box::new(std::io::Error::other("Missing YOUTUBE_CLIENT_ID")) as Error);
-    }
-    if client_secret.is_empty() {
-        return Err(Box::new(std::io::Error::other("Missing YOUTUBE_CLIENT_SECRET")) as Error);
-    }
-    if redirect_uri.is_empty() {
-        return Err(Box::new(std::io::Error::other("Missing YOUTUBE_REDIRECT_URI")) as Error);
-    }
+/// Moves the watermark forward only; backfill runs for older windows never rewind it.
+pub async fn advance_youtube_last_synced_dt(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    synced_dt: chrono::NaiveDate,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      UPDATE channel_connections
+      SET last_synced_dt = GREATEST(COALESCE(last_synced_dt, ?), ?)
+      WHERE tenant_id = ? AND oauth_provider = 'youtube' AND channel_id = ?;
+    "#,
+    )
+    .bind(synced_dt)
+    .bind(synced_dt)
+    .bind(tenant_id)
+    .bind(channel_id)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(YoutubeOAuthAppConfig {
-        client_id,
-        client_secret: Some(client_secret),
-        redirect_uri,
-    })
+    Ok(())
 }
 
-pub async fn fetch_or_seed_youtube_oauth_app_config(
-    pool: &MySqlPool,
+const REWIND_SYNC_WATERMARK_SQL: &str = r#"
+      UPDATE channel_connections
+      SET last_synced_dt = DATE_SUB(?, INTERVAL 1 DAY)
+      WHERE tenant_id = ? AND oauth_provider = 'youtube' AND channel_id = ?
+        AND last_synced_dt >= ?;
+    "#;
+
+/// Rewinds the watermark to just before `start_dt` when a purge removed synced days, so the next
+/// daily sync fetches them again; refill records for the purged days are dropped with it.
+pub async fn rewind_youtube_sync_for_purge(
+    conn: &mut sqlx::MySqlConnection,
+    tenant_id: &str,
+    channel_id: &str,
+    start_dt: chrono::NaiveDate,
+    end_dt: chrono::NaiveDate,
+) -> Result<(), Error> {
+    sqlx::query(REWIND_SYNC_WATERMARK_SQL)
+    .bind(start_dt)
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(start_dt)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    sqlx::query(
+        r#"
+      DELETE FROM yt_metric_gap_fills
+      WHERE tenant_id = ? AND channel_id = ? AND dt BETWEEN ? AND ?;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(start_dt)
+    .bind(end_dt)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+/// `None` until the alert evaluation has seen enough views to tell; see
+/// `youtube_alerts::infer_monetized`.
+pub async fn fetch_youtube_monetized(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+) -> Result<Option<bool>, Error> {
+    let row = sqlx::query_as::<_, (Option<bool>,)>(
+        r#"
+      SELECT monetized
+      FROM channel_connections
+      WHERE tenant_id = ? AND oauth_provider = 'youtube' AND channel_id = ?
+      LIMIT 1;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(row.and_then(|(monetized,)| monetized))
+}
+
+pub async fn set_youtube_monetized(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    monetized: bool,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      UPDATE channel_connections
+      SET monetized = ?
+      WHERE tenant_id = ? AND oauth_provider = 'youtube' AND channel_id = ?;
+    "#,
+    )
+    .bind(monetized)
+    .bind(tenant_id)
+    .bind(channel_id)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+pub async fn set_youtube_content_owner_id(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    content_owner_id: Option<&str>,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      UPDATE channel_connections
+      SET content_owner_id = ?
+      WHERE tenant_id = ? AND oauth_provider = 'youtube';
+    "#,
+    )
+    .bind(content_owner_id)
+    .bind(tenant_id)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct YoutubeOAuthAppConfig {
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub redirect_uri: String,
+}
+
+pub async fn fetch_youtube_oauth_app_config(
+    pool: &MySqlPool,
+    tenant_id: &str,
+) -> Result<Option<YoutubeOAuthAppConfig>, Error> {
+    let row = sqlx::query_as::<_, (String, Option<String>, String)>(
+        r#"
+      SELECT client_id, client_secret, redirect_uri
+      FROM oauth_apps
+      WHERE tenant_id = ? AND provider = 'youtube'
+      LIMIT 1;
+    "#,
+    )
+    .bind(tenant_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(row.map(
+        |(client_id, client_secret, redirect_uri)| YoutubeOAuthAppConfig {
+            client_id,
+            client_secret,
+            redirect_uri,
+        },
+    ))
+}
+
+pub async fn upsert_youtube_oauth_app_config(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    client_id: &str,
+    client_secret: Option<&str>,
+    redirect_uri: &str,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      INSERT INTO oauth_apps (tenant_id, provider, client_id, client_secret, redirect_uri)
+      VALUES (?, 'youtube', ?, ?, ?)
+      ON DUPLICATE KEY UPDATE
+        client_id = VALUES(client_id),
+        client_secret = COALESCE(VALUES(client_secret), client_secret),
+        redirect_uri = VALUES(redirect_uri),
+        updated_at = CURRENT_TIMESTAMP(3);
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(client_id)
+    .bind(client_secret)
+    .bind(redirect_uri)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+pub fn youtube_oauth_app_config_from_env() -> Result<YoutubeOAuthAppConfig, Error> {
+    let client_id = std::env::var("YOUTUBE_CLIENT_ID")
+        .map_err(|_| Box::new(std::io::Error::other("Missing YOUTUBE_CLIENT_ID")) as Error)?;
+    let client_secret = std::env::var("YOUTUBE_CLIENT_SECRET")
+        .map_err(|_| Box::new(std::io::Error::other("Missing YOUTUBE_CLIENT_SECRET")) as Error)?;
+    let redirect_uri = std::env::var("YOUTUBE_REDIRECT_URI")
+        .map_err(|_| Box::new(std::io::Error::other("Missing YOUTUBE_REDIRECT_URI")) as Error)?;
+
+    let client_id = client_id.trim().to_string();
+    let client_secret = client_secret.trim().to_string();
+    let redirect_uri = redirect_uri.trim().to_string();
+
+    if client_id.is_empty() {
+        return Err(Box::new(std::io::Error::other("Missing YOUTUBE_CLIENT_ID")) as Error);
+    }
+    if client_secret.is_empty() {
+        return Err(Box::new(std::io::Error::other("Missing YOUTUBE_CLIENT_SECRET")) as Error);
+    }
+    if redirect_uri.is_empty() {
+        return Err(Box::new(std::io::Error::other("Missing YOUTUBE_REDIRECT_URI")) as Error);
+    }
+
+    Ok(YoutubeOAuthAppConfig {
+        client_id,
+        client_secret: Some(client_secret),
+        redirect_uri,
+    })
+}
+
+pub async fn fetch_or_seed_youtube_oauth_app_config(
+    pool: &MySqlPool,
     tenant_id: &str,
 ) -> Result<Option<YoutubeOAuthAppConfig>, Error> {
     let existing = fetch_youtube_oauth_app_config(pool, tenant_id).await?;
@@ -1374,6 +2367,7 @@ pub async fn upsert_video_daily_metric(
         impressions = CASE WHEN VALUES(impressions) > 0 THEN VALUES(impressions) ELSE impressions END,
         impressions_ctr = COALESCE(VALUES(impressions_ctr), impressions_ctr),
         views = VALUES(views),
+        derived_total = 0,
         updated_at = CURRENT_TIMESTAMP(3);
     "#,
   )
@@ -1392,65 +2386,123 @@ pub async fn upsert_video_daily_metric(
     Ok(())
 }
 
-pub async fn upsert_video_daily_reach_metrics(
+/// Multi-row form of `upsert_video_daily_metric` with the same merge rules; callers chunk by
+/// `backfill::METRIC_UPSERT_BATCH_SIZE`.
+pub async fn upsert_video_daily_metrics_batch(
     pool: &MySqlPool,
     tenant_id: &str,
     channel_id: &str,
-    dt: chrono::NaiveDate,
-    video_id: &str,
-    impressions: i64,
-    impressions_ctr: Option<f64>,
-    views: i64,
+    rows: &[VideoDailyMetricRow],
 ) -> Result<(), Error> {
-    sqlx::query(
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let mut qb = sqlx::QueryBuilder::<sqlx::MySql>::new(
+        "INSERT INTO video_daily_metrics (tenant_id, channel_id, dt, video_id, estimated_revenue_usd, impressions, impressions_ctr, views) ",
+    );
+    qb.push_values(rows.iter(), |mut b, row| {
+        b.push_bind(tenant_id);
+        b.push_bind(channel_id);
+        b.push_bind(row.dt);
+        b.push_bind(&row.video_id);
+        b.push_bind(row.estimated_revenue_usd);
+        b.push_bind(row.impressions);
+        b.push_bind(row.impressions_ctr);
+        b.push_bind(row.views);
+    });
+    qb.push(
         r#"
-      INSERT INTO video_daily_metrics
-        (tenant_id, channel_id, dt, video_id, impressions, impressions_ctr, views)
-      VALUES
-        (?, ?, ?, ?, ?, ?, ?)
       ON DUPLICATE KEY UPDATE
-        impressions = VALUES(impressions),
+        estimated_revenue_usd = VALUES(estimated_revenue_usd),
+        impressions = CASE WHEN VALUES(impressions) > 0 THEN VALUES(impressions) ELSE impressions END,
         impressions_ctr = COALESCE(VALUES(impressions_ctr), impressions_ctr),
-        views = CASE WHEN VALUES(views) > 0 THEN VALUES(views) ELSE views END,
+        views = VALUES(views),
+        derived_total = 0,
         updated_at = CURRENT_TIMESTAMP(3);
     "#,
+    );
+
+    qb.build()
+        .execute(pool)
+        .await
+        .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+/// Stored API rows (per-video plus `__CHANNEL_TOTAL__`) for a window, shaped like a fresh
+/// Analytics fetch so decisions can be recomputed without re-downloading unchanged days.
+pub async fn fetch_video_daily_metric_rows(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    start_dt: chrono::NaiveDate,
+    end_dt: chrono::NaiveDate,
+) -> Result<Vec<VideoDailyMetricRow>, Error> {
+    let rows = sqlx::query_as::<_, (chrono::NaiveDate, String, f64, i64, Option<f64>, i64)>(
+        &format!(
+            r#"
+      SELECT dt, video_id,
+             CAST(estimated_revenue_usd AS DOUBLE) AS estimated_revenue_usd,
+             impressions, impressions_ctr, views
+      FROM video_daily_metrics
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND dt BETWEEN ? AND ?
+        AND video_id <> '{CSV_CHANNEL_TOTAL_VIDEO_ID}'
+      ORDER BY dt ASC, video_id ASC;
+    "#
+        ),
     )
     .bind(tenant_id)
     .bind(channel_id)
-    .bind(dt)
-    .bind(video_id)
-    .bind(impressions)
-    .bind(impressions_ctr)
-    .bind(views)
-    .execute(pool)
+    .bind(start_dt)
+    .bind(end_dt)
+    .fetch_all(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(())
+    Ok(rows
+        .into_iter()
+        .map(
+            |(dt, video_id, estimated_revenue_usd, impressions, impressions_ctr, views)| {
+                VideoDailyMetricRow {
+                    dt,
+                    video_id,
+                    estimated_revenue_usd,
+                    impressions,
+                    impressions_ctr,
+                    views,
+                }
+            },
+        )
+        .collect())
 }
 
-pub async fn fetch_new_video_publish_counts_by_dt(
+/// Days in the window that already hold a channel total written by an ingest (not rebuilt from
+/// per-video sums by `upsert_derived_channel_totals`).
+pub async fn fetch_authoritative_channel_total_dts(
     pool: &MySqlPool,
     tenant_id: &str,
     channel_id: &str,
     start_dt: chrono::NaiveDate,
     end_dt: chrono::NaiveDate,
-) -> Result<Vec<(chrono::NaiveDate, i64)>, Error> {
-    let rows = sqlx::query_as::<_, (chrono::NaiveDate, i64)>(
-        r#"
-      SELECT first_dt AS dt, COUNT(*) AS new_videos
-      FROM (
-        SELECT video_id, MIN(dt) AS first_dt
-        FROM video_daily_metrics
-        WHERE tenant_id = ?
-          AND channel_id = ?
-          AND video_id NOT IN ('__CHANNEL_TOTAL__','csv_channel_total')
-        GROUP BY video_id
-      ) AS v
-      WHERE first_dt BETWEEN ? AND ?
-      GROUP BY first_dt
-      ORDER BY first_dt ASC;
+) -> Result<Vec<chrono::NaiveDate>, Error> {
+    let rows = sqlx::query_as::<_, (chrono::NaiveDate,)>(
+        &format!(
+            r#"
+      SELECT DISTINCT dt
+      FROM video_daily_metrics
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND dt BETWEEN ? AND ?
+        AND {channel_totals}
+        AND derived_total = 0
+      ORDER BY dt ASC;
     "#,
+            channel_totals = channel_total_filter(),
+        ),
     )
     .bind(tenant_id)
     .bind(channel_id)
@@ -1460,32 +2512,96 @@ pub async fn fetch_new_video_publish_counts_by_dt(
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(rows)
+    Ok(rows.into_iter().map(|(dt,)| dt).collect())
 }
 
-pub async fn upsert_observed_action(
+/// Writes rows from `channel_totals::derive_channel_totals` with `derived_total = 1`. Re-running
+/// replaces earlier derived values, but a row an ingest has since made authoritative is kept, and
+/// impressions/CTR a reach ingest wrote are never replaced by per-video sums.
+pub async fn upsert_derived_channel_totals(
     pool: &MySqlPool,
     tenant_id: &str,
     channel_id: &str,
-    dt: chrono::NaiveDate,
-    action_type: &str,
-    action_meta_json: Option<&str>,
+    rows: &[VideoDailyMetricRow],
 ) -> Result<(), Error> {
-    sqlx::query(
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let mut qb = sqlx::QueryBuilder::<sqlx::MySql>::new(
+        "INSERT INTO video_daily_metrics (tenant_id, channel_id, dt, video_id, estimated_revenue_usd, impressions, impressions_ctr, views, derived_total) ",
+    );
+    qb.push_values(rows.iter(), |mut b, row| {
+        b.push_bind(tenant_id);
+        b.push_bind(channel_id);
+        b.push_bind(row.dt);
+        b.push_bind(&row.video_id);
+        b.push_bind(row.estimated_revenue_usd);
+        b.push_bind(row.impressions);
+        b.push_bind(row.impressions_ctr);
+        b.push_bind(row.views);
+        b.push_bind(1_i8);
+    });
+    qb.push(
         r#"
-      INSERT INTO observed_actions
-        (tenant_id, channel_id, dt, action_type, action_meta_json)
+      ON DUPLICATE KEY UPDATE
+        estimated_revenue_usd = IF(derived_total = 1, VALUES(estimated_revenue_usd), estimated_revenue_usd),
+        impressions = IF(derived_total = 1 AND reach_ingested_at IS NULL, VALUES(impressions), impressions),
+        impressions_ctr = IF(derived_total = 1 AND reach_ingested_at IS NULL, VALUES(impressions_ctr), impressions_ctr),
+        views = IF(derived_total = 1, VALUES(views), views),
+        updated_at = CURRENT_TIMESTAMP(3);
+    "#,
+    );
+
+    qb.build()
+        .execute(pool)
+        .await
+        .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+/// `clicks` is only known when the reach report carries a clicks column. Rows are stamped with
+/// `reach_ingested_at`. A channel total the reach report creates carries no revenue, so it is
+/// inserted as `derived_total = 1`: the day still counts as derivable, and a rebuild fills in
+/// revenue and views while keeping the reach impressions and CTR.
+#[allow(clippy::too_many_arguments)]
+pub async fn upsert_video_daily_reach_metrics(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    dt: chrono::NaiveDate,
+    video_id: &str,
+    impressions: i64,
+    impressions_ctr: Option<f64>,
+    clicks: Option<i64>,
+    views: i64,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      INSERT INTO video_daily_metrics
+        (tenant_id, channel_id, dt, video_id, impressions, impressions_ctr, clicks, views,
+         derived_total, reach_ingested_at)
       VALUES
-        (?, ?, ?, ?, ?)
+        (?, ?, ?, ?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP(3))
       ON DUPLICATE KEY UPDATE
-        action_meta_json = VALUES(action_meta_json);
+        impressions = VALUES(impressions),
+        impressions_ctr = COALESCE(VALUES(impressions_ctr), impressions_ctr),
+        clicks = COALESCE(VALUES(clicks), clicks),
+        views = CASE WHEN VALUES(views) > 0 THEN VALUES(views) ELSE views END,
+        reach_ingested_at = CURRENT_TIMESTAMP(3),
+        updated_at = CURRENT_TIMESTAMP(3);
     "#,
     )
     .bind(tenant_id)
     .bind(channel_id)
     .bind(dt)
-    .bind(action_type)
-    .bind(action_meta_json)
+    .bind(video_id)
+    .bind(impressions)
+    .bind(impressions_ctr)
+    .bind(clicks)
+    .bind(views)
+    .bind(is_channel_total_video_id(video_id) as i8)
     .execute(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
@@ -1493,237 +2609,292 @@ pub async fn upsert_observed_action(
     Ok(())
 }
 
-pub async fn decision_daily_exists(
+pub async fn fetch_new_video_publish_counts_by_dt(
     pool: &MySqlPool,
     tenant_id: &str,
     channel_id: &str,
-    as_of_dt: chrono::NaiveDate,
-) -> Result<bool, Error> {
-    let row = sqlx::query_as::<_, (i32,)>(
-        r#"
-      SELECT 1
-      FROM decision_daily
-      WHERE tenant_id = ?
-        AND channel_id = ?
-        AND as_of_dt = ?
-      LIMIT 1;
+    start_dt: chrono::NaiveDate,
+    end_dt: chrono::NaiveDate,
+) -> Result<Vec<(chrono::NaiveDate, i64)>, Error> {
+    let rows = sqlx::query_as::<_, (chrono::NaiveDate, i64)>(
+        &format!(
+            r#"
+      SELECT first_dt AS dt, COUNT(*) AS new_videos
+      FROM (
+        SELECT video_id, MIN(dt) AS first_dt
+        FROM video_daily_metrics
+        WHERE tenant_id = ?
+          AND channel_id = ?
+          AND {video_rows}
+        GROUP BY video_id
+      ) AS v
+      WHERE first_dt BETWEEN ? AND ?
+      GROUP BY first_dt
+      ORDER BY first_dt ASC;
     "#,
+            video_rows = video_rows_filter(),
+        ),
     )
     .bind(tenant_id)
     .bind(channel_id)
-    .bind(as_of_dt)
-    .fetch_optional(pool)
+    .bind(start_dt)
+    .bind(end_dt)
+    .fetch_all(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(row.is_some())
+    Ok(rows)
 }
 
-pub async fn fetch_revenue_sum_usd_7d(
+pub async fn upsert_observed_action(
     pool: &MySqlPool,
     tenant_id: &str,
     channel_id: &str,
-    start_dt: chrono::NaiveDate,
-    end_dt: chrono::NaiveDate,
-) -> Result<f64, Error> {
-    let (total_rows, total_sum_usd): (i64, f64) = sqlx::query_as(
+    dt: chrono::NaiveDate,
+    action_type: &str,
+    action_meta_json: Option<&str>,
+) -> Result<(), Error> {
+    sqlx::query(
         r#"
-      SELECT CAST(COUNT(*) AS SIGNED) AS rows_n,
-             COALESCE(SUM(CAST(estimated_revenue_usd AS DOUBLE)), 0) AS revenue_sum_usd
-      FROM video_daily_metrics
-      WHERE tenant_id = ?
-        AND channel_id = ?
-        AND dt BETWEEN ? AND ?
-        AND video_id IN ('__CHANNEL_TOTAL__','csv_channel_total');
+      INSERT INTO observed_actions
+        (tenant_id, channel_id, dt, action_type, action_meta_json)
+      VALUES
+        (?, ?, ?, ?, ?)
+      ON DUPLICATE KEY UPDATE
+        action_meta_json = VALUES(action_meta_json);
     "#,
     )
     .bind(tenant_id)
     .bind(channel_id)
-    .bind(start_dt)
-    .bind(end_dt)
-    .fetch_one(pool)
+    .bind(dt)
+    .bind(action_type)
+    .bind(action_meta_json)
+    .execute(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    if total_rows > 0 {
-        return Ok(total_sum_usd);
-    }
+    Ok(())
+}
 
-    let (sum_usd,): (f64,) = sqlx::query_as(
+/// Records that the app changed a video (`kind`: title / thumbnail / publish_time) so experiment
+/// baselines can avoid the contaminated days.
+pub async fn record_video_change(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    video_id: &str,
+    kind: &str,
+    dt: chrono::NaiveDate,
+) -> Result<(), Error> {
+    let action_type = crate::experiments::video_change_action_type(kind, video_id);
+    let meta_json =
+        serde_json::json!({"video_id": video_id, "kind": kind, "source": "app"}).to_string();
+    upsert_observed_action(
+        pool,
+        tenant_id,
+        channel_id,
+        dt,
+        &action_type,
+        Some(&meta_json),
+    )
+    .await?;
+
+    // The stored snapshot predates this change; drop it so the snapshot diff reseeds instead of
+    // reporting the app's own edit as a manual one.
+    sqlx::query(
         r#"
-      SELECT COALESCE(SUM(CAST(estimated_revenue_usd AS DOUBLE)), 0) AS revenue_sum_usd
-      FROM video_daily_metrics
-      WHERE tenant_id = ?
-        AND channel_id = ?
-        AND dt BETWEEN ? AND ?
-        AND video_id NOT IN ('__CHANNEL_TOTAL__','csv_channel_total');
+      DELETE FROM yt_video_snapshots
+      WHERE tenant_id = ? AND channel_id = ? AND video_id = ?;
     "#,
     )
     .bind(tenant_id)
     .bind(channel_id)
-    .bind(start_dt)
-    .bind(end_dt)
-    .fetch_one(pool)
+    .bind(video_id)
+    .execute(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
-
-    Ok(sum_usd)
+    Ok(())
 }
 
-pub async fn fetch_top_video_ids_by_revenue(
+pub async fn fetch_video_change_dts(
     pool: &MySqlPool,
     tenant_id: &str,
     channel_id: &str,
+    video_id: &str,
     start_dt: chrono::NaiveDate,
     end_dt: chrono::NaiveDate,
-    limit: i64,
-) -> Result<Vec<String>, Error> {
-    let limit = limit.clamp(1, 50);
-    let rows = sqlx::query_as::<_, (String,)>(
+) -> Result<Vec<chrono::NaiveDate>, Error> {
+    let rows = sqlx::query_as::<_, (chrono::NaiveDate, String)>(
         r#"
-      SELECT video_id
-      FROM video_daily_metrics
-      WHERE tenant_id = ?
-        AND channel_id = ?
+      SELECT dt, action_type
+      FROM observed_actions
+      WHERE tenant_id = ? AND channel_id = ?
         AND dt BETWEEN ? AND ?
-        AND video_id NOT IN ('__CHANNEL_TOTAL__','csv_channel_total')
-      GROUP BY video_id
-      ORDER BY SUM(CAST(estimated_revenue_usd AS DOUBLE)) DESC
-      LIMIT ?;
+        AND action_type LIKE 'video\_change:%'
+      ORDER BY dt ASC;
     "#,
     )
     .bind(tenant_id)
     .bind(channel_id)
     .bind(start_dt)
     .bind(end_dt)
-    .bind(limit)
     .fetch_all(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(rows.into_iter().map(|(video_id,)| video_id).collect())
+    Ok(rows
+        .into_iter()
+        .filter(|(_, action_type)| {
+            crate::experiments::is_video_change_action_for(action_type, video_id)
+        })
+        .map(|(dt, _)| dt)
+        .collect())
 }
 
-pub async fn upsert_decision_outcome(
+/// Kinds of the app's own changes to `video_id` recorded between `start_dt` and `end_dt`.
+pub async fn fetch_app_video_change_kinds(
     pool: &MySqlPool,
     tenant_id: &str,
     channel_id: &str,
-    decision_dt: chrono::NaiveDate,
-    outcome_dt: chrono::NaiveDate,
-    revenue_change_pct_7d: Option<f64>,
-    catastrophic_flag: bool,
-    new_top_asset_flag: bool,
-    notes: Option<&str>,
-) -> Result<(), Error> {
-    sqlx::query(
-    r#"
-      INSERT INTO decision_outcome
-        (tenant_id, channel_id, decision_dt, outcome_dt, revenue_change_pct_7d, catastrophic_flag, new_top_asset_flag, notes)
-      VALUES
-        (?, ?, ?, ?, ?, ?, ?, ?)
-      ON DUPLICATE KEY UPDATE
-        revenue_change_pct_7d = VALUES(revenue_change_pct_7d),
-        catastrophic_flag = VALUES(catastrophic_flag),
-        new_top_asset_flag = VALUES(new_top_asset_flag),
-        notes = VALUES(notes);
+    video_id: &str,
+    start_dt: chrono::NaiveDate,
+    end_dt: chrono::NaiveDate,
+) -> Result<Vec<String>, Error> {
+    let rows = sqlx::query_scalar::<_, String>(
+        r#"
+      SELECT DISTINCT action_type
+      FROM observed_actions
+      WHERE tenant_id = ? AND channel_id = ?
+        AND dt BETWEEN ? AND ?
+        AND action_type LIKE 'video\_change:%'
+        AND JSON_UNQUOTE(JSON_EXTRACT(action_meta_json, '$.source')) = 'app';
     "#,
-  )
-  .bind(tenant_id)
-  .bind(channel_id)
-  .bind(decision_dt)
-  .bind(outcome_dt)
-  .bind(revenue_change_pct_7d)
-  .bind(if catastrophic_flag { 1 } else { 0 })
-  .bind(if new_top_asset_flag { 1 } else { 0 })
-  .bind(notes)
-  .execute(pool)
-  .await
-  .map_err(|e| -> Error { Box::new(e) })?;
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(start_dt)
+    .bind(end_dt)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(())
+    Ok(rows
+        .iter()
+        .filter_map(|action_type| {
+            crate::experiments::video_change_kind_for(action_type, video_id).map(str::to_string)
+        })
+        .collect())
 }
 
-pub async fn fetch_policy_params_json(
+#[derive(Debug, Clone)]
+pub struct StoredVideoSnapshot {
+    pub title: String,
+    pub thumbnail_url: Option<String>,
+    pub publish_at: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+pub async fn fetch_stored_video_snapshot(
     pool: &MySqlPool,
     tenant_id: &str,
     channel_id: &str,
-    version: &str,
-) -> Result<Option<String>, Error> {
-    let row = sqlx::query_as::<_, (String,)>(
+    video_id: &str,
+) -> Result<Option<StoredVideoSnapshot>, Error> {
+    let row = sqlx::query_as::<_, (String, Option<String>, Option<String>, DateTime<Utc>)>(
         r#"
-      SELECT params_json
-      FROM policy_params
-      WHERE tenant_id = ?
-        AND channel_id = ?
-        AND version = ?
+      SELECT title, thumbnail_url, publish_at, checked_at
+      FROM yt_video_snapshots
+      WHERE tenant_id = ? AND channel_id = ? AND video_id = ?
       LIMIT 1;
     "#,
     )
     .bind(tenant_id)
     .bind(channel_id)
-    .bind(version)
+    .bind(video_id)
     .fetch_optional(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(row.map(|(json,)| json))
+    Ok(row.map(
+        |(title, thumbnail_url, publish_at, checked_at)| StoredVideoSnapshot {
+            title,
+            thumbnail_url,
+            publish_at,
+            checked_at,
+        },
+    ))
 }
 
-pub async fn upsert_policy_params(
+pub async fn upsert_stored_video_snapshot(
     pool: &MySqlPool,
     tenant_id: &str,
     channel_id: &str,
-    version: &str,
-    params_json: &str,
-    created_by: &str,
+    video_id: &str,
+    snapshot: &VideoSnapshot,
 ) -> Result<(), Error> {
     sqlx::query(
         r#"
-      INSERT INTO policy_params
-        (tenant_id, channel_id, version, params_json, created_by)
+      INSERT INTO yt_video_snapshots
+        (tenant_id, channel_id, video_id, title, thumbnail_url, publish_at, checked_at)
       VALUES
-        (?, ?, ?, ?, ?)
+        (?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP(3))
       ON DUPLICATE KEY UPDATE
-        params_json = VALUES(params_json),
-        created_by = VALUES(created_by);
+        title = VALUES(title),
+        thumbnail_url = VALUES(thumbnail_url),
+        publish_at = VALUES(publish_at),
+        checked_at = VALUES(checked_at);
     "#,
     )
     .bind(tenant_id)
     .bind(channel_id)
-    .bind(version)
-    .bind(params_json)
-    .bind(created_by)
+    .bind(video_id)
+    .bind(&snapshot.title)
+    .bind(snapshot.thumbnail_url.as_deref())
+    .bind(snapshot.publish_at.as_deref())
     .execute(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
-
     Ok(())
 }
 
-pub async fn upsert_policy_eval_report(
+pub async fn upsert_decision_daily(
     pool: &MySqlPool,
     tenant_id: &str,
     channel_id: &str,
-    candidate_version: &str,
-    replay_metrics_json: &str,
-    approved: bool,
+    decision: &crate::decision_engine::DecisionDailyComputed,
 ) -> Result<(), Error> {
+    let evidence_json =
+        serde_json::to_string(&decision.evidence).unwrap_or_else(|_| "[]".to_string());
+    let forbidden_json =
+        serde_json::to_string(&decision.forbidden).unwrap_or_else(|_| "[]".to_string());
+    let reevaluate_json =
+        serde_json::to_string(&decision.reevaluate).unwrap_or_else(|_| "[]".to_string());
+
     sqlx::query(
         r#"
-      INSERT INTO policy_eval_report
-        (tenant_id, channel_id, candidate_version, replay_metrics_json, approved)
-      VALUES
-        (?, ?, ?, ?, ?)
+      INSERT INTO decision_daily (
+        tenant_id, channel_id, as_of_dt,
+        direction, confidence,
+        evidence_json, forbidden_json, reevaluate_json
+      )
+      VALUES (?, ?, ?, ?, ?, ?, ?, ?)
       ON DUPLICATE KEY UPDATE
-        replay_metrics_json = VALUES(replay_metrics_json),
-        approved = VALUES(approved);
+        direction = VALUES(direction),
+        confidence = VALUES(confidence),
+        evidence_json = VALUES(evidence_json),
+        forbidden_json = VALUES(forbidden_json),
+        reevaluate_json = VALUES(reevaluate_json),
+        updated_at = CURRENT_TIMESTAMP(3);
     "#,
     )
     .bind(tenant_id)
     .bind(channel_id)
-    .bind(candidate_version)
-    .bind(replay_metrics_json)
-    .bind(if approved { 1 } else { 0 })
+    .bind(decision.as_of_dt)
+    .bind(&decision.direction)
+    .bind(decision.confidence)
+    .bind(evidence_json)
+    .bind(forbidden_json)
+    .bind(reevaluate_json)
     .execute(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
@@ -1731,361 +2902,975 @@ pub async fn upsert_policy_eval_report(
     Ok(())
 }
 
-#[derive(Debug, Clone)]
-pub struct TenantAiProviderSettingRow {
-    pub tenant_id: String,
-    pub provider: String,
-    pub status: String,
-    pub default_model: String,
-    pub model_allowlist_json: Option<String>,
-    pub encrypted_api_key: String,
-    pub encrypted_dek: Option<String>,
-    pub key_version: String,
-    pub key_fingerprint: String,
-    pub last_test_status: Option<String>,
-    pub last_test_error: Option<String>,
-    pub last_test_at: Option<DateTime<Utc>>,
-    pub created_by: String,
-    pub updated_by: String,
-    pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
-}
+pub async fn decision_daily_exists(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    as_of_dt: chrono::NaiveDate,
+) -> Result<bool, Error> {
+    let row = sqlx::query_as::<_, (i32,)>(
+        r#"
+      SELECT 1
+      FROM decision_daily
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND as_of_dt = ?
+      LIMIT 1;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(as_of_dt)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
 
-#[derive(Debug, Clone)]
-pub struct TenantAiRoutingPolicyRow {
-    pub tenant_id: String,
-    pub default_provider: String,
-    pub monthly_budget_usd: Option<f64>,
-    pub updated_by: String,
-    pub updated_at: DateTime<Utc>,
+    Ok(row.is_some())
 }
 
-pub async fn upsert_tenant_ai_provider_setting(
+/// A stored decision: the one for `as_of_dt`, or with `before` the latest one before it.
+pub async fn fetch_decision_daily(
     pool: &MySqlPool,
     tenant_id: &str,
-    provider: &str,
-    status: &str,
-    default_model: &str,
-    model_allowlist_json: Option<&str>,
-    encrypted_api_key: &str,
-    encrypted_dek: Option<&str>,
-    key_version: &str,
-    key_fingerprint: &str,
-    created_by: &str,
-    updated_by: &str,
-) -> Result<(), Error> {
-    sqlx::query(
+    channel_id: &str,
+    as_of_dt: chrono::NaiveDate,
+    before: bool,
+) -> Result<Option<crate::decision_engine::DecisionDailyComputed>, Error> {
+    let sql = if before {
         r#"
-      INSERT INTO tenant_ai_provider_settings
-        (
-          tenant_id, provider, status, default_model, model_allowlist_json,
-          encrypted_api_key, encrypted_dek, key_version, key_fingerprint,
-          created_by, updated_by
-        )
-      VALUES
-        (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-      ON DUPLICATE KEY UPDATE
-        status = VALUES(status),
-        default_model = VALUES(default_model),
-        model_allowlist_json = VALUES(model_allowlist_json),
-        encrypted_api_key = VALUES(encrypted_api_key),
-        encrypted_dek = VALUES(encrypted_dek),
-        key_version = VALUES(key_version),
-        key_fingerprint = VALUES(key_fingerprint),
-        updated_by = VALUES(updated_by),
-        updated_at = CURRENT_TIMESTAMP(3);
+      SELECT as_of_dt, direction, CAST(confidence AS DOUBLE) AS confidence,
+             evidence_json, forbidden_json, reevaluate_json
+      FROM decision_daily
+      WHERE tenant_id = ? AND channel_id = ? AND as_of_dt < ?
+      ORDER BY as_of_dt DESC
+      LIMIT 1;
+    "#
+    } else {
+        r#"
+      SELECT as_of_dt, direction, CAST(confidence AS DOUBLE) AS confidence,
+             evidence_json, forbidden_json, reevaluate_json
+      FROM decision_daily
+      WHERE tenant_id = ? AND channel_id = ? AND as_of_dt = ?
+      LIMIT 1;
+    "#
+    };
+    let row = sqlx::query_as::<_, (chrono::NaiveDate, String, f64, String, String, String)>(sql)
+        .bind(tenant_id)
+        .bind(channel_id)
+        .bind(as_of_dt)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| -> Error { Box::new(e) })?;
+
+    let list = |raw: &str| serde_json::from_str::<Vec<String>>(raw).unwrap_or_default();
+    Ok(row.map(
+        |(as_of_dt, direction, confidence, evidence, forbidden, reevaluate)| {
+            crate::decision_engine::DecisionDailyComputed {
+                as_of_dt,
+                direction,
+                confidence,
+                evidence: list(&evidence),
+                forbidden: list(&forbidden),
+                reevaluate: list(&reevaluate),
+            }
+        },
+    ))
+}
+
+pub async fn fetch_revenue_sum_usd_7d(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    start_dt: chrono::NaiveDate,
+    end_dt: chrono::NaiveDate,
+) -> Result<f64, Error> {
+    let (total_rows, total_sum_usd): (i64, f64) = sqlx::query_as(
+        &format!(
+            r#"
+      SELECT CAST(COUNT(*) AS SIGNED) AS rows_n,
+             COALESCE(SUM(CAST(estimated_revenue_usd AS DOUBLE)), 0) AS revenue_sum_usd
+      FROM video_daily_metrics
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND dt BETWEEN ? AND ?
+        AND {channel_totals};
     "#,
+            channel_totals = channel_total_filter(),
+        ),
     )
     .bind(tenant_id)
-    .bind(provider)
-    .bind(status)
-    .bind(default_model)
-    .bind(model_allowlist_json)
-    .bind(encrypted_api_key)
-    .bind(encrypted_dek)
-    .bind(key_version)
-    .bind(key_fingerprint)
-    .bind(created_by)
-    .bind(updated_by)
-    .execute(pool)
+    .bind(channel_id)
+    .bind(start_dt)
+    .bind(end_dt)
+    .fetch_one(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(())
+    if total_rows > 0 {
+        return Ok(total_sum_usd);
+    }
+
+    let (sum_usd,): (f64,) = sqlx::query_as(
+        &format!(
+            r#"
+      SELECT COALESCE(SUM(CAST(estimated_revenue_usd AS DOUBLE)), 0) AS revenue_sum_usd
+      FROM video_daily_metrics
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND dt BETWEEN ? AND ?
+        AND {video_rows};
+    "#,
+            video_rows = video_rows_filter(),
+        ),
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(start_dt)
+    .bind(end_dt)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(sum_usd)
 }
 
-pub async fn fetch_tenant_ai_provider_settings(
+pub async fn fetch_top_video_ids_by_revenue(
     pool: &MySqlPool,
     tenant_id: &str,
-) -> Result<Vec<TenantAiProviderSettingRow>, Error> {
-    let rows = sqlx::query_as::<
-        _,
-        (
-            String,
-            String,
-            String,
-            String,
-            Option<String>,
-            String,
-            Option<String>,
-            String,
-            String,
-            Option<String>,
-            Option<String>,
-            Option<DateTime<Utc>>,
-            String,
-            String,
-            DateTime<Utc>,
-            DateTime<Utc>,
-        ),
-    >(
-        r#"
-      SELECT
-        tenant_id,
-        provider,
-        status,
-        default_model,
-        model_allowlist_json,
-        encrypted_api_key,
-        encrypted_dek,
-        key_version,
-        key_fingerprint,
-        last_test_status,
-        last_test_error,
-        last_test_at,
-        created_by,
-        updated_by,
-        created_at,
-        updated_at
-      FROM tenant_ai_provider_settings
+    channel_id: &str,
+    start_dt: chrono::NaiveDate,
+    end_dt: chrono::NaiveDate,
+    limit: i64,
+) -> Result<Vec<String>, Error> {
+    let rows =
+        fetch_top_videos_with_revenue(pool, tenant_id, channel_id, start_dt, end_dt, limit).await?;
+    Ok(rows.into_iter().map(|(video_id, _)| video_id).collect())
+}
+
+/// Top videos by summed revenue over the window, with that revenue (highest first).
+pub async fn fetch_top_videos_with_revenue(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    start_dt: chrono::NaiveDate,
+    end_dt: chrono::NaiveDate,
+    limit: i64,
+) -> Result<Vec<(String, f64)>, Error> {
+    let limit = limit.clamp(1, 50);
+    let rows = sqlx::query_as::<_, (String, f64)>(
+        &format!(
+            r#"
+      SELECT video_id, COALESCE(SUM(CAST(estimated_revenue_usd AS DOUBLE)), 0) AS revenue_usd
+      FROM video_daily_metrics
       WHERE tenant_id = ?
-      ORDER BY provider ASC;
+        AND channel_id = ?
+        AND dt BETWEEN ? AND ?
+        AND {video_rows}
+      GROUP BY video_id
+      ORDER BY revenue_usd DESC
+      LIMIT ?;
     "#,
+            video_rows = video_rows_filter(),
+        ),
     )
     .bind(tenant_id)
+    .bind(channel_id)
+    .bind(start_dt)
+    .bind(end_dt)
+    .bind(limit)
     .fetch_all(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(rows
-        .into_iter()
-        .map(
-            |(
-                tenant_id,
-                provider,
-                status,
-                default_model,
-                model_allowlist_json,
-                encrypted_api_key,
-                encrypted_dek,
-                key_version,
-                key_fingerprint,
-                last_test_status,
-                last_test_error,
-                last_test_at,
-                created_by,
-                updated_by,
-                created_at,
-                updated_at,
-            )| TenantAiProviderSettingRow {
-                tenant_id,
-                provider,
-                status,
-                default_model,
-                model_allowlist_json,
-                encrypted_api_key,
-                encrypted_dek,
-                key_version,
-                key_fingerprint,
-                last_test_status,
-                last_test_error,
-                last_test_at,
-                created_by,
-                updated_by,
-                created_at,
-                updated_at,
-            },
-        )
-        .collect())
+    Ok(rows)
 }
 
-pub async fn fetch_tenant_ai_provider_setting(
+#[allow(clippy::too_many_arguments)]
+pub async fn upsert_decision_outcome(
     pool: &MySqlPool,
     tenant_id: &str,
-    provider: &str,
-) -> Result<Option<TenantAiProviderSettingRow>, Error> {
-    let row = sqlx::query_as::<
-        _,
-        (
-            String,
-            String,
-            String,
-            String,
-            Option<String>,
-            String,
-            Option<String>,
-            String,
-            String,
-            Option<String>,
-            Option<String>,
-            Option<DateTime<Utc>>,
-            String,
-            String,
-            DateTime<Utc>,
-            DateTime<Utc>,
-        ),
-    >(
+    channel_id: &str,
+    decision_dt: chrono::NaiveDate,
+    outcome_dt: chrono::NaiveDate,
+    revenue_change_pct_7d: Option<f64>,
+    catastrophic_flag: bool,
+    new_top_asset_flag: bool,
+    provisional: bool,
+    outcome_window_days: i64,
+    notes: Option<&str>,
+) -> Result<(), Error> {
+    sqlx::query(
+    r#"
+      INSERT INTO decision_outcome
+        (tenant_id, channel_id, decision_dt, outcome_dt, revenue_change_pct_7d, catastrophic_flag, new_top_asset_flag, provisional, outcome_window_days, notes)
+      VALUES
+        (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+      ON DUPLICATE KEY UPDATE
+        revenue_change_pct_7d = VALUES(revenue_change_pct_7d),
+        catastrophic_flag = VALUES(catastrophic_flag),
+        new_top_asset_flag = VALUES(new_top_asset_flag),
+        provisional = VALUES(provisional),
+        outcome_window_days = VALUES(outcome_window_days),
+        notes = VALUES(notes);
+    "#,
+  )
+  .bind(tenant_id)
+  .bind(channel_id)
+  .bind(decision_dt)
+  .bind(outcome_dt)
+  .bind(revenue_change_pct_7d)
+  .bind(if catastrophic_flag { 1 } else { 0 })
+  .bind(if new_top_asset_flag { 1 } else { 0 })
+  .bind(if provisional { 1 } else { 0 })
+  .bind(outcome_window_days)
+  .bind(notes)
+  .execute(pool)
+  .await
+  .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+/// Newest stored metric day for the channel, kept across disconnects.
+pub async fn fetch_latest_metric_dt(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+) -> Result<Option<chrono::NaiveDate>, Error> {
+    let (dt,): (Option<chrono::NaiveDate>,) = sqlx::query_as(
         r#"
-      SELECT
-        tenant_id,
-        provider,
-        status,
-        default_model,
-        model_allowlist_json,
-        encrypted_api_key,
-        encrypted_dek,
-        key_version,
-        key_fingerprint,
-        last_test_status,
-        last_test_error,
-        last_test_at,
-        created_by,
-        updated_by,
-        created_at,
-        updated_at
-      FROM tenant_ai_provider_settings
-      WHERE tenant_id = ?
-        AND provider = ?
-      LIMIT 1;
+      SELECT MAX(dt)
+      FROM video_daily_metrics
+      WHERE tenant_id = ? AND channel_id = ?;
     "#,
     )
     .bind(tenant_id)
-    .bind(provider)
+    .bind(channel_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(dt)
+}
+
+/// Stores a tenant API key by its hash; returns the key row id.
+pub async fn insert_tenant_api_key(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    key_hash: &str,
+    key_prefix: &str,
+    label: Option<&str>,
+) -> Result<i64, Error> {
+    let res = sqlx::query(
+        r#"
+      INSERT INTO tenant_api_keys (tenant_id, key_hash, key_prefix, label)
+      VALUES (?, ?, ?, ?);
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(key_hash)
+    .bind(key_prefix)
+    .bind(label)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(res.last_insert_id() as i64)
+}
+
+/// The tenant an unrevoked key hash belongs to, touching its last_used_at.
+pub async fn fetch_tenant_id_for_api_key_hash(
+    pool: &MySqlPool,
+    key_hash: &str,
+) -> Result<Option<String>, Error> {
+    let row: Option<(i64, String)> = sqlx::query_as(
+        r#"
+      SELECT id, tenant_id
+      FROM tenant_api_keys
+      WHERE key_hash = ? AND revoked_at IS NULL
+      LIMIT 1;
+    "#,
+    )
+    .bind(key_hash)
     .fetch_optional(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(row.map(
-        |(
-            tenant_id,
-            provider,
-            status,
-            default_model,
-            model_allowlist_json,
-            encrypted_api_key,
-            encrypted_dek,
-            key_version,
-            key_fingerprint,
-            last_test_status,
-            last_test_error,
-            last_test_at,
-            created_by,
-            updated_by,
-            created_at,
-            updated_at,
-        )| TenantAiProviderSettingRow {
-            tenant_id,
-            provider,
-            status,
-            default_model,
-            model_allowlist_json,
-            encrypted_api_key,
-            encrypted_dek,
-            key_version,
-            key_fingerprint,
-            last_test_status,
-            last_test_error,
-            last_test_at,
-            created_by,
-            updated_by,
-            created_at,
-            updated_at,
-        },
-    ))
+    let Some((id, tenant_id)) = row else {
+        return Ok(None);
+    };
+
+    sqlx::query(
+        r#"
+      UPDATE tenant_api_keys
+      SET last_used_at = CURRENT_TIMESTAMP(3)
+      WHERE id = ?;
+    "#,
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(Some(tenant_id))
 }
 
-pub async fn fetch_active_tenant_ai_provider_setting(
+pub async fn revoke_tenant_api_key(
     pool: &MySqlPool,
     tenant_id: &str,
-    provider: Option<&str>,
-) -> Result<Option<TenantAiProviderSettingRow>, Error> {
-    let row = if let Some(provider) = provider {
-        sqlx::query_as::<
-            _,
-            (
-                String,
-                String,
-                String,
-                String,
-                Option<String>,
-                String,
-                Option<String>,
-                String,
-                String,
-                Option<String>,
-                Option<String>,
-                Option<DateTime<Utc>>,
-                String,
-                String,
-                DateTime<Utc>,
-                DateTime<Utc>,
-            ),
-        >(
+    key_id: i64,
+) -> Result<bool, Error> {
+    let res = sqlx::query(
+        r#"
+      UPDATE tenant_api_keys
+      SET revoked_at = CURRENT_TIMESTAMP(3)
+      WHERE tenant_id = ? AND id = ? AND revoked_at IS NULL;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(key_id)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(res.rows_affected() > 0)
+}
+
+/// Queues daily_channel runs with the dispatcher's dedupe key, so a run it already queued is
+/// left as is.
+pub async fn enqueue_daily_channel_tasks(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    run_for_dts: &[chrono::NaiveDate],
+) -> Result<u64, Error> {
+    let max_attempt = max_attempt_for_job_type("daily_channel");
+    let mut inserted: u64 = 0;
+    for run_for_dt in run_for_dts.iter().copied() {
+        let dedupe_key = format!("{tenant_id}:daily_channel:{channel_id}:{run_for_dt}");
+        let res = sqlx::query(
             r#"
-        SELECT
-          tenant_id,
-          provider,
-          status,
-          default_model,
-          model_allowlist_json,
-          encrypted_api_key,
-          encrypted_dek,
-          key_version,
-          key_fingerprint,
-          last_test_status,
-          last_test_error,
-          last_test_at,
-          created_by,
-          updated_by,
-          created_at,
-          updated_at
-        FROM tenant_ai_provider_settings
-        WHERE tenant_id = ?
-          AND provider = ?
-          AND status = 'active'
-        LIMIT 1;
+        INSERT INTO job_tasks (tenant_id, job_type, channel_id, run_for_dt, dedupe_key, status, max_attempt)
+        VALUES (?, 'daily_channel', ?, ?, ?, 'pending', ?)
+        ON DUPLICATE KEY UPDATE updated_at = CURRENT_TIMESTAMP(3);
       "#,
         )
         .bind(tenant_id)
-        .bind(provider)
-        .fetch_optional(pool)
+        .bind(channel_id)
+        .bind(run_for_dt)
+        .bind(dedupe_key)
+        .bind(max_attempt)
+        .execute(pool)
         .await
-    } else {
-        sqlx::query_as::<
-            _,
-            (
-                String,
-                String,
-                String,
-                String,
-                Option<String>,
-                String,
-                Option<String>,
-                String,
-                String,
-                Option<String>,
-                Option<String>,
-                Option<DateTime<Utc>>,
-                String,
-                String,
-                DateTime<Utc>,
-                DateTime<Utc>,
-            ),
-        >(
-            r#"
-        SELECT
-          tenant_id,
-          provider,
+        .map_err(|e| -> Error { Box::new(e) })?;
+
+        inserted = inserted.saturating_add(res.rows_affected());
+    }
+
+    Ok(inserted)
+}
+
+/// Distinct dates in `[start_dt, end_dt]` with any stored metric row for the channel.
+pub async fn fetch_days_with_data(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    start_dt: chrono::NaiveDate,
+    end_dt: chrono::NaiveDate,
+) -> Result<i64, Error> {
+    let (days,): (i64,) = sqlx::query_as(
+        r#"
+      SELECT CAST(COUNT(DISTINCT dt) AS SIGNED)
+      FROM video_daily_metrics
+      WHERE tenant_id = ? AND channel_id = ? AND dt BETWEEN ? AND ?;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(start_dt)
+    .bind(end_dt)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(days)
+}
+
+/// `(decision_dt, outcome_dt, outcome_window_days)` of provisional outcomes still waiting on
+/// post-window data, oldest first. The window length is the one the outcome was first computed
+/// with (NULL for rows stored before it was recorded).
+pub async fn fetch_provisional_decision_outcomes(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    limit: i64,
+) -> Result<Vec<(chrono::NaiveDate, chrono::NaiveDate, Option<i64>)>, Error> {
+    let rows = sqlx::query_as::<_, (chrono::NaiveDate, chrono::NaiveDate, Option<i64>)>(
+        r#"
+      SELECT decision_dt, outcome_dt, CAST(outcome_window_days AS SIGNED) AS outcome_window_days
+      FROM decision_outcome
+      WHERE tenant_id = ? AND channel_id = ? AND provisional = 1
+      ORDER BY decision_dt ASC
+      LIMIT ?;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(rows)
+}
+
+/// Finalizes provisional outcomes whose `outcome_dt` is before `cutoff_dt`: their post window is
+/// treated as permanently incomplete, so they keep their last computed values and stop taking
+/// recheck slots from newer outcomes. Returns the number of rows finalized.
+pub async fn finalize_expired_provisional_decision_outcomes(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    cutoff_dt: chrono::NaiveDate,
+) -> Result<u64, Error> {
+    let result = sqlx::query(
+        r#"
+      UPDATE decision_outcome
+      SET provisional = 0,
+          notes = IF(
+            JSON_VALID(notes),
+            JSON_SET(notes, '$.provisional', CAST('false' AS JSON), '$.finalized_incomplete', CAST('true' AS JSON)),
+            notes
+          )
+      WHERE tenant_id = ? AND channel_id = ? AND provisional = 1
+        AND outcome_dt < ?;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(cutoff_dt)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(result.rows_affected())
+}
+
+pub async fn fetch_policy_params_json(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    version: &str,
+) -> Result<Option<String>, Error> {
+    let row = sqlx::query_as::<_, (String,)>(
+        r#"
+      SELECT params_json
+      FROM policy_params
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND version = ?
+      LIMIT 1;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(version)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(row.map(|(json,)| json))
+}
+
+pub async fn upsert_policy_params(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    version: &str,
+    params_json: &str,
+    created_by: &str,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      INSERT INTO policy_params
+        (tenant_id, channel_id, version, params_json, created_by)
+      VALUES
+        (?, ?, ?, ?, ?)
+      ON DUPLICATE KEY UPDATE
+        params_json = VALUES(params_json),
+        created_by = VALUES(created_by);
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(version)
+    .bind(params_json)
+    .bind(created_by)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+pub async fn fetch_alert_templates(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+) -> Result<Vec<(String, String)>, Error> {
+    sqlx::query_as::<_, (String, String)>(
+        r#"
+      SELECT alert_key, template
+      FROM yt_alert_templates
+      WHERE tenant_id = ?
+        AND channel_id = ?
+      ORDER BY alert_key ASC;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })
+}
+
+pub async fn upsert_alert_template(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    alert_key: &str,
+    template: &str,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      INSERT INTO yt_alert_templates (tenant_id, channel_id, alert_key, template)
+      VALUES (?, ?, ?, ?)
+      ON DUPLICATE KEY UPDATE
+        template = VALUES(template),
+        updated_at = CURRENT_TIMESTAMP(3);
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(alert_key)
+    .bind(template)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+pub async fn delete_alert_template(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    alert_key: &str,
+) -> Result<bool, Error> {
+    let result = sqlx::query(
+        r#"
+      DELETE FROM yt_alert_templates
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND alert_key = ?;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(alert_key)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn upsert_policy_eval_report(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    candidate_version: &str,
+    replay_metrics_json: &str,
+    approved: bool,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      INSERT INTO policy_eval_report
+        (tenant_id, channel_id, candidate_version, replay_metrics_json, approved)
+      VALUES
+        (?, ?, ?, ?, ?)
+      ON DUPLICATE KEY UPDATE
+        replay_metrics_json = VALUES(replay_metrics_json),
+        approved = VALUES(approved);
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(candidate_version)
+    .bind(replay_metrics_json)
+    .bind(if approved { 1 } else { 0 })
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct TenantAiProviderSettingRow {
+    pub tenant_id: String,
+    pub provider: String,
+    pub status: String,
+    pub default_model: String,
+    pub model_allowlist_json: Option<String>,
+    pub encrypted_api_key: String,
+    pub encrypted_dek: Option<String>,
+    pub key_version: String,
+    pub key_fingerprint: String,
+    pub last_test_status: Option<String>,
+    pub last_test_error: Option<String>,
+    pub last_test_at: Option<DateTime<Utc>>,
+    pub created_by: String,
+    pub updated_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TenantAiRoutingPolicyRow {
+    pub tenant_id: String,
+    pub default_provider: String,
+    pub monthly_budget_usd: Option<f64>,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub async fn upsert_tenant_ai_provider_setting(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    provider: &str,
+    status: &str,
+    default_model: &str,
+    model_allowlist_json: Option<&str>,
+    encrypted_api_key: &str,
+    encrypted_dek: Option<&str>,
+    key_version: &str,
+    key_fingerprint: &str,
+    created_by: &str,
+    updated_by: &str,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      INSERT INTO tenant_ai_provider_settings
+        (
+          tenant_id, provider, status, default_model, model_allowlist_json,
+          encrypted_api_key, encrypted_dek, key_version, key_fingerprint,
+          created_by, updated_by
+        )
+      VALUES
+        (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+      ON DUPLICATE KEY UPDATE
+        status = VALUES(status),
+        default_model = VALUES(default_model),
+        model_allowlist_json = VALUES(model_allowlist_json),
+        encrypted_api_key = VALUES(encrypted_api_key),
+        encrypted_dek = VALUES(encrypted_dek),
+        key_version = VALUES(key_version),
+        key_fingerprint = VALUES(key_fingerprint),
+        updated_by = VALUES(updated_by),
+        updated_at = CURRENT_TIMESTAMP(3);
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(provider)
+    .bind(status)
+    .bind(default_model)
+    .bind(model_allowlist_json)
+    .bind(encrypted_api_key)
+    .bind(encrypted_dek)
+    .bind(key_version)
+    .bind(key_fingerprint)
+    .bind(created_by)
+    .bind(updated_by)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+pub async fn fetch_tenant_ai_provider_settings(
+    pool: &MySqlPool,
+    tenant_id: &str,
+) -> Result<Vec<TenantAiProviderSettingRow>, Error> {
+    let rows = sqlx::query_as::<
+        _,
+        (
+            String,
+            String,
+            String,
+            String,
+            Option<String>,
+            String,
+            Option<String>,
+            String,
+            String,
+            Option<String>,
+            Option<String>,
+            Option<DateTime<Utc>>,
+            String,
+            String,
+            DateTime<Utc>,
+            DateTime<Utc>,
+        ),
+    >(
+        r#"
+      SELECT
+        tenant_id,
+        provider,
+        status,
+        default_model,
+        model_allowlist_json,
+        encrypted_api_key,
+        encrypted_dek,
+        key_version,
+        key_fingerprint,
+        last_test_status,
+        last_test_error,
+        last_test_at,
+        created_by,
+        updated_by,
+        created_at,
+        updated_at
+      FROM tenant_ai_provider_settings
+      WHERE tenant_id = ?
+      ORDER BY provider ASC;
+    "#,
+    )
+    .bind(tenant_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(
+                tenant_id,
+                provider,
+                status,
+                default_model,
+                model_allowlist_json,
+                encrypted_api_key,
+                encrypted_dek,
+                key_version,
+                key_fingerprint,
+                last_test_status,
+                last_test_error,
+                last_test_at,
+                created_by,
+                updated_by,
+                created_at,
+                updated_at,
+            )| TenantAiProviderSettingRow {
+                tenant_id,
+                provider,
+                status,
+                default_model,
+                model_allowlist_json,
+                encrypted_api_key,
+                encrypted_dek,
+                key_version,
+                key_fingerprint,
+                last_test_status,
+                last_test_error,
+                last_test_at,
+                created_by,
+                updated_by,
+                created_at,
+                updated_at,
+            },
+        )
+        .collect())
+}
+
+pub async fn fetch_tenant_ai_provider_setting(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    provider: &str,
+) -> Result<Option<TenantAiProviderSettingRow>, Error> {
+    let row = sqlx::query_as::<
+        _,
+        (
+            String,
+            String,
+            String,
+            String,
+            Option<String>,
+            String,
+            Option<String>,
+            String,
+            String,
+            Option<String>,
+            Option<String>,
+            Option<DateTime<Utc>>,
+            String,
+            String,
+            DateTime<Utc>,
+            DateTime<Utc>,
+        ),
+    >(
+        r#"
+      SELECT
+        tenant_id,
+        provider,
+        status,
+        default_model,
+        model_allowlist_json,
+        encrypted_api_key,
+        encrypted_dek,
+        key_version,
+        key_fingerprint,
+        last_test_status,
+        last_test_error,
+        last_test_at,
+        created_by,
+        updated_by,
+        created_at,
+        updated_at
+      FROM tenant_ai_provider_settings
+      WHERE tenant_id = ?
+        AND provider = ?
+      LIMIT 1;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(provider)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(row.map(
+        |(
+            tenant_id,
+            provider,
+            status,
+            default_model,
+            model_allowlist_json,
+            encrypted_api_key,
+            encrypted_dek,
+            key_version,
+            key_fingerprint,
+            last_test_status,
+            last_test_error,
+            last_test_at,
+            created_by,
+            updated_by,
+            created_at,
+            updated_at,
+        )| TenantAiProviderSettingRow {
+            tenant_id,
+            provider,
+            status,
+            default_model,
+            model_allowlist_json,
+            encrypted_api_key,
+            encrypted_dek,
+            key_version,
+            key_fingerprint,
+            last_test_status,
+            last_test_error,
+            last_test_at,
+            created_by,
+            updated_by,
+            created_at,
+            updated_at,
+        },
+    ))
+}
+
+pub async fn fetch_active_tenant_ai_provider_setting(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    provider: Option<&str>,
+) -> Result<Option<TenantAiProviderSettingRow>, Error> {
+    let row = if let Some(provider) = provider {
+        sqlx::query_as::<
+            _,
+            (
+                String,
+                String,
+                String,
+                String,
+                Option<String>,
+                String,
+                Option<String>,
+                String,
+                String,
+                Option<String>,
+                Option<String>,
+                Option<DateTime<Utc>>,
+                String,
+                String,
+                DateTime<Utc>,
+                DateTime<Utc>,
+            ),
+        >(
+            r#"
+        SELECT
+          tenant_id,
+          provider,
+          status,
+          default_model,
+          model_allowlist_json,
+          encrypted_api_key,
+          encrypted_dek,
+          key_version,
+          key_fingerprint,
+          last_test_status,
+          last_test_error,
+          last_test_at,
+          created_by,
+          updated_by,
+          created_at,
+          updated_at
+        FROM tenant_ai_provider_settings
+        WHERE tenant_id = ?
+          AND provider = ?
+          AND status = 'active'
+        LIMIT 1;
+      "#,
+        )
+        .bind(tenant_id)
+        .bind(provider)
+        .fetch_optional(pool)
+        .await
+    } else {
+        sqlx::query_as::<
+            _,
+            (
+                String,
+                String,
+                String,
+                String,
+                Option<String>,
+                String,
+                Option<String>,
+                String,
+                String,
+                Option<String>,
+                Option<String>,
+                Option<DateTime<Utc>>,
+                String,
+                String,
+                DateTime<Utc>,
+                DateTime<Utc>,
+            ),
+        >(
+            r#"
+        SELECT
+          tenant_id,
+          provider,
           status,
           default_model,
           model_allowlist_json,
@@ -2102,158 +3887,821 @@ pub async fn fetch_active_tenant_ai_provider_setting(
           updated_at
         FROM tenant_ai_provider_settings
         WHERE tenant_id = ?
-          AND status = 'active'
-        ORDER BY updated_at DESC
-        LIMIT 1;
+          AND status = 'active'
+        ORDER BY updated_at DESC
+        LIMIT 1;
+      "#,
+        )
+        .bind(tenant_id)
+        .fetch_optional(pool)
+        .await
+    }
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(row.map(
+        |(
+            tenant_id,
+            provider,
+            status,
+            default_model,
+            model_allowlist_json,
+            encrypted_api_key,
+            encrypted_dek,
+            key_version,
+            key_fingerprint,
+            last_test_status,
+            last_test_error,
+            last_test_at,
+            created_by,
+            updated_by,
+            created_at,
+            updated_at,
+        )| TenantAiProviderSettingRow {
+            tenant_id,
+            provider,
+            status,
+            default_model,
+            model_allowlist_json,
+            encrypted_api_key,
+            encrypted_dek,
+            key_version,
+            key_fingerprint,
+            last_test_status,
+            last_test_error,
+            last_test_at,
+            created_by,
+            updated_by,
+            created_at,
+            updated_at,
+        },
+    ))
+}
+
+pub async fn update_tenant_ai_provider_test_status(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    provider: &str,
+    test_status: &str,
+    test_error: Option<&str>,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      UPDATE tenant_ai_provider_settings
+      SET last_test_status = ?,
+          last_test_error = ?,
+          last_test_at = CURRENT_TIMESTAMP(3),
+          updated_at = CURRENT_TIMESTAMP(3)
+      WHERE tenant_id = ?
+        AND provider = ?;
+    "#,
+    )
+    .bind(test_status)
+    .bind(test_error)
+    .bind(tenant_id)
+    .bind(provider)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+pub async fn set_tenant_ai_provider_status(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    provider: &str,
+    status: &str,
+    updated_by: &str,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      UPDATE tenant_ai_provider_settings
+      SET status = ?,
+          updated_by = ?,
+          updated_at = CURRENT_TIMESTAMP(3)
+      WHERE tenant_id = ?
+        AND provider = ?;
+    "#,
+    )
+    .bind(status)
+    .bind(updated_by)
+    .bind(tenant_id)
+    .bind(provider)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+pub async fn insert_tenant_ai_provider_audit(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    provider: &str,
+    action: &str,
+    actor: &str,
+    request_id: Option<&str>,
+    before_json: Option<&str>,
+    after_json: Option<&str>,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      INSERT INTO tenant_ai_provider_audit
+        (tenant_id, provider, action, actor, request_id, before_json, after_json)
+      VALUES
+        (?, ?, ?, ?, ?, ?, ?);
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(provider)
+    .bind(action)
+    .bind(actor)
+    .bind(request_id)
+    .bind(before_json)
+    .bind(after_json)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+pub async fn fetch_tenant_ai_routing_policy(
+    pool: &MySqlPool,
+    tenant_id: &str,
+) -> Result<Option<TenantAiRoutingPolicyRow>, Error> {
+    let row = sqlx::query_as::<_, (String, String, Option<f64>, String, DateTime<Utc>)>(
+        r#"
+      SELECT
+        tenant_id,
+        default_provider,
+        CAST(monthly_budget_usd AS DOUBLE) AS monthly_budget_usd,
+        updated_by,
+        updated_at
+      FROM tenant_ai_routing_policy
+      WHERE tenant_id = ?
+      LIMIT 1;
+    "#,
+    )
+    .bind(tenant_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(row.map(
+        |(tenant_id, default_provider, monthly_budget_usd, updated_by, updated_at)| {
+            TenantAiRoutingPolicyRow {
+            tenant_id,
+            default_provider,
+            monthly_budget_usd,
+            updated_by,
+            updated_at,
+        }
+        },
+    ))
+}
+
+pub async fn upsert_tenant_ai_routing_policy(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    default_provider: &str,
+    monthly_budget_usd: Option<f64>,
+    updated_by: &str,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      INSERT INTO tenant_ai_routing_policy
+        (tenant_id, default_provider, monthly_budget_usd, updated_by)
+      VALUES
+        (?, ?, ?, ?)
+      ON DUPLICATE KEY UPDATE
+        default_provider = VALUES(default_provider),
+        monthly_budget_usd = VALUES(monthly_budget_usd),
+        updated_by = VALUES(updated_by),
+        updated_at = CURRENT_TIMESTAMP(3);
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(default_provider)
+    .bind(monthly_budget_usd)
+    .bind(updated_by)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct SubscriptionRow {
+    pub status: String,
+    pub current_period_end: Option<DateTime<Utc>>,
+}
+
+pub async fn fetch_subscription(
+    pool: &MySqlPool,
+    tenant_id: &str,
+) -> Result<Option<SubscriptionRow>, Error> {
+    let row = sqlx::query_as::<_, (String, Option<DateTime<Utc>>)>(
+        r#"
+      SELECT status, current_period_end
+      FROM subscriptions
+      WHERE tenant_id = ?
+      LIMIT 1;
+    "#,
+    )
+    .bind(tenant_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(row.map(|(status, current_period_end)| SubscriptionRow {
+        status,
+        current_period_end,
+    }))
+}
+
+pub async fn upsert_subscription(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    status: &str,
+    provider_customer_id: Option<&str>,
+    provider_subscription_id: Option<&str>,
+    current_period_end: Option<DateTime<Utc>>,
+) -> Result<(), Error> {
+    sqlx::query(
+    r#"
+      INSERT INTO subscriptions
+        (tenant_id, status, provider, provider_customer_id, provider_subscription_id, current_period_end)
+      VALUES
+        (?, ?, 'shopify', ?, ?, ?)
+      ON DUPLICATE KEY UPDATE
+        status = VALUES(status),
+        provider_customer_id = COALESCE(VALUES(provider_customer_id), provider_customer_id),
+        provider_subscription_id = COALESCE(VALUES(provider_subscription_id), provider_subscription_id),
+        current_period_end = COALESCE(VALUES(current_period_end), current_period_end),
+        updated_at = CURRENT_TIMESTAMP(3);
+    "#,
+  )
+  .bind(tenant_id)
+  .bind(status)
+  .bind(provider_customer_id)
+  .bind(provider_subscription_id)
+  .bind(current_period_end)
+  .execute(pool)
+  .await
+  .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
+}
+
+pub async fn upsert_youtube_connection(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    tokens: &crate::providers::youtube::YoutubeOAuthTokens,
+) -> Result<(), sqlx::Error> {
+    let expires_at = tokens
+        .expires_in_seconds
+        .map(|secs| chrono::Utc::now() + chrono::Duration::seconds(secs as i64));
+
+    sqlx::query(
+    r#"
+      INSERT INTO channel_connections
+        (tenant_id, oauth_provider, channel_id, access_token, refresh_token, token_type, scope, expires_at)
+      VALUES
+        (?, 'youtube', ?, ?, ?, ?, ?, ?)
+      ON DUPLICATE KEY UPDATE
+        channel_id = VALUES(channel_id),
+        access_token = VALUES(access_token),
+        refresh_token = COALESCE(VALUES(refresh_token), refresh_token),
+        token_type = VALUES(token_type),
+        scope = VALUES(scope),
+        expires_at = VALUES(expires_at),
+        needs_reauth = 0,
+        updated_at = CURRENT_TIMESTAMP(3);
+    "#,
+  )
+  .bind(tenant_id)
+  .bind(channel_id)
+  .bind(&tokens.access_token)
+  .bind(tokens.refresh_token.as_deref())
+  .bind(&tokens.token_type)
+  .bind(tokens.scope.as_deref())
+  .bind(expires_at)
+  .execute(pool)
+  .await?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct GeoMonitorProjectRow {
+    pub id: i64,
+    pub tenant_id: String,
+    pub name: String,
+    pub website: Option<String>,
+    pub brand_aliases_json: Option<String>,
+    pub competitor_names_json: Option<String>,
+    pub schedule: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeoMonitorPromptRow {
+    pub id: i64,
+    pub project_id: i64,
+    pub theme: Option<String>,
+    pub prompt_text: String,
+    pub enabled: bool,
+    pub sort_order: i32,
+    /// Per-prompt generation overrides; `None` uses the geo-monitor defaults.
+    pub temperature: Option<f64>,
+    pub max_output_tokens: Option<i32>,
+}
+
+type GeoMonitorPromptTuple = (i64, i64, Option<String>, String, i8, i32, Option<f64>, Option<i32>);
+
+#[derive(Debug, Clone)]
+pub struct GeoMonitorPromptInput {
+    pub theme: Option<String>,
+    pub prompt_text: String,
+    pub temperature: Option<f64>,
+    pub max_output_tokens: Option<i32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeoMonitorRunRow {
+    pub id: i64,
+    pub tenant_id: String,
+    pub project_id: i64,
+    pub run_for_dt: chrono::NaiveDate,
+    pub provider: String,
+    pub model: String,
+    pub status: String,
+    pub prompt_total: i32,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeoMonitorRunSummary {
+    pub results_total: i64,
+    pub presence_count: i64,
+    pub top3_count: i64,
+    pub top5_count: i64,
+    pub error_count: i64,
+    pub cost_usd: f64,
+}
+
+pub async fn create_geo_monitor_project(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    name: &str,
+    website: Option<&str>,
+    brand_aliases_json: Option<&str>,
+    competitor_names_json: Option<&str>,
+    schedule: &str,
+) -> Result<i64, Error> {
+    let schedule = match schedule.trim() {
+        "daily" | "Daily" | "DAILY" => "daily",
+        _ => "weekly",
+    };
+
+    let res = sqlx::query(
+        r#"
+      INSERT INTO geo_monitor_projects
+        (tenant_id, name, website, brand_aliases_json, competitor_names_json, schedule, enabled)
+      VALUES
+        (?, ?, ?, ?, ?, ?, 1);
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(name)
+    .bind(website)
+    .bind(brand_aliases_json)
+    .bind(competitor_names_json)
+    .bind(schedule)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(res.last_insert_id() as i64)
+}
+
+pub async fn list_geo_monitor_projects(
+    pool: &MySqlPool,
+    tenant_id: &str,
+) -> Result<Vec<GeoMonitorProjectRow>, Error> {
+    let rows: Vec<(i64, String, String, Option<String>, Option<String>, Option<String>, String, i8)> =
+    sqlx::query_as(
+      r#"
+        SELECT id, tenant_id, name, website, brand_aliases_json, competitor_names_json, schedule, enabled
+        FROM geo_monitor_projects
+        WHERE tenant_id = ?
+        ORDER BY updated_at DESC, id DESC;
       "#,
-        )
-        .bind(tenant_id)
-        .fetch_optional(pool)
-        .await
-    }
+    )
+    .bind(tenant_id)
+    .fetch_all(pool)
+    .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
+    Ok(rows
+        .into_iter()
+        .map(
+            |(
+                id,
+                tenant_id,
+                name,
+                website,
+                brand_aliases_json,
+                competitor_names_json,
+                schedule,
+                enabled,
+            )| {
+                GeoMonitorProjectRow {
+                    id,
+                    tenant_id,
+                    name,
+                    website,
+                    brand_aliases_json,
+                    competitor_names_json,
+                    schedule,
+                    enabled: enabled != 0,
+                }
+            },
+        )
+        .collect())
+}
+
+pub async fn fetch_geo_monitor_project(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    project_id: i64,
+) -> Result<Option<GeoMonitorProjectRow>, Error> {
+    let row: Option<(
+    i64,
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    String,
+    i8,
+  )> = sqlx::query_as(
+    r#"
+      SELECT id, tenant_id, name, website, brand_aliases_json, competitor_names_json, schedule, enabled
+      FROM geo_monitor_projects
+      WHERE tenant_id = ? AND id = ?
+      LIMIT 1;
+    "#,
+  )
+  .bind(tenant_id)
+  .bind(project_id)
+  .fetch_optional(pool)
+  .await
+  .map_err(|e| -> Error { Box::new(e) })?;
+
     Ok(row.map(
         |(
+            id,
             tenant_id,
-            provider,
-            status,
-            default_model,
-            model_allowlist_json,
-            encrypted_api_key,
-            encrypted_dek,
-            key_version,
-            key_fingerprint,
-            last_test_status,
-            last_test_error,
-            last_test_at,
-            created_by,
-            updated_by,
-            created_at,
-            updated_at,
-        )| TenantAiProviderSettingRow {
-            tenant_id,
-            provider,
-            status,
-            default_model,
-            model_allowlist_json,
-            encrypted_api_key,
-            encrypted_dek,
-            key_version,
-            key_fingerprint,
-            last_test_status,
-            last_test_error,
-            last_test_at,
-            created_by,
-            updated_by,
-            created_at,
-            updated_at,
+            name,
+            website,
+            brand_aliases_json,
+            competitor_names_json,
+            schedule,
+            enabled,
+        )| {
+            GeoMonitorProjectRow {
+                id,
+                tenant_id,
+                name,
+                website,
+                brand_aliases_json,
+                competitor_names_json,
+                schedule,
+                enabled: enabled != 0,
+            }
         },
     ))
 }
 
-pub async fn update_tenant_ai_provider_test_status(
+pub async fn replace_geo_monitor_prompts(
     pool: &MySqlPool,
     tenant_id: &str,
-    provider: &str,
-    test_status: &str,
-    test_error: Option<&str>,
+    project_id: i64,
+    prompts: &[GeoMonitorPromptInput],
 ) -> Result<(), Error> {
+    let mut tx = pool.begin().await.map_err(|e| -> Error { Box::new(e) })?;
+
     sqlx::query(
         r#"
-      UPDATE tenant_ai_provider_settings
-      SET last_test_status = ?,
-          last_test_error = ?,
-          last_test_at = CURRENT_TIMESTAMP(3),
-          updated_at = CURRENT_TIMESTAMP(3)
-      WHERE tenant_id = ?
-        AND provider = ?;
+      DELETE FROM geo_monitor_prompts
+      WHERE tenant_id = ? AND project_id = ?;
     "#,
     )
-    .bind(test_status)
-    .bind(test_error)
     .bind(tenant_id)
-    .bind(provider)
-    .execute(pool)
+    .bind(project_id)
+    .execute(&mut *tx)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
+    for (idx, prompt) in prompts.iter().enumerate() {
+        sqlx::query(
+            r#"
+        INSERT INTO geo_monitor_prompts
+          (tenant_id, project_id, theme, prompt_text, enabled, sort_order, temperature, max_output_tokens)
+        VALUES
+          (?, ?, ?, ?, 1, ?, ?, ?);
+      "#,
+        )
+        .bind(tenant_id)
+        .bind(project_id)
+        .bind(prompt.theme.as_deref())
+        .bind(&prompt.prompt_text)
+        .bind(idx as i32)
+        .bind(prompt.temperature)
+        .bind(prompt.max_output_tokens)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| -> Error { Box::new(e) })?;
+    }
+
+    tx.commit().await.map_err(|e| -> Error { Box::new(e) })?;
     Ok(())
 }
 
-pub async fn set_tenant_ai_provider_status(
+pub async fn list_geo_monitor_prompts(
     pool: &MySqlPool,
     tenant_id: &str,
-    provider: &str,
-    status: &str,
-    updated_by: &str,
-) -> Result<(), Error> {
-    sqlx::query(
+    project_id: i64,
+) -> Result<Vec<GeoMonitorPromptRow>, Error> {
+    let rows: Vec<GeoMonitorPromptTuple> = sqlx::query_as(
         r#"
-      UPDATE tenant_ai_provider_settings
-      SET status = ?,
-          updated_by = ?,
-          updated_at = CURRENT_TIMESTAMP(3)
-      WHERE tenant_id = ?
-        AND provider = ?;
+      SELECT id, project_id, theme, prompt_text, enabled, sort_order, temperature, max_output_tokens
+      FROM geo_monitor_prompts
+      WHERE tenant_id = ? AND project_id = ?
+      ORDER BY sort_order ASC, id ASC;
     "#,
     )
-    .bind(status)
-    .bind(updated_by)
     .bind(tenant_id)
-    .bind(provider)
-    .execute(pool)
+    .bind(project_id)
+    .fetch_all(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(())
+    Ok(rows
+        .into_iter()
+        .map(
+            |(
+                id,
+                project_id,
+                theme,
+                prompt_text,
+                enabled,
+                sort_order,
+                temperature,
+                max_output_tokens,
+            )| GeoMonitorPromptRow {
+                id,
+                project_id,
+                theme,
+                prompt_text,
+                enabled: enabled != 0,
+                sort_order,
+                temperature,
+                max_output_tokens,
+            },
+        )
+        .collect())
 }
 
-pub async fn insert_tenant_ai_provider_audit(
+pub async fn fetch_geo_monitor_prompt(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    project_id: i64,
+    prompt_id: i64,
+) -> Result<Option<GeoMonitorPromptRow>, Error> {
+    let row: Option<GeoMonitorPromptTuple> = sqlx::query_as(
+        r#"
+      SELECT id, project_id, theme, prompt_text, enabled, sort_order, temperature, max_output_tokens
+      FROM geo_monitor_prompts
+      WHERE tenant_id = ? AND project_id = ? AND id = ?
+      LIMIT 1;
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(project_id)
+    .bind(prompt_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(row.map(
+        |(
+            id,
+            project_id,
+            theme,
+            prompt_text,
+            enabled,
+            sort_order,
+            temperature,
+            max_output_tokens,
+        )| GeoMonitorPromptRow {
+            id,
+            project_id,
+            theme,
+            prompt_text,
+            enabled: enabled != 0,
+            sort_order,
+            temperature,
+            max_output_tokens,
+        },
+    ))
+}
+
+pub async fn ensure_geo_monitor_run(
     pool: &MySqlPool,
     tenant_id: &str,
+    project_id: i64,
+    run_for_dt: chrono::NaiveDate,
     provider: &str,
-    action: &str,
-    actor: &str,
-    request_id: Option<&str>,
-    before_json: Option<&str>,
-    after_json: Option<&str>,
-) -> Result<(), Error> {
-    sqlx::query(
+    model: &str,
+    prompt_total: i32,
+) -> Result<GeoMonitorRunRow, Error> {
+    let existing: Option<(
+    i64,
+    String,
+    i64,
+    chrono::NaiveDate,
+    String,
+    String,
+    String,
+    i32,
+    DateTime<Utc>,
+    Option<DateTime<Utc>>,
+  )> = sqlx::query_as(
+    r#"
+      SELECT id, tenant_id, project_id, run_for_dt, provider, model, status, prompt_total, started_at, finished_at
+      FROM geo_monitor_runs
+      WHERE tenant_id = ? AND project_id = ? AND run_for_dt = ?
+      LIMIT 1;
+    "#,
+  )
+  .bind(tenant_id)
+  .bind(project_id)
+  .bind(run_for_dt)
+  .fetch_optional(pool)
+  .await
+  .map_err(|e| -> Error { Box::new(e) })?;
+
+    if let Some((
+        id,
+        tenant_id,
+        project_id,
+        run_for_dt,
+        provider,
+        model,
+        status,
+        prompt_total_db,
+        started_at,
+        finished_at,
+    )) = existing
+    {
+        // Best-effort: keep prompt_total up to date for current prompt set, but do not reset existing runs.
+        if prompt_total_db != prompt_total && prompt_total > 0 {
+            sqlx::query(
+                r#"
+          UPDATE geo_monitor_runs
+          SET prompt_total = ?, updated_at = CURRENT_TIMESTAMP(3)
+          WHERE id = ?;
+        "#,
+            )
+            .bind(prompt_total)
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(|e| -> Error { Box::new(e) })?;
+        }
+
+        return Ok(GeoMonitorRunRow {
+            id,
+            tenant_id,
+            project_id,
+            run_for_dt,
+            provider,
+            model,
+            status,
+            prompt_total: prompt_total_db,
+            started_at,
+            finished_at,
+        });
+    }
+
+    let res = sqlx::query(
         r#"
-      INSERT INTO tenant_ai_provider_audit
-        (tenant_id, provider, action, actor, request_id, before_json, after_json)
+      INSERT INTO geo_monitor_runs
+        (tenant_id, project_id, run_for_dt, provider, model, status, prompt_total)
       VALUES
-        (?, ?, ?, ?, ?, ?, ?);
+        (?, ?, ?, ?, ?, 'running', ?);
     "#,
     )
     .bind(tenant_id)
+    .bind(project_id)
+    .bind(run_for_dt)
     .bind(provider)
-    .bind(action)
-    .bind(actor)
-    .bind(request_id)
-    .bind(before_json)
-    .bind(after_json)
+    .bind(model)
+    .bind(prompt_total)
     .execute(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(())
+    let id = res.last_insert_id() as i64;
+    let row: (
+    i64,
+    String,
+    i64,
+    chrono::NaiveDate,
+    String,
+    String,
+    String,
+    i32,
+    DateTime<Utc>,
+    Option<DateTime<Utc>>,
+  ) = sqlx::query_as(
+    r#"
+      SELECT id, tenant_id, project_id, run_for_dt, provider, model, status, prompt_total, started_at, finished_at
+      FROM geo_monitor_runs
+      WHERE id = ?
+      LIMIT 1;
+    "#,
+  )
+  .bind(id)
+  .fetch_one(pool)
+  .await
+  .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(GeoMonitorRunRow {
+        id: row.0,
+        tenant_id: row.1,
+        project_id: row.2,
+        run_for_dt: row.3,
+        provider: row.4,
+        model: row.5,
+        status: row.6,
+        prompt_total: row.7,
+        started_at: row.8,
+        finished_at: row.9,
+    })
 }
 
-pub async fn fetch_tenant_ai_routing_policy(
+pub const DEFAULT_JOB_MAX_ATTEMPT: i32 = 3;
+
+fn max_attempt_from_lookup(job_type: &str, lookup: impl Fn(&str) -> Option<String>) -> i32 {
+    lookup(&format!("JOB_MAX_ATTEMPT_{}", job_type.to_ascii_uppercase()))
+        .and_then(|v| v.trim().parse::<i32>().ok())
+        .filter(|v| *v >= 1)
+        .unwrap_or(DEFAULT_JOB_MAX_ATTEMPT)
+        .min(50)
+}
+
+/// `JOB_MAX_ATTEMPT_<JOB_TYPE>` (e.g. `JOB_MAX_ATTEMPT_YOUTUBE_REPORTING=6`), else 3.
+pub fn max_attempt_for_job_type(job_type: &str) -> i32 {
+    max_attempt_from_lookup(job_type, |name| std::env::var(name).ok())
+}
+
+pub const DEFAULT_JOB_TASK_LOCK_TTL_SECS: i64 = 600;
+
+fn lock_ttl_from_lookup(job_type: Option<&str>, lookup: impl Fn(&str) -> Option<String>) -> i64 {
+    let parse = |name: &str| lookup(name).and_then(|v| v.trim().parse::<i64>().ok());
+    // Per-type values may exceed the global ceiling: reporting ingestion can legitimately hold a
+    // lock for well over an hour.
+    job_type
+        .and_then(|t| parse(&format!("JOB_TASK_LOCK_TTL_SECS_{}", t.to_ascii_uppercase())))
+        .map(|v| v.clamp(60, 6 * 3600))
+        .unwrap_or_else(|| {
+            parse("JOB_TASK_LOCK_TTL_SECS")
+                .unwrap_or(DEFAULT_JOB_TASK_LOCK_TTL_SECS)
+                .clamp(60, 3600)
+        })
+}
+
+/// `JOB_TASK_LOCK_TTL_SECS_<JOB_TYPE>` (60s-6h), else `JOB_TASK_LOCK_TTL_SECS` (60s-1h, default
+/// 600). `None` reads the global value only.
+pub fn job_task_lock_ttl_secs(job_type: Option<&str>) -> i64 {
+    lock_ttl_from_lookup(job_type, |name| std::env::var(name).ok())
+}
+
+pub async fn fetch_alert_notification_settings(
     pool: &MySqlPool,
     tenant_id: &str,
-) -> Result<Option<TenantAiRoutingPolicyRow>, Error> {
-    let row = sqlx::query_as::<_, (String, String, Option<f64>, String, DateTime<Utc>)>(
+) -> Result<crate::youtube_alerts::AlertNotificationSettings, Error> {
+    let row = sqlx::query_as::<_, (String, i64)>(
         r#"
-      SELECT
-        tenant_id,
-        default_provider,
-        CAST(monthly_budget_usd AS DOUBLE) AS monthly_budget_usd,
-        updated_by,
-        updated_at
-      FROM tenant_ai_routing_policy
+      SELECT notification_mode, CAST(digest_window_minutes AS SIGNED)
+      FROM tenant_alert_settings
       WHERE tenant_id = ?
       LIMIT 1;
     "#,
@@ -2263,43 +4711,32 @@ pub async fn fetch_tenant_ai_routing_policy(
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(row.map(
-        |(tenant_id, default_provider, monthly_budget_usd, updated_by, updated_at)| {
-            TenantAiRoutingPolicyRow {
-            tenant_id,
-            default_provider,
-            monthly_budget_usd,
-            updated_by,
-            updated_at,
+    Ok(match row {
+        Some((mode, window)) => {
+            crate::youtube_alerts::AlertNotificationSettings::from_stored(Some(&mode), Some(window))
         }
-        },
-    ))
+        None => crate::youtube_alerts::AlertNotificationSettings::default(),
+    })
 }
 
-pub async fn upsert_tenant_ai_routing_policy(
+pub async fn upsert_alert_notification_settings(
     pool: &MySqlPool,
     tenant_id: &str,
-    default_provider: &str,
-    monthly_budget_usd: Option<f64>,
-    updated_by: &str,
+    settings: &crate::youtube_alerts::AlertNotificationSettings,
 ) -> Result<(), Error> {
     sqlx::query(
         r#"
-      INSERT INTO tenant_ai_routing_policy
-        (tenant_id, default_provider, monthly_budget_usd, updated_by)
-      VALUES
-        (?, ?, ?, ?)
+      INSERT INTO tenant_alert_settings (tenant_id, notification_mode, digest_window_minutes)
+      VALUES (?, ?, ?)
       ON DUPLICATE KEY UPDATE
-        default_provider = VALUES(default_provider),
-        monthly_budget_usd = VALUES(monthly_budget_usd),
-        updated_by = VALUES(updated_by),
+        notification_mode = VALUES(notification_mode),
+        digest_window_minutes = VALUES(digest_window_minutes),
         updated_at = CURRENT_TIMESTAMP(3);
     "#,
     )
     .bind(tenant_id)
-    .bind(default_provider)
-    .bind(monthly_budget_usd)
-    .bind(updated_by)
+    .bind(settings.mode.as_str())
+    .bind(settings.digest_window_minutes)
     .execute(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
@@ -2307,203 +4744,105 @@ pub async fn upsert_tenant_ai_routing_policy(
     Ok(())
 }
 
-#[derive(Debug, Clone)]
-pub struct SubscriptionRow {
-    pub status: String,
-    pub current_period_end: Option<DateTime<Utc>>,
-}
-
-pub async fn fetch_subscription(
+/// The channel's manual RPM baseline and its note, if the creator set one.
+pub async fn fetch_channel_rpm_baseline(
     pool: &MySqlPool,
     tenant_id: &str,
-) -> Result<Option<SubscriptionRow>, Error> {
-    let row = sqlx::query_as::<_, (String, Option<DateTime<Utc>>)>(
+    channel_id: &str,
+) -> Result<Option<(f64, Option<String>)>, Error> {
+    sqlx::query_as::<_, (f64, Option<String>)>(
         r#"
-      SELECT status, current_period_end
-      FROM subscriptions
+      SELECT rpm_usd, note
+      FROM yt_channel_rpm_baselines
       WHERE tenant_id = ?
+        AND channel_id = ?
       LIMIT 1;
     "#,
     )
     .bind(tenant_id)
+    .bind(channel_id)
     .fetch_optional(pool)
     .await
-    .map_err(|e| -> Error { Box::new(e) })?;
-
-    Ok(row.map(|(status, current_period_end)| SubscriptionRow {
-        status,
-        current_period_end,
-    }))
+    .map_err(|e| -> Error { Box::new(e) })
 }
 
-pub async fn upsert_subscription(
-    pool: &MySqlPool,
-    tenant_id: &str,
-    status: &str,
-    provider_customer_id: Option<&str>,
-    provider_subscription_id: Option<&str>,
-    current_period_end: Option<DateTime<Utc>>,
-) -> Result<(), Error> {
-    sqlx::query(
-    r#"
-      INSERT INTO subscriptions
-        (tenant_id, status, provider, provider_customer_id, provider_subscription_id, current_period_end)
-      VALUES
-        (?, ?, 'shopify', ?, ?, ?)
-      ON DUPLICATE KEY UPDATE
-        status = VALUES(status),
-        provider_customer_id = COALESCE(VALUES(provider_customer_id), provider_customer_id),
-        provider_subscription_id = COALESCE(VALUES(provider_subscription_id), provider_subscription_id),
-        current_period_end = COALESCE(VALUES(current_period_end), current_period_end),
-        updated_at = CURRENT_TIMESTAMP(3);
-    "#,
-  )
-  .bind(tenant_id)
-  .bind(status)
-  .bind(provider_customer_id)
-  .bind(provider_subscription_id)
-  .bind(current_period_end)
-  .execute(pool)
-  .await
-  .map_err(|e| -> Error { Box::new(e) })?;
-
-    Ok(())
-}
-
-pub async fn upsert_youtube_connection(
+pub async fn upsert_channel_rpm_baseline(
     pool: &MySqlPool,
     tenant_id: &str,
     channel_id: &str,
-    tokens: &crate::providers::youtube::YoutubeOAuthTokens,
-) -> Result<(), sqlx::Error> {
-    let expires_at = tokens
-        .expires_in_seconds
-        .map(|secs| chrono::Utc::now() + chrono::Duration::seconds(secs as i64));
-
+    rpm_usd: f64,
+    note: Option<&str>,
+) -> Result<(), Error> {
     sqlx::query(
-    r#"
-      INSERT INTO channel_connections
-        (tenant_id, oauth_provider, channel_id, access_token, refresh_token, token_type, scope, expires_at)
-      VALUES
-        (?, 'youtube', ?, ?, ?, ?, ?, ?)
+        r#"
+      INSERT INTO yt_channel_rpm_baselines (tenant_id, channel_id, rpm_usd, note)
+      VALUES (?, ?, ?, ?)
       ON DUPLICATE KEY UPDATE
-        channel_id = VALUES(channel_id),
-        access_token = VALUES(access_token),
-        refresh_token = COALESCE(VALUES(refresh_token), refresh_token),
-        token_type = VALUES(token_type),
-        scope = VALUES(scope),
-        expires_at = VALUES(expires_at),
+        rpm_usd = VALUES(rpm_usd),
+        note = VALUES(note),
         updated_at = CURRENT_TIMESTAMP(3);
     "#,
-  )
-  .bind(tenant_id)
-  .bind(channel_id)
-  .bind(&tokens.access_token)
-  .bind(tokens.refresh_token.as_deref())
-  .bind(&tokens.token_type)
-  .bind(tokens.scope.as_deref())
-  .bind(expires_at)
-  .execute(pool)
-  .await?;
-
-    Ok(())
-}
-
-#[derive(Debug, Clone)]
-pub struct GeoMonitorProjectRow {
-    pub id: i64,
-    pub tenant_id: String,
-    pub name: String,
-    pub website: Option<String>,
-    pub brand_aliases_json: Option<String>,
-    pub competitor_names_json: Option<String>,
-    pub schedule: String,
-    pub enabled: bool,
-}
-
-#[derive(Debug, Clone)]
-pub struct GeoMonitorPromptRow {
-    pub id: i64,
-    pub project_id: i64,
-    pub theme: Option<String>,
-    pub prompt_text: String,
-    pub enabled: bool,
-    pub sort_order: i32,
-}
-
-#[derive(Debug, Clone)]
-pub struct GeoMonitorRunRow {
-    pub id: i64,
-    pub tenant_id: String,
-    pub project_id: i64,
-    pub run_for_dt: chrono::NaiveDate,
-    pub provider: String,
-    pub model: String,
-    pub status: String,
-    pub prompt_total: i32,
-    pub started_at: DateTime<Utc>,
-    pub finished_at: Option<DateTime<Utc>>,
-}
-
-#[derive(Debug, Clone)]
-pub struct GeoMonitorRunSummary {
-    pub results_total: i64,
-    pub presence_count: i64,
-    pub top3_count: i64,
-    pub top5_count: i64,
-    pub error_count: i64,
-    pub cost_usd: f64,
-}
-
-pub async fn create_geo_monitor_project(
-    pool: &MySqlPool,
-    tenant_id: &str,
-    name: &str,
-    website: Option<&str>,
-    brand_aliases_json: Option<&str>,
-    competitor_names_json: Option<&str>,
-    schedule: &str,
-) -> Result<i64, Error> {
-    let schedule = match schedule.trim() {
-        "daily" | "Daily" | "DAILY" => "daily",
-        _ => "weekly",
-    };
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(rpm_usd)
+    .bind(note)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
 
-    let res = sqlx::query(
+    Ok(())
+}
+
+pub async fn delete_channel_rpm_baseline(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+) -> Result<(), Error> {
+    sqlx::query(
         r#"
-      INSERT INTO geo_monitor_projects
-        (tenant_id, name, website, brand_aliases_json, competitor_names_json, schedule, enabled)
-      VALUES
-        (?, ?, ?, ?, ?, ?, 1);
+      DELETE FROM yt_channel_rpm_baselines
+      WHERE tenant_id = ?
+        AND channel_id = ?;
     "#,
     )
     .bind(tenant_id)
-    .bind(name)
-    .bind(website)
-    .bind(brand_aliases_json)
-    .bind(competitor_names_json)
-    .bind(schedule)
+    .bind(channel_id)
     .execute(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(res.last_insert_id() as i64)
+    Ok(())
 }
 
-pub async fn list_geo_monitor_projects(
+/// Open alerts across the tenant's channels detected at or after `since`.
+pub async fn fetch_open_alerts_detected_since(
     pool: &MySqlPool,
     tenant_id: &str,
-) -> Result<Vec<GeoMonitorProjectRow>, Error> {
-    let rows: Vec<(i64, String, String, Option<String>, Option<String>, Option<String>, String, i8)> =
-    sqlx::query_as(
-      r#"
-        SELECT id, tenant_id, name, website, brand_aliases_json, competitor_names_json, schedule, enabled
-        FROM geo_monitor_projects
-        WHERE tenant_id = ?
-        ORDER BY updated_at DESC, id DESC;
-      "#,
+    since: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<crate::youtube_alerts::NotifiableAlert>, Error> {
+    type AlertRow = (
+        String,
+        String,
+        String,
+        String,
+        String,
+        chrono::DateTime<chrono::Utc>,
+    );
+    let rows = sqlx::query_as::<_, AlertRow>(
+        r#"
+      SELECT channel_id, alert_key, kind, severity, message,
+             CAST(detected_at AS DATETIME) AS detected_at
+      FROM yt_alerts
+      WHERE tenant_id = ?
+        AND resolved_at IS NULL
+        AND detected_at >= ?
+      ORDER BY channel_id ASC, detected_at ASC
+      LIMIT 1000;
+    "#,
     )
     .bind(tenant_id)
+    .bind(since)
     .fetch_all(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
@@ -2511,327 +4850,475 @@ pub async fn list_geo_monitor_projects(
     Ok(rows
         .into_iter()
         .map(
-            |(
-                id,
-                tenant_id,
-                name,
-                website,
-                brand_aliases_json,
-                competitor_names_json,
-                schedule,
-                enabled,
-            )| {
-                GeoMonitorProjectRow {
-                    id,
-                    tenant_id,
-                    name,
-                    website,
-                    brand_aliases_json,
-                    competitor_names_json,
-                    schedule,
-                    enabled: enabled != 0,
+            |(channel_id, alert_key, kind, severity, message, detected_at)| {
+                crate::youtube_alerts::NotifiableAlert {
+                    channel_id,
+                    alert_key,
+                    kind,
+                    severity,
+                    message,
+                    detected_at,
                 }
             },
         )
         .collect())
 }
 
-pub async fn fetch_geo_monitor_project(
+pub async fn fetch_tenant_feature_flags(
     pool: &MySqlPool,
     tenant_id: &str,
-    project_id: i64,
-) -> Result<Option<GeoMonitorProjectRow>, Error> {
-    let row: Option<(
-    i64,
-    String,
-    String,
-    Option<String>,
-    Option<String>,
-    Option<String>,
-    String,
-    i8,
-  )> = sqlx::query_as(
-    r#"
-      SELECT id, tenant_id, name, website, brand_aliases_json, competitor_names_json, schedule, enabled
-      FROM geo_monitor_projects
-      WHERE tenant_id = ? AND id = ?
+) -> Result<Vec<(String, bool)>, Error> {
+    sqlx::query_as::<_, (String, bool)>(
+        r#"
+      SELECT feature, enabled
+      FROM tenant_feature_flags
+      WHERE tenant_id = ?;
+    "#,
+    )
+    .bind(tenant_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })
+}
+
+/// The pre-change snapshot an experiment captured in full-snapshot mode; `None` for experiments
+/// that only roll back the changed field.
+pub async fn fetch_experiment_full_snapshot(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    experiment_id: i64,
+) -> Result<Option<VideoSnapshot>, Error> {
+    let raw = sqlx::query_scalar::<_, Option<String>>(
+        r#"
+      SELECT full_snapshot_json
+      FROM yt_experiments
+      WHERE id = ? AND tenant_id = ?
       LIMIT 1;
     "#,
-  )
-  .bind(tenant_id)
-  .bind(project_id)
-  .fetch_optional(pool)
-  .await
-  .map_err(|e| -> Error { Box::new(e) })?;
+    )
+    .bind(experiment_id)
+    .bind(tenant_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?
+    .flatten();
 
-    Ok(row.map(
-        |(
-            id,
-            tenant_id,
-            name,
-            website,
-            brand_aliases_json,
-            competitor_names_json,
-            schedule,
-            enabled,
-        )| {
-            GeoMonitorProjectRow {
-                id,
-                tenant_id,
-                name,
-                website,
-                brand_aliases_json,
-                competitor_names_json,
-                schedule,
-                enabled: enabled != 0,
-            }
-        },
-    ))
+    Ok(raw
+        .as_deref()
+        .and_then(|raw| serde_json::from_str::<VideoSnapshot>(raw).ok()))
 }
 
-pub async fn replace_geo_monitor_prompts(
+pub async fn insert_experiment_baseline_thumbnail(
+    conn: &mut sqlx::MySqlConnection,
+    experiment_id: i64,
+    thumbnail: &DecodedThumbnail,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      INSERT INTO yt_experiment_baseline_thumbnails (experiment_id, content_type, sha256, image_bytes)
+      VALUES (?, ?, ?, ?)
+      ON DUPLICATE KEY UPDATE
+        content_type = VALUES(content_type),
+        sha256 = VALUES(sha256),
+        image_bytes = VALUES(image_bytes);
+    "#,
+    )
+    .bind(experiment_id)
+    .bind(thumbnail.content_type)
+    .bind(&thumbnail.sha256)
+    .bind(thumbnail.bytes.as_ref())
+    .execute(conn)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+    Ok(())
+}
+
+/// The original thumbnail a thumbnail experiment replaced; `None` for other experiment types and
+/// for experiments created before the image was stored.
+pub async fn fetch_experiment_baseline_thumbnail(
+    pool: &MySqlPool,
+    experiment_id: i64,
+) -> Result<Option<DecodedThumbnail>, Error> {
+    let bytes = sqlx::query_scalar::<_, Vec<u8>>(
+        r#"
+      SELECT image_bytes
+      FROM yt_experiment_baseline_thumbnails
+      WHERE experiment_id = ?
+      LIMIT 1;
+    "#,
+    )
+    .bind(experiment_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    bytes
+        .map(|bytes| decoded_thumbnail(bytes.into()).map_err(|e| -> Error { Box::new(e) }))
+        .transpose()
+}
+
+pub async fn mark_experiment_rollback_failed(
     pool: &MySqlPool,
     tenant_id: &str,
-    project_id: i64,
-    prompts: &[(Option<String>, String)],
+    experiment_id: i64,
 ) -> Result<(), Error> {
-    let mut tx = pool.begin().await.map_err(|e| -> Error { Box::new(e) })?;
-
     sqlx::query(
         r#"
-      DELETE FROM geo_monitor_prompts
-      WHERE tenant_id = ? AND project_id = ?;
+      UPDATE yt_experiments
+      SET rollback_failed_at = CURRENT_TIMESTAMP(3),
+          updated_at = CURRENT_TIMESTAMP(3)
+      WHERE id = ? AND tenant_id = ?;
     "#,
     )
+    .bind(experiment_id)
     .bind(tenant_id)
-    .bind(project_id)
-    .execute(&mut *tx)
+    .execute(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
+    Ok(())
+}
 
-    for (idx, (theme, prompt_text)) in prompts.iter().enumerate() {
-        sqlx::query(
-            r#"
-        INSERT INTO geo_monitor_prompts
-          (tenant_id, project_id, theme, prompt_text, enabled, sort_order)
-        VALUES
-          (?, ?, ?, ?, 1, ?);
-      "#,
-        )
-        .bind(tenant_id)
-        .bind(project_id)
-        .bind(theme.as_deref())
-        .bind(prompt_text)
-        .bind(idx as i32)
-        .execute(&mut *tx)
-        .await
-        .map_err(|e| -> Error { Box::new(e) })?;
+/// `(total, apply_failed, rollback_failed)` over experiments created since `since`.
+pub async fn fetch_experiment_failure_counts(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    since: DateTime<Utc>,
+) -> Result<(i64, i64, i64), Error> {
+    sqlx::query_as::<_, (i64, i64, i64)>(
+        r#"
+      SELECT CAST(COUNT(*) AS SIGNED),
+             CAST(COALESCE(SUM(CASE WHEN state = 'failed' THEN 1 ELSE 0 END), 0) AS SIGNED),
+             CAST(COALESCE(SUM(CASE WHEN state <> 'failed' AND rollback_failed_at IS NOT NULL THEN 1 ELSE 0 END), 0) AS SIGNED)
+      FROM yt_experiments
+      WHERE tenant_id = ? AND channel_id = ? AND created_at >= ? AND state <> 'draft';
+    "#,
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(since)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })
+}
+
+pub const DEFAULT_EVIDENCE_RETENTION_DAYS: i64 = 365;
+/// Outcomes are evaluated up to ~30 days after a decision and replay looks back a quarter, so
+/// retention never drops below this.
+pub const MIN_EVIDENCE_RETENTION_DAYS: i64 = 90;
+pub const MAX_EVIDENCE_RETENTION_DAYS: i64 = 3650;
+
+/// `evidence_retention_days` from the channel's active policy_params, else
+/// `EVIDENCE_RETENTION_DAYS` (passed as `env_value`), else one year.
+pub fn evidence_retention_days(policy_params_json: Option<&str>, env_value: Option<&str>) -> i64 {
+    policy_params_json
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .and_then(|v| v.get("evidence_retention_days").and_then(|v| v.as_i64()))
+        .or_else(|| env_value.and_then(|v| v.trim().parse::<i64>().ok()))
+        .unwrap_or(DEFAULT_EVIDENCE_RETENTION_DAYS)
+        .clamp(MIN_EVIDENCE_RETENTION_DAYS, MAX_EVIDENCE_RETENTION_DAYS)
+}
+
+/// Rows dated strictly before the cutoff are rolled up and deleted.
+pub fn evidence_retention_cutoff(run_for_dt: chrono::NaiveDate, retention_days: i64) -> chrono::NaiveDate {
+    run_for_dt - chrono::Duration::days(retention_days)
+}
+
+const SUMMARIZE_PRUNED_DECISIONS_SQL: &str = r#"
+      INSERT INTO decision_evidence_monthly
+        (tenant_id, channel_id, month_start, decisions, exploit_decisions, explore_decisions,
+         protect_decisions, confidence_sum)
+      SELECT tenant_id, channel_id, DATE_SUB(as_of_dt, INTERVAL DAYOFMONTH(as_of_dt) - 1 DAY) AS month_start,
+             COUNT(*),
+             SUM(CASE WHEN direction = 'EXPLOIT' THEN 1 ELSE 0 END),
+             SUM(CASE WHEN direction = 'EXPLORE' THEN 1 ELSE 0 END),
+             SUM(CASE WHEN direction = 'PROTECT' THEN 1 ELSE 0 END),
+             COALESCE(SUM(confidence), 0)
+      FROM decision_daily
+      WHERE tenant_id = ? AND channel_id = ? AND as_of_dt < ?
+      GROUP BY tenant_id, channel_id, month_start
+      ON DUPLICATE KEY UPDATE
+        decisions = decisions + VALUES(decisions),
+        exploit_decisions = exploit_decisions + VALUES(exploit_decisions),
+        explore_decisions = explore_decisions + VALUES(explore_decisions),
+        protect_decisions = protect_decisions + VALUES(protect_decisions),
+        confidence_sum = confidence_sum + VALUES(confidence_sum);
+    "#;
+
+const SUMMARIZE_PRUNED_OUTCOMES_SQL: &str = r#"
+      INSERT INTO decision_evidence_monthly
+        (tenant_id, channel_id, month_start, outcomes, revenue_change_pct_7d_sum,
+         revenue_change_pct_7d_n, catastrophic_outcomes)
+      SELECT tenant_id, channel_id, DATE_SUB(outcome_dt, INTERVAL DAYOFMONTH(outcome_dt) - 1 DAY) AS month_start,
+             COUNT(*),
+             COALESCE(SUM(revenue_change_pct_7d), 0),
+             COUNT(revenue_change_pct_7d),
+             SUM(CASE WHEN catastrophic_flag <> 0 THEN 1 ELSE 0 END)
+      FROM decision_outcome
+      WHERE tenant_id = ? AND channel_id = ? AND outcome_dt < ?
+      GROUP BY tenant_id, channel_id, month_start
+      ON DUPLICATE KEY UPDATE
+        outcomes = outcomes + VALUES(outcomes),
+        revenue_change_pct_7d_sum = revenue_change_pct_7d_sum + VALUES(revenue_change_pct_7d_sum),
+        revenue_change_pct_7d_n = revenue_change_pct_7d_n + VALUES(revenue_change_pct_7d_n),
+        catastrophic_outcomes = catastrophic_outcomes + VALUES(catastrophic_outcomes);
+    "#;
+
+const PRUNE_DECISIONS_SQL: &str = r#"
+      DELETE FROM decision_daily
+      WHERE tenant_id = ? AND channel_id = ? AND as_of_dt < ?;
+    "#;
+
+const PRUNE_OUTCOMES_SQL: &str = r#"
+      DELETE FROM decision_outcome
+      WHERE tenant_id = ? AND channel_id = ? AND outcome_dt < ?;
+    "#;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct EvidencePruneCounts {
+    pub decisions_pruned: u64,
+    pub outcomes_pruned: u64,
+}
+
+/// Rolls rows older than `cutoff` into `decision_evidence_monthly` and deletes them, in one
+/// transaction so a failed prune never loses rows without their summary.
+pub async fn prune_decision_evidence(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    cutoff: chrono::NaiveDate,
+) -> Result<EvidencePruneCounts, Error> {
+    let mut tx = pool.begin().await.map_err(|e| -> Error { Box::new(e) })?;
+    let mut counts = EvidencePruneCounts::default();
+
+    for (summarize_sql, prune_sql, is_outcomes) in [
+        (SUMMARIZE_PRUNED_DECISIONS_SQL, PRUNE_DECISIONS_SQL, false),
+        (SUMMARIZE_PRUNED_OUTCOMES_SQL, PRUNE_OUTCOMES_SQL, true),
+    ] {
+        sqlx::query(summarize_sql)
+            .bind(tenant_id)
+            .bind(channel_id)
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| -> Error { Box::new(e) })?;
+        let pruned = sqlx::query(prune_sql)
+            .bind(tenant_id)
+            .bind(channel_id)
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| -> Error { Box::new(e) })?
+            .rows_affected();
+        if is_outcomes {
+            counts.outcomes_pruned = pruned;
+        } else {
+            counts.decisions_pruned = pruned;
+        }
     }
 
     tx.commit().await.map_err(|e| -> Error { Box::new(e) })?;
-    Ok(())
+    Ok(counts)
 }
 
-pub async fn list_geo_monitor_prompts(
+/// Gap dates a refill task already fetched successfully.
+pub async fn fetch_refilled_gap_dts(
     pool: &MySqlPool,
     tenant_id: &str,
-    project_id: i64,
-) -> Result<Vec<GeoMonitorPromptRow>, Error> {
-    let rows: Vec<(i64, i64, Option<String>, String, i8, i32)> = sqlx::query_as(
+    channel_id: &str,
+    start_dt: chrono::NaiveDate,
+    end_dt: chrono::NaiveDate,
+) -> Result<Vec<chrono::NaiveDate>, Error> {
+    sqlx::query_scalar::<_, chrono::NaiveDate>(
         r#"
-      SELECT id, project_id, theme, prompt_text, enabled, sort_order
-      FROM geo_monitor_prompts
-      WHERE tenant_id = ? AND project_id = ?
-      ORDER BY sort_order ASC, id ASC;
+      SELECT dt
+      FROM yt_metric_gap_fills
+      WHERE tenant_id = ? AND channel_id = ? AND dt BETWEEN ? AND ?
+        AND refilled_at IS NOT NULL;
     "#,
     )
     .bind(tenant_id)
-    .bind(project_id)
+    .bind(channel_id)
+    .bind(start_dt)
+    .bind(end_dt)
     .fetch_all(pool)
     .await
-    .map_err(|e| -> Error { Box::new(e) })?;
+    .map_err(|e| -> Error { Box::new(e) })
+}
 
-    Ok(rows
-        .into_iter()
-        .map(
-            |(id, project_id, theme, prompt_text, enabled, sort_order)| GeoMonitorPromptRow {
-                id,
-                project_id,
-                theme,
-                prompt_text,
-                enabled: enabled != 0,
-                sort_order,
-            },
-        )
-        .collect())
+/// Records that a refill was enqueued for these gap dates; they count as refilled only once
+/// `mark_gap_fills_refilled` covers them. Dates already recorded keep their first attempt.
+pub async fn record_gap_fill_attempts(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    dts: &[chrono::NaiveDate],
+) -> Result<(), Error> {
+    if dts.is_empty() {
+        return Ok(());
+    }
+
+    let mut qb = sqlx::QueryBuilder::<sqlx::MySql>::new(
+        "INSERT IGNORE INTO yt_metric_gap_fills (tenant_id, channel_id, dt) ",
+    );
+    qb.push_values(dts.iter(), |mut b, dt| {
+        b.push_bind(tenant_id);
+        b.push_bind(channel_id);
+        b.push_bind(*dt);
+    });
+
+    qb.build()
+        .execute(pool)
+        .await
+        .map_err(|e| -> Error { Box::new(e) })?;
+
+    Ok(())
 }
 
-pub async fn fetch_geo_monitor_prompt(
+/// Marks the pending gap dates in `start_dt..=end_dt` as refilled by a successful daily_channel
+/// fetch of that window.
+pub async fn mark_gap_fills_refilled(
     pool: &MySqlPool,
     tenant_id: &str,
-    project_id: i64,
-    prompt_id: i64,
-) -> Result<Option<GeoMonitorPromptRow>, Error> {
-    let row: Option<(i64, i64, Option<String>, String, i8, i32)> = sqlx::query_as(
+    channel_id: &str,
+    start_dt: chrono::NaiveDate,
+    end_dt: chrono::NaiveDate,
+) -> Result<(), Error> {
+    sqlx::query(
         r#"
-      SELECT id, project_id, theme, prompt_text, enabled, sort_order
-      FROM geo_monitor_prompts
-      WHERE tenant_id = ? AND project_id = ? AND id = ?
-      LIMIT 1;
+      UPDATE yt_metric_gap_fills
+      SET refilled_at = CURRENT_TIMESTAMP(3)
+      WHERE tenant_id = ? AND channel_id = ? AND dt BETWEEN ? AND ?
+        AND refilled_at IS NULL;
     "#,
     )
     .bind(tenant_id)
-    .bind(project_id)
-    .bind(prompt_id)
-    .fetch_optional(pool)
+    .bind(channel_id)
+    .bind(start_dt)
+    .bind(end_dt)
+    .execute(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    Ok(row.map(
-        |(id, project_id, theme, prompt_text, enabled, sort_order)| GeoMonitorPromptRow {
-            id,
-            project_id,
-            theme,
-            prompt_text,
-            enabled: enabled != 0,
-            sort_order,
-        },
-    ))
+    Ok(())
 }
 
-pub async fn ensure_geo_monitor_run(
+pub const DEFAULT_DISPATCH_LOCK_TTL_SECS: i64 = 60;
+
+/// `DISPATCH_LOCK_TTL_SECS` (default 60, max 900): how long a dispatch suppresses repeats.
+pub fn dispatch_lock_ttl_secs() -> i64 {
+    std::env::var("DISPATCH_LOCK_TTL_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|v| *v >= 0)
+        .unwrap_or(DEFAULT_DISPATCH_LOCK_TTL_SECS)
+        .min(900)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DispatchLockOutcome {
+    Acquired,
+    /// Another dispatch holds the lock; `result_json` is its response once it finished.
+    Held { result_json: Option<String> },
+}
+
+/// A lock row that has not expired yet blocks a new dispatch; `TRY_ACQUIRE_DISPATCH_LOCK_SQL`
+/// applies the same rule in its upsert.
+pub fn dispatch_lock_is_active(expires_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    expires_at.is_some_and(|expires_at| expires_at > now)
+}
+
+/// Takes over the row only when its lock has expired (`expires_at <= now`); a live lock is left
+/// untouched. `expires_at` is assigned last because MySQL evaluates the assignments in order and
+/// the earlier ones must still see the old expiry.
+const TRY_ACQUIRE_DISPATCH_LOCK_SQL: &str = r#"
+  INSERT INTO dispatch_locks (lock_key, holder, expires_at, result_json)
+  VALUES (?, ?, ?, NULL)
+  ON DUPLICATE KEY UPDATE
+    result_json = IF(expires_at <= ?, NULL, result_json),
+    holder = IF(expires_at <= ?, VALUES(holder), holder),
+    expires_at = IF(expires_at <= ?, VALUES(expires_at), expires_at);
+"#;
+
+pub async fn try_acquire_dispatch_lock(
     pool: &MySqlPool,
-    tenant_id: &str,
-    project_id: i64,
-    run_for_dt: chrono::NaiveDate,
-    provider: &str,
-    model: &str,
-    prompt_total: i32,
-) -> Result<GeoMonitorRunRow, Error> {
-    let existing: Option<(
-    i64,
-    String,
-    i64,
-    chrono::NaiveDate,
-    String,
-    String,
-    String,
-    i32,
-    DateTime<Utc>,
-    Option<DateTime<Utc>>,
-  )> = sqlx::query_as(
-    r#"
-      SELECT id, tenant_id, project_id, run_for_dt, provider, model, status, prompt_total, started_at, finished_at
-      FROM geo_monitor_runs
-      WHERE tenant_id = ? AND project_id = ? AND run_for_dt = ?
-      LIMIT 1;
-    "#,
-  )
-  .bind(tenant_id)
-  .bind(project_id)
-  .bind(run_for_dt)
-  .fetch_optional(pool)
-  .await
-  .map_err(|e| -> Error { Box::new(e) })?;
+    lock_key: &str,
+    holder: &str,
+    now: DateTime<Utc>,
+    ttl_secs: i64,
+) -> Result<DispatchLockOutcome, Error> {
+    sqlx::query(TRY_ACQUIRE_DISPATCH_LOCK_SQL)
+        .bind(lock_key)
+        .bind(holder)
+        .bind(now + chrono::Duration::seconds(ttl_secs))
+        .bind(now)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await
+        .map_err(|e| -> Error { Box::new(e) })?;
 
-    if let Some((
-        id,
-        tenant_id,
-        project_id,
-        run_for_dt,
-        provider,
-        model,
-        status,
-        prompt_total_db,
-        started_at,
-        finished_at,
-    )) = existing
-    {
-        // Best-effort: keep prompt_total up to date for current prompt set, but do not reset existing runs.
-        if prompt_total_db != prompt_total && prompt_total > 0 {
-            sqlx::query(
-                r#"
-          UPDATE geo_monitor_runs
-          SET prompt_total = ?, updated_at = CURRENT_TIMESTAMP(3)
-          WHERE id = ?;
-        "#,
-            )
-            .bind(prompt_total)
-            .bind(id)
-            .execute(pool)
-            .await
-            .map_err(|e| -> Error { Box::new(e) })?;
-        }
+    // Holders are unique per attempt, so whoever's holder is on the row won the race.
+    let (current_holder, result_json): (String, Option<String>) = sqlx::query_as(
+        r#"
+      SELECT holder, result_json
+      FROM dispatch_locks
+      WHERE lock_key = ?;
+    "#,
+    )
+    .bind(lock_key)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
 
-        return Ok(GeoMonitorRunRow {
-            id,
-            tenant_id,
-            project_id,
-            run_for_dt,
-            provider,
-            model,
-            status,
-            prompt_total: prompt_total_db,
-            started_at,
-            finished_at,
-        });
+    if current_holder == holder {
+        Ok(DispatchLockOutcome::Acquired)
+    } else {
+        Ok(DispatchLockOutcome::Held { result_json })
     }
+}
 
-    let res = sqlx::query(
+/// Stores the finished dispatch's response so repeats inside the window can return it.
+pub async fn complete_dispatch_lock(
+    pool: &MySqlPool,
+    lock_key: &str,
+    holder: &str,
+    result_json: &str,
+) -> Result<(), Error> {
+    sqlx::query(
         r#"
-      INSERT INTO geo_monitor_runs
-        (tenant_id, project_id, run_for_dt, provider, model, status, prompt_total)
-      VALUES
-        (?, ?, ?, ?, ?, 'running', ?);
+      UPDATE dispatch_locks
+      SET result_json = ?
+      WHERE lock_key = ? AND holder = ?;
     "#,
     )
-    .bind(tenant_id)
-    .bind(project_id)
-    .bind(run_for_dt)
-    .bind(provider)
-    .bind(model)
-    .bind(prompt_total)
+    .bind(result_json)
+    .bind(lock_key)
+    .bind(holder)
     .execute(pool)
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
+    Ok(())
+}
 
-    let id = res.last_insert_id() as i64;
-    let row: (
-    i64,
-    String,
-    i64,
-    chrono::NaiveDate,
-    String,
-    String,
-    String,
-    i32,
-    DateTime<Utc>,
-    Option<DateTime<Utc>>,
-  ) = sqlx::query_as(
-    r#"
-      SELECT id, tenant_id, project_id, run_for_dt, provider, model, status, prompt_total, started_at, finished_at
-      FROM geo_monitor_runs
-      WHERE id = ?
-      LIMIT 1;
+/// Drops the lock after a failed dispatch so a retry is not suppressed.
+pub async fn release_dispatch_lock(
+    pool: &MySqlPool,
+    lock_key: &str,
+    holder: &str,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+      DELETE FROM dispatch_locks
+      WHERE lock_key = ? AND holder = ?;
     "#,
-  )
-  .bind(id)
-  .fetch_one(pool)
-  .await
-  .map_err(|e| -> Error { Box::new(e) })?;
-
-    Ok(GeoMonitorRunRow {
-        id: row.0,
-        tenant_id: row.1,
-        project_id: row.2,
-        run_for_dt: row.3,
-        provider: row.4,
-        model: row.5,
-        status: row.6,
-        prompt_total: row.7,
-        started_at: row.8,
-        finished_at: row.9,
-    })
+    )
+    .bind(lock_key)
+    .bind(holder)
+    .execute(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+    Ok(())
 }
 
 pub async fn enqueue_geo_monitor_prompt_tasks(
@@ -2841,6 +5328,7 @@ pub async fn enqueue_geo_monitor_prompt_tasks(
     run_for_dt: chrono::NaiveDate,
     prompt_ids: &[i64],
 ) -> Result<u64, Error> {
+    let max_attempt = max_attempt_for_job_type("geo_monitor_prompt");
     let mut inserted: u64 = 0;
     for prompt_id in prompt_ids.iter().copied() {
         let dedupe_key =
@@ -2849,15 +5337,57 @@ pub async fn enqueue_geo_monitor_prompt_tasks(
 
         let res = sqlx::query(
             r#"
-        INSERT INTO job_tasks (tenant_id, job_type, channel_id, run_for_dt, dedupe_key, status)
-        VALUES (?, 'geo_monitor_prompt', ?, ?, ?, 'pending')
+        INSERT INTO job_tasks (tenant_id, job_type, channel_id, run_for_dt, dedupe_key, status, max_attempt)
+        VALUES (?, 'geo_monitor_prompt', ?, ?, ?, 'pending', ?)
+        ON DUPLICATE KEY UPDATE updated_at = CURRENT_TIMESTAMP(3);
+      "#,
+        )
+        .bind(tenant_id)
+        .bind(channel_id)
+        .bind(run_for_dt)
+        .bind(dedupe_key)
+        .bind(max_attempt)
+        .execute(pool)
+        .await
+        .map_err(|e| -> Error { Box::new(e) })?;
+
+        inserted = inserted.saturating_add(res.rows_affected());
+    }
+
+    Ok(inserted)
+}
+
+/// One task per `batch_size` prompts (see `geo_monitor::geo_monitor_batch_channel_id`).
+pub async fn enqueue_geo_monitor_prompt_batch_tasks(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    project_id: i64,
+    run_for_dt: chrono::NaiveDate,
+    prompt_count: usize,
+    batch_size: usize,
+) -> Result<u64, Error> {
+    let job_type = crate::geo_monitor::GEO_MONITOR_PROMPT_BATCH_JOB_TYPE;
+    let batch_size = batch_size.max(1);
+    let max_attempt = max_attempt_for_job_type(job_type);
+    let mut inserted: u64 = 0;
+    for batch_index in 0..prompt_count.div_ceil(batch_size) {
+        let channel_id =
+            crate::geo_monitor::geo_monitor_batch_channel_id(project_id, batch_size, batch_index);
+        let dedupe_key = format!("{tenant_id}:{job_type}:{run_for_dt}:{channel_id}");
+
+        let res = sqlx::query(
+            r#"
+        INSERT INTO job_tasks (tenant_id, job_type, channel_id, run_for_dt, dedupe_key, status, max_attempt)
+        VALUES (?, ?, ?, ?, ?, 'pending', ?)
         ON DUPLICATE KEY UPDATE updated_at = CURRENT_TIMESTAMP(3);
       "#,
         )
         .bind(tenant_id)
+        .bind(job_type)
         .bind(channel_id)
         .bind(run_for_dt)
         .bind(dedupe_key)
+        .bind(max_attempt)
         .execute(pool)
         .await
         .map_err(|e| -> Error { Box::new(e) })?;
@@ -2868,6 +5398,21 @@ pub async fn enqueue_geo_monitor_prompt_tasks(
     Ok(inserted)
 }
 
+pub async fn fetch_geo_monitor_run_result_prompt_ids(
+    pool: &MySqlPool,
+    run_id: i64,
+) -> Result<Vec<i64>, Error> {
+    sqlx::query_scalar::<_, i64>(
+        r#"
+      SELECT prompt_id FROM geo_monitor_run_results WHERE run_id = ?;
+    "#,
+    )
+    .bind(run_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })
+}
+
 pub async fn fetch_latest_geo_monitor_run(
     pool: &MySqlPool,
     tenant_id: &str,
@@ -2987,7 +5532,7 @@ pub async fn finalize_geo_monitor_run_if_complete(
     .await
     .map_err(|e| -> Error { Box::new(e) })?;
 
-    if results_total < prompt_total as i64 {
+    if !crate::geo_monitor::geo_monitor_run_complete(prompt_total, results_total) {
         return Ok(false);
     }
 
@@ -3129,6 +5674,33 @@ pub fn sanitize_sql_identifier(header: &str) -> String {
     normalized
 }
 
+/// MySQL's identifier length limit.
+pub const MAX_SQL_IDENTIFIER_LEN: usize = 64;
+
+/// Whether `name` can be spliced into SQL between backticks: 1-64 characters of `[a-z0-9_]` not
+/// starting with a digit, the only shape `sanitize_sql_identifier` produces.
+pub fn is_safe_sql_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_SQL_IDENTIFIER_LEN
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// `name` unchanged when `is_safe_sql_identifier` accepts it; checked again right before dynamic
+/// DDL/DML is built rather than trusting the sanitizer upstream.
+pub fn validate_sql_identifier(name: &str) -> Result<&str, Error> {
+    if is_safe_sql_identifier(name) {
+        Ok(name)
+    } else {
+        Err(Box::new(std::io::Error::other(format!(
+            "unsafe sql identifier rejected: {:?}",
+            name.chars().take(80).collect::<String>()
+        ))))
+    }
+}
+
 pub fn dedupe_columns(headers: &[String]) -> Vec<String> {
     let mut seen: HashMap<String, usize> = HashMap::new();
     let mut out: Vec<String> = Vec::with_capacity(headers.len());
@@ -3151,6 +5723,90 @@ pub fn dedupe_columns(headers: &[String]) -> Vec<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn usage_events_record_attribution_and_group_by_it() {
+        assert_eq!(UsageCostGroupBy::parse("project"), Some(UsageCostGroupBy::Project));
+        assert_eq!(UsageCostGroupBy::parse(" Channel_ID "), Some(UsageCostGroupBy::Channel));
+        assert_eq!(UsageCostGroupBy::parse("event_type"), Some(UsageCostGroupBy::EventType));
+        assert_eq!(UsageCostGroupBy::parse("model"), None);
+
+        let totals = |event_type: &str, project_id: Option<i64>, channel_id: Option<&str>, cost_usd: f64| {
+            UsageAttributionTotals {
+                event_type: event_type.to_string(),
+                project_id,
+                channel_id: channel_id.map(str::to_string),
+                events: 2,
+                prompt_tokens: 100,
+                completion_tokens: 10,
+                cost_usd,
+            }
+        };
+        let seeded = vec![
+            totals("geo_monitor_run", Some(7), None, 0.5),
+            totals("geo_monitor_run", Some(8), None, 0.25),
+            totals("chat", None, Some("UC1"), 1.0),
+            totals("chat", Some(7), Some("UC1"), 0.25),
+        ];
+
+        let by_project = group_usage_costs(&seeded, UsageCostGroupBy::Project);
+        let keys: Vec<(&str, f64, i64)> = by_project
+            .iter()
+            .map(|g| (g.key.as_str(), g.cost_usd, g.events))
+            .collect();
+        // Unattributed spend lands in the "" bucket.
+        assert_eq!(keys, vec![("", 1.0, 2), ("7", 0.75, 4), ("8", 0.25, 2)]);
+
+        let by_channel = group_usage_costs(&seeded, UsageCostGroupBy::Channel);
+        assert_eq!(by_channel[0].key, "UC1");
+        assert_eq!(by_channel[0].cost_usd, 1.25);
+        assert_eq!(by_channel[0].prompt_tokens, 200);
+        assert_eq!(by_channel[1].key, "");
+
+        let by_type = group_usage_costs(&seeded, UsageCostGroupBy::EventType);
+        assert_eq!(by_type.len(), 2);
+        assert_eq!((by_type[0].key.as_str(), by_type[0].cost_usd), ("chat", 1.25));
+    }
+
+    #[test]
+    fn job_type_with_configured_max_attempt_gets_it_on_insert() {
+        let lookup = |name: &str| match name {
+            "JOB_MAX_ATTEMPT_YOUTUBE_REPORTING" => Some("6".to_string()),
+            "JOB_MAX_ATTEMPT_GEO_MONITOR_PROMPT" => Some("1".to_string()),
+            "JOB_MAX_ATTEMPT_WEEKLY_CHANNEL" => Some("zero".to_string()),
+            _ => None,
+        };
+        assert_eq!(max_attempt_from_lookup("youtube_reporting", lookup), 6);
+        assert_eq!(max_attempt_from_lookup("geo_monitor_prompt", lookup), 1);
+        assert_eq!(
+            max_attempt_from_lookup("weekly_channel", lookup),
+            DEFAULT_JOB_MAX_ATTEMPT
+        );
+        assert_eq!(
+            max_attempt_from_lookup("daily_channel", lookup),
+            DEFAULT_JOB_MAX_ATTEMPT
+        );
+    }
+
+    #[test]
+    fn job_type_lock_ttl_falls_back_to_the_global_ttl() {
+        let lookup = |name: &str| match name {
+            "JOB_TASK_LOCK_TTL_SECS" => Some("300".to_string()),
+            "JOB_TASK_LOCK_TTL_SECS_YOUTUBE_REPORTING_REPORT" => Some("7200".to_string()),
+            "JOB_TASK_LOCK_TTL_SECS_GEO_MONITOR_PROMPT" => Some("5".to_string()),
+            "JOB_TASK_LOCK_TTL_SECS_WEEKLY_CHANNEL" => Some("long".to_string()),
+            _ => None,
+        };
+        assert_eq!(lock_ttl_from_lookup(Some("youtube_reporting_report"), lookup), 7200);
+        assert_eq!(lock_ttl_from_lookup(Some("geo_monitor_prompt"), lookup), 60);
+        assert_eq!(lock_ttl_from_lookup(Some("weekly_channel"), lookup), 300);
+        assert_eq!(lock_ttl_from_lookup(Some("daily_channel"), lookup), 300);
+        assert_eq!(lock_ttl_from_lookup(None, lookup), 300);
+        assert_eq!(
+            lock_ttl_from_lookup(Some("daily_channel"), |_| None),
+            DEFAULT_JOB_TASK_LOCK_TTL_SECS
+        );
+    }
+
     #[test]
     fn utc_day_bounds_returns_midnight_and_next_midnight() {
         let now = Utc.with_ymd_and_hms(2026, 1, 20, 16, 30, 0).unwrap();
@@ -3159,6 +5815,30 @@ mod tests {
         assert_eq!(end, Utc.with_ymd_and_hms(2026, 1, 21, 0, 0, 0).unwrap());
     }
 
+    #[test]
+    fn strict_identifier_validation_rejects_unsafe_names() {
+        for name in ["channel_basic_a2", "yt_rpt_views_1a2b3c4d", "c_123_views", "_x"] {
+            assert!(validate_sql_identifier(name).is_ok(), "{name}");
+        }
+        let too_long = "a".repeat(MAX_SQL_IDENTIFIER_LEN + 1);
+        for name in [
+            "",
+            "Views",
+            "1views",
+            "x`; DROP TABLE t; --",
+            "views` LONGTEXT, `x",
+            "a b",
+            "caf\u{e9}",
+            too_long.as_str(),
+        ] {
+            assert!(validate_sql_identifier(name).is_err(), "{name}");
+        }
+        // Everything the sanitizer emits passes the strict check.
+        for raw in ["Total Revenue ($)", "123 Views", "视频", "x`; DROP TABLE t; --"] {
+            assert!(is_safe_sql_identifier(&sanitize_sql_identifier(raw)), "{raw}");
+        }
+    }
+
     #[test]
     fn sanitize_sql_identifier_normalizes_headers() {
         assert_eq!(
@@ -3263,4 +5943,62 @@ mod tests {
             "db.rs should expose insert_tenant_ai_provider_audit()"
         );
     }
+
+    #[test]
+    fn evidence_older_than_retention_is_pruned_and_recent_kept() {
+        assert_eq!(evidence_retention_days(None, None), DEFAULT_EVIDENCE_RETENTION_DAYS);
+        assert_eq!(evidence_retention_days(None, Some("180")), 180);
+        assert_eq!(
+            evidence_retention_days(Some(r#"{"evidence_retention_days": 120}"#), Some("180")),
+            120
+        );
+        assert_eq!(evidence_retention_days(None, Some("7")), MIN_EVIDENCE_RETENTION_DAYS);
+
+        let run_for_dt = chrono::NaiveDate::from_ymd_opt(2026, 6, 30).unwrap();
+        let cutoff = evidence_retention_cutoff(run_for_dt, 90);
+        assert_eq!(cutoff, chrono::NaiveDate::from_ymd_opt(2026, 4, 1).unwrap());
+        let kept: Vec<chrono::NaiveDate> = [
+            chrono::NaiveDate::from_ymd_opt(2026, 3, 31).unwrap(),
+            cutoff,
+            chrono::NaiveDate::from_ymd_opt(2026, 6, 29).unwrap(),
+        ]
+        .into_iter()
+        .filter(|dt| *dt >= cutoff)
+        .collect();
+        assert_eq!(kept.len(), 2);
+
+        // Deletes and roll-ups select exactly the rows before the cutoff, scoped to one channel.
+        for (sql, column) in [
+            (PRUNE_DECISIONS_SQL, "as_of_dt"),
+            (SUMMARIZE_PRUNED_DECISIONS_SQL, "as_of_dt"),
+            (PRUNE_OUTCOMES_SQL, "outcome_dt"),
+            (SUMMARIZE_PRUNED_OUTCOMES_SQL, "outcome_dt"),
+        ] {
+            assert!(
+                sql.contains(&format!("tenant_id = ? AND channel_id = ? AND {column} < ?")),
+                "{sql}"
+            );
+        }
+        assert!(SUMMARIZE_PRUNED_DECISIONS_SQL.contains("decisions = decisions + VALUES(decisions)"));
+    }
+
+    #[test]
+    fn purge_rewinds_only_a_watermark_inside_or_past_the_range() {
+        let sql = REWIND_SYNC_WATERMARK_SQL;
+        assert!(sql.contains("SET last_synced_dt = DATE_SUB(?, INTERVAL 1 DAY)"));
+        // A watermark before the purged range is already behind it and stays put.
+        assert!(sql.contains("AND last_synced_dt >= ?"));
+    }
+
+    #[test]
+    fn dispatch_lock_upsert_only_takes_over_expired_locks() {
+        let sql = TRY_ACQUIRE_DISPATCH_LOCK_SQL;
+        assert!(sql.contains("holder = IF(expires_at <= ?, VALUES(holder), holder)"));
+        assert!(sql.contains("result_json = IF(expires_at <= ?, NULL, result_json)"));
+        // The expiry is reassigned last so the other conditions still compare the old value.
+        let expires = sql.find("expires_at = IF(").unwrap();
+        assert!(sql.find("holder = IF(").unwrap() < expires);
+        assert!(sql.find("result_json = IF(").unwrap() < expires);
+        assert_eq!(sql.matches('?').count(), 6);
+    }
 }
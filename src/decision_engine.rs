@@ -1,5 +1,6 @@
 use chrono::NaiveDate;
 
+use crate::outcome_engine::{DEFAULT_CATASTROPHIC_DROP_PCT, DEFAULT_OUTCOME_WINDOW_DAYS};
 use crate::providers::youtube_analytics::VideoDailyMetricRow;
 
 #[derive(Debug, Clone)]
@@ -8,6 +9,13 @@ pub struct DecisionEngineConfig {
     pub high_concentration_threshold: f64,
     pub trend_down_threshold_usd: f64,
     pub top_n_for_new_asset: usize,
+    pub catastrophic_drop_pct: f64,
+    pub new_asset_top_k: Option<usize>,
+    pub new_asset_min_revenue_share: f64,
+    /// Top videos compared between the outcome's pre and post windows (None = `top_n_for_new_asset`).
+    pub outcome_top_n: Option<usize>,
+    /// Length of each outcome comparison window in days.
+    pub outcome_window_days: i64,
 }
 
 impl Default for DecisionEngineConfig {
@@ -17,10 +25,94 @@ impl Default for DecisionEngineConfig {
             high_concentration_threshold: 0.6,
             trend_down_threshold_usd: -0.01,
             top_n_for_new_asset: 3,
+            catastrophic_drop_pct: DEFAULT_CATASTROPHIC_DROP_PCT,
+            new_asset_top_k: None,
+            new_asset_min_revenue_share: 0.0,
+            outcome_top_n: None,
+            outcome_window_days: DEFAULT_OUTCOME_WINDOW_DAYS,
         }
     }
 }
 
+#[derive(serde::Deserialize)]
+struct DecisionEngineConfigJson {
+    #[serde(default)]
+    min_days_with_data: Option<usize>,
+    #[serde(default)]
+    high_concentration_threshold: Option<f64>,
+    #[serde(default)]
+    trend_down_threshold_usd: Option<f64>,
+    #[serde(default)]
+    top_n_for_new_asset: Option<usize>,
+    #[serde(default)]
+    catastrophic_drop_pct: Option<f64>,
+    #[serde(default)]
+    new_asset_top_k: Option<usize>,
+    #[serde(default)]
+    new_asset_min_revenue_share: Option<f64>,
+    #[serde(default)]
+    outcome_top_n: Option<usize>,
+    #[serde(default)]
+    outcome_window_days: Option<i64>,
+}
+
+impl DecisionEngineConfig {
+    /// Defaults with the channel's `policy_params` applied; out-of-range values are clamped or
+    /// ignored. `None` when the JSON does not parse.
+    pub fn from_policy_params_json(raw: &str) -> Option<Self> {
+        let parsed: DecisionEngineConfigJson = serde_json::from_str(raw).ok()?;
+        let mut cfg = DecisionEngineConfig::default();
+
+        if let Some(v) = parsed.min_days_with_data {
+            cfg.min_days_with_data = v;
+        }
+        if let Some(v) = parsed.high_concentration_threshold {
+            cfg.high_concentration_threshold = v;
+        }
+        if let Some(v) = parsed.trend_down_threshold_usd {
+            cfg.trend_down_threshold_usd = v;
+        }
+        if let Some(v) = parsed.top_n_for_new_asset {
+            cfg.top_n_for_new_asset = v;
+        }
+        if let Some(v) = parsed
+            .catastrophic_drop_pct
+            .filter(|v| v.is_finite() && *v > 0.0)
+        {
+            cfg.catastrophic_drop_pct = v.min(1.0);
+        }
+        if let Some(v) = parsed.new_asset_top_k.filter(|v| *v > 0) {
+            cfg.new_asset_top_k = Some(v);
+        }
+        if let Some(v) = parsed.new_asset_min_revenue_share.filter(|v| v.is_finite()) {
+            cfg.new_asset_min_revenue_share = v.clamp(0.0, 1.0);
+        }
+        if let Some(v) = parsed.outcome_top_n.filter(|v| *v > 0) {
+            cfg.outcome_top_n = Some(v.min(50));
+        }
+        if let Some(v) = parsed.outcome_window_days.filter(|v| *v > 0) {
+            cfg.outcome_window_days = v.min(28);
+        }
+
+        Some(cfg)
+    }
+
+    /// The `policy_params` shape `from_policy_params_json` reads back.
+    pub fn to_policy_params_value(&self) -> serde_json::Value {
+        serde_json::json!({
+          "min_days_with_data": self.min_days_with_data,
+          "high_concentration_threshold": self.high_concentration_threshold,
+          "trend_down_threshold_usd": self.trend_down_threshold_usd,
+          "top_n_for_new_asset": self.top_n_for_new_asset,
+          "catastrophic_drop_pct": self.catastrophic_drop_pct,
+          "new_asset_top_k": self.new_asset_top_k,
+          "new_asset_min_revenue_share": self.new_asset_min_revenue_share,
+          "outcome_top_n": self.outcome_top_n,
+          "outcome_window_days": self.outcome_window_days,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DecisionDailyComputed {
     pub as_of_dt: NaiveDate,
@@ -31,6 +123,29 @@ pub struct DecisionDailyComputed {
     pub reevaluate: Vec<String>,
 }
 
+/// Decisions below this confidence are shown without an actionable direction; override per
+/// channel with `min_surfaced_confidence` in the active policy params.
+pub const DEFAULT_MIN_SURFACED_CONFIDENCE: f64 = 0.5;
+pub const INSUFFICIENT_CONFIDENCE: &str = "insufficient_confidence";
+
+pub fn min_surfaced_confidence(policy_params_json: Option<&str>) -> f64 {
+    policy_params_json
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .and_then(|v| v.get("min_surfaced_confidence").and_then(|v| v.as_f64()))
+        .filter(|v| v.is_finite())
+        .map(|v| v.clamp(0.0, 1.0))
+        .unwrap_or(DEFAULT_MIN_SURFACED_CONFIDENCE)
+}
+
+/// The direction users may act on, or `None` when the decision is too uncertain to surface.
+pub fn surfaced_direction(direction: &str, confidence: f64, min_confidence: f64) -> Option<&str> {
+    if confidence >= min_confidence {
+        Some(direction)
+    } else {
+        None
+    }
+}
+
 fn format_usd(value: f64) -> String {
     format!("${:.2}", value)
 }
@@ -262,6 +377,284 @@ pub fn compute_decision(
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExplanationLang {
+    En,
+    Es,
+    Zh,
+}
+
+impl ExplanationLang {
+    /// Accepts tags like `es`, `es-MX`, `zh-CN`; anything else falls back to English.
+    pub fn parse(value: Option<&str>) -> Self {
+        let tag = value.unwrap_or("").trim().to_ascii_lowercase();
+        match tag.split(['-', '_']).next().unwrap_or("") {
+            "es" => ExplanationLang::Es,
+            "zh" => ExplanationLang::Zh,
+            _ => ExplanationLang::En,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExplanationLang::En => "en",
+            ExplanationLang::Es => "es",
+            ExplanationLang::Zh => "zh",
+        }
+    }
+}
+
+/// One-paragraph, template-based explanation of a stored decision. Evidence and forbidden items
+/// are quoted as stored (English), only the surrounding phrasing is localized.
+pub fn explain_decision(
+    direction: &str,
+    confidence: f64,
+    evidence: &[String],
+    forbidden: &[String],
+    lang: ExplanationLang,
+) -> String {
+    let pct = (clamp(confidence, 0.0, 1.0) * 100.0).round() as i64;
+    let band = if confidence >= 0.75 {
+        0
+    } else if confidence >= 0.6 {
+        1
+    } else {
+        2
+    };
+    let evidence = evidence
+        .iter()
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .take(2)
+        .collect::<Vec<_>>()
+        .join("; ");
+    let avoid = forbidden
+        .iter()
+        .map(|v| v.trim())
+        .find(|v| !v.is_empty())
+        .unwrap_or("");
+
+    let (action, bands, confidence_fmt, based_on, avoid_fmt, stop) = match lang {
+        ExplanationLang::En => (
+            match direction {
+                "EXPLOIT" => "Double down on your top-performing content".to_string(),
+                "EXPLORE" => "Try new formats and topics".to_string(),
+                "PROTECT" => "Hold steady and protect current revenue".to_string(),
+                INSUFFICIENT_CONFIDENCE => {
+                    "Signals are too weak to recommend a change yet".to_string()
+                }
+                other => format!("Follow the {other} direction"),
+            },
+            ["high", "moderate", "low"],
+            " ({band} confidence, {pct}%)",
+            " Based on: ",
+            " Avoid: ",
+            ".",
+        ),
+        ExplanationLang::Es => (
+            match direction {
+                "EXPLOIT" => "Refuerza tu contenido con mejor rendimiento".to_string(),
+                "EXPLORE" => "Prueba nuevos formatos y temas".to_string(),
+                "PROTECT" => "Mantén el rumbo y protege los ingresos actuales".to_string(),
+                INSUFFICIENT_CONFIDENCE => {
+                    "Las señales aún son demasiado débiles para recomendar un cambio".to_string()
+                }
+                other => format!("Sigue la dirección {other}"),
+            },
+            ["alta", "moderada", "baja"],
+            " (confianza {band}, {pct}%)",
+            " Basado en: ",
+            " Evita: ",
+            ".",
+        ),
+        ExplanationLang::Zh => (
+            match direction {
+                "EXPLOIT" => "加大投入表现最好的内容".to_string(),
+                "EXPLORE" => "尝试新的形式和选题".to_string(),
+                "PROTECT" => "保持稳定，保护现有收入".to_string(),
+                INSUFFICIENT_CONFIDENCE => "信号仍不足以给出调整建议".to_string(),
+                other => format!("按 {other} 方向执行"),
+            },
+            ["高", "中", "低"],
+            "（置信度{band}，{pct}%）",
+            "依据：",
+            "避免：",
+            "。",
+        ),
+    };
+
+    let mut out = action;
+    out.push_str(
+        &confidence_fmt
+            .replace("{band}", bands[band])
+            .replace("{pct}", &pct.to_string()),
+    );
+    out.push_str(stop);
+    if !evidence.is_empty() {
+        out.push_str(based_on);
+        out.push_str(&evidence);
+        out.push_str(stop);
+    }
+    if !avoid.is_empty() {
+        out.push_str(avoid_fmt);
+        out.push_str(avoid);
+        out.push_str(stop);
+    }
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionDiffChange {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct DecisionDiffItem {
+    /// `evidence`, `forbidden` or `reevaluate`.
+    pub section: &'static str,
+    pub label: String,
+    pub change: DecisionDiffChange,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    /// Numbers read from `before`/`after` (`$12.50`, `64%`, `0.31`), when there is one.
+    pub before_value: Option<f64>,
+    pub after_value: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct DecisionDiff {
+    pub before_dt: NaiveDate,
+    pub after_dt: NaiveDate,
+    pub direction_before: String,
+    pub direction_after: String,
+    pub direction_flipped: bool,
+    pub confidence_before: f64,
+    pub confidence_after: f64,
+    pub items: Vec<DecisionDiffItem>,
+}
+
+/// Splits `"Top asset (7d) share: 64%"` into its label and value; forbidden/reevaluate lines
+/// have no value.
+fn split_decision_item(item: &str) -> (&str, Option<&str>) {
+    match item.split_once(": ") {
+        Some((label, value)) => (label.trim(), Some(value.trim())),
+        None => (item.trim(), None),
+    }
+}
+
+/// A label's date window (`(2026-03-01 → 2026-03-07)`) moves every day, so it is left out of
+/// the key that pairs items across the two decisions.
+fn decision_item_key(label: &str) -> String {
+    if let Some((head, rest)) = label.split_once(" (") {
+        if let Some((window, tail)) = rest.split_once(')') {
+            if window.contains('→') {
+                return format!("{head}{tail}");
+            }
+        }
+    }
+    label.to_string()
+}
+
+fn decision_item_value(value: &str) -> Option<f64> {
+    let cleaned: String = value
+        .split_whitespace()
+        .next()?
+        .chars()
+        .filter(|c| !matches!(c, '$' | '%' | ','))
+        .collect();
+    cleaned.parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+fn diff_decision_section(
+    section: &'static str,
+    before: &[String],
+    after: &[String],
+    items: &mut Vec<DecisionDiffItem>,
+) {
+    let keyed = |list: &[String]| -> Vec<(String, String, Option<String>)> {
+        list.iter()
+            .map(|item| {
+                let (label, value) = split_decision_item(item);
+                (
+                    decision_item_key(label),
+                    label.to_string(),
+                    value.map(str::to_string),
+                )
+            })
+            .collect()
+    };
+    let before = keyed(before);
+    let after = keyed(after);
+
+    for (key, label, after_value) in &after {
+        match before.iter().find(|(k, _, _)| k == key) {
+            None => items.push(DecisionDiffItem {
+                section,
+                label: label.clone(),
+                change: DecisionDiffChange::Added,
+                before: None,
+                after: after_value.clone(),
+                before_value: None,
+                after_value: after_value.as_deref().and_then(decision_item_value),
+            }),
+            Some((_, _, before_value)) if before_value != after_value => {
+                items.push(DecisionDiffItem {
+                    section,
+                    label: label.clone(),
+                    change: DecisionDiffChange::Changed,
+                    before: before_value.clone(),
+                    after: after_value.clone(),
+                    before_value: before_value.as_deref().and_then(decision_item_value),
+                    after_value: after_value.as_deref().and_then(decision_item_value),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, label, before_value) in &before {
+        if !after.iter().any(|(k, _, _)| k == key) {
+            items.push(DecisionDiffItem {
+                section,
+                label: label.clone(),
+                change: DecisionDiffChange::Removed,
+                before: before_value.clone(),
+                after: None,
+                before_value: before_value.as_deref().and_then(decision_item_value),
+                after_value: None,
+            });
+        }
+    }
+}
+
+/// What moved between two stored decisions, section by section; unchanged items are left out.
+pub fn diff_decisions(
+    before: &DecisionDailyComputed,
+    after: &DecisionDailyComputed,
+) -> DecisionDiff {
+    let mut items = Vec::new();
+    diff_decision_section("evidence", &before.evidence, &after.evidence, &mut items);
+    diff_decision_section("forbidden", &before.forbidden, &after.forbidden, &mut items);
+    diff_decision_section(
+        "reevaluate",
+        &before.reevaluate,
+        &after.reevaluate,
+        &mut items,
+    );
+    DecisionDiff {
+        before_dt: before.as_of_dt,
+        after_dt: after.as_of_dt,
+        direction_before: before.direction.clone(),
+        direction_after: after.direction.clone(),
+        direction_flipped: before.direction != after.direction,
+        confidence_before: before.confidence,
+        confidence_after: after.confidence,
+        items,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -277,6 +670,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn low_confidence_decisions_are_masked_and_confident_ones_shown() {
+        let min = min_surfaced_confidence(None);
+        assert_eq!(min, DEFAULT_MIN_SURFACED_CONFIDENCE);
+        assert_eq!(surfaced_direction("EXPLOIT", 0.45, min), None);
+        assert_eq!(surfaced_direction("EXPLOIT", 0.8, min), Some("EXPLOIT"));
+
+        let strict = min_surfaced_confidence(Some(r#"{"min_surfaced_confidence": 0.7}"#));
+        assert_eq!(strict, 0.7);
+        assert_eq!(surfaced_direction("PROTECT", 0.6, strict), None);
+        assert_eq!(surfaced_direction("PROTECT", 0.7, strict), Some("PROTECT"));
+
+        assert_eq!(
+            min_surfaced_confidence(Some(r#"{"min_surfaced_confidence": 3}"#)),
+            1.0
+        );
+        assert_eq!(
+            min_surfaced_confidence(Some("not json")),
+            DEFAULT_MIN_SURFACED_CONFIDENCE
+        );
+
+        let text = explain_decision(INSUFFICIENT_CONFIDENCE, 0.45, &[], &[], ExplanationLang::En);
+        assert!(text.starts_with("Signals are too weak to recommend a change yet"));
+    }
+
+    #[test]
+    fn explains_representative_decisions() {
+        let evidence = vec![
+            "7d estimated revenue: $120.00".to_string(),
+            "Top asset (7d) share: 72%".to_string(),
+            "New asset emergence (Top-3): no".to_string(),
+        ];
+        let forbidden = vec!["Avoid major pivots while the top asset is accelerating".to_string()];
+
+        assert_eq!(
+            explain_decision("EXPLOIT", 0.9, &evidence, &forbidden, ExplanationLang::En),
+            "Double down on your top-performing content (high confidence, 90%). Based on: 7d estimated revenue: $120.00; Top asset (7d) share: 72%. Avoid: Avoid major pivots while the top asset is accelerating."
+        );
+        assert_eq!(
+            explain_decision("PROTECT", 0.6, &[], &[], ExplanationLang::En),
+            "Hold steady and protect current revenue (moderate confidence, 60%)."
+        );
+        assert_eq!(
+            explain_decision("EXPLORE", 0.5, &[], &forbidden, ExplanationLang::parse(Some("es-MX"))),
+            "Prueba nuevos formatos y temas (confianza baja, 50%). Evita: Avoid major pivots while the top asset is accelerating."
+        );
+        assert_eq!(
+            explain_decision(
+                "PROTECT",
+                0.8,
+                &[],
+                &[],
+                ExplanationLang::parse(Some("zh-CN"))
+            ),
+            "保持稳定，保护现有收入（置信度高，80%）。"
+        );
+        assert_eq!(ExplanationLang::parse(Some("fr")), ExplanationLang::En);
+    }
+
     #[test]
     fn chooses_exploit_when_high_concentration_and_top_trending_up() {
         let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
@@ -336,4 +788,60 @@ mod tests {
         );
         assert_eq!(decision.direction, "PROTECT");
     }
+
+    #[test]
+    fn decision_diff_shows_a_changed_concentration() {
+        let start = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 3, 7).unwrap();
+        let day = |i: i64| start + chrono::Duration::days(i);
+        let cfg = DecisionEngineConfig::default();
+
+        // Monday: two videos share revenue evenly. Tuesday: v1 takes over and grows.
+        let even: Vec<_> = (0..7)
+            .flat_map(|i| [row(day(i), "v1", 5.0), row(day(i), "v2", 5.0)])
+            .collect();
+        let concentrated: Vec<_> = (1..8)
+            .flat_map(|i| [row(day(i), "v1", 10.0 + i as f64), row(day(i), "v2", 1.0)])
+            .collect();
+        let before = compute_decision(&even, end, start, end, cfg.clone());
+        let after = compute_decision(&concentrated, day(7), day(1), day(7), cfg);
+
+        let diff = diff_decisions(&before, &after);
+        assert_eq!(diff.before_dt, end);
+        assert_eq!(diff.after_dt, day(7));
+        assert_eq!(diff.direction_after, "EXPLOIT");
+        assert!(diff.direction_flipped);
+
+        let share = diff
+            .items
+            .iter()
+            .find(|i| i.label == "Top asset (7d) share")
+            .unwrap();
+        assert_eq!(share.section, "evidence");
+        assert_eq!(share.change, DecisionDiffChange::Changed);
+        assert_eq!(share.before_value, Some(50.0));
+        assert!(share.after_value.unwrap() > 85.0);
+
+        // The trend line pairs across days even though its date window moved.
+        let trend = diff
+            .items
+            .iter()
+            .find(|i| i.label.starts_with("Top asset (") && i.label.ends_with("change"))
+            .unwrap();
+        assert_eq!(trend.change, DecisionDiffChange::Changed);
+        assert_eq!(trend.before_value, Some(0.0));
+        assert_eq!(trend.after_value, Some(6.0));
+
+        assert!(diff.items.iter().any(|i| i.section == "forbidden"
+            && i.change == DecisionDiffChange::Added
+            && i.label.starts_with("Avoid major pivots")));
+        assert!(diff
+            .items
+            .iter()
+            .all(|i| i.label != "7d estimated revenue" || i.change == DecisionDiffChange::Changed));
+
+        let same = diff_decisions(&after, &after);
+        assert!(same.items.is_empty());
+        assert!(!same.direction_flipped);
+    }
 }
@@ -0,0 +1,321 @@
+use serde_json::Value;
+
+/// The table of codes and categories; every annotated error body points here.
+pub const ERROR_DOCS_URL: &str = "README.md#error-codes";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// The request itself is wrong; retrying unchanged will fail again.
+    Client,
+    /// Missing/invalid credentials or a connection the tenant has to (re)establish.
+    Auth,
+    /// Server or tenant configuration is missing or switches the capability off.
+    Config,
+    /// A plan, budget or size limit was hit.
+    Limit,
+    /// A provider (YouTube, LLM) failed or timed out; usually retryable.
+    Upstream,
+    /// Storage or processing failure on our side.
+    Internal,
+}
+
+impl ErrorCategory {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCategory::Client => "client",
+            ErrorCategory::Auth => "auth",
+            ErrorCategory::Config => "config",
+            ErrorCategory::Limit => "limit",
+            ErrorCategory::Upstream => "upstream",
+            ErrorCategory::Internal => "internal",
+        }
+    }
+}
+
+/// Every value handlers put in an error body's `"error"` (or SSE `"code"`) field. The string
+/// values are part of the API contract and must not change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    BadRequest,
+    BadCsv,
+    CsvTooManyRows,
+    CsvDateSpanTooLarge,
+    PayloadTooLarge,
+    MissingIdempotencyKey,
+    InvalidState,
+    InvalidMetadata,
+    ConfirmRequired,
+    Conflict,
+    StaleSnapshot,
+    NotFound,
+    Expired,
+    MethodNotAllowed,
+    Deprecated,
+    RedirectUriNotAllowed,
+    Unauthorized,
+    Forbidden,
+    ReauthRequired,
+    NotConnected,
+    NotConfigured,
+    ConfigError,
+    FeatureDisabled,
+    EntitlementExceeded,
+    BudgetExceeded,
+    UpstreamError,
+    YoutubeApiError,
+    YoutubeAnalyticsError,
+    ProviderTestFailed,
+    Timeout,
+    InternalError,
+    TidbError,
+    ApplyFailed,
+    RollbackFailed,
+    OutcomeQueryFailed,
+    AlertsQueryFailed,
+}
+
+impl ErrorCode {
+    pub const ALL: &'static [ErrorCode] = &[
+        ErrorCode::BadRequest,
+        ErrorCode::BadCsv,
+        ErrorCode::CsvTooManyRows,
+        ErrorCode::CsvDateSpanTooLarge,
+        ErrorCode::PayloadTooLarge,
+        ErrorCode::MissingIdempotencyKey,
+        ErrorCode::InvalidState,
+        ErrorCode::InvalidMetadata,
+        ErrorCode::ConfirmRequired,
+        ErrorCode::Conflict,
+        ErrorCode::StaleSnapshot,
+        ErrorCode::NotFound,
+        ErrorCode::Expired,
+        ErrorCode::MethodNotAllowed,
+        ErrorCode::Deprecated,
+        ErrorCode::RedirectUriNotAllowed,
+        ErrorCode::Unauthorized,
+        ErrorCode::Forbidden,
+        ErrorCode::ReauthRequired,
+        ErrorCode::NotConnected,
+        ErrorCode::NotConfigured,
+        ErrorCode::ConfigError,
+        ErrorCode::FeatureDisabled,
+        ErrorCode::EntitlementExceeded,
+        ErrorCode::BudgetExceeded,
+        ErrorCode::UpstreamError,
+        ErrorCode::YoutubeApiError,
+        ErrorCode::YoutubeAnalyticsError,
+        ErrorCode::ProviderTestFailed,
+        ErrorCode::Timeout,
+        ErrorCode::InternalError,
+        ErrorCode::TidbError,
+        ErrorCode::ApplyFailed,
+        ErrorCode::RollbackFailed,
+        ErrorCode::OutcomeQueryFailed,
+        ErrorCode::AlertsQueryFailed,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::BadRequest => "bad_request",
+            ErrorCode::BadCsv => "bad_csv",
+            ErrorCode::CsvTooManyRows => "csv_too_many_rows",
+            ErrorCode::CsvDateSpanTooLarge => "csv_date_span_too_large",
+            ErrorCode::PayloadTooLarge => "payload_too_large",
+            ErrorCode::MissingIdempotencyKey => "missing_idempotency_key",
+            ErrorCode::InvalidState => "invalid_state",
+            ErrorCode::InvalidMetadata => "invalid_metadata",
+            ErrorCode::ConfirmRequired => "confirm_required",
+            ErrorCode::Conflict => "conflict",
+            ErrorCode::StaleSnapshot => "stale_snapshot",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::Expired => "expired",
+            ErrorCode::MethodNotAllowed => "method_not_allowed",
+            ErrorCode::Deprecated => "deprecated",
+            ErrorCode::RedirectUriNotAllowed => "redirect_uri_not_allowed",
+            ErrorCode::Unauthorized => "unauthorized",
+            ErrorCode::Forbidden => "forbidden",
+            ErrorCode::ReauthRequired => "reauth_required",
+            ErrorCode::NotConnected => "not_connected",
+            ErrorCode::NotConfigured => "not_configured",
+            ErrorCode::ConfigError => "config_error",
+            ErrorCode::FeatureDisabled => "feature_disabled",
+            ErrorCode::EntitlementExceeded => "entitlement_exceeded",
+            ErrorCode::BudgetExceeded => "budget_exceeded",
+            ErrorCode::UpstreamError => "upstream_error",
+            ErrorCode::YoutubeApiError => "youtube_api_error",
+            ErrorCode::YoutubeAnalyticsError => "youtube_analytics_error",
+            ErrorCode::ProviderTestFailed => "provider_test_failed",
+            ErrorCode::Timeout => "timeout",
+            ErrorCode::InternalError => "internal_error",
+            ErrorCode::TidbError => "tidb_error",
+            ErrorCode::ApplyFailed => "apply_failed",
+            ErrorCode::RollbackFailed => "rollback_failed",
+            ErrorCode::OutcomeQueryFailed => "outcome_query_failed",
+            ErrorCode::AlertsQueryFailed => "alerts_query_failed",
+        }
+    }
+
+    pub fn category(self) -> ErrorCategory {
+        match self {
+            ErrorCode::BadRequest
+            | ErrorCode::BadCsv
+            | ErrorCode::CsvTooManyRows
+            | ErrorCode::CsvDateSpanTooLarge
+            | ErrorCode::PayloadTooLarge
+            | ErrorCode::MissingIdempotencyKey
+            | ErrorCode::InvalidState
+            | ErrorCode::InvalidMetadata
+            | ErrorCode::ConfirmRequired
+            | ErrorCode::Conflict
+            | ErrorCode::StaleSnapshot
+            | ErrorCode::NotFound
+            | ErrorCode::Expired
+            | ErrorCode::MethodNotAllowed
+            | ErrorCode::Deprecated
+            | ErrorCode::RedirectUriNotAllowed => ErrorCategory::Client,
+            ErrorCode::Unauthorized
+            | ErrorCode::Forbidden
+            | ErrorCode::ReauthRequired
+            | ErrorCode::NotConnected => ErrorCategory::Auth,
+            ErrorCode::NotConfigured | ErrorCode::ConfigError | ErrorCode::FeatureDisabled => {
+                ErrorCategory::Config
+            }
+            ErrorCode::EntitlementExceeded | ErrorCode::BudgetExceeded => ErrorCategory::Limit,
+            ErrorCode::UpstreamError
+            | ErrorCode::YoutubeApiError
+            | ErrorCode::YoutubeAnalyticsError
+            | ErrorCode::ProviderTestFailed
+            | ErrorCode::Timeout => ErrorCategory::Upstream,
+            ErrorCode::InternalError
+            | ErrorCode::TidbError
+            | ErrorCode::ApplyFailed
+            | ErrorCode::RollbackFailed
+            | ErrorCode::OutcomeQueryFailed
+            | ErrorCode::AlertsQueryFailed => ErrorCategory::Internal,
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<ErrorCode> {
+        ErrorCode::ALL.iter().copied().find(|c| c.as_str() == code)
+    }
+}
+
+/// Adds `error_category` and `error_docs` next to a top-level `"error"` code, so clients can
+/// branch on the category without keeping their own table. Bodies without a known code are left
+/// untouched.
+pub fn annotate_error_body(value: &mut Value) {
+    let Some(code) = value
+        .get("error")
+        .and_then(|v| v.as_str())
+        .and_then(ErrorCode::from_code)
+    else {
+        return;
+    };
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert(
+            "error_category".to_string(),
+            Value::from(code.category().as_str()),
+        );
+        obj.insert("error_docs".to_string(), Value::from(ERROR_DOCS_URL));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HANDLER_SOURCES: &[(&str, &str)] = &[
+        (
+            "api/oauth/youtube/router.rs",
+            include_str!("../api/oauth/youtube/router.rs"),
+        ),
+        (
+            "api/jobs/worker/tick.rs",
+            include_str!("../api/jobs/worker/tick.rs"),
+        ),
+        ("api/geo_monitor.rs", include_str!("../api/geo_monitor.rs")),
+        (
+            "api/chat/risk_check.rs",
+            include_str!("../api/chat/risk_check.rs"),
+        ),
+        (
+            "api/usage/chat_risk_check.rs",
+            include_str!("../api/usage/chat_risk_check.rs"),
+        ),
+        (
+            "api/decision/today.rs",
+            include_str!("../api/decision/today.rs"),
+        ),
+        (
+            "api/tenants/llm_settings.rs",
+            include_str!("../api/tenants/llm_settings.rs"),
+        ),
+        (
+            "api/tenants/ai_settings.rs",
+            include_str!("../api/tenants/ai_settings.rs"),
+        ),
+        (
+            "api/webhooks/billing.rs",
+            include_str!("../api/webhooks/billing.rs"),
+        ),
+        ("src/feature_flags.rs", include_str!("feature_flags.rs")),
+    ];
+
+    /// String literals following `"<key>":` (with or without a space) in `source`.
+    fn literal_codes<'a>(source: &'a str, key: &str) -> Vec<&'a str> {
+        let mut out = Vec::new();
+        for needle in [format!("\"{key}\": \""), format!("\"{key}\":\"")] {
+            for (idx, _) in source.match_indices(needle.as_str()) {
+                let rest = &source[idx + needle.len()..];
+                if let Some(end) = rest.find('"') {
+                    out.push(&rest[..end]);
+                }
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn codes_round_trip_and_are_unique() {
+        for code in ErrorCode::ALL {
+            assert_eq!(ErrorCode::from_code(code.as_str()), Some(*code));
+        }
+        let mut names: Vec<&str> = ErrorCode::ALL.iter().map(|c| c.as_str()).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), ErrorCode::ALL.len());
+        assert_eq!(ErrorCode::from_code("no_such_code"), None);
+    }
+
+    #[test]
+    fn handlers_only_return_codes_from_the_enum() {
+        let mut unknown = Vec::new();
+        for (path, source) in HANDLER_SOURCES {
+            for key in ["error", "code"] {
+                for code in literal_codes(source, key) {
+                    let looks_like_code = !code.is_empty()
+                        && code.bytes().all(|b| b.is_ascii_lowercase() || b == b'_');
+                    if looks_like_code && ErrorCode::from_code(code).is_none() {
+                        unknown.push(format!("{path}: {code}"));
+                    }
+                }
+            }
+        }
+        assert!(
+            unknown.is_empty(),
+            "codes missing from ErrorCode: {unknown:?}"
+        );
+    }
+
+    #[test]
+    fn annotate_adds_category_and_docs_for_known_codes() {
+        let mut body = serde_json::json!({"ok": false, "error": "not_connected"});
+        annotate_error_body(&mut body);
+        assert_eq!(body["error_category"], "auth");
+        assert_eq!(body["error_docs"], ERROR_DOCS_URL);
+
+        let mut ok = serde_json::json!({"ok": true, "items": []});
+        annotate_error_body(&mut ok);
+        assert!(ok.get("error_category").is_none());
+    }
+}
@@ -0,0 +1,366 @@
+use chrono::{Duration, NaiveDate};
+
+use crate::db::StoredVideoSnapshot;
+use crate::providers::youtube_videos::VideoSnapshot;
+
+/// Days back from today (or a task's `run_for_dt`) to the newest day experiment math treats as
+/// final. Analytics lags 1-2 days, so the day before today is usually still provisional.
+pub const DEFAULT_EXPERIMENT_COMPLETED_DAY_OFFSET: i64 = 2;
+
+fn completed_day_offset_from_lookup(lookup: impl Fn(&str) -> Option<String>) -> i64 {
+    lookup("EXPERIMENT_COMPLETED_DAY_OFFSET")
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .unwrap_or(DEFAULT_EXPERIMENT_COMPLETED_DAY_OFFSET)
+        .clamp(1, 7)
+}
+
+/// `EXPERIMENT_COMPLETED_DAY_OFFSET` (default 2, clamped to `1..=7`).
+pub fn experiment_completed_day_offset() -> i64 {
+    completed_day_offset_from_lookup(|name| std::env::var(name).ok())
+}
+
+pub fn experiment_last_complete_dt(reference_dt: NaiveDate, offset_days: i64) -> NaiveDate {
+    reference_dt - Duration::days(offset_days)
+}
+
+/// End of an experiment's current window: the ended date when it has ended, never past the
+/// last complete day.
+pub fn experiment_current_end_dt(
+    ended_dt: Option<NaiveDate>,
+    last_complete_dt: NaiveDate,
+) -> NaiveDate {
+    ended_dt.unwrap_or(last_complete_dt).min(last_complete_dt)
+}
+
+/// Shortest planned duration honoured; shorter plans are held to this floor.
+pub const DEFAULT_MIN_EXPERIMENT_DURATION_DAYS: i64 = 3;
+
+fn min_duration_days_from_lookup(lookup: impl Fn(&str) -> Option<String>) -> i64 {
+    lookup("EXPERIMENT_MIN_DURATION_DAYS")
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .unwrap_or(DEFAULT_MIN_EXPERIMENT_DURATION_DAYS)
+        .clamp(1, 30)
+}
+
+/// `EXPERIMENT_MIN_DURATION_DAYS` (default 3, clamped to `1..=30`).
+pub fn experiment_min_duration_days() -> i64 {
+    min_duration_days_from_lookup(|name| std::env::var(name).ok())
+}
+
+/// Duration after which the planned-duration completion fires; `None` when no plan was set.
+/// Stop-loss is independent of this.
+pub fn effective_experiment_duration_days(
+    planned_duration_days: Option<i64>,
+    min_duration_days: i64,
+) -> Option<i64> {
+    planned_duration_days
+        .filter(|v| *v > 0)
+        .map(|v| v.max(min_duration_days))
+}
+
+/// Default baseline: the 7 days before the experiment started.
+pub const EXPERIMENT_BASELINE_DAYS: i64 = 7;
+/// A baseline shortened below this many days is shifted before the change instead.
+pub const MIN_EXPERIMENT_BASELINE_DAYS: i64 = 3;
+
+/// `observed_actions.action_type` recorded when the app changes a video's title, thumbnail, or
+/// publish time.
+pub fn video_change_action_type(kind: &str, video_id: &str) -> String {
+    format!("video_change:{kind}:{video_id}")
+}
+
+pub fn is_video_change_action_for(action_type: &str, video_id: &str) -> bool {
+    video_change_kind_for(action_type, video_id).is_some()
+}
+
+/// The change kind of a `video_change:{kind}:{video_id}` action for `video_id`.
+pub fn video_change_kind_for<'a>(action_type: &'a str, video_id: &str) -> Option<&'a str> {
+    action_type
+        .strip_prefix("video_change:")
+        .and_then(|rest| rest.split_once(':'))
+        .filter(|(_, id)| *id == video_id)
+        .map(|(kind, _)| kind)
+}
+
+fn normalized(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// A token can manage several channels, so a mistyped `video_id` could still be editable; only
+/// videos whose `snippet.channelId` is the experiment's channel may be changed.
+pub fn video_owned_by_channel(snapshot: &VideoSnapshot, channel_id: &str) -> bool {
+    normalized(snapshot.channel_id.as_deref()).is_some_and(|owner| owner == channel_id.trim())
+}
+
+/// Change kinds (`title`, `publish_time`) between the stored and a fresh snapshot. Thumbnails are
+/// not compared: a video keeps its thumbnail URL when the image is replaced.
+pub fn video_snapshot_changes(
+    previous: &StoredVideoSnapshot,
+    current: &VideoSnapshot,
+) -> Vec<&'static str> {
+    let mut out = Vec::new();
+    if previous.title.trim() != current.title.trim() {
+        out.push("title");
+    }
+    if normalized(previous.publish_at.as_deref()) != normalized(current.publish_at.as_deref()) {
+        out.push("publish_time");
+    }
+    out
+}
+
+/// `(action_type, action_meta_json)` rows for changes a creator made outside the app. The first
+/// snapshot of a video only establishes the reference and records nothing; kinds in
+/// `app_change_kinds` were made by the app since that snapshot and are not reported again.
+pub fn snapshot_change_observed_actions(
+    video_id: &str,
+    previous: Option<&StoredVideoSnapshot>,
+    current: &VideoSnapshot,
+    app_change_kinds: &[String],
+) -> Vec<(String, String)> {
+    let Some(previous) = previous else {
+        return Vec::new();
+    };
+    video_snapshot_changes(previous, current)
+        .into_iter()
+        .filter(|kind| !app_change_kinds.iter().any(|k| k == kind))
+        .map(|kind| {
+            let (from, to) = match kind {
+                "title" => (Some(previous.title.clone()), Some(current.title.clone())),
+                _ => (previous.publish_at.clone(), current.publish_at.clone()),
+            };
+            let meta = serde_json::json!({
+              "video_id": video_id,
+              "kind": kind,
+              "source": "snapshot",
+              "from": from,
+              "to": to,
+            });
+            (video_change_action_type(kind, video_id), meta.to_string())
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExperimentBaselineWindow {
+    pub start_dt: NaiveDate,
+    pub end_dt: NaiveDate,
+    /// Set when a video change inside the default window moved the baseline.
+    pub note: Option<String>,
+}
+
+/// Baseline for an experiment starting on `start_dt`, avoiding days contaminated by a video
+/// change (`change_dts`). Starts after the latest change when enough days remain; otherwise
+/// shifts to the full window before the earliest change.
+pub fn experiment_baseline_window(
+    start_dt: NaiveDate,
+    change_dts: &[NaiveDate],
+) -> ExperimentBaselineWindow {
+    let default_start = start_dt - Duration::days(EXPERIMENT_BASELINE_DAYS);
+    let default_end = start_dt - Duration::days(1);
+
+    let mut in_window: Vec<NaiveDate> = change_dts
+        .iter()
+        .copied()
+        .filter(|dt| *dt >= default_start && *dt <= default_end)
+        .collect();
+    in_window.sort();
+    in_window.dedup();
+
+    let (Some(earliest), Some(latest)) = (in_window.first().copied(), in_window.last().copied())
+    else {
+        return ExperimentBaselineWindow {
+            start_dt: default_start,
+            end_dt: default_end,
+            note: None,
+        };
+    };
+
+    let remaining_days = (default_end - latest).num_days();
+    if remaining_days >= MIN_EXPERIMENT_BASELINE_DAYS {
+        return ExperimentBaselineWindow {
+            start_dt: latest + Duration::days(1),
+            end_dt: default_end,
+            note: Some(format!(
+                "baseline shortened to {remaining_days} days: video changed on {latest}"
+            )),
+        };
+    }
+
+    let end_dt = earliest - Duration::days(1);
+    let start_dt = end_dt - Duration::days(EXPERIMENT_BASELINE_DAYS - 1);
+    ExperimentBaselineWindow {
+        start_dt,
+        end_dt,
+        note: Some(format!(
+            "baseline shifted to {start_dt}..{end_dt}: video changed on {earliest}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn larger_offset_excludes_newest_day_from_current_window() {
+        let run_for_dt = d(2026, 3, 10);
+        let one_day = experiment_current_end_dt(None, experiment_last_complete_dt(run_for_dt, 1));
+        let two_days = experiment_current_end_dt(
+            None,
+            experiment_last_complete_dt(run_for_dt, DEFAULT_EXPERIMENT_COMPLETED_DAY_OFFSET),
+        );
+        assert_eq!(one_day, d(2026, 3, 9));
+        assert_eq!(two_days, d(2026, 3, 8));
+
+        // An experiment that ended earlier keeps its own end date.
+        assert_eq!(
+            experiment_current_end_dt(Some(d(2026, 3, 5)), two_days),
+            d(2026, 3, 5)
+        );
+    }
+
+    #[test]
+    fn completed_day_offset_defaults_and_clamps() {
+        assert_eq!(
+            completed_day_offset_from_lookup(|_| None),
+            DEFAULT_EXPERIMENT_COMPLETED_DAY_OFFSET
+        );
+        assert_eq!(completed_day_offset_from_lookup(|_| Some("3".into())), 3);
+        assert_eq!(completed_day_offset_from_lookup(|_| Some("0".into())), 1);
+        assert_eq!(completed_day_offset_from_lookup(|_| Some("30".into())), 7);
+    }
+
+    #[test]
+    fn baseline_window_change_shortens_the_baseline() {
+        let start_dt = d(2026, 3, 10);
+        let clean = experiment_baseline_window(start_dt, &[d(2026, 2, 20)]);
+        assert_eq!(
+            (clean.start_dt, clean.end_dt),
+            (d(2026, 3, 3), d(2026, 3, 9))
+        );
+        assert_eq!(clean.note, None);
+
+        let shortened = experiment_baseline_window(start_dt, &[d(2026, 3, 4), d(2026, 3, 5)]);
+        assert_eq!(
+            (shortened.start_dt, shortened.end_dt),
+            (d(2026, 3, 6), d(2026, 3, 9))
+        );
+        assert!(shortened.note.unwrap().contains("2026-03-05"));
+
+        // A change the day before start leaves too little; shift before it instead.
+        let shifted = experiment_baseline_window(start_dt, &[d(2026, 3, 8)]);
+        assert_eq!(
+            (shifted.start_dt, shifted.end_dt),
+            (d(2026, 3, 1), d(2026, 3, 7))
+        );
+        assert!(shifted.note.is_some());
+    }
+
+    #[test]
+    fn video_change_action_type_matches_only_its_video() {
+        let action = video_change_action_type("title", "abc_123-x");
+        assert_eq!(action, "video_change:title:abc_123-x");
+        assert!(is_video_change_action_for(&action, "abc_123-x"));
+        assert!(!is_video_change_action_for(&action, "abc_123"));
+        assert!(!is_video_change_action_for("resolve_alert:7", "7"));
+    }
+
+    #[test]
+    fn one_day_plan_is_held_to_the_duration_floor() {
+        let floor = min_duration_days_from_lookup(|_| None);
+        assert_eq!(floor, DEFAULT_MIN_EXPERIMENT_DURATION_DAYS);
+        assert_eq!(effective_experiment_duration_days(Some(1), floor), Some(3));
+        assert_eq!(
+            effective_experiment_duration_days(Some(14), floor),
+            Some(14)
+        );
+        assert_eq!(effective_experiment_duration_days(Some(0), floor), None);
+        assert_eq!(effective_experiment_duration_days(None, floor), None);
+
+        assert_eq!(min_duration_days_from_lookup(|_| Some("5".into())), 5);
+        assert_eq!(min_duration_days_from_lookup(|_| Some("0".into())), 1);
+    }
+
+    fn snapshot(title: &str, thumbnail_url: Option<&str>) -> VideoSnapshot {
+        VideoSnapshot {
+            channel_id: Some("UC_owner".to_string()),
+            title: title.to_string(),
+            description: String::new(),
+            category_id: None,
+            tags: None,
+            privacy_status: Some("public".to_string()),
+            publish_at: None,
+            thumbnail_url: thumbnail_url.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn changed_snapshot_produces_an_observed_action() {
+        let stored = StoredVideoSnapshot {
+            title: "Old title".to_string(),
+            thumbnail_url: Some("https://i.ytimg.com/vi/v1/a.jpg".to_string()),
+            publish_at: None,
+            checked_at: chrono::Utc::now(),
+        };
+
+        let unchanged = snapshot("Old title ", Some("https://i.ytimg.com/vi/v1/a.jpg"));
+        assert!(snapshot_change_observed_actions("v1", Some(&stored), &unchanged, &[]).is_empty());
+
+        // Thumbnail URLs don't change when the image does, so they are not diffed.
+        let new_thumbnail_url = snapshot("Old title", Some("https://i.ytimg.com/vi/v1/b.jpg"));
+        assert!(
+            snapshot_change_observed_actions("v1", Some(&stored), &new_thumbnail_url, &[])
+                .is_empty()
+        );
+
+        let retitled = snapshot("New title", Some("https://i.ytimg.com/vi/v1/a.jpg"));
+        let actions = snapshot_change_observed_actions("v1", Some(&stored), &retitled, &[]);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].0, "video_change:title:v1");
+        let meta: serde_json::Value = serde_json::from_str(&actions[0].1).unwrap();
+        assert_eq!(meta["from"], "Old title");
+        assert_eq!(meta["to"], "New title");
+        assert_eq!(meta["source"], "snapshot");
+
+        // The app's own retitle is already recorded.
+        let app_kinds = vec!["title".to_string()];
+        assert!(
+            snapshot_change_observed_actions("v1", Some(&stored), &retitled, &app_kinds).is_empty()
+        );
+
+        // The first snapshot only seeds the reference.
+        assert!(snapshot_change_observed_actions("v1", None, &retitled, &[]).is_empty());
+    }
+
+    #[test]
+    fn video_change_kind_is_read_for_the_matching_video_only() {
+        assert_eq!(
+            video_change_kind_for("video_change:title:v1", "v1"),
+            Some("title")
+        );
+        assert_eq!(
+            video_change_kind_for("video_change:publish_time:v1", "v1"),
+            Some("publish_time")
+        );
+        assert_eq!(video_change_kind_for("video_change:title:v2", "v1"), None);
+        assert_eq!(video_change_kind_for("upload:v1", "v1"), None);
+    }
+
+    #[test]
+    fn video_from_another_channel_is_rejected() {
+        let video = snapshot("Title", None);
+        assert!(video_owned_by_channel(&video, "UC_owner"));
+        assert!(video_owned_by_channel(&video, " UC_owner "));
+        assert!(!video_owned_by_channel(&video, "UC_other"));
+
+        let unknown_owner = VideoSnapshot {
+            channel_id: None,
+            ..snapshot("Title", None)
+        };
+        assert!(!video_owned_by_channel(&unknown_owner, "UC_owner"));
+    }
+}
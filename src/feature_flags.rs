@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use sqlx::MySqlPool;
+use vercel_runtime::Error;
+
+use crate::db::fetch_tenant_feature_flags;
+
+/// Capabilities that can be switched off per tenant via `tenant_feature_flags`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TenantFeature {
+    Experiments,
+    GeoMonitor,
+    SponsorQuotes,
+}
+
+impl TenantFeature {
+    pub const ALL: &'static [TenantFeature] = &[
+        TenantFeature::Experiments,
+        TenantFeature::GeoMonitor,
+        TenantFeature::SponsorQuotes,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TenantFeature::Experiments => "experiments",
+            TenantFeature::GeoMonitor => "geo_monitor",
+            TenantFeature::SponsorQuotes => "sponsor_quotes",
+        }
+    }
+}
+
+/// Features are on unless the tenant has a row disabling them.
+pub fn feature_enabled(flags: &[(String, bool)], feature: TenantFeature) -> bool {
+    flags
+        .iter()
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case(feature.as_str()))
+        .map(|(_, enabled)| *enabled)
+        .unwrap_or(true)
+}
+
+pub fn feature_disabled_body(feature: TenantFeature) -> serde_json::Value {
+    serde_json::json!({
+      "ok": false,
+      "error": "feature_disabled",
+      "feature": feature.as_str(),
+      "message": format!("{} is not enabled for this tenant", feature.as_str()),
+    })
+}
+
+/// `Err` carries the `feature_disabled` body handlers return as-is.
+pub fn feature_gate(
+    flags: &[(String, bool)],
+    feature: TenantFeature,
+) -> Result<(), serde_json::Value> {
+    if feature_enabled(flags, feature) {
+        Ok(())
+    } else {
+        Err(feature_disabled_body(feature))
+    }
+}
+
+pub const DEFAULT_FEATURE_FLAGS_CACHE_TTL_MS: u64 = 30_000;
+
+struct CachedFlags {
+    flags: Vec<(String, bool)>,
+    expires_at: Instant,
+}
+
+static FEATURE_FLAGS_CACHE: OnceLock<Mutex<HashMap<String, CachedFlags>>> = OnceLock::new();
+
+/// `FEATURE_FLAGS_CACHE_TTL_MS` (default 30s; `0` disables caching).
+fn feature_flags_cache_ttl() -> Duration {
+    let ms = std::env::var("FEATURE_FLAGS_CACHE_TTL_MS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_FEATURE_FLAGS_CACHE_TTL_MS);
+    Duration::from_millis(ms)
+}
+
+/// Reads the tenant's flags once and keeps them for a short TTL, so a flip in the table takes
+/// effect within `FEATURE_FLAGS_CACHE_TTL_MS`.
+pub async fn fetch_tenant_feature_flags_cached(
+    pool: &MySqlPool,
+    tenant_id: &str,
+) -> Result<Vec<(String, bool)>, Error> {
+    let cache = FEATURE_FLAGS_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Ok(guard) = cache.lock() {
+        if let Some(entry) = guard.get(tenant_id) {
+            if entry.expires_at > Instant::now() {
+                return Ok(entry.flags.clone());
+            }
+        }
+    }
+
+    let flags = fetch_tenant_feature_flags(pool, tenant_id).await?;
+    let ttl = feature_flags_cache_ttl();
+    if !ttl.is_zero() {
+        if let Ok(mut guard) = cache.lock() {
+            guard.insert(
+                tenant_id.to_string(),
+                CachedFlags {
+                    flags: flags.clone(),
+                    expires_at: Instant::now() + ttl,
+                },
+            );
+        }
+    }
+    Ok(flags)
+}
+
+pub async fn check_tenant_feature(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    feature: TenantFeature,
+) -> Result<Result<(), serde_json::Value>, Error> {
+    let flags = fetch_tenant_feature_flags_cached(pool, tenant_id).await?;
+    Ok(feature_gate(&flags, feature))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_feature_is_gated_and_enabled_feature_proceeds() {
+        let flags = vec![
+            ("experiments".to_string(), false),
+            ("sponsor_quotes".to_string(), true),
+        ];
+
+        let err = feature_gate(&flags, TenantFeature::Experiments).unwrap_err();
+        assert_eq!(err["ok"], false);
+        assert_eq!(err["error"], "feature_disabled");
+        assert_eq!(err["feature"], "experiments");
+
+        assert!(feature_gate(&flags, TenantFeature::SponsorQuotes).is_ok());
+        // No row: the feature stays available.
+        assert!(feature_gate(&flags, TenantFeature::GeoMonitor).is_ok());
+        assert!(feature_gate(&[], TenantFeature::Experiments).is_ok());
+    }
+}
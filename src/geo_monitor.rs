@@ -1,4 +1,14 @@
+use std::collections::HashSet;
+use std::future::Future;
+
 use serde_json::Value;
+use vercel_runtime::Error;
+
+use crate::db::GeoMonitorPromptRow;
+use crate::providers::llm_error::LlmProviderError;
+
+/// Job type for tasks that run several prompts of one project in a single claim.
+pub const GEO_MONITOR_PROMPT_BATCH_JOB_TYPE: &str = "geo_monitor_prompt_batch";
 
 pub fn parse_string_list_json(raw: Option<&str>) -> Vec<String> {
     let input = raw.unwrap_or("").trim();
@@ -112,10 +122,318 @@ pub fn extract_rank_from_markdown_list(haystack: &str, needles: &[String]) -> Op
     None
 }
 
+/// `GEO_MONITOR_PROMPT_BATCH_SIZE` (default 1 = one task per prompt, the original granularity).
+pub fn geo_monitor_prompt_batch_size() -> usize {
+    std::env::var("GEO_MONITOR_PROMPT_BATCH_SIZE")
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .unwrap_or(1)
+        .clamp(1, 100)
+}
+
+/// Batch tasks encode `project_id:b<batch_size>:<batch_index>` in `job_tasks.channel_id`.
+pub fn geo_monitor_batch_channel_id(
+    project_id: i64,
+    batch_size: usize,
+    batch_index: usize,
+) -> String {
+    format!("{project_id}:b{batch_size}:{batch_index}")
+}
+
+pub fn parse_geo_monitor_batch_channel_id(channel_id: &str) -> Option<(i64, usize, usize)> {
+    let mut parts = channel_id.split(':');
+    let project_id = parts.next()?.parse().ok()?;
+    let batch_size = parts.next()?.strip_prefix('b')?.parse().ok()?;
+    let batch_index = parts.next()?.parse().ok()?;
+    if parts.next().is_some() || batch_size == 0 {
+        return None;
+    }
+    Some((project_id, batch_size, batch_index))
+}
+
+/// Prompt ids owned by a batch; ids are sorted so every task slices the same ordering.
+pub fn geo_monitor_batch_prompt_ids(
+    prompt_ids: &[i64],
+    batch_size: usize,
+    batch_index: usize,
+) -> Vec<i64> {
+    let mut sorted = prompt_ids.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    sorted
+        .chunks(batch_size.max(1))
+        .nth(batch_index)
+        .map(|chunk| chunk.to_vec())
+        .unwrap_or_default()
+}
+
+/// Runs `process` for each prompt of the batch that has no result yet, so a retried batch task
+/// only redoes the prompts a previous attempt did not record. Returns how many prompts ran.
+pub async fn run_geo_monitor_prompt_batch<F, Fut>(
+    prompt_ids: &[i64],
+    already_recorded: &HashSet<i64>,
+    mut process: F,
+) -> Result<usize, Error>
+where
+    F: FnMut(i64) -> Fut,
+    Fut: Future<Output = Result<(), Error>>,
+{
+    let mut processed = 0usize;
+    for prompt_id in prompt_ids.iter().copied() {
+        if already_recorded.contains(&prompt_id) {
+            continue;
+        }
+        process(prompt_id).await?;
+        processed += 1;
+    }
+    Ok(processed)
+}
+
+/// Extra attempts for a single prompt on transient provider errors
+/// (`GEO_MONITOR_PROMPT_RETRIES`, default 2) before an error result is recorded.
+pub fn geo_monitor_prompt_retries() -> u32 {
+    std::env::var("GEO_MONITOR_PROMPT_RETRIES")
+        .ok()
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .unwrap_or(2)
+        .min(5)
+}
+
+/// Rate limits, 5xx responses, and network timeouts are worth retrying; other provider errors
+/// (bad request, auth, safety blocks) are recorded immediately. HTTP failures are judged by the
+/// status carried on `LlmProviderError`, whichever provider produced them.
+pub fn is_transient_provider_error(err: &Error) -> bool {
+    if let Some(err) = err.downcast_ref::<LlmProviderError>() {
+        if err.status.is_some() {
+            return err.is_transient_status();
+        }
+    }
+    let lower = err.to_string().to_ascii_lowercase();
+    [
+        "timed out",
+        "timeout",
+        "connection reset",
+        "connection closed",
+        "error sending request",
+    ]
+    .iter()
+    .any(|needle| lower.contains(needle))
+}
+
+/// Calls `call` until it succeeds, fails with a non-transient error, or `retries` extra
+/// attempts are used up; `backoff(attempt)` runs between attempts.
+pub async fn call_with_prompt_retry<T, F, Fut, B, BFut>(
+    retries: u32,
+    mut call: F,
+    mut backoff: B,
+) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+    B: FnMut(u32) -> BFut,
+    BFut: Future<Output = ()>,
+{
+    let mut attempt = 0u32;
+    loop {
+        match call().await {
+            Ok(v) => return Ok(v),
+            Err(err) if attempt < retries && is_transient_provider_error(&err) => {
+                attempt += 1;
+                backoff(attempt).await;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// A run is complete once every enabled prompt has a terminal result (success or exhausted error).
+pub fn geo_monitor_run_complete(prompt_total: i32, terminal_results: i64) -> bool {
+    prompt_total > 0 && terminal_results >= prompt_total as i64
+}
+
+pub const DEFAULT_GEO_MONITOR_TEMPERATURE: f64 = 0.2;
+pub const DEFAULT_GEO_MONITOR_MAX_OUTPUT_TOKENS: u32 = 1024;
+pub const GEO_MONITOR_MAX_TEMPERATURE: f64 = 2.0;
+pub const GEO_MONITOR_MAX_OUTPUT_TOKENS_LIMIT: u32 = 8192;
+
+/// Clamps a stored temperature override to `[0, 2]`; non-finite values are dropped.
+pub fn clamp_geo_monitor_temperature(raw: Option<f64>) -> Option<f64> {
+    raw.filter(|v| v.is_finite())
+        .map(|v| v.clamp(0.0, GEO_MONITOR_MAX_TEMPERATURE))
+}
+
+/// Clamps a stored max-output-tokens override to `[1, 8192]`.
+pub fn clamp_geo_monitor_max_output_tokens(raw: Option<i32>) -> Option<i32> {
+    raw.map(|v| v.clamp(1, GEO_MONITOR_MAX_OUTPUT_TOKENS_LIMIT as i32))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoMonitorGenerationParams {
+    pub temperature: f64,
+    pub max_output_tokens: u32,
+}
+
+impl GeoMonitorGenerationParams {
+    /// Per-prompt overrides (clamped) on top of the defaults.
+    pub fn for_prompt(prompt: &GeoMonitorPromptRow) -> Self {
+        Self {
+            temperature: clamp_geo_monitor_temperature(prompt.temperature)
+                .unwrap_or(DEFAULT_GEO_MONITOR_TEMPERATURE),
+            max_output_tokens: clamp_geo_monitor_max_output_tokens(prompt.max_output_tokens)
+                .map(|v| v as u32)
+                .unwrap_or(DEFAULT_GEO_MONITOR_MAX_OUTPUT_TOKENS),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn prompt_that_fails_once_then_succeeds_finalizes_with_success() {
+        let calls = std::sync::atomic::AtomicU32::new(0);
+        let mut backoffs = Vec::new();
+        let result = call_with_prompt_retry(
+            2,
+            || {
+                let n = calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                async move {
+                    if n == 0 {
+                        Err(Box::new(LlmProviderError::from_http_response(
+                            "Gemini",
+                            503,
+                            br#"{"error":{"code":503,"message":"overloaded","status":"UNAVAILABLE"}}"#,
+                        )) as Error)
+                    } else {
+                        Ok("1. Acme".to_string())
+                    }
+                }
+            },
+            |attempt| {
+                backoffs.push(attempt);
+                async {}
+            },
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), "1. Acme");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(backoffs, vec![1]);
+
+        // The single success result is terminal, so a one-prompt run finalizes.
+        assert!(geo_monitor_run_complete(1, 1));
+        assert!(!geo_monitor_run_complete(2, 1));
+    }
+
+    #[tokio::test]
+    async fn permanent_prompt_errors_are_not_retried() {
+        let calls = std::sync::atomic::AtomicU32::new(0);
+        let result: Result<String, Error> = call_with_prompt_retry(
+            2,
+            || {
+                calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                async {
+                    Err(Box::new(LlmProviderError::from_http_response(
+                        "Gemini",
+                        400,
+                        br#"{"error":{"code":400,"message":"bad","status":"INVALID_ARGUMENT"}}"#,
+                    )) as Error)
+                }
+            },
+            |_| async {},
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn openai_rate_limits_and_gateway_errors_are_retried() {
+        // The body OpenAI returns for a 429, and the HTML page a gateway returns for a 502.
+        let rate_limited = br#"{
+    "error": {
+        "message": "Rate limit reached for gpt-4o-mini in organization org-abc on requests per min (RPM): Limit 500, Used 500, Requested 1. Please try again in 120ms.",
+        "type": "requests",
+        "param": null,
+        "code": "rate_limit_exceeded"
+    }
+}"#;
+        let failures = [
+            LlmProviderError::from_http_response("OpenAI", 429, rate_limited),
+            LlmProviderError::from_http_response(
+                "OpenAI",
+                502,
+                b"<html><body>502 Bad Gateway</body></html>",
+            ),
+        ];
+        assert!(failures[0].message.starts_with("Rate limit reached"));
+
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+        let result = call_with_prompt_retry(
+            2,
+            || {
+                let n = calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let failure = failures.get(n).cloned();
+                async move {
+                    match failure {
+                        Some(err) => Err(Box::new(err) as Error),
+                        None => Ok("1. Acme".to_string()),
+                    }
+                }
+            },
+            |_| async {},
+        )
+        .await;
+        assert_eq!(result.unwrap(), "1. Acme");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+        // An invalid key is final; a dropped connection (no status) is retried.
+        let unauthorized: Error = Box::new(LlmProviderError::from_http_response(
+            "OpenAI",
+            401,
+            br#"{"error":{"message":"Incorrect API key provided: sk-abc.","type":"invalid_request_error","param":null,"code":"invalid_api_key"}}"#,
+        ));
+        assert!(!is_transient_provider_error(&unauthorized));
+        let dropped: Error = Box::new(std::io::Error::other("error sending request for url"));
+        assert!(is_transient_provider_error(&dropped));
+    }
+
+    #[tokio::test]
+    async fn batched_task_records_all_prompt_results() {
+        let prompt_ids = [14, 11, 12, 13, 15];
+        let batches: Vec<Vec<i64>> = (0..3)
+            .map(|idx| geo_monitor_batch_prompt_ids(&prompt_ids, 2, idx))
+            .collect();
+        assert_eq!(batches, vec![vec![11, 12], vec![13, 14], vec![15]]);
+
+        let channel_id = geo_monitor_batch_channel_id(7, 2, 1);
+        assert_eq!(
+            parse_geo_monitor_batch_channel_id(&channel_id),
+            Some((7, 2, 1))
+        );
+        assert_eq!(parse_geo_monitor_batch_channel_id("7:11"), None);
+
+        let recorded = std::sync::Mutex::new(Vec::new());
+        let already = HashSet::from([12]);
+        let mut ran = 0;
+        for batch in batches.iter() {
+            ran += run_geo_monitor_prompt_batch(batch, &already, |prompt_id| {
+                recorded.lock().unwrap().push(prompt_id);
+                async { Ok(()) }
+            })
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(ran, 4);
+        let mut recorded = recorded.into_inner().unwrap();
+        recorded.extend(already.iter().copied());
+        recorded.sort_unstable();
+        assert_eq!(recorded, vec![11, 12, 13, 14, 15]);
+    }
+
     #[test]
     fn normalize_aliases_dedupes_case_insensitive() {
         let out = normalize_aliases(
@@ -151,4 +469,58 @@ mod tests {
     fn parse_string_list_json_returns_empty_on_invalid_json() {
         assert!(parse_string_list_json(Some("not json")).is_empty());
     }
+
+    fn prompt_row(temperature: Option<f64>, max_output_tokens: Option<i32>) -> GeoMonitorPromptRow {
+        GeoMonitorPromptRow {
+            id: 1,
+            project_id: 7,
+            theme: None,
+            prompt_text: "best budget laptops".to_string(),
+            enabled: true,
+            sort_order: 0,
+            temperature,
+            max_output_tokens,
+        }
+    }
+
+    #[tokio::test]
+    async fn prompt_generation_overrides_flow_into_the_call() {
+        let seen = std::sync::Mutex::new(Vec::new());
+        let call = |temperature: f64, max_output_tokens: u32| {
+            seen.lock().unwrap().push((temperature, max_output_tokens));
+            async { Ok::<_, Error>(()) }
+        };
+
+        for prompt in [
+            prompt_row(None, None),
+            prompt_row(Some(0.0), Some(4096)),
+            prompt_row(Some(9.5), Some(100_000)),
+            prompt_row(Some(f64::NAN), Some(-5)),
+        ] {
+            let params = GeoMonitorGenerationParams::for_prompt(&prompt);
+            call_with_prompt_retry(
+                0,
+                || call(params.temperature, params.max_output_tokens),
+                |_| async {},
+            )
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                (
+                    DEFAULT_GEO_MONITOR_TEMPERATURE,
+                    DEFAULT_GEO_MONITOR_MAX_OUTPUT_TOKENS
+                ),
+                (0.0, 4096),
+                (
+                    GEO_MONITOR_MAX_TEMPERATURE,
+                    GEO_MONITOR_MAX_OUTPUT_TOKENS_LIMIT
+                ),
+                (DEFAULT_GEO_MONITOR_TEMPERATURE, 1),
+            ]
+        );
+    }
 }
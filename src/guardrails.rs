@@ -151,6 +151,138 @@ pub fn evaluate_guardrails(input: &GuardrailInput) -> Vec<GuardrailAlert> {
     out
 }
 
+/// Same-day channel totals from the two ingestion paths: Studio CSV channel totals vs the
+/// per-video rows synced from the YouTube Analytics API.
+#[derive(Debug, Clone, Copy)]
+pub struct SourceDayComparison {
+    pub dt: NaiveDate,
+    pub csv: WindowAgg,
+    pub api: WindowAgg,
+}
+
+impl SourceDayComparison {
+    /// Relative revenue gap, normalized by the larger of the two sources.
+    pub fn revenue_diff_pct(&self) -> Option<f64> {
+        let denom = self.csv.revenue_usd.abs().max(self.api.revenue_usd.abs());
+        if denom <= 0.0 || !denom.is_finite() {
+            return None;
+        }
+        Some((self.csv.revenue_usd - self.api.revenue_usd).abs() / denom)
+    }
+}
+
+pub const SOURCE_DIVERGENCE_THRESHOLD_PCT: f64 = 0.20;
+const SOURCE_DIVERGENCE_MIN_REVENUE_USD: f64 = 1.0;
+
+/// Days where CSV and API revenue disagree beyond `threshold_pct`.
+/// Days where both sources are under $1 are ignored (rounding noise dominates).
+pub fn diverging_source_days(
+    days: &[SourceDayComparison],
+    threshold_pct: f64,
+) -> Vec<SourceDayComparison> {
+    days.iter()
+        .filter(|d| d.csv.revenue_usd.max(d.api.revenue_usd) >= SOURCE_DIVERGENCE_MIN_REVENUE_USD)
+        .filter(|d| d.revenue_diff_pct().is_some_and(|pct| pct > threshold_pct))
+        .copied()
+        .collect()
+}
+
+pub fn evaluate_source_divergence(
+    days: &[SourceDayComparison],
+    threshold_pct: f64,
+) -> Option<GuardrailAlert> {
+    let diverging = diverging_source_days(days, threshold_pct);
+    if diverging.is_empty() {
+        return None;
+    }
+
+    let max_pct = diverging
+        .iter()
+        .filter_map(|d| d.revenue_diff_pct())
+        .fold(0.0_f64, f64::max);
+    let severity = if diverging.len() * 2 > days.len() {
+        "error"
+    } else {
+        "warning"
+    };
+
+    Some(GuardrailAlert {
+        key: "source_divergence",
+        kind: "Source divergence",
+        severity,
+        message: format!(
+            "CSV and API revenue disagree on {} of {} overlapping days (max gap {:.0}%). Check that the CSV was exported for this channel.",
+            diverging.len(),
+            days.len(),
+            max_pct * 100.0
+        ),
+    })
+}
+
+/// Apply/rollback outcomes of a channel's recently started experiments.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExperimentFailureCounts {
+    pub total: i64,
+    pub apply_failed: i64,
+    pub rollback_failed: i64,
+}
+
+impl ExperimentFailureCounts {
+    pub fn failed(&self) -> i64 {
+        self.apply_failed + self.rollback_failed
+    }
+
+    pub fn failure_rate(&self) -> Option<f64> {
+        (self.total > 0).then(|| (self.failed() as f64) / (self.total as f64))
+    }
+}
+
+pub const EXPERIMENT_FAILURE_WINDOW_DAYS: i64 = 14;
+pub const DEFAULT_EXPERIMENT_FAILURE_RATE_THRESHOLD: f64 = 0.5;
+/// One or two failed experiments are usually a bad payload, not a systemic problem.
+pub const EXPERIMENT_FAILURE_MIN_FAILED: i64 = 3;
+
+/// `experiment_failure_rate_threshold` in the channel's active policy_params (0.05–1.0).
+pub fn experiment_failure_rate_threshold(policy_params_json: Option<&str>) -> f64 {
+    policy_params_json
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .and_then(|v| {
+            v.get("experiment_failure_rate_threshold")
+                .and_then(|v| v.as_f64())
+        })
+        .filter(|v| v.is_finite())
+        .map(|v| v.clamp(0.05, 1.0))
+        .unwrap_or(DEFAULT_EXPERIMENT_FAILURE_RATE_THRESHOLD)
+}
+
+pub fn evaluate_experiment_failures(
+    counts: &ExperimentFailureCounts,
+    threshold: f64,
+) -> Option<GuardrailAlert> {
+    let rate = counts.failure_rate()?;
+    if counts.failed() < EXPERIMENT_FAILURE_MIN_FAILED || rate < threshold {
+        return None;
+    }
+
+    let severity = if counts.failed() == counts.total {
+        "error"
+    } else {
+        "warning"
+    };
+    Some(GuardrailAlert {
+        key: "experiment_failures",
+        kind: "Experiment failures",
+        severity,
+        message: format!(
+            "{} of {} experiments started in the last {EXPERIMENT_FAILURE_WINDOW_DAYS} days failed to apply or roll back ({} apply, {} rollback). This usually has a common cause, such as a missing YouTube scope or lost channel permissions.",
+            counts.failed(),
+            counts.total,
+            counts.apply_failed,
+            counts.rollback_failed
+        ),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,4 +356,92 @@ mod tests {
         let alerts = evaluate_guardrails(&input);
         assert!(alerts.iter().any(|a| a.key == "rev_volatility_7d"));
     }
+
+    fn source_day(day: u32, csv_rev: f64, api_rev: f64) -> SourceDayComparison {
+        SourceDayComparison {
+            dt: NaiveDate::from_ymd_opt(2026, 2, day).unwrap(),
+            csv: WindowAgg {
+                revenue_usd: csv_rev,
+                views: 1000,
+            },
+            api: WindowAgg {
+                revenue_usd: api_rev,
+                views: 1000,
+            },
+        }
+    }
+
+    #[test]
+    fn source_divergence_triggers_when_csv_and_api_disagree() {
+        let days = vec![
+            source_day(1, 10.0, 10.2),
+            source_day(2, 40.0, 10.0),
+            source_day(3, 0.2, 0.9),
+        ];
+
+        let diverging = diverging_source_days(&days, SOURCE_DIVERGENCE_THRESHOLD_PCT);
+        assert_eq!(diverging.len(), 1);
+        assert_eq!(
+            diverging[0].dt,
+            NaiveDate::from_ymd_opt(2026, 2, 2).unwrap()
+        );
+
+        let alert = evaluate_source_divergence(&days, SOURCE_DIVERGENCE_THRESHOLD_PCT)
+            .expect("divergent sources should raise an alert");
+        assert_eq!(alert.key, "source_divergence");
+        assert_eq!(alert.severity, "warning");
+    }
+
+    #[test]
+    fn source_divergence_silent_when_sources_agree() {
+        let days = vec![source_day(1, 10.0, 10.5), source_day(2, 20.0, 19.0)];
+        assert!(evaluate_source_divergence(&days, SOURCE_DIVERGENCE_THRESHOLD_PCT).is_none());
+    }
+
+    #[test]
+    fn burst_of_failed_experiments_trips_the_alert() {
+        let burst = ExperimentFailureCounts {
+            total: 5,
+            apply_failed: 3,
+            rollback_failed: 1,
+        };
+        let alert = evaluate_experiment_failures(&burst, DEFAULT_EXPERIMENT_FAILURE_RATE_THRESHOLD)
+            .expect("4/5 failures should alert");
+        assert_eq!(alert.key, "experiment_failures");
+        assert_eq!(alert.severity, "warning");
+        assert!(alert.message.contains("4 of 5"));
+
+        let all_failed = ExperimentFailureCounts {
+            total: 3,
+            apply_failed: 3,
+            rollback_failed: 0,
+        };
+        let alert = evaluate_experiment_failures(&all_failed, 0.5).unwrap();
+        assert_eq!(alert.severity, "error");
+
+        // Isolated failures and healthy rates stay quiet.
+        let isolated = ExperimentFailureCounts {
+            total: 2,
+            apply_failed: 2,
+            rollback_failed: 0,
+        };
+        assert!(evaluate_experiment_failures(&isolated, 0.5).is_none());
+        let healthy = ExperimentFailureCounts {
+            total: 20,
+            apply_failed: 3,
+            rollback_failed: 0,
+        };
+        assert!(evaluate_experiment_failures(&healthy, 0.5).is_none());
+        assert!(evaluate_experiment_failures(&ExperimentFailureCounts::default(), 0.5).is_none());
+
+        // A stricter tenant threshold catches the same counts.
+        let threshold = experiment_failure_rate_threshold(Some(
+            r#"{"experiment_failure_rate_threshold": 0.1}"#,
+        ));
+        assert!(evaluate_experiment_failures(&healthy, threshold).is_some());
+        assert_eq!(
+            experiment_failure_rate_threshold(None),
+            DEFAULT_EXPERIMENT_FAILURE_RATE_THRESHOLD
+        );
+    }
 }
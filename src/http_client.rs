@@ -1,14 +1,60 @@
 use std::sync::OnceLock;
 use std::time::Duration;
 
+pub const DEFAULT_HTTP_CONNECT_TIMEOUT_SECS: u64 = 10;
+pub const DEFAULT_HTTP_TIMEOUT_SECS: u64 = 45;
+pub const DEFAULT_HTTP_POOL_IDLE_TIMEOUT_SECS: u64 = 90;
+
 static SHARED_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
 static NO_PROXY_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+static NO_REDIRECT_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HttpClientTimeouts {
+    connect: Duration,
+    request: Duration,
+    pool_idle: Duration,
+}
+
+/// `HTTP_CONNECT_TIMEOUT_SECS` (1..=60), `HTTP_TIMEOUT_SECS` (1..=300) and
+/// `HTTP_POOL_IDLE_TIMEOUT_SECS` (0..=600); unset or unparsable values keep the defaults.
+fn http_client_timeouts_from_lookup(lookup: impl Fn(&str) -> Option<String>) -> HttpClientTimeouts {
+    let secs = |key: &str, default: u64, min: u64, max: u64| {
+        let value = lookup(key)
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(default)
+            .clamp(min, max);
+        Duration::from_secs(value)
+    };
+    HttpClientTimeouts {
+        connect: secs(
+            "HTTP_CONNECT_TIMEOUT_SECS",
+            DEFAULT_HTTP_CONNECT_TIMEOUT_SECS,
+            1,
+            60,
+        ),
+        request: secs("HTTP_TIMEOUT_SECS", DEFAULT_HTTP_TIMEOUT_SECS, 1, 300),
+        pool_idle: secs(
+            "HTTP_POOL_IDLE_TIMEOUT_SECS",
+            DEFAULT_HTTP_POOL_IDLE_TIMEOUT_SECS,
+            0,
+            600,
+        ),
+    }
+}
 
-fn build_http_client(no_proxy: bool) -> Result<reqwest::Client, reqwest::Error> {
+fn build_http_client(
+    no_proxy: bool,
+    follow_redirects: bool,
+) -> Result<reqwest::Client, reqwest::Error> {
+    let timeouts = http_client_timeouts_from_lookup(|key| std::env::var(key).ok());
     let mut builder = reqwest::Client::builder()
         // Keep requests bounded in serverless and avoid hanging local dev sessions.
-        .connect_timeout(Duration::from_secs(10))
-        .timeout(Duration::from_secs(45))
+        .connect_timeout(timeouts.connect)
+        .timeout(timeouts.request)
+        // Idle connections are kept for reuse, so a backfill's many calls to the same Google
+        // hosts share TLS sessions instead of handshaking per request.
+        .pool_idle_timeout(timeouts.pool_idle)
         // Proxy behavior:
         // - By default, reqwest respects HTTP_PROXY / HTTPS_PROXY / NO_PROXY.
         // - We intentionally keep that default so local dev behind a proxy works.
@@ -17,10 +63,27 @@ fn build_http_client(no_proxy: bool) -> Result<reqwest::Client, reqwest::Error>
     if no_proxy {
         builder = builder.no_proxy();
     }
+    if !follow_redirects {
+        builder = builder.redirect(reqwest::redirect::Policy::none());
+    }
 
     builder.build()
 }
 
+fn shared(
+    lock: &'static OnceLock<reqwest::Client>,
+    no_proxy: bool,
+    follow_redirects: bool,
+) -> Result<&'static reqwest::Client, reqwest::Error> {
+    if let Some(client) = lock.get() {
+        return Ok(client);
+    }
+
+    let client = build_http_client(no_proxy, follow_redirects)?;
+    let _ = lock.set(client);
+    Ok(lock.get().expect("http client must be initialized"))
+}
+
 pub fn http_client_for_url(url: &str) -> Result<&'static reqwest::Client, reqwest::Error> {
     let host = reqwest::Url::parse(url)
         .ok()
@@ -29,17 +92,120 @@ pub fn http_client_for_url(url: &str) -> Result<&'static reqwest::Client, reqwes
 
     let is_loopback = matches!(host.as_str(), "127.0.0.1" | "localhost" | "::1");
 
-    let lock = if is_loopback {
-        &NO_PROXY_CLIENT
+    if is_loopback {
+        shared(&NO_PROXY_CLIENT, true, true)
     } else {
-        &SHARED_CLIENT
-    };
+        shared(&SHARED_CLIENT, false, true)
+    }
+}
 
-    if let Some(client) = lock.get() {
-        return Ok(client);
+/// Shared client that returns redirects instead of following them: OAuth token exchanges, and
+/// fetches of caller-supplied URLs whose host was checked up front.
+pub fn http_client_no_redirects() -> Result<&'static reqwest::Client, reqwest::Error> {
+    shared(&NO_REDIRECT_CLIENT, false, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PER_CALL_CLIENT_PATTERNS: &[&str] = &[
+        "reqwest::Client::new()",
+        "reqwest::Client::builder()",
+        "ClientBuilder::new()",
+        "hyper_util::client::legacy::Client::builder(",
+    ];
+
+    const PROVIDER_SOURCES: &[(&str, &str)] = &[
+        (
+            "src/providers/youtube.rs",
+            include_str!("providers/youtube.rs"),
+        ),
+        (
+            "src/providers/youtube_analytics.rs",
+            include_str!("providers/youtube_analytics.rs"),
+        ),
+        (
+            "src/providers/youtube_api.rs",
+            include_str!("providers/youtube_api.rs"),
+        ),
+        (
+            "src/providers/youtube_partner.rs",
+            include_str!("providers/youtube_partner.rs"),
+        ),
+        (
+            "src/providers/youtube_reporting.rs",
+            include_str!("providers/youtube_reporting.rs"),
+        ),
+        (
+            "src/providers/youtube_videos.rs",
+            include_str!("providers/youtube_videos.rs"),
+        ),
+        (
+            "src/providers/openai.rs",
+            include_str!("providers/openai.rs"),
+        ),
+        (
+            "api/jobs/worker/tick.rs",
+            include_str!("../api/jobs/worker/tick.rs"),
+        ),
+        (
+            "api/chat/risk_check.rs",
+            include_str!("../api/chat/risk_check.rs"),
+        ),
+        (
+            "api/tenants/ai_settings.rs",
+            include_str!("../api/tenants/ai_settings.rs"),
+        ),
+    ];
+
+    #[test]
+    fn providers_use_the_shared_client() {
+        let mut per_call = Vec::new();
+        for (path, source) in PROVIDER_SOURCES {
+            let code = source.split("#[cfg(test)]").next().unwrap_or(source);
+            for pattern in PER_CALL_CLIENT_PATTERNS {
+                if code.contains(pattern) {
+                    per_call.push(format!("{path}: {pattern}"));
+                }
+            }
+        }
+        assert!(per_call.is_empty(), "per-call HTTP clients: {per_call:?}");
+
+        // Gemini streams over hyper, through its own lazily-built static client.
+        let gemini = include_str!("providers/gemini.rs");
+        assert_eq!(
+            gemini
+                .matches("hyper_util::client::legacy::Client::builder(")
+                .count(),
+            1
+        );
+        assert!(gemini.contains("static GEMINI_HTTP_CLIENT: OnceLock<"));
     }
 
-    let client = build_http_client(is_loopback)?;
-    let _ = lock.set(client);
-    Ok(lock.get().expect("http client must be initialized"))
+    #[test]
+    fn timeouts_read_env_and_clamp() {
+        let defaults = http_client_timeouts_from_lookup(|_| None);
+        assert_eq!(
+            defaults,
+            HttpClientTimeouts {
+                connect: Duration::from_secs(DEFAULT_HTTP_CONNECT_TIMEOUT_SECS),
+                request: Duration::from_secs(DEFAULT_HTTP_TIMEOUT_SECS),
+                pool_idle: Duration::from_secs(DEFAULT_HTTP_POOL_IDLE_TIMEOUT_SECS),
+            }
+        );
+
+        let custom = http_client_timeouts_from_lookup(|key| match key {
+            "HTTP_CONNECT_TIMEOUT_SECS" => Some("0".to_string()),
+            "HTTP_TIMEOUT_SECS" => Some(" 120 ".to_string()),
+            "HTTP_POOL_IDLE_TIMEOUT_SECS" => Some("nope".to_string()),
+            _ => None,
+        });
+        assert_eq!(custom.connect, Duration::from_secs(1));
+        assert_eq!(custom.request, Duration::from_secs(120));
+        assert_eq!(
+            custom.pool_idle,
+            Duration::from_secs(DEFAULT_HTTP_POOL_IDLE_TIMEOUT_SECS)
+        );
+    }
 }
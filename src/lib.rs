@@ -1,14 +1,22 @@
 pub mod backfill;
+pub mod channel_totals;
 pub mod cost;
 pub mod db;
 pub mod decision_engine;
+pub mod error_codes;
+pub mod experiments;
+pub mod feature_flags;
 pub mod geo_monitor;
 pub mod guardrails;
 pub mod http_client;
+pub mod onboarding;
 pub mod outcome_engine;
 pub mod providers;
 pub mod reach_reporting;
 pub mod replay_gate;
 pub mod secrets;
 pub mod sse;
+pub mod sync_schedule;
+pub mod tenant_api_keys;
 pub mod youtube_alerts;
+pub mod youtube_auth;
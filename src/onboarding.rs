@@ -0,0 +1,327 @@
+use chrono::{Duration, NaiveDate};
+use sqlx::MySqlPool;
+use vercel_runtime::Error;
+
+use crate::db::{upsert_decision_daily, upsert_video_daily_metric};
+use crate::decision_engine::{compute_decision, DecisionDailyComputed, DecisionEngineConfig};
+use crate::providers::youtube_analytics::{
+    fetch_video_daily_metrics_for_channel, VideoDailyMetricRow, YoutubeAnalyticsError,
+};
+use crate::youtube_auth::{
+    call_with_fresh_youtube_token, ensure_fresh_youtube_tokens, YoutubeTokenError,
+};
+
+/// Hybrid onboarding uses the last 7 completed days (ending the day before `as_of_dt`) as the
+/// first decision window.
+pub fn first_decision_window(as_of_dt: NaiveDate) -> (NaiveDate, NaiveDate) {
+    (as_of_dt - Duration::days(7), as_of_dt - Duration::days(1))
+}
+
+pub fn compute_first_decision(
+    metrics: &[VideoDailyMetricRow],
+    as_of_dt: NaiveDate,
+) -> DecisionDailyComputed {
+    let (start_dt, end_dt) = first_decision_window(as_of_dt);
+    compute_decision(
+        metrics,
+        as_of_dt,
+        start_dt,
+        end_dt,
+        DecisionEngineConfig::default(),
+    )
+}
+
+/// Weeks of history queued behind the first decision on connect (`ONBOARDING_BACKFILL_WEEKS`).
+/// The first decision already covers the newest week, so `0` queues nothing extra.
+pub const DEFAULT_ONBOARDING_BACKFILL_WEEKS: i64 = 0;
+/// Weeks a dispatch backfills for a channel with no stored metrics (`NEW_CHANNEL_BACKFILL_WEEKS`).
+pub const DEFAULT_NEW_CHANNEL_BACKFILL_WEEKS: i64 = 4;
+/// One daily_channel task per week; a year keeps a single backfill within the Analytics quota.
+pub const MAX_BACKFILL_WEEKS: i64 = 52;
+
+/// Request override, then the env value, then `default`, clamped to `min..=MAX_BACKFILL_WEEKS`.
+pub fn configured_backfill_weeks(
+    requested: Option<i64>,
+    env_value: Option<&str>,
+    default: i64,
+    min: i64,
+) -> i64 {
+    requested
+        .or_else(|| env_value.and_then(|v| v.trim().parse().ok()))
+        .unwrap_or(default)
+        .clamp(min, MAX_BACKFILL_WEEKS)
+}
+
+/// `weeks` daily_channel run dates stepping back a week from `newest_run_for_dt`, newest first so
+/// the worker processes current data first.
+pub fn weekly_backfill_run_for_dts(newest_run_for_dt: NaiveDate, weeks: i64) -> Vec<NaiveDate> {
+    (0..weeks.max(0))
+        .map(|i| newest_run_for_dt - Duration::days(i * 7))
+        .collect()
+}
+
+/// Backfill runs queued on connect: the weeks before the first decision's window, which is the
+/// window of a daily_channel run for `as_of_dt`.
+pub fn onboarding_backfill_run_for_dts(as_of_dt: NaiveDate, weeks: i64) -> Vec<NaiveDate> {
+    weekly_backfill_run_for_dts(as_of_dt - Duration::days(7), weeks)
+}
+
+/// At most this many daily_channel tasks (7 days each) are queued to catch a reconnected channel
+/// up; an older gap is left to gap-fill dispatch.
+pub const MAX_RECONNECT_CATCH_UP_TASKS: usize = 13;
+
+/// What a connect does for the channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectOnboarding {
+    /// No stored history: fetch the last 7 completed days and compute the first decision.
+    FirstDecision,
+    /// The channel was connected before and its metrics are still stored: queue daily_channel
+    /// runs (newest first) covering the days since `resume_after_dt` instead.
+    CatchUp {
+        resume_after_dt: NaiveDate,
+        run_for_dts: Vec<NaiveDate>,
+    },
+}
+
+impl ConnectOnboarding {
+    pub fn connection_status(&self) -> &'static str {
+        match self {
+            ConnectOnboarding::FirstDecision => "connected",
+            ConnectOnboarding::CatchUp { .. } => "reconnected",
+        }
+    }
+}
+
+/// `latest_stored_dt` is the newest stored metric day for the channel; the `last_synced_dt`
+/// watermark wins when both exist. Each daily_channel run covers the 7 days before its
+/// `run_for_dt` and re-reads only from the watermark, so consecutive runs step back a week until
+/// one reaches the day after the watermark.
+pub fn connect_onboarding(
+    latest_stored_dt: Option<NaiveDate>,
+    last_synced_dt: Option<NaiveDate>,
+    as_of_dt: NaiveDate,
+) -> ConnectOnboarding {
+    let Some(resume_after_dt) = last_synced_dt.or(latest_stored_dt) else {
+        return ConnectOnboarding::FirstDecision;
+    };
+
+    let mut run_for_dts = vec![as_of_dt];
+    let mut run_for_dt = as_of_dt;
+    while run_for_dt - Duration::days(7) > resume_after_dt + Duration::days(1)
+        && run_for_dts.len() < MAX_RECONNECT_CATCH_UP_TASKS
+    {
+        run_for_dt -= Duration::days(7);
+        run_for_dts.push(run_for_dt);
+    }
+
+    ConnectOnboarding::CatchUp {
+        resume_after_dt,
+        run_for_dts,
+    }
+}
+
+/// Why a connect could not produce the first decision.
+#[derive(Debug)]
+pub enum FirstDecisionError {
+    /// The connection's stored tokens could not be loaded or refreshed.
+    Token(YoutubeTokenError),
+    /// YouTube Analytics rejected the window fetch, after one refresh-and-retry on 401.
+    Analytics(YoutubeAnalyticsError),
+    Other(Error),
+}
+
+impl From<Error> for FirstDecisionError {
+    fn from(err: Error) -> Self {
+        FirstDecisionError::Other(err)
+    }
+}
+
+/// The fetched rows the first decision stores: only days inside its window.
+pub fn first_decision_metric_rows(
+    metrics: &[VideoDailyMetricRow],
+    as_of_dt: NaiveDate,
+) -> Vec<&VideoDailyMetricRow> {
+    let (start_dt, end_dt) = first_decision_window(as_of_dt);
+    metrics
+        .iter()
+        .filter(|row| row.dt >= start_dt && row.dt <= end_dt)
+        .collect()
+}
+
+/// Fetches the first decision window for `channel_id` with the tokens stored for
+/// `connection_channel_id` (the connected channel; the same Google account can read the other
+/// channels it manages), refreshing them when expired or rejected, then stores the window
+/// metrics and the first decision. Both writes are upserts, so retrying a connect (or
+/// re-selecting the same channel) is safe.
+pub async fn create_first_decision(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    connection_channel_id: &str,
+    channel_id: &str,
+    as_of_dt: NaiveDate,
+) -> Result<DecisionDailyComputed, FirstDecisionError> {
+    let mut tokens = ensure_fresh_youtube_tokens(pool, tenant_id, connection_channel_id)
+        .await
+        .map_err(FirstDecisionError::Token)?;
+    let (start_dt, end_dt) = first_decision_window(as_of_dt);
+    let metrics = call_with_fresh_youtube_token(
+        pool,
+        tenant_id,
+        connection_channel_id,
+        &mut tokens,
+        |err: &YoutubeAnalyticsError| err.status == Some(401),
+        |access_token| async move {
+            fetch_video_daily_metrics_for_channel(&access_token, channel_id, start_dt, end_dt).await
+        },
+    )
+    .await
+    .map_err(FirstDecisionError::Token)?
+    .map_err(FirstDecisionError::Analytics)?;
+
+    for row in first_decision_metric_rows(&metrics, as_of_dt) {
+        upsert_video_daily_metric(
+            pool,
+            tenant_id,
+            channel_id,
+            row.dt,
+            &row.video_id,
+            row.estimated_revenue_usd,
+            row.impressions,
+            row.impressions_ctr,
+            row.views,
+        )
+        .await?;
+    }
+
+    let decision = compute_first_decision(&metrics, as_of_dt);
+    upsert_decision_daily(pool, tenant_id, channel_id, &decision).await?;
+    Ok(decision)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn reconnect_skips_full_onboarding() {
+        let as_of = d(2026, 3, 1);
+        assert_eq!(
+            connect_onboarding(None, None, as_of),
+            ConnectOnboarding::FirstDecision
+        );
+        assert_eq!(
+            connect_onboarding(None, None, as_of).connection_status(),
+            "connected"
+        );
+
+        // Synced two days ago: one incremental run catches up.
+        let recent = connect_onboarding(Some(d(2026, 2, 27)), Some(d(2026, 2, 27)), as_of);
+        assert_eq!(recent.connection_status(), "reconnected");
+        assert_eq!(
+            recent,
+            ConnectOnboarding::CatchUp {
+                resume_after_dt: d(2026, 2, 27),
+                run_for_dts: vec![as_of],
+            }
+        );
+
+        // Disconnected for ~3 weeks with no watermark: resume after the newest stored day and
+        // step back a week per run until the window reaches it.
+        let ConnectOnboarding::CatchUp {
+            resume_after_dt,
+            run_for_dts,
+        } = connect_onboarding(Some(d(2026, 2, 5)), None, as_of)
+        else {
+            panic!("expected a catch-up");
+        };
+        assert_eq!(resume_after_dt, d(2026, 2, 5));
+        assert_eq!(
+            run_for_dts,
+            vec![d(2026, 3, 1), d(2026, 2, 22), d(2026, 2, 15), d(2026, 2, 8)]
+        );
+
+        let ConnectOnboarding::CatchUp { run_for_dts, .. } =
+            connect_onboarding(Some(d(2024, 1, 1)), None, as_of)
+        else {
+            panic!("expected a catch-up");
+        };
+        assert_eq!(run_for_dts.len(), MAX_RECONNECT_CATCH_UP_TASKS);
+    }
+
+    #[test]
+    fn a_configured_longer_backfill_enqueues_more_weeks() {
+        let as_of = d(2026, 3, 1);
+
+        let weeks = configured_backfill_weeks(None, None, DEFAULT_ONBOARDING_BACKFILL_WEEKS, 0);
+        assert!(onboarding_backfill_run_for_dts(as_of, weeks).is_empty());
+
+        let weeks =
+            configured_backfill_weeks(None, Some("12"), DEFAULT_ONBOARDING_BACKFILL_WEEKS, 0);
+        let run_for_dts = onboarding_backfill_run_for_dts(as_of, weeks);
+        assert_eq!(run_for_dts.len(), 12);
+        // Starts right before the first decision's window and steps back a week per task.
+        let (first_start, _) = first_decision_window(as_of);
+        assert_eq!(run_for_dts[0], first_start);
+        assert_eq!(run_for_dts[11], d(2025, 12, 7));
+
+        // The request beats the env; both are capped.
+        assert_eq!(configured_backfill_weeks(Some(2), Some("12"), 0, 0), 2);
+        assert_eq!(
+            configured_backfill_weeks(Some(500), None, 0, 0),
+            MAX_BACKFILL_WEEKS
+        );
+
+        let new_channel =
+            configured_backfill_weeks(None, Some("8"), DEFAULT_NEW_CHANNEL_BACKFILL_WEEKS, 1);
+        assert_eq!(weekly_backfill_run_for_dts(as_of, new_channel).len(), 8);
+        assert_eq!(
+            configured_backfill_weeks(None, Some("0"), DEFAULT_NEW_CHANNEL_BACKFILL_WEEKS, 1),
+            1
+        );
+        assert_eq!(
+            weekly_backfill_run_for_dts(
+                as_of,
+                configured_backfill_weeks(None, None, DEFAULT_NEW_CHANNEL_BACKFILL_WEEKS, 1)
+            ),
+            vec![d(2026, 3, 1), d(2026, 2, 22), d(2026, 2, 15), d(2026, 2, 8)]
+        );
+    }
+
+    #[test]
+    fn first_decision_stores_only_its_window() {
+        let as_of = d(2026, 2, 10);
+        // The last 7 completed days: as_of itself is still in progress.
+        assert_eq!(first_decision_window(as_of), (d(2026, 2, 3), d(2026, 2, 9)));
+
+        let row = |day: u32, revenue: f64| VideoDailyMetricRow {
+            dt: d(2026, 2, day),
+            video_id: "vidA".to_string(),
+            estimated_revenue_usd: revenue,
+            impressions: 0,
+            impressions_ctr: None,
+            views: 100,
+        };
+        let mut metrics: Vec<VideoDailyMetricRow> = (3..=9).map(|day| row(day, 5.0)).collect();
+        metrics.push(row(2, 500.0));
+        metrics.push(row(10, 500.0));
+
+        let stored: Vec<NaiveDate> = first_decision_metric_rows(&metrics, as_of)
+            .iter()
+            .map(|r| r.dt)
+            .collect();
+        assert_eq!(
+            stored,
+            (3..=9).map(|day| d(2026, 2, day)).collect::<Vec<_>>()
+        );
+
+        let decision = compute_first_decision(&metrics, as_of);
+        assert_eq!(decision.as_of_dt, as_of);
+        assert!(decision
+            .evidence
+            .contains(&"7d estimated revenue: $35.00".to_string()));
+    }
+}
@@ -1,3 +1,5 @@
+use chrono::{Duration, NaiveDate};
+
 #[derive(Debug, Clone)]
 pub struct OutcomeComputed {
     pub revenue_change_pct_7d: Option<f64>,
@@ -5,11 +7,83 @@ pub struct OutcomeComputed {
     pub new_top_asset_flag: bool,
 }
 
+pub const DEFAULT_CATASTROPHIC_DROP_PCT: f64 = 0.30;
+pub const DEFAULT_OUTCOME_WINDOW_DAYS: i64 = 7;
+
+/// Pre/post comparison windows for the decision made `window_days` before `run_for_dt`; the post
+/// window ends the day before `run_for_dt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutcomeWindows {
+    pub decision_dt: NaiveDate,
+    pub pre_start_dt: NaiveDate,
+    pub pre_end_dt: NaiveDate,
+    pub post_start_dt: NaiveDate,
+    pub post_end_dt: NaiveDate,
+}
+
+pub fn outcome_windows(run_for_dt: NaiveDate, window_days: i64) -> OutcomeWindows {
+    let window_days = window_days.clamp(1, 28);
+    let decision_dt = run_for_dt - Duration::days(window_days);
+    OutcomeWindows {
+        decision_dt,
+        pre_start_dt: decision_dt - Duration::days(window_days),
+        pre_end_dt: decision_dt - Duration::days(1),
+        post_start_dt: decision_dt,
+        post_end_dt: decision_dt + Duration::days(window_days - 1),
+    }
+}
+
+/// Windows of an already stored outcome, rebuilt from its decision date and the window length it
+/// was first computed with, so a later `outcome_window_days` change does not re-score it.
+pub fn stored_outcome_windows(decision_dt: NaiveDate, window_days: i64) -> OutcomeWindows {
+    outcome_windows(
+        decision_dt + Duration::days(window_days.clamp(1, 28)),
+        window_days,
+    )
+}
+
+/// Days after `outcome_dt` a provisional outcome keeps being rechecked. Past that its missing
+/// post-window days are treated as a permanent gap and the outcome is finalized as computed.
+pub const PROVISIONAL_OUTCOME_MAX_AGE_DAYS: i64 = 14;
+
+impl OutcomeWindows {
+    pub fn post_window_days(&self) -> i64 {
+        (self.post_end_dt - self.post_start_dt).num_days() + 1
+    }
+
+    /// An outcome is provisional until every post-window day has data; provisional outcomes are
+    /// recomputed on later runs instead of being treated as final.
+    pub fn is_provisional(&self, post_days_with_data: i64) -> bool {
+        post_days_with_data < self.post_window_days()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OutcomeLabelConfig {
+    /// A 7d revenue drop strictly larger than this fraction (0.30 = -30%) is catastrophic.
+    pub catastrophic_drop_pct: f64,
+    /// Only the first `new_asset_top_k` post-window entries can count as "new" (None = all of them).
+    pub new_asset_top_k: Option<usize>,
+    /// A new asset must also earn at least this share of post-window revenue (0.0 = no floor).
+    pub new_asset_min_revenue_share: f64,
+}
+
+impl Default for OutcomeLabelConfig {
+    fn default() -> Self {
+        Self {
+            catastrophic_drop_pct: DEFAULT_CATASTROPHIC_DROP_PCT,
+            new_asset_top_k: None,
+            new_asset_min_revenue_share: 0.0,
+        }
+    }
+}
+
 pub fn compute_outcome_label(
     pre_revenue_sum_usd_7d: f64,
     post_revenue_sum_usd_7d: f64,
     pre_top_video_ids: &[String],
-    post_top_video_ids: &[String],
+    post_top_videos: &[(String, f64)],
+    cfg: &OutcomeLabelConfig,
 ) -> OutcomeComputed {
     let revenue_change_pct_7d = if pre_revenue_sum_usd_7d > 0.0 {
         Some((post_revenue_sum_usd_7d - pre_revenue_sum_usd_7d) / pre_revenue_sum_usd_7d)
@@ -18,14 +92,23 @@ pub fn compute_outcome_label(
     };
 
     let catastrophic_flag = revenue_change_pct_7d
-        .map(|pct| pct < -0.30)
+        .map(|pct| pct < -cfg.catastrophic_drop_pct)
         .unwrap_or(false);
 
     let pre_set: std::collections::HashSet<&str> =
         pre_top_video_ids.iter().map(|id| id.as_str()).collect();
-    let new_top_asset_flag = post_top_video_ids
+    let top_k = cfg.new_asset_top_k.unwrap_or(post_top_videos.len());
+    let min_share = cfg.new_asset_min_revenue_share.max(0.0);
+    let new_top_asset_flag = post_top_videos
         .iter()
-        .any(|id| !pre_set.contains(id.as_str()));
+        .take(top_k)
+        .filter(|(id, _)| !pre_set.contains(id.as_str()))
+        .any(|(_, revenue_usd)| {
+            if min_share <= 0.0 {
+                return true;
+            }
+            post_revenue_sum_usd_7d > 0.0 && revenue_usd / post_revenue_sum_usd_7d >= min_share
+        });
 
     OutcomeComputed {
         revenue_change_pct_7d,
@@ -38,11 +121,28 @@ pub fn compute_outcome_label(
 mod tests {
     use super::*;
 
+    #[test]
+    fn stored_outcome_windows_keep_their_original_length() {
+        let run_for_dt = NaiveDate::from_ymd_opt(2026, 3, 20).unwrap();
+        for window_days in [7, 14] {
+            let windows = outcome_windows(run_for_dt, window_days);
+            assert_eq!(
+                stored_outcome_windows(windows.decision_dt, windows.post_window_days()),
+                windows
+            );
+        }
+        // A 7-day outcome rechecked after the config moved to 14 days keeps its 7-day windows.
+        let seven = outcome_windows(run_for_dt, 7);
+        let rechecked = stored_outcome_windows(seven.decision_dt, 7);
+        assert_eq!(rechecked.post_window_days(), 7);
+        assert_ne!(rechecked, stored_outcome_windows(seven.decision_dt, 14));
+    }
+
     #[test]
     fn flags_catastrophic_when_revenue_drop_large() {
         let pre = 100.0;
         let post = 50.0;
-        let computed = compute_outcome_label(pre, post, &[], &[]);
+        let computed = compute_outcome_label(pre, post, &[], &[], &OutcomeLabelConfig::default());
         assert!(computed.revenue_change_pct_7d.is_some());
         assert!(computed.catastrophic_flag);
     }
@@ -50,15 +150,165 @@ mod tests {
     #[test]
     fn marks_new_top_asset_when_post_top_changes() {
         let pre_top = vec!["a".to_string(), "b".to_string(), "c".to_string()];
-        let post_top = vec!["a".to_string(), "d".to_string(), "c".to_string()];
-        let computed = compute_outcome_label(10.0, 11.0, &pre_top, &post_top);
+        let post_top = vec![
+            ("a".to_string(), 5.0),
+            ("d".to_string(), 3.0),
+            ("c".to_string(), 2.0),
+        ];
+        let computed = compute_outcome_label(
+            10.0,
+            11.0,
+            &pre_top,
+            &post_top,
+            &OutcomeLabelConfig::default(),
+        );
         assert!(computed.new_top_asset_flag);
     }
 
     #[test]
     fn avoids_divide_by_zero() {
-        let computed = compute_outcome_label(0.0, 10.0, &[], &[]);
+        let computed = compute_outcome_label(0.0, 10.0, &[], &[], &OutcomeLabelConfig::default());
         assert!(computed.revenue_change_pct_7d.is_none());
         assert!(!computed.catastrophic_flag);
     }
+
+    #[test]
+    fn catastrophic_threshold_is_configurable() {
+        let strict = OutcomeLabelConfig {
+            catastrophic_drop_pct: 0.20,
+            ..OutcomeLabelConfig::default()
+        };
+        let borderline = compute_outcome_label(100.0, 75.0, &[], &[], &strict);
+        assert!(borderline.catastrophic_flag);
+
+        let default = compute_outcome_label(100.0, 75.0, &[], &[], &OutcomeLabelConfig::default());
+        assert!(!default.catastrophic_flag);
+
+        let lenient = OutcomeLabelConfig {
+            catastrophic_drop_pct: 0.50,
+            ..OutcomeLabelConfig::default()
+        };
+        let large = compute_outcome_label(100.0, 60.0, &[], &[], &lenient);
+        assert!(!large.catastrophic_flag);
+    }
+
+    #[test]
+    fn reshuffled_tail_does_not_flag_but_new_high_earner_does() {
+        let pre_top = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let reshuffled = vec![
+            ("a".to_string(), 60.0),
+            ("b".to_string(), 30.0),
+            ("d".to_string(), 4.0),
+        ];
+        let top_k = OutcomeLabelConfig {
+            new_asset_top_k: Some(2),
+            ..OutcomeLabelConfig::default()
+        };
+        let share = OutcomeLabelConfig {
+            new_asset_min_revenue_share: 0.15,
+            ..OutcomeLabelConfig::default()
+        };
+
+        let default = compute_outcome_label(
+            100.0,
+            100.0,
+            &pre_top,
+            &reshuffled,
+            &OutcomeLabelConfig::default(),
+        );
+        assert!(default.new_top_asset_flag);
+        assert!(
+            !compute_outcome_label(100.0, 100.0, &pre_top, &reshuffled, &top_k).new_top_asset_flag
+        );
+        assert!(
+            !compute_outcome_label(100.0, 100.0, &pre_top, &reshuffled, &share).new_top_asset_flag
+        );
+
+        let breakout = vec![
+            ("e".to_string(), 45.0),
+            ("a".to_string(), 35.0),
+            ("b".to_string(), 10.0),
+        ];
+        assert!(
+            compute_outcome_label(100.0, 100.0, &pre_top, &breakout, &top_k).new_top_asset_flag
+        );
+        assert!(
+            compute_outcome_label(100.0, 100.0, &pre_top, &breakout, &share).new_top_asset_flag
+        );
+    }
+
+    fn d(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn default_outcome_windows_match_fixed_seven_days() {
+        let w = outcome_windows(d(2026, 3, 15), DEFAULT_OUTCOME_WINDOW_DAYS);
+        assert_eq!(w.decision_dt, d(2026, 3, 8));
+        assert_eq!(
+            (w.pre_start_dt, w.pre_end_dt),
+            (d(2026, 3, 1), d(2026, 3, 7))
+        );
+        assert_eq!(
+            (w.post_start_dt, w.post_end_dt),
+            (d(2026, 3, 8), d(2026, 3, 14))
+        );
+    }
+
+    #[test]
+    fn incomplete_post_window_yields_a_provisional_outcome() {
+        let w = outcome_windows(d(2026, 3, 15), DEFAULT_OUTCOME_WINDOW_DAYS);
+        assert_eq!(w.post_window_days(), 7);
+        // Reporting lag: only 5 of the 7 post-window days have landed yet.
+        assert!(w.is_provisional(5));
+        assert!(w.is_provisional(0));
+        assert!(!w.is_provisional(7));
+
+        let short = outcome_windows(d(2026, 3, 15), 3);
+        assert!(!short.is_provisional(3));
+        assert!(short.is_provisional(2));
+    }
+
+    #[test]
+    fn changed_window_changes_new_top_asset_flag() {
+        // "v_old" spiked early in the long pre window; "v_mid" earns steadily and leads the post
+        // window. Only the 7-day pre window still sees "v_old" as the top asset.
+        let revenue_on = |video_id: &str, dt: NaiveDate| -> f64 {
+            match video_id {
+                "v_old" if dt <= d(2026, 3, 3) => 100.0,
+                "v_mid" => 20.0,
+                _ => 0.0,
+            }
+        };
+        let top1 = |start: NaiveDate, end: NaiveDate| -> Vec<(String, f64)> {
+            let mut out: Vec<(String, f64)> = ["v_old", "v_mid"]
+                .iter()
+                .map(|id| {
+                    let days = (end - start).num_days() + 1;
+                    let sum = (0..days)
+                        .map(|i| revenue_on(id, start + Duration::days(i)))
+                        .sum();
+                    (id.to_string(), sum)
+                })
+                .collect();
+            out.sort_by(|a, b| b.1.total_cmp(&a.1));
+            out.truncate(1);
+            out
+        };
+        let flag_for = |window_days: i64| -> bool {
+            // Same decision day (2026-03-08) for every window length.
+            let w = outcome_windows(d(2026, 3, 8) + Duration::days(window_days), window_days);
+            assert_eq!(w.decision_dt, d(2026, 3, 8));
+            let pre: Vec<String> = top1(w.pre_start_dt, w.pre_end_dt)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect();
+            let post = top1(w.post_start_dt, w.post_end_dt);
+            compute_outcome_label(1.0, 1.0, &pre, &post, &OutcomeLabelConfig::default())
+                .new_top_asset_flag
+        };
+
+        assert!(flag_for(7));
+        assert!(!flag_for(3));
+    }
 }
@@ -8,6 +8,7 @@ use std::sync::OnceLock;
 use vercel_runtime::Error;
 
 use crate::cost::ModelPricingUsdPerMToken;
+use crate::providers::llm_error::LlmProviderError;
 
 type GeminiHttpsConnector =
     hyper_rustls::HttpsConnector<hyper_util::client::legacy::connect::HttpConnector>;
@@ -202,11 +203,11 @@ pub async fn generate_text(
         .to_bytes();
 
     if status != StatusCode::OK {
-        let msg = String::from_utf8_lossy(&body_bytes).to_string();
-        return Err(Box::new(std::io::Error::other(format!(
-            "Gemini error (status {}): {msg}",
-            status.as_u16()
-        ))));
+        return Err(Box::new(LlmProviderError::from_http_response(
+            "Gemini",
+            status.as_u16(),
+            &body_bytes,
+        )));
     }
 
     let json: Value = serde_json::from_slice(&body_bytes).map_err(|e| {
@@ -257,11 +258,11 @@ where
             .await
             .map_err(|e| Box::new(std::io::Error::other(e.to_string())) as Error)?
             .to_bytes();
-        let msg = String::from_utf8_lossy(&body_bytes).to_string();
-        return Err(Box::new(std::io::Error::other(format!(
-            "Gemini stream error (status {}): {msg}",
-            status.as_u16()
-        ))));
+        return Err(Box::new(LlmProviderError::from_http_response(
+            "Gemini stream",
+            status.as_u16(),
+            &body_bytes,
+        )));
     }
 
     let mut body = resp.into_body();
@@ -0,0 +1,90 @@
+use serde_json::Value;
+
+/// Upper bound on the response text kept in a provider error message.
+const MAX_ERROR_MESSAGE_CHARS: usize = 500;
+
+/// A failed call to an LLM provider. `status` is the HTTP status when the provider answered, so
+/// retry decisions do not depend on each provider's message format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LlmProviderError {
+    pub provider: &'static str,
+    pub status: Option<u16>,
+    pub message: String,
+}
+
+impl std::fmt::Display for LlmProviderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.status {
+            Some(status) => write!(
+                f,
+                "{} error (status {status}): {}",
+                self.provider, self.message
+            ),
+            None => write!(f, "{} error: {}", self.provider, self.message),
+        }
+    }
+}
+
+impl std::error::Error for LlmProviderError {}
+
+impl LlmProviderError {
+    /// Error for a non-success response. OpenAI, Anthropic and Gemini all wrap the reason as
+    /// `{"error": {"message": ...}}`; anything else (a gateway's HTML 502 page, an empty body) is
+    /// kept as truncated text.
+    pub fn from_http_response(provider: &'static str, status: u16, body: &[u8]) -> Self {
+        let message = serde_json::from_slice::<Value>(body)
+            .ok()
+            .and_then(|json| {
+                json.get("error")
+                    .and_then(|e| e.get("message"))
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| {
+                String::from_utf8_lossy(body)
+                    .trim()
+                    .chars()
+                    .take(MAX_ERROR_MESSAGE_CHARS)
+                    .collect()
+            });
+        LlmProviderError {
+            provider,
+            status: Some(status),
+            message,
+        }
+    }
+
+    /// Rate limits and 5xx responses are worth retrying.
+    pub fn is_transient_status(&self) -> bool {
+        self.status
+            .is_some_and(|status| status == 429 || (500..600).contains(&status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn http_errors_keep_status_and_provider_message() {
+        let err = LlmProviderError::from_http_response(
+            "Gemini",
+            503,
+            br#"{"error":{"code":503,"message":"The model is overloaded.","status":"UNAVAILABLE"}}"#,
+        );
+        assert_eq!(err.status, Some(503));
+        assert_eq!(
+            err.to_string(),
+            "Gemini error (status 503): The model is overloaded."
+        );
+        assert!(err.is_transient_status());
+
+        let html = LlmProviderError::from_http_response("OpenAI", 502, b"<html>Bad Gateway</html>");
+        assert_eq!(html.message, "<html>Bad Gateway</html>");
+        assert!(html.is_transient_status());
+
+        let bad = LlmProviderError::from_http_response("Anthropic", 400, b"{}");
+        assert_eq!(bad.message, "{}");
+        assert!(!bad.is_transient_status());
+    }
+}
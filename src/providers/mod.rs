@@ -1,4 +1,5 @@
 pub mod gemini;
+pub mod llm_error;
 pub mod openai;
 pub mod youtube;
 pub mod youtube_analytics;
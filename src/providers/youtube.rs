@@ -1,11 +1,13 @@
-use oauth2::basic::BasicClient;
+use oauth2::basic::{BasicClient, BasicErrorResponse, BasicErrorResponseType};
 use oauth2::{
     AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken, EndpointNotSet, EndpointSet,
-    RedirectUrl, RefreshToken, Scope, TokenResponse, TokenUrl,
+    RedirectUrl, RefreshToken, RequestTokenError, Scope, TokenResponse, TokenUrl,
 };
 use serde::Serialize;
 use vercel_runtime::Error;
 
+use crate::http_client::http_client_no_redirects;
+
 pub type YoutubeOAuthClient =
     BasicClient<EndpointSet, EndpointNotSet, EndpointNotSet, EndpointNotSet, EndpointSet>;
 
@@ -60,6 +62,68 @@ pub fn youtube_oauth_client_from_env() -> Result<(YoutubeOAuthClient, RedirectUr
     youtube_oauth_client_from_config(&client_id, &client_secret, &redirect_uri)
 }
 
+/// Server-side allow-list for configured redirect URIs, read from
+/// `OAUTH_REDIRECT_ALLOWED_HOSTS` / `OAUTH_REDIRECT_ALLOWED_SCHEMES` (comma-separated).
+/// Empty lists allow anything.
+#[derive(Debug, Clone, Default)]
+pub struct RedirectUriAllowList {
+    pub hosts: Vec<String>,
+    pub schemes: Vec<String>,
+}
+
+impl RedirectUriAllowList {
+    pub fn from_env() -> Self {
+        fn list(name: &str) -> Vec<String> {
+            std::env::var(name)
+                .unwrap_or_default()
+                .split(',')
+                .map(|v| v.trim().to_ascii_lowercase())
+                .filter(|v| !v.is_empty())
+                .collect()
+        }
+        Self {
+            hosts: list("OAUTH_REDIRECT_ALLOWED_HOSTS"),
+            schemes: list("OAUTH_REDIRECT_ALLOWED_SCHEMES"),
+        }
+    }
+
+    fn host_allowed(&self, host: &str) -> bool {
+        self.hosts
+            .iter()
+            .any(|allowed| match allowed.strip_prefix("*.") {
+                Some(suffix) => host
+                    .strip_suffix(suffix)
+                    .is_some_and(|prefix| prefix.ends_with('.')),
+                None => host == allowed,
+            })
+    }
+}
+
+/// Checks `redirect_uri` against the allow-list; hosts may be exact or `*.example.com`
+/// (subdomains only).
+pub fn validate_redirect_uri(
+    redirect_uri: &str,
+    allow: &RedirectUriAllowList,
+) -> Result<(), String> {
+    if allow.hosts.is_empty() && allow.schemes.is_empty() {
+        return Ok(());
+    }
+
+    let url = reqwest::Url::parse(redirect_uri.trim())
+        .map_err(|_| "redirect_uri must be an absolute URL".to_string())?;
+    let scheme = url.scheme().to_ascii_lowercase();
+    if !allow.schemes.is_empty() && !allow.schemes.contains(&scheme) {
+        return Err(format!("redirect_uri scheme {scheme} is not allowed"));
+    }
+
+    let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+    if !allow.hosts.is_empty() && !allow.host_allowed(&host) {
+        return Err(format!("redirect_uri host {host} is not allowed"));
+    }
+
+    Ok(())
+}
+
 pub fn build_authorize_url(client: &YoutubeOAuthClient, state: Option<String>) -> (String, String) {
     let (url, csrf) = client
         .authorize_url(|| {
@@ -97,14 +161,12 @@ pub async fn exchange_code_for_tokens(
     client: &YoutubeOAuthClient,
     code: &str,
 ) -> Result<YoutubeOAuthTokens, Error> {
-    let http_client = oauth2::reqwest::ClientBuilder::new()
-        .redirect(oauth2::reqwest::redirect::Policy::none())
-        .build()
+    let http_client = http_client_no_redirects()
         .map_err(|e| Box::new(std::io::Error::other(e.to_string())) as Error)?;
 
     let token = client
         .exchange_code(AuthorizationCode::new(code.to_string()))
-        .request_async(&http_client)
+        .request_async(http_client)
         .await
         .map_err(|e| Box::new(std::io::Error::other(e.to_string())) as Error)?;
 
@@ -123,20 +185,55 @@ pub async fn exchange_code_for_tokens(
     })
 }
 
+/// Google answered the refresh with `invalid_grant`: the user revoked access (or the token
+/// expired for good), so retrying is pointless until they reconnect.
+#[derive(Debug)]
+pub struct YoutubeRefreshRevoked {
+    pub description: Option<String>,
+}
+
+impl std::fmt::Display for YoutubeRefreshRevoked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.description.as_deref() {
+            Some(desc) => write!(f, "youtube refresh token revoked (invalid_grant): {desc}"),
+            None => write!(f, "youtube refresh token revoked (invalid_grant)"),
+        }
+    }
+}
+
+impl std::error::Error for YoutubeRefreshRevoked {}
+
+pub fn is_refresh_revoked(err: &Error) -> bool {
+    err.downcast_ref::<YoutubeRefreshRevoked>().is_some()
+}
+
+fn refresh_error_to_vercel_error<RE: std::error::Error + 'static>(
+    err: RequestTokenError<RE, BasicErrorResponse>,
+) -> Error {
+    match &err {
+        RequestTokenError::ServerResponse(resp)
+            if *resp.error() == BasicErrorResponseType::InvalidGrant =>
+        {
+            Box::new(YoutubeRefreshRevoked {
+                description: resp.error_description().cloned(),
+            })
+        }
+        _ => Box::new(std::io::Error::other(err.to_string())),
+    }
+}
+
 pub async fn refresh_tokens(
     client: &YoutubeOAuthClient,
     refresh_token: &str,
 ) -> Result<YoutubeOAuthTokens, Error> {
-    let http_client = oauth2::reqwest::ClientBuilder::new()
-        .redirect(oauth2::reqwest::redirect::Policy::none())
-        .build()
+    let http_client = http_client_no_redirects()
         .map_err(|e| Box::new(std::io::Error::other(e.to_string())) as Error)?;
 
     let token = client
         .exchange_refresh_token(&RefreshToken::new(refresh_token.to_string()))
-        .request_async(&http_client)
+        .request_async(http_client)
         .await
-        .map_err(|e| Box::new(std::io::Error::other(e.to_string())) as Error)?;
+        .map_err(refresh_error_to_vercel_error)?;
 
     Ok(YoutubeOAuthTokens {
         access_token: token.access_token().secret().to_string(),
@@ -157,6 +254,30 @@ pub async fn refresh_tokens(
 mod tests {
     use super::*;
 
+    #[test]
+    fn redirect_uri_allow_list_accepts_configured_hosts_only() {
+        let allow = RedirectUriAllowList {
+            hosts: vec![
+                "app.globaflux.com".to_string(),
+                "*.preview.globaflux.com".to_string(),
+            ],
+            schemes: vec!["https".to_string()],
+        };
+
+        assert!(validate_redirect_uri("https://app.globaflux.com/oauth/callback", &allow).is_ok());
+        assert!(validate_redirect_uri("https://pr-12.preview.globaflux.com/cb", &allow).is_ok());
+
+        assert!(validate_redirect_uri("https://evil.example.com/cb", &allow).is_err());
+        assert!(validate_redirect_uri("https://preview.globaflux.com.evil.io/cb", &allow).is_err());
+        assert!(validate_redirect_uri("http://app.globaflux.com/cb", &allow).is_err());
+
+        assert!(validate_redirect_uri(
+            "https://anything.example/cb",
+            &RedirectUriAllowList::default()
+        )
+        .is_ok());
+    }
+
     #[test]
     fn builds_google_authorize_url_with_expected_params() {
         let client = BasicClient::new(ClientId::new("id".to_string()))
@@ -181,4 +302,25 @@ mod tests {
         assert!(url.contains("prompt=consent"));
         assert_eq!(state, "state123");
     }
+
+    #[test]
+    fn invalid_grant_refresh_error_is_marked_revoked() {
+        let revoked: RequestTokenError<std::io::Error, BasicErrorResponse> =
+            RequestTokenError::ServerResponse(BasicErrorResponse::new(
+                BasicErrorResponseType::InvalidGrant,
+                Some("Token has been expired or revoked.".to_string()),
+                None,
+            ));
+        let err = refresh_error_to_vercel_error(revoked);
+        assert!(is_refresh_revoked(&err));
+        assert!(err.to_string().contains("invalid_grant"));
+
+        let other: RequestTokenError<std::io::Error, BasicErrorResponse> =
+            RequestTokenError::ServerResponse(BasicErrorResponse::new(
+                BasicErrorResponseType::InvalidClient,
+                None,
+                None,
+            ));
+        assert!(!is_refresh_revoked(&refresh_error_to_vercel_error(other)));
+    }
 }
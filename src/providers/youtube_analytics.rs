@@ -22,7 +22,10 @@ pub struct VideoTotalsRow {
     pub views: i64,
 }
 
-const FALLBACK_CHANNEL_VIDEO_ID: &str = "__CHANNEL_TOTAL__";
+use crate::channel_totals::API_CHANNEL_TOTAL_VIDEO_ID as FALLBACK_CHANNEL_VIDEO_ID;
+
+/// Analytics `reports.query` calls are billed against the project's quota like Data API reads.
+pub const REPORTS_QUERY_QUOTA_UNITS: i64 = 1;
 
 #[derive(Debug)]
 pub struct YoutubeAnalyticsError {
@@ -927,6 +930,88 @@ pub async fn fetch_video_daily_metrics_for_channel(
     .await
 }
 
+/// Top-video totals found behind an all-empty daily fetch. Analytics can answer 200 with zero rows
+/// when it doesn't serve `dimensions=day[,video]` for a channel, which reads exactly like a channel
+/// with no activity; the `dimensions=video` report tells the two apart.
+#[derive(Debug, Clone)]
+pub struct SilentEmptyDailyReport {
+    /// Views of the probed top videos over the window (a lower bound on channel views).
+    pub top_videos_views: i64,
+    pub top_videos: Vec<VideoTotalsRow>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChannelDailyMetricsFetch {
+    pub rows: Vec<VideoDailyMetricRow>,
+    /// Set when every daily report was empty but the channel still had views in the window.
+    pub silent_empty: Option<SilentEmptyDailyReport>,
+}
+
+const SILENT_EMPTY_PROBE_VIDEOS: i64 = 10;
+
+async fn fetch_channel_daily_metrics_checked_with_base_url(
+    access_token: &str,
+    base_url: &str,
+    channel_id: &str,
+    start_dt: NaiveDate,
+    end_dt: NaiveDate,
+) -> Result<ChannelDailyMetricsFetch, YoutubeAnalyticsError> {
+    let rows = fetch_video_daily_metrics_for_channel_with_base_url(
+        access_token,
+        base_url,
+        channel_id,
+        start_dt,
+        end_dt,
+    )
+    .await?;
+    if !rows.is_empty() {
+        return Ok(ChannelDailyMetricsFetch {
+            rows,
+            silent_empty: None,
+        });
+    }
+
+    // Best-effort: a failing probe leaves the empty fetch looking like a quiet channel.
+    let ids_value = format!("channel=={}", channel_id.trim());
+    let top_videos = fetch_top_videos_by_views_for_ids_with_base_url(
+        access_token,
+        base_url,
+        &ids_value,
+        start_dt,
+        end_dt,
+        SILENT_EMPTY_PROBE_VIDEOS,
+    )
+    .await
+    .unwrap_or_default();
+    let top_videos_views: i64 = top_videos.iter().map(|r| r.views.max(0)).sum();
+
+    Ok(ChannelDailyMetricsFetch {
+        rows,
+        silent_empty: (top_videos_views > 0).then_some(SilentEmptyDailyReport {
+            top_videos_views,
+            top_videos,
+        }),
+    })
+}
+
+/// `fetch_video_daily_metrics_for_channel`, plus a `dimensions=video` probe when it comes back
+/// empty, so ingestion can tell a silent empty response from a channel with no views.
+pub async fn fetch_channel_daily_metrics_checked(
+    access_token: &str,
+    channel_id: &str,
+    start_dt: NaiveDate,
+    end_dt: NaiveDate,
+) -> Result<ChannelDailyMetricsFetch, YoutubeAnalyticsError> {
+    fetch_channel_daily_metrics_checked_with_base_url(
+        access_token,
+        "https://youtubeanalytics.googleapis.com/",
+        channel_id,
+        start_dt,
+        end_dt,
+    )
+    .await
+}
+
 pub async fn fetch_video_daily_metrics_with_base_url(
     access_token: &str,
     base_url: &str,
@@ -1554,6 +1639,83 @@ mod tests {
         let _ = task.await;
     }
 
+    async fn serve_reports_silent_empty(listener: TcpListener, video_totals_rows: &'static str) {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let io = TokioIo::new(stream);
+            tokio::spawn(async move {
+                let _ = http1::Builder::new()
+                    .serve_connection(
+                        io,
+                        service_fn(move |req: Request<Incoming>| async move {
+                            let query = req.uri().query().unwrap_or("");
+                            // Every day-level report answers 200 with no rows.
+                            let body = if query.contains("dimensions=video&") {
+                                format!(
+                                    r#"{{"columnHeaders": [
+                                      {{"name":"video","columnType":"DIMENSION","dataType":"STRING"}},
+                                      {{"name":"views","columnType":"METRIC","dataType":"INTEGER"}}
+                                    ], "rows": {video_totals_rows}}}"#
+                                )
+                            } else {
+                                r#"{"columnHeaders": [
+                                  {"name":"day","columnType":"DIMENSION","dataType":"STRING"},
+                                  {"name":"views","columnType":"METRIC","dataType":"INTEGER"}
+                                ], "rows": []}"#
+                                    .to_string()
+                            };
+                            Ok::<_, hyper::Error>(
+                                Response::builder()
+                                    .status(StatusCode::OK)
+                                    .header("content-type", "application/json")
+                                    .body(Full::new(Bytes::from(body)))
+                                    .unwrap(),
+                            )
+                        }),
+                    )
+                    .await;
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn empty_daily_reports_fall_back_to_a_video_totals_probe() {
+        let start_dt = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let end_dt = NaiveDate::from_ymd_opt(2026, 1, 7).unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}/", listener.local_addr().unwrap());
+        let task = tokio::spawn(serve_reports_silent_empty(
+            listener,
+            r#"[["vid1", 300], ["vid2", 45]]"#,
+        ));
+        let fetched = fetch_channel_daily_metrics_checked_with_base_url(
+            "token123", &base_url, "UC123", start_dt, end_dt,
+        )
+        .await
+        .unwrap();
+        assert!(fetched.rows.is_empty());
+        let silent = fetched.silent_empty.unwrap();
+        assert_eq!(silent.top_videos_views, 345);
+        assert_eq!(silent.top_videos[0].video_id, "vid1");
+        task.abort();
+        let _ = task.await;
+
+        // A channel that really had no views stays a plain empty fetch.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}/", listener.local_addr().unwrap());
+        let task = tokio::spawn(serve_reports_silent_empty(listener, "[]"));
+        let fetched = fetch_channel_daily_metrics_checked_with_base_url(
+            "token123", &base_url, "UC123", start_dt, end_dt,
+        )
+        .await
+        .unwrap();
+        assert!(fetched.rows.is_empty());
+        assert!(fetched.silent_empty.is_none());
+        task.abort();
+        let _ = task.await;
+    }
+
     async fn serve_reports_with_impressions(listener: TcpListener, max_connections: usize) {
         for _ in 0..max_connections {
             let (stream, _) = listener.accept().await.unwrap();
@@ -1,10 +1,10 @@
-use bytes::Bytes;
-use http_body_util::{BodyExt, Empty};
-use hyper::header::{ACCEPT, AUTHORIZATION};
-use hyper::{Method, Request, StatusCode};
+use reqwest::header::ACCEPT;
+use reqwest::StatusCode;
 use serde_json::Value;
 use vercel_runtime::Error;
 
+use crate::http_client::http_client_for_url;
+
 pub fn build_content_owners_list_url(base_url: &str) -> String {
     let base = base_url.trim_end_matches('/');
     format!("{base}/contentOwners?fetchMine=true")
@@ -26,37 +26,22 @@ pub async fn fetch_my_content_owner_id_with_base_url(
     access_token: &str,
     base_url: &str,
 ) -> Result<Option<String>, Error> {
-    let connector = hyper_rustls::HttpsConnectorBuilder::new()
-        .with_native_roots()
-        .map_err(|e| Box::new(std::io::Error::other(e.to_string())) as Error)?
-        .https_or_http()
-        .enable_http1()
-        .build();
-
-    let client = hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
-        .build(connector);
-
     let url = build_content_owners_list_url(base_url);
-    let req = Request::builder()
-        .method(Method::GET)
-        .uri(url)
-        .header(AUTHORIZATION, format!("Bearer {}", access_token))
-        .header(ACCEPT, "application/json")
-        .body(Empty::<Bytes>::new())
+    let client = http_client_for_url(&url)
         .map_err(|e| Box::new(std::io::Error::other(e.to_string())) as Error)?;
-
     let resp = client
-        .request(req)
+        .get(&url)
+        .bearer_auth(access_token)
+        .header(ACCEPT, "application/json")
+        .send()
         .await
         .map_err(|e| Box::new(std::io::Error::other(e.to_string())) as Error)?;
 
     let status = resp.status();
     let body_bytes = resp
-        .into_body()
-        .collect()
+        .bytes()
         .await
-        .map_err(|e| Box::new(std::io::Error::other(e.to_string())) as Error)?
-        .to_bytes();
+        .map_err(|e| Box::new(std::io::Error::other(e.to_string())) as Error)?;
 
     if status == StatusCode::FORBIDDEN {
         return Ok(None);
@@ -146,6 +131,8 @@ mod tests {
             .unwrap();
         assert_eq!(owner_id, Some("CMS123".to_string()));
 
-        task.await.unwrap();
+        // The shared client keeps the connection pooled, so the server never sees it close.
+        task.abort();
+        let _ = task.await;
     }
 }
@@ -1,10 +1,11 @@
 use bytes::Bytes;
-use http_body_util::{BodyExt, Empty, Full};
-use hyper::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
-use hyper::{Method, Request, StatusCode};
+use reqwest::header::{ACCEPT, CONTENT_TYPE, IF_MATCH};
+use reqwest::StatusCode;
 use serde_json::Value;
 use std::net::IpAddr;
 
+use crate::http_client::{http_client_for_url, http_client_no_redirects};
+
 #[derive(Debug, Clone)]
 pub struct YoutubeVideoError {
     pub status: Option<u16>,
@@ -22,14 +23,60 @@ impl std::fmt::Display for YoutubeVideoError {
 
 impl std::error::Error for YoutubeVideoError {}
 
-#[derive(Debug, Clone)]
+/// Prefix of the error returned when a video kept changing under a `videos.update`.
+pub const STALE_SNAPSHOT: &str = "stale_snapshot";
+
+impl YoutubeVideoError {
+    /// `videos.update` rejected the `If-Match` ETag: the video changed since it was read.
+    fn is_conflict(&self) -> bool {
+        matches!(self.status, Some(409) | Some(412))
+    }
+
+    /// The update still conflicted after a fresh re-read; retrying the experiment is safe.
+    pub fn is_stale_snapshot(&self) -> bool {
+        self.message.starts_with(STALE_SNAPSHOT)
+    }
+}
+
+const YOUTUBE_DATA_API_BASE_URL: &str = "https://youtube.googleapis.com/youtube/v3";
+
+/// YouTube Data API v3 quota costs of the calls made here.
+pub const VIDEOS_LIST_QUOTA_UNITS: i64 = 1;
+pub const VIDEOS_UPDATE_QUOTA_UNITS: i64 = 50;
+pub const THUMBNAILS_SET_QUOTA_UNITS: i64 = 50;
+
+/// `(api_method, units)` spent applying one experiment change; title and publish-time updates
+/// read the video first, so they cost a `videos.list` on top of the update.
+pub fn experiment_change_quota_units(exp_type: &str) -> Option<(&'static str, i64)> {
+    match exp_type {
+        "title" | "publish_time" => Some((
+            "videos.update",
+            VIDEOS_LIST_QUOTA_UNITS + VIDEOS_UPDATE_QUOTA_UNITS,
+        )),
+        "thumbnail" => Some(("thumbnails.set", THUMBNAILS_SET_QUOTA_UNITS)),
+        _ => None,
+    }
+}
+
+/// `Serialize`/`Deserialize` let an experiment keep the full pre-change snapshot
+/// (`yt_experiments.full_snapshot_json`) for a complete restore.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct VideoSnapshot {
+    /// `snippet.channelId`: the channel that owns the video.
+    #[serde(default)]
+    pub channel_id: Option<String>,
     pub title: String,
+    #[serde(default)]
     pub description: String,
+    #[serde(default)]
     pub category_id: Option<String>,
+    #[serde(default)]
     pub tags: Option<Vec<String>>,
+    #[serde(default)]
     pub privacy_status: Option<String>,
+    #[serde(default)]
     pub publish_at: Option<String>,
+    #[serde(default)]
     pub thumbnail_url: Option<String>,
 }
 
@@ -50,46 +97,20 @@ fn best_thumbnail_url(snippet: &Value) -> Option<String> {
     None
 }
 
-async fn fetch_json(access_token: &str, url: &str) -> Result<Value, YoutubeVideoError> {
-    let connector = hyper_rustls::HttpsConnectorBuilder::new()
-        .with_native_roots()
-        .map_err(|e| YoutubeVideoError {
-            status: None,
-            message: e.to_string(),
-        })?
-        .https_or_http()
-        .enable_http1()
-        .build();
-
-    let client = hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
-        .build(connector);
-
-    let req = Request::builder()
-        .method(Method::GET)
-        .uri(url)
-        .header(AUTHORIZATION, format!("Bearer {}", access_token))
-        .header(ACCEPT, "application/json")
-        .body(Empty::<Bytes>::new())
-        .map_err(|e| YoutubeVideoError {
-            status: None,
-            message: e.to_string(),
-        })?;
-
-    let resp = client.request(req).await.map_err(|e| YoutubeVideoError {
-        status: None,
+fn request_error(e: reqwest::Error) -> YoutubeVideoError {
+    YoutubeVideoError {
+        status: e.status().map(|s| s.as_u16()),
         message: e.to_string(),
-    })?;
+    }
+}
 
+/// Body of a 200 response; any other status becomes an error carrying the body text.
+async fn ok_body(resp: reqwest::Response) -> Result<Bytes, YoutubeVideoError> {
     let status = resp.status();
-    let body_bytes = resp
-        .into_body()
-        .collect()
-        .await
-        .map_err(|e| YoutubeVideoError {
-            status: Some(status.as_u16()),
-            message: e.to_string(),
-        })?
-        .to_bytes();
+    let body_bytes = resp.bytes().await.map_err(|e| YoutubeVideoError {
+        status: Some(status.as_u16()),
+        message: e.to_string(),
+    })?;
 
     if status != StatusCode::OK {
         let msg = String::from_utf8_lossy(&body_bytes).to_string();
@@ -99,77 +120,66 @@ async fn fetch_json(access_token: &str, url: &str) -> Result<Value, YoutubeVideo
         });
     }
 
+    Ok(body_bytes)
+}
+
+async fn json_body(resp: reqwest::Response) -> Result<Value, YoutubeVideoError> {
+    let status = resp.status();
+    let body_bytes = ok_body(resp).await?;
     serde_json::from_slice::<Value>(&body_bytes).map_err(|e| YoutubeVideoError {
         status: Some(status.as_u16()),
         message: format!("invalid json response: {e}"),
     })
 }
 
-async fn put_json(access_token: &str, url: &str, body: &Value) -> Result<Value, YoutubeVideoError> {
-    let connector = hyper_rustls::HttpsConnectorBuilder::new()
-        .with_native_roots()
-        .map_err(|e| YoutubeVideoError {
-            status: None,
-            message: e.to_string(),
-        })?
-        .https_or_http()
-        .enable_http1()
-        .build();
-
-    let client = hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
-        .build(connector);
-
-    let body_str = serde_json::to_string(body).map_err(|e| YoutubeVideoError {
-        status: None,
-        message: e.to_string(),
-    })?;
-
-    let req = Request::builder()
-        .method(Method::PUT)
-        .uri(url)
-        .header(AUTHORIZATION, format!("Bearer {}", access_token))
+async fn fetch_json(access_token: &str, url: &str) -> Result<Value, YoutubeVideoError> {
+    let client = http_client_for_url(url).map_err(request_error)?;
+    let resp = client
+        .get(url)
+        .bearer_auth(access_token)
         .header(ACCEPT, "application/json")
-        .header(CONTENT_TYPE, "application/json")
-        .body(Full::new(Bytes::from(body_str)))
-        .map_err(|e| YoutubeVideoError {
-            status: None,
-            message: e.to_string(),
-        })?;
-
-    let resp = client.request(req).await.map_err(|e| YoutubeVideoError {
-        status: None,
-        message: e.to_string(),
-    })?;
-
-    let status = resp.status();
-    let body_bytes = resp
-        .into_body()
-        .collect()
+        .send()
         .await
-        .map_err(|e| YoutubeVideoError {
-            status: Some(status.as_u16()),
-            message: e.to_string(),
-        })?
-        .to_bytes();
+        .map_err(request_error)?;
 
-    if status != StatusCode::OK {
-        let msg = String::from_utf8_lossy(&body_bytes).to_string();
-        return Err(YoutubeVideoError {
-            status: Some(status.as_u16()),
-            message: msg,
-        });
+    json_body(resp).await
+}
+
+async fn put_json(
+    access_token: &str,
+    url: &str,
+    body: &Value,
+    if_match: Option<&str>,
+) -> Result<Value, YoutubeVideoError> {
+    let client = http_client_for_url(url).map_err(request_error)?;
+    let mut req = client
+        .put(url)
+        .bearer_auth(access_token)
+        .header(ACCEPT, "application/json")
+        .json(body);
+    if let Some(etag) = if_match {
+        req = req.header(IF_MATCH, etag);
     }
+    let resp = req.send().await.map_err(request_error)?;
 
-    serde_json::from_slice::<Value>(&body_bytes).map_err(|e| YoutubeVideoError {
-        status: Some(status.as_u16()),
-        message: format!("invalid json response: {e}"),
-    })
+    json_body(resp).await
 }
 
 pub async fn fetch_video_snapshot(
     access_token: &str,
     video_id: &str,
 ) -> Result<VideoSnapshot, YoutubeVideoError> {
+    fetch_video_item(access_token, YOUTUBE_DATA_API_BASE_URL, video_id)
+        .await
+        .map(|(snapshot, _)| snapshot)
+}
+
+/// The snapshot plus the item's ETag, which `videos.update` takes as `If-Match`.
+async fn fetch_video_item(
+    access_token: &str,
+    base_url: &str,
+    video_id: &str,
+) -> Result<(VideoSnapshot, Option<String>), YoutubeVideoError> {
     let video_id = video_id.trim();
     if video_id.is_empty() {
         return Err(YoutubeVideoError {
@@ -179,7 +189,8 @@ pub async fn fetch_video_snapshot(
     }
 
     let url = format!(
-        "https://youtube.googleapis.com/youtube/v3/videos?part=snippet,status&id={}",
+        "{}/videos?part=snippet,status&id={}",
+        base_url.trim_end_matches('/'),
         video_id
     );
     let json = fetch_json(access_token, &url).await?;
@@ -193,6 +204,48 @@ pub async fn fetch_video_snapshot(
             message: "video not found".to_string(),
         })?;
 
+    let etag = item
+        .get("etag")
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string());
+    Ok((video_snapshot_from_item(item), etag))
+}
+
+/// Applies one `videos.update?part=<part>` built by `build` from a fresh read, sent with that
+/// read's ETag. On a conflict the video is re-read and the update retried once; a second
+/// conflict returns a `stale_snapshot` error instead of clobbering a concurrent edit.
+async fn update_video_from_fresh_snapshot(
+    access_token: &str,
+    base_url: &str,
+    video_id: &str,
+    part: &str,
+    build: impl Fn(&VideoSnapshot) -> Result<Value, YoutubeVideoError>,
+) -> Result<(), YoutubeVideoError> {
+    const MAX_ATTEMPTS: usize = 2;
+    let url = format!("{}/videos?part={part}", base_url.trim_end_matches('/'));
+    let mut attempt = 1;
+    loop {
+        let (snap, etag) = fetch_video_item(access_token, base_url, video_id).await?;
+        let body = build(&snap)?;
+        match put_json(access_token, &url, &body, etag.as_deref()).await {
+            Ok(_) => return Ok(()),
+            Err(err) if err.is_conflict() && attempt < MAX_ATTEMPTS => attempt += 1,
+            Err(err) if err.is_conflict() => {
+                return Err(YoutubeVideoError {
+                    status: err.status,
+                    message: format!(
+                        "{STALE_SNAPSHOT}: video {} changed while it was being updated; retry the experiment",
+                        video_id.trim()
+                    ),
+                });
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Parses one `videos.list?part=snippet,status` item.
+pub fn video_snapshot_from_item(item: &Value) -> VideoSnapshot {
     let snippet = item
         .get("snippet")
         .cloned()
@@ -202,6 +255,10 @@ pub async fn fetch_video_snapshot(
         .cloned()
         .unwrap_or_else(|| serde_json::json!({}));
 
+    let channel_id = snippet
+        .get("channelId")
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string());
     let title = snippet
         .get("title")
         .and_then(|v| v.as_str())
@@ -233,7 +290,8 @@ pub async fn fetch_video_snapshot(
         .and_then(|v| v.as_str())
         .map(|v| v.to_string());
 
-    Ok(VideoSnapshot {
+    VideoSnapshot {
+        channel_id,
         title,
         description,
         category_id,
@@ -241,13 +299,23 @@ pub async fn fetch_video_snapshot(
         privacy_status,
         publish_at,
         thumbnail_url: best_thumbnail_url(&snippet),
-    })
+    }
 }
 
 pub async fn update_video_title(
     access_token: &str,
     video_id: &str,
     new_title: &str,
+) -> Result<(), YoutubeVideoError> {
+    update_video_title_with_base_url(access_token, YOUTUBE_DATA_API_BASE_URL, video_id, new_title)
+        .await
+}
+
+async fn update_video_title_with_base_url(
+    access_token: &str,
+    base_url: &str,
+    video_id: &str,
+    new_title: &str,
 ) -> Result<(), YoutubeVideoError> {
     let new_title = new_title.trim();
     if new_title.is_empty() {
@@ -257,34 +325,32 @@ pub async fn update_video_title(
         });
     }
 
-    let snap = fetch_video_snapshot(access_token, video_id).await?;
-    let Some(category_id) = snap.category_id.clone() else {
-        return Err(YoutubeVideoError {
-            status: None,
-            message: "missing categoryId for video snippet update".to_string(),
+    update_video_from_fresh_snapshot(access_token, base_url, video_id, "snippet", |snap| {
+        let Some(category_id) = snap.category_id.clone() else {
+            return Err(YoutubeVideoError {
+                status: None,
+                message: "missing categoryId for video snippet update".to_string(),
+            });
+        };
+
+        let mut snippet = serde_json::json!({
+          "title": new_title,
+          "description": snap.description,
+          "categoryId": category_id,
         });
-    };
-
-    let mut snippet = serde_json::json!({
-      "title": new_title,
-      "description": snap.description,
-      "categoryId": category_id,
-    });
-    if let Some(tags) = snap.tags {
-        snippet
-            .as_object_mut()
-            .unwrap()
-            .insert("tags".to_string(), serde_json::json!(tags));
-    }
-
-    let body = serde_json::json!({
-      "id": video_id,
-      "snippet": snippet,
-    });
+        if let Some(tags) = snap.tags.as_ref() {
+            snippet
+                .as_object_mut()
+                .unwrap()
+                .insert("tags".to_string(), serde_json::json!(tags));
+        }
 
-    let url = "https://youtube.googleapis.com/youtube/v3/videos?part=snippet";
-    let _ = put_json(access_token, url, &body).await?;
-    Ok(())
+        Ok(serde_json::json!({
+          "id": video_id,
+          "snippet": snippet,
+        }))
+    })
+    .await
 }
 
 pub async fn update_video_publish_at(
@@ -300,33 +366,102 @@ pub async fn update_video_publish_at(
         });
     }
 
-    let snap = fetch_video_snapshot(access_token, video_id).await?;
-    let Some(privacy_status) = snap.privacy_status else {
+    update_video_from_fresh_snapshot(
+        access_token,
+        YOUTUBE_DATA_API_BASE_URL,
+        video_id,
+        "status",
+        |snap| {
+            let Some(privacy_status) = snap.privacy_status.clone() else {
+                return Err(YoutubeVideoError {
+                    status: None,
+                    message: "missing privacyStatus for video status update".to_string(),
+                });
+            };
+
+            if privacy_status != "private" {
+                return Err(YoutubeVideoError {
+                    status: Some(400),
+                    message: format!(
+                        "publish_time experiments only support scheduled videos (privacyStatus=private), got {privacy_status}"
+                    ),
+                });
+            }
+
+            Ok(serde_json::json!({
+              "id": video_id,
+              "status": {
+                "privacyStatus": privacy_status,
+                "publishAt": new_publish_at_rfc3339,
+              }
+            }))
+        },
+    )
+    .await
+}
+
+/// The `videos.update?part=snippet` body that puts title, description, tags and category back
+/// to `snapshot`. `categoryId` is mandatory for snippet updates, so a snapshot without it cannot
+/// be restored.
+pub fn snapshot_restore_body(
+    video_id: &str,
+    snapshot: &VideoSnapshot,
+) -> Result<Value, YoutubeVideoError> {
+    let Some(category_id) = snapshot
+        .category_id
+        .as_deref()
+        .filter(|v| !v.trim().is_empty())
+    else {
         return Err(YoutubeVideoError {
             status: None,
-            message: "missing privacyStatus for video status update".to_string(),
+            message: "snapshot is missing categoryId".to_string(),
         });
     };
-
-    if privacy_status != "private" {
+    if snapshot.title.trim().is_empty() {
         return Err(YoutubeVideoError {
-      status: Some(400),
-      message: format!(
-        "publish_time experiments only support scheduled videos (privacyStatus=private), got {privacy_status}"
-      ),
-    });
+            status: None,
+            message: "snapshot is missing title".to_string(),
+        });
     }
 
-    let body = serde_json::json!({
+    Ok(serde_json::json!({
       "id": video_id,
-      "status": {
-        "privacyStatus": privacy_status,
-        "publishAt": new_publish_at_rfc3339,
+      "snippet": {
+        "title": snapshot.title,
+        "description": snapshot.description,
+        "categoryId": category_id,
+        // An empty list clears tags added after the snapshot was taken.
+        "tags": snapshot.tags.clone().unwrap_or_default(),
       }
-    });
+    }))
+}
+
+/// `(api_method, units)` spent by [`restore_video_snapshot`].
+pub fn snapshot_restore_quota_units(restores_thumbnail: bool) -> Vec<(&'static str, i64)> {
+    let mut out = vec![("videos.update", VIDEOS_UPDATE_QUOTA_UNITS)];
+    if restores_thumbnail {
+        out.push(("thumbnails.set", THUMBNAILS_SET_QUOTA_UNITS));
+    }
+    out
+}
+
+/// Restores every captured snippet field, not just the field an experiment changed, so edits
+/// made elsewhere during the experiment are undone too. The thumbnail is only re-uploaded when
+/// the experiment changed it, from the `original_thumbnail` bytes captured before the change:
+/// the snapshot's `i.ytimg.com` URL serves whatever the thumbnail is now.
+pub async fn restore_video_snapshot(
+    access_token: &str,
+    video_id: &str,
+    snapshot: &VideoSnapshot,
+    original_thumbnail: Option<&DecodedThumbnail>,
+) -> Result<(), YoutubeVideoError> {
+    let body = snapshot_restore_body(video_id, snapshot)?;
+    let url = format!("{YOUTUBE_DATA_API_BASE_URL}/videos?part=snippet");
+    let _ = put_json(access_token, &url, &body, None).await?;
 
-    let url = "https://youtube.googleapis.com/youtube/v3/videos?part=status";
-    let _ = put_json(access_token, url, &body).await?;
+    if let Some(thumbnail) = original_thumbnail {
+        set_video_thumbnail_from_bytes(access_token, video_id, thumbnail).await?;
+    }
     Ok(())
 }
 
@@ -424,35 +559,14 @@ async fn download_image_bytes(
         });
     }
 
-    let connector = hyper_rustls::HttpsConnectorBuilder::new()
-        .with_native_roots()
-        .map_err(|e| YoutubeVideoError {
-            status: None,
-            message: e.to_string(),
-        })?
-        .https_or_http()
-        .enable_http1()
-        .build();
-
-    let client = hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
-        .build(connector);
-
-    let req = Request::builder()
-        .method(Method::GET)
-        .uri(url)
+    let client = http_client_no_redirects().map_err(request_error)?;
+    let resp = client
+        .get(url)
         .header(ACCEPT, "image/*")
-        .body(Empty::<Bytes>::new())
-        .map_err(|e| YoutubeVideoError {
-            status: None,
-            message: e.to_string(),
-        })?;
-
-    let resp = client.request(req).await.map_err(|e| YoutubeVideoError {
-        status: None,
-        message: e.to_string(),
-    })?;
+        .send()
+        .await
+        .map_err(request_error)?;
 
-    let status = resp.status();
     let content_type = resp
         .headers()
         .get(CONTENT_TYPE)
@@ -460,23 +574,7 @@ async fn download_image_bytes(
         .map(|v| v.to_string())
         .unwrap_or_else(|| "image/jpeg".to_string());
 
-    let body_bytes = resp
-        .into_body()
-        .collect()
-        .await
-        .map_err(|e| YoutubeVideoError {
-            status: Some(status.as_u16()),
-            message: e.to_string(),
-        })?
-        .to_bytes();
-
-    if status != StatusCode::OK {
-        let msg = String::from_utf8_lossy(&body_bytes).to_string();
-        return Err(YoutubeVideoError {
-            status: Some(status.as_u16()),
-            message: msg,
-        });
-    }
+    let body_bytes = ok_body(resp).await?;
 
     if body_bytes.len() > max_bytes {
         return Err(YoutubeVideoError {
@@ -495,60 +593,158 @@ pub async fn set_video_thumbnail_from_url(
 ) -> Result<(), YoutubeVideoError> {
     const MAX_THUMBNAIL_BYTES: usize = 5 * 1024 * 1024;
     let (bytes, content_type) = download_image_bytes(thumbnail_url, MAX_THUMBNAIL_BYTES).await?;
+    upload_thumbnail(access_token, video_id, bytes, &content_type).await
+}
 
-    let connector = hyper_rustls::HttpsConnectorBuilder::new()
-        .with_native_roots()
-        .map_err(|e| YoutubeVideoError {
-            status: None,
-            message: e.to_string(),
-        })?
-        .https_or_http()
-        .enable_http1()
-        .build();
+/// YouTube rejects custom thumbnails over 2MB.
+pub const MAX_UPLOADED_THUMBNAIL_BYTES: usize = 2 * 1024 * 1024;
 
-    let client = hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
-        .build(connector);
+/// A validated thumbnail image decoded from a variant payload's `thumbnail_base64`.
+#[derive(Debug, Clone)]
+pub struct DecodedThumbnail {
+    pub bytes: Bytes,
+    pub content_type: &'static str,
+    /// Hex SHA-256 of the image; stored in the variant payload instead of the raw bytes.
+    pub sha256: String,
+}
 
-    let url = format!(
-    "https://youtube.googleapis.com/upload/youtube/v3/thumbnails/set?videoId={}&uploadType=media",
-    video_id
-  );
-
-    let req = Request::builder()
-        .method(Method::POST)
-        .uri(url)
-        .header(AUTHORIZATION, format!("Bearer {}", access_token))
-        .header(CONTENT_TYPE, content_type)
-        .body(Full::new(bytes))
-        .map_err(|e| YoutubeVideoError {
-            status: None,
-            message: e.to_string(),
-        })?;
+impl DecodedThumbnail {
+    /// What the variant payload keeps once the image was uploaded.
+    pub fn payload_reference(&self) -> Value {
+        serde_json::json!({
+          "sha256": self.sha256,
+          "content_type": self.content_type,
+          "bytes": self.bytes.len(),
+        })
+    }
+}
 
-    let resp = client.request(req).await.map_err(|e| YoutubeVideoError {
-        status: None,
-        message: e.to_string(),
-    })?;
+fn sniff_image_content_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+        Some("image/png")
+    } else {
+        None
+    }
+}
 
-    let status = resp.status();
-    let body_bytes = resp
-        .into_body()
-        .collect()
-        .await
-        .map_err(|e| YoutubeVideoError {
-            status: Some(status.as_u16()),
-            message: e.to_string(),
-        })?
-        .to_bytes();
+/// Accepts plain base64 or a `data:image/...;base64,` URL. The format is taken from the image's
+/// magic bytes, not the declared type, and only JPEG/PNG under 2MB pass.
+pub fn decode_thumbnail_base64(data: &str) -> Result<DecodedThumbnail, YoutubeVideoError> {
+    use base64::Engine as _;
 
-    if status != StatusCode::OK {
-        let msg = String::from_utf8_lossy(&body_bytes).to_string();
+    let bad_request = |message: String| YoutubeVideoError {
+        status: Some(400),
+        message,
+    };
+
+    let trimmed = data.trim();
+    let encoded = match trimmed.strip_prefix("data:") {
+        Some(rest) => match rest.split_once(',') {
+            Some((meta, payload)) if meta.ends_with(";base64") => payload,
+            _ => return Err(bad_request("thumbnail data URL must be base64".to_string())),
+        },
+        None => trimmed,
+    };
+    let encoded: String = encoded.chars().filter(|c| !c.is_whitespace()).collect();
+    if encoded.is_empty() {
+        return Err(bad_request("thumbnail_base64 is empty".to_string()));
+    }
+    // Reject before decoding so an oversized payload never gets buffered twice.
+    if encoded.len() / 4 * 3 > MAX_UPLOADED_THUMBNAIL_BYTES + 3 {
         return Err(YoutubeVideoError {
-            status: Some(status.as_u16()),
-            message: msg,
+            status: Some(413),
+            message: format!("thumbnail must be under {MAX_UPLOADED_THUMBNAIL_BYTES} bytes"),
+        });
+    }
+
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded.as_bytes())
+        .map_err(|e| bad_request(format!("invalid thumbnail_base64: {e}")))?;
+    decoded_thumbnail(Bytes::from(bytes))
+}
+
+/// Validates raw image bytes as an uploadable thumbnail: JPEG/PNG (by magic bytes) under 2MB.
+pub fn decoded_thumbnail(bytes: Bytes) -> Result<DecodedThumbnail, YoutubeVideoError> {
+    use sha2::Digest as _;
+
+    let bad_request = |message: String| YoutubeVideoError {
+        status: Some(400),
+        message,
+    };
+
+    if bytes.len() > MAX_UPLOADED_THUMBNAIL_BYTES {
+        return Err(YoutubeVideoError {
+            status: Some(413),
+            message: format!(
+                "thumbnail too large ({} bytes; max {MAX_UPLOADED_THUMBNAIL_BYTES})",
+                bytes.len()
+            ),
         });
     }
+    let Some(content_type) = sniff_image_content_type(&bytes) else {
+        return Err(bad_request(
+            "thumbnail must be a JPEG or PNG image".to_string(),
+        ));
+    };
+
+    let sha256 = sha2::Sha256::digest(&bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+    Ok(DecodedThumbnail {
+        bytes,
+        content_type,
+        sha256,
+    })
+}
+
+/// Downloads the video's current thumbnail so it can be re-uploaded on rollback. Taken before an
+/// experiment changes the thumbnail, while the CDN URL still serves the original.
+pub async fn download_video_thumbnail(
+    thumbnail_url: &str,
+) -> Result<DecodedThumbnail, YoutubeVideoError> {
+    let (bytes, _) = download_image_bytes(thumbnail_url, MAX_UPLOADED_THUMBNAIL_BYTES).await?;
+    decoded_thumbnail(bytes)
+}
+
+pub async fn set_video_thumbnail_from_bytes(
+    access_token: &str,
+    video_id: &str,
+    thumbnail: &DecodedThumbnail,
+) -> Result<(), YoutubeVideoError> {
+    upload_thumbnail(
+        access_token,
+        video_id,
+        thumbnail.bytes.clone(),
+        thumbnail.content_type,
+    )
+    .await
+}
+
+async fn upload_thumbnail(
+    access_token: &str,
+    video_id: &str,
+    bytes: Bytes,
+    content_type: &str,
+) -> Result<(), YoutubeVideoError> {
+    let url = format!(
+        "https://youtube.googleapis.com/upload/youtube/v3/thumbnails/set?videoId={}&uploadType=media",
+        video_id
+    );
+
+    let client = http_client_for_url(&url).map_err(request_error)?;
+    let resp = client
+        .post(&url)
+        .bearer_auth(access_token)
+        .header(CONTENT_TYPE, content_type)
+        .body(bytes)
+        .send()
+        .await
+        .map_err(request_error)?;
 
+    ok_body(resp).await?;
     Ok(())
 }
 
@@ -596,4 +792,226 @@ mod tests {
             Some(Ipv6Addr::from(bytes))
         }
     }
+
+    #[test]
+    fn stored_original_thumbnail_round_trips_as_uploadable_bytes() {
+        let jpeg = Bytes::from_static(&[0xFF, 0xD8, 0xFF, 0xE0, 0, 16, b'J', b'F', b'I', b'F']);
+        let original = decoded_thumbnail(jpeg.clone()).unwrap();
+        assert_eq!(original.content_type, "image/jpeg");
+
+        // What the baseline table stores and hands back to the rollback.
+        let reloaded = decoded_thumbnail(Bytes::from(original.bytes.to_vec())).unwrap();
+        assert_eq!(reloaded.bytes, jpeg);
+        assert_eq!(reloaded.sha256, original.sha256);
+
+        assert_eq!(
+            decoded_thumbnail(Bytes::from_static(b"<html>"))
+                .unwrap_err()
+                .status,
+            Some(400)
+        );
+        let oversized = Bytes::from(vec![0xFF; MAX_UPLOADED_THUMBNAIL_BYTES + 1]);
+        assert_eq!(decoded_thumbnail(oversized).unwrap_err().status, Some(413));
+    }
+
+    #[test]
+    fn thumbnail_base64_is_decoded_and_validated() {
+        use base64::Engine as _;
+        let encode = |bytes: &[u8]| base64::engine::general_purpose::STANDARD.encode(bytes);
+
+        let png = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 13];
+        let decoded = decode_thumbnail_base64(&encode(&png)).unwrap();
+        assert_eq!(decoded.content_type, "image/png");
+        assert_eq!(decoded.bytes.as_ref(), &png);
+        assert_eq!(decoded.sha256.len(), 64);
+        let reference = decoded.payload_reference();
+        assert_eq!(reference["bytes"], 12);
+        assert!(reference.get("data").is_none());
+
+        // Data URLs are accepted; the sniffed type wins over the declared one.
+        let jpeg = [0xFF, 0xD8, 0xFF, 0xE0, 0, 16];
+        let data_url = format!("data:image/png;base64,{}", encode(&jpeg));
+        assert_eq!(
+            decode_thumbnail_base64(&data_url).unwrap().content_type,
+            "image/jpeg"
+        );
+
+        let gif = encode(b"GIF89a....");
+        assert_eq!(decode_thumbnail_base64(&gif).unwrap_err().status, Some(400));
+        assert_eq!(
+            decode_thumbnail_base64("not base64!").unwrap_err().status,
+            Some(400)
+        );
+        assert!(decode_thumbnail_base64("data:image/png,raw").is_err());
+        assert!(decode_thumbnail_base64("   ").is_err());
+
+        let mut big = vec![0u8; MAX_UPLOADED_THUMBNAIL_BYTES + 1];
+        big[..3].copy_from_slice(&[0xFF, 0xD8, 0xFF]);
+        assert_eq!(
+            decode_thumbnail_base64(&encode(&big)).unwrap_err().status,
+            Some(413)
+        );
+    }
+
+    #[test]
+    fn full_snapshot_round_trips_through_capture_and_restore() {
+        let item = serde_json::json!({
+          "id": "v1",
+          "snippet": {
+            "channelId": "UC1",
+            "title": "Original title",
+            "description": "Links below",
+            "categoryId": "22",
+            "tags": ["cooking", "pasta"],
+            "thumbnails": {
+              "high": {"url": "https://i.ytimg.com/vi/v1/hqdefault.jpg"},
+              "maxres": {"url": "https://i.ytimg.com/vi/v1/maxresdefault.jpg"}
+            }
+          },
+          "status": {"privacyStatus": "public"}
+        });
+
+        let captured = video_snapshot_from_item(&item);
+        assert_eq!(captured.description, "Links below");
+        assert_eq!(captured.category_id.as_deref(), Some("22"));
+        assert_eq!(
+            captured.thumbnail_url.as_deref(),
+            Some("https://i.ytimg.com/vi/v1/maxresdefault.jpg")
+        );
+
+        // What the experiment stores and later reads back.
+        let stored = serde_json::to_string(&captured).unwrap();
+        let restored: VideoSnapshot = serde_json::from_str(&stored).unwrap();
+        assert_eq!(restored, captured);
+
+        let body = snapshot_restore_body("v1", &restored).unwrap();
+        assert_eq!(body["id"], "v1");
+        assert_eq!(body["snippet"]["title"], "Original title");
+        assert_eq!(body["snippet"]["description"], "Links below");
+        assert_eq!(body["snippet"]["categoryId"], "22");
+        assert_eq!(
+            body["snippet"]["tags"],
+            serde_json::json!(["cooking", "pasta"])
+        );
+        // Only a thumbnail experiment's rollback re-uploads the (stored original) thumbnail;
+        // title and publish_at rollbacks leave it alone.
+        assert_eq!(
+            snapshot_restore_quota_units(true),
+            vec![
+                ("videos.update", VIDEOS_UPDATE_QUOTA_UNITS),
+                ("thumbnails.set", THUMBNAILS_SET_QUOTA_UNITS)
+            ]
+        );
+        assert_eq!(
+            snapshot_restore_quota_units(false),
+            vec![("videos.update", VIDEOS_UPDATE_QUOTA_UNITS)]
+        );
+
+        let no_category = VideoSnapshot {
+            category_id: None,
+            ..restored
+        };
+        assert!(snapshot_restore_body("v1", &no_category).is_err());
+    }
+
+    /// Serves `videos.list` with a new ETag per read and answers the first `conflicts`
+    /// `videos.update` calls with 412. Returns the `If-Match` values the updates carried.
+    async fn serve_videos(
+        listener: tokio::net::TcpListener,
+        conflicts: usize,
+        if_matches: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    ) {
+        use http_body_util::Full;
+        use hyper::server::conn::http1;
+        use hyper::service::service_fn;
+        use hyper::{Method, Response};
+        use hyper_util::rt::TokioIo;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let reads = Arc::new(AtomicUsize::new(0));
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let reads = reads.clone();
+            let if_matches = if_matches.clone();
+            tokio::spawn(async move {
+                let _ = http1::Builder::new()
+                    .serve_connection(
+                        TokioIo::new(stream),
+                        service_fn(move |req: hyper::Request<hyper::body::Incoming>| {
+                            let reads = reads.clone();
+                            let if_matches = if_matches.clone();
+                            async move {
+                                let (status, body) = if req.method() == Method::PUT {
+                                    let mut seen = if_matches.lock().unwrap();
+                                    seen.push(
+                                        req.headers()
+                                            .get(IF_MATCH)
+                                            .and_then(|v| v.to_str().ok())
+                                            .unwrap_or("")
+                                            .to_string(),
+                                    );
+                                    if seen.len() <= conflicts {
+                                        (412, r#"{"error":{"code":412}}"#.to_string())
+                                    } else {
+                                        (200, "{}".to_string())
+                                    }
+                                } else {
+                                    let n = reads.fetch_add(1, Ordering::SeqCst) + 1;
+                                    let item = serde_json::json!({"items": [{
+                                      "etag": format!("etag-{n}"),
+                                      "snippet": {"title": "Old", "description": "d", "categoryId": "22"},
+                                      "status": {"privacyStatus": "private"},
+                                    }]});
+                                    (200, item.to_string())
+                                };
+                                Ok::<_, hyper::Error>(
+                                    Response::builder()
+                                        .status(status)
+                                        .header("content-type", "application/json")
+                                        .body(Full::new(Bytes::from(body)))
+                                        .unwrap(),
+                                )
+                            }
+                        }),
+                    )
+                    .await;
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn title_update_rereads_and_retries_once_after_a_conflict() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let if_matches = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let server = tokio::spawn(serve_videos(listener, 1, if_matches.clone()));
+
+        update_video_title_with_base_url("token", &base_url, "vid1", "New title")
+            .await
+            .unwrap();
+        // The retry carries the ETag of the second read, not the stale one.
+        assert_eq!(*if_matches.lock().unwrap(), vec!["etag-1", "etag-2"]);
+
+        server.abort();
+        let _ = server.await;
+    }
+
+    #[tokio::test]
+    async fn repeated_conflicts_surface_a_stale_snapshot_error() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let if_matches = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let server = tokio::spawn(serve_videos(listener, usize::MAX, if_matches.clone()));
+
+        let err = update_video_title_with_base_url("token", &base_url, "vid1", "New title")
+            .await
+            .unwrap_err();
+        assert!(err.is_stale_snapshot(), "{err}");
+        assert_eq!(err.status, Some(412));
+        assert_eq!(if_matches.lock().unwrap().len(), 2);
+
+        server.abort();
+        let _ = server.await;
+    }
 }
@@ -1,6 +1,7 @@
 use chrono::{Duration, NaiveDate, SecondsFormat, Utc};
 use vercel_runtime::Error;
 
+use crate::channel_totals::API_CHANNEL_TOTAL_VIDEO_ID;
 use crate::db::upsert_video_daily_reach_metrics;
 use crate::providers::youtube_reporting::{
     download_report_file, ensure_job_for_report_type_channel, list_reports_channel,
@@ -60,6 +61,22 @@ fn parse_ctr_field(raw: &str) -> Option<f64> {
     Some(out)
 }
 
+/// Weighted-CTR contribution of one row as `(ctr * impressions, impressions)`: the stored CTR
+/// when present, else `clicks / impressions` when the report carried clicks.
+pub fn ctr_weight(
+    impressions: i64,
+    impressions_ctr: Option<f64>,
+    clicks: Option<i64>,
+) -> Option<(f64, i64)> {
+    match (impressions_ctr, clicks) {
+        (Some(ctr), _) => Some((ctr * impressions as f64, impressions)),
+        (None, Some(clicks)) if impressions > 0 => {
+            Some((clicks.min(impressions) as f64, impressions))
+        }
+        _ => None,
+    }
+}
+
 fn maybe_gunzip_bytes(input: &[u8]) -> Result<Vec<u8>, std::io::Error> {
     use std::io::Read;
 
@@ -148,7 +165,7 @@ pub async fn ingest_channel_reach_basic_a1(
     let mut rows_upserted = 0usize;
 
     // Aggregate channel totals while parsing per-video rows.
-    let mut totals_by_dt: std::collections::BTreeMap<NaiveDate, (i64, i64, f64)> =
+    let mut totals_by_dt: std::collections::BTreeMap<NaiveDate, (i64, i64, f64, Option<i64>)> =
         std::collections::BTreeMap::new();
 
     for (dt, (_create_time, download_url)) in by_dt.iter() {
@@ -199,6 +216,12 @@ pub async fn ingest_channel_reach_basic_a1(
             "impr",
             "impression",
         ]);
+        let clicks_idx = find_idx(&[
+            "video_thumbnail_impressions_clicks",
+            "thumbnail_impressions_clicks",
+            "thumbnail_clicks",
+            "clicks",
+        ]);
         let ctr_idx = find_idx(&[
             "video_thumbnail_impressions_ctr",
             "video_thumbnail_impressions_click_through_rate",
@@ -234,10 +257,10 @@ pub async fn ingest_channel_reach_basic_a1(
 
             let video_id = match video_idx.and_then(|i| rec.get(i)) {
                 Some(v) => v.trim().to_string(),
-                None => "__CHANNEL_TOTAL__".to_string(),
+                None => API_CHANNEL_TOTAL_VIDEO_ID.to_string(),
             };
             let video_id = if video_id.is_empty() {
-                "__CHANNEL_TOTAL__".to_string()
+                API_CHANNEL_TOTAL_VIDEO_ID.to_string()
             } else {
                 video_id
             };
@@ -255,6 +278,10 @@ pub async fn ingest_channel_reach_basic_a1(
                 .max(0);
 
             let impressions_ctr = ctr_idx.and_then(|i| rec.get(i)).and_then(parse_ctr_field);
+            let clicks = clicks_idx
+                .and_then(|i| rec.get(i))
+                .and_then(parse_i64_field)
+                .map(|v| v.max(0));
 
             upsert_video_daily_reach_metrics(
                 pool,
@@ -264,27 +291,28 @@ pub async fn ingest_channel_reach_basic_a1(
                 &video_id,
                 impressions,
                 impressions_ctr,
+                clicks,
                 views,
             )
             .await?;
             rows_upserted += 1;
 
+            let entry = totals_by_dt.entry(row_dt).or_insert((0, 0, 0.0, None));
+            entry.0 += views;
             if impressions > 0 {
-                let entry = totals_by_dt.entry(row_dt).or_insert((0, 0, 0.0));
-                entry.0 += views;
                 entry.1 += impressions;
                 if let Some(ctr) = impressions_ctr {
                     entry.2 += ctr * (impressions as f64);
                 }
-            } else {
-                let entry = totals_by_dt.entry(row_dt).or_insert((0, 0, 0.0));
-                entry.0 += views;
+            }
+            if let Some(clicks) = clicks {
+                entry.3 = Some(entry.3.unwrap_or(0) + clicks);
             }
         }
     }
 
     // Ensure we have a channel-total row per day for faster dashboard queries.
-    for (dt, (views_sum, impr_sum, ctr_weighted_sum)) in totals_by_dt.into_iter() {
+    for (dt, (views_sum, impr_sum, ctr_weighted_sum, clicks_sum)) in totals_by_dt.into_iter() {
         let blended_ctr = if impr_sum > 0 {
             Some(ctr_weighted_sum / (impr_sum as f64))
         } else {
@@ -295,9 +323,10 @@ pub async fn ingest_channel_reach_basic_a1(
             tenant_id,
             channel_id,
             dt,
-            "__CHANNEL_TOTAL__",
+            API_CHANNEL_TOTAL_VIDEO_ID,
             impr_sum,
             blended_ctr,
+            clicks_sum,
             views_sum,
         )
         .await?;
@@ -334,4 +363,12 @@ mod tests {
         assert!(parse_ctr_field("").is_none());
         assert!(parse_ctr_field("180").is_none());
     }
+
+    #[test]
+    fn ctr_weight_falls_back_to_clicks_without_stored_ctr() {
+        assert_eq!(ctr_weight(1000, Some(0.05), Some(80)), Some((50.0, 1000)));
+        assert_eq!(ctr_weight(1000, None, Some(40)), Some((40.0, 1000)));
+        assert_eq!(ctr_weight(0, None, Some(3)), None);
+        assert_eq!(ctr_weight(1000, None, None), None);
+    }
 }
@@ -1,4 +1,5 @@
 use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::hmac;
 use ring::rand::{SecureRandom, SystemRandom};
 use sha2::Digest;
 use std::collections::HashMap;
@@ -174,6 +175,89 @@ pub fn fingerprint_secret(plaintext: &str) -> String {
     format!("{:x}", sha2::Sha256::digest(plaintext.as_bytes()))
 }
 
+pub const DEFAULT_OAUTH_STATE_TTL_SECS: i64 = 600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OAuthStateError {
+    Malformed,
+    BadSignature,
+    Expired,
+}
+
+impl OAuthStateError {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OAuthStateError::Malformed => "malformed",
+            OAuthStateError::BadSignature => "bad_signature",
+            OAuthStateError::Expired => "expired",
+        }
+    }
+}
+
+/// Key for signing OAuth `state`: `OAUTH_STATE_SECRET`, falling back to `RUST_INTERNAL_TOKEN`.
+pub fn oauth_state_secret() -> Option<String> {
+    ["OAUTH_STATE_SECRET", "RUST_INTERNAL_TOKEN"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .map(|v| v.trim().to_string())
+        .find(|v| !v.is_empty())
+}
+
+pub fn oauth_state_ttl_secs() -> i64 {
+    std::env::var("OAUTH_STATE_TTL_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_OAUTH_STATE_TTL_SECS)
+}
+
+fn oauth_state_tag(secret: &str, tenant_id: &str, state: &str, issued_at: i64) -> hmac::Tag {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hmac::sign(
+        &key,
+        format!("{tenant_id}\n{state}\n{issued_at}").as_bytes(),
+    )
+}
+
+/// Wraps a caller-supplied state as `<state>.<issued_at>.<hmac>`, binding it to the tenant.
+pub fn sign_oauth_state(secret: &str, tenant_id: &str, state: &str, issued_at: i64) -> String {
+    let tag = oauth_state_tag(secret, tenant_id, state, issued_at);
+    format!("{state}.{issued_at}.{}", hex_encode(tag.as_ref()))
+}
+
+/// Verifies a state produced by [`sign_oauth_state`] and returns the original caller state.
+pub fn verify_oauth_state(
+    secret: &str,
+    tenant_id: &str,
+    signed: &str,
+    now: i64,
+    ttl_secs: i64,
+) -> Result<String, OAuthStateError> {
+    let mut parts = signed.rsplitn(3, '.');
+    let (Some(tag_hex), Some(issued_at), Some(state)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(OAuthStateError::Malformed);
+    };
+    let issued_at = issued_at
+        .parse::<i64>()
+        .map_err(|_| OAuthStateError::Malformed)?;
+    let tag = hex_decode(tag_hex).map_err(|_| OAuthStateError::Malformed)?;
+
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hmac::verify(
+        &key,
+        format!("{tenant_id}\n{state}\n{issued_at}").as_bytes(),
+        &tag,
+    )
+    .map_err(|_| OAuthStateError::BadSignature)?;
+
+    if now < issued_at || now - issued_at > ttl_secs {
+        return Err(OAuthStateError::Expired);
+    }
+
+    Ok(state.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -200,4 +284,40 @@ mod tests {
             decrypt_secret(&encrypted.ciphertext, "v2").expect_err("version mismatch must fail");
         assert!(err.to_string().contains("unsupported key version"));
     }
+
+    #[test]
+    fn signed_oauth_state_verifies_and_returns_original_state() {
+        let signed = sign_oauth_state("k", "tenant-1", "csrf.abc", 1_000);
+        assert_eq!(
+            verify_oauth_state("k", "tenant-1", &signed, 1_300, 600),
+            Ok("csrf.abc".to_string())
+        );
+    }
+
+    #[test]
+    fn tampered_oauth_state_is_rejected() {
+        let signed = sign_oauth_state("k", "tenant-1", "csrf", 1_000);
+        let tampered = signed.replacen("csrf", "evil", 1);
+        assert_eq!(
+            verify_oauth_state("k", "tenant-1", &tampered, 1_000, 600),
+            Err(OAuthStateError::BadSignature)
+        );
+        assert_eq!(
+            verify_oauth_state("k", "tenant-2", &signed, 1_000, 600),
+            Err(OAuthStateError::BadSignature)
+        );
+        assert_eq!(
+            verify_oauth_state("k", "tenant-1", "csrf", 1_000, 600),
+            Err(OAuthStateError::Malformed)
+        );
+    }
+
+    #[test]
+    fn expired_oauth_state_is_rejected() {
+        let signed = sign_oauth_state("k", "tenant-1", "csrf", 1_000);
+        assert_eq!(
+            verify_oauth_state("k", "tenant-1", &signed, 1_601, 600),
+            Err(OAuthStateError::Expired)
+        );
+    }
 }
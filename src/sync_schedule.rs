@@ -0,0 +1,153 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Timelike, Utc, Weekday};
+use serde::Deserialize;
+
+/// How often a channel's scheduled dispatch enqueues a sync.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncFrequency {
+    Daily,
+    Weekly(Weekday),
+}
+
+/// A channel's `policy_params.sync_schedule`, e.g.
+/// `{"frequency": "weekly", "weekday": "mon", "hour": 6, "utc_offset_minutes": -300}`.
+/// Channels without one keep syncing on every dispatch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncSchedule {
+    pub frequency: SyncFrequency,
+    /// Local hour (0-23) the sync runs in; `None` accepts any dispatch on a due day. The
+    /// dispatch cron has to run at least hourly for an hour preference to be met.
+    pub hour: Option<u32>,
+    /// The creator's offset from UTC, used for the local day, weekday and hour.
+    pub utc_offset_minutes: i32,
+}
+
+#[derive(Deserialize)]
+struct PolicyParamsJson {
+    #[serde(default)]
+    sync_schedule: Option<SyncScheduleJson>,
+}
+
+#[derive(Deserialize)]
+struct SyncScheduleJson {
+    #[serde(default)]
+    frequency: Option<String>,
+    #[serde(default)]
+    weekday: Option<String>,
+    #[serde(default)]
+    hour: Option<u32>,
+    #[serde(default)]
+    utc_offset_minutes: Option<i32>,
+}
+
+/// UTC-14:00 through UTC+14:00 covers every real zone.
+const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+
+impl SyncSchedule {
+    /// `None` when the params carry no (valid) `sync_schedule`, which means "every dispatch".
+    pub fn from_policy_params_json(raw: &str) -> Option<Self> {
+        let parsed: PolicyParamsJson = serde_json::from_str(raw).ok()?;
+        let raw = parsed.sync_schedule?;
+        let frequency = match raw.frequency.as_deref().map(str::trim) {
+            None | Some("") | Some("daily") => SyncFrequency::Daily,
+            Some("weekly") => SyncFrequency::Weekly(
+                match raw
+                    .weekday
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                {
+                    Some(day) => day.parse::<Weekday>().ok()?,
+                    None => Weekday::Mon,
+                },
+            ),
+            Some(_) => return None,
+        };
+        Some(SyncSchedule {
+            frequency,
+            hour: raw.hour.filter(|h| *h < 24),
+            utc_offset_minutes: raw
+                .utc_offset_minutes
+                .unwrap_or(0)
+                .clamp(-MAX_UTC_OFFSET_MINUTES, MAX_UTC_OFFSET_MINUTES),
+        })
+    }
+
+    fn local_time(&self, now: DateTime<Utc>) -> chrono::NaiveDateTime {
+        now.naive_utc() + Duration::minutes(self.utc_offset_minutes as i64)
+    }
+
+    /// Whether a dispatch at `now` falls in the channel's window.
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        let local = self.local_time(now);
+        let day_ok = match self.frequency {
+            SyncFrequency::Daily => true,
+            SyncFrequency::Weekly(day) => local.weekday() == day,
+        };
+        day_ok && self.hour.is_none_or(|h| local.hour() == h)
+    }
+
+    /// The creator's local calendar day at `now`, used as the run date instead of the UTC day.
+    pub fn local_run_for_dt(&self, now: DateTime<Utc>) -> NaiveDate {
+        self.local_time(now).date()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn a_channel_outside_its_window_is_not_due() {
+        let weekly = SyncSchedule::from_policy_params_json(
+            r#"{"sync_schedule": {"frequency": "weekly", "weekday": "mon", "hour": 6, "utc_offset_minutes": -300}}"#,
+        )
+        .unwrap();
+        assert_eq!(weekly.frequency, SyncFrequency::Weekly(Weekday::Mon));
+
+        // Monday 06:30 in UTC-5 is 11:30 UTC.
+        let due = Utc.with_ymd_and_hms(2026, 3, 2, 11, 30, 0).unwrap();
+        assert!(weekly.is_due(due));
+        // Same Monday at 06:30 UTC is 01:30 local: wrong hour.
+        assert!(!weekly.is_due(Utc.with_ymd_and_hms(2026, 3, 2, 6, 30, 0).unwrap()));
+        // Tuesday, right hour.
+        assert!(!weekly.is_due(Utc.with_ymd_and_hms(2026, 3, 3, 11, 30, 0).unwrap()));
+
+        // 02:00 UTC Tuesday is still Monday evening locally.
+        let late = Utc.with_ymd_and_hms(2026, 3, 3, 2, 0, 0).unwrap();
+        assert_eq!(
+            weekly.local_run_for_dt(late),
+            NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()
+        );
+
+        let daily =
+            SyncSchedule::from_policy_params_json(r#"{"sync_schedule": {"hour": 6}}"#).unwrap();
+        assert!(daily.is_due(Utc.with_ymd_and_hms(2026, 3, 3, 6, 0, 0).unwrap()));
+        assert!(!daily.is_due(Utc.with_ymd_and_hms(2026, 3, 3, 7, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn missing_or_invalid_schedules_keep_the_default() {
+        assert_eq!(SyncSchedule::from_policy_params_json("{}"), None);
+        assert_eq!(
+            SyncSchedule::from_policy_params_json(r#"{"min_days_with_data": 3}"#),
+            None
+        );
+        assert_eq!(
+            SyncSchedule::from_policy_params_json(r#"{"sync_schedule": {"frequency": "hourly"}}"#),
+            None
+        );
+        assert_eq!(
+            SyncSchedule::from_policy_params_json(
+                r#"{"sync_schedule": {"frequency": "weekly", "weekday": "someday"}}"#
+            ),
+            None
+        );
+        let clamped = SyncSchedule::from_policy_params_json(
+            r#"{"sync_schedule": {"hour": 30, "utc_offset_minutes": 100000}}"#,
+        )
+        .unwrap();
+        assert_eq!(clamped.hour, None);
+        assert_eq!(clamped.utc_offset_minutes, MAX_UTC_OFFSET_MINUTES);
+    }
+}
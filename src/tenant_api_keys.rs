@@ -0,0 +1,134 @@
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::Deserialize;
+use sha2::Digest;
+use vercel_runtime::Error;
+
+/// Tenant keys are told apart from `RUST_INTERNAL_TOKEN` by this prefix.
+pub const TENANT_API_KEY_PREFIX: &str = "gfk_";
+
+const TENANT_API_KEY_RANDOM_BYTES: usize = 24;
+
+/// A new `gfk_<48 hex>` key. Only its `hash_tenant_api_key` digest is stored; the key itself is
+/// shown to the caller once.
+pub fn generate_tenant_api_key() -> Result<String, Error> {
+    let mut bytes = [0u8; TENANT_API_KEY_RANDOM_BYTES];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| Box::new(std::io::Error::other("failed to generate api key")) as Error)?;
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    Ok(format!("{TENANT_API_KEY_PREFIX}{hex}"))
+}
+
+pub fn is_tenant_api_key(token: &str) -> bool {
+    token.starts_with(TENANT_API_KEY_PREFIX) && token.len() > TENANT_API_KEY_PREFIX.len()
+}
+
+/// Hex SHA-256 of the key. Keys are high-entropy random strings, so an unsalted digest is enough
+/// to make a leaked table useless without the keys.
+pub fn hash_tenant_api_key(key: &str) -> String {
+    format!("{:x}", sha2::Sha256::digest(key.trim().as_bytes()))
+}
+
+/// Prefix plus the first 8 key characters, stored to help a tenant tell its keys apart.
+pub fn tenant_api_key_display_prefix(key: &str) -> String {
+    key.chars().take(TENANT_API_KEY_PREFIX.len() + 8).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TenantKeyAccess {
+    Allowed,
+    /// No active key with that hash (never issued, or revoked): 401.
+    UnknownKey,
+    /// The key belongs to another tenant, or the request names no tenant: 403.
+    TenantMismatch,
+}
+
+/// `key_tenant_id` is the tenant the presented key resolved to; `requested_tenant_id` is the
+/// `tenant_id` the request acts for. A tenant key never authorizes tenant-less requests.
+pub fn tenant_key_access(
+    key_tenant_id: Option<&str>,
+    requested_tenant_id: Option<&str>,
+) -> TenantKeyAccess {
+    let Some(key_tenant_id) = key_tenant_id else {
+        return TenantKeyAccess::UnknownKey;
+    };
+    match requested_tenant_id.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(requested) if requested == key_tenant_id.trim() => TenantKeyAccess::Allowed,
+        _ => TenantKeyAccess::TenantMismatch,
+    }
+}
+
+#[derive(Deserialize)]
+struct TenantScopedBody {
+    #[serde(default)]
+    tenant_id: Option<String>,
+}
+
+/// The `tenant_id` a request acts for, from its query string and/or JSON body. `None` when
+/// neither names one, or when they disagree, so a key cannot pass with one tenant in the query
+/// while the handler reads another from the body.
+pub fn requested_tenant_id(query_tenant_id: Option<&str>, body: &[u8]) -> Option<String> {
+    let clean = |v: &str| Some(v.trim().to_string()).filter(|v| !v.is_empty());
+    let from_query = query_tenant_id.and_then(clean);
+    let from_body = serde_json::from_slice::<TenantScopedBody>(body)
+        .ok()
+        .and_then(|b| b.tenant_id)
+        .and_then(|v| clean(&v));
+    match (from_query, from_body) {
+        (Some(q), Some(b)) if q != b => None,
+        (q, b) => q.or(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tenant_key_works_for_its_tenant_and_is_rejected_for_others() {
+        let key = generate_tenant_api_key().unwrap();
+        assert!(is_tenant_api_key(&key));
+        assert_eq!(key.len(), TENANT_API_KEY_PREFIX.len() + 48);
+        assert_ne!(generate_tenant_api_key().unwrap(), key);
+
+        let hash = hash_tenant_api_key(&key);
+        assert_eq!(hash.len(), 64);
+        assert!(!hash.contains(&key[TENANT_API_KEY_PREFIX.len()..]));
+        assert_eq!(hash_tenant_api_key(&format!(" {key} ")), hash);
+        assert!(tenant_api_key_display_prefix(&key).starts_with("gfk_"));
+
+        // The stored hash resolved to tenant t1.
+        assert_eq!(
+            tenant_key_access(Some("t1"), Some("t1")),
+            TenantKeyAccess::Allowed
+        );
+        assert_eq!(
+            tenant_key_access(Some("t1"), Some("t2")),
+            TenantKeyAccess::TenantMismatch
+        );
+        assert_eq!(
+            tenant_key_access(Some("t1"), None),
+            TenantKeyAccess::TenantMismatch
+        );
+        assert_eq!(
+            tenant_key_access(None, Some("t1")),
+            TenantKeyAccess::UnknownKey
+        );
+
+        assert_eq!(requested_tenant_id(Some("t1"), b""), Some("t1".to_string()));
+        assert_eq!(
+            requested_tenant_id(None, br#"{"tenant_id": "t1"}"#),
+            Some("t1".to_string())
+        );
+        assert_eq!(
+            tenant_key_access(
+                Some("t1"),
+                requested_tenant_id(Some("t1"), br#"{"tenant_id": "t2"}"#).as_deref()
+            ),
+            TenantKeyAccess::TenantMismatch
+        );
+
+        assert!(!is_tenant_api_key("internal-secret"));
+        assert!(!is_tenant_api_key(TENANT_API_KEY_PREFIX));
+    }
+}
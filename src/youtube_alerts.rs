@@ -1,14 +1,22 @@
-use chrono::{Duration, NaiveDate, Utc};
+use chrono::{DateTime, Duration, NaiveDate, SecondsFormat, Utc};
 use sqlx::MySqlPool;
 use vercel_runtime::Error;
 
+use crate::channel_totals::{
+    channel_total_filter, channel_total_sums, video_rows_filter, CSV_CHANNEL_TOTAL_VIDEO_ID,
+};
 use crate::db::{
-    fetch_or_seed_youtube_oauth_app_config, fetch_youtube_connection_tokens,
-    update_youtube_connection_tokens,
+    fetch_alert_templates, fetch_experiment_failure_counts, fetch_policy_params_json,
+    fetch_youtube_connection_tokens, fetch_youtube_monetized, set_youtube_monetized,
+};
+use crate::guardrails::{
+    diverging_source_days, evaluate_experiment_failures, evaluate_guardrails,
+    evaluate_source_divergence, experiment_failure_rate_threshold, ExperimentFailureCounts,
+    GuardrailAlert, GuardrailInput, SourceDayComparison, WindowAgg, EXPERIMENT_FAILURE_WINDOW_DAYS,
+    SOURCE_DIVERGENCE_THRESHOLD_PCT,
 };
-use crate::guardrails::{evaluate_guardrails, GuardrailAlert, GuardrailInput, WindowAgg};
-use crate::providers::youtube::{refresh_tokens, youtube_oauth_client_from_config};
 use crate::providers::youtube_analytics::fetch_top_videos_by_revenue_for_channel;
+use crate::youtube_auth::{refresh_youtube_tokens_if_expired, YoutubeTokenError};
 
 fn truncate_string(value: &str, max_chars: usize) -> String {
     if max_chars == 0 {
@@ -28,6 +36,67 @@ fn round2(v: f64) -> f64 {
     (v * 100.0).round() / 100.0
 }
 
+/// Renders a tenant-supplied alert template by replacing `{name}` placeholders with the
+/// matching value. Unknown placeholders are left as-is so typos stay visible in the message.
+pub fn render_alert_template(template: &str, vars: &[(&str, String)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let Some(close) = after.find('}') else {
+            out.push_str(&rest[open..]);
+            return out;
+        };
+        let name = &after[..close];
+        match vars.iter().find(|(k, _)| *k == name) {
+            Some((_, value)) => out.push_str(value),
+            None => out.push_str(&rest[open..open + close + 2]),
+        }
+        rest = &after[close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn format_pct(ratio: f64) -> String {
+    format!("{:.0}%", ratio * 100.0)
+}
+
+/// Monetization is inferred over a long window so a few zero-revenue days don't flip it.
+const MONETIZATION_WINDOW_DAYS: i64 = 90;
+const MONETIZATION_MIN_VIEWS: i64 = 50_000;
+
+/// Alerts that only make sense when the channel earns revenue.
+const REVENUE_ALERT_KEYS: [&str; 4] = [
+    "rpm_drop_7d",
+    "rev_concentration_top1_7d",
+    "rev_volatility_7d",
+    "revenue_missing_7d",
+];
+
+/// Any revenue marks the channel monetized; substantial views with no revenue marks it not
+/// monetized. Otherwise the previously stored state is kept.
+pub fn infer_monetized(revenue_usd: f64, views: i64, previous: Option<bool>) -> Option<bool> {
+    if revenue_usd.is_finite() && revenue_usd > 0.01 {
+        Some(true)
+    } else if views >= MONETIZATION_MIN_VIEWS {
+        Some(false)
+    } else {
+        previous
+    }
+}
+
+/// Drops revenue-based alerts for channels known to be not monetized; views/CTR and data
+/// freshness alerts are kept. Returns true when suppression applied.
+fn suppress_revenue_alerts(desired: &mut Vec<GuardrailAlert>, monetized: Option<bool>) -> bool {
+    if monetized != Some(false) {
+        return false;
+    }
+    desired.retain(|alert| !REVENUE_ALERT_KEYS.contains(&alert.key));
+    true
+}
+
 async fn best_effort_youtube_access_token(
     pool: &MySqlPool,
     tenant_id: &str,
@@ -38,32 +107,13 @@ async fn best_effort_youtube_access_token(
         None => return Ok(None),
     };
 
-    let needs_refresh = tokens
-        .expires_at
-        .map(|dt| dt <= chrono::Utc::now())
-        .unwrap_or(false);
-
-    if needs_refresh {
-        if let Some(refresh) = tokens.refresh_token.clone() {
-            if let Some(app) = fetch_or_seed_youtube_oauth_app_config(pool, tenant_id).await? {
-                if let Some(client_secret) = app
-                    .client_secret
-                    .as_deref()
-                    .map(str::trim)
-                    .filter(|v| !v.is_empty())
-                {
-                    let (client, _redirect) = youtube_oauth_client_from_config(
-                        &app.client_id,
-                        client_secret,
-                        &app.redirect_uri,
-                    )?;
-                    let refreshed = refresh_tokens(&client, &refresh).await?;
-                    update_youtube_connection_tokens(pool, tenant_id, channel_id, &refreshed)
-                        .await?;
-                    tokens.access_token = refreshed.access_token;
-                }
-            }
-        }
+    // Best-effort: without a usable OAuth app config we keep the stored token.
+    match refresh_youtube_tokens_if_expired(pool, tenant_id, channel_id, &mut tokens).await {
+        Ok(_)
+        | Err(YoutubeTokenError::MissingAppConfig)
+        | Err(YoutubeTokenError::MissingClientSecret) => {}
+        Err(YoutubeTokenError::Revoked) => return Ok(None),
+        Err(err) => return Err(Box::new(err)),
     }
 
     Ok(Some(tokens.access_token))
@@ -138,6 +188,34 @@ async fn auto_resolve_alert(
     Ok(())
 }
 
+pub async fn raise_reauth_required_alert(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    detail: &str,
+) -> Result<(), Error> {
+    let details_json = serde_json::json!({ "error": truncate_string(detail, 500) }).to_string();
+    upsert_alert(
+        pool,
+        tenant_id,
+        channel_id,
+        "reauth_required",
+        "reauth_required",
+        "critical",
+        "YouTube access was revoked. Reconnect the channel to resume syncing.",
+        Some(&details_json),
+    )
+    .await
+}
+
+pub async fn resolve_reauth_required_alert(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+) -> Result<(), Error> {
+    auto_resolve_alert(pool, tenant_id, channel_id, "reauth_required").await
+}
+
 pub async fn evaluate_youtube_alerts(
     pool: &MySqlPool,
     tenant_id: &str,
@@ -153,7 +231,7 @@ pub async fn evaluate_youtube_alerts(
         start_dt: NaiveDate,
         end_dt: NaiveDate,
     ) -> Result<(f64, i64, &'static str), Error> {
-        let (rows_n, rev, views) = sqlx::query_as::<_, (i64, f64, i64)>(
+        let (rows_n, rev, views) = sqlx::query_as::<_, (i64, f64, i64)>(&format!(
             r#"
           SELECT CAST(COUNT(*) AS SIGNED) AS rows_n,
                  CAST(COALESCE(SUM(estimated_revenue_usd), 0) AS DOUBLE) AS revenue_usd,
@@ -162,9 +240,10 @@ pub async fn evaluate_youtube_alerts(
           WHERE tenant_id = ?
             AND channel_id = ?
             AND dt BETWEEN ? AND ?
-            AND video_id IN ('__CHANNEL_TOTAL__','csv_channel_total');
+            AND {channel_totals};
         "#,
-        )
+            channel_totals = channel_total_filter()
+        ))
         .bind(tenant_id)
         .bind(channel_id)
         .bind(start_dt)
@@ -177,7 +256,7 @@ pub async fn evaluate_youtube_alerts(
             return Ok((rev, views, "channel_total"));
         }
 
-        let (rev, views) = sqlx::query_as::<_, (f64, i64)>(
+        let (rev, views) = sqlx::query_as::<_, (f64, i64)>(&format!(
             r#"
           SELECT CAST(COALESCE(SUM(estimated_revenue_usd), 0) AS DOUBLE) AS revenue_usd,
                  CAST(COALESCE(SUM(views), 0) AS SIGNED) AS views
@@ -185,9 +264,10 @@ pub async fn evaluate_youtube_alerts(
           WHERE tenant_id = ?
             AND channel_id = ?
             AND dt BETWEEN ? AND ?
-            AND video_id NOT IN ('__CHANNEL_TOTAL__','csv_channel_total');
+            AND {video_rows};
         "#,
-        )
+            video_rows = video_rows_filter()
+        ))
         .bind(tenant_id)
         .bind(channel_id)
         .bind(start_dt)
@@ -199,6 +279,8 @@ pub async fn evaluate_youtube_alerts(
         Ok((rev, views, "video_sum"))
     }
 
+    // Both windows are exactly seven days, so the baseline already covers the same weekdays as
+    // the current window; weekly seasonality cancels out without a separate week-aligned mode.
     let today = Utc::now().date_naive();
     let current_start = today - Duration::days(7);
     let current_end = today - Duration::days(1);
@@ -210,6 +292,20 @@ pub async fn evaluate_youtube_alerts(
     let (base_rev, base_views, base_source) =
         sum_rev_views_window(pool, tenant_id, channel_id, baseline_start, baseline_end).await?;
 
+    let (long_rev, long_views, _long_source) = sum_rev_views_window(
+        pool,
+        tenant_id,
+        channel_id,
+        today - Duration::days(MONETIZATION_WINDOW_DAYS),
+        current_end,
+    )
+    .await?;
+    let stored_monetized = fetch_youtube_monetized(pool, tenant_id, channel_id).await?;
+    let monetized = infer_monetized(long_rev, long_views, stored_monetized);
+    if let Some(value) = monetized.filter(|v| Some(*v) != stored_monetized) {
+        set_youtube_monetized(pool, tenant_id, channel_id, value).await?;
+    }
+
     let total_rev_7d = if cur_rev.is_finite() {
         Some(cur_rev)
     } else {
@@ -217,19 +313,20 @@ pub async fn evaluate_youtube_alerts(
     };
 
     let mut top_video_7d = if total_rev_7d.unwrap_or(0.0) >= 20.0 {
-        sqlx::query_as::<_, (String, f64)>(
+        sqlx::query_as::<_, (String, f64)>(&format!(
             r#"
         SELECT video_id, CAST(SUM(estimated_revenue_usd) AS DOUBLE) AS rev
         FROM video_daily_metrics
         WHERE tenant_id = ?
           AND channel_id = ?
           AND dt BETWEEN ? AND ?
-          AND video_id NOT IN ('__CHANNEL_TOTAL__','csv_channel_total')
+          AND {video_rows}
         GROUP BY video_id
         ORDER BY rev DESC
         LIMIT 1;
       "#,
-        )
+            video_rows = video_rows_filter()
+        ))
         .bind(tenant_id)
         .bind(channel_id)
         .bind(current_start)
@@ -274,23 +371,24 @@ pub async fn evaluate_youtube_alerts(
     };
     let can_compute_concentration = top1_concentration_7d.is_some() && total_rev_7d.is_some();
 
-    let mut daily_totals = sqlx::query_as::<_, (NaiveDate, f64)>(
+    let mut daily_totals = sqlx::query_as::<_, (NaiveDate, f64)>(&format!(
         r#"
       SELECT dt,
              CAST(COALESCE(
-               SUM(CASE WHEN video_id='csv_channel_total' THEN estimated_revenue_usd END),
-               SUM(CASE WHEN video_id='__CHANNEL_TOTAL__' THEN estimated_revenue_usd END),
+               {sums_0},
                0
              ) AS DOUBLE) AS rev
       FROM video_daily_metrics
       WHERE tenant_id = ?
         AND channel_id = ?
         AND dt BETWEEN ? AND ?
-        AND video_id IN ('__CHANNEL_TOTAL__','csv_channel_total')
+        AND {channel_totals}
       GROUP BY dt
       ORDER BY dt ASC;
     "#,
-    )
+        sums_0 = channel_total_sums("estimated_revenue_usd"),
+        channel_totals = channel_total_filter()
+    ))
     .bind(tenant_id)
     .bind(channel_id)
     .bind(current_start)
@@ -300,18 +398,19 @@ pub async fn evaluate_youtube_alerts(
     .map_err(|e| -> Error { Box::new(e) })?;
 
     if daily_totals.is_empty() {
-        daily_totals = sqlx::query_as::<_, (NaiveDate, f64)>(
+        daily_totals = sqlx::query_as::<_, (NaiveDate, f64)>(&format!(
             r#"
         SELECT dt, CAST(SUM(estimated_revenue_usd) AS DOUBLE) AS rev
         FROM video_daily_metrics
         WHERE tenant_id = ?
           AND channel_id = ?
           AND dt BETWEEN ? AND ?
-          AND video_id NOT IN ('__CHANNEL_TOTAL__','csv_channel_total')
+          AND {video_rows}
         GROUP BY dt
         ORDER BY dt ASC;
       "#,
-        )
+            video_rows = video_rows_filter()
+        ))
         .bind(tenant_id)
         .bind(channel_id)
         .bind(current_start)
@@ -547,10 +646,52 @@ pub async fn evaluate_youtube_alerts(
     );
     }
 
+    let revenue_alerts_suppressed = suppress_revenue_alerts(&mut desired, monetized);
+
     let desired_keys: HashSet<&str> = desired.iter().map(|a| a.key).collect();
 
+    let templates: HashMap<String, String> = if desired.is_empty() {
+        HashMap::new()
+    } else {
+        fetch_alert_templates(pool, tenant_id, channel_id)
+            .await?
+            .into_iter()
+            .collect()
+    };
+    let window = format!("{current_start}..{current_end}");
+
     for alert in desired.iter() {
         let details_json = details_by_key.get(alert.key).map(|v| v.as_str());
+        let message = match templates.get(alert.key) {
+            Some(template) => {
+                let pct = match alert.key {
+                    "rpm_drop_7d" => Some(rpm_drop_pct),
+                    "rev_concentration_top1_7d" => top1_concentration_7d,
+                    _ => None,
+                };
+                let vars = [
+                    ("message", alert.message.clone()),
+                    ("window", window.clone()),
+                    ("start_dt", current_start.to_string()),
+                    ("end_dt", current_end.to_string()),
+                    ("pct", pct.map(format_pct).unwrap_or_default()),
+                    ("rpm", format!("{:.2}", cur_rpm)),
+                    ("baseline_rpm", format!("{:.2}", base_rpm)),
+                    ("revenue_usd", format!("{:.2}", cur_rev)),
+                    ("views", cur_views.to_string()),
+                    (
+                        "max_metric_dt",
+                        max_dt.map(|d| d.to_string()).unwrap_or_default(),
+                    ),
+                    (
+                        "age_days",
+                        stale_age_days.map(|d| d.to_string()).unwrap_or_default(),
+                    ),
+                ];
+                render_alert_template(template, &vars)
+            }
+            None => alert.message.clone(),
+        };
         upsert_alert(
             pool,
             tenant_id,
@@ -558,7 +699,7 @@ pub async fn evaluate_youtube_alerts(
             alert.key,
             alert.kind,
             alert.severity,
-            &alert.message,
+            &message,
             details_json,
         )
         .await?;
@@ -599,11 +740,438 @@ pub async fn evaluate_youtube_alerts(
         auto_resolve_alert(pool, tenant_id, channel_id, "revenue_missing_7d").await?;
     }
 
+    if revenue_alerts_suppressed {
+        for key in REVENUE_ALERT_KEYS {
+            auto_resolve_alert(pool, tenant_id, channel_id, key).await?;
+        }
+    }
+
     Ok(())
 }
 
+/// Cross-checks CSV channel totals against API per-video rows on overlapping dates and raises
+/// `source_divergence` when they disagree (typically a CSV exported for the wrong channel).
+pub async fn evaluate_source_divergence_alert(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+) -> Result<(), Error> {
+    let today = Utc::now().date_naive();
+    let start_dt = today - Duration::days(28);
+    let end_dt = today - Duration::days(1);
+
+    let rows = sqlx::query_as::<_, (NaiveDate, f64, i64, f64, i64)>(
+        &format!(
+            r#"
+      SELECT dt,
+             CAST(COALESCE(SUM(CASE WHEN video_id = '{CSV_CHANNEL_TOTAL_VIDEO_ID}' THEN estimated_revenue_usd END), 0) AS DOUBLE) AS csv_revenue_usd,
+             CAST(COALESCE(SUM(CASE WHEN video_id = '{CSV_CHANNEL_TOTAL_VIDEO_ID}' THEN views END), 0) AS SIGNED) AS csv_views,
+             CAST(COALESCE(SUM(CASE WHEN {video_rows} THEN estimated_revenue_usd END), 0) AS DOUBLE) AS api_revenue_usd,
+             CAST(COALESCE(SUM(CASE WHEN {video_rows} THEN views END), 0) AS SIGNED) AS api_views
+      FROM video_daily_metrics
+      WHERE tenant_id = ?
+        AND channel_id = ?
+        AND dt BETWEEN ? AND ?
+      GROUP BY dt
+      HAVING SUM(CASE WHEN video_id = '{CSV_CHANNEL_TOTAL_VIDEO_ID}' THEN 1 ELSE 0 END) > 0
+         AND SUM(CASE WHEN {video_rows} THEN 1 ELSE 0 END) > 0
+      ORDER BY dt ASC;
+    "#,
+            video_rows = video_rows_filter(),
+        ),
+    )
+    .bind(tenant_id)
+    .bind(channel_id)
+    .bind(start_dt)
+    .bind(end_dt)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| -> Error { Box::new(e) })?;
+
+    let days: Vec<SourceDayComparison> = rows
+        .into_iter()
+        .map(
+            |(dt, csv_rev, csv_views, api_rev, api_views)| SourceDayComparison {
+                dt,
+                csv: WindowAgg {
+                    revenue_usd: csv_rev,
+                    views: csv_views,
+                },
+                api: WindowAgg {
+                    revenue_usd: api_rev,
+                    views: api_views,
+                },
+            },
+        )
+        .collect();
+
+    let Some(alert) = evaluate_source_divergence(&days, SOURCE_DIVERGENCE_THRESHOLD_PCT) else {
+        // Only clear a previous alert when there is overlap to compare against.
+        if !days.is_empty() {
+            auto_resolve_alert(pool, tenant_id, channel_id, "source_divergence").await?;
+        }
+        return Ok(());
+    };
+
+    let rpm = |agg: &WindowAgg| {
+        if agg.views > 0 {
+            round2((agg.revenue_usd / (agg.views as f64)) * 1000.0)
+        } else {
+            0.0
+        }
+    };
+    let diffs: Vec<serde_json::Value> = diverging_source_days(&days, SOURCE_DIVERGENCE_THRESHOLD_PCT)
+        .iter()
+        .map(|d| {
+            serde_json::json!({
+              "dt": d.dt.to_string(),
+              "csv": { "revenue_usd": round2(d.csv.revenue_usd), "views": d.csv.views, "rpm": rpm(&d.csv) },
+              "api": { "revenue_usd": round2(d.api.revenue_usd), "views": d.api.views, "rpm": rpm(&d.api) },
+              "revenue_diff_usd": round2(d.csv.revenue_usd - d.api.revenue_usd),
+              "revenue_diff_pct": d.revenue_diff_pct().map(|v| (v * 10000.0).round() / 10000.0),
+            })
+        })
+        .collect();
+
+    let details_json = serde_json::json!({
+      "window": { "start_dt": start_dt.to_string(), "end_dt": end_dt.to_string() },
+      "overlapping_days": days.len(),
+      "threshold_pct": SOURCE_DIVERGENCE_THRESHOLD_PCT,
+      "diffs": diffs,
+    })
+    .to_string();
+
+    upsert_alert(
+        pool,
+        tenant_id,
+        channel_id,
+        alert.key,
+        alert.kind,
+        alert.severity,
+        &alert.message,
+        Some(&details_json),
+    )
+    .await
+}
+
+/// Raises `experiment_failures` when many recent experiments failed to apply or roll back, which
+/// individual `apply_failed` responses don't surface; resolves it once the rate recovers.
+pub async fn evaluate_experiment_failure_alert(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+) -> Result<(), Error> {
+    let since = Utc::now() - Duration::days(EXPERIMENT_FAILURE_WINDOW_DAYS);
+    let (total, apply_failed, rollback_failed) =
+        fetch_experiment_failure_counts(pool, tenant_id, channel_id, since).await?;
+    let counts = ExperimentFailureCounts {
+        total,
+        apply_failed,
+        rollback_failed,
+    };
+    let params_json = fetch_policy_params_json(pool, tenant_id, channel_id, "active").await?;
+    let threshold = experiment_failure_rate_threshold(params_json.as_deref());
+
+    let Some(alert) = evaluate_experiment_failures(&counts, threshold) else {
+        return auto_resolve_alert(pool, tenant_id, channel_id, "experiment_failures").await;
+    };
+
+    let details_json = serde_json::json!({
+      "window_days": EXPERIMENT_FAILURE_WINDOW_DAYS,
+      "experiments": counts.total,
+      "apply_failed": counts.apply_failed,
+      "rollback_failed": counts.rollback_failed,
+      "failure_rate": counts.failure_rate().map(round2),
+      "threshold": threshold,
+    })
+    .to_string();
+
+    upsert_alert(
+        pool,
+        tenant_id,
+        channel_id,
+        alert.key,
+        alert.kind,
+        alert.severity,
+        &alert.message,
+        Some(&details_json),
+    )
+    .await
+}
+
+pub const DEFAULT_ALERT_DIGEST_WINDOW_MINUTES: i64 = 15;
+pub const MAX_ALERT_DIGEST_WINDOW_MINUTES: i64 = 24 * 60;
+
+/// How a tenant's alerts turn into notifications: one per alert, or one grouped digest per
+/// channel for alerts detected within the digest window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertNotificationMode {
+    Immediate,
+    Digest,
+}
+
+impl AlertNotificationMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "immediate" => Some(Self::Immediate),
+            "digest" => Some(Self::Digest),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Immediate => "immediate",
+            Self::Digest => "digest",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlertNotificationSettings {
+    pub mode: AlertNotificationMode,
+    pub digest_window_minutes: i64,
+}
+
+impl Default for AlertNotificationSettings {
+    fn default() -> Self {
+        Self {
+            mode: AlertNotificationMode::Immediate,
+            digest_window_minutes: DEFAULT_ALERT_DIGEST_WINDOW_MINUTES,
+        }
+    }
+}
+
+impl AlertNotificationSettings {
+    /// Stored values, with unknown modes and out-of-range windows falling back to the defaults.
+    pub fn from_stored(mode: Option<&str>, digest_window_minutes: Option<i64>) -> Self {
+        let defaults = Self::default();
+        Self {
+            mode: mode
+                .and_then(AlertNotificationMode::parse)
+                .unwrap_or(defaults.mode),
+            digest_window_minutes: digest_window_minutes
+                .map(|v| v.clamp(1, MAX_ALERT_DIGEST_WINDOW_MINUTES))
+                .unwrap_or(defaults.digest_window_minutes),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotifiableAlert {
+    pub channel_id: String,
+    pub alert_key: String,
+    pub kind: String,
+    pub severity: String,
+    pub message: String,
+    pub detected_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertNotification {
+    pub channel_id: String,
+    pub digest: bool,
+    pub window_start: DateTime<Utc>,
+    pub window_end: DateTime<Utc>,
+    /// A digest is sent once its window has closed, so late alerts of the same incident join it.
+    pub ready: bool,
+    pub alerts: Vec<NotifiableAlert>,
+}
+
+impl AlertNotification {
+    pub fn to_json(&self) -> serde_json::Value {
+        let ts = |dt: &DateTime<Utc>| dt.to_rfc3339_opts(SecondsFormat::Millis, true);
+        serde_json::json!({
+          "channel_id": self.channel_id,
+          "digest": self.digest,
+          "window_start": ts(&self.window_start),
+          "window_end": ts(&self.window_end),
+          "ready": self.ready,
+          "alert_count": self.alerts.len(),
+          "alerts": self.alerts.iter().map(|a| serde_json::json!({
+            "alert_key": a.alert_key,
+            "kind": a.kind,
+            "severity": a.severity,
+            "message": a.message,
+            "detected_at": ts(&a.detected_at),
+          })).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Groups alerts into notification payloads. In digest mode a channel's alerts are batched from
+/// the first one detected until `digest_window_minutes` later; a later alert opens the next digest.
+pub fn build_alert_notifications(
+    settings: AlertNotificationSettings,
+    alerts: &[NotifiableAlert],
+    now: DateTime<Utc>,
+) -> Vec<AlertNotification> {
+    let mut sorted: Vec<&NotifiableAlert> = alerts.iter().collect();
+    sorted.sort_by(|a, b| {
+        a.channel_id
+            .cmp(&b.channel_id)
+            .then(a.detected_at.cmp(&b.detected_at))
+            .then(a.alert_key.cmp(&b.alert_key))
+    });
+
+    if settings.mode == AlertNotificationMode::Immediate {
+        return sorted
+            .into_iter()
+            .map(|alert| AlertNotification {
+                channel_id: alert.channel_id.clone(),
+                digest: false,
+                window_start: alert.detected_at,
+                window_end: alert.detected_at,
+                ready: true,
+                alerts: vec![alert.clone()],
+            })
+            .collect();
+    }
+
+    let window = Duration::minutes(settings.digest_window_minutes);
+    let mut out: Vec<AlertNotification> = Vec::new();
+    for alert in sorted {
+        match out.last_mut() {
+            Some(current)
+                if current.channel_id == alert.channel_id
+                    && alert.detected_at < current.window_end =>
+            {
+                current.alerts.push(alert.clone());
+            }
+            _ => out.push(AlertNotification {
+                channel_id: alert.channel_id.clone(),
+                digest: true,
+                window_start: alert.detected_at,
+                window_end: alert.detected_at + window,
+                ready: false,
+                alerts: vec![alert.clone()],
+            }),
+        }
+    }
+    for notification in &mut out {
+        notification.ready = notification.window_end <= now;
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
+    use super::{
+        build_alert_notifications, infer_monetized, render_alert_template, suppress_revenue_alerts,
+        AlertNotificationMode, AlertNotificationSettings, NotifiableAlert,
+    };
+    use crate::guardrails::GuardrailAlert;
+
+    #[test]
+    fn alert_template_renders_placeholders_with_computed_values() {
+        let vars = [
+            ("pct", "23%".to_string()),
+            ("window", "2026-01-01..2026-01-07".to_string()),
+            ("rpm", "3.10".to_string()),
+        ];
+        assert_eq!(
+            render_alert_template("RPM cayó {pct} ({window}), ahora {rpm}.", &vars),
+            "RPM cayó 23% (2026-01-01..2026-01-07), ahora 3.10."
+        );
+        assert_eq!(
+            render_alert_template("{unknown} stays, {pct} and a dangling {brace", &vars),
+            "{unknown} stays, 23% and a dangling {brace"
+        );
+    }
+
+    #[test]
+    fn revenue_alerts_are_suppressed_for_non_monetized_channels() {
+        let alert = |key| GuardrailAlert {
+            key,
+            kind: "test",
+            severity: "info",
+            message: String::new(),
+        };
+        let all = || {
+            vec![
+                alert("rpm_drop_7d"),
+                alert("revenue_missing_7d"),
+                alert("rev_volatility_7d"),
+                alert("metrics_stale"),
+            ]
+        };
+
+        let mut desired = all();
+        assert!(suppress_revenue_alerts(&mut desired, Some(false)));
+        let keys: Vec<&str> = desired.iter().map(|a| a.key).collect();
+        assert_eq!(keys, vec!["metrics_stale"]);
+
+        for monetized in [Some(true), None] {
+            let mut desired = all();
+            assert!(!suppress_revenue_alerts(&mut desired, monetized));
+            assert_eq!(desired.len(), 4);
+        }
+    }
+
+    #[test]
+    fn alerts_within_the_window_produce_one_digest() {
+        use chrono::TimeZone;
+        let at = |min: i64| {
+            chrono::Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap()
+                + chrono::Duration::minutes(min)
+        };
+        let alert = |channel: &str, key: &str, min: i64| NotifiableAlert {
+            channel_id: channel.to_string(),
+            alert_key: key.to_string(),
+            kind: "Data sync".to_string(),
+            severity: "warning".to_string(),
+            message: format!("{key} fired"),
+            detected_at: at(min),
+        };
+        // A reporting outage trips four alerts on c1 within minutes, then one more much later.
+        let alerts = vec![
+            alert("c1", "reach_reporting_pending", 0),
+            alert("c1", "metrics_stale", 3),
+            alert("c2", "rpm_drop_7d", 5),
+            alert("c1", "revenue_missing_7d", 9),
+            alert("c1", "analytics_silent_empty", 14),
+            alert("c1", "rpm_drop_7d", 40),
+        ];
+
+        let digest = AlertNotificationSettings::from_stored(Some("digest"), Some(15));
+        let out = build_alert_notifications(digest, &alerts, at(45));
+        assert_eq!(out.len(), 3);
+        let first = &out[0];
+        assert!(first.digest && first.ready);
+        assert_eq!(first.channel_id, "c1");
+        assert_eq!(first.alerts.len(), 4);
+        assert_eq!(first.window_end, at(15));
+        assert_eq!(first.to_json()["alert_count"], 4);
+        // The late c1 alert opens its own digest, still collecting at `now`.
+        assert_eq!(out[1].alerts.len(), 1);
+        assert!(!out[1].ready);
+        assert_eq!(out[2].channel_id, "c2");
+
+        let immediate = AlertNotificationSettings::from_stored(None, None);
+        assert_eq!(immediate.mode, AlertNotificationMode::Immediate);
+        let out = build_alert_notifications(immediate, &alerts, at(45));
+        assert_eq!(out.len(), alerts.len());
+        assert!(out
+            .iter()
+            .all(|n| !n.digest && n.ready && n.alerts.len() == 1));
+
+        let clamped = AlertNotificationSettings::from_stored(Some("hourly"), Some(100_000));
+        assert_eq!(clamped.mode, AlertNotificationMode::Immediate);
+        assert_eq!(
+            clamped.digest_window_minutes,
+            super::MAX_ALERT_DIGEST_WINDOW_MINUTES
+        );
+    }
+
+    #[test]
+    fn monetized_is_inferred_from_long_window_revenue_and_views() {
+        assert_eq!(infer_monetized(0.0, 120_000, None), Some(false));
+        assert_eq!(infer_monetized(0.0, 120_000, Some(true)), Some(false));
+        assert_eq!(infer_monetized(12.5, 120_000, Some(false)), Some(true));
+        assert_eq!(infer_monetized(0.0, 800, None), None);
+        assert_eq!(infer_monetized(0.0, 800, Some(false)), Some(false));
+    }
+
     #[test]
     fn upsert_alert_preserves_detected_at_for_open_alerts() {
         let src_youtube_alerts = include_str!("youtube_alerts.rs");
@@ -0,0 +1,384 @@
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
+use sqlx::MySqlPool;
+use vercel_runtime::Error;
+
+use crate::db::{
+    fetch_or_seed_youtube_oauth_app_config, fetch_youtube_connection_tokens,
+    set_youtube_connection_needs_reauth, update_youtube_connection_tokens, YoutubeConnectionTokens,
+};
+use crate::providers::youtube::{
+    is_refresh_revoked, refresh_tokens, youtube_oauth_client_from_config, YoutubeOAuthTokens,
+};
+use crate::youtube_alerts::raise_reauth_required_alert;
+
+#[derive(Debug)]
+pub enum YoutubeTokenError {
+    NotConnected,
+    MissingAppConfig,
+    MissingClientSecret,
+    NoRefreshToken,
+    /// Google rejected the refresh token (`invalid_grant`); the connection now needs reauth.
+    Revoked,
+    Other(Error),
+}
+
+impl std::fmt::Display for YoutubeTokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            YoutubeTokenError::NotConnected => write!(f, "missing youtube channel connection"),
+            YoutubeTokenError::MissingAppConfig => write!(f, "missing youtube oauth app config"),
+            YoutubeTokenError::MissingClientSecret => {
+                write!(f, "missing youtube oauth client_secret")
+            }
+            YoutubeTokenError::NoRefreshToken => write!(
+                f,
+                "youtube access token expired and no refresh token available"
+            ),
+            YoutubeTokenError::Revoked => write!(
+                f,
+                "youtube refresh token revoked; channel must be reconnected"
+            ),
+            YoutubeTokenError::Other(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for YoutubeTokenError {}
+
+impl From<Error> for YoutubeTokenError {
+    fn from(err: Error) -> Self {
+        YoutubeTokenError::Other(err)
+    }
+}
+
+/// True when a task failed because the connection needs reauth, so retrying cannot help.
+pub fn is_reauth_required(err: &Error) -> bool {
+    matches!(
+        err.downcast_ref::<YoutubeTokenError>(),
+        Some(YoutubeTokenError::Revoked)
+    )
+}
+
+pub fn token_expired(expires_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    expires_at.map(|dt| dt <= now).unwrap_or(false)
+}
+
+/// Google usually omits the refresh token on refresh responses; keep the one we already have.
+pub fn apply_refreshed_tokens(
+    tokens: &mut YoutubeConnectionTokens,
+    refreshed: &YoutubeOAuthTokens,
+    now: DateTime<Utc>,
+) {
+    tokens.access_token = refreshed.access_token.clone();
+    if let Some(refresh) = refreshed.refresh_token.clone() {
+        tokens.refresh_token = Some(refresh);
+    }
+    tokens.expires_at = refreshed
+        .expires_in_seconds
+        .map(|secs| now + chrono::Duration::seconds(secs as i64));
+}
+
+async fn refresh_with<R, Fut>(
+    tokens: &mut YoutubeConnectionTokens,
+    now: DateTime<Utc>,
+    refresh: R,
+) -> Result<(), YoutubeTokenError>
+where
+    R: FnOnce(String) -> Fut,
+    Fut: Future<Output = Result<YoutubeOAuthTokens, YoutubeTokenError>>,
+{
+    let Some(refresh_token) = tokens.refresh_token.clone() else {
+        return Err(YoutubeTokenError::NoRefreshToken);
+    };
+    let refreshed = refresh(refresh_token).await?;
+    apply_refreshed_tokens(tokens, &refreshed, now);
+    Ok(())
+}
+
+/// Proactive refresh: only when expired, and a missing refresh token is tolerated (the stored
+/// access token is returned as-is and the API call decides). Returns whether a refresh happened.
+async fn refresh_if_expired_with<R, Fut>(
+    tokens: &mut YoutubeConnectionTokens,
+    now: DateTime<Utc>,
+    refresh: R,
+) -> Result<bool, YoutubeTokenError>
+where
+    R: FnOnce(String) -> Fut,
+    Fut: Future<Output = Result<YoutubeOAuthTokens, YoutubeTokenError>>,
+{
+    if !token_expired(tokens.expires_at, now) {
+        return Ok(false);
+    }
+    match refresh_with(tokens, now, refresh).await {
+        Ok(()) => Ok(true),
+        Err(YoutubeTokenError::NoRefreshToken) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Runs `call` with the current access token; if it fails as unauthorized, refreshes once and
+/// retries. The inner result is the API call's own outcome.
+async fn call_with_retry<T, E, C, CFut, R, RFut>(
+    tokens: &mut YoutubeConnectionTokens,
+    now: DateTime<Utc>,
+    is_unauthorized: impl Fn(&E) -> bool,
+    refresh: R,
+    mut call: C,
+) -> Result<Result<T, E>, YoutubeTokenError>
+where
+    C: FnMut(String) -> CFut,
+    CFut: Future<Output = Result<T, E>>,
+    R: FnOnce(String) -> RFut,
+    RFut: Future<Output = Result<YoutubeOAuthTokens, YoutubeTokenError>>,
+{
+    match call(tokens.access_token.clone()).await {
+        Err(err) if is_unauthorized(&err) => {
+            refresh_with(tokens, now, refresh).await?;
+            Ok(call(tokens.access_token.clone()).await)
+        }
+        other => Ok(other),
+    }
+}
+
+/// On `invalid_grant`, runs `mark_revoked` (flag the connection + alert) and reports `Revoked`.
+async fn revoke_on_invalid_grant<T, M, MFut>(
+    result: Result<T, Error>,
+    mark_revoked: M,
+) -> Result<T, YoutubeTokenError>
+where
+    M: FnOnce(String) -> MFut,
+    MFut: Future<Output = Result<(), Error>>,
+{
+    match result {
+        Ok(v) => Ok(v),
+        Err(err) if is_refresh_revoked(&err) => {
+            mark_revoked(err.to_string()).await?;
+            Err(YoutubeTokenError::Revoked)
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+async fn refresh_and_persist(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    refresh_token: String,
+) -> Result<YoutubeOAuthTokens, YoutubeTokenError> {
+    let app = fetch_or_seed_youtube_oauth_app_config(pool, tenant_id)
+        .await?
+        .ok_or(YoutubeTokenError::MissingAppConfig)?;
+    let client_secret = app
+        .client_secret
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .ok_or(YoutubeTokenError::MissingClientSecret)?;
+
+    let (client, _redirect) =
+        youtube_oauth_client_from_config(&app.client_id, client_secret, &app.redirect_uri)?;
+    let refreshed = revoke_on_invalid_grant(
+        refresh_tokens(&client, &refresh_token).await,
+        |detail| async move {
+            set_youtube_connection_needs_reauth(pool, tenant_id, channel_id, true).await?;
+            raise_reauth_required_alert(pool, tenant_id, channel_id, &detail).await
+        },
+    )
+    .await?;
+    update_youtube_connection_tokens(pool, tenant_id, channel_id, &refreshed).await?;
+    Ok(refreshed)
+}
+
+pub async fn refresh_youtube_tokens_if_expired(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    tokens: &mut YoutubeConnectionTokens,
+) -> Result<bool, YoutubeTokenError> {
+    refresh_if_expired_with(tokens, Utc::now(), |refresh_token| {
+        refresh_and_persist(pool, tenant_id, channel_id, refresh_token)
+    })
+    .await
+}
+
+/// Loads the stored tokens for a connection and refreshes (and persists) them when expired.
+pub async fn ensure_fresh_youtube_tokens(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+) -> Result<YoutubeConnectionTokens, YoutubeTokenError> {
+    let mut tokens = fetch_youtube_connection_tokens(pool, tenant_id, channel_id)
+        .await?
+        .ok_or(YoutubeTokenError::NotConnected)?;
+    refresh_youtube_tokens_if_expired(pool, tenant_id, channel_id, &mut tokens).await?;
+    Ok(tokens)
+}
+
+/// Calls a YouTube API with `tokens`, refreshing and retrying once when it reports 401.
+pub async fn call_with_fresh_youtube_token<T, E, C, CFut>(
+    pool: &MySqlPool,
+    tenant_id: &str,
+    channel_id: &str,
+    tokens: &mut YoutubeConnectionTokens,
+    is_unauthorized: impl Fn(&E) -> bool,
+    call: C,
+) -> Result<Result<T, E>, YoutubeTokenError>
+where
+    C: FnMut(String) -> CFut,
+    CFut: Future<Output = Result<T, E>>,
+{
+    call_with_retry(
+        tokens,
+        Utc::now(),
+        is_unauthorized,
+        |refresh_token| refresh_and_persist(pool, tenant_id, channel_id, refresh_token),
+        call,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::youtube::YoutubeRefreshRevoked;
+    use std::cell::Cell;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-02-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn stored(refresh_token: Option<&str>, expires_in_secs: i64) -> YoutubeConnectionTokens {
+        YoutubeConnectionTokens {
+            access_token: "old_access".to_string(),
+            refresh_token: refresh_token.map(str::to_string),
+            expires_at: Some(now() + chrono::Duration::seconds(expires_in_secs)),
+        }
+    }
+
+    fn refreshed(access_token: &str) -> YoutubeOAuthTokens {
+        YoutubeOAuthTokens {
+            access_token: access_token.to_string(),
+            refresh_token: None,
+            token_type: "Bearer".to_string(),
+            scope: None,
+            expires_in_seconds: Some(3600),
+        }
+    }
+
+    #[tokio::test]
+    async fn expired_tokens_are_refreshed_and_keep_refresh_token() {
+        let mut tokens = stored(Some("refresh_1"), -60);
+        let seen = Cell::new(None::<String>);
+
+        let did_refresh = refresh_if_expired_with(&mut tokens, now(), |rt| {
+            seen.set(Some(rt));
+            async { Ok(refreshed("new_access")) }
+        })
+        .await
+        .unwrap();
+
+        assert!(did_refresh);
+        assert_eq!(seen.take().as_deref(), Some("refresh_1"));
+        assert_eq!(tokens.access_token, "new_access");
+        assert_eq!(tokens.refresh_token.as_deref(), Some("refresh_1"));
+        assert_eq!(
+            tokens.expires_at,
+            Some(now() + chrono::Duration::seconds(3600))
+        );
+
+        let mut valid = stored(Some("refresh_1"), 600);
+        let did_refresh = refresh_if_expired_with(&mut valid, now(), |_| async {
+            Ok::<_, YoutubeTokenError>(refreshed("unused"))
+        })
+        .await
+        .unwrap();
+        assert!(!did_refresh);
+        assert_eq!(valid.access_token, "old_access");
+    }
+
+    #[tokio::test]
+    async fn unauthorized_call_refreshes_once_and_retries() {
+        let mut tokens = stored(Some("refresh_1"), 600);
+        let calls = Cell::new(0);
+
+        let result = call_with_retry(
+            &mut tokens,
+            now(),
+            |status: &u16| *status == 401,
+            |_| async { Ok(refreshed("new_access")) },
+            |access_token| {
+                calls.set(calls.get() + 1);
+                async move {
+                    if access_token == "new_access" {
+                        Ok("payload")
+                    } else {
+                        Err(401_u16)
+                    }
+                }
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result, Ok("payload"));
+        assert_eq!(calls.get(), 2);
+        assert_eq!(tokens.access_token, "new_access");
+    }
+
+    #[tokio::test]
+    async fn missing_refresh_token_is_tolerated_proactively_but_fails_on_401() {
+        let mut tokens = stored(None, -60);
+        let did_refresh = refresh_if_expired_with(&mut tokens, now(), |_| async {
+            Ok::<_, YoutubeTokenError>(refreshed("unused"))
+        })
+        .await
+        .unwrap();
+        assert!(!did_refresh);
+        assert_eq!(tokens.access_token, "old_access");
+
+        let err = call_with_retry(
+            &mut tokens,
+            now(),
+            |status: &u16| *status == 401,
+            |_| async { Ok(refreshed("unused")) },
+            |_| async { Err::<(), u16>(401) },
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, YoutubeTokenError::NoRefreshToken));
+    }
+
+    #[tokio::test]
+    async fn invalid_grant_marks_connection_and_is_not_retryable() {
+        let marked = Cell::new(None::<String>);
+        let revoked: Result<YoutubeOAuthTokens, Error> = Err(Box::new(YoutubeRefreshRevoked {
+            description: Some("Token has been expired or revoked.".to_string()),
+        }));
+
+        let err = revoke_on_invalid_grant(revoked, |detail| {
+            marked.set(Some(detail));
+            async { Ok(()) }
+        })
+        .await
+        .unwrap_err();
+        assert!(matches!(err, YoutubeTokenError::Revoked));
+        assert!(marked.take().unwrap().contains("invalid_grant"));
+        assert!(is_reauth_required(&(Box::new(err) as Error)));
+
+        let transient: Result<YoutubeOAuthTokens, Error> =
+            Err(Box::new(std::io::Error::other("connection reset")));
+        let err = revoke_on_invalid_grant(transient, |_| {
+            marked.set(Some("unexpected".to_string()));
+            async { Ok(()) }
+        })
+        .await
+        .unwrap_err();
+        assert!(matches!(err, YoutubeTokenError::Other(_)));
+        assert!(marked.take().is_none());
+        assert!(!is_reauth_required(&(Box::new(err) as Error)));
+    }
+}